@@ -164,6 +164,11 @@ client_request_definitions! {
         response: v2::LogoutAccountResponse,
     },
 
+    SwitchAccount => "account/switch" {
+        params: v2::SwitchAccountParams,
+        response: v2::SwitchAccountResponse,
+    },
+
     GetAccountRateLimits => "account/rateLimits/read" {
         params: #[ts(type = "undefined")] #[serde(skip_serializing_if = "Option::is_none")] Option<()>,
         response: v2::GetAccountRateLimitsResponse,
@@ -765,6 +770,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn serialize_account_switch() -> Result<()> {
+        let request = ClientRequest::SwitchAccount {
+            request_id: RequestId::Integer(6),
+            params: v2::SwitchAccountParams {
+                name: Some("work".to_string()),
+            },
+        };
+        assert_eq!(
+            json!({
+                "method": "account/switch",
+                "id": 6,
+                "params": {
+                    "name": "work"
+                }
+            }),
+            serde_json::to_value(&request)?,
+        );
+        Ok(())
+    }
+
     #[test]
     fn serialize_get_account() -> Result<()> {
         let request = ClientRequest::GetAccount {
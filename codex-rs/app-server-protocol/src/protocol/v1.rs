@@ -116,6 +116,8 @@ pub struct ConversationSummary {
     pub conversation_id: ConversationId,
     pub path: PathBuf,
     pub preview: String,
+    pub title: Option<String>,
+    pub summary: Option<String>,
     pub timestamp: Option<String>,
     pub model_provider: String,
     pub cwd: PathBuf,
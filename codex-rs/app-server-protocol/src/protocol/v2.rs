@@ -18,6 +18,8 @@ use codex_protocol::plan_tool::StepStatus as CorePlanStepStatus;
 use codex_protocol::protocol::AskForApproval as CoreAskForApproval;
 use codex_protocol::protocol::CodexErrorInfo as CoreCodexErrorInfo;
 use codex_protocol::protocol::CreditsSnapshot as CoreCreditsSnapshot;
+use codex_protocol::protocol::HunkDecision as CoreHunkDecision;
+use codex_protocol::protocol::HunkReview as CoreHunkReview;
 use codex_protocol::protocol::NetworkAccess as CoreNetworkAccess;
 use codex_protocol::protocol::RateLimitSnapshot as CoreRateLimitSnapshot;
 use codex_protocol::protocol::RateLimitWindow as CoreRateLimitWindow;
@@ -200,6 +202,12 @@ v2_enum_from_core!(
     }
 );
 
+v2_enum_from_core!(
+    pub enum ExecPolicyAmendmentTarget from codex_protocol::approvals::ExecPolicyAmendmentTarget {
+        User, Project
+    }
+);
+
 v2_enum_from_core!(
     pub enum McpAuthStatus from codex_protocol::protocol::McpAuthStatus {
         Unsupported,
@@ -466,6 +474,12 @@ pub enum ApprovalDecision {
     AcceptForSession,
     AcceptWithExecpolicyAmendment {
         execpolicy_amendment: ExecPolicyAmendment,
+        /// Rules file to save the amendment to; defaults to the user's rules.
+        /// `project` is only honored when the request reported
+        /// `projectExecpolicyAvailable`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        execpolicy_target: Option<ExecPolicyAmendmentTarget>,
     },
     Decline,
     Cancel,
@@ -782,6 +796,21 @@ pub struct CancelLoginAccountResponse {
 #[ts(export_to = "v2/")]
 pub struct LogoutAccountResponse {}
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct SwitchAccountParams {
+    /// Stored account to switch to, as created with `codex login --name`.
+    /// Omit (or pass `"default"`) for the default account.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct SwitchAccountResponse {}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -827,6 +856,11 @@ pub struct Model {
     pub default_reasoning_effort: ReasoningEffort,
     // Only one model should be marked as default.
     pub is_default: bool,
+    /// Provider that serves this model, for models discovered from a
+    /// configured provider. `None` for the session's built-in model list.
+    /// Selecting a discovered model switches the thread to its provider.
+    #[serde(default)]
+    pub model_provider: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -1153,6 +1187,10 @@ pub struct Thread {
     pub id: String,
     /// Usually the first user message in the thread, if available.
     pub preview: String,
+    /// Generated title for the thread, if one has been recorded.
+    pub title: Option<String>,
+    /// Generated one-paragraph summary of the thread, if one has been recorded.
+    pub summary: Option<String>,
     /// Model provider used for this thread (for example, 'openai').
     pub model_provider: String,
     /// Unix timestamp (in seconds) when the thread was created.
@@ -1298,6 +1336,9 @@ pub struct TurnStartParams {
     pub sandbox_policy: Option<SandboxPolicy>,
     /// Override the model for this turn and subsequent turns.
     pub model: Option<String>,
+    /// Provider serving `model`, as reported by `model/list` for discovered
+    /// models. Switches the thread to that provider.
+    pub model_provider: Option<String>,
     /// Override the reasoning effort for this turn and subsequent turns.
     pub effort: Option<ReasoningEffort>,
     /// Override the reasoning summary for this turn and subsequent turns.
@@ -1823,6 +1864,10 @@ pub struct CommandExecutionRequestApprovalParams {
     pub reason: Option<String>,
     /// Optional proposed execpolicy amendment to allow similar commands without prompting.
     pub proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+    /// True when the amendment may also be saved to the project's rules
+    /// because the project is trusted.
+    #[serde(default)]
+    pub project_execpolicy_available: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -1847,9 +1892,57 @@ pub struct FileChangeRequestApprovalParams {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
 pub struct FileChangeRequestApprovalResponse {
     pub decision: ApprovalDecision,
+    /// Per-hunk review of the proposed changes. Only honored with `accept`;
+    /// hunks that are not listed are applied as proposed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub hunks: Option<Vec<FileChangeHunkReview>>,
+}
+
+/// Decision for one hunk of a `fileChange` item. `path` matches
+/// `FileUpdateChange.path` and `hunk` is the zero-based position of the hunk in
+/// that change's `diff`; changes without a textual diff are a single hunk `0`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileChangeHunkReview {
+    pub path: String,
+    pub hunk: usize,
+    pub decision: FileChangeHunkDecision,
+}
+
+impl FileChangeHunkReview {
+    pub fn into_core(self) -> CoreHunkReview {
+        CoreHunkReview {
+            path: PathBuf::from(self.path),
+            hunk: self.hunk,
+            decision: match self.decision {
+                FileChangeHunkDecision::Accept => CoreHunkDecision::Accept,
+                FileChangeHunkDecision::Reject => CoreHunkDecision::Reject,
+                FileChangeHunkDecision::Edit { replacement } => {
+                    CoreHunkDecision::Edit { replacement }
+                }
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(tag = "type")]
+#[ts(export_to = "v2/")]
+pub enum FileChangeHunkDecision {
+    Accept,
+    Reject,
+    /// Apply `replacement` in place of the lines the hunk covers, context
+    /// lines included.
+    Edit {
+        replacement: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -2097,4 +2190,36 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn execpolicy_amendment_decision_reads_optional_target() {
+        let decision: ApprovalDecision = serde_json::from_value(json!({
+            "acceptWithExecpolicyAmendment": {
+                "execpolicy_amendment": ["cargo", "test"],
+                "execpolicy_target": "project"
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            decision,
+            ApprovalDecision::AcceptWithExecpolicyAmendment {
+                execpolicy_amendment: ExecPolicyAmendment {
+                    command: vec!["cargo".to_string(), "test".to_string()],
+                },
+                execpolicy_target: Some(ExecPolicyAmendmentTarget::Project),
+            }
+        );
+
+        let decision: ApprovalDecision = serde_json::from_value(json!({
+            "acceptWithExecpolicyAmendment": { "execpolicy_amendment": ["ls"] }
+        }))
+        .unwrap();
+        assert!(matches!(
+            decision,
+            ApprovalDecision::AcceptWithExecpolicyAmendment {
+                execpolicy_target: None,
+                ..
+            }
+        ));
+    }
 }
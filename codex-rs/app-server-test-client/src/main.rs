@@ -757,6 +757,7 @@ impl CodexClient {
             item_id,
             reason,
             proposed_execpolicy_amendment,
+            project_execpolicy_available: _,
         } = params;
 
         println!(
@@ -802,6 +803,7 @@ impl CodexClient {
 
         let response = FileChangeRequestApprovalResponse {
             decision: ApprovalDecision::Accept,
+            hunks: None,
         };
         self.send_server_request_response(request_id, &response)?;
         println!("< approved fileChange request for item {item_id}");
@@ -22,6 +22,8 @@ use codex_app_server_protocol::ErrorNotification;
 use codex_app_server_protocol::ExecCommandApprovalParams;
 use codex_app_server_protocol::ExecCommandApprovalResponse;
 use codex_app_server_protocol::ExecPolicyAmendment as V2ExecPolicyAmendment;
+use codex_app_server_protocol::ExecPolicyAmendmentTarget as V2ExecPolicyAmendmentTarget;
+use codex_app_server_protocol::FileChangeHunkReview;
 use codex_app_server_protocol::FileChangeOutputDeltaNotification;
 use codex_app_server_protocol::FileChangeRequestApprovalParams;
 use codex_app_server_protocol::FileChangeRequestApprovalResponse;
@@ -342,6 +344,7 @@ pub(crate) async fn apply_bespoke_event_handling(
             cwd,
             reason,
             proposed_execpolicy_amendment,
+            project_execpolicy_available,
             parsed_cmd,
         }) => match api_version {
             ApiVersion::V1 => {
@@ -379,6 +382,7 @@ pub(crate) async fn apply_bespoke_event_handling(
                     item_id: item_id.clone(),
                     reason,
                     proposed_execpolicy_amendment: proposed_execpolicy_amendment_v2,
+                    project_execpolicy_available,
                 };
                 let rx = outgoing
                     .send_request(ServerRequestPayload::CommandExecutionRequestApproval(
@@ -1235,11 +1239,17 @@ fn convert_patch_changes(changes: &HashMap<PathBuf, CoreFileChange>) -> Vec<File
 
 fn map_patch_change_kind(change: &CoreFileChange) -> V2PatchChangeKind {
     match change {
-        CoreFileChange::Add { .. } => V2PatchChangeKind::Add,
+        CoreFileChange::Add { .. }
+        | CoreFileChange::Binary { old_size: None, .. }
+        | CoreFileChange::Symlink { .. } => V2PatchChangeKind::Add,
         CoreFileChange::Delete { .. } => V2PatchChangeKind::Delete,
         CoreFileChange::Update { move_path, .. } => V2PatchChangeKind::Update {
             move_path: move_path.clone(),
         },
+        CoreFileChange::Binary {
+            old_size: Some(_), ..
+        }
+        | CoreFileChange::Mode { .. } => V2PatchChangeKind::Update { move_path: None },
     }
 }
 
@@ -1257,6 +1267,9 @@ fn format_file_change_diff(change: &CoreFileChange) -> String {
                 unified_diff.clone()
             }
         }
+        CoreFileChange::Binary { .. }
+        | CoreFileChange::Symlink { .. }
+        | CoreFileChange::Mode { .. } => change.non_text_summary().unwrap_or_default(),
     }
 }
 
@@ -1279,15 +1292,25 @@ async fn on_file_change_request_approval_response(
                     error!("failed to deserialize FileChangeRequestApprovalResponse: {err}");
                     FileChangeRequestApprovalResponse {
                         decision: ApprovalDecision::Decline,
+                        hunks: None,
                     }
                 });
 
             let (decision, completion_status) = match response.decision {
                 ApprovalDecision::Accept
                 | ApprovalDecision::AcceptForSession
-                | ApprovalDecision::AcceptWithExecpolicyAmendment { .. } => {
-                    (ReviewDecision::Approved, None)
-                }
+                | ApprovalDecision::AcceptWithExecpolicyAmendment { .. } => match response.hunks {
+                    Some(hunks) if !hunks.is_empty() => (
+                        ReviewDecision::ApprovedWithHunkReview {
+                            hunks: hunks
+                                .into_iter()
+                                .map(FileChangeHunkReview::into_core)
+                                .collect(),
+                        },
+                        None,
+                    ),
+                    _ => (ReviewDecision::Approved, None),
+                },
                 ApprovalDecision::Decline => {
                     (ReviewDecision::Denied, Some(PatchApplyStatus::Declined))
                 }
@@ -1359,9 +1382,15 @@ async fn on_command_execution_request_approval_response(
                 ApprovalDecision::AcceptForSession => (ReviewDecision::ApprovedForSession, None),
                 ApprovalDecision::AcceptWithExecpolicyAmendment {
                     execpolicy_amendment,
+                    execpolicy_target,
                 } => (
                     ReviewDecision::ApprovedExecpolicyAmendment {
                         proposed_execpolicy_amendment: execpolicy_amendment.into_core(),
+                        // Core falls back to the user's rules when project
+                        // rules are not active.
+                        target: execpolicy_target
+                            .unwrap_or(V2ExecPolicyAmendmentTarget::User)
+                            .to_core(),
                     },
                     None,
                 ),
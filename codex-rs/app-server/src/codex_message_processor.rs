@@ -1,6 +1,7 @@
 use crate::bespoke_event_handling::apply_bespoke_event_handling;
 use crate::error_code::INTERNAL_ERROR_CODE;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
+use crate::fuzzy_file_search::FileIndexCache;
 use crate::fuzzy_file_search::run_fuzzy_file_search;
 use crate::models::supported_models;
 use crate::outgoing_message::OutgoingMessageSender;
@@ -83,6 +84,8 @@ use codex_app_server_protocol::SetDefaultModelParams;
 use codex_app_server_protocol::SetDefaultModelResponse;
 use codex_app_server_protocol::SkillsListParams;
 use codex_app_server_protocol::SkillsListResponse;
+use codex_app_server_protocol::SwitchAccountParams;
+use codex_app_server_protocol::SwitchAccountResponse;
 use codex_app_server_protocol::Thread;
 use codex_app_server_protocol::ThreadArchiveParams;
 use codex_app_server_protocol::ThreadArchiveResponse;
@@ -116,11 +119,13 @@ use codex_core::NewConversation;
 use codex_core::RolloutRecorder;
 use codex_core::SessionMeta;
 use codex_core::auth::CLIENT_ID;
-use codex_core::auth::login_with_api_key;
+use codex_core::auth::login_account_with_api_key;
+use codex_core::auth::parse_account_name;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::config::ConfigService;
 use codex_core::config::edit::ConfigEditsBuilder;
+use codex_core::config::types::AuxiliaryTask;
 use codex_core::config::types::McpServerTransportConfig;
 use codex_core::default_client::get_codex_user_agent;
 use codex_core::exec::ExecParams;
@@ -226,6 +231,8 @@ pub(crate) struct CodexMessageProcessor {
     pending_interrupts: PendingInterrupts,
     turn_summary_store: TurnSummaryStore,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+    // Watched file indexes for recent fuzzy search roots, kept alive across requests.
+    file_indexes: FileIndexCache,
     feedback: CodexFeedback,
 }
 
@@ -281,6 +288,7 @@ impl CodexMessageProcessor {
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
             turn_summary_store: Arc::new(Mutex::new(HashMap::new())),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
+            file_indexes: FileIndexCache::default(),
             feedback,
         }
     }
@@ -421,6 +429,9 @@ impl CodexMessageProcessor {
             } => {
                 self.logout_v2(request_id).await;
             }
+            ClientRequest::SwitchAccount { request_id, params } => {
+                self.switch_account(request_id, params).await;
+            }
             ClientRequest::CancelLoginAccount { request_id, params } => {
                 self.cancel_login_v2(request_id, params).await;
             }
@@ -554,8 +565,10 @@ impl CodexMessageProcessor {
             }
         }
 
-        match login_with_api_key(
+        let account = self.auth_manager.account();
+        match login_account_with_api_key(
             &self.config.codex_home,
+            account.as_deref(),
             &params.api_key,
             self.config.cli_auth_credentials_store_mode,
         ) {
@@ -637,6 +650,7 @@ impl CodexMessageProcessor {
 
         Ok(LoginServerOptions {
             open_browser: false,
+            account: self.auth_manager.account(),
             ..LoginServerOptions::new(
                 config.codex_home.clone(),
                 CLIENT_ID.to_string(),
@@ -946,6 +960,55 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn switch_account(&mut self, request_id: RequestId, params: SwitchAccountParams) {
+        let account = match params.name.as_deref().map(parse_account_name).transpose() {
+            Ok(account) => account.flatten(),
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: err.to_string(),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        // Cancel any active login attempt; it would save into the old account.
+        {
+            let mut guard = self.active_login.lock().await;
+            if let Some(active) = guard.take() {
+                drop(active);
+            }
+        }
+
+        if let Err(err) = self.auth_manager.switch_account(account.as_deref()) {
+            let code = if err.kind() == std::io::ErrorKind::NotFound {
+                INVALID_REQUEST_ERROR_CODE
+            } else {
+                INTERNAL_ERROR_CODE
+            };
+            let error = JSONRPCErrorError {
+                code,
+                message: format!("failed to switch account: {err}"),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        }
+
+        self.outgoing
+            .send_response(request_id, SwitchAccountResponse {})
+            .await;
+
+        let payload_v2 = AccountUpdatedNotification {
+            auth_mode: self.auth_manager.get_auth_mode(),
+        };
+        self.outgoing
+            .send_server_notification(ServerNotification::AccountUpdated(payload_v2))
+            .await;
+    }
+
     async fn refresh_token_if_requested(&self, do_refresh: bool) {
         if do_refresh && let Err(err) = self.auth_manager.refresh_token().await {
             tracing::warn!("failed to refresh token whilte getting account: {err}");
@@ -2790,6 +2853,7 @@ impl CodexMessageProcessor {
             || params.approval_policy.is_some()
             || params.sandbox_policy.is_some()
             || params.model.is_some()
+            || params.model_provider.is_some()
             || params.effort.is_some()
             || params.summary.is_some();
 
@@ -2801,6 +2865,7 @@ impl CodexMessageProcessor {
                     approval_policy: params.approval_policy.map(AskForApproval::to_core),
                     sandbox_policy: params.sandbox_policy.map(|p| p.to_core()),
                     model: params.model,
+                    model_provider: params.model_provider,
                     effort: params.effort.map(Some),
                     summary: params.summary,
                 })
@@ -2946,7 +3011,9 @@ impl CodexMessageProcessor {
         })?;
 
         let mut config = self.config.as_ref().clone();
-        config.model = Some(self.config.review_model.clone());
+        if !config.apply_task_model(&AuxiliaryTask::Review) {
+            config.model = Some(self.config.review_model.clone());
+        }
 
         let NewConversation {
             conversation_id,
@@ -3277,7 +3344,16 @@ impl CodexMessageProcessor {
 
         let results = match query.as_str() {
             "" => vec![],
-            _ => run_fuzzy_file_search(query, roots, cancel_flag.clone()).await,
+            _ => {
+                let indexes = roots
+                    .into_iter()
+                    .map(|root| {
+                        let index = self.file_indexes.get_or_create(&root);
+                        (root, index)
+                    })
+                    .collect();
+                run_fuzzy_file_search(query, indexes, cancel_flag.clone()).await
+            }
         };
 
         if let Some(token) = cancellation_token {
@@ -3474,6 +3550,8 @@ async fn read_summary_from_rollout(
         timestamp,
         path: path.to_path_buf(),
         preview: String::new(),
+        title: session_meta.title,
+        summary: session_meta.summary,
         model_provider,
         cwd: session_meta.cwd,
         cli_version: session_meta.cli_version,
@@ -3543,6 +3621,8 @@ fn extract_conversation_summary(
         timestamp,
         path,
         preview: preview.to_string(),
+        title: session_meta.title.clone(),
+        summary: session_meta.summary.clone(),
         model_provider,
         cwd: session_meta.cwd.clone(),
         cli_version: session_meta.cli_version.clone(),
@@ -3572,6 +3652,8 @@ fn summary_to_thread(summary: ConversationSummary) -> Thread {
         conversation_id,
         path,
         preview,
+        title,
+        summary,
         timestamp,
         model_provider,
         cwd,
@@ -3590,6 +3672,8 @@ fn summary_to_thread(summary: ConversationSummary) -> Thread {
     Thread {
         id: conversation_id.to_string(),
         preview,
+        title,
+        summary,
         model_provider,
         created_at: created_at.map(|dt| dt.timestamp()).unwrap_or(0),
         path,
@@ -3655,6 +3739,8 @@ mod tests {
             timestamp,
             path,
             preview: "Count to 5".to_string(),
+            title: None,
+            summary: None,
             model_provider: "test-provider".to_string(),
             cwd: PathBuf::from("/"),
             cli_version: "0.0.0".to_string(),
@@ -3703,6 +3789,8 @@ mod tests {
             timestamp: Some(timestamp),
             path: path.clone(),
             preview: String::new(),
+            title: None,
+            summary: None,
             model_provider: "fallback".to_string(),
             cwd: PathBuf::new(),
             cli_version: String::new(),
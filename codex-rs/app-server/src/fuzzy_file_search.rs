@@ -1,25 +1,56 @@
+use std::collections::VecDeque;
 use std::num::NonZero;
-use std::num::NonZeroUsize;
 use std::path::Path;
-use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use codex_app_server_protocol::FuzzyFileSearchResult;
 use codex_file_search as file_search;
+use codex_file_search::FileIndex;
 use tokio::task::JoinSet;
 use tracing::warn;
 
 const LIMIT_PER_ROOT: usize = 50;
-const MAX_THREADS: usize = 12;
 const COMPUTE_INDICES: bool = true;
+/// Search roots whose indexes are kept warm. Each index holds a file watcher,
+/// so the least recently searched root is dropped beyond this.
+const MAX_WARM_ROOTS: usize = 8;
 
+/// Watched file indexes for the most recently searched roots.
+#[derive(Default)]
+pub(crate) struct FileIndexCache {
+    /// Most recently used first.
+    entries: VecDeque<(String, Arc<FileIndex>)>,
+}
+
+impl FileIndexCache {
+    /// Returns the index for `root`, creating it if needed and evicting the
+    /// least recently used root when the cache is full.
+    pub(crate) fn get_or_create(&mut self, root: &str) -> Arc<FileIndex> {
+        let index = match self
+            .entries
+            .iter()
+            .position(|(existing, _)| existing == root)
+        {
+            Some(position) => self.entries.remove(position).map(|(_, index)| index),
+            None => None,
+        }
+        .unwrap_or_else(|| FileIndex::for_root(Path::new(root)));
+        self.entries
+            .push_front((root.to_string(), Arc::clone(&index)));
+        self.entries.truncate(MAX_WARM_ROOTS);
+        index
+    }
+}
+
+/// Searches each `(root, index)` pair and merges the results. Indexes are
+/// owned by the caller so they stay warm between requests.
 pub(crate) async fn run_fuzzy_file_search(
     query: String,
-    roots: Vec<String>,
+    indexes: Vec<(String, Arc<FileIndex>)>,
     cancellation_flag: Arc<AtomicBool>,
 ) -> Vec<FuzzyFileSearchResult> {
-    if roots.is_empty() {
+    if indexes.is_empty() {
         return Vec::new();
     }
 
@@ -27,31 +58,14 @@ pub(crate) async fn run_fuzzy_file_search(
     let limit_per_root =
         NonZero::new(LIMIT_PER_ROOT).expect("LIMIT_PER_ROOT should be a valid non-zero usize");
 
-    let cores = std::thread::available_parallelism()
-        .map(std::num::NonZero::get)
-        .unwrap_or(1);
-    let threads = cores.min(MAX_THREADS);
-    let threads_per_root = (threads / roots.len()).max(1);
-    let threads = NonZero::new(threads_per_root).unwrap_or(NonZeroUsize::MIN);
-
     let mut files: Vec<FuzzyFileSearchResult> = Vec::new();
     let mut join_set = JoinSet::new();
 
-    for root in roots {
-        let search_dir = PathBuf::from(&root);
+    for (root, index) in indexes {
         let query = query.clone();
         let cancel_flag = cancellation_flag.clone();
         join_set.spawn_blocking(move || {
-            match file_search::run(
-                query.as_str(),
-                limit_per_root,
-                &search_dir,
-                Vec::new(),
-                threads,
-                cancel_flag,
-                COMPUTE_INDICES,
-                true,
-            ) {
+            match index.search(query.as_str(), limit_per_root, cancel_flag, COMPUTE_INDICES) {
                 Ok(res) => Ok((root, res)),
                 Err(err) => Err((root, err)),
             }
@@ -95,3 +109,39 @@ pub(crate) async fn run_fuzzy_file_search(
 
     files
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn file_index_cache_evicts_least_recently_used_root() {
+        let dirs: Vec<TempDir> = (0..=MAX_WARM_ROOTS)
+            .map(|_| TempDir::new().expect("tempdir"))
+            .collect();
+        let roots: Vec<String> = dirs
+            .iter()
+            .map(|dir| dir.path().display().to_string())
+            .collect();
+
+        let mut cache = FileIndexCache::default();
+        let first = cache.get_or_create(&roots[0]);
+        for root in &roots[1..MAX_WARM_ROOTS] {
+            cache.get_or_create(root);
+        }
+        // Touching the first root again makes the second one the oldest.
+        assert!(Arc::ptr_eq(&first, &cache.get_or_create(&roots[0])));
+        cache.get_or_create(&roots[MAX_WARM_ROOTS]);
+
+        let cached: Vec<&str> = cache
+            .entries
+            .iter()
+            .map(|(root, _)| root.as_str())
+            .collect();
+        assert_eq!(cached.len(), MAX_WARM_ROOTS);
+        assert!(cached.contains(&roots[0].as_str()));
+        assert!(!cached.contains(&roots[1].as_str()));
+    }
+}
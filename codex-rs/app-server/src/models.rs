@@ -11,15 +11,25 @@ pub async fn supported_models(
     conversation_manager: Arc<ConversationManager>,
     config: &Config,
 ) -> Vec<Model> {
-    conversation_manager
+    let mut models: Vec<Model> = conversation_manager
         .list_models(config)
         .await
         .into_iter()
-        .map(model_from_preset)
-        .collect()
+        .map(|preset| model_from_preset(preset, None))
+        .collect();
+    // Discovered models follow the built-in list, grouped by provider.
+    for group in conversation_manager.list_provider_models(config).await {
+        models.extend(
+            group
+                .models
+                .into_iter()
+                .map(|preset| model_from_preset(preset, Some(group.provider_id.clone()))),
+        );
+    }
+    models
 }
 
-fn model_from_preset(preset: ModelPreset) -> Model {
+fn model_from_preset(preset: ModelPreset, model_provider: Option<String>) -> Model {
     Model {
         id: preset.id.to_string(),
         model: preset.model.to_string(),
@@ -30,6 +40,7 @@ fn model_from_preset(preset: ModelPreset) -> Model {
         ),
         default_reasoning_effort: preset.default_reasoning_effort,
         is_default: preset.is_default,
+        model_provider,
     }
 }
 
@@ -78,6 +78,7 @@ async fn list_models_returns_all_models_with_large_limit() -> Result<()> {
             ],
             default_reasoning_effort: ReasoningEffort::Medium,
             is_default: true,
+            model_provider: None,
         },
         Model {
             id: "gpt-5.1-codex-mini".to_string(),
@@ -97,6 +98,7 @@ async fn list_models_returns_all_models_with_large_limit() -> Result<()> {
             ],
             default_reasoning_effort: ReasoningEffort::Medium,
             is_default: false,
+            model_provider: None,
         },
         Model {
             id: "gpt-5.1-codex-max".to_string(),
@@ -124,6 +126,7 @@ async fn list_models_returns_all_models_with_large_limit() -> Result<()> {
             ],
             default_reasoning_effort: ReasoningEffort::Medium,
             is_default: false,
+            model_provider: None,
         },
         Model {
             id: "gpt-5.2-codex".to_string(),
@@ -151,6 +154,7 @@ async fn list_models_returns_all_models_with_large_limit() -> Result<()> {
             ],
             default_reasoning_effort: ReasoningEffort::Medium,
             is_default: false,
+            model_provider: None,
         },
         Model {
             id: "gemini-3-flash-preview-gemini".to_string(),
@@ -178,6 +182,7 @@ async fn list_models_returns_all_models_with_large_limit() -> Result<()> {
             ],
             default_reasoning_effort: ReasoningEffort::High,
             is_default: false,
+            model_provider: None,
         },
         Model {
             id: "gemini-3-pro-preview-codex".to_string(),
@@ -201,6 +206,7 @@ async fn list_models_returns_all_models_with_large_limit() -> Result<()> {
             ],
             default_reasoning_effort: ReasoningEffort::High,
             is_default: false,
+            model_provider: None,
         },
         Model {
             id: "gemini-3-pro-image-preview".to_string(),
@@ -215,6 +221,7 @@ async fn list_models_returns_all_models_with_large_limit() -> Result<()> {
             }],
             default_reasoning_effort: ReasoningEffort::Medium,
             is_default: false,
+            model_provider: None,
         },
     ];
 
@@ -538,6 +538,7 @@ async fn turn_start_updates_sandbox_and_cwd_between_turns_v2() -> Result<()> {
                 exclude_slash_tmp: false,
             }),
             model: Some("mock-model".to_string()),
+            model_provider: None,
             effort: Some(ReasoningEffort::Medium),
             summary: Some(ReasoningSummary::Auto),
         })
@@ -564,6 +565,7 @@ async fn turn_start_updates_sandbox_and_cwd_between_turns_v2() -> Result<()> {
             approval_policy: Some(codex_app_server_protocol::AskForApproval::Never),
             sandbox_policy: Some(codex_app_server_protocol::SandboxPolicy::DangerFullAccess),
             model: Some("mock-model".to_string()),
+            model_provider: None,
             effort: Some(ReasoningEffort::Medium),
             summary: Some(ReasoningSummary::Auto),
         })
@@ -721,6 +723,7 @@ async fn turn_start_file_change_approval_v2() -> Result<()> {
         request_id,
         serde_json::to_value(FileChangeRequestApprovalResponse {
             decision: ApprovalDecision::Accept,
+            hunks: None,
         })?,
     )
     .await?;
@@ -887,6 +890,7 @@ async fn turn_start_file_change_approval_decline_v2() -> Result<()> {
         request_id,
         serde_json::to_value(FileChangeRequestApprovalResponse {
             decision: ApprovalDecision::Decline,
+            hunks: None,
         })?,
     )
     .await?;
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::LazyLock;
 
 use tree_sitter::Parser;
@@ -15,6 +16,7 @@ use crate::ApplyPatchFileChange;
 use crate::ApplyPatchFileUpdate;
 use crate::IoError;
 use crate::MaybeApplyPatchVerified;
+use crate::file_permissions;
 use crate::parser::Hunk;
 use crate::parser::ParseError;
 use crate::parser::parse_patch;
@@ -165,7 +167,14 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                 let path = hunk.resolve_path(&effective_cwd);
                 match hunk {
                     Hunk::AddFile { contents, .. } => {
-                        changes.insert(path, ApplyPatchFileChange::Add { content: contents });
+                        let mode = pending_mode(&changes, &path);
+                        changes.insert(
+                            path,
+                            ApplyPatchFileChange::Add {
+                                content: contents,
+                                mode,
+                            },
+                        );
                     }
                     Hunk::DeleteFile { .. } => {
                         let content = match std::fs::read_to_string(&path) {
@@ -193,15 +202,67 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                                 return MaybeApplyPatchVerified::CorrectnessError(e);
                             }
                         };
+                        let mode = pending_mode(&changes, &path);
                         changes.insert(
                             path,
                             ApplyPatchFileChange::Update {
                                 unified_diff,
                                 move_path: move_path.map(|p| effective_cwd.join(p)),
                                 new_content: contents,
+                                mode,
+                            },
+                        );
+                    }
+                    Hunk::AddBinaryFile { contents, .. } => {
+                        changes.insert(
+                            path,
+                            ApplyPatchFileChange::Binary {
+                                old_size: None,
+                                new_content: contents,
+                            },
+                        );
+                    }
+                    Hunk::UpdateBinaryFile { contents, .. } => {
+                        let old_size = match std::fs::metadata(&path) {
+                            Ok(metadata) => metadata.len(),
+                            Err(e) => {
+                                return MaybeApplyPatchVerified::CorrectnessError(
+                                    ApplyPatchError::IoError(IoError {
+                                        context: format!("Failed to read {}", path.display()),
+                                        source: e,
+                                    }),
+                                );
+                            }
+                        };
+                        changes.insert(
+                            path,
+                            ApplyPatchFileChange::Binary {
+                                old_size: Some(old_size),
+                                new_content: contents,
                             },
                         );
                     }
+                    Hunk::AddSymlink { target, .. } => {
+                        changes.insert(path, ApplyPatchFileChange::Symlink { target });
+                    }
+                    Hunk::ChangeMode { mode, .. } => match changes.get_mut(&path) {
+                        // A file added or updated earlier in the same patch
+                        // keeps its content change and carries the mode.
+                        Some(
+                            ApplyPatchFileChange::Add { mode: carried, .. }
+                            | ApplyPatchFileChange::Update { mode: carried, .. },
+                        ) => *carried = Some(mode),
+                        _ => {
+                            let old_mode = file_permissions(&path);
+                            changes.insert(
+                                path,
+                                ApplyPatchFileChange::Mode {
+                                    old_mode,
+                                    new_mode: mode,
+                                },
+                            );
+                        }
+                    },
                 }
             }
             MaybeApplyPatchVerified::Body(ApplyPatchAction {
@@ -216,6 +277,14 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
     }
 }
 
+/// Mode set by a `*** Chmod File:` hunk for `path` earlier in the patch.
+fn pending_mode(changes: &HashMap<PathBuf, ApplyPatchFileChange>, path: &Path) -> Option<u32> {
+    match changes.get(path) {
+        Some(ApplyPatchFileChange::Mode { new_mode, .. }) => Some(*new_mode),
+        _ => None,
+    }
+}
+
 /// Extract the heredoc body (and optional `cd` workdir) from a `bash -lc` script
 /// that invokes the apply_patch tool using a heredoc.
 ///
@@ -756,6 +825,98 @@ PATCH"#,
                         .to_string(),
                         move_path: None,
                         new_content: "updated session directory content\n".to_string(),
+                        mode: None,
+                    },
+                )]),
+                patch: argv[1].clone(),
+                cwd: session_dir.path().to_path_buf(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_binary_symlink_and_mode_changes_are_verified() {
+        let session_dir = tempdir().unwrap();
+        fs::write(session_dir.path().join("data.bin"), [0u8, 1, 2]).unwrap();
+
+        let argv = vec![
+            "apply_patch".to_string(),
+            r#"*** Begin Patch
+*** Update Binary File: data.bin
++/w==
+*** Add Symlink: latest
+*** Link to: data.bin
+*** Add File: run.sh
++echo hi
+*** Chmod File: run.sh
+*** Mode: 755
+*** End Patch"#
+                .to_string(),
+        ];
+
+        let result = maybe_parse_apply_patch_verified(&argv, session_dir.path());
+
+        assert_eq!(
+            result,
+            MaybeApplyPatchVerified::Body(ApplyPatchAction {
+                changes: HashMap::from([
+                    (
+                        session_dir.path().join("data.bin"),
+                        ApplyPatchFileChange::Binary {
+                            old_size: Some(3),
+                            new_content: vec![0xff],
+                        },
+                    ),
+                    (
+                        session_dir.path().join("latest"),
+                        ApplyPatchFileChange::Symlink {
+                            target: PathBuf::from("data.bin"),
+                        },
+                    ),
+                    (
+                        session_dir.path().join("run.sh"),
+                        ApplyPatchFileChange::Add {
+                            content: "echo hi\n".to_string(),
+                            mode: Some(0o755),
+                        },
+                    ),
+                ]),
+                patch: argv[1].clone(),
+                cwd: session_dir.path().to_path_buf(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_mode_change_is_carried_on_update_of_same_file() {
+        let session_dir = tempdir().unwrap();
+        fs::write(session_dir.path().join("run.sh"), "echo hi\n").unwrap();
+
+        let argv = vec![
+            "apply_patch".to_string(),
+            r#"*** Begin Patch
+*** Update File: run.sh
+@@
+-echo hi
++echo hello
+*** Chmod File: run.sh
+*** Mode: 755
+*** End Patch"#
+                .to_string(),
+        ];
+
+        let result = maybe_parse_apply_patch_verified(&argv, session_dir.path());
+
+        assert_eq!(
+            result,
+            MaybeApplyPatchVerified::Body(ApplyPatchAction {
+                changes: HashMap::from([(
+                    session_dir.path().join("run.sh"),
+                    ApplyPatchFileChange::Update {
+                        unified_diff: "@@ -1 +1 @@\n-echo hi\n+echo hello\n".to_string(),
+                        move_path: None,
+                        new_content: "echo hello\n".to_string(),
+                        mode: Some(0o755),
                     },
                 )]),
                 patch: argv[1].clone(),
@@ -86,6 +86,8 @@ pub struct ApplyPatchArgs {
 pub enum ApplyPatchFileChange {
     Add {
         content: String,
+        /// Permission bits set by a `*** Chmod File:` hunk for the same path.
+        mode: Option<u32>,
     },
     Delete {
         content: String,
@@ -95,6 +97,23 @@ pub enum ApplyPatchFileChange {
         move_path: Option<PathBuf>,
         /// new_content that will result after the unified_diff is applied.
         new_content: String,
+        /// Permission bits set by a `*** Chmod File:` hunk for the same path.
+        mode: Option<u32>,
+    },
+    /// Binary contents written in full. `old_size` is `None` when the file is
+    /// being created.
+    Binary {
+        old_size: Option<u64>,
+        new_content: Vec<u8>,
+    },
+    Symlink {
+        target: PathBuf,
+    },
+    /// Permission bits change. `old_mode` is `None` when the file does not
+    /// exist yet or the platform has no permission bits.
+    Mode {
+        old_mode: Option<u32>,
+        new_mode: u32,
     },
 }
 
@@ -157,7 +176,13 @@ impl ApplyPatchAction {
 + {content}
 *** End Patch"#,
         );
-        let changes = HashMap::from([(path.to_path_buf(), ApplyPatchFileChange::Add { content })]);
+        let changes = HashMap::from([(
+            path.to_path_buf(),
+            ApplyPatchFileChange::Add {
+                content,
+                mode: None,
+            },
+        )]);
         #[expect(clippy::expect_used)]
         Self {
             changes,
@@ -212,11 +237,13 @@ pub fn apply_hunks(
     let _existing_paths: Vec<&Path> = hunks
         .iter()
         .filter_map(|hunk| match hunk {
-            Hunk::AddFile { .. } => {
+            Hunk::AddFile { .. } | Hunk::AddBinaryFile { .. } | Hunk::AddSymlink { .. } => {
                 // The file is being added, so it doesn't exist yet.
                 None
             }
-            Hunk::DeleteFile { path } => Some(path.as_path()),
+            Hunk::DeleteFile { path }
+            | Hunk::UpdateBinaryFile { path, .. }
+            | Hunk::ChangeMode { path, .. } => Some(path.as_path()),
             Hunk::UpdateFile {
                 path, move_path, ..
             } => match move_path {
@@ -320,6 +347,38 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
                     modified.push(path.clone());
                 }
             }
+            Hunk::AddBinaryFile { path, contents } => {
+                create_parent_dirs(path)?;
+                std::fs::write(path, contents)
+                    .with_context(|| format!("Failed to write file {}", path.display()))?;
+                added.push(path.clone());
+            }
+            Hunk::UpdateBinaryFile { path, contents } => {
+                if !path.is_file() {
+                    anyhow::bail!("Failed to update binary file {}: not found", path.display());
+                }
+                std::fs::write(path, contents)
+                    .with_context(|| format!("Failed to write file {}", path.display()))?;
+                modified.push(path.clone());
+            }
+            Hunk::AddSymlink { path, target } => {
+                create_parent_dirs(path)?;
+                create_symlink(target, path).with_context(|| {
+                    format!(
+                        "Failed to create symlink {} -> {}",
+                        path.display(),
+                        target.display()
+                    )
+                })?;
+                added.push(path.clone());
+            }
+            Hunk::ChangeMode { path, mode } => {
+                set_mode(path, *mode)
+                    .with_context(|| format!("Failed to change mode of {}", path.display()))?;
+                if !added.contains(path) && !modified.contains(path) {
+                    modified.push(path.clone());
+                }
+            }
         }
     }
     Ok(AffectedPaths {
@@ -329,6 +388,69 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
     })
 }
 
+fn create_parent_dirs(path: &Path) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent).with_context(|| {
+            format!("Failed to create parent directories for {}", path.display())
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    let resolved = link.parent().unwrap_or(Path::new("")).join(target);
+    if resolved.is_dir() {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn create_symlink(_target: &Path, _link: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "symlinks are not supported on this platform",
+    ))
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+}
+
+/// Permission bits are not tracked outside unix, mirroring git's behavior;
+/// the file still has to exist.
+#[cfg(not(unix))]
+fn set_mode(path: &Path, _mode: u32) -> std::io::Result<()> {
+    std::fs::metadata(path).map(|_| ())
+}
+
+/// Current permission bits of `path`, if it exists and the platform has them.
+fn file_permissions(path: &Path) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path)
+            .ok()
+            .map(|metadata| metadata.permissions().mode() & 0o7777)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        None
+    }
+}
+
 struct AppliedPatch {
     original_contents: String,
     new_contents: String,
@@ -1043,6 +1165,38 @@ g
         );
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_and_chmod_hunks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let script = dir.path().join("run.sh");
+        let link = dir.path().join("bin/run");
+        let patch = wrap_patch(&format!(
+            "*** Add File: {}\n+echo hi\n*** Chmod File: {}\n*** Mode: 755\n*** Add Symlink: {}\n*** Link to: ../run.sh",
+            script.display(),
+            script.display(),
+            link.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            format!(
+                "Success. Updated the following files:\nA {}\nA {}\n",
+                script.display(),
+                link.display()
+            )
+        );
+        let mode = fs::metadata(&script).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o755);
+        assert_eq!(fs::read_link(&link).unwrap(), PathBuf::from("../run.sh"));
+        assert_eq!(fs::read_to_string(&link).unwrap(), "echo hi\n");
+    }
+
     #[test]
     fn test_apply_patch_fails_on_write_error() {
         let dir = tempdir().unwrap();
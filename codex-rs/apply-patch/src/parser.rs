@@ -7,12 +7,19 @@
 //! begin_patch: "*** Begin Patch" LF
 //! end_patch: "*** End Patch" LF?
 //!
-//! hunk: add_hunk | delete_hunk | update_hunk
+//! hunk: add_hunk | delete_hunk | update_hunk | add_binary_hunk | update_binary_hunk
+//!     | add_symlink_hunk | chmod_hunk
 //! add_hunk: "*** Add File: " filename LF add_line+
 //! delete_hunk: "*** Delete File: " filename LF
 //! update_hunk: "*** Update File: " filename LF change_move? change?
+//! add_binary_hunk: "*** Add Binary File: " filename LF base64_line*
+//! update_binary_hunk: "*** Update Binary File: " filename LF base64_line*
+//! add_symlink_hunk: "*** Add Symlink: " filename LF "*** Link to: " filename LF
+//! chmod_hunk: "*** Chmod File: " filename LF "*** Mode: " mode LF
 //! filename: /(.+)/
 //! add_line: "+" /(.+)/ LF -> line
+//! base64_line: "+" /([A-Za-z0-9+\/=]+)/ LF
+//! mode: /(0?[0-7]{3}|100644|100755)/
 //!
 //! change_move: "*** Move to: " filename LF
 //! change: (change_context | change_line)+ eof_line?
@@ -26,6 +33,8 @@ use crate::ApplyPatchArgs;
 use std::path::Path;
 use std::path::PathBuf;
 
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use thiserror::Error;
 
 const BEGIN_PATCH_MARKER: &str = "*** Begin Patch";
@@ -34,6 +43,12 @@ const ADD_FILE_MARKER: &str = "*** Add File: ";
 const DELETE_FILE_MARKER: &str = "*** Delete File: ";
 const UPDATE_FILE_MARKER: &str = "*** Update File: ";
 const MOVE_TO_MARKER: &str = "*** Move to: ";
+const ADD_BINARY_FILE_MARKER: &str = "*** Add Binary File: ";
+const UPDATE_BINARY_FILE_MARKER: &str = "*** Update Binary File: ";
+const ADD_SYMLINK_MARKER: &str = "*** Add Symlink: ";
+const LINK_TO_MARKER: &str = "*** Link to: ";
+const CHMOD_FILE_MARKER: &str = "*** Chmod File: ";
+const MODE_MARKER: &str = "*** Mode: ";
 const EOF_MARKER: &str = "*** End of File";
 const CHANGE_CONTEXT_MARKER: &str = "@@ ";
 const EMPTY_CHANGE_CONTEXT_MARKER: &str = "@@";
@@ -73,14 +88,39 @@ pub enum Hunk {
         /// should occur later in the file than the previous chunk.
         chunks: Vec<UpdateFileChunk>,
     },
+    /// Creates (or overwrites) a file with base64-decoded contents.
+    AddBinaryFile {
+        path: PathBuf,
+        contents: Vec<u8>,
+    },
+    /// Replaces the full contents of an existing file with base64-decoded bytes.
+    UpdateBinaryFile {
+        path: PathBuf,
+        contents: Vec<u8>,
+    },
+    /// Creates a symbolic link at `path`. `target` is stored verbatim, so a
+    /// relative target is resolved against the link's directory.
+    AddSymlink {
+        path: PathBuf,
+        target: PathBuf,
+    },
+    /// Sets the permission bits of an existing file, e.g. `0o755`.
+    ChangeMode {
+        path: PathBuf,
+        mode: u32,
+    },
 }
 
 impl Hunk {
     pub fn resolve_path(&self, cwd: &Path) -> PathBuf {
         match self {
-            Hunk::AddFile { path, .. } => cwd.join(path),
-            Hunk::DeleteFile { path } => cwd.join(path),
-            Hunk::UpdateFile { path, .. } => cwd.join(path),
+            Hunk::AddFile { path, .. }
+            | Hunk::DeleteFile { path }
+            | Hunk::UpdateFile { path, .. }
+            | Hunk::AddBinaryFile { path, .. }
+            | Hunk::UpdateBinaryFile { path, .. }
+            | Hunk::AddSymlink { path, .. }
+            | Hunk::ChangeMode { path, .. } => cwd.join(path),
         }
     }
 }
@@ -327,16 +367,121 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             },
             parsed_lines,
         ));
+    } else if let Some(path) = first_line.strip_prefix(ADD_BINARY_FILE_MARKER) {
+        let (contents, parsed_lines) = parse_base64_lines(&lines[1..], path, line_number)?;
+        return Ok((
+            AddBinaryFile {
+                path: PathBuf::from(path),
+                contents,
+            },
+            parsed_lines + 1,
+        ));
+    } else if let Some(path) = first_line.strip_prefix(UPDATE_BINARY_FILE_MARKER) {
+        let (contents, parsed_lines) = parse_base64_lines(&lines[1..], path, line_number)?;
+        return Ok((
+            UpdateBinaryFile {
+                path: PathBuf::from(path),
+                contents,
+            },
+            parsed_lines + 1,
+        ));
+    } else if let Some(path) = first_line.strip_prefix(ADD_SYMLINK_MARKER) {
+        let Some(target) = lines
+            .get(1)
+            .and_then(|line| line.trim().strip_prefix(LINK_TO_MARKER))
+            .filter(|target| !target.is_empty())
+        else {
+            return Err(InvalidHunkError {
+                message: format!(
+                    "Symlink hunk for path '{path}' must be followed by '*** Link to: {{target}}'"
+                ),
+                line_number: line_number + 1,
+            });
+        };
+        return Ok((
+            AddSymlink {
+                path: PathBuf::from(path),
+                target: PathBuf::from(target),
+            },
+            2,
+        ));
+    } else if let Some(path) = first_line.strip_prefix(CHMOD_FILE_MARKER) {
+        let Some(mode) = lines
+            .get(1)
+            .and_then(|line| line.trim().strip_prefix(MODE_MARKER))
+        else {
+            return Err(InvalidHunkError {
+                message: format!(
+                    "Chmod hunk for path '{path}' must be followed by '*** Mode: {{mode}}'"
+                ),
+                line_number: line_number + 1,
+            });
+        };
+        let Some(mode) = parse_mode(mode) else {
+            return Err(InvalidHunkError {
+                message: format!(
+                    "Invalid mode '{mode}' for path '{path}'; expected octal permissions such as 755 or 644"
+                ),
+                line_number: line_number + 1,
+            });
+        };
+        return Ok((
+            ChangeMode {
+                path: PathBuf::from(path),
+                mode,
+            },
+            2,
+        ));
     }
 
     Err(InvalidHunkError {
         message: format!(
-            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Delete File: {{path}}', '*** Update File: {{path}}'"
+            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Delete File: {{path}}', '*** Update File: {{path}}', '*** Add Binary File: {{path}}', '*** Update Binary File: {{path}}', '*** Add Symlink: {{path}}', '*** Chmod File: {{path}}'"
         ),
         line_number,
     })
 }
 
+/// Collects the `+`-prefixed base64 lines that follow a binary hunk header and
+/// decodes them. Returns the decoded bytes and the number of lines consumed.
+fn parse_base64_lines(
+    lines: &[&str],
+    path: &str,
+    line_number: usize,
+) -> Result<(Vec<u8>, usize), ParseError> {
+    let mut encoded = String::new();
+    let mut parsed_lines = 0;
+    for line in lines {
+        let Some(chunk) = line.strip_prefix('+') else {
+            break;
+        };
+        encoded.push_str(chunk.trim());
+        parsed_lines += 1;
+    }
+    let contents = BASE64_STANDARD
+        .decode(encoded.as_bytes())
+        .map_err(|err| InvalidHunkError {
+            message: format!("Invalid base64 contents for binary file '{path}': {err}"),
+            line_number,
+        })?;
+    Ok((contents, parsed_lines))
+}
+
+/// Parses octal permission bits (`755`, `0644`) or a git file mode
+/// (`100755`, `100644`) into permission bits. Setuid, setgid and sticky bits
+/// are rejected.
+fn parse_mode(mode: &str) -> Option<u32> {
+    let mode = mode.trim();
+    let permissions = match mode {
+        "100644" | "100755" => &mode[3..],
+        _ if (3..=4).contains(&mode.len()) => mode,
+        _ => return None,
+    };
+    u32::from_str_radix(permissions, 8)
+        .ok()
+        .filter(|bits| *bits <= 0o777)
+}
+
 fn parse_update_file_chunk(
     lines: &[&str],
     line_number: usize,
@@ -653,7 +798,9 @@ fn test_parse_one_hunk() {
         parse_one_hunk(&["bad"], 234),
         Err(InvalidHunkError {
             message: "'bad' is not a valid hunk header. \
-            Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}'".to_string(),
+            Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}', \
+            '*** Add Binary File: {path}', '*** Update Binary File: {path}', '*** Add Symlink: {path}', \
+            '*** Chmod File: {path}'".to_string(),
             line_number: 234
         })
     );
@@ -739,3 +886,83 @@ fn test_update_file_chunk() {
         ))
     );
 }
+
+#[test]
+fn test_parse_binary_symlink_and_mode_hunks() {
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n\
+             *** Add Binary File: assets/logo.png\n\
+             +iVBORw0K\n\
+             +GgoAAAA=\n\
+             *** Update Binary File: data.bin\n\
+             +AAEC\n\
+             *** Add Symlink: bin/tool\n\
+             *** Link to: ../scripts/tool.sh\n\
+             *** Chmod File: scripts/tool.sh\n\
+             *** Mode: 755\n\
+             *** Chmod File: README.md\n\
+             *** Mode: 100644\n\
+             *** End Patch",
+            ParseMode::Strict
+        )
+        .unwrap()
+        .hunks,
+        vec![
+            AddBinaryFile {
+                path: PathBuf::from("assets/logo.png"),
+                contents: vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n', 0, 0, 0],
+            },
+            UpdateBinaryFile {
+                path: PathBuf::from("data.bin"),
+                contents: vec![0, 1, 2],
+            },
+            AddSymlink {
+                path: PathBuf::from("bin/tool"),
+                target: PathBuf::from("../scripts/tool.sh"),
+            },
+            ChangeMode {
+                path: PathBuf::from("scripts/tool.sh"),
+                mode: 0o755,
+            },
+            ChangeMode {
+                path: PathBuf::from("README.md"),
+                mode: 0o644,
+            },
+        ]
+    );
+
+    assert_eq!(
+        parse_one_hunk(&["*** Add Binary File: a.bin", "+not base64!"], 7),
+        Err(InvalidHunkError {
+            message:
+                "Invalid base64 contents for binary file 'a.bin': Invalid symbol 32, offset 3."
+                    .to_string(),
+            line_number: 7
+        })
+    );
+    assert_eq!(
+        parse_one_hunk(&["*** Add Symlink: link"], 3),
+        Err(InvalidHunkError {
+            message: "Symlink hunk for path 'link' must be followed by '*** Link to: {target}'"
+                .to_string(),
+            line_number: 4
+        })
+    );
+    assert_eq!(
+        parse_one_hunk(&["*** Chmod File: run.sh", "*** Mode: 999"], 5),
+        Err(InvalidHunkError {
+            message: "Invalid mode '999' for path 'run.sh'; expected octal permissions such as 755 or 644"
+                .to_string(),
+            line_number: 6
+        })
+    );
+    assert_eq!(
+        parse_one_hunk(&["*** Chmod File: run.sh", "*** Mode: 4755"], 5),
+        Err(InvalidHunkError {
+            message: "Invalid mode '4755' for path 'run.sh'; expected octal permissions such as 755 or 644"
+                .to_string(),
+            line_number: 6
+        })
+    );
+}
@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_core::config::find_codex_home;
+use codex_core::config::load_config_as_toml_with_cli_overrides;
+use codex_core::execpolicy_audit::AuditChange;
+use codex_core::execpolicy_audit::AuditReport;
+use codex_core::execpolicy_audit::RecordedOutcome;
+use codex_core::execpolicy_audit::audit_rollouts;
+use codex_core::load_exec_policy;
+use codex_core::load_exec_policy_dir;
+use codex_execpolicy::Decision;
+
+/// Replay commands from recorded sessions against a candidate policy.
+#[derive(Debug, clap::Parser)]
+pub struct ExecPolicyAuditCommand {
+    /// Directory holding the candidate `.rules` files.
+    #[arg(short = 'r', long = "rules", value_name = "DIR")]
+    pub rules: PathBuf,
+
+    /// Maximum number of recent sessions to scan.
+    #[arg(long = "sessions", value_name = "N", default_value_t = 200)]
+    pub sessions: usize,
+
+    /// Maximum number of suggested prefix rules.
+    #[arg(long = "suggestions", value_name = "N", default_value_t = 10)]
+    pub suggestions: usize,
+
+    /// Output the report as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl ExecPolicyAuditCommand {
+    pub async fn run(self) -> Result<()> {
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        // Only project trust is read, to decide whose checked-in rules apply.
+        let config = load_config_as_toml_with_cli_overrides(&codex_home, Vec::new())
+            .await
+            .context("failed to load config")?;
+        let current = load_exec_policy(&codex_home).await?;
+        let candidate = load_exec_policy_dir(&self.rules).await?;
+
+        let report = audit_rollouts(
+            &codex_home,
+            &config,
+            &current,
+            &candidate,
+            self.sessions,
+            self.suggestions,
+        )
+        .await
+        .context("failed to read recorded sessions")?;
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print_report(&report);
+        }
+        Ok(())
+    }
+}
+
+fn print_report(report: &AuditReport) {
+    println!(
+        "Scanned {} commands from {} sessions.",
+        report.commands_scanned, report.sessions_scanned
+    );
+
+    print_changes("Newly allowed", &report.newly_allowed);
+    print_changes("Newly prompted", &report.newly_prompted);
+    print_changes("Newly forbidden", &report.newly_forbidden);
+
+    if !report.suggested_rules.is_empty() {
+        println!();
+        println!("Suggested rules for frequently approved commands:");
+        for rule in &report.suggested_rules {
+            println!("  {}  # {} approvals", rule.to_rule(), rule.occurrences);
+        }
+    }
+}
+
+fn print_changes(title: &str, changes: &[AuditChange]) {
+    if changes.is_empty() {
+        return;
+    }
+    println!();
+    println!("{title} ({}):", changes.len());
+    for change in changes {
+        println!(
+            "  {}  [{} -> {}, recorded: {}, {}x]",
+            change.command.join(" "),
+            describe(change.current),
+            describe(change.candidate),
+            describe_outcome(change.outcome),
+            change.occurrences
+        );
+    }
+}
+
+fn describe(decision: Decision) -> &'static str {
+    match decision {
+        Decision::Allow => "allow",
+        Decision::Prompt => "prompt",
+        Decision::Forbidden => "forbidden",
+    }
+}
+
+fn describe_outcome(outcome: RecordedOutcome) -> &'static str {
+    match outcome {
+        RecordedOutcome::Ran => "ran",
+        RecordedOutcome::Approved => "approved",
+        RecordedOutcome::Denied => "denied",
+        RecordedOutcome::Forbidden => "forbidden",
+    }
+}
@@ -3,8 +3,13 @@ use codex_common::CliConfigOverrides;
 use codex_core::CodexAuth;
 use codex_core::auth::AuthCredentialsStoreMode;
 use codex_core::auth::CLIENT_ID;
-use codex_core::auth::login_with_api_key;
-use codex_core::auth::logout;
+use codex_core::auth::DEFAULT_ACCOUNT_NAME;
+use codex_core::auth::load_accounts;
+use codex_core::auth::login_account_with_api_key;
+use codex_core::auth::logout_account;
+use codex_core::auth::parse_account_name;
+use codex_core::auth::resolve_account;
+use codex_core::auth::set_active_account;
 use codex_core::config::Config;
 use codex_login::ServerOptions;
 use codex_login::run_device_code_login;
@@ -16,15 +21,17 @@ use std::path::PathBuf;
 
 pub async fn login_with_chatgpt(
     codex_home: PathBuf,
+    account: Option<String>,
     forced_chatgpt_workspace_id: Option<String>,
     cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
 ) -> std::io::Result<()> {
-    let opts = ServerOptions::new(
+    let mut opts = ServerOptions::new(
         codex_home,
         CLIENT_ID.to_string(),
         forced_chatgpt_workspace_id,
         cli_auth_credentials_store_mode,
     );
+    opts.account = account;
     let server = run_login_server(opts)?;
 
     eprintln!(
@@ -35,7 +42,11 @@ pub async fn login_with_chatgpt(
     server.block_until_done().await
 }
 
-pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) -> ! {
+pub async fn run_login_with_chatgpt(
+    cli_config_overrides: CliConfigOverrides,
+    account_name: Option<String>,
+) -> ! {
+    let account = parse_account_or_exit(account_name.as_deref());
     let config = load_config_or_exit(cli_config_overrides).await;
 
     if matches!(config.forced_login_method, Some(ForcedLoginMethod::Api)) {
@@ -47,6 +58,7 @@ pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) ->
 
     match login_with_chatgpt(
         config.codex_home,
+        account,
         forced_chatgpt_workspace_id,
         config.cli_auth_credentials_store_mode,
     )
@@ -65,8 +77,10 @@ pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) ->
 
 pub async fn run_login_with_api_key(
     cli_config_overrides: CliConfigOverrides,
+    account_name: Option<String>,
     api_key: String,
 ) -> ! {
+    let account = parse_account_or_exit(account_name.as_deref());
     let config = load_config_or_exit(cli_config_overrides).await;
 
     if matches!(config.forced_login_method, Some(ForcedLoginMethod::Chatgpt)) {
@@ -74,8 +88,9 @@ pub async fn run_login_with_api_key(
         std::process::exit(1);
     }
 
-    match login_with_api_key(
+    match login_account_with_api_key(
         &config.codex_home,
+        account.as_deref(),
         &api_key,
         config.cli_auth_credentials_store_mode,
     ) {
@@ -120,9 +135,11 @@ pub fn read_api_key_from_stdin() -> String {
 /// Login using the OAuth device code flow.
 pub async fn run_login_with_device_code(
     cli_config_overrides: CliConfigOverrides,
+    account_name: Option<String>,
     issuer_base_url: Option<String>,
     client_id: Option<String>,
 ) -> ! {
+    let account = parse_account_or_exit(account_name.as_deref());
     let config = load_config_or_exit(cli_config_overrides).await;
     if matches!(config.forced_login_method, Some(ForcedLoginMethod::Api)) {
         eprintln!("ChatGPT login is disabled. Use API key login instead.");
@@ -135,6 +152,7 @@ pub async fn run_login_with_device_code(
         forced_chatgpt_workspace_id,
         config.cli_auth_credentials_store_mode,
     );
+    opts.account = account;
     if let Some(iss) = issuer_base_url {
         opts.issuer = iss;
     }
@@ -150,10 +168,21 @@ pub async fn run_login_with_device_code(
     }
 }
 
-pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
+pub async fn run_login_status(
+    cli_config_overrides: CliConfigOverrides,
+    account_name: Option<String>,
+) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
+    let account = match account_name {
+        Some(name) => parse_account_or_exit(Some(&name)),
+        None => resolve_account(&config.codex_home, config.account.as_deref()),
+    };
 
-    match CodexAuth::from_auth_storage(&config.codex_home, config.cli_auth_credentials_store_mode) {
+    match CodexAuth::from_account_storage(
+        &config.codex_home,
+        account.as_deref(),
+        config.cli_auth_credentials_store_mode,
+    ) {
         Ok(Some(auth)) => match auth.mode {
             AuthMode::ApiKey => match auth.get_token().await {
                 Ok(api_key) => {
@@ -181,10 +210,21 @@ pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
     }
 }
 
-pub async fn run_logout(cli_config_overrides: CliConfigOverrides) -> ! {
+pub async fn run_logout(
+    cli_config_overrides: CliConfigOverrides,
+    account_name: Option<String>,
+) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
+    let account = match account_name {
+        Some(name) => parse_account_or_exit(Some(&name)),
+        None => resolve_account(&config.codex_home, config.account.as_deref()),
+    };
 
-    match logout(&config.codex_home, config.cli_auth_credentials_store_mode) {
+    match logout_account(
+        &config.codex_home,
+        account.as_deref(),
+        config.cli_auth_credentials_store_mode,
+    ) {
         Ok(true) => {
             eprintln!("Successfully logged out");
             std::process::exit(0);
@@ -200,6 +240,75 @@ pub async fn run_logout(cli_config_overrides: CliConfigOverrides) -> ! {
     }
 }
 
+pub async fn run_account_list(cli_config_overrides: CliConfigOverrides) -> ! {
+    let config = load_config_or_exit(cli_config_overrides).await;
+    let accounts = match load_accounts(&config.codex_home) {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            eprintln!("Error reading accounts: {e}");
+            std::process::exit(1);
+        }
+    };
+    let in_use = resolve_account(&config.codex_home, config.account.as_deref());
+
+    let names = std::iter::once(None).chain(accounts.accounts.into_iter().map(Some));
+    for account in names {
+        let auth = CodexAuth::from_account_storage(
+            &config.codex_home,
+            account.as_deref(),
+            config.cli_auth_credentials_store_mode,
+        );
+        let description = match auth {
+            Ok(Some(auth)) => match auth.mode {
+                AuthMode::ApiKey => "API key".to_string(),
+                AuthMode::ChatGPT => match auth.get_account_email() {
+                    Some(email) => format!("ChatGPT ({email})"),
+                    None => "ChatGPT".to_string(),
+                },
+            },
+            Ok(None) if account.is_none() && in_use.is_some() => continue,
+            Ok(None) => "not logged in".to_string(),
+            Err(e) => format!("error: {e}"),
+        };
+        let marker = if account == in_use { "*" } else { " " };
+        let name = account.as_deref().unwrap_or(DEFAULT_ACCOUNT_NAME);
+        println!("{marker} {name}\t{description}");
+    }
+    std::process::exit(0);
+}
+
+pub async fn run_account_use(cli_config_overrides: CliConfigOverrides, name: String) -> ! {
+    let account = parse_account_or_exit(Some(&name));
+    let config = load_config_or_exit(cli_config_overrides).await;
+
+    match set_active_account(&config.codex_home, account.as_deref()) {
+        Ok(()) => {
+            eprintln!("Now using account {name}");
+            if let Some(configured) = &config.account {
+                eprintln!(
+                    "Note: the active profile pins account {configured}, which takes precedence."
+                );
+            }
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Error switching account: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn parse_account_or_exit(name: Option<&str>) -> Option<String> {
+    let name = name?;
+    match parse_account_name(name) {
+        Ok(account) => account,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
+}
+
 async fn load_config_or_exit(cli_config_overrides: CliConfigOverrides) -> Config {
     let cli_overrides = match cli_config_overrides.parse_overrides() {
         Ok(v) => v,
@@ -1,3 +1,4 @@
+use anyhow::Context;
 use clap::Args;
 use clap::CommandFactory;
 use clap::Parser;
@@ -10,6 +11,8 @@ use codex_cli::LandlockCommand;
 use codex_cli::SeatbeltCommand;
 use codex_cli::WindowsCommand;
 use codex_cli::login::read_api_key_from_stdin;
+use codex_cli::login::run_account_list;
+use codex_cli::login::run_account_use;
 use codex_cli::login::run_login_status;
 use codex_cli::login::run_login_with_api_key;
 use codex_cli::login::run_login_with_chatgpt;
@@ -21,6 +24,7 @@ use codex_exec::Cli as ExecCli;
 use codex_exec::Command as ExecCommand;
 use codex_exec::ReviewArgs;
 use codex_execpolicy::ExecPolicyCheckCommand;
+use codex_otel::summarize::Summarizer;
 use codex_responses_api_proxy::Args as ResponsesApiProxyArgs;
 use codex_tui::AppExitInfo;
 use codex_tui::Cli as TuiCli;
@@ -30,10 +34,12 @@ use owo_colors::OwoColorize;
 use std::path::PathBuf;
 use supports_color::Stream;
 
+mod execpolicy_audit;
 mod mcp_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::execpolicy_audit::ExecPolicyAuditCommand;
 use crate::mcp_cmd::McpCli;
 
 use codex_core::config::Config;
@@ -89,6 +95,9 @@ enum Subcommand {
     /// Remove stored authentication credentials.
     Logout(LogoutCommand),
 
+    /// List stored accounts or choose the active one.
+    Account(AccountCommand),
+
     /// [experimental] Run Codex as an MCP server and manage MCP servers.
     Mcp(McpCli),
 
@@ -133,6 +142,9 @@ enum Subcommand {
 
     /// Inspect feature flags.
     Features(FeaturesCli),
+
+    /// Inspect telemetry written by the OTEL `file` exporter.
+    Otel(OtelCli),
 }
 
 #[derive(Debug, Parser)]
@@ -192,6 +204,10 @@ enum ExecpolicySubcommand {
     /// Check execpolicy files against a command.
     #[clap(name = "check")]
     Check(ExecPolicyCheckCommand),
+
+    /// Replay recorded sessions against a candidate policy directory.
+    #[clap(name = "audit")]
+    Audit(ExecPolicyAuditCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -216,6 +232,11 @@ struct LoginCommand {
     #[arg(long = "device-auth")]
     use_device_code: bool,
 
+    /// Store the credentials under a named account instead of the default
+    /// account, and make it the active account.
+    #[arg(long = "name", value_name = "ACCOUNT")]
+    account: Option<String>,
+
     /// EXPERIMENTAL: Use custom OAuth issuer base URL (advanced)
     /// Override the OAuth issuer base URL (advanced)
     #[arg(long = "experimental_issuer", value_name = "URL", hide = true)]
@@ -239,6 +260,31 @@ enum LoginSubcommand {
 struct LogoutCommand {
     #[clap(skip)]
     config_overrides: CliConfigOverrides,
+
+    /// Account to log out of. Defaults to the account currently in use.
+    #[arg(long = "name", value_name = "ACCOUNT")]
+    account: Option<String>,
+}
+
+#[derive(Debug, Parser)]
+struct AccountCommand {
+    #[clap(skip)]
+    config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    action: AccountSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum AccountSubcommand {
+    /// List stored accounts; the account in use is marked with `*`.
+    List,
+
+    /// Make the named account active (`default` selects the unnamed account).
+    Use {
+        #[arg(value_name = "ACCOUNT")]
+        name: String,
+    },
 }
 
 #[derive(Debug, Parser)]
@@ -419,6 +465,47 @@ enum FeaturesSubcommand {
     List,
 }
 
+#[derive(Debug, Parser)]
+struct OtelCli {
+    #[command(subcommand)]
+    sub: OtelSubcommand,
+}
+
+#[derive(Debug, Parser)]
+enum OtelSubcommand {
+    /// Print per-session latency and token breakdowns from OTLP-JSON files.
+    Summarize {
+        /// Files written by the `file` exporter, including rotated `<path>.N` files.
+        #[arg(value_name = "FILE", required = true)]
+        files: Vec<PathBuf>,
+    },
+}
+
+fn run_otel_summarize(files: &[PathBuf]) -> anyhow::Result<()> {
+    let mut summarizer = Summarizer::new();
+    for path in files {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        summarizer
+            .add_reader(std::io::BufReader::new(file))
+            .with_context(|| format!("failed to read {}", path.display()))?;
+    }
+    if summarizer.skipped_lines() > 0 {
+        eprintln!("Skipped {} malformed line(s).", summarizer.skipped_lines());
+    }
+    let sessions = summarizer.finish();
+    if sessions.is_empty() {
+        println!("No sessions found.");
+    }
+    for (index, session) in sessions.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        println!("{session}");
+    }
+    Ok(())
+}
+
 fn stage_str(stage: codex_core::features::Stage) -> &'static str {
     use codex_core::features::Stage;
     match stage {
@@ -528,12 +615,13 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             match login_cli.action {
                 Some(LoginSubcommand::Status) => {
-                    run_login_status(login_cli.config_overrides).await;
+                    run_login_status(login_cli.config_overrides, login_cli.account).await;
                 }
                 None => {
                     if login_cli.use_device_code {
                         run_login_with_device_code(
                             login_cli.config_overrides,
+                            login_cli.account,
                             login_cli.issuer_base_url,
                             login_cli.client_id,
                         )
@@ -545,9 +633,14 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                         std::process::exit(1);
                     } else if login_cli.with_api_key {
                         let api_key = read_api_key_from_stdin();
-                        run_login_with_api_key(login_cli.config_overrides, api_key).await;
+                        run_login_with_api_key(
+                            login_cli.config_overrides,
+                            login_cli.account,
+                            api_key,
+                        )
+                        .await;
                     } else {
-                        run_login_with_chatgpt(login_cli.config_overrides).await;
+                        run_login_with_chatgpt(login_cli.config_overrides, login_cli.account).await;
                     }
                 }
             }
@@ -557,7 +650,19 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 &mut logout_cli.config_overrides,
                 root_config_overrides.clone(),
             );
-            run_logout(logout_cli.config_overrides).await;
+            run_logout(logout_cli.config_overrides, logout_cli.account).await;
+        }
+        Some(Subcommand::Account(mut account_cli)) => {
+            prepend_config_flags(
+                &mut account_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            match account_cli.action {
+                AccountSubcommand::List => run_account_list(account_cli.config_overrides).await,
+                AccountSubcommand::Use { name } => {
+                    run_account_use(account_cli.config_overrides, name).await
+                }
+            }
         }
         Some(Subcommand::Completion(completion_cli)) => {
             print_completion(completion_cli);
@@ -606,6 +711,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
         },
         Some(Subcommand::Execpolicy(ExecpolicyCommand { sub })) => match sub {
             ExecpolicySubcommand::Check(cmd) => run_execpolicycheck(cmd)?,
+            ExecpolicySubcommand::Audit(cmd) => cmd.run().await?,
         },
         Some(Subcommand::Apply(mut apply_cli)) => {
             prepend_config_flags(
@@ -664,6 +770,9 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 }
             }
         },
+        Some(Subcommand::Otel(OtelCli { sub })) => match sub {
+            OtelSubcommand::Summarize { files } => run_otel_summarize(&files)?,
+        },
     }
 
     Ok(())
@@ -679,7 +788,16 @@ async fn run_tumix_command(tumix_cli: TumixCommand) -> anyhow::Result<()> {
         println!("{msg}");
     }) as codex_tumix::ProgressCallback);
 
-    let result = codex_tumix::run_tumix(tumix_cli.session_id, None, progress_cb).await?;
+    let cli_kv_overrides = tumix_cli
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(cli_kv_overrides).await?;
+    let meta_model = codex_tumix::MetaAgentModel::from_config(&config);
+
+    let result =
+        codex_tumix::run_tumix_with_meta_model(tumix_cli.session_id, None, progress_cb, meta_model)
+            .await?;
 
     println!();
     println!("✨ TUMIX Round 1 completed successfully!");
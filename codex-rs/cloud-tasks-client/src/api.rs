@@ -49,7 +49,8 @@ pub struct TaskSummary {
     pub attempt_total: Option<usize>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum AttemptStatus {
     Pending,
     InProgress,
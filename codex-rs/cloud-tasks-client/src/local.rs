@@ -0,0 +1,1067 @@
+//! A [`CloudBackend`] that runs tasks on this machine.
+//!
+//! Each task checks out `git_ref` into one git worktree per attempt and runs a
+//! headless Codex session in it. When an attempt finishes, its staged diff is
+//! captured and the worktree is removed. Task state lives on disk:
+//!
+//! ```text
+//! $CODEX_HOME/local-tasks/<task id>/task.json
+//! $CODEX_HOME/local-tasks/<task id>/attempt-<n>.diff
+//! $CODEX_HOME/local-tasks/<task id>/attempt-<n>.lock        (while running)
+//! $CODEX_HOME/local-tasks/<task id>/worktrees/attempt-<n>   (while running)
+//! ```
+//!
+//! The process running an attempt holds an exclusive lock on its `.lock`
+//! file. An unfinished attempt whose lock can be taken belonged to a process
+//! that exited early; loading the task marks it failed and removes its
+//! worktree.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Utc;
+use codex_core::AuthManager;
+use codex_core::ConversationManager;
+use codex_core::NewConversation;
+use codex_core::config::Config;
+use codex_core::git_info::get_git_repo_root;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::SessionSource;
+use codex_protocol::approvals::ElicitationAction;
+use codex_protocol::user_input::UserInput;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::ApplyOutcome;
+use crate::ApplyStatus;
+use crate::AttemptStatus;
+use crate::CloudBackend;
+use crate::CloudTaskError;
+use crate::CreatedTask;
+use crate::DiffSummary;
+use crate::Result;
+use crate::TaskId;
+use crate::TaskStatus;
+use crate::TaskSummary;
+use crate::TaskText;
+use crate::TurnAttempt;
+
+/// Directory under `CODEX_HOME` that holds local task state.
+pub const LOCAL_TASKS_SUBDIR: &str = "local-tasks";
+
+/// The single environment id that local tasks report.
+pub const LOCAL_ENVIRONMENT_ID: &str = "local";
+
+const TASK_FILE: &str = "task.json";
+
+#[derive(Clone)]
+pub struct LocalBackend {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    root: PathBuf,
+    config: Config,
+    conversation_manager: ConversationManager,
+    /// Serializes read-modify-write cycles on `task.json`.
+    store_lock: tokio::sync::Mutex<()>,
+    running: std::sync::Mutex<HashMap<String, Vec<JoinHandle<()>>>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct LocalTask {
+    id: TaskId,
+    title: String,
+    prompt: String,
+    git_ref: String,
+    repo_root: PathBuf,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    #[serde(default)]
+    applied: bool,
+    attempts: Vec<LocalAttempt>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct LocalAttempt {
+    turn_id: String,
+    placement: i64,
+    status: AttemptStatus,
+    created_at: DateTime<Utc>,
+    #[serde(default)]
+    messages: Vec<String>,
+    #[serde(default)]
+    summary: DiffSummary,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl LocalTask {
+    fn status(&self) -> TaskStatus {
+        if self.applied {
+            return TaskStatus::Applied;
+        }
+        let statuses = || self.attempts.iter().map(|a| a.status);
+        if statuses().any(|s| matches!(s, AttemptStatus::Pending | AttemptStatus::InProgress)) {
+            TaskStatus::Pending
+        } else if statuses().any(|s| s == AttemptStatus::Completed) {
+            TaskStatus::Ready
+        } else {
+            TaskStatus::Error
+        }
+    }
+
+    fn primary(&self) -> Result<&LocalAttempt> {
+        self.attempts
+            .first()
+            .ok_or_else(|| CloudTaskError::Msg(format!("Task {} has no attempts", self.id.0)))
+    }
+
+    fn summary(&self) -> TaskSummary {
+        TaskSummary {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            status: self.status(),
+            updated_at: self.updated_at,
+            environment_id: Some(LOCAL_ENVIRONMENT_ID.to_string()),
+            environment_label: Some(LOCAL_ENVIRONMENT_ID.to_string()),
+            summary: self
+                .attempts
+                .first()
+                .map(|a| a.summary.clone())
+                .unwrap_or_default(),
+            is_review: false,
+            attempt_total: Some(self.attempts.len()),
+        }
+    }
+}
+
+impl LocalBackend {
+    /// Creates a backend that stores tasks under `config.codex_home` and runs
+    /// them with the model and provider settings from `config`.
+    pub fn new(config: Config, auth_manager: Arc<AuthManager>) -> Self {
+        let conversation_manager = ConversationManager::new(auth_manager, SessionSource::Exec);
+        Self::with_conversation_manager(config, conversation_manager)
+    }
+
+    fn with_conversation_manager(
+        config: Config,
+        conversation_manager: ConversationManager,
+    ) -> Self {
+        let root = config.codex_home.join(LOCAL_TASKS_SUBDIR);
+        Self {
+            inner: Arc::new(Inner {
+                root,
+                config,
+                conversation_manager,
+                store_lock: tokio::sync::Mutex::new(()),
+                running: std::sync::Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Waits for every attempt of a task started by this process to finish.
+    pub async fn wait_for_task(&self, id: &TaskId) {
+        let handles = self
+            .inner
+            .running
+            .lock()
+            .ok()
+            .and_then(|mut running| running.remove(&id.0))
+            .unwrap_or_default();
+        for handle in handles {
+            let _ = handle.await;
+        }
+    }
+
+    /// Directory holding the state of task `id`. Ids come from the command
+    /// line and the UI, so anything that could escape `root` is rejected.
+    fn task_dir(&self, id: &TaskId) -> Result<PathBuf> {
+        let valid = !id.0.is_empty()
+            && id
+                .0
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(CloudTaskError::Msg(format!(
+                "Invalid local task id {:?}",
+                id.0
+            )));
+        }
+        Ok(self.inner.root.join(&id.0))
+    }
+
+    fn diff_path(&self, id: &TaskId, turn_id: &str) -> Result<PathBuf> {
+        Ok(self.task_dir(id)?.join(format!("{turn_id}.diff")))
+    }
+
+    fn lock_path(&self, id: &TaskId, turn_id: &str) -> Result<PathBuf> {
+        Ok(self.task_dir(id)?.join(format!("{turn_id}.lock")))
+    }
+
+    fn worktree_path(&self, id: &TaskId, turn_id: &str) -> Result<PathBuf> {
+        Ok(self.task_dir(id)?.join("worktrees").join(turn_id))
+    }
+
+    /// Loads a task, failing any attempt whose runner has gone away.
+    async fn load(&self, id: &TaskId) -> Result<LocalTask> {
+        let task = self.read_task(id).await?;
+        if self.dead_attempts(&task).is_empty() {
+            return Ok(task);
+        }
+        let _guard = self.inner.store_lock.lock().await;
+        let mut task = self.read_task(id).await?;
+        let dead = self.dead_attempts(&task);
+        if dead.is_empty() {
+            return Ok(task);
+        }
+        for attempt in &mut task.attempts {
+            if dead.contains(&attempt.turn_id) {
+                attempt.status = AttemptStatus::Failed;
+                attempt.error = Some("The process running this attempt exited early".to_string());
+            }
+        }
+        task.updated_at = Utc::now();
+        self.save(&task).await?;
+        for turn_id in &dead {
+            if let Ok(worktree) = self.worktree_path(id, turn_id)
+                && worktree.exists()
+            {
+                remove_worktree(&task.repo_root, &worktree).await;
+            }
+            if let Ok(lock) = self.lock_path(id, turn_id) {
+                let _ = tokio::fs::remove_file(lock).await;
+            }
+        }
+        Ok(task)
+    }
+
+    /// Unfinished attempts that no process is running any more.
+    fn dead_attempts(&self, task: &LocalTask) -> Vec<String> {
+        task.attempts
+            .iter()
+            .filter(|a| matches!(a.status, AttemptStatus::Pending | AttemptStatus::InProgress))
+            .filter(|a| match self.lock_path(&task.id, &a.turn_id) {
+                Ok(path) => !attempt_is_running(&path),
+                Err(_) => false,
+            })
+            .map(|a| a.turn_id.clone())
+            .collect()
+    }
+
+    async fn read_task(&self, id: &TaskId) -> Result<LocalTask> {
+        let path = self.task_dir(id)?.join(TASK_FILE);
+        let bytes = tokio::fs::read(&path).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                CloudTaskError::Msg(format!("Task {} not found", id.0))
+            } else {
+                CloudTaskError::Io(format!("failed to read {}: {e}", path.display()))
+            }
+        })?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| CloudTaskError::Msg(format!("invalid task file {}: {e}", path.display())))
+    }
+
+    async fn save(&self, task: &LocalTask) -> Result<()> {
+        let dir = self.task_dir(&task.id)?;
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|e| CloudTaskError::Io(format!("failed to create {}: {e}", dir.display())))?;
+        let json = serde_json::to_vec_pretty(task)
+            .map_err(|e| CloudTaskError::Msg(format!("failed to serialize task: {e}")))?;
+        let path = dir.join(TASK_FILE);
+        let tmp = dir.join(format!("{TASK_FILE}.tmp"));
+        tokio::fs::write(&tmp, json)
+            .await
+            .map_err(|e| CloudTaskError::Io(format!("failed to write {}: {e}", tmp.display())))?;
+        tokio::fs::rename(&tmp, &path)
+            .await
+            .map_err(|e| CloudTaskError::Io(format!("failed to write {}: {e}", path.display())))
+    }
+
+    async fn update(&self, id: &TaskId, f: impl FnOnce(&mut LocalTask)) -> Result<()> {
+        let _guard = self.inner.store_lock.lock().await;
+        let mut task = self.read_task(id).await?;
+        f(&mut task);
+        task.updated_at = Utc::now();
+        self.save(&task).await
+    }
+
+    async fn read_diff(&self, id: &TaskId, turn_id: &str) -> Option<String> {
+        tokio::fs::read_to_string(self.diff_path(id, turn_id).ok()?)
+            .await
+            .ok()
+            .filter(|diff| !diff.trim().is_empty())
+    }
+
+    /// Runs one attempt while holding `lock`, its attempt lock.
+    async fn run_attempt(
+        &self,
+        id: TaskId,
+        turn_id: String,
+        worktree: PathBuf,
+        lock: std::fs::File,
+    ) {
+        let _ = self
+            .update(&id, |task| {
+                set_attempt(task, &turn_id, |a| a.status = AttemptStatus::InProgress);
+            })
+            .await;
+
+        let outcome = self.drive_session(&id, &worktree).await;
+        let diff = match &outcome {
+            Ok(_) => capture_diff(&worktree).await,
+            Err(err) => Err(CloudTaskError::Msg(err.to_string())),
+        };
+        if let Ok(diff) = &diff
+            && let Ok(path) = self.diff_path(&id, &turn_id)
+            && let Err(err) = tokio::fs::write(path, diff).await
+        {
+            tracing::warn!("failed to store diff for local task {}: {err}", id.0);
+        }
+        if let Ok(task) = self.read_task(&id).await {
+            remove_worktree(&task.repo_root, &worktree).await;
+        }
+
+        let _ = self
+            .update(&id, |task| {
+                set_attempt(task, &turn_id, |attempt| match (outcome, diff) {
+                    (Ok(messages), Ok(diff)) => {
+                        attempt.status = AttemptStatus::Completed;
+                        attempt.messages = messages;
+                        attempt.summary = diff_summary(&diff);
+                    }
+                    (Ok(messages), Err(err)) => {
+                        attempt.status = AttemptStatus::Failed;
+                        attempt.messages = messages;
+                        attempt.error = Some(err.to_string());
+                    }
+                    (Err(err), _) => {
+                        attempt.status = AttemptStatus::Failed;
+                        attempt.error = Some(err.to_string());
+                    }
+                });
+            })
+            .await;
+        if let Ok(path) = self.lock_path(&id, &turn_id) {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+        drop(lock);
+    }
+
+    /// Runs one headless turn in `worktree` and returns the assistant messages.
+    async fn drive_session(&self, id: &TaskId, worktree: &Path) -> Result<Vec<String>> {
+        let task = self.read_task(id).await?;
+        let mut config = self.inner.config.clone();
+        config.cwd = worktree.to_path_buf();
+        config.sandbox_policy = SandboxPolicy::new_workspace_write_policy();
+        config
+            .approval_policy
+            .set(AskForApproval::Never)
+            .map_err(|e| CloudTaskError::Msg(format!("approval policy: {e}")))?;
+
+        let manager = &self.inner.conversation_manager;
+        let model = manager
+            .get_models_manager()
+            .get_model(&config.model, &config)
+            .await;
+        let NewConversation {
+            conversation_id,
+            conversation,
+            ..
+        } = manager
+            .new_conversation(config.clone())
+            .await
+            .map_err(|e| CloudTaskError::Msg(format!("failed to start session: {e}")))?;
+
+        let submit = |op: Op| {
+            let conversation = conversation.clone();
+            async move {
+                conversation
+                    .submit(op)
+                    .await
+                    .map_err(|e| CloudTaskError::Msg(format!("session error: {e}")))
+            }
+        };
+        submit(Op::UserTurn {
+            items: vec![UserInput::Text { text: task.prompt }],
+            cwd: config.cwd.clone(),
+            approval_policy: config.approval_policy.value(),
+            sandbox_policy: config.sandbox_policy.clone(),
+            model,
+            effort: config.model_reasoning_effort,
+            summary: config.model_reasoning_summary,
+            final_output_json_schema: None,
+        })
+        .await?;
+
+        let mut messages = Vec::new();
+        let mut error = None;
+        loop {
+            let event = conversation
+                .next_event()
+                .await
+                .map_err(|e| CloudTaskError::Msg(format!("session error: {e}")))?;
+            match event.msg {
+                EventMsg::AgentMessage(ev) => messages.push(ev.message),
+                EventMsg::Error(ev) => error = Some(ev.message),
+                EventMsg::ElicitationRequest(ev) => {
+                    submit(Op::ResolveElicitation {
+                        server_name: ev.server_name,
+                        request_id: ev.id,
+                        decision: ElicitationAction::Cancel,
+                    })
+                    .await?;
+                }
+                EventMsg::TaskComplete(_) | EventMsg::TurnAborted(_) => {
+                    submit(Op::Shutdown).await?;
+                }
+                EventMsg::ShutdownComplete => break,
+                _ => {}
+            }
+        }
+        manager.remove_conversation(&conversation_id).await;
+
+        match error {
+            Some(message) if messages.is_empty() => Err(CloudTaskError::Msg(message)),
+            _ => Ok(messages),
+        }
+    }
+
+    async fn apply(
+        &self,
+        id: TaskId,
+        diff_override: Option<String>,
+        preflight: bool,
+    ) -> Result<ApplyOutcome> {
+        let task = self.load(&id).await?;
+        let diff = match diff_override {
+            Some(diff) => diff,
+            None => self
+                .read_diff(&id, &task.primary()?.turn_id)
+                .await
+                .ok_or_else(|| {
+                    CloudTaskError::Msg(format!("No diff available for task {}", id.0))
+                })?,
+        };
+
+        let req = codex_git::ApplyGitRequest {
+            cwd: task.repo_root.clone(),
+            diff,
+            revert: false,
+            preflight,
+        };
+        let r = codex_git::apply_git_patch(&req)
+            .map_err(|e| CloudTaskError::Io(format!("git apply failed to run: {e}")))?;
+
+        let status = if r.exit_code == 0 {
+            ApplyStatus::Success
+        } else if !r.applied_paths.is_empty() || !r.conflicted_paths.is_empty() {
+            ApplyStatus::Partial
+        } else {
+            ApplyStatus::Error
+        };
+        let applied = status == ApplyStatus::Success && !preflight;
+        let counts = format!(
+            "applied={}, skipped={}, conflicts={}",
+            r.applied_paths.len(),
+            r.skipped_paths.len(),
+            r.conflicted_paths.len()
+        );
+        let message = match (preflight, &status) {
+            (true, ApplyStatus::Success) => {
+                format!("Preflight passed for task {} (applies cleanly)", id.0)
+            }
+            (true, ApplyStatus::Partial) => format!(
+                "Preflight: patch does not fully apply for task {} ({counts})",
+                id.0
+            ),
+            (true, ApplyStatus::Error) => format!("Preflight failed for task {} ({counts})", id.0),
+            (false, ApplyStatus::Success) => format!(
+                "Applied task {} to {} ({} files)",
+                id.0,
+                task.repo_root.display(),
+                r.applied_paths.len()
+            ),
+            (false, ApplyStatus::Partial) => {
+                format!("Apply partially succeeded for task {} ({counts})", id.0)
+            }
+            (false, ApplyStatus::Error) => format!("Apply failed for task {} ({counts})", id.0),
+        };
+
+        if applied {
+            self.update(&id, |task| task.applied = true).await?;
+        }
+
+        Ok(ApplyOutcome {
+            applied,
+            status,
+            message,
+            skipped_paths: r.skipped_paths,
+            conflict_paths: r.conflicted_paths,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl CloudBackend for LocalBackend {
+    async fn list_tasks(&self, _env: Option<&str>) -> Result<Vec<TaskSummary>> {
+        let mut entries = match tokio::fs::read_dir(&self.inner.root).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(CloudTaskError::Io(format!(
+                    "failed to read {}: {e}",
+                    self.inner.root.display()
+                )));
+            }
+        };
+        let mut tasks = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let id = TaskId(entry.file_name().to_string_lossy().into_owned());
+            match self.load(&id).await {
+                Ok(task) => tasks.push(task.summary()),
+                Err(err) => tracing::debug!("skipping local task {}: {err}", id.0),
+            }
+        }
+        tasks.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(tasks)
+    }
+
+    async fn get_task_summary(&self, id: TaskId) -> Result<TaskSummary> {
+        Ok(self.load(&id).await?.summary())
+    }
+
+    async fn get_task_diff(&self, id: TaskId) -> Result<Option<String>> {
+        let task = self.load(&id).await?;
+        Ok(self.read_diff(&id, &task.primary()?.turn_id).await)
+    }
+
+    async fn get_task_messages(&self, id: TaskId) -> Result<Vec<String>> {
+        let task = self.load(&id).await?;
+        let primary = task.primary()?;
+        let mut messages = primary.messages.clone();
+        messages.extend(primary.error.clone());
+        Ok(messages)
+    }
+
+    async fn get_task_text(&self, id: TaskId) -> Result<TaskText> {
+        let task = self.load(&id).await?;
+        let primary = task.primary()?;
+        Ok(TaskText {
+            prompt: Some(task.prompt.clone()),
+            messages: primary.messages.clone(),
+            turn_id: Some(primary.turn_id.clone()),
+            sibling_turn_ids: task.attempts[1..]
+                .iter()
+                .map(|a| a.turn_id.clone())
+                .collect(),
+            attempt_placement: Some(primary.placement),
+            attempt_status: primary.status,
+        })
+    }
+
+    async fn list_sibling_attempts(
+        &self,
+        task: TaskId,
+        turn_id: String,
+    ) -> Result<Vec<TurnAttempt>> {
+        let record = self.load(&task).await?;
+        let mut attempts = Vec::new();
+        for attempt in record.attempts.iter().filter(|a| a.turn_id != turn_id) {
+            attempts.push(TurnAttempt {
+                turn_id: attempt.turn_id.clone(),
+                attempt_placement: Some(attempt.placement),
+                created_at: Some(attempt.created_at),
+                status: attempt.status,
+                diff: self.read_diff(&task, &attempt.turn_id).await,
+                messages: attempt.messages.clone(),
+            });
+        }
+        Ok(attempts)
+    }
+
+    async fn apply_task_preflight(
+        &self,
+        id: TaskId,
+        diff_override: Option<String>,
+    ) -> Result<ApplyOutcome> {
+        self.apply(id, diff_override, true).await
+    }
+
+    async fn apply_task(&self, id: TaskId, diff_override: Option<String>) -> Result<ApplyOutcome> {
+        self.apply(id, diff_override, false).await
+    }
+
+    async fn create_task(
+        &self,
+        _env_id: &str,
+        prompt: &str,
+        git_ref: &str,
+        _qa_mode: bool,
+        best_of_n: usize,
+    ) -> Result<CreatedTask> {
+        let repo_root = get_git_repo_root(&self.inner.config.cwd).ok_or_else(|| {
+            CloudTaskError::Msg(format!(
+                "{} is not inside a git repository",
+                self.inner.config.cwd.display()
+            ))
+        })?;
+        let now = Utc::now();
+        let id = new_task_id(now);
+        let dir = self.task_dir(&id)?;
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|e| CloudTaskError::Io(format!("failed to create {}: {e}", dir.display())))?;
+
+        let mut runs = Vec::new();
+        let created: Result<()> = async {
+            let mut attempts = Vec::new();
+            for placement in 0..best_of_n.max(1) {
+                let turn_id = format!("attempt-{}", placement + 1);
+                let lock = lock_attempt(&self.lock_path(&id, &turn_id)?)?;
+                let path = self.worktree_path(&id, &turn_id)?;
+                // Keep the lock in `runs` before adding the worktree so a
+                // failure below still cleans up this attempt.
+                runs.push((turn_id.clone(), path.clone(), lock));
+                git(
+                    &repo_root,
+                    &[
+                        "worktree",
+                        "add",
+                        "--detach",
+                        &path.to_string_lossy(),
+                        git_ref,
+                    ],
+                )
+                .await?;
+                attempts.push(LocalAttempt {
+                    turn_id,
+                    placement: placement as i64,
+                    status: AttemptStatus::Pending,
+                    created_at: now,
+                    messages: Vec::new(),
+                    summary: DiffSummary::default(),
+                    error: None,
+                });
+            }
+
+            let task = LocalTask {
+                id: id.clone(),
+                title: title_from_prompt(prompt),
+                prompt: prompt.to_string(),
+                git_ref: git_ref.to_string(),
+                repo_root: repo_root.clone(),
+                created_at: now,
+                updated_at: now,
+                applied: false,
+                attempts,
+            };
+            self.save(&task).await
+        }
+        .await;
+        if let Err(err) = created {
+            // Without a saved task `load` never sees these attempts, so undo
+            // the worktrees and lock files made so far.
+            for (_, path, _) in &runs {
+                if path.exists() {
+                    remove_worktree(&repo_root, path).await;
+                }
+            }
+            drop(runs);
+            let _ = tokio::fs::remove_dir_all(&dir).await;
+            return Err(err);
+        }
+
+        let handles = runs
+            .into_iter()
+            .map(|(turn_id, path, lock)| {
+                let backend = self.clone();
+                let id = id.clone();
+                tokio::spawn(async move { backend.run_attempt(id, turn_id, path, lock).await })
+            })
+            .collect();
+        if let Ok(mut running) = self.inner.running.lock() {
+            running.insert(id.0.clone(), handles);
+        }
+        Ok(CreatedTask { id })
+    }
+}
+
+fn set_attempt(task: &mut LocalTask, turn_id: &str, f: impl FnOnce(&mut LocalAttempt)) {
+    if let Some(attempt) = task.attempts.iter_mut().find(|a| a.turn_id == turn_id) {
+        f(attempt);
+    }
+}
+
+/// A task id that sorts by creation time. The random suffix keeps ids
+/// distinct when several tasks are created in the same millisecond.
+fn new_task_id(now: DateTime<Utc>) -> TaskId {
+    let suffix: String = Uuid::new_v4()
+        .simple()
+        .to_string()
+        .chars()
+        .take(8)
+        .collect();
+    TaskId(format!(
+        "local-{}-{suffix}",
+        now.format("%Y%m%d-%H%M%S-%3f")
+    ))
+}
+
+/// Creates the lock file at `path` and takes an exclusive lock on it for the
+/// lifetime of the returned handle.
+fn lock_attempt(path: &Path) -> Result<std::fs::File> {
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+        .map_err(|e| CloudTaskError::Io(format!("failed to create {}: {e}", path.display())))?;
+    file.try_lock()
+        .map_err(|e| CloudTaskError::Io(format!("failed to lock {}: {e}", path.display())))?;
+    Ok(file)
+}
+
+/// Whether some process still holds the attempt lock at `path`.
+fn attempt_is_running(path: &Path) -> bool {
+    let file = match std::fs::OpenOptions::new().write(true).open(path) {
+        Ok(file) => file,
+        Err(e) => return e.kind() != std::io::ErrorKind::NotFound,
+    };
+    // Getting the lock means its holder is gone; dropping `file` releases it.
+    file.try_lock().is_err()
+}
+
+fn title_from_prompt(prompt: &str) -> String {
+    let first_line = prompt.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    let title: String = first_line.trim().chars().take(80).collect();
+    if title.is_empty() {
+        "Untitled task".to_string()
+    } else {
+        title
+    }
+}
+
+async fn git(cwd: &Path, args: &[&str]) -> Result<String> {
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(cwd)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| CloudTaskError::Io(format!("failed to run git: {e}")))?;
+    if !output.status.success() {
+        return Err(CloudTaskError::Io(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Stages everything in the worktree and returns it as a diff against the
+/// checked-out commit.
+async fn capture_diff(worktree: &Path) -> Result<String> {
+    git(worktree, &["add", "-A"]).await?;
+    git(worktree, &["diff", "--cached", "--binary", "HEAD"]).await
+}
+
+async fn remove_worktree(repo_root: &Path, worktree: &Path) {
+    let path = worktree.to_string_lossy();
+    if let Err(err) = git(repo_root, &["worktree", "remove", "--force", &path]).await {
+        tracing::warn!("failed to remove worktree {path}: {err}");
+    }
+}
+
+fn diff_summary(diff: &str) -> DiffSummary {
+    let mut summary = DiffSummary::default();
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            summary.files_changed += 1;
+        } else if !line.starts_with("+++") && !line.starts_with("---") {
+            match line.as_bytes().first() {
+                Some(b'+') => summary.lines_added += 1,
+                Some(b'-') => summary.lines_removed += 1,
+                _ => {}
+            }
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::CodexAuth;
+    use codex_core::ModelProviderInfo;
+    use codex_core::built_in_model_providers;
+    use core_test_support::load_default_config_for_test;
+    use core_test_support::responses::ev_assistant_message;
+    use core_test_support::responses::ev_completed;
+    use core_test_support::responses::ev_response_created;
+    use core_test_support::responses::mount_sse_sequence;
+    use core_test_support::responses::sse;
+    use core_test_support::responses::start_mock_server;
+    use core_test_support::skip_if_no_network;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn attempt(status: AttemptStatus) -> LocalAttempt {
+        LocalAttempt {
+            turn_id: "attempt-1".to_string(),
+            placement: 0,
+            status,
+            created_at: Utc::now(),
+            messages: Vec::new(),
+            summary: DiffSummary::default(),
+            error: None,
+        }
+    }
+
+    fn task(attempts: Vec<LocalAttempt>) -> LocalTask {
+        LocalTask {
+            id: TaskId("local-1".to_string()),
+            title: "t".to_string(),
+            prompt: "t".to_string(),
+            git_ref: "main".to_string(),
+            repo_root: PathBuf::from("/repo"),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            applied: false,
+            attempts,
+        }
+    }
+
+    #[test]
+    fn task_status_follows_attempts() {
+        let running = task(vec![
+            attempt(AttemptStatus::Completed),
+            attempt(AttemptStatus::InProgress),
+        ]);
+        assert_eq!(running.status(), TaskStatus::Pending);
+
+        let ready = task(vec![
+            attempt(AttemptStatus::Failed),
+            attempt(AttemptStatus::Completed),
+        ]);
+        assert_eq!(ready.status(), TaskStatus::Ready);
+
+        let failed = task(vec![attempt(AttemptStatus::Failed)]);
+        assert_eq!(failed.status(), TaskStatus::Error);
+
+        let mut applied = task(vec![attempt(AttemptStatus::Completed)]);
+        applied.applied = true;
+        assert_eq!(applied.status(), TaskStatus::Applied);
+    }
+
+    #[test]
+    fn diff_summary_counts_files_and_lines() {
+        let diff = "diff --git a/a.txt b/a.txt\n--- a/a.txt\n+++ b/a.txt\n@@ -1 +1,2 @@\n-old\n+new\n+more\ndiff --git a/b.txt b/b.txt\n--- /dev/null\n+++ b/b.txt\n@@ -0,0 +1 @@\n+b\n";
+        assert_eq!(
+            diff_summary(diff),
+            DiffSummary {
+                files_changed: 2,
+                lines_added: 3,
+                lines_removed: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn title_uses_first_non_empty_line() {
+        assert_eq!(
+            title_from_prompt("\n  Fix the build\nmore"),
+            "Fix the build"
+        );
+        assert_eq!(title_from_prompt("   "), "Untitled task");
+    }
+
+    #[test]
+    fn task_ids_are_unique_within_a_millisecond() {
+        let now = Utc::now();
+        assert_ne!(new_task_id(now), new_task_id(now));
+    }
+
+    async fn backend_in(codex_home: &TempDir) -> LocalBackend {
+        let config = load_default_config_for_test(codex_home).await;
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        LocalBackend::new(config, auth_manager)
+    }
+
+    #[tokio::test]
+    async fn task_dir_rejects_ids_outside_the_root() {
+        let codex_home = TempDir::new().unwrap();
+        let backend = backend_in(&codex_home).await;
+
+        for id in ["", "..", "../elsewhere", "a/b", "a\\b"] {
+            assert!(backend.task_dir(&TaskId(id.to_string())).is_err(), "{id:?}");
+        }
+        assert_eq!(
+            backend.task_dir(&TaskId("local-1".to_string())).unwrap(),
+            codex_home.path().join(LOCAL_TASKS_SUBDIR).join("local-1")
+        );
+    }
+
+    #[tokio::test]
+    async fn attempts_without_a_running_process_fail_on_load() {
+        let codex_home = TempDir::new().unwrap();
+        let backend = backend_in(&codex_home).await;
+        let mut running = attempt(AttemptStatus::InProgress);
+        running.turn_id = "attempt-2".to_string();
+        let stale = task(vec![attempt(AttemptStatus::InProgress), running]);
+        backend.save(&stale).await.unwrap();
+        let _lock = lock_attempt(&backend.lock_path(&stale.id, "attempt-2").unwrap()).unwrap();
+
+        let loaded = backend.load(&stale.id).await.unwrap();
+
+        let statuses: Vec<_> = loaded.attempts.iter().map(|a| a.status).collect();
+        assert_eq!(
+            statuses,
+            vec![AttemptStatus::Failed, AttemptStatus::InProgress]
+        );
+        assert_eq!(
+            loaded.attempts[0].error.as_deref(),
+            Some("The process running this attempt exited early")
+        );
+        assert_eq!(
+            backend.read_task(&stale.id).await.unwrap().attempts[0].status,
+            AttemptStatus::Failed
+        );
+    }
+
+    fn git_in(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?}");
+    }
+
+    fn init_repo(repo: &Path) {
+        git_in(repo, &["init", "-q"]);
+        std::fs::write(repo.join("README.md"), "hello\n").unwrap();
+        git_in(repo, &["add", "README.md"]);
+        git_in(
+            repo,
+            &[
+                "-c",
+                "user.name=t",
+                "-c",
+                "user.email=t@example.com",
+                "commit",
+                "-qm",
+                "init",
+            ],
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn create_task_removes_earlier_worktrees_when_an_attempt_fails() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let repo = TempDir::new().unwrap();
+        init_repo(repo.path());
+        // `git worktree add` reports a failing post-checkout hook; fail only
+        // the second attempt.
+        let hook = repo.path().join(".git").join("hooks").join("post-checkout");
+        std::fs::write(
+            &hook,
+            "#!/bin/sh\ncase \"$PWD\" in *attempt-2) exit 1 ;; esac\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let codex_home = TempDir::new().unwrap();
+        let mut config = load_default_config_for_test(&codex_home).await;
+        config.cwd = repo.path().to_path_buf();
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let backend = LocalBackend::new(config, auth_manager);
+
+        assert!(
+            backend
+                .create_task(LOCAL_ENVIRONMENT_ID, "Say done", "HEAD", false, 3)
+                .await
+                .is_err()
+        );
+
+        let root = codex_home.path().join(LOCAL_TASKS_SUBDIR);
+        assert_eq!(std::fs::read_dir(&root).unwrap().count(), 0);
+        let worktrees = std::process::Command::new("git")
+            .arg("-C")
+            .arg(repo.path())
+            .args(["worktree", "list", "--porcelain"])
+            .output()
+            .unwrap();
+        let worktrees = String::from_utf8_lossy(&worktrees.stdout);
+        assert_eq!(worktrees.matches("worktree ").count(), 1, "{worktrees}");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn create_task_runs_each_attempt_in_its_own_worktree() {
+        skip_if_no_network!();
+
+        let server = start_mock_server().await;
+        let body = sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-1"),
+        ]);
+        let responses = mount_sse_sequence(&server, vec![body.clone(), body]).await;
+
+        let repo = TempDir::new().unwrap();
+        init_repo(repo.path());
+
+        let codex_home = TempDir::new().unwrap();
+        let mut config = load_default_config_for_test(&codex_home).await;
+        config.cwd = repo.path().to_path_buf();
+        config.model_provider = ModelProviderInfo {
+            base_url: Some(format!("{}/v1", server.uri())),
+            ..built_in_model_providers()["openai"].clone()
+        };
+        let manager = ConversationManager::with_models_provider_and_home(
+            CodexAuth::from_api_key("Test API Key"),
+            config.model_provider.clone(),
+            config.codex_home.clone(),
+        );
+        let backend = LocalBackend::with_conversation_manager(config, manager);
+
+        let created = backend
+            .create_task(LOCAL_ENVIRONMENT_ID, "Say done", "HEAD", false, 2)
+            .await
+            .unwrap();
+        backend.wait_for_task(&created.id).await;
+
+        assert_eq!(responses.requests().len(), 2);
+        let summary = backend.get_task_summary(created.id.clone()).await.unwrap();
+        assert_eq!(summary.status, TaskStatus::Ready);
+        assert_eq!(summary.attempt_total, Some(2));
+        let text = backend.get_task_text(created.id.clone()).await.unwrap();
+        assert_eq!(text.messages, vec!["done".to_string()]);
+        assert_eq!(text.sibling_turn_ids, vec!["attempt-2".to_string()]);
+        assert_eq!(
+            backend.get_task_diff(created.id.clone()).await.unwrap(),
+            None
+        );
+
+        let dir = backend.task_dir(&created.id).unwrap();
+        for turn_id in ["attempt-1", "attempt-2"] {
+            assert!(!dir.join("worktrees").join(turn_id).exists(), "{turn_id}");
+            assert!(!dir.join(format!("{turn_id}.lock")).exists(), "{turn_id}");
+        }
+    }
+}
@@ -30,7 +30,8 @@ pub struct ExecCommand {
     #[arg(value_name = "QUERY")]
     pub query: Option<String>,
 
-    /// Target environment identifier (see `codex cloud` to browse).
+    /// Target environment identifier (see `codex cloud` to browse). Ignored when
+    /// `CODEX_CLOUD_TASKS_MODE=local` runs tasks on this machine.
     #[arg(long = "env", value_name = "ENV_ID")]
     pub environment: String,
 
@@ -10,6 +10,7 @@ pub use cli::Cli;
 use anyhow::anyhow;
 use chrono::Utc;
 use codex_cloud_tasks_client::TaskStatus;
+use codex_common::CliConfigOverrides;
 use codex_login::AuthManager;
 use owo_colors::OwoColorize;
 use owo_colors::Stream;
@@ -36,13 +37,17 @@ struct ApplyJob {
 struct BackendContext {
     backend: Arc<dyn codex_cloud_tasks_client::CloudBackend>,
     base_url: String,
+    /// Set when tasks run on this machine instead of Codex Cloud.
+    local: Option<codex_cloud_tasks_client::LocalBackend>,
 }
 
-async fn init_backend(user_agent_suffix: &str) -> anyhow::Result<BackendContext> {
-    let use_mock = matches!(
-        std::env::var("CODEX_CLOUD_TASKS_MODE").ok().as_deref(),
-        Some("mock") | Some("MOCK")
-    );
+async fn init_backend(
+    user_agent_suffix: &str,
+    config_overrides: &CliConfigOverrides,
+) -> anyhow::Result<BackendContext> {
+    let mode = std::env::var("CODEX_CLOUD_TASKS_MODE").ok();
+    let use_mock = matches!(mode.as_deref(), Some("mock") | Some("MOCK"));
+    let use_local = matches!(mode.as_deref(), Some("local") | Some("LOCAL"));
     let base_url = std::env::var("CODEX_CLOUD_TASKS_BASE_URL")
         .unwrap_or_else(|_| "https://chatgpt.com/backend-api".to_string());
 
@@ -52,6 +57,25 @@ async fn init_backend(user_agent_suffix: &str) -> anyhow::Result<BackendContext>
         return Ok(BackendContext {
             backend: Arc::new(codex_cloud_tasks_client::MockClient),
             base_url,
+            local: None,
+        });
+    }
+
+    if use_local {
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = codex_core::config::Config::load_with_cli_overrides(overrides).await?;
+        append_error_log(format!(
+            "startup: local backend codex_home={}",
+            config.codex_home.display()
+        ));
+        let auth_manager = AuthManager::shared_from_config(&config, true);
+        let local = codex_cloud_tasks_client::LocalBackend::new(config, auth_manager);
+        return Ok(BackendContext {
+            backend: Arc::new(local.clone()),
+            base_url,
+            local: Some(local),
         });
     }
 
@@ -101,6 +125,7 @@ async fn init_backend(user_agent_suffix: &str) -> anyhow::Result<BackendContext>
     Ok(BackendContext {
         backend: Arc::new(http),
         base_url,
+        local: None,
     })
 }
 
@@ -152,16 +177,22 @@ async fn resolve_git_ref_with_git_info(
     }
 }
 
-async fn run_exec_command(args: crate::cli::ExecCommand) -> anyhow::Result<()> {
+async fn run_exec_command(
+    args: crate::cli::ExecCommand,
+    config_overrides: &CliConfigOverrides,
+) -> anyhow::Result<()> {
     let crate::cli::ExecCommand {
         query,
         environment,
         branch,
         attempts,
     } = args;
-    let ctx = init_backend("codex_cloud_tasks_exec").await?;
+    let ctx = init_backend("codex_cloud_tasks_exec", config_overrides).await?;
     let prompt = resolve_query_input(query)?;
-    let env_id = resolve_environment_id(&ctx, &environment).await?;
+    let env_id = match ctx.local {
+        Some(_) => codex_cloud_tasks_client::LOCAL_ENVIRONMENT_ID.to_string(),
+        None => resolve_environment_id(&ctx, &environment).await?,
+    };
     let git_ref = resolve_git_ref(branch.as_ref()).await;
     let created = codex_cloud_tasks_client::CloudBackend::create_task(
         &*ctx.backend,
@@ -172,6 +203,18 @@ async fn run_exec_command(args: crate::cli::ExecCommand) -> anyhow::Result<()> {
         attempts,
     )
     .await?;
+    if let Some(local) = &ctx.local {
+        // Local attempts run inside this process, so stay alive until they finish.
+        local.wait_for_task(&created.id).await;
+        let summary =
+            codex_cloud_tasks_client::CloudBackend::get_task_summary(&*ctx.backend, created.id)
+                .await?;
+        let colorize = supports_color::on(SupportStream::Stdout).is_some();
+        for line in format_task_status_lines(&summary, Utc::now(), colorize) {
+            println!("{line}");
+        }
+        return Ok(());
+    }
     let url = util::task_url(&ctx.base_url, &created.id.0);
     println!("{url}");
     Ok(())
@@ -470,8 +513,11 @@ fn format_task_status_lines(
     lines
 }
 
-async fn run_status_command(args: crate::cli::StatusCommand) -> anyhow::Result<()> {
-    let ctx = init_backend("codex_cloud_tasks_status").await?;
+async fn run_status_command(
+    args: crate::cli::StatusCommand,
+    config_overrides: &CliConfigOverrides,
+) -> anyhow::Result<()> {
+    let ctx = init_backend("codex_cloud_tasks_status", config_overrides).await?;
     let task_id = parse_task_id(&args.task_id)?;
     let summary =
         codex_cloud_tasks_client::CloudBackend::get_task_summary(&*ctx.backend, task_id).await?;
@@ -486,8 +532,11 @@ async fn run_status_command(args: crate::cli::StatusCommand) -> anyhow::Result<(
     Ok(())
 }
 
-async fn run_diff_command(args: crate::cli::DiffCommand) -> anyhow::Result<()> {
-    let ctx = init_backend("codex_cloud_tasks_diff").await?;
+async fn run_diff_command(
+    args: crate::cli::DiffCommand,
+    config_overrides: &CliConfigOverrides,
+) -> anyhow::Result<()> {
+    let ctx = init_backend("codex_cloud_tasks_diff", config_overrides).await?;
     let task_id = parse_task_id(&args.task_id)?;
     let attempts = collect_attempt_diffs(&*ctx.backend, &task_id).await?;
     let selected = select_attempt(&attempts, args.attempt)?;
@@ -495,8 +544,11 @@ async fn run_diff_command(args: crate::cli::DiffCommand) -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn run_apply_command(args: crate::cli::ApplyCommand) -> anyhow::Result<()> {
-    let ctx = init_backend("codex_cloud_tasks_apply").await?;
+async fn run_apply_command(
+    args: crate::cli::ApplyCommand,
+    config_overrides: &CliConfigOverrides,
+) -> anyhow::Result<()> {
+    let ctx = init_backend("codex_cloud_tasks_apply", config_overrides).await?;
     let task_id = parse_task_id(&args.task_id)?;
     let attempts = collect_attempt_diffs(&*ctx.backend, &task_id).await?;
     let selected = select_attempt(&attempts, args.attempt)?;
@@ -641,16 +693,22 @@ fn spawn_apply(
 // (no standalone patch summarizer needed – UI displays raw diffs)
 
 /// Entry point for the `codex cloud` subcommand.
-pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()> {
-    if let Some(command) = cli.command {
+pub async fn run_main(
+    mut cli: Cli,
+    _codex_linux_sandbox_exe: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    if let Some(command) = cli.command.take() {
         return match command {
-            crate::cli::Command::Exec(args) => run_exec_command(args).await,
-            crate::cli::Command::Status(args) => run_status_command(args).await,
-            crate::cli::Command::Apply(args) => run_apply_command(args).await,
-            crate::cli::Command::Diff(args) => run_diff_command(args).await,
+            crate::cli::Command::Exec(args) => run_exec_command(args, &cli.config_overrides).await,
+            crate::cli::Command::Status(args) => {
+                run_status_command(args, &cli.config_overrides).await
+            }
+            crate::cli::Command::Apply(args) => {
+                run_apply_command(args, &cli.config_overrides).await
+            }
+            crate::cli::Command::Diff(args) => run_diff_command(args, &cli.config_overrides).await,
         };
     }
-    let Cli { .. } = cli;
 
     // Very minimal logging setup; mirrors other crates' pattern.
     let default_level = "error";
@@ -665,8 +723,10 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
         .try_init();
 
     info!("Launching Cloud Tasks list UI");
-    let BackendContext { backend, .. } = init_backend("codex_cloud_tasks_tui").await?;
+    let BackendContext { backend, local, .. } =
+        init_backend("codex_cloud_tasks_tui", &cli.config_overrides).await?;
     let backend = backend;
+    let is_local = local.is_some();
 
     // Terminal setup
     use crossterm::ExecutableCommand;
@@ -720,6 +780,17 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
     app.list_generation = app.list_generation.saturating_add(1);
     app.in_flight.clear();
     // reset any in-flight enrichment state
+    if is_local {
+        // Local tasks have a single implicit environment, so skip the cloud lookups.
+        let id = codex_cloud_tasks_client::LOCAL_ENVIRONMENT_ID.to_string();
+        app.environments = vec![app::EnvironmentRow {
+            id: id.clone(),
+            label: Some("Local".to_string()),
+            is_pinned: true,
+            repo_hints: None,
+        }];
+        app.env_filter = Some(id);
+    }
 
     // Event stream
     use crossterm::event::Event;
@@ -746,7 +817,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
         });
     }
     // Fetch environment list in parallel so the header can show friendly names quickly.
-    {
+    if !is_local {
         let tx = tx.clone();
         tokio::spawn(async move {
             let base_url = util::normalize_base_url(
@@ -761,7 +832,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
 
     // Try to auto-detect a likely environment id on startup and refresh if found.
     // Do this concurrently so the initial list shows quickly; on success we refetch with filter.
-    {
+    if !is_local {
         let tx = tx.clone();
         tokio::spawn(async move {
             let base_url = util::normalize_base_url(
@@ -2036,6 +2107,7 @@ mod tests {
     use codex_cloud_tasks_client::TaskId;
     use codex_cloud_tasks_client::TaskStatus;
     use codex_cloud_tasks_client::TaskSummary;
+    use codex_common::CliConfigOverrides;
     use codex_tui::ComposerAction;
     use codex_tui::ComposerInput;
     use crossterm::event::KeyCode;
@@ -25,7 +25,23 @@ pub async fn ensure_oss_provider_ready(
                 .map_err(|e| std::io::Error::other(format!("OSS setup failed: {e}")))?;
         }
         OLLAMA_OSS_PROVIDER_ID => {
-            codex_ollama::ensure_oss_ready(config)
+            // Look the provider up in the Config so that any overrides in
+            // config.toml are taken into account.
+            let base_url = config
+                .model_providers
+                .get(OLLAMA_OSS_PROVIDER_ID)
+                .and_then(|provider| provider.base_url.as_deref())
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("Built-in provider {OLLAMA_OSS_PROVIDER_ID} not found"),
+                    )
+                })?;
+            let model = config
+                .model
+                .as_deref()
+                .unwrap_or(codex_ollama::DEFAULT_OSS_MODEL);
+            codex_ollama::ensure_oss_ready(model, base_url)
                 .await
                 .map_err(|e| std::io::Error::other(format!("OSS setup failed: {e}")))?;
         }
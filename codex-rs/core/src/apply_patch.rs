@@ -1,6 +1,8 @@
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
+use crate::patch_review::ReviewedPatch;
+use crate::patch_review::apply_hunk_review;
 use crate::protocol::FileChange;
 use crate::protocol::ReviewDecision;
 use crate::safety::SafetyCheck;
@@ -31,6 +33,9 @@ pub(crate) enum InternalApplyPatchInvocation {
 pub(crate) struct ApplyPatchExec {
     pub(crate) action: ApplyPatchAction,
     pub(crate) user_explicitly_approved_this_action: bool,
+    /// Set when the user reviewed the patch hunk by hunk; describes what was
+    /// rejected or edited so it can be appended to the tool output.
+    pub(crate) hunk_review_summary: Option<String>,
 }
 
 pub(crate) async fn apply_patch(
@@ -51,6 +56,7 @@ pub(crate) async fn apply_patch(
         } => InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
             action,
             user_explicitly_approved_this_action: user_explicitly_approved,
+            hunk_review_summary: None,
         }),
         SafetyCheck::AskUser => {
             // Compute a readable summary of path changes to include in the
@@ -76,8 +82,34 @@ pub(crate) async fn apply_patch(
                     InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
                         action,
                         user_explicitly_approved_this_action: true,
+                        hunk_review_summary: None,
                     })
                 }
+                ReviewDecision::ApprovedWithHunkReview { hunks } => {
+                    match apply_hunk_review(&action, &hunks) {
+                        Ok(ReviewedPatch {
+                            action: Some(action),
+                            summary,
+                        }) => InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                            action,
+                            user_explicitly_approved_this_action: true,
+                            hunk_review_summary: Some(summary),
+                        }),
+                        Ok(ReviewedPatch {
+                            action: None,
+                            summary,
+                        }) => InternalApplyPatchInvocation::Output(Err(
+                            FunctionCallError::RespondToModel(format!(
+                                "patch rejected by user\n\n{summary}"
+                            )),
+                        )),
+                        Err(err) => InternalApplyPatchInvocation::Output(Err(
+                            FunctionCallError::RespondToModel(format!(
+                                "patch not applied: failed to apply the user's hunk review: {err}"
+                            )),
+                        )),
+                    }
+                }
                 ReviewDecision::Denied | ReviewDecision::Abort => {
                     InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
                         "patch rejected by user".to_string(),
@@ -98,7 +130,7 @@ pub(crate) fn convert_apply_patch_to_protocol(
     let mut result = HashMap::with_capacity(changes.len());
     for (path, change) in changes {
         let protocol_change = match change {
-            ApplyPatchFileChange::Add { content } => FileChange::Add {
+            ApplyPatchFileChange::Add { content, .. } => FileChange::Add {
                 content: content.clone(),
             },
             ApplyPatchFileChange::Delete { content } => FileChange::Delete {
@@ -107,11 +139,25 @@ pub(crate) fn convert_apply_patch_to_protocol(
             ApplyPatchFileChange::Update {
                 unified_diff,
                 move_path,
-                new_content: _new_content,
+                ..
             } => FileChange::Update {
                 unified_diff: unified_diff.clone(),
                 move_path: move_path.clone(),
             },
+            ApplyPatchFileChange::Binary {
+                old_size,
+                new_content,
+            } => FileChange::Binary {
+                old_size: *old_size,
+                new_size: new_content.len() as u64,
+            },
+            ApplyPatchFileChange::Symlink { target } => FileChange::Symlink {
+                target: target.clone(),
+            },
+            ApplyPatchFileChange::Mode { old_mode, new_mode } => FileChange::Mode {
+                old_mode: *old_mode,
+                new_mode: *new_mode,
+            },
         };
         result.insert(path.clone(), protocol_change);
     }
@@ -1,3 +1,4 @@
+mod accounts;
 mod storage;
 
 use chrono::Utc;
@@ -18,9 +19,19 @@ use std::time::Duration;
 use codex_app_server_protocol::AuthMode;
 use codex_protocol::config_types::ForcedLoginMethod;
 
+pub use crate::auth::accounts::AccountsDotJson;
+pub use crate::auth::accounts::DEFAULT_ACCOUNT_NAME;
+use crate::auth::accounts::activate_account;
+pub use crate::auth::accounts::active_account;
+use crate::auth::accounts::forget_account;
+pub use crate::auth::accounts::load_accounts;
+pub use crate::auth::accounts::parse_account_name;
+pub use crate::auth::accounts::resolve_account;
+pub use crate::auth::accounts::set_active_account;
 pub use crate::auth::storage::AuthCredentialsStoreMode;
 pub use crate::auth::storage::AuthDotJson;
 use crate::auth::storage::AuthStorageBackend;
+use crate::auth::storage::create_account_auth_storage;
 use crate::auth::storage::create_auth_storage;
 use crate::config::Config;
 use crate::error::RefreshTokenFailedError;
@@ -134,12 +145,23 @@ impl CodexAuth {
         Ok(access)
     }
 
-    /// Loads the available auth information from auth storage.
+    /// Loads the available auth information of the active account from auth storage.
     pub fn from_auth_storage(
         codex_home: &Path,
         auth_credentials_store_mode: AuthCredentialsStoreMode,
     ) -> std::io::Result<Option<CodexAuth>> {
-        load_auth(codex_home, false, auth_credentials_store_mode)
+        let account = active_account(codex_home);
+        Self::from_account_storage(codex_home, account.as_deref(), auth_credentials_store_mode)
+    }
+
+    /// Loads the stored auth information of `account` (`None` is the default
+    /// account).
+    pub fn from_account_storage(
+        codex_home: &Path,
+        account: Option<&str>,
+        auth_credentials_store_mode: AuthCredentialsStoreMode,
+    ) -> std::io::Result<Option<CodexAuth>> {
+        load_auth(codex_home, account, false, auth_credentials_store_mode)
     }
 
     pub async fn get_token_data(&self) -> Result<TokenData, std::io::Error> {
@@ -312,8 +334,18 @@ pub fn read_gemini_api_key_from_auth_json(
     codex_home: &Path,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
 ) -> Option<String> {
-    let storage = create_auth_storage(codex_home.to_path_buf(), auth_credentials_store_mode);
-    let auth = storage.load().ok().flatten()?;
+    read_account_gemini_api_key(codex_home, None, auth_credentials_store_mode)
+}
+
+/// Read the Gemini API key stored for `account`. Returns None if not found.
+pub fn read_account_gemini_api_key(
+    codex_home: &Path,
+    account: Option<&str>,
+    auth_credentials_store_mode: AuthCredentialsStoreMode,
+) -> Option<String> {
+    let auth = load_account_auth_dot_json(codex_home, account, auth_credentials_store_mode)
+        .ok()
+        .flatten()?;
 
     // Prefer a dedicated Gemini key when present, but fall back to the
     // OpenAI API key stored in auth.json. This matches the documented
@@ -337,8 +369,26 @@ pub fn logout(
     codex_home: &Path,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
 ) -> std::io::Result<bool> {
-    let storage = create_auth_storage(codex_home.to_path_buf(), auth_credentials_store_mode);
-    storage.delete()
+    logout_account(codex_home, None, auth_credentials_store_mode)
+}
+
+/// Delete the stored credentials of `account` (`None` is the default
+/// account). A named account is also removed from the account registry.
+pub fn logout_account(
+    codex_home: &Path,
+    account: Option<&str>,
+    auth_credentials_store_mode: AuthCredentialsStoreMode,
+) -> std::io::Result<bool> {
+    let storage = create_account_auth_storage(
+        codex_home.to_path_buf(),
+        account.map(str::to_string),
+        auth_credentials_store_mode,
+    );
+    let removed = storage.delete()?;
+    if let Some(name) = account {
+        forget_account(codex_home, name)?;
+    }
+    Ok(removed)
 }
 
 /// Writes an `auth.json` that contains only the API key.
@@ -346,6 +396,16 @@ pub fn login_with_api_key(
     codex_home: &Path,
     api_key: &str,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
+) -> std::io::Result<()> {
+    login_account_with_api_key(codex_home, None, api_key, auth_credentials_store_mode)
+}
+
+/// Stores an API key as the credentials of `account` and makes it active.
+pub fn login_account_with_api_key(
+    codex_home: &Path,
+    account: Option<&str>,
+    api_key: &str,
+    auth_credentials_store_mode: AuthCredentialsStoreMode,
 ) -> std::io::Result<()> {
     let auth_dot_json = AuthDotJson {
         openai_api_key: Some(api_key.to_string()),
@@ -353,7 +413,12 @@ pub fn login_with_api_key(
         tokens: None,
         last_refresh: None,
     };
-    save_auth(codex_home, &auth_dot_json, auth_credentials_store_mode)
+    save_account_auth(
+        codex_home,
+        account,
+        &auth_dot_json,
+        auth_credentials_store_mode,
+    )
 }
 
 /// Persist the provided auth payload using the specified backend.
@@ -362,8 +427,38 @@ pub fn save_auth(
     auth: &AuthDotJson,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
 ) -> std::io::Result<()> {
-    let storage = create_auth_storage(codex_home.to_path_buf(), auth_credentials_store_mode);
-    storage.save(auth)
+    save_account_auth(codex_home, None, auth, auth_credentials_store_mode)
+}
+
+/// Persist `auth` as the credentials of `account` (`None` is the default
+/// account) and make that account active, so a fresh login is used right away.
+pub fn save_account_auth(
+    codex_home: &Path,
+    account: Option<&str>,
+    auth: &AuthDotJson,
+    auth_credentials_store_mode: AuthCredentialsStoreMode,
+) -> std::io::Result<()> {
+    let storage = create_account_auth_storage(
+        codex_home.to_path_buf(),
+        account.map(str::to_string),
+        auth_credentials_store_mode,
+    );
+    storage.save(auth)?;
+    activate_account(codex_home, account)
+}
+
+/// Load the stored credentials of `account` without refreshing them.
+pub fn load_account_auth_dot_json(
+    codex_home: &Path,
+    account: Option<&str>,
+    auth_credentials_store_mode: AuthCredentialsStoreMode,
+) -> std::io::Result<Option<AuthDotJson>> {
+    let storage = create_account_auth_storage(
+        codex_home.to_path_buf(),
+        account.map(str::to_string),
+        auth_credentials_store_mode,
+    );
+    storage.load()
 }
 
 /// Load CLI auth data using the configured credential store backend.
@@ -380,8 +475,11 @@ pub fn load_auth_dot_json(
 }
 
 pub async fn enforce_login_restrictions(config: &Config) -> std::io::Result<()> {
+    let account = resolve_account(&config.codex_home, config.account.as_deref());
+    let account = account.as_deref();
     let Some(auth) = load_auth(
         &config.codex_home,
+        account,
         true,
         config.cli_auth_credentials_store_mode,
     )?
@@ -406,6 +504,7 @@ pub async fn enforce_login_restrictions(config: &Config) -> std::io::Result<()>
         if let Some(message) = method_violation {
             return logout_with_message(
                 &config.codex_home,
+                account,
                 message,
                 config.cli_auth_credentials_store_mode,
             );
@@ -422,6 +521,7 @@ pub async fn enforce_login_restrictions(config: &Config) -> std::io::Result<()>
             Err(err) => {
                 return logout_with_message(
                     &config.codex_home,
+                    account,
                     format!(
                         "Failed to load ChatGPT credentials while enforcing workspace restrictions: {err}. Logging out."
                     ),
@@ -443,6 +543,7 @@ pub async fn enforce_login_restrictions(config: &Config) -> std::io::Result<()>
             };
             return logout_with_message(
                 &config.codex_home,
+                account,
                 message,
                 config.cli_auth_credentials_store_mode,
             );
@@ -454,10 +555,11 @@ pub async fn enforce_login_restrictions(config: &Config) -> std::io::Result<()>
 
 fn logout_with_message(
     codex_home: &Path,
+    account: Option<&str>,
     message: String,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
 ) -> std::io::Result<()> {
-    match logout(codex_home, auth_credentials_store_mode) {
+    match logout_account(codex_home, account, auth_credentials_store_mode) {
         Ok(_) => Err(std::io::Error::other(message)),
         Err(err) => Err(std::io::Error::other(format!(
             "{message}. Failed to remove auth.json: {err}"
@@ -467,6 +569,7 @@ fn logout_with_message(
 
 fn load_auth(
     codex_home: &Path,
+    account: Option<&str>,
     enable_codex_api_key_env: bool,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
 ) -> std::io::Result<Option<CodexAuth>> {
@@ -478,7 +581,11 @@ fn load_auth(
         )));
     }
 
-    let storage = create_auth_storage(codex_home.to_path_buf(), auth_credentials_store_mode);
+    let storage = create_account_auth_storage(
+        codex_home.to_path_buf(),
+        account.map(str::to_string),
+        auth_credentials_store_mode,
+    );
 
     let client = crate::default_client::create_client();
     let auth_dot_json = match storage.load()? {
@@ -663,6 +770,8 @@ use std::sync::RwLock;
 /// Internal cached auth state.
 #[derive(Clone, Debug)]
 struct CachedAuth {
+    /// Account the cached auth was loaded from; `None` is the default account.
+    account: Option<String>,
     auth: Option<CodexAuth>,
 }
 
@@ -809,9 +918,14 @@ mod tests {
             auth_dot_json,
             storage: _,
             ..
-        } = super::load_auth(codex_home.path(), false, AuthCredentialsStoreMode::File)
-            .unwrap()
-            .unwrap();
+        } = super::load_auth(
+            codex_home.path(),
+            None,
+            false,
+            AuthCredentialsStoreMode::File,
+        )
+        .unwrap()
+        .unwrap();
         assert_eq!(None, api_key);
         assert_eq!(AuthMode::ChatGPT, mode);
 
@@ -853,7 +967,7 @@ mod tests {
         )
         .unwrap();
 
-        let auth = super::load_auth(dir.path(), false, AuthCredentialsStoreMode::File)
+        let auth = super::load_auth(dir.path(), None, false, AuthCredentialsStoreMode::File)
             .unwrap()
             .unwrap();
         assert_eq!(auth.mode, AuthMode::ApiKey);
@@ -1105,9 +1219,14 @@ mod tests {
         )
         .expect("failed to write auth file");
 
-        let auth = super::load_auth(codex_home.path(), false, AuthCredentialsStoreMode::File)
-            .expect("load auth")
-            .expect("auth available");
+        let auth = super::load_auth(
+            codex_home.path(),
+            None,
+            false,
+            AuthCredentialsStoreMode::File,
+        )
+        .expect("load auth")
+        .expect("auth available");
 
         pretty_assertions::assert_eq!(auth.account_plan_type(), Some(AccountPlanType::Pro));
     }
@@ -1125,12 +1244,39 @@ mod tests {
         )
         .expect("failed to write auth file");
 
-        let auth = super::load_auth(codex_home.path(), false, AuthCredentialsStoreMode::File)
-            .expect("load auth")
-            .expect("auth available");
+        let auth = super::load_auth(
+            codex_home.path(),
+            None,
+            false,
+            AuthCredentialsStoreMode::File,
+        )
+        .expect("load auth")
+        .expect("auth available");
 
         pretty_assertions::assert_eq!(auth.account_plan_type(), Some(AccountPlanType::Unknown));
     }
+
+    #[test]
+    fn switch_account_keeps_the_active_account_when_credentials_fail_to_load() {
+        let codex_home = tempdir().unwrap();
+        std::fs::write(
+            codex_home.path().join("accounts.json"),
+            r#"{"accounts": ["work"]}"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(codex_home.path().join("accounts")).unwrap();
+        std::fs::write(codex_home.path().join("accounts/work.json"), "not json").unwrap();
+        let manager = AuthManager::new(
+            codex_home.path().to_path_buf(),
+            false,
+            AuthCredentialsStoreMode::File,
+        );
+
+        assert!(manager.switch_account(Some("work")).is_err());
+
+        assert_eq!(manager.account(), None);
+        assert_eq!(active_account(codex_home.path()), None);
+    }
 }
 
 /// Central manager providing a single source of truth for auth.json derived
@@ -1158,9 +1304,25 @@ impl AuthManager {
         codex_home: PathBuf,
         enable_codex_api_key_env: bool,
         auth_credentials_store_mode: AuthCredentialsStoreMode,
+    ) -> Self {
+        let account = active_account(&codex_home);
+        Self::new_for_account(
+            codex_home,
+            account,
+            enable_codex_api_key_env,
+            auth_credentials_store_mode,
+        )
+    }
+
+    fn new_for_account(
+        codex_home: PathBuf,
+        account: Option<String>,
+        enable_codex_api_key_env: bool,
+        auth_credentials_store_mode: AuthCredentialsStoreMode,
     ) -> Self {
         let auth = load_auth(
             &codex_home,
+            account.as_deref(),
             enable_codex_api_key_env,
             auth_credentials_store_mode,
         )
@@ -1168,7 +1330,7 @@ impl AuthManager {
         .flatten();
         Self {
             codex_home,
-            inner: RwLock::new(CachedAuth { auth }),
+            inner: RwLock::new(CachedAuth { account, auth }),
             enable_codex_api_key_env,
             auth_credentials_store_mode,
         }
@@ -1178,7 +1340,10 @@ impl AuthManager {
     #[expect(clippy::expect_used)]
     /// Create an AuthManager with a specific CodexAuth, for testing only.
     pub fn from_auth_for_testing(auth: CodexAuth) -> Arc<Self> {
-        let cached = CachedAuth { auth: Some(auth) };
+        let cached = CachedAuth {
+            account: None,
+            auth: Some(auth),
+        };
         let temp_dir = tempfile::tempdir().expect("temp codex home");
         let codex_home = temp_dir.path().to_path_buf();
         TEST_AUTH_TEMP_DIRS
@@ -1196,7 +1361,10 @@ impl AuthManager {
     #[cfg(any(test, feature = "test-support"))]
     /// Create an AuthManager with a specific CodexAuth and codex home, for testing only.
     pub fn from_auth_for_testing_with_home(auth: CodexAuth, codex_home: PathBuf) -> Arc<Self> {
-        let cached = CachedAuth { auth: Some(auth) };
+        let cached = CachedAuth {
+            account: None,
+            auth: Some(auth),
+        };
         Arc::new(Self {
             codex_home,
             inner: RwLock::new(cached),
@@ -1214,11 +1382,39 @@ impl AuthManager {
         &self.codex_home
     }
 
+    /// Name of the account auth is loaded from; `None` is the default account.
+    pub fn account(&self) -> Option<String> {
+        self.inner.read().ok().and_then(|c| c.account.clone())
+    }
+
+    /// Switch to the stored credentials of `account` (`None` is the default
+    /// account) and persist it as the active account for future sessions.
+    pub fn switch_account(&self, account: Option<&str>) -> std::io::Result<()> {
+        let auth = load_auth(
+            &self.codex_home,
+            account,
+            self.enable_codex_api_key_env,
+            self.auth_credentials_store_mode,
+        )?;
+        set_active_account(&self.codex_home, account)?;
+        let mut guard = self
+            .inner
+            .write()
+            .map_err(|_| std::io::Error::other("auth cache poisoned"))?;
+        *guard = CachedAuth {
+            account: account.map(str::to_string),
+            auth,
+        };
+        Ok(())
+    }
+
     /// Force a reload of the auth information from auth.json. Returns
     /// whether the auth value changed.
     pub fn reload(&self) -> bool {
+        let account = self.account();
         let new_auth = load_auth(
             &self.codex_home,
+            account.as_deref(),
             self.enable_codex_api_key_env,
             self.auth_credentials_store_mode,
         )
@@ -1254,6 +1450,17 @@ impl AuthManager {
         ))
     }
 
+    /// Like [`AuthManager::shared`], but starts on the account selected by
+    /// `config` (the profile's `account`), falling back to the active account.
+    pub fn shared_from_config(config: &Config, enable_codex_api_key_env: bool) -> Arc<Self> {
+        Arc::new(Self::new_for_account(
+            config.codex_home.clone(),
+            resolve_account(&config.codex_home, config.account.as_deref()),
+            enable_codex_api_key_env,
+            config.cli_auth_credentials_store_mode,
+        ))
+    }
+
     /// Attempt to refresh the current auth token (if any). On success, reload
     /// the auth state from disk so other components observe refreshed token.
     /// If the token refresh fails in a permanent (non‑transient) way, logs out
@@ -1281,7 +1488,12 @@ impl AuthManager {
     /// reloads the in‑memory auth cache so callers immediately observe the
     /// unauthenticated state.
     pub fn logout(&self) -> std::io::Result<bool> {
-        let removed = super::auth::logout(&self.codex_home, self.auth_credentials_store_mode)?;
+        let account = self.account();
+        let removed = logout_account(
+            &self.codex_home,
+            account.as_deref(),
+            self.auth_credentials_store_mode,
+        )?;
         // Always reload to clear any cached auth (even if file absent).
         self.reload();
         Ok(removed)
@@ -0,0 +1,182 @@
+//! Registry of named accounts stored next to the default `auth.json`.
+//!
+//! Credentials for each account live in the configured
+//! [`AuthCredentialsStoreMode`](super::AuthCredentialsStoreMode) backend; this
+//! file only records which names exist and which one is active so that
+//! `codex account list` works even when the credentials are in the keyring.
+
+use std::collections::BTreeSet;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Name that selects the unnamed account stored in `auth.json`.
+pub const DEFAULT_ACCOUNT_NAME: &str = "default";
+
+/// Expected structure for $CODEX_HOME/accounts.json.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct AccountsDotJson {
+    /// Account used when neither the profile nor the caller picks one.
+    /// `None` selects the default account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+
+    #[serde(default)]
+    pub accounts: BTreeSet<String>,
+}
+
+fn get_accounts_file(codex_home: &Path) -> PathBuf {
+    codex_home.join("accounts.json")
+}
+
+/// Maps a user-supplied account name to the storage slot, where `None` is the
+/// default account. Names may only use ASCII letters, digits, `-`, `_` and `.`
+/// because they become file names and keyring keys.
+pub fn parse_account_name(name: &str) -> std::io::Result<Option<String>> {
+    let name = name.trim();
+    if name == DEFAULT_ACCOUNT_NAME {
+        return Ok(None);
+    }
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(Some(name.to_string()))
+    } else {
+        Err(std::io::Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "invalid account name `{name}`: use letters, digits, `-`, `_` or `.`, not starting with `.`"
+            ),
+        ))
+    }
+}
+
+pub fn load_accounts(codex_home: &Path) -> std::io::Result<AccountsDotJson> {
+    match std::fs::read_to_string(get_accounts_file(codex_home)) {
+        Ok(contents) => Ok(serde_json::from_str(&contents)?),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(AccountsDotJson::default()),
+        Err(err) => Err(err),
+    }
+}
+
+fn save_accounts(codex_home: &Path, accounts: &AccountsDotJson) -> std::io::Result<()> {
+    std::fs::create_dir_all(codex_home)?;
+    let json = serde_json::to_string_pretty(accounts)?;
+    std::fs::write(get_accounts_file(codex_home), json)
+}
+
+/// The persisted active account, or `None` for the default account.
+pub fn active_account(codex_home: &Path) -> Option<String> {
+    load_accounts(codex_home)
+        .ok()
+        .and_then(|accounts| accounts.active)
+}
+
+/// Resolves the account to use: an explicitly configured one (for example a
+/// profile's `account`) wins over the persisted active account.
+pub fn resolve_account(codex_home: &Path, configured: Option<&str>) -> Option<String> {
+    match configured {
+        Some(name) => parse_account_name(name).ok().flatten(),
+        None => active_account(codex_home),
+    }
+}
+
+/// Records `account` as known and makes it the active account. Selecting the
+/// default account before any named account exists leaves no registry behind.
+pub(super) fn activate_account(codex_home: &Path, account: Option<&str>) -> std::io::Result<()> {
+    let path = get_accounts_file(codex_home);
+    if account.is_none() && !path.exists() {
+        return Ok(());
+    }
+    let mut accounts = load_accounts(codex_home)?;
+    if let Some(name) = account {
+        accounts.accounts.insert(name.to_string());
+    }
+    accounts.active = account.map(str::to_string);
+    save_accounts(codex_home, &accounts)
+}
+
+/// Makes an already registered `account` active.
+pub fn set_active_account(codex_home: &Path, account: Option<&str>) -> std::io::Result<()> {
+    let mut accounts = load_accounts(codex_home)?;
+    if let Some(name) = account
+        && !accounts.accounts.contains(name)
+    {
+        return Err(std::io::Error::new(
+            ErrorKind::NotFound,
+            format!("no account named `{name}`; log in with `codex login --name {name}`"),
+        ));
+    }
+    accounts.active = account.map(str::to_string);
+    save_accounts(codex_home, &accounts)
+}
+
+/// Forgets `account`. If it was active, the default account becomes active.
+pub(super) fn forget_account(codex_home: &Path, account: &str) -> std::io::Result<()> {
+    let mut accounts = load_accounts(codex_home)?;
+    if !accounts.accounts.remove(account) {
+        return Ok(());
+    }
+    if accounts.active.as_deref() == Some(account) {
+        accounts.active = None;
+    }
+    save_accounts(codex_home, &accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn parse_account_name_maps_default_and_rejects_paths() {
+        assert_eq!(parse_account_name("default").unwrap(), None);
+        assert_eq!(
+            parse_account_name("work-2").unwrap(),
+            Some("work-2".to_string())
+        );
+        assert!(parse_account_name("../work").is_err());
+        assert!(parse_account_name("").is_err());
+        assert!(parse_account_name(".hidden").is_err());
+    }
+
+    #[test]
+    fn activating_and_forgetting_accounts_updates_the_registry() -> std::io::Result<()> {
+        let codex_home = tempdir()?;
+
+        activate_account(codex_home.path(), None)?;
+        assert!(!get_accounts_file(codex_home.path()).exists());
+
+        activate_account(codex_home.path(), Some("work"))?;
+        activate_account(codex_home.path(), Some("personal"))?;
+        assert_eq!(
+            active_account(codex_home.path()),
+            Some("personal".to_string())
+        );
+
+        set_active_account(codex_home.path(), Some("work"))?;
+        assert_eq!(
+            resolve_account(codex_home.path(), None),
+            Some("work".to_string())
+        );
+        assert_eq!(resolve_account(codex_home.path(), Some("default")), None);
+        assert!(set_active_account(codex_home.path(), Some("missing")).is_err());
+
+        forget_account(codex_home.path(), "work")?;
+        assert_eq!(
+            load_accounts(codex_home.path())?,
+            AccountsDotJson {
+                active: None,
+                accounts: BTreeSet::from(["personal".to_string()]),
+            }
+        );
+        Ok(())
+    }
+}
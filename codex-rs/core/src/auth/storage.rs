@@ -19,6 +19,9 @@ use tracing::warn;
 use crate::token_data::TokenData;
 use codex_keyring_store::DefaultKeyringStore;
 use codex_keyring_store::KeyringStore;
+use codex_keyring_store::encrypted_file;
+use codex_keyring_store::encrypted_file::FileEncryption;
+use codex_keyring_store::encrypted_file::FileEncryptionKey;
 
 /// Determine where Codex should store CLI auth credentials.
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -29,8 +32,12 @@ pub enum AuthCredentialsStoreMode {
     File,
     /// Persist credentials in the keyring. Fail if unavailable.
     Keyring,
-    /// Use keyring when available; otherwise, fall back to a file in CODEX_HOME.
+    /// Use keyring when available; otherwise, fall back to a file in CODEX_HOME
+    /// that is encrypted when `CODEX_AUTH_KEY` or `CODEX_AUTH_KEY_FILE` is set.
     Auto,
+    /// Persist credentials in CODEX_HOME/auth.json, encrypted with the key from
+    /// `CODEX_AUTH_KEY` or `CODEX_AUTH_KEY_FILE`. Fail if no key is configured.
+    Encrypted,
 }
 
 /// Expected structure for $CODEX_HOME/auth.json.
@@ -57,8 +64,17 @@ pub(super) fn get_auth_file(codex_home: &Path) -> PathBuf {
     codex_home.join("auth.json")
 }
 
-pub(super) fn delete_file_if_exists(codex_home: &Path) -> std::io::Result<bool> {
-    let auth_file = get_auth_file(codex_home);
+/// File holding the credentials of `account`; the unnamed default account
+/// keeps using `auth.json`.
+pub(super) fn get_account_auth_file(codex_home: &Path, account: Option<&str>) -> PathBuf {
+    match account {
+        Some(name) => codex_home.join("accounts").join(format!("{name}.json")),
+        None => get_auth_file(codex_home),
+    }
+}
+
+fn delete_file_if_exists(codex_home: &Path, account: Option<&str>) -> std::io::Result<bool> {
+    let auth_file = get_account_auth_file(codex_home, account);
     match std::fs::remove_file(&auth_file) {
         Ok(()) => Ok(true),
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
@@ -75,43 +91,99 @@ pub(super) trait AuthStorageBackend: Debug + Send + Sync {
 #[derive(Clone, Debug)]
 pub(super) struct FileAuthStorage {
     codex_home: PathBuf,
+    account: Option<String>,
+    encryption: FileEncryption,
+    /// Overrides the key read from the environment; used by tests.
+    encryption_key: Option<FileEncryptionKey>,
 }
 
 impl FileAuthStorage {
     pub(super) fn new(codex_home: PathBuf) -> Self {
-        Self { codex_home }
+        Self {
+            codex_home,
+            account: None,
+            encryption: FileEncryption::Plaintext,
+            encryption_key: None,
+        }
+    }
+
+    /// Store credentials for the named `account` instead of the default one.
+    pub(super) fn with_account(mut self, account: Option<String>) -> Self {
+        self.account = account;
+        self
+    }
+
+    /// Controls whether saved files are encrypted. Encrypted files are read
+    /// regardless of this setting as long as a key is configured.
+    pub(super) fn with_encryption(mut self, encryption: FileEncryption) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
+    fn encryption_key(&self) -> std::io::Result<Option<FileEncryptionKey>> {
+        match &self.encryption_key {
+            Some(key) => Ok(Some(key.clone())),
+            None => FileEncryptionKey::from_env(),
+        }
     }
 
     /// Attempt to read and refresh the `auth.json` file in the given `CODEX_HOME` directory.
     /// Returns the full AuthDotJson structure after refreshing if necessary.
     pub(super) fn try_read_auth_json(&self, auth_file: &Path) -> std::io::Result<AuthDotJson> {
+        self.read_auth_json(auth_file).map(|(auth, _)| auth)
+    }
+
+    /// Reads `auth_file`, decrypting it if needed. Also reports whether the
+    /// file was stored in plaintext.
+    fn read_auth_json(&self, auth_file: &Path) -> std::io::Result<(AuthDotJson, bool)> {
         let mut file = File::open(auth_file)?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
+        let key = if encrypted_file::is_encrypted(&contents) {
+            self.encryption_key()?
+        } else {
+            None
+        };
+        let (contents, was_encrypted) = encrypted_file::open(key.as_ref(), contents, auth_file)?;
         let auth_dot_json: AuthDotJson = serde_json::from_str(&contents)?;
 
-        Ok(auth_dot_json)
+        Ok((auth_dot_json, !was_encrypted))
     }
 }
 
 impl AuthStorageBackend for FileAuthStorage {
     fn load(&self) -> std::io::Result<Option<AuthDotJson>> {
-        let auth_file = get_auth_file(&self.codex_home);
-        let auth_dot_json = match self.try_read_auth_json(&auth_file) {
+        let auth_file = get_account_auth_file(&self.codex_home, self.account.as_deref());
+        let (auth_dot_json, is_plaintext) = match self.read_auth_json(&auth_file) {
             Ok(auth) => auth,
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
             Err(err) => return Err(err),
         };
+        let should_encrypt = match self.encryption {
+            FileEncryption::Plaintext => false,
+            FileEncryption::WhenKeyAvailable => self.encryption_key()?.is_some(),
+            FileEncryption::Required => true,
+        };
+        if is_plaintext && should_encrypt {
+            // Migrate credentials written before encryption was enabled.
+            self.save(&auth_dot_json)?;
+        }
         Ok(Some(auth_dot_json))
     }
 
     fn save(&self, auth_dot_json: &AuthDotJson) -> std::io::Result<()> {
-        let auth_file = get_auth_file(&self.codex_home);
+        let auth_file = get_account_auth_file(&self.codex_home, self.account.as_deref());
 
         if let Some(parent) = auth_file.parent() {
             std::fs::create_dir_all(parent)?;
         }
         let json_data = serde_json::to_string_pretty(auth_dot_json)?;
+        let key = match self.encryption {
+            FileEncryption::Plaintext => None,
+            FileEncryption::WhenKeyAvailable | FileEncryption::Required => self.encryption_key()?,
+        };
+        let json_data =
+            encrypted_file::seal(self.encryption, key.as_ref(), &json_data, &auth_file)?;
         let mut options = OpenOptions::new();
         options.truncate(true).write(true).create(true);
         #[cfg(unix)]
@@ -125,7 +197,7 @@ impl AuthStorageBackend for FileAuthStorage {
     }
 
     fn delete(&self) -> std::io::Result<bool> {
-        delete_file_if_exists(&self.codex_home)
+        delete_file_if_exists(&self.codex_home, self.account.as_deref())
     }
 }
 
@@ -145,9 +217,18 @@ fn compute_store_key(codex_home: &Path) -> std::io::Result<String> {
     Ok(format!("cli|{truncated}"))
 }
 
+fn compute_account_store_key(codex_home: &Path, account: Option<&str>) -> std::io::Result<String> {
+    let key = compute_store_key(codex_home)?;
+    Ok(match account {
+        Some(name) => format!("{key}|{name}"),
+        None => key,
+    })
+}
+
 #[derive(Clone, Debug)]
 struct KeyringAuthStorage {
     codex_home: PathBuf,
+    account: Option<String>,
     keyring_store: Arc<dyn KeyringStore>,
 }
 
@@ -155,10 +236,20 @@ impl KeyringAuthStorage {
     fn new(codex_home: PathBuf, keyring_store: Arc<dyn KeyringStore>) -> Self {
         Self {
             codex_home,
+            account: None,
             keyring_store,
         }
     }
 
+    fn with_account(mut self, account: Option<String>) -> Self {
+        self.account = account;
+        self
+    }
+
+    fn store_key(&self) -> std::io::Result<String> {
+        compute_account_store_key(&self.codex_home, self.account.as_deref())
+    }
+
     fn load_from_keyring(&self, key: &str) -> std::io::Result<Option<AuthDotJson>> {
         match self.keyring_store.load(KEYRING_SERVICE, key) {
             Ok(Some(serialized)) => serde_json::from_str(&serialized).map(Some).map_err(|err| {
@@ -191,30 +282,30 @@ impl KeyringAuthStorage {
 
 impl AuthStorageBackend for KeyringAuthStorage {
     fn load(&self) -> std::io::Result<Option<AuthDotJson>> {
-        let key = compute_store_key(&self.codex_home)?;
+        let key = self.store_key()?;
         self.load_from_keyring(&key)
     }
 
     fn save(&self, auth: &AuthDotJson) -> std::io::Result<()> {
-        let key = compute_store_key(&self.codex_home)?;
+        let key = self.store_key()?;
         // Simpler error mapping per style: prefer method reference over closure
         let serialized = serde_json::to_string(auth).map_err(std::io::Error::other)?;
         self.save_to_keyring(&key, &serialized)?;
-        if let Err(err) = delete_file_if_exists(&self.codex_home) {
+        if let Err(err) = delete_file_if_exists(&self.codex_home, self.account.as_deref()) {
             warn!("failed to remove CLI auth fallback file: {err}");
         }
         Ok(())
     }
 
     fn delete(&self) -> std::io::Result<bool> {
-        let key = compute_store_key(&self.codex_home)?;
+        let key = self.store_key()?;
         let keyring_removed = self
             .keyring_store
             .delete(KEYRING_SERVICE, &key)
             .map_err(|err| {
                 std::io::Error::other(format!("failed to delete auth from keyring: {err}"))
             })?;
-        let file_removed = delete_file_if_exists(&self.codex_home)?;
+        let file_removed = delete_file_if_exists(&self.codex_home, self.account.as_deref())?;
         Ok(keyring_removed || file_removed)
     }
 }
@@ -227,9 +318,24 @@ struct AutoAuthStorage {
 
 impl AutoAuthStorage {
     fn new(codex_home: PathBuf, keyring_store: Arc<dyn KeyringStore>) -> Self {
+        Self::for_account(codex_home, keyring_store, None)
+    }
+
+    fn for_account(
+        codex_home: PathBuf,
+        keyring_store: Arc<dyn KeyringStore>,
+        account: Option<String>,
+    ) -> Self {
         Self {
-            keyring_storage: Arc::new(KeyringAuthStorage::new(codex_home.clone(), keyring_store)),
-            file_storage: Arc::new(FileAuthStorage::new(codex_home)),
+            keyring_storage: Arc::new(
+                KeyringAuthStorage::new(codex_home.clone(), keyring_store)
+                    .with_account(account.clone()),
+            ),
+            file_storage: Arc::new(
+                FileAuthStorage::new(codex_home)
+                    .with_account(account)
+                    .with_encryption(FileEncryption::WhenKeyAvailable),
+            ),
         }
     }
 }
@@ -265,22 +371,44 @@ impl AuthStorageBackend for AutoAuthStorage {
 pub(super) fn create_auth_storage(
     codex_home: PathBuf,
     mode: AuthCredentialsStoreMode,
+) -> Arc<dyn AuthStorageBackend> {
+    create_account_auth_storage(codex_home, None, mode)
+}
+
+/// Like [`create_auth_storage`], but for the named `account` (`None` selects
+/// the default account).
+pub(super) fn create_account_auth_storage(
+    codex_home: PathBuf,
+    account: Option<String>,
+    mode: AuthCredentialsStoreMode,
 ) -> Arc<dyn AuthStorageBackend> {
     let keyring_store: Arc<dyn KeyringStore> = Arc::new(DefaultKeyringStore);
-    create_auth_storage_with_keyring_store(codex_home, mode, keyring_store)
+    create_auth_storage_with_keyring_store(codex_home, account, mode, keyring_store)
 }
 
 fn create_auth_storage_with_keyring_store(
     codex_home: PathBuf,
+    account: Option<String>,
     mode: AuthCredentialsStoreMode,
     keyring_store: Arc<dyn KeyringStore>,
 ) -> Arc<dyn AuthStorageBackend> {
     match mode {
-        AuthCredentialsStoreMode::File => Arc::new(FileAuthStorage::new(codex_home)),
+        AuthCredentialsStoreMode::File => {
+            Arc::new(FileAuthStorage::new(codex_home).with_account(account))
+        }
         AuthCredentialsStoreMode::Keyring => {
-            Arc::new(KeyringAuthStorage::new(codex_home, keyring_store))
+            Arc::new(KeyringAuthStorage::new(codex_home, keyring_store).with_account(account))
         }
-        AuthCredentialsStoreMode::Auto => Arc::new(AutoAuthStorage::new(codex_home, keyring_store)),
+        AuthCredentialsStoreMode::Auto => Arc::new(AutoAuthStorage::for_account(
+            codex_home,
+            keyring_store,
+            account,
+        )),
+        AuthCredentialsStoreMode::Encrypted => Arc::new(
+            FileAuthStorage::new(codex_home)
+                .with_account(account)
+                .with_encryption(FileEncryption::Required),
+        ),
     }
 }
 
@@ -359,6 +487,29 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn encrypted_file_storage_migrates_plaintext_auth_file() -> anyhow::Result<()> {
+        let dir = tempdir()?;
+        let auth_dot_json = AuthDotJson {
+            openai_api_key: Some("sk-test-key".to_string()),
+            gemini_api_key: None,
+            tokens: None,
+            last_refresh: None,
+        };
+        FileAuthStorage::new(dir.path().to_path_buf()).save(&auth_dot_json)?;
+
+        let mut storage = FileAuthStorage::new(dir.path().to_path_buf())
+            .with_encryption(FileEncryption::Required);
+        storage.encryption_key = Some(FileEncryptionKey::new("test passphrase"));
+        assert_eq!(storage.load()?, Some(auth_dot_json.clone()));
+
+        let contents = std::fs::read_to_string(get_auth_file(dir.path()))?;
+        assert!(encrypted_file::is_encrypted(&contents));
+        assert!(!contents.contains("sk-test-key"));
+        assert_eq!(storage.load()?, Some(auth_dot_json));
+        Ok(())
+    }
+
     fn seed_keyring_and_fallback_auth_file_for_delete<F>(
         mock_keyring: &MockKeyringStore,
         codex_home: &Path,
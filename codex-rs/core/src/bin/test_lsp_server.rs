@@ -0,0 +1,124 @@
+//! Minimal stdio language server used by the LSP integration tests. It
+//! publishes one error diagnostic for every line that contains `LSP_ERROR`.
+
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+
+use serde_json::Value;
+use serde_json::json;
+
+const MARKER: &str = "LSP_ERROR";
+
+fn main() -> io::Result<()> {
+    let stdin = io::stdin();
+    let mut reader = stdin.lock();
+    let mut stdout = io::stdout();
+
+    while let Some(message) = read_message(&mut reader)? {
+        let id = message.get("id").cloned();
+        let method = message
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        match method {
+            "initialize" => reply(
+                &mut stdout,
+                id,
+                json!({ "capabilities": { "textDocumentSync": 1 } }),
+            )?,
+            "shutdown" => reply(&mut stdout, id, Value::Null)?,
+            "exit" => return Ok(()),
+            "textDocument/didOpen" => {
+                let uri = params.pointer("/textDocument/uri").cloned();
+                let text = params.pointer("/textDocument/text").and_then(Value::as_str);
+                publish(&mut stdout, uri, text.unwrap_or_default())?;
+            }
+            "textDocument/didChange" => {
+                let uri = params.pointer("/textDocument/uri").cloned();
+                let text = params
+                    .get("contentChanges")
+                    .and_then(Value::as_array)
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change.get("text"))
+                    .and_then(Value::as_str);
+                publish(&mut stdout, uri, text.unwrap_or_default())?;
+            }
+            "textDocument/didClose" => {
+                let uri = params.pointer("/textDocument/uri").cloned();
+                publish(&mut stdout, uri, "")?;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn publish(stdout: &mut impl Write, uri: Option<Value>, text: &str) -> io::Result<()> {
+    let Some(uri) = uri else {
+        return Ok(());
+    };
+    let diagnostics: Vec<Value> = text
+        .lines()
+        .enumerate()
+        .filter_map(|(line, content)| {
+            let character = content.find(MARKER)?;
+            Some(json!({
+                "range": {
+                    "start": { "line": line, "character": character },
+                    "end": { "line": line, "character": character + MARKER.len() },
+                },
+                "severity": 1,
+                "code": "T001",
+                "source": "test-lsp",
+                "message": format!("found {MARKER} marker"),
+            }))
+        })
+        .collect();
+    write_message(
+        stdout,
+        &json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        }),
+    )
+}
+
+fn reply(stdout: &mut impl Write, id: Option<Value>, result: Value) -> io::Result<()> {
+    write_message(
+        stdout,
+        &json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+    )
+}
+
+fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let mut body = vec![0; content_length.unwrap_or_default()];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn write_message(stdout: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = serde_json::to_vec(message)?;
+    write!(stdout, "Content-Length: {}\r\n\r\n", body.len())?;
+    stdout.write_all(&body)?;
+    stdout.flush()
+}
@@ -0,0 +1,506 @@
+//! Checkpoint timeline built on the per-turn workspace snapshots.
+//!
+//! Every turn records a `ResponseItem::GhostSnapshot`: a ghost commit inside a
+//! Git repository, a file snapshot manifest everywhere else, or both. Each of
+//! those is a checkpoint. `Op::ListCheckpoints` reports them together with the
+//! changes made since, and `Op::RestoreCheckpoint` rolls selected files (or
+//! selected hunks of a file) back to one of them without touching history.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_git::CreateGhostCommitOptions;
+use codex_git::GhostCommit;
+use codex_git::GhostSnapshotConfig;
+use codex_git::GitToolingError;
+use codex_git::changed_paths_between_ghost_commits;
+use codex_git::changed_paths_since_ghost_commit;
+use codex_git::read_ghost_commit_file;
+use codex_protocol::items::TurnItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::CheckpointRestorePath;
+use codex_protocol::protocol::CheckpointSummary;
+use codex_protocol::protocol::FileChange;
+use similar::DiffTag;
+use similar::TextDiff;
+use tracing::warn;
+
+use crate::event_mapping::parse_turn_item;
+use crate::file_snapshots::FileSnapshotStore;
+use crate::git_info::get_git_repo_root;
+
+/// Context lines around each hunk. Hunk indexes in `Op::RestoreCheckpoint`
+/// refer to the hunks of a diff rendered with this radius.
+const HUNK_CONTEXT_LINES: usize = 3;
+const PROMPT_PREVIEW_CHARS: usize = 80;
+
+struct Checkpoint {
+    ghost_commit: GhostCommit,
+    turn: u32,
+    prompt_preview: String,
+}
+
+/// A file whose contents differ between a checkpoint and the workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CheckpointFile {
+    path: PathBuf,
+    /// Contents at the checkpoint, `None` if the file did not exist yet.
+    before: Option<Vec<u8>>,
+    /// Current contents, `None` if the file has been deleted since.
+    after: Option<Vec<u8>>,
+}
+
+/// Where the workspace snapshots for a session live.
+pub(crate) struct CheckpointSources<'a> {
+    pub(crate) cwd: &'a Path,
+    pub(crate) file_snapshots: &'a FileSnapshotStore,
+    pub(crate) ghost_snapshot: &'a GhostSnapshotConfig,
+}
+
+/// Summarizes every checkpoint in `history`, oldest first. Checkpoints whose
+/// snapshot can no longer be read are skipped.
+pub(crate) fn list_checkpoints(
+    history: &[ResponseItem],
+    sources: &CheckpointSources<'_>,
+) -> Vec<CheckpointSummary> {
+    let checkpoints = checkpoints_from_history(history);
+    let Some(latest) = checkpoints
+        .last()
+        .map(|checkpoint| checkpoint.ghost_commit.clone())
+    else {
+        return Vec::new();
+    };
+
+    let current = match WorkspaceChanges::since(sources, &latest) {
+        Ok(current) => current,
+        Err(err) => {
+            warn!("failed to diff workspace for checkpoint listing: {err}");
+            return Vec::new();
+        }
+    };
+
+    checkpoints
+        .into_iter()
+        .filter_map(|checkpoint| {
+            match changed_files(sources, &checkpoint.ghost_commit, current.as_ref()) {
+                Ok(files) => Some(CheckpointSummary {
+                    id: checkpoint.ghost_commit.id().to_string(),
+                    turn: checkpoint.turn,
+                    prompt_preview: checkpoint.prompt_preview,
+                    changes: files
+                        .iter()
+                        .map(|file| (file.path.clone(), file_change(file)))
+                        .collect(),
+                }),
+                Err(err) => {
+                    warn!(
+                        "skipping checkpoint {}: {err}",
+                        checkpoint.ghost_commit.id()
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Rolls the selected paths back to the checkpoint `id` and returns the
+/// absolute paths that were written or removed. An empty selection restores
+/// every file changed since the checkpoint. Nothing is written unless every
+/// selected path and hunk is valid.
+pub(crate) fn restore_checkpoint(
+    history: &[ResponseItem],
+    sources: &CheckpointSources<'_>,
+    id: &str,
+    selection: &[CheckpointRestorePath],
+) -> Result<Vec<PathBuf>, String> {
+    let checkpoints = checkpoints_from_history(history);
+    let Some(checkpoint) = checkpoints
+        .iter()
+        .find(|checkpoint| checkpoint.ghost_commit.id() == id)
+    else {
+        return Err(format!("No checkpoint with id {id}."));
+    };
+    let Some(latest) = checkpoints.last() else {
+        return Err(format!("No checkpoint with id {id}."));
+    };
+
+    let current =
+        WorkspaceChanges::since(sources, &latest.ghost_commit).map_err(|err| err.to_string())?;
+    let files = changed_files(sources, &checkpoint.ghost_commit, current.as_ref())
+        .map_err(|err| err.to_string())?;
+
+    let mut planned: Vec<(&CheckpointFile, Option<&[usize]>)> = Vec::new();
+    if selection.is_empty() {
+        planned.extend(files.iter().map(|file| (file, None)));
+    } else {
+        for selected in selection {
+            let path = sources.cwd.join(&selected.path);
+            let Some(file) = files.iter().find(|file| file.path == path) else {
+                return Err(format!(
+                    "{} has not changed since checkpoint {id}.",
+                    selected.path.display()
+                ));
+            };
+            planned.push((file, selected.hunks.as_deref()));
+        }
+    }
+
+    let writes = planned
+        .into_iter()
+        .map(|(file, hunks)| Ok((file.path.clone(), restored_contents(file, hunks)?)))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut restored = Vec::with_capacity(writes.len());
+    for (path, contents) in writes {
+        write_or_remove(&path, contents.as_deref())
+            .map_err(|err| format!("Failed to restore {}: {err}", path.display()))?;
+        restored.push(path);
+    }
+    Ok(restored)
+}
+
+fn checkpoints_from_history(history: &[ResponseItem]) -> Vec<Checkpoint> {
+    let mut checkpoints = Vec::new();
+    let mut turn = 0;
+    let mut prompt_preview = String::new();
+    for item in history {
+        if let ResponseItem::GhostSnapshot { ghost_commit } = item {
+            checkpoints.push(Checkpoint {
+                ghost_commit: ghost_commit.clone(),
+                turn,
+                prompt_preview: prompt_preview.clone(),
+            });
+        } else if let Some(TurnItem::UserMessage(message)) = parse_turn_item(item) {
+            turn += 1;
+            prompt_preview = preview(&message.message());
+        }
+    }
+    checkpoints
+}
+
+fn preview(prompt: &str) -> String {
+    let first_line = prompt
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    let mut preview: String = first_line.chars().take(PROMPT_PREVIEW_CHARS).collect();
+    if first_line.chars().count() > PROMPT_PREVIEW_CHARS {
+        preview.push('…');
+    }
+    preview
+}
+
+/// How the working tree differs from the latest turn's ghost commit, when the
+/// cwd is inside a Git repository. Older checkpoints are diffed against that
+/// commit plus these paths, so listing never has to snapshot the workspace.
+struct WorkspaceChanges {
+    latest: GhostCommit,
+    paths: Vec<PathBuf>,
+}
+
+impl WorkspaceChanges {
+    fn since(
+        sources: &CheckpointSources<'_>,
+        latest: &GhostCommit,
+    ) -> Result<Option<Self>, GitToolingError> {
+        if get_git_repo_root(sources.cwd).is_none() {
+            return Ok(None);
+        }
+        let options = CreateGhostCommitOptions::new(sources.cwd)
+            .ghost_snapshot(sources.ghost_snapshot.clone());
+        let paths = changed_paths_since_ghost_commit(&options, latest)?;
+        Ok(Some(Self {
+            latest: latest.clone(),
+            paths,
+        }))
+    }
+}
+
+fn changed_files(
+    sources: &CheckpointSources<'_>,
+    ghost_commit: &GhostCommit,
+    current: Option<&WorkspaceChanges>,
+) -> Result<Vec<CheckpointFile>, GitToolingError> {
+    let mut snapshot_contents: Vec<(PathBuf, Option<Vec<u8>>)> = Vec::new();
+    if let Some(current) = current {
+        let mut paths: HashSet<PathBuf> = current.paths.iter().cloned().collect();
+        if current.latest.id() != ghost_commit.id() {
+            paths.extend(changed_paths_between_ghost_commits(
+                sources.cwd,
+                ghost_commit,
+                &current.latest,
+            )?);
+        }
+        for rel in paths {
+            let before = read_ghost_commit_file(sources.cwd, ghost_commit, &rel)?;
+            snapshot_contents.push((sources.cwd.join(rel), before));
+        }
+    }
+    if sources.file_snapshots.contains(ghost_commit.id()) {
+        snapshot_contents.extend(sources.file_snapshots.changed_files(ghost_commit.id())?);
+    } else if current.is_none() {
+        return Err(GitToolingError::NotAGitRepository {
+            path: sources.cwd.to_path_buf(),
+        });
+    }
+
+    let mut files: Vec<CheckpointFile> = snapshot_contents
+        .into_iter()
+        .filter_map(|(path, before)| {
+            let after = fs::read(&path).ok();
+            (before != after).then_some(CheckpointFile {
+                path,
+                before,
+                after,
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// The change from the checkpoint to the workspace, in the shape clients
+/// already render for patches.
+fn file_change(file: &CheckpointFile) -> FileChange {
+    match (&file.before, &file.after) {
+        (Some(before), Some(after)) => {
+            match (std::str::from_utf8(before), std::str::from_utf8(after)) {
+                (Ok(before), Ok(after)) => FileChange::Update {
+                    unified_diff: TextDiff::from_lines(before, after)
+                        .unified_diff()
+                        .context_radius(HUNK_CONTEXT_LINES)
+                        .to_string(),
+                    move_path: None,
+                },
+                _ => FileChange::Binary {
+                    old_size: Some(before.len() as u64),
+                    new_size: after.len() as u64,
+                },
+            }
+        }
+        (None, Some(after)) => match std::str::from_utf8(after) {
+            Ok(content) => FileChange::Add {
+                content: content.to_string(),
+            },
+            Err(_) => FileChange::Binary {
+                old_size: None,
+                new_size: after.len() as u64,
+            },
+        },
+        (before, None) => FileChange::Delete {
+            content: before
+                .as_deref()
+                .map(String::from_utf8_lossy)
+                .unwrap_or_default()
+                .into_owned(),
+        },
+    }
+}
+
+fn restored_contents(
+    file: &CheckpointFile,
+    hunks: Option<&[usize]>,
+) -> Result<Option<Vec<u8>>, String> {
+    let Some(hunks) = hunks else {
+        return Ok(file.before.clone());
+    };
+    let display = file.path.display();
+    let (Some(before), Some(after)) = (&file.before, &file.after) else {
+        return Err(format!(
+            "{display} was added or deleted since the checkpoint; restore the whole file instead."
+        ));
+    };
+    let (Ok(before), Ok(after)) = (std::str::from_utf8(before), std::str::from_utf8(after)) else {
+        return Err(format!(
+            "{display} is not a text file; restore the whole file instead."
+        ));
+    };
+    revert_hunks(before, after, hunks)
+        .map(|contents| Some(contents.into_bytes()))
+        .map_err(|err| format!("{display}: {err}"))
+}
+
+/// Returns `after` with the selected hunks of the `before` -> `after` diff
+/// reverted to their `before` lines.
+fn revert_hunks(before: &str, after: &str, hunks: &[usize]) -> Result<String, String> {
+    let diff = TextDiff::from_lines(before, after);
+    let groups = diff.grouped_ops(HUNK_CONTEXT_LINES);
+    let mut selected: HashSet<(usize, usize)> = HashSet::new();
+    for &idx in hunks {
+        let Some(group) = groups.get(idx) else {
+            return Err(format!(
+                "hunk {idx} does not exist; the diff has {} hunks",
+                groups.len()
+            ));
+        };
+        for op in group.iter().filter(|op| op.tag() != DiffTag::Equal) {
+            selected.insert((op.old_range().start, op.new_range().start));
+        }
+    }
+
+    let old_lines = diff.old_slices();
+    let new_lines = diff.new_slices();
+    let mut restored = String::with_capacity(after.len());
+    for op in diff.ops() {
+        let revert = op.tag() != DiffTag::Equal
+            && selected.contains(&(op.old_range().start, op.new_range().start));
+        let lines = if revert {
+            &old_lines[op.old_range()]
+        } else {
+            &new_lines[op.new_range()]
+        };
+        for line in lines {
+            restored.push_str(line);
+        }
+    }
+    Ok(restored)
+}
+
+fn write_or_remove(path: &Path, contents: Option<&[u8]>) -> io::Result<()> {
+    match contents {
+        Some(contents) => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, contents)
+        }
+        None => match fs::remove_file(path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            thought_signature: None,
+        }
+    }
+
+    fn ghost_snapshot(id: &str) -> ResponseItem {
+        ResponseItem::GhostSnapshot {
+            ghost_commit: GhostCommit::new(id.to_string(), None, Vec::new(), Vec::new()),
+        }
+    }
+
+    #[test]
+    fn checkpoints_pair_snapshots_with_their_prompts() {
+        let history = vec![
+            user_message("first prompt\nwith details"),
+            ghost_snapshot("a"),
+            user_message("second prompt"),
+            ghost_snapshot("b"),
+        ];
+
+        let checkpoints = checkpoints_from_history(&history);
+        let summary: Vec<(String, u32, String)> = checkpoints
+            .into_iter()
+            .map(|c| (c.ghost_commit.id().to_string(), c.turn, c.prompt_preview))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("a".to_string(), 1, "first prompt".to_string()),
+                ("b".to_string(), 2, "second prompt".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn revert_hunks_only_touches_selected_hunks() {
+        let before = (1..=20).map(|n| format!("line {n}\n")).collect::<String>();
+        let after = before
+            .replace("line 2\n", "line two\n")
+            .replace("line 18\n", "line eighteen\n");
+
+        let diff = TextDiff::from_lines(before.as_str(), after.as_str());
+        assert_eq!(diff.grouped_ops(HUNK_CONTEXT_LINES).len(), 2);
+
+        let restored = revert_hunks(&before, &after, &[1]).expect("revert");
+        assert_eq!(restored, before.replace("line 2\n", "line two\n"));
+        assert!(revert_hunks(&before, &after, &[2]).is_err());
+    }
+
+    #[test]
+    fn restore_rolls_back_selected_files_from_file_snapshots() {
+        let codex_home = TempDir::new().expect("codex home");
+        let workspace = TempDir::new().expect("workspace");
+        let cwd = workspace.path().to_path_buf();
+        let store = FileSnapshotStore::new(codex_home.path());
+        let config = GhostSnapshotConfig::default();
+
+        fs::write(cwd.join("keep.txt"), "one\n").expect("write");
+        fs::write(cwd.join("revert.txt"), "original\n").expect("write");
+        store
+            .capture("checkpoint", std::slice::from_ref(&cwd), &config)
+            .expect("capture");
+
+        fs::write(cwd.join("keep.txt"), "two\n").expect("write");
+        fs::write(cwd.join("revert.txt"), "changed\n").expect("write");
+        fs::write(cwd.join("added.txt"), "new\n").expect("write");
+
+        let sources = CheckpointSources {
+            cwd: &cwd,
+            file_snapshots: &store,
+            ghost_snapshot: &config,
+        };
+        let history = vec![user_message("edit files"), ghost_snapshot("checkpoint")];
+
+        let listed = list_checkpoints(&history, &sources);
+        assert_eq!(listed.len(), 1);
+        let mut changed: Vec<PathBuf> = listed[0].changes.keys().cloned().collect();
+        changed.sort();
+        assert_eq!(
+            changed,
+            vec![
+                cwd.join("added.txt"),
+                cwd.join("keep.txt"),
+                cwd.join("revert.txt")
+            ]
+        );
+
+        let selection = vec![
+            CheckpointRestorePath {
+                path: PathBuf::from("revert.txt"),
+                hunks: None,
+            },
+            CheckpointRestorePath {
+                path: PathBuf::from("added.txt"),
+                hunks: None,
+            },
+        ];
+        let restored =
+            restore_checkpoint(&history, &sources, "checkpoint", &selection).expect("restore");
+        assert_eq!(
+            restored,
+            vec![cwd.join("revert.txt"), cwd.join("added.txt")]
+        );
+
+        assert_eq!(
+            fs::read_to_string(cwd.join("revert.txt")).expect("read"),
+            "original\n"
+        );
+        assert!(!cwd.join("added.txt").exists());
+        assert_eq!(
+            fs::read_to_string(cwd.join("keep.txt")).expect("read"),
+            "two\n"
+        );
+    }
+}
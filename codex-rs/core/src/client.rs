@@ -301,6 +301,7 @@ impl ModelClient {
                 }
             }
             WireApi::Gemini => self.stream_gemini(prompt).await,
+            WireApi::Ollama => self.stream_ollama(prompt).await,
         }
     }
 
@@ -357,6 +358,34 @@ impl ModelClient {
         }
     }
 
+    /// Streams a turn via Ollama's native `/api/chat` endpoint so the
+    /// request can carry `num_ctx` and `keep_alive`.
+    async fn stream_ollama(&self, prompt: &Prompt) -> Result<ResponseStream> {
+        if prompt.output_schema.is_some() {
+            return Err(CodexErr::UnsupportedOperation(
+                "output_schema is not supported for the Ollama chat API".to_string(),
+            ));
+        }
+
+        let model = self.get_model();
+        let model_family = self.get_model_family();
+        let instructions = prompt.get_full_instructions(&model_family).into_owned();
+        let input = prompt.get_formatted_input();
+        let info = crate::ollama::model_info(&self.provider, &model).await;
+        let request = crate::ollama::build_chat_request(crate::ollama::ChatRequestParams {
+            model: &model,
+            instructions: &instructions,
+            input: &input,
+            tools: create_tools_json_for_chat_completions_api(&prompt.tools)?,
+            info: info.as_ref(),
+            effort: self.effort.or(model_family.default_reasoning_effort),
+            num_ctx: model_family.context_window,
+            keep_alive: self.config.ollama_keep_alive.as_deref(),
+        });
+
+        crate::ollama::stream_chat(&self.provider, &request).await
+    }
+
     async fn stream_gemini(&self, prompt: &Prompt) -> Result<ResponseStream> {
         let base_url = self.provider.base_url.as_ref().ok_or_else(|| {
             CodexErr::UnsupportedOperation("Gemini providers must define a base_url".to_string())
@@ -495,8 +524,13 @@ impl ModelClient {
         // This matches the documented behaviour where a dedicated Gemini key
         // in the env takes precedence over the shared key stored in auth.json.
         let gemini_api_key = crate::auth::read_gemini_api_key_from_env().or_else(|| {
-            crate::auth::read_gemini_api_key_from_auth_json(
+            let account = crate::auth::resolve_account(
+                &self.config.codex_home,
+                self.config.account.as_deref(),
+            );
+            crate::auth::read_account_gemini_api_key(
                 &self.config.codex_home,
+                account.as_deref(),
                 self.config.cli_auth_credentials_store_mode,
             )
         });
@@ -690,7 +724,7 @@ instead (for example: `codex -p codex`), or execute the command manually in your
         })
     }
 
-    fn normalize_gemini_base_url(base_url: &str) -> Cow<'_, str> {
+    pub(crate) fn normalize_gemini_base_url(base_url: &str) -> Cow<'_, str> {
         let trimmed = base_url.trim_end_matches('/');
         if let Some(prefix) = trimmed.strip_suffix("/v1") {
             Cow::Owned(format!("{prefix}/v1beta"))
@@ -0,0 +1,730 @@
+//! Tree-sitter backed symbol extraction used by the `code_outline` and
+//! `find_symbol` tools.
+//!
+//! Each supported language maps a handful of grammar node kinds onto a small,
+//! language-neutral [`SymbolKind`] vocabulary. Containers (impls, classes,
+//! traits, modules, ...) keep their members as children; function bodies are
+//! not descended into so the outline stays at the declaration level.
+
+use std::path::Path;
+
+use tree_sitter::Language;
+use tree_sitter::Node;
+use tree_sitter::Parser;
+
+/// Longest signature line we keep before clipping.
+const MAX_SIGNATURE_CHARS: usize = 160;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OutlineLanguage {
+    Rust,
+    Python,
+    TypeScript,
+    Tsx,
+    JavaScript,
+    Go,
+    Java,
+    C,
+    Cpp,
+    Ruby,
+}
+
+impl OutlineLanguage {
+    /// Picks a grammar from the file extension, or `None` when unsupported.
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        let language = match ext.as_str() {
+            "rs" => Self::Rust,
+            "py" | "pyi" => Self::Python,
+            "ts" | "mts" | "cts" => Self::TypeScript,
+            "tsx" => Self::Tsx,
+            "js" | "mjs" | "cjs" | "jsx" => Self::JavaScript,
+            "go" => Self::Go,
+            "java" => Self::Java,
+            "c" | "h" => Self::C,
+            "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => Self::Cpp,
+            "rb" => Self::Ruby,
+            _ => return None,
+        };
+        Some(language)
+    }
+
+    fn grammar(self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+            Self::Java => tree_sitter_java::LANGUAGE.into(),
+            Self::C => tree_sitter_c::LANGUAGE.into(),
+            Self::Cpp => tree_sitter_cpp::LANGUAGE.into(),
+            Self::Ruby => tree_sitter_ruby::LANGUAGE.into(),
+        }
+    }
+
+    /// Separator used when qualifying a member with its container name.
+    pub(crate) fn scope_separator(self) -> &'static str {
+        match self {
+            Self::Rust | Self::Cpp | Self::Ruby => "::",
+            _ => ".",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SymbolKind {
+    Function,
+    Method,
+    Class,
+    Struct,
+    Enum,
+    Interface,
+    Trait,
+    Impl,
+    Module,
+    Type,
+    Constant,
+    Macro,
+}
+
+impl SymbolKind {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Function => "fn",
+            Self::Method => "method",
+            Self::Class => "class",
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Interface => "interface",
+            Self::Trait => "trait",
+            Self::Impl => "impl",
+            Self::Module => "mod",
+            Self::Type => "type",
+            Self::Constant => "const",
+            Self::Macro => "macro",
+        }
+    }
+
+    /// Parses the labels accepted by the `find_symbol` `kind` filter.
+    pub(crate) fn from_label(label: &str) -> Option<Self> {
+        let kind = match label.trim().to_ascii_lowercase().as_str() {
+            "fn" | "function" => Self::Function,
+            "method" => Self::Method,
+            "class" => Self::Class,
+            "struct" => Self::Struct,
+            "enum" => Self::Enum,
+            "interface" => Self::Interface,
+            "trait" => Self::Trait,
+            "impl" => Self::Impl,
+            "mod" | "module" | "namespace" => Self::Module,
+            "type" => Self::Type,
+            "const" | "constant" => Self::Constant,
+            "macro" => Self::Macro,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    fn is_container(self) -> bool {
+        matches!(
+            self,
+            Self::Class
+                | Self::Struct
+                | Self::Enum
+                | Self::Interface
+                | Self::Trait
+                | Self::Impl
+                | Self::Module
+        )
+    }
+
+    fn holds_methods(self) -> bool {
+        self.is_container() && self != Self::Module
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Symbol {
+    pub kind: SymbolKind,
+    pub name: String,
+    /// Name used to qualify members; differs from `name` for Rust trait impls
+    /// (`Display for Foo` qualifies its members as `Foo`).
+    pub scope_name: String,
+    /// First line of the declaration, trimmed.
+    pub signature: String,
+    /// 1-based, inclusive.
+    pub start_line: usize,
+    /// 1-based, inclusive.
+    pub end_line: usize,
+    pub children: Vec<Symbol>,
+}
+
+/// Parses `source` and returns its top-level symbols. Returns `None` if the
+/// grammar could not be loaded or parsing was aborted.
+pub(crate) fn outline_source(language: OutlineLanguage, source: &str) -> Option<Vec<Symbol>> {
+    let mut parser = Parser::new();
+    parser.set_language(&language.grammar()).ok()?;
+    let tree = parser.parse(source, None)?;
+    let mut symbols = Vec::new();
+    collect_symbols(
+        language,
+        tree.root_node(),
+        source.as_bytes(),
+        None,
+        &mut symbols,
+    );
+    Some(symbols)
+}
+
+fn collect_symbols(
+    language: OutlineLanguage,
+    node: Node,
+    source: &[u8],
+    container: Option<SymbolKind>,
+    out: &mut Vec<Symbol>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match classify(language, child, source, container) {
+            Some((kind, name, scope_name)) => {
+                let mut symbol = Symbol {
+                    kind,
+                    name,
+                    scope_name,
+                    signature: signature_line(child, source),
+                    start_line: child.start_position().row + 1,
+                    end_line: child.end_position().row + 1,
+                    children: Vec::new(),
+                };
+                if kind.is_container() {
+                    collect_symbols(language, child, source, Some(kind), &mut symbol.children);
+                }
+                out.push(symbol);
+            }
+            None if descends_into(language, child) => {
+                collect_symbols(language, child, source, container, out);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Non-symbol nodes whose children may still declare symbols (bodies,
+/// export wrappers, decorators, declaration lists, ...).
+fn descends_into(language: OutlineLanguage, node: Node) -> bool {
+    let kind = node.kind();
+    match language {
+        OutlineLanguage::Rust => matches!(kind, "declaration_list" | "attribute_item"),
+        OutlineLanguage::Python => matches!(kind, "block" | "decorated_definition"),
+        OutlineLanguage::TypeScript | OutlineLanguage::Tsx | OutlineLanguage::JavaScript => {
+            matches!(
+                kind,
+                "export_statement"
+                    | "class_body"
+                    | "interface_body"
+                    | "object_type"
+                    | "statement_block"
+                    | "lexical_declaration"
+                    | "variable_declaration"
+                    | "ambient_declaration"
+                    | "expression_statement"
+            )
+        }
+        OutlineLanguage::Go => matches!(kind, "type_declaration"),
+        OutlineLanguage::Java => matches!(
+            kind,
+            "class_body" | "interface_body" | "enum_body" | "enum_body_declarations"
+        ),
+        OutlineLanguage::C | OutlineLanguage::Cpp => matches!(
+            kind,
+            "declaration_list"
+                | "field_declaration_list"
+                | "template_declaration"
+                | "linkage_specification"
+                | "preproc_if"
+                | "preproc_ifdef"
+                | "preproc_else"
+                | "preproc_elif"
+        ),
+        OutlineLanguage::Ruby => matches!(kind, "body_statement"),
+    }
+}
+
+/// Returns `(kind, name, scope_name)` when `node` declares a symbol.
+fn classify(
+    language: OutlineLanguage,
+    node: Node,
+    source: &[u8],
+    container: Option<SymbolKind>,
+) -> Option<(SymbolKind, String, String)> {
+    let in_type = container.is_some_and(SymbolKind::holds_methods);
+    let function_kind = if in_type {
+        SymbolKind::Method
+    } else {
+        SymbolKind::Function
+    };
+    let named = |kind: SymbolKind| {
+        let name = field_text(node, "name", source)?;
+        Some((kind, name.clone(), name))
+    };
+
+    match language {
+        OutlineLanguage::Rust => match node.kind() {
+            "function_item" | "function_signature_item" => named(function_kind),
+            "struct_item" | "union_item" => named(SymbolKind::Struct),
+            "enum_item" => named(SymbolKind::Enum),
+            "trait_item" => named(SymbolKind::Trait),
+            "mod_item" => named(SymbolKind::Module),
+            "type_item" => named(SymbolKind::Type),
+            "const_item" | "static_item" => named(SymbolKind::Constant),
+            "macro_definition" => named(SymbolKind::Macro),
+            "impl_item" => {
+                let ty = field_text(node, "type", source)?;
+                let name = match field_text(node, "trait", source) {
+                    Some(trait_name) => format!("{trait_name} for {ty}"),
+                    None => ty.clone(),
+                };
+                Some((SymbolKind::Impl, name, ty))
+            }
+            _ => None,
+        },
+        OutlineLanguage::Python => match node.kind() {
+            "function_definition" => named(function_kind),
+            "class_definition" => named(SymbolKind::Class),
+            _ => None,
+        },
+        OutlineLanguage::TypeScript | OutlineLanguage::Tsx | OutlineLanguage::JavaScript => {
+            match node.kind() {
+                "function_declaration"
+                | "generator_function_declaration"
+                | "function_signature" => named(function_kind),
+                "method_definition" | "method_signature" | "abstract_method_signature" => {
+                    named(SymbolKind::Method)
+                }
+                "class_declaration" | "abstract_class_declaration" | "class" => {
+                    named(SymbolKind::Class)
+                }
+                "interface_declaration" => named(SymbolKind::Interface),
+                "enum_declaration" => named(SymbolKind::Enum),
+                "type_alias_declaration" => named(SymbolKind::Type),
+                "internal_module" | "module" => named(SymbolKind::Module),
+                "variable_declarator" => {
+                    let value = node.child_by_field_name("value")?;
+                    matches!(
+                        value.kind(),
+                        "arrow_function" | "function_expression" | "function"
+                    )
+                    .then(|| named(function_kind))
+                    .flatten()
+                }
+                _ => None,
+            }
+        }
+        OutlineLanguage::Go => match node.kind() {
+            "function_declaration" => named(SymbolKind::Function),
+            "method_declaration" => {
+                let name = field_text(node, "name", source)?;
+                let receiver = node
+                    .child_by_field_name("receiver")
+                    .and_then(|params| go_receiver_type(params, source));
+                let scope_name = receiver.unwrap_or_else(|| name.clone());
+                Some((SymbolKind::Method, name, scope_name))
+            }
+            "type_spec" => {
+                let kind = match node.child_by_field_name("type").map(|ty| ty.kind()) {
+                    Some("struct_type") => SymbolKind::Struct,
+                    Some("interface_type") => SymbolKind::Interface,
+                    _ => SymbolKind::Type,
+                };
+                named(kind)
+            }
+            "type_alias" => named(SymbolKind::Type),
+            _ => None,
+        },
+        OutlineLanguage::Java => match node.kind() {
+            "method_declaration" | "constructor_declaration" => named(SymbolKind::Method),
+            "class_declaration" | "record_declaration" => named(SymbolKind::Class),
+            "interface_declaration" | "annotation_type_declaration" => named(SymbolKind::Interface),
+            "enum_declaration" => named(SymbolKind::Enum),
+            _ => None,
+        },
+        OutlineLanguage::C | OutlineLanguage::Cpp => match node.kind() {
+            "function_definition" => {
+                let name = c_declarator_name(node, source)?;
+                let kind = if in_type || name.contains("::") {
+                    SymbolKind::Method
+                } else {
+                    SymbolKind::Function
+                };
+                Some((kind, name.clone(), name))
+            }
+            "struct_specifier" | "union_specifier"
+                if node.child_by_field_name("body").is_some() =>
+            {
+                named(SymbolKind::Struct)
+            }
+            "class_specifier" if node.child_by_field_name("body").is_some() => {
+                named(SymbolKind::Class)
+            }
+            "enum_specifier" if node.child_by_field_name("body").is_some() => {
+                named(SymbolKind::Enum)
+            }
+            "namespace_definition" => named(SymbolKind::Module),
+            "type_definition" | "alias_declaration" => {
+                let name = field_text(node, "declarator", source)
+                    .or_else(|| field_text(node, "name", source))?;
+                Some((SymbolKind::Type, name.clone(), name))
+            }
+            // `struct Foo { ... };` at file scope parses as a declaration-less
+            // specifier wrapped in a `declaration` or `type_definition`.
+            "declaration" | "field_declaration" => {
+                let ty = node.child_by_field_name("type")?;
+                classify(language, ty, source, container)
+            }
+            _ => None,
+        },
+        OutlineLanguage::Ruby => match node.kind() {
+            "method" | "singleton_method" => named(function_kind),
+            "class" => named(SymbolKind::Class),
+            "module" => named(SymbolKind::Module),
+            _ => None,
+        },
+    }
+}
+
+fn field_text(node: Node, field: &str, source: &[u8]) -> Option<String> {
+    let child = node.child_by_field_name(field)?;
+    let text = child.utf8_text(source).ok()?.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Follows nested `declarator` fields (pointers, references, function
+/// declarators) down to the identifier naming a C/C++ function.
+fn c_declarator_name(node: Node, source: &[u8]) -> Option<String> {
+    let mut current = node.child_by_field_name("declarator")?;
+    while let Some(inner) = current.child_by_field_name("declarator") {
+        current = inner;
+    }
+    let text = current.utf8_text(source).ok()?.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Extracts `T` from a Go receiver list such as `(s *T)` or `(T[K])`.
+fn go_receiver_type(params: Node, source: &[u8]) -> Option<String> {
+    let mut cursor = params.walk();
+    let param = params
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "parameter_declaration")?;
+    let text = field_text(param, "type", source)?;
+    let text = text.trim_start_matches('*');
+    let base = text.split('[').next().unwrap_or(text);
+    Some(base.to_string())
+}
+
+fn signature_line(node: Node, source: &[u8]) -> String {
+    let text = node.utf8_text(source).unwrap_or_default();
+    let first_line = text.lines().next().unwrap_or_default().trim();
+    let trimmed = first_line
+        .trim_end_matches('{')
+        .trim_end_matches(':')
+        .trim_end();
+    if trimmed.chars().count() > MAX_SIGNATURE_CHARS {
+        let clipped: String = trimmed.chars().take(MAX_SIGNATURE_CHARS).collect();
+        format!("{clipped}…")
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Renders symbols as an indented outline, one symbol per line, descending at
+/// most `max_depth` levels (1 = top level only).
+pub(crate) fn render_outline(symbols: &[Symbol], max_depth: usize, indent: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    render_into(symbols, max_depth, indent, 1, &mut lines);
+    lines
+}
+
+fn render_into(
+    symbols: &[Symbol],
+    max_depth: usize,
+    indent: usize,
+    depth: usize,
+    lines: &mut Vec<String>,
+) {
+    for symbol in symbols {
+        let pad = " ".repeat(indent + (depth - 1) * 2);
+        lines.push(format!(
+            "{pad}{} {} [{}-{}]",
+            symbol.kind.label(),
+            symbol.name,
+            symbol.start_line,
+            symbol.end_line
+        ));
+        if depth < max_depth {
+            render_into(&symbol.children, max_depth, indent, depth + 1, lines);
+        }
+    }
+}
+
+/// A symbol flattened out of the tree together with its qualified name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct QualifiedSymbol<'a> {
+    pub qualified_name: String,
+    pub symbol: &'a Symbol,
+}
+
+/// Flattens the symbol tree in source order, qualifying members with their
+/// container names.
+pub(crate) fn flatten_symbols(
+    language: OutlineLanguage,
+    symbols: &[Symbol],
+) -> Vec<QualifiedSymbol<'_>> {
+    let mut out = Vec::new();
+    flatten_into(language, symbols, None, &mut out);
+    out
+}
+
+fn flatten_into<'a>(
+    language: OutlineLanguage,
+    symbols: &'a [Symbol],
+    prefix: Option<&str>,
+    out: &mut Vec<QualifiedSymbol<'a>>,
+) {
+    let separator = language.scope_separator();
+    for symbol in symbols {
+        let qualify = |name: &str| match prefix {
+            Some(prefix) => format!("{prefix}{separator}{name}"),
+            None => name.to_string(),
+        };
+        // A Go method's receiver type acts as its container.
+        let qualified_name = if language == OutlineLanguage::Go
+            && symbol.kind == SymbolKind::Method
+            && symbol.scope_name != symbol.name
+        {
+            format!("{}{separator}{}", symbol.scope_name, symbol.name)
+        } else {
+            qualify(&symbol.name)
+        };
+        out.push(QualifiedSymbol {
+            qualified_name,
+            symbol,
+        });
+        let scope = qualify(&symbol.scope_name);
+        flatten_into(language, &symbol.children, Some(&scope), out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn summarize(language: OutlineLanguage, source: &str) -> Vec<String> {
+        let symbols = outline_source(language, source).expect("parse");
+        flatten_symbols(language, &symbols)
+            .into_iter()
+            .map(|entry| {
+                format!(
+                    "{} {} {}-{}",
+                    entry.symbol.kind.label(),
+                    entry.qualified_name,
+                    entry.symbol.start_line,
+                    entry.symbol.end_line
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rust_outline_nests_impl_members() {
+        let source = r#"
+use std::fmt;
+
+pub struct Point {
+    x: i32,
+}
+
+impl Point {
+    pub fn new(x: i32) -> Self {
+        fn helper() {}
+        Self { x }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.x)
+    }
+}
+
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+mod inner {
+    pub const LIMIT: usize = 3;
+}
+"#;
+        assert_eq!(
+            summarize(OutlineLanguage::Rust, source),
+            vec![
+                "struct Point 4-6",
+                "impl Point 8-13",
+                "method Point::new 9-12",
+                "impl fmt::Display for Point 15-19",
+                "method Point::fmt 16-18",
+                "trait Shape 21-23",
+                "method Shape::area 22-22",
+                "mod inner 25-27",
+                "const inner::LIMIT 26-26",
+            ]
+        );
+    }
+
+    #[test]
+    fn python_outline_handles_classes_and_decorators() {
+        let source = r#"
+import os
+
+@dataclass
+class Config:
+    name: str
+
+    def load(self):
+        def inner():
+            pass
+        return 1
+
+    @staticmethod
+    def default():
+        return Config("x")
+
+def main():
+    pass
+"#;
+        assert_eq!(
+            summarize(OutlineLanguage::Python, source),
+            vec![
+                "class Config 5-15",
+                "method Config.load 8-11",
+                "method Config.default 14-15",
+                "fn main 17-18",
+            ]
+        );
+    }
+
+    #[test]
+    fn typescript_outline_covers_exports_and_arrow_functions() {
+        let source = r#"
+export interface Shape {
+  area(): number;
+}
+
+export class Circle implements Shape {
+  constructor(private r: number) {}
+  area(): number {
+    return Math.PI * this.r * this.r;
+  }
+}
+
+export const makeCircle = (r: number) => new Circle(r);
+
+type Id = string;
+
+function helper() {}
+"#;
+        assert_eq!(
+            summarize(OutlineLanguage::TypeScript, source),
+            vec![
+                "interface Shape 2-4",
+                "method Shape.area 3-3",
+                "class Circle 6-11",
+                "method Circle.constructor 7-7",
+                "method Circle.area 8-10",
+                "fn makeCircle 13-13",
+                "type Id 15-15",
+                "fn helper 17-17",
+            ]
+        );
+    }
+
+    #[test]
+    fn go_outline_qualifies_methods_with_receiver() {
+        let source = r#"package main
+
+type Server struct {
+	addr string
+}
+
+type Handler interface {
+	Serve()
+}
+
+func (s *Server) Start() error {
+	return nil
+}
+
+func main() {}
+"#;
+        assert_eq!(
+            summarize(OutlineLanguage::Go, source),
+            vec![
+                "struct Server 3-5",
+                "interface Handler 7-9",
+                "method Server.Start 11-13",
+                "fn main 15-15",
+            ]
+        );
+    }
+
+    #[test]
+    fn c_family_outline_resolves_declarator_names() {
+        let c_source = r#"
+struct node {
+    int value;
+};
+
+static int *lookup(struct node *n) {
+    return 0;
+}
+"#;
+        assert_eq!(
+            summarize(OutlineLanguage::C, c_source),
+            vec!["struct node 2-4", "fn lookup 6-8"]
+        );
+
+        let cpp_source = r#"
+namespace app {
+class Widget {
+public:
+    void draw();
+};
+
+void Widget::draw() {}
+}
+"#;
+        assert_eq!(
+            summarize(OutlineLanguage::Cpp, cpp_source),
+            vec![
+                "mod app 2-9",
+                "class app::Widget 3-6",
+                "method app::Widget::draw 8-8",
+            ]
+        );
+    }
+
+    #[test]
+    fn language_detection_uses_extension() {
+        assert_eq!(
+            OutlineLanguage::from_path(Path::new("src/lib.RS")),
+            Some(OutlineLanguage::Rust)
+        );
+        assert_eq!(
+            OutlineLanguage::from_path(Path::new("app.tsx")),
+            Some(OutlineLanguage::Tsx)
+        );
+        assert_eq!(OutlineLanguage::from_path(Path::new("README.md")), None);
+    }
+}
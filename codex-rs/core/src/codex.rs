@@ -1,10 +1,12 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
+use std::time::Instant;
 
 use crate::AuthManager;
 use crate::SandboxState;
@@ -13,8 +15,11 @@ use crate::compact;
 use crate::compact::run_inline_auto_compact_task;
 use crate::compact::should_use_remote_compact_task;
 use crate::compact_remote::run_inline_remote_auto_compact_task;
+use crate::compact_structured::PinnedContext;
 use crate::delegate_tool::DelegateToolAdapter;
+use crate::exec_policy::ExecPolicy;
 use crate::exec_policy::load_exec_policy_for_features;
+use crate::exec_policy::project_rules_dir;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::openai_models::model_family::ModelFamily;
@@ -32,6 +37,7 @@ use async_channel::Receiver;
 use async_channel::Sender;
 use codex_protocol::ConversationId;
 use codex_protocol::approvals::ExecPolicyAmendment;
+use codex_protocol::approvals::ExecPolicyAmendmentTarget;
 use codex_protocol::items::TurnItem;
 use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::HasLegacyEvent;
@@ -82,6 +88,8 @@ use crate::config::Constrained;
 use crate::config::ConstraintError;
 use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
+use crate::config::types::AuxiliaryTask;
+use crate::config::types::FallbackTarget;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::context_manager::ContextManager;
 use crate::environment_context::EnvironmentContext;
@@ -90,10 +98,14 @@ use crate::error::Result as CodexResult;
 #[cfg(test)]
 use crate::exec::StreamOutput;
 use crate::exec_policy::ExecPolicyUpdateError;
+use crate::file_snapshots::FileSnapshotStore;
+use crate::lsp::LspManager;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::model_provider_info::CHAT_WIRE_API_DEPRECATION_SUMMARY;
+use crate::project_doc::NestedProjectDocs;
 use crate::project_doc::get_user_instructions;
+use crate::project_doc::read_nested_project_docs;
 use crate::protocol::AgentMessageContentDeltaEvent;
 use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
@@ -123,6 +135,7 @@ use crate::protocol::WarningEvent;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
+use crate::session_title;
 use crate::shell;
 use crate::shell_snapshot::ShellSnapshot;
 use crate::skills::SkillError;
@@ -150,7 +163,6 @@ use crate::user_instructions::UserInstructions;
 use crate::user_notification::UserNotification;
 use crate::util::backoff;
 use codex_async_utils::OrCancelExt;
-use codex_execpolicy::Policy as ExecPolicy;
 use codex_otel::otel_manager::OtelManager;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
 use codex_protocol::models::ContentItem;
@@ -162,6 +174,7 @@ use codex_protocol::protocol::InitialHistory;
 use codex_protocol::user_input::UserInput;
 use codex_utils_readiness::Readiness;
 use codex_utils_readiness::ReadinessFlag;
+use codex_utils_tokenizer::Tokenizer;
 use reqwest::StatusCode;
 
 /// The high-level interface to the Codex system.
@@ -245,9 +258,13 @@ impl Codex {
         )
         .await;
 
-        let exec_policy = load_exec_policy_for_features(&config.features, &config.codex_home)
-            .await
-            .map_err(|err| CodexErr::Fatal(format!("failed to load execpolicy: {err}")))?;
+        let exec_policy = load_exec_policy_for_features(
+            &config.features,
+            &config.codex_home,
+            project_rules_dir(&config).as_deref(),
+        )
+        .await
+        .map_err(|err| CodexErr::Fatal(format!("failed to load execpolicy: {err}")))?;
         let exec_policy = Arc::new(RwLock::new(exec_policy));
 
         let config = Arc::new(config);
@@ -256,6 +273,15 @@ impl Codex {
         {
             error!("failed to refresh available models: {err:?}");
         }
+        if config.features.enabled(Feature::RemoteModels) {
+            // Discovered models only feed the model pickers, so listing other
+            // providers must not delay the session.
+            let models_manager = Arc::clone(&models_manager);
+            let config = Arc::clone(&config);
+            tokio::spawn(async move {
+                models_manager.refresh_provider_models(&config).await;
+            });
+        }
         let model = models_manager.get_model(&config.model, &config).await;
         let session_configuration = SessionConfiguration {
             provider: config.model_provider.clone(),
@@ -378,6 +404,7 @@ pub(crate) struct TurnContext {
     pub(crate) tool_call_gate: Arc<ReadinessFlag>,
     pub(crate) exec_policy: Arc<RwLock<ExecPolicy>>,
     pub(crate) truncation_policy: TruncationPolicy,
+    pub(crate) tokenizer: Tokenizer,
 }
 
 impl TurnContext {
@@ -482,16 +509,16 @@ impl SessionConfiguration {
                 "SessionConfiguration::apply - checking provider switch"
             );
 
-            if let Some(provider_id) = self
+            if let Some(provider_id) = updates.model_provider.clone().or_else(|| {
+                self.original_config_do_not_use
+                    .preferred_model_provider_id_for_model(
+                        current_provider_id,
+                        &next_configuration.model,
+                    )
+            }) && let Some(provider) = self
                 .original_config_do_not_use
-                .preferred_model_provider_id_for_model(
-                    current_provider_id,
-                    &next_configuration.model,
-                )
-                && let Some(provider) = self
-                    .original_config_do_not_use
-                    .model_providers
-                    .get(&provider_id)
+                .model_providers
+                .get(&provider_id)
             {
                 tracing::info!(
                     from_provider = ?current_provider_id,
@@ -538,6 +565,8 @@ pub(crate) struct SessionSettingsUpdate {
     pub(crate) approval_policy: Option<AskForApproval>,
     pub(crate) sandbox_policy: Option<SandboxPolicy>,
     pub(crate) model: Option<String>,
+    /// Provider to use with `model`, as a key of `model_providers`.
+    pub(crate) model_provider: Option<String>,
     pub(crate) reasoning_effort: Option<Option<ReasoningEffortConfig>>,
     pub(crate) reasoning_summary: Option<ReasoningSummaryConfig>,
     pub(crate) final_output_json_schema: Option<Option<Value>>,
@@ -551,6 +580,7 @@ impl Session {
         let mut per_turn_config = (*config).clone();
         per_turn_config.model_reasoning_effort = session_configuration.model_reasoning_effort;
         per_turn_config.model_reasoning_summary = session_configuration.model_reasoning_summary;
+        per_turn_config.model_provider = session_configuration.provider.clone();
         per_turn_config.features = config.features.clone();
         per_turn_config
     }
@@ -613,6 +643,7 @@ impl Session {
                 per_turn_config.as_ref(),
                 model_family.truncation_policy,
             ),
+            tokenizer: model_family.tokenizer,
         }
     }
 
@@ -731,7 +762,8 @@ impl Session {
                     .await
                     .map(Arc::new);
         }
-        let state = SessionState::new(session_configuration.clone());
+        let mut state = SessionState::new(session_configuration.clone());
+        state.nested_project_docs = NestedProjectDocs::new(&config);
 
         let services = SessionServices {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
@@ -747,6 +779,8 @@ impl Session {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             delegate_adapter,
             skills_manager,
+            lsp_manager: Arc::new(LspManager::from_config(&config)),
+            file_snapshots: Arc::new(FileSnapshotStore::new(&config.codex_home)),
         };
 
         let sess = Arc::new(Session {
@@ -897,6 +931,10 @@ impl Session {
                     self.record_into_history(&reconstructed_history, &turn_context)
                         .await;
                 }
+                self.restore_nested_project_docs(&reconstructed_history)
+                    .await;
+                self.restore_pinned_messages(&rollout_items, &reconstructed_history)
+                    .await;
 
                 // If persisting, persist all rollout items as-is (recorder filters)
                 if persist && !rollout_items.is_empty() {
@@ -1015,6 +1053,24 @@ impl Session {
         final_output_json_schema: Option<Option<Value>>,
         sandbox_policy_changed: bool,
     ) -> Arc<TurnContext> {
+        Arc::new(
+            self.build_turn_from_configuration(
+                sub_id,
+                session_configuration,
+                final_output_json_schema,
+                sandbox_policy_changed,
+            )
+            .await,
+        )
+    }
+
+    async fn build_turn_from_configuration(
+        &self,
+        sub_id: String,
+        session_configuration: SessionConfiguration,
+        final_output_json_schema: Option<Option<Value>>,
+        sandbox_policy_changed: bool,
+    ) -> TurnContext {
         let per_turn_config = Self::build_per_turn_config(&session_configuration);
 
         if sandbox_policy_changed {
@@ -1066,6 +1122,80 @@ impl Session {
             });
         }
 
+        turn_context
+    }
+
+    /// Builds a copy of `parent` that talks to the fallback `target` instead
+    /// of the session's provider. The session configuration itself is left
+    /// untouched, so the next task starts on the primary provider again.
+    async fn new_fallback_turn(
+        &self,
+        parent: &TurnContext,
+        target: &FallbackTarget,
+    ) -> Arc<TurnContext> {
+        self.new_rerouted_turn(
+            parent,
+            &Some(target.model.clone()),
+            target.model_provider.clone(),
+            None,
+        )
+        .await
+    }
+
+    /// Builds a copy of `parent` for an auxiliary `task` routed through
+    /// `[models.tasks]`, or `None` when the task has no route.
+    pub(crate) async fn new_task_turn(
+        &self,
+        parent: &TurnContext,
+        task: &AuxiliaryTask,
+    ) -> Option<Arc<TurnContext>> {
+        let route = parent.client.config().task_models.get(task).cloned()?;
+        Some(
+            self.new_rerouted_turn(
+                parent,
+                &Some(route.model),
+                route.model_provider,
+                route.model_reasoning_effort,
+            )
+            .await,
+        )
+    }
+
+    async fn new_rerouted_turn(
+        &self,
+        parent: &TurnContext,
+        model: &Option<String>,
+        provider: ModelProviderInfo,
+        reasoning_effort: Option<ReasoningEffortConfig>,
+    ) -> Arc<TurnContext> {
+        let mut session_configuration = {
+            let state = self.state.lock().await;
+            state.session_configuration.clone()
+        };
+        let per_turn_config = Self::build_per_turn_config(&session_configuration);
+        session_configuration.model = self
+            .services
+            .models_manager
+            .get_model(model, &per_turn_config)
+            .await;
+        session_configuration.provider = provider;
+        if reasoning_effort.is_some() {
+            session_configuration.model_reasoning_effort = reasoning_effort;
+        }
+
+        let mut turn_context = self
+            .build_turn_from_configuration(
+                parent.sub_id.clone(),
+                session_configuration,
+                Some(parent.final_output_json_schema.clone()),
+                false,
+            )
+            .await;
+        // Keep task-specific prompts (for example review mode) intact.
+        turn_context.base_instructions = parent.base_instructions.clone();
+        turn_context.developer_instructions = parent.developer_instructions.clone();
+        turn_context.user_instructions = parent.user_instructions.clone();
+        turn_context.tool_call_gate = Arc::clone(&parent.tool_call_gate);
         Arc::new(turn_context)
     }
 
@@ -1160,10 +1290,12 @@ impl Session {
     }
 
     /// Adds an execpolicy amendment to both the in-memory and on-disk policies so future
-    /// commands can use the newly approved prefix.
+    /// commands can use the newly approved prefix. `target` picks between the user's
+    /// rules and the project's checked-in rules.
     pub(crate) async fn persist_execpolicy_amendment(
         &self,
         amendment: &ExecPolicyAmendment,
+        target: ExecPolicyAmendmentTarget,
     ) -> Result<(), ExecPolicyUpdateError> {
         let features = self.features.clone();
         let (codex_home, current_policy) = {
@@ -1187,6 +1319,7 @@ impl Session {
             &codex_home,
             &current_policy,
             &amendment.command,
+            target,
         )
         .await?;
 
@@ -1227,6 +1360,13 @@ impl Session {
         }
 
         let parsed_cmd = parse_command(&command);
+        let project_execpolicy_available = proposed_execpolicy_amendment.is_some()
+            && turn_context
+                .exec_policy
+                .read()
+                .await
+                .project_rules_dir()
+                .is_some();
         let event = EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
             call_id,
             turn_id: turn_context.sub_id.clone(),
@@ -1234,6 +1374,7 @@ impl Session {
             cwd,
             reason,
             proposed_execpolicy_amendment,
+            project_execpolicy_available,
             parsed_cmd,
         });
         self.send_event(turn_context, event).await;
@@ -1324,6 +1465,81 @@ impl Session {
         self.send_raw_response_items(turn_context, items).await;
     }
 
+    /// Notes files or directories the agent touched, so AGENTS.md files in
+    /// directories below the cwd are loaded before the next model request.
+    pub(crate) async fn note_touched_paths<P: AsRef<Path>>(
+        &self,
+        paths: impl IntoIterator<Item = P>,
+    ) {
+        let mut state = self.state.lock().await;
+        for path in paths {
+            state.nested_project_docs.note_path(path.as_ref());
+        }
+    }
+
+    /// Records AGENTS.md files found in directories touched since the last
+    /// request as developer messages, so they are persisted to the rollout.
+    async fn record_nested_project_docs(&self, turn_context: &TurnContext) {
+        let search = {
+            let mut state = self.state.lock().await;
+            state.nested_project_docs.take_search()
+        };
+        let Some(search) = search else {
+            return;
+        };
+        let docs = match read_nested_project_docs(search).await {
+            Ok(docs) => docs,
+            Err(e) => {
+                warn!("failed to read nested project docs: {e:#}");
+                return;
+            }
+        };
+        if docs.is_empty() {
+            return;
+        }
+
+        self.state
+            .lock()
+            .await
+            .nested_project_docs
+            .record_loaded(&docs);
+        let items: Vec<ResponseItem> = docs.into_iter().map(ResponseItem::from).collect();
+        self.record_conversation_items(turn_context, &items).await;
+    }
+
+    /// Marks nested AGENTS.md files already present in a resumed history as
+    /// loaded so they are not injected again.
+    async fn restore_nested_project_docs(&self, history: &[ResponseItem]) {
+        let mut state = self.state.lock().await;
+        state.nested_project_docs.mark_loaded_from_history(history);
+    }
+
+    /// Restores the pins from the last recorded update, falling back to the
+    /// pinned context of the last structured compaction.
+    async fn restore_pinned_messages(
+        &self,
+        rollout_items: &[RolloutItem],
+        history: &[ResponseItem],
+    ) {
+        let recorded = rollout_items.iter().rev().find_map(|item| match item {
+            RolloutItem::EventMsg(EventMsg::PinnedMessagesUpdated(event)) => {
+                Some(event.messages.clone())
+            }
+            _ => None,
+        });
+        let pinned = recorded.or_else(|| {
+            history
+                .iter()
+                .rev()
+                .find_map(PinnedContext::from_item)
+                .map(|pinned| pinned.messages)
+        });
+        if let Some(pinned) = pinned {
+            let mut state = self.state.lock().await;
+            state.pinned_messages = pinned;
+        }
+    }
+
     fn reconstruct_history_from_rollout(
         &self,
         turn_context: &TurnContext,
@@ -1336,6 +1552,7 @@ impl Session {
                     history.record_items(
                         std::iter::once(response_item),
                         turn_context.truncation_policy,
+                        turn_context.tokenizer,
                     );
                 }
                 RolloutItem::Compacted(compacted) => {
@@ -1349,6 +1566,7 @@ impl Session {
                             self.build_initial_context(turn_context),
                             &user_messages,
                             &compacted.message,
+                            turn_context.tokenizer,
                         );
                         history.replace(rebuilt);
                     }
@@ -1366,7 +1584,11 @@ impl Session {
         turn_context: &TurnContext,
     ) {
         let mut state = self.state.lock().await;
-        state.record_items(items.iter(), turn_context.truncation_policy);
+        state.record_items(
+            items.iter(),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
     }
 
     pub(crate) async fn record_model_warning(&self, message: impl Into<String>, ctx: &TurnContext) {
@@ -1388,6 +1610,10 @@ impl Session {
 
     pub(crate) async fn replace_history(&self, items: Vec<ResponseItem>) {
         let mut state = self.state.lock().await;
+        // Compaction and undo can drop nested AGENTS.md messages; search their
+        // directories again unless the new history still carries them.
+        state.nested_project_docs.forget_loaded();
+        state.nested_project_docs.mark_loaded_from_history(&items);
         state.replace_history(items);
     }
 
@@ -1471,11 +1697,29 @@ impl Session {
         }
     }
 
+    /// Records a generated session title and summary in the rollout.
+    pub(crate) async fn record_session_title(&self, title: String, summary: Option<String>) {
+        let recorder = {
+            let guard = self.services.rollout.lock().await;
+            guard.clone()
+        };
+        if let Some(rec) = recorder
+            && let Err(e) = rec.record_session_title(title, summary).await
+        {
+            error!("failed to record session title: {e:#}");
+        }
+    }
+
     pub(crate) async fn clone_history(&self) -> ContextManager {
         let state = self.state.lock().await;
         state.clone_history()
     }
 
+    pub(crate) async fn pinned_messages(&self) -> Vec<String> {
+        let state = self.state.lock().await;
+        state.pinned_messages.clone()
+    }
+
     pub(crate) async fn update_token_usage_info(
         &self,
         turn_context: &TurnContext,
@@ -1507,10 +1751,8 @@ impl Session {
     }
 
     pub(crate) async fn recompute_token_usage(&self, turn_context: &TurnContext) {
-        let Some(estimated_total_tokens) = self
-            .clone_history()
-            .await
-            .estimate_token_count(turn_context)
+        let Some(estimated_total_tokens) =
+            self.state.lock().await.estimate_token_count(turn_context)
         else {
             return;
         };
@@ -1771,6 +2013,7 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 approval_policy,
                 sandbox_policy,
                 model,
+                model_provider,
                 effort,
                 summary,
             } => {
@@ -1782,6 +2025,7 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                         approval_policy,
                         sandbox_policy,
                         model,
+                        model_provider,
                         reasoning_effort: effort,
                         reasoning_summary: summary,
                         ..Default::default()
@@ -1818,9 +2062,21 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::Undo => {
                 handlers::undo(&sess, sub.id.clone()).await;
             }
+            Op::ListCheckpoints => {
+                handlers::list_checkpoints(&sess, sub.id.clone()).await;
+            }
+            Op::RestoreCheckpoint { id, paths } => {
+                handlers::restore_checkpoint(&sess, sub.id.clone(), id, paths).await;
+            }
             Op::Compact => {
                 handlers::compact(&sess, sub.id.clone()).await;
             }
+            Op::PinMessage { message } => {
+                handlers::pin_message(&sess, sub.id.clone(), message).await;
+            }
+            Op::UnpinMessage { message } => {
+                handlers::unpin_message(&sess, sub.id.clone(), &message).await;
+            }
             Op::SetReferenceImages { paths } => {
                 handlers::set_reference_images(&sess, paths).await;
             }
@@ -1866,7 +2122,9 @@ mod handlers {
     use crate::codex::SessionSettingsUpdate;
     use crate::codex::TurnContext;
 
+    use crate::checkpoints::CheckpointSources;
     use crate::codex::spawn_review_thread;
+    use crate::compact;
     use crate::config::Config;
     use crate::features::Feature;
     use crate::mcp::auth::compute_auth_statuses;
@@ -1877,13 +2135,17 @@ mod handlers {
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandTask;
     use codex_protocol::custom_prompts::CustomPrompt;
+    use codex_protocol::protocol::CheckpointRestorePath;
+    use codex_protocol::protocol::CheckpointRestoredEvent;
     use codex_protocol::protocol::CodexErrorInfo;
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::Event;
     use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::ListCheckpointsResponseEvent;
     use codex_protocol::protocol::ListCustomPromptsResponseEvent;
     use codex_protocol::protocol::ListSkillsResponseEvent;
     use codex_protocol::protocol::Op;
+    use codex_protocol::protocol::PinnedMessagesUpdatedEvent;
     use codex_protocol::protocol::ReviewDecision;
     use codex_protocol::protocol::ReviewRequest;
     use codex_protocol::protocol::SkillsListEntry;
@@ -1945,6 +2207,7 @@ mod handlers {
                     approval_policy: Some(approval_policy),
                     sandbox_policy: Some(sandbox_policy),
                     model: Some(model),
+                    model_provider: None,
                     reasoning_effort: Some(effort),
                     reasoning_summary: Some(summary),
                     final_output_json_schema: Some(final_output_json_schema),
@@ -2081,9 +2344,10 @@ mod handlers {
     pub async fn exec_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
         if let ReviewDecision::ApprovedExecpolicyAmendment {
             proposed_execpolicy_amendment,
+            target,
         } = &decision
             && let Err(err) = sess
-                .persist_execpolicy_amendment(proposed_execpolicy_amendment)
+                .persist_execpolicy_amendment(proposed_execpolicy_amendment, *target)
                 .await
         {
             let message = format!("Failed to apply execpolicy amendment: {err}");
@@ -2242,19 +2506,138 @@ mod handlers {
             .await;
     }
 
+    pub async fn list_checkpoints(sess: &Arc<Session>, sub_id: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id.clone()).await;
+        let history = sess.clone_history().await.get_history();
+        let file_snapshots = Arc::clone(&sess.services.file_snapshots);
+        let checkpoints = tokio::task::spawn_blocking(move || {
+            let sources = CheckpointSources {
+                cwd: &turn_context.cwd,
+                file_snapshots: &file_snapshots,
+                ghost_snapshot: &turn_context.ghost_snapshot,
+            };
+            crate::checkpoints::list_checkpoints(&history, &sources)
+        })
+        .await
+        .unwrap_or_else(|err| {
+            warn!("checkpoint listing panicked: {err}");
+            Vec::new()
+        });
+
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::ListCheckpointsResponse(ListCheckpointsResponseEvent { checkpoints }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn restore_checkpoint(
+        sess: &Arc<Session>,
+        sub_id: String,
+        id: String,
+        paths: Vec<CheckpointRestorePath>,
+    ) {
+        // A running turn could be writing the same files.
+        if sess.active_turn.lock().await.is_some() {
+            let event = Event {
+                id: sub_id,
+                msg: EventMsg::CheckpointRestored(CheckpointRestoredEvent {
+                    id,
+                    success: false,
+                    message: Some(
+                        "Cannot restore a checkpoint while a turn is running; wait for it to finish or interrupt it first."
+                            .to_string(),
+                    ),
+                    paths: Vec::new(),
+                }),
+            };
+            sess.send_event_raw(event).await;
+            return;
+        }
+
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id.clone()).await;
+        let history = sess.clone_history().await.get_history();
+        let file_snapshots = Arc::clone(&sess.services.file_snapshots);
+        let checkpoint_id = id.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let sources = CheckpointSources {
+                cwd: &turn_context.cwd,
+                file_snapshots: &file_snapshots,
+                ghost_snapshot: &turn_context.ghost_snapshot,
+            };
+            crate::checkpoints::restore_checkpoint(&history, &sources, &checkpoint_id, &paths)
+        })
+        .await
+        .unwrap_or_else(|err| Err(format!("Checkpoint restore panicked: {err}")));
+
+        let restored = match result {
+            Ok(paths) => CheckpointRestoredEvent {
+                message: Some(format!("Restored {} file(s) from checkpoint.", paths.len())),
+                id,
+                success: true,
+                paths,
+            },
+            Err(message) => {
+                warn!("{message}");
+                CheckpointRestoredEvent {
+                    id,
+                    success: false,
+                    message: Some(message),
+                    paths: Vec::new(),
+                }
+            }
+        };
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::CheckpointRestored(restored),
+        };
+        sess.send_event_raw(event).await;
+    }
+
     pub async fn compact(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
 
         sess.spawn_task(
             Arc::clone(&turn_context),
             vec![UserInput::Text {
-                text: turn_context.compact_prompt().to_string(),
+                text: compact::compaction_prompt(sess, &turn_context),
             }],
             CompactTask,
         )
         .await;
     }
 
+    pub async fn pin_message(sess: &Arc<Session>, sub_id: String, message: String) {
+        if message.trim().is_empty() {
+            return;
+        }
+        let messages = {
+            let mut state = sess.state.lock().await;
+            state.pin_message(message);
+            state.pinned_messages.clone()
+        };
+        send_pinned_messages(sess, sub_id, messages).await;
+    }
+
+    pub async fn unpin_message(sess: &Arc<Session>, sub_id: String, message: &str) {
+        let messages = {
+            let mut state = sess.state.lock().await;
+            state.unpin_message(message);
+            state.pinned_messages.clone()
+        };
+        send_pinned_messages(sess, sub_id, messages).await;
+    }
+
+    /// Sends the current pins; the event is persisted so resumed sessions
+    /// restore them.
+    async fn send_pinned_messages(sess: &Arc<Session>, sub_id: String, messages: Vec<String>) {
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::PinnedMessagesUpdated(PinnedMessagesUpdatedEvent { messages }),
+        })
+        .await;
+    }
+
     pub async fn shutdown(sess: &Arc<Session>, sub_id: String) -> bool {
         sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
         sess.services
@@ -2331,7 +2714,10 @@ async fn spawn_review_thread(
     sub_id: String,
     resolved: crate::review_prompts::ResolvedReviewRequest,
 ) {
-    let model = config.review_model.clone();
+    let route = config.task_models.get(&AuxiliaryTask::Review).cloned();
+    let model = route
+        .as_ref()
+        .map_or_else(|| config.review_model.clone(), |route| route.model.clone());
     let review_model_family = sess
         .services
         .models_manager
@@ -2350,20 +2736,28 @@ async fn spawn_review_thread(
 
     let base_instructions = REVIEW_PROMPT.to_string();
     let review_prompt = resolved.prompt.clone();
-    let provider = parent_turn_context.client.get_provider();
+    let provider = route.as_ref().map_or_else(
+        || parent_turn_context.client.get_provider(),
+        |route| route.model_provider.clone(),
+    );
     let auth_manager = parent_turn_context.client.get_auth_manager();
     let model_family = review_model_family.clone();
 
     // Build per‑turn client with the requested model/family.
     let mut per_turn_config = (*config).clone();
-    per_turn_config.model_reasoning_effort = Some(ReasoningEffortConfig::Low);
+    per_turn_config.model_reasoning_effort = Some(
+        route
+            .as_ref()
+            .and_then(|route| route.model_reasoning_effort)
+            .unwrap_or(ReasoningEffortConfig::Low),
+    );
     per_turn_config.model_reasoning_summary = ReasoningSummaryConfig::Detailed;
     per_turn_config.features = review_features.clone();
 
-    let otel_manager = parent_turn_context.client.get_otel_manager().with_model(
-        config.review_model.as_str(),
-        review_model_family.slug.as_str(),
-    );
+    let otel_manager = parent_turn_context
+        .client
+        .get_otel_manager()
+        .with_model(model.as_str(), review_model_family.slug.as_str());
 
     let per_turn_config = Arc::new(per_turn_config);
     let client = ModelClient::new(
@@ -2396,6 +2790,7 @@ async fn spawn_review_thread(
         tool_call_gate: Arc::new(ReadinessFlag::new()),
         exec_policy: parent_turn_context.exec_policy.clone(),
         truncation_policy: TruncationPolicy::new(&per_turn_config, model_family.truncation_policy),
+        tokenizer: model_family.tokenizer,
     };
 
     // Seed the child task with the review prompt as the initial user message.
@@ -2453,7 +2848,7 @@ fn errors_to_info(errors: &[SkillError]) -> Vec<SkillErrorInfo> {
 ///
 pub(crate) async fn run_task(
     sess: Arc<Session>,
-    turn_context: Arc<TurnContext>,
+    mut turn_context: Arc<TurnContext>,
     input: Vec<UserInput>,
     cancellation_token: CancellationToken,
 ) -> Option<String> {
@@ -2507,6 +2902,9 @@ pub(crate) async fn run_task(
     // Although from the perspective of codex.rs, TurnDiffTracker has the lifecycle of a Task which contains
     // many turns, from the perspective of the user, it is a single turn.
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
+    let mut fallback_targets = turn_context.client.config().fallback.clone().into_iter();
+    // Set once a fallback provider answers for history another provider wrote.
+    let mut strip_provider_state = false;
 
     loop {
         // Note that pending_input would be something like a message the user
@@ -2523,7 +2921,13 @@ pub(crate) async fn run_task(
         let turn_input: Vec<ResponseItem> = {
             sess.record_conversation_items(&turn_context, &pending_input)
                 .await;
-            sess.clone_history().await.get_history_for_prompt()
+            sess.record_nested_project_docs(&turn_context).await;
+            let history = sess.clone_history().await.get_history_for_prompt();
+            if strip_provider_state {
+                strip_provider_bound_state(history)
+            } else {
+                history
+            }
         };
 
         let turn_input_messages = turn_input
@@ -2583,6 +2987,25 @@ pub(crate) async fn run_task(
                 state.history.replace_last_turn_images("Invalid image");
             }
             Err(e) => {
+                if should_fall_back(&e)
+                    && let Some(target) = fallback_targets.next()
+                {
+                    let failed_provider = turn_context.client.get_provider();
+                    let failed_model = turn_context.client.get_model();
+                    strip_provider_state |= target.model_provider != failed_provider;
+                    turn_context = sess.new_fallback_turn(&turn_context, &target).await;
+                    let message = format!(
+                        "{failed_model} via {} failed ({e}); retrying with fallback profile `{}` ({} via {}).",
+                        failed_provider.name,
+                        target.profile,
+                        turn_context.client.get_model(),
+                        target.model_provider.name,
+                    );
+                    warn!("{message}");
+                    sess.send_event(&turn_context, EventMsg::Warning(WarningEvent { message }))
+                        .await;
+                    continue;
+                }
                 info!("Turn error: {e:#}");
                 let event = EventMsg::Error(e.to_error_event(None));
                 sess.send_event(&turn_context, event).await;
@@ -2596,11 +3019,12 @@ pub(crate) async fn run_task(
 }
 
 async fn run_auto_compact(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) {
-    if should_use_remote_compact_task(sess.as_ref(), &turn_context.client.get_provider()) {
+    if should_use_remote_compact_task(sess.as_ref(), turn_context.as_ref()) {
         run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
     } else {
         run_inline_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
     }
+    session_title::refresh_session_title(sess, turn_context);
 }
 
 #[instrument(level = "trace",
@@ -2741,6 +3165,62 @@ async fn run_turn(
     }
 }
 
+/// Whether a turn that failed with `err` after exhausting its retries should
+/// be retried on the next fallback provider. Errors caused by the request
+/// itself (or by the user) would fail the same way anywhere.
+fn should_fall_back(err: &CodexErr) -> bool {
+    match err {
+        CodexErr::UsageLimitReached(_)
+        | CodexErr::UsageNotIncluded
+        | CodexErr::QuotaExceeded
+        | CodexErr::RetryLimit(_)
+        | CodexErr::Stream(..)
+        | CodexErr::Timeout
+        | CodexErr::ConnectionFailed(_)
+        | CodexErr::ResponseStreamFailed(_)
+        | CodexErr::InternalServerError => true,
+        CodexErr::UnexpectedStatus(err) => {
+            err.status == StatusCode::TOO_MANY_REQUESTS || err.status.is_server_error()
+        }
+        _ => false,
+    }
+}
+
+/// Drops history state that only the provider which wrote it can read:
+/// reasoning items (their encrypted content is bound to the provider's keys)
+/// and Gemini thought signatures. Fallback turns send the rest, which every
+/// wire API understands.
+fn strip_provider_bound_state(items: Vec<ResponseItem>) -> Vec<ResponseItem> {
+    items
+        .into_iter()
+        .filter_map(|item| match item {
+            ResponseItem::Reasoning { .. } => None,
+            ResponseItem::Message {
+                id, role, content, ..
+            } => Some(ResponseItem::Message {
+                id,
+                role,
+                content,
+                thought_signature: None,
+            }),
+            ResponseItem::FunctionCall {
+                id,
+                name,
+                arguments,
+                call_id,
+                ..
+            } => Some(ResponseItem::FunctionCall {
+                id,
+                name,
+                arguments,
+                call_id,
+                thought_signature: None,
+            }),
+            item => Some(item),
+        })
+        .collect()
+}
+
 #[derive(Debug)]
 struct TurnRunResult {
     needs_follow_up: bool,
@@ -2793,6 +3273,7 @@ async fn try_run_turn(
 
     sess.persist_rollout_items(&[rollout_item]).await;
 
+    let request_started = Instant::now();
     let mut stream = turn_context
         .client
         .clone()
@@ -2813,6 +3294,7 @@ async fn try_run_turn(
     let mut last_agent_message: Option<String> = None;
     let mut active_item: Option<TurnItem> = None;
     let mut should_emit_turn_diff = false;
+    let mut first_output_seen = false;
     let receiving_span = trace_span!("receiving_stream");
     let outcome: CodexResult<TurnRunResult> = loop {
         let handle_responses = trace_span!(
@@ -2846,6 +3328,20 @@ async fn try_run_turn(
         sess.services
             .otel_manager
             .record_responses(&handle_responses, &event);
+        if !first_output_seen
+            && matches!(
+                event,
+                ResponseEvent::OutputTextDelta(_)
+                    | ResponseEvent::ReasoningSummaryDelta { .. }
+                    | ResponseEvent::ReasoningContentDelta { .. }
+                    | ResponseEvent::OutputItemDone(_)
+            )
+        {
+            first_output_seen = true;
+            sess.services
+                .otel_manager
+                .record_time_to_first_token(request_started.elapsed());
+        }
 
         match event {
             ResponseEvent::Created => {}
@@ -3060,6 +3556,7 @@ mod tests {
     use crate::protocol::CompactedItem;
     use crate::protocol::CreditsSnapshot;
     use crate::protocol::InitialHistory;
+    use crate::protocol::PinnedMessagesUpdatedEvent;
     use crate::protocol::RateLimitSnapshot;
     use crate::protocol::RateLimitWindow;
     use crate::protocol::ResumedHistory;
@@ -3225,6 +3722,34 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[tokio::test]
+    async fn record_initial_history_restores_recorded_pins() {
+        let (session, turn_context) = make_session_and_context().await;
+        let (mut rollout_items, _) = sample_rollout(&session, &turn_context);
+        for messages in [
+            vec!["keep the API".to_string()],
+            vec!["keep the API".to_string(), "no new deps".to_string()],
+            vec!["no new deps".to_string()],
+        ] {
+            rollout_items.push(RolloutItem::EventMsg(EventMsg::PinnedMessagesUpdated(
+                PinnedMessagesUpdatedEvent { messages },
+            )));
+        }
+
+        session
+            .record_initial_history(InitialHistory::Resumed(ResumedHistory {
+                conversation_id: ConversationId::default(),
+                history: rollout_items,
+                rollout_path: PathBuf::from("/tmp/resume.jsonl"),
+            }))
+            .await;
+
+        assert_eq!(
+            session.pinned_messages().await,
+            vec!["no new deps".to_string()]
+        );
+    }
+
     #[tokio::test]
     async fn set_rate_limits_retains_previous_credits() {
         let codex_home = tempfile::tempdir().expect("create temp dir");
@@ -3397,7 +3922,11 @@ mod tests {
         };
         let (_, turn_context) = make_session_and_context().await;
 
-        let out = format_exec_output_str(&exec, turn_context.truncation_policy);
+        let out = format_exec_output_str(
+            &exec,
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
 
         assert_eq!(
             out,
@@ -3550,6 +4079,8 @@ mod tests {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             delegate_adapter: None,
             skills_manager,
+            lsp_manager: Arc::new(LspManager::from_config(&config)),
+            file_snapshots: Arc::new(FileSnapshotStore::new(&config.codex_home)),
         };
 
         let turn_context = Session::make_turn_context(
@@ -3637,6 +4168,8 @@ mod tests {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             delegate_adapter: None,
             skills_manager,
+            lsp_manager: Arc::new(LspManager::from_config(&config)),
+            file_snapshots: Arc::new(FileSnapshotStore::new(&config.codex_home)),
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
@@ -3970,7 +4503,11 @@ mod tests {
         for item in &initial_context {
             rollout_items.push(RolloutItem::ResponseItem(item.clone()));
         }
-        live_history.record_items(initial_context.iter(), turn_context.truncation_policy);
+        live_history.record_items(
+            initial_context.iter(),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
 
         let user1 = ResponseItem::Message {
             id: None,
@@ -3980,7 +4517,11 @@ mod tests {
             }],
             thought_signature: None,
         };
-        live_history.record_items(std::iter::once(&user1), turn_context.truncation_policy);
+        live_history.record_items(
+            std::iter::once(&user1),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
         rollout_items.push(RolloutItem::ResponseItem(user1.clone()));
 
         let assistant1 = ResponseItem::Message {
@@ -3991,7 +4532,11 @@ mod tests {
             }],
             thought_signature: None,
         };
-        live_history.record_items(std::iter::once(&assistant1), turn_context.truncation_policy);
+        live_history.record_items(
+            std::iter::once(&assistant1),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
         rollout_items.push(RolloutItem::ResponseItem(assistant1.clone()));
 
         let summary1 = "summary one";
@@ -4001,6 +4546,7 @@ mod tests {
             session.build_initial_context(turn_context),
             &user_messages1,
             summary1,
+            turn_context.tokenizer,
         );
         live_history.replace(rebuilt1);
         rollout_items.push(RolloutItem::Compacted(CompactedItem {
@@ -4016,7 +4562,11 @@ mod tests {
             }],
             thought_signature: None,
         };
-        live_history.record_items(std::iter::once(&user2), turn_context.truncation_policy);
+        live_history.record_items(
+            std::iter::once(&user2),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
         rollout_items.push(RolloutItem::ResponseItem(user2.clone()));
 
         let assistant2 = ResponseItem::Message {
@@ -4027,7 +4577,11 @@ mod tests {
             }],
             thought_signature: None,
         };
-        live_history.record_items(std::iter::once(&assistant2), turn_context.truncation_policy);
+        live_history.record_items(
+            std::iter::once(&assistant2),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
         rollout_items.push(RolloutItem::ResponseItem(assistant2.clone()));
 
         let summary2 = "summary two";
@@ -4037,6 +4591,7 @@ mod tests {
             session.build_initial_context(turn_context),
             &user_messages2,
             summary2,
+            turn_context.tokenizer,
         );
         live_history.replace(rebuilt2);
         rollout_items.push(RolloutItem::Compacted(CompactedItem {
@@ -4052,7 +4607,11 @@ mod tests {
             }],
             thought_signature: None,
         };
-        live_history.record_items(std::iter::once(&user3), turn_context.truncation_policy);
+        live_history.record_items(
+            std::iter::once(&user3),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
         rollout_items.push(RolloutItem::ResponseItem(user3.clone()));
 
         let assistant3 = ResponseItem::Message {
@@ -4063,7 +4622,11 @@ mod tests {
             }],
             thought_signature: None,
         };
-        live_history.record_items(std::iter::once(&assistant3), turn_context.truncation_policy);
+        live_history.record_items(
+            std::iter::once(&assistant3),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
         rollout_items.push(RolloutItem::ResponseItem(assistant3.clone()));
 
         (rollout_items, live_history.get_history())
@@ -4245,4 +4808,38 @@ mod tests {
 
         pretty_assertions::assert_eq!(output, expected);
     }
+
+    #[test]
+    fn fallback_history_drops_provider_bound_state() {
+        let message = |thought_signature: Option<String>| ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: vec![ContentItem::OutputText {
+                text: "done".to_string(),
+            }],
+            thought_signature,
+        };
+        let call = |thought_signature: Option<String>| ResponseItem::FunctionCall {
+            id: None,
+            name: "shell".to_string(),
+            arguments: "{}".to_string(),
+            call_id: "call-1".to_string(),
+            thought_signature,
+        };
+        let history = vec![
+            ResponseItem::Reasoning {
+                id: "rs-1".to_string(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some("opaque".to_string()),
+            },
+            message(Some("sig-1".to_string())),
+            call(Some("sig-2".to_string())),
+        ];
+
+        pretty_assertions::assert_eq!(
+            strip_provider_bound_state(history),
+            vec![message(None), call(None)]
+        );
+    }
 }
@@ -1,11 +1,17 @@
 use std::sync::Arc;
 
-use crate::ModelProviderInfo;
 use crate::Prompt;
+use crate::client::ModelClient;
 use crate::client_common::ResponseEvent;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::codex::get_last_assistant_message_from_turn;
+use crate::compact_structured::PinnedContext;
+use crate::compact_structured::STRUCTURED_SUMMARIZATION_PROMPT;
+use crate::compact_structured::build_structured_compacted_history;
+use crate::compact_structured::elide_old_tool_outputs;
+use crate::compact_structured::recent_tool_calls;
+use crate::config::types::AuxiliaryTask;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::features::Feature;
@@ -16,7 +22,6 @@ use crate::protocol::TaskStartedEvent;
 use crate::protocol::TurnContextItem;
 use crate::protocol::WarningEvent;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::approx_token_count;
 use crate::truncate::truncate_text;
 use crate::util::backoff;
 use codex_protocol::items::TurnItem;
@@ -25,6 +30,7 @@ use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::user_input::UserInput;
+use codex_utils_tokenizer::Tokenizer;
 use futures::prelude::*;
 use tracing::error;
 
@@ -32,18 +38,36 @@ pub const SUMMARIZATION_PROMPT: &str = include_str!("../templates/compact/prompt
 pub const SUMMARY_PREFIX: &str = include_str!("../templates/compact/summary_prefix.md");
 const COMPACT_USER_MESSAGE_MAX_TOKENS: usize = 20_000;
 
+/// Remote compaction runs on the session's own model, so a `compact` route in
+/// `[models.tasks]` always selects the local path.
 pub(crate) fn should_use_remote_compact_task(
     session: &Session,
-    provider: &ModelProviderInfo,
+    turn_context: &TurnContext,
 ) -> bool {
-    provider.is_openai() && session.enabled(Feature::RemoteCompaction)
+    turn_context.client.get_provider().is_openai()
+        && session.enabled(Feature::RemoteCompaction)
+        && !turn_context
+            .client
+            .config()
+            .task_models
+            .contains_key(&AuxiliaryTask::Compact)
+}
+
+/// Prompt for a local compaction. A configured `compact_prompt` always wins;
+/// otherwise structured compaction asks for a sectioned summary.
+pub(crate) fn compaction_prompt(session: &Session, turn_context: &TurnContext) -> String {
+    if turn_context.compact_prompt.is_none() && session.enabled(Feature::StructuredCompaction) {
+        STRUCTURED_SUMMARIZATION_PROMPT.to_string()
+    } else {
+        turn_context.compact_prompt().to_string()
+    }
 }
 
 pub(crate) async fn run_inline_auto_compact_task(
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
 ) {
-    let prompt = turn_context.compact_prompt().to_string();
+    let prompt = compaction_prompt(&sess, &turn_context);
     let input = vec![UserInput::Text { text: prompt }];
 
     run_compact_task_inner(sess, turn_context, input).await;
@@ -67,16 +91,33 @@ async fn run_compact_task_inner(
     input: Vec<UserInput>,
 ) {
     let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input);
+    let structured = sess.enabled(Feature::StructuredCompaction);
 
     let mut history = sess.clone_history().await;
+    if structured {
+        let mut items = history.get_history();
+        elide_old_tool_outputs(&mut items, turn_context.tokenizer);
+        history.replace(items);
+    }
     history.record_items(
         &[initial_input_for_turn.into()],
         turn_context.truncation_policy,
+        turn_context.tokenizer,
     );
 
     let mut truncated_count = 0usize;
 
-    let max_retries = turn_context.client.get_provider().stream_max_retries();
+    // The summary may come from a model routed in `[models.tasks]`. History
+    // stays on the session's turn, but a routed model's usage and rate limits
+    // are its own and do not count against the session's context window.
+    let routed_summarizer = sess
+        .new_task_turn(turn_context.as_ref(), &AuxiliaryTask::Compact)
+        .await;
+    let track_usage = routed_summarizer.is_none();
+    let summarizer = routed_summarizer
+        .map(|turn| turn.client.clone())
+        .unwrap_or_else(|| turn_context.client.clone());
+    let max_retries = summarizer.get_provider().stream_max_retries();
     let mut retries = 0;
 
     let rollout_item = RolloutItem::TurnContext(TurnContextItem {
@@ -96,7 +137,14 @@ async fn run_compact_task_inner(
             reference_images: Vec::new(),
             ..Default::default()
         };
-        let attempt_result = drain_to_completed(&sess, turn_context.as_ref(), &prompt).await;
+        let attempt_result = drain_to_completed(
+            &sess,
+            turn_context.as_ref(),
+            &summarizer,
+            &prompt,
+            track_usage,
+        )
+        .await;
 
         match attempt_result {
             Ok(()) => {
@@ -125,7 +173,9 @@ async fn run_compact_task_inner(
                     retries = 0;
                     continue;
                 }
-                sess.set_total_tokens_full(turn_context.as_ref()).await;
+                if track_usage {
+                    sess.set_total_tokens_full(turn_context.as_ref()).await;
+                }
                 let event = EventMsg::Error(e.to_error_event(None));
                 sess.send_event(&turn_context, event).await;
                 return;
@@ -158,19 +208,39 @@ async fn run_compact_task_inner(
     let user_messages = collect_user_messages(&history_snapshot);
 
     let initial_context = sess.build_initial_context(turn_context.as_ref());
-    let mut new_history = build_compacted_history(initial_context, &user_messages, &summary_text);
+    let mut new_history = if structured {
+        let pinned = PinnedContext::collect(&history_snapshot, &sess.pinned_messages().await);
+        build_structured_compacted_history(
+            initial_context,
+            &user_messages,
+            pinned,
+            recent_tool_calls(&history_snapshot),
+            &summary_text,
+            turn_context.tokenizer,
+        )
+    } else {
+        build_compacted_history(
+            initial_context,
+            &user_messages,
+            &summary_text,
+            turn_context.tokenizer,
+        )
+    };
     let ghost_snapshots: Vec<ResponseItem> = history_snapshot
         .iter()
         .filter(|item| matches!(item, ResponseItem::GhostSnapshot { .. }))
         .cloned()
         .collect();
     new_history.extend(ghost_snapshots);
+    // Structured histories cannot be rebuilt from the summary alone, so they
+    // are persisted whole.
+    let replacement_history = structured.then(|| new_history.clone());
     sess.replace_history(new_history).await;
     sess.recompute_token_usage(&turn_context).await;
 
     let rollout_item = RolloutItem::Compacted(CompactedItem {
         message: summary_text.clone(),
-        replacement_history: None,
+        replacement_history,
     });
     sess.persist_rollout_items(&[rollout_item]).await;
 
@@ -226,12 +296,14 @@ pub(crate) fn build_compacted_history(
     initial_context: Vec<ResponseItem>,
     user_messages: &[String],
     summary_text: &str,
+    tokenizer: Tokenizer,
 ) -> Vec<ResponseItem> {
     build_compacted_history_with_limit(
         initial_context,
         user_messages,
         summary_text,
         COMPACT_USER_MESSAGE_MAX_TOKENS,
+        tokenizer,
     )
 }
 
@@ -240,6 +312,7 @@ fn build_compacted_history_with_limit(
     user_messages: &[String],
     summary_text: &str,
     max_tokens: usize,
+    tokenizer: Tokenizer,
 ) -> Vec<ResponseItem> {
     let mut selected_messages: Vec<String> = Vec::new();
     if max_tokens > 0 {
@@ -248,12 +321,13 @@ fn build_compacted_history_with_limit(
             if remaining == 0 {
                 break;
             }
-            let tokens = approx_token_count(message);
+            let tokens = tokenizer.count(message);
             if tokens <= remaining {
                 selected_messages.push(message.clone());
                 remaining = remaining.saturating_sub(tokens);
             } else {
-                let truncated = truncate_text(message, TruncationPolicy::Tokens(remaining));
+                let truncated =
+                    truncate_text(message, TruncationPolicy::Tokens(remaining), tokenizer);
                 selected_messages.push(truncated);
                 break;
             }
@@ -288,12 +362,16 @@ fn build_compacted_history_with_limit(
     history
 }
 
+/// Streams `prompt` into the session's history. With `track_usage` unset the
+/// response's token usage and rate limits are left out of the session's.
 async fn drain_to_completed(
     sess: &Session,
     turn_context: &TurnContext,
+    client: &ModelClient,
     prompt: &Prompt,
+    track_usage: bool,
 ) -> CodexResult<()> {
-    let mut stream = turn_context.client.clone().stream(prompt).await?;
+    let mut stream = client.stream(prompt).await?;
     loop {
         let maybe_event = stream.next().await;
         let Some(event) = maybe_event else {
@@ -308,11 +386,15 @@ async fn drain_to_completed(
                     .await;
             }
             Ok(ResponseEvent::RateLimits(snapshot)) => {
-                sess.update_rate_limits(turn_context, snapshot).await;
+                if track_usage {
+                    sess.update_rate_limits(turn_context, snapshot).await;
+                }
             }
             Ok(ResponseEvent::Completed { token_usage, .. }) => {
-                sess.update_token_usage_info(turn_context, token_usage.as_ref())
-                    .await;
+                if track_usage {
+                    sess.update_token_usage_info(turn_context, token_usage.as_ref())
+                        .await;
+                }
                 return Ok(());
             }
             Ok(_) => continue,
@@ -430,6 +512,7 @@ mod tests {
             std::slice::from_ref(&big),
             "SUMMARY",
             max_tokens,
+            Tokenizer::O200kBase,
         );
         assert_eq!(history.len(), 2);
 
@@ -467,7 +550,12 @@ mod tests {
         let user_messages = vec!["first user message".to_string()];
         let summary_text = "summary text";
 
-        let history = build_compacted_history(initial_context, &user_messages, summary_text);
+        let history = build_compacted_history(
+            initial_context,
+            &user_messages,
+            summary_text,
+            Tokenizer::Approximate,
+        );
         assert!(
             !history.is_empty(),
             "expected compacted history to include summary"
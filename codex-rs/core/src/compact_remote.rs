@@ -3,7 +3,9 @@ use std::sync::Arc;
 use crate::Prompt;
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::compact_structured::PinnedContext;
 use crate::error::Result as CodexResult;
+use crate::features::Feature;
 use crate::protocol::CompactedItem;
 use crate::protocol::ContextCompactedEvent;
 use crate::protocol::EventMsg;
@@ -56,9 +58,15 @@ async fn run_remote_compact_task_inner_impl(
         .client
         .compact_conversation_history(&prompt)
         .await?;
+    let history_snapshot = history.get_history();
+    if sess.enabled(Feature::StructuredCompaction) {
+        let pinned = PinnedContext::collect(&history_snapshot, &sess.pinned_messages().await);
+        if !pinned.is_empty() {
+            new_history.push(pinned.into());
+        }
+    }
     // Required to keep `/undo` available after compaction
-    let ghost_snapshots: Vec<ResponseItem> = history
-        .get_history()
+    let ghost_snapshots: Vec<ResponseItem> = history_snapshot
         .iter()
         .filter(|item| matches!(item, ResponseItem::GhostSnapshot { .. }))
         .cloned()
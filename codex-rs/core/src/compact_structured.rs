@@ -0,0 +1,569 @@
+//! Structured local compaction.
+//!
+//! Instead of replacing the whole history with one free-form summary, the
+//! structured mode keeps the current plan, the messages the user pinned, the
+//! files changed so far and the last few tool calls verbatim, shortens older
+//! tool outputs before the summarizer sees them, and asks for a summary split
+//! into fixed sections.
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::plan_tool::StepStatus;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+use codex_utils_tokenizer::Tokenizer;
+use serde_json::Value;
+
+use crate::compact::build_compacted_history;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+pub const STRUCTURED_SUMMARIZATION_PROMPT: &str =
+    include_str!("../templates/compact/structured_prompt.md");
+
+const PINNED_CONTEXT_OPEN_TAG: &str = "<pinned_context>";
+const PINNED_CONTEXT_CLOSE_TAG: &str = "</pinned_context>";
+const PINNED_CONTEXT_PREAMBLE: &str = "Context kept verbatim when the conversation was compacted.";
+
+const PLAN_TAG: &str = "plan";
+const PINNED_MESSAGE_TAG: &str = "pinned_message";
+const FILES_TOUCHED_TAG: &str = "files_touched";
+
+/// The most recent tool outputs are sent to the summarizer unchanged.
+const INTACT_TOOL_OUTPUTS: usize = 10;
+/// Older tool outputs are cut to this many tokens before summarizing.
+const ELIDED_TOOL_OUTPUT_TOKENS: usize = 256;
+/// Number of trailing tool calls kept, with their outputs, after compaction.
+const KEPT_TOOL_CALLS: usize = 3;
+/// Only the most recently touched files are listed in the pinned context.
+const MAX_FILES_TOUCHED: usize = 200;
+
+const PATCH_BEGIN_MARKER: &str = "*** Begin Patch";
+const PATCH_FILE_HEADERS: [&str; 4] = [
+    "*** Add File: ",
+    "*** Update File: ",
+    "*** Delete File: ",
+    "*** Move to: ",
+];
+
+/// Items that survive a structured compaction verbatim, rendered as a single
+/// user message placed right before the summary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct PinnedContext {
+    pub plan: Option<String>,
+    pub messages: Vec<String>,
+    pub files: Vec<String>,
+}
+
+impl PinnedContext {
+    /// Gathers the pinned context for `history`, carrying forward whatever
+    /// an earlier compaction pinned.
+    pub fn collect(history: &[ResponseItem], pinned_messages: &[String]) -> Self {
+        let previous = history
+            .iter()
+            .rev()
+            .find_map(Self::from_item)
+            .unwrap_or_default();
+
+        let mut files = previous.files;
+        for path in touched_files(history) {
+            push_unique(&mut files, path);
+        }
+        if files.len() > MAX_FILES_TOUCHED {
+            files.drain(..files.len() - MAX_FILES_TOUCHED);
+        }
+
+        Self {
+            plan: latest_plan(history).or(previous.plan),
+            messages: pinned_messages.to_vec(),
+            files,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plan.is_none() && self.messages.is_empty() && self.files.is_empty()
+    }
+
+    pub fn is_pinned_context(message: &[ContentItem]) -> bool {
+        if let [ContentItem::InputText { text }] = message {
+            text.starts_with(PINNED_CONTEXT_OPEN_TAG)
+        } else {
+            false
+        }
+    }
+
+    /// Returns the pinned context carried by `item`, e.g. when rebuilding
+    /// session state from a rollout.
+    pub fn from_item(item: &ResponseItem) -> Option<Self> {
+        let ResponseItem::Message { role, content, .. } = item else {
+            return None;
+        };
+        if role != "user" {
+            return None;
+        }
+        let [ContentItem::InputText { text }] = content.as_slice() else {
+            return None;
+        };
+        Self::parse(text)
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let mut rest = text
+            .strip_prefix(PINNED_CONTEXT_OPEN_TAG)?
+            .strip_suffix(PINNED_CONTEXT_CLOSE_TAG)?;
+        let mut context = Self::default();
+        while let Some((tag, body, after)) = next_section(rest) {
+            match tag {
+                PLAN_TAG => context.plan = Some(body.to_string()),
+                PINNED_MESSAGE_TAG => context.messages.push(body.to_string()),
+                FILES_TOUCHED_TAG => {
+                    context.files = body.lines().map(str::to_string).collect();
+                }
+                _ => {}
+            }
+            rest = after;
+        }
+        Some(context)
+    }
+
+    fn render(&self) -> String {
+        let mut sections = vec![
+            PINNED_CONTEXT_OPEN_TAG.to_string(),
+            PINNED_CONTEXT_PREAMBLE.to_string(),
+        ];
+        if let Some(plan) = &self.plan {
+            sections.push(section(PLAN_TAG, plan));
+        }
+        for message in &self.messages {
+            sections.push(section(PINNED_MESSAGE_TAG, message));
+        }
+        if !self.files.is_empty() {
+            sections.push(section(FILES_TOUCHED_TAG, &self.files.join("\n")));
+        }
+        sections.push(PINNED_CONTEXT_CLOSE_TAG.to_string());
+        sections.join("\n")
+    }
+}
+
+impl From<PinnedContext> for ResponseItem {
+    fn from(context: PinnedContext) -> Self {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: context.render(),
+            }],
+            thought_signature: None,
+        }
+    }
+}
+
+/// Shortens all but the most recent tool outputs so the summarizer spends
+/// its context on the conversation rather than on stale command output.
+pub(crate) fn elide_old_tool_outputs(items: &mut [ResponseItem], tokenizer: Tokenizer) {
+    let policy = TruncationPolicy::Tokens(ELIDED_TOOL_OUTPUT_TOKENS);
+    let old_outputs = items
+        .iter_mut()
+        .rev()
+        .filter(|item| {
+            matches!(
+                item,
+                ResponseItem::FunctionCallOutput { .. } | ResponseItem::CustomToolCallOutput { .. }
+            )
+        })
+        .skip(INTACT_TOOL_OUTPUTS);
+    for item in old_outputs {
+        match item {
+            ResponseItem::FunctionCallOutput { output, .. } => {
+                output.content = truncate_text(&output.content, policy, tokenizer);
+            }
+            ResponseItem::CustomToolCallOutput { output, .. } => {
+                *output = truncate_text(output, policy, tokenizer);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The last few tool calls that have an output, each followed by its output,
+/// in their original order.
+pub(crate) fn recent_tool_calls(history: &[ResponseItem]) -> Vec<ResponseItem> {
+    let mut call_ids: Vec<&str> = Vec::new();
+    for item in history.iter().rev() {
+        if call_ids.len() == KEPT_TOOL_CALLS {
+            break;
+        }
+        let output_call_id = match item {
+            ResponseItem::FunctionCallOutput { call_id, .. }
+            | ResponseItem::CustomToolCallOutput { call_id, .. } => call_id.as_str(),
+            _ => continue,
+        };
+        let has_call = history.iter().any(|candidate| match candidate {
+            ResponseItem::FunctionCall { call_id, .. }
+            | ResponseItem::CustomToolCall { call_id, .. } => call_id == output_call_id,
+            _ => false,
+        });
+        if has_call {
+            call_ids.push(output_call_id);
+        }
+    }
+
+    history
+        .iter()
+        .filter(|item| match item {
+            ResponseItem::FunctionCall { call_id, .. }
+            | ResponseItem::CustomToolCall { call_id, .. }
+            | ResponseItem::FunctionCallOutput { call_id, .. }
+            | ResponseItem::CustomToolCallOutput { call_id, .. } => {
+                call_ids.contains(&call_id.as_str())
+            }
+            _ => false,
+        })
+        .cloned()
+        .collect()
+}
+
+/// Builds the history that replaces the conversation: initial context, recent
+/// user messages, the kept tool calls, the pinned context and the summary.
+pub(crate) fn build_structured_compacted_history(
+    initial_context: Vec<ResponseItem>,
+    user_messages: &[String],
+    pinned: PinnedContext,
+    recent_tool_calls: Vec<ResponseItem>,
+    summary_text: &str,
+    tokenizer: Tokenizer,
+) -> Vec<ResponseItem> {
+    let mut history =
+        build_compacted_history(initial_context, user_messages, summary_text, tokenizer);
+    let summary = history.pop();
+    history.extend(recent_tool_calls);
+    if !pinned.is_empty() {
+        history.push(pinned.into());
+    }
+    history.extend(summary);
+    history
+}
+
+/// The plan from the most recent `update_plan` call.
+fn latest_plan(history: &[ResponseItem]) -> Option<String> {
+    history.iter().rev().find_map(|item| match item {
+        ResponseItem::FunctionCall {
+            name, arguments, ..
+        } if name == "update_plan" => serde_json::from_str::<UpdatePlanArgs>(arguments)
+            .ok()
+            .map(|args| render_plan(&args)),
+        _ => None,
+    })
+}
+
+fn render_plan(args: &UpdatePlanArgs) -> String {
+    let mut lines = Vec::new();
+    if let Some(explanation) = args.explanation.as_deref().map(str::trim)
+        && !explanation.is_empty()
+    {
+        lines.push(explanation.to_string());
+    }
+    for item in &args.plan {
+        let status = match item.status {
+            StepStatus::Pending => "pending",
+            StepStatus::InProgress => "in_progress",
+            StepStatus::Completed => "completed",
+        };
+        lines.push(format!("- [{status}] {}", item.step));
+    }
+    lines.join("\n")
+}
+
+/// Paths named by patches in tool calls, in the order they were first seen.
+fn touched_files(history: &[ResponseItem]) -> Vec<String> {
+    let mut files = Vec::new();
+    for item in history {
+        match item {
+            ResponseItem::FunctionCall { arguments, .. } => {
+                if let Ok(value) = serde_json::from_str::<Value>(arguments) {
+                    collect_patch_paths_from_value(&value, &mut files);
+                }
+            }
+            ResponseItem::CustomToolCall { input, .. } => collect_patch_paths(input, &mut files),
+            _ => {}
+        }
+    }
+    files
+}
+
+fn collect_patch_paths_from_value(value: &Value, files: &mut Vec<String>) {
+    match value {
+        Value::String(text) => collect_patch_paths(text, files),
+        Value::Array(values) => {
+            for value in values {
+                collect_patch_paths_from_value(value, files);
+            }
+        }
+        Value::Object(map) => {
+            for value in map.values() {
+                collect_patch_paths_from_value(value, files);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_patch_paths(text: &str, files: &mut Vec<String>) {
+    if !text.contains(PATCH_BEGIN_MARKER) {
+        return;
+    }
+    for line in text.lines() {
+        let line = line.trim_start();
+        if let Some(path) = PATCH_FILE_HEADERS
+            .iter()
+            .find_map(|header| line.strip_prefix(header))
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+        {
+            push_unique(files, path.to_string());
+        }
+    }
+}
+
+fn push_unique(files: &mut Vec<String>, path: String) {
+    if !files.contains(&path) {
+        files.push(path);
+    }
+}
+
+fn section(tag: &str, body: &str) -> String {
+    format!("<{tag}>\n{body}\n</{tag}>")
+}
+
+/// Splits the next `<tag>\n...\n</tag>` section off `text`, returning the
+/// tag, its body and the remaining text.
+fn next_section(text: &str) -> Option<(&str, &str, &str)> {
+    let start = text.find("\n<")? + 2;
+    let (tag, after_tag) = text[start..].split_once(">\n")?;
+    let close = format!("\n</{tag}>");
+    let end = after_tag.find(&close)?;
+    Some((tag, &after_tag[..end], &after_tag[end + close.len()..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            thought_signature: None,
+        }
+    }
+
+    fn function_call(call_id: &str, name: &str, arguments: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+            call_id: call_id.to_string(),
+            thought_signature: None,
+        }
+    }
+
+    fn function_output(call_id: &str, content: &str) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_string(),
+            output: FunctionCallOutputPayload {
+                content: content.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn call_ids(items: &[ResponseItem]) -> Vec<&str> {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                ResponseItem::FunctionCall { call_id, .. }
+                | ResponseItem::FunctionCallOutput { call_id, .. } => Some(call_id.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pinned_context_round_trips() {
+        let context = PinnedContext {
+            plan: Some("- [in_progress] Write tests".to_string()),
+            messages: vec![
+                "Never touch the public API.".to_string(),
+                "Use <tags>\nacross lines.".to_string(),
+            ],
+            files: vec!["src/lib.rs".to_string(), "README.md".to_string()],
+        };
+
+        let item: ResponseItem = context.clone().into();
+        let ResponseItem::Message { content, .. } = &item else {
+            panic!("expected ResponseItem::Message");
+        };
+
+        assert!(PinnedContext::is_pinned_context(content));
+        assert_eq!(PinnedContext::from_item(&item), Some(context));
+        assert_eq!(PinnedContext::from_item(&user_message("hello")), None);
+    }
+
+    #[test]
+    fn collect_reads_plan_and_patched_files() {
+        let patch = "*** Begin Patch\n*** Update File: src/main.rs\n@@\n-a\n+b\n*** Add File: docs/new.md\n+hi\n*** End Patch";
+        let shell_args = serde_json::json!({ "command": ["apply_patch", patch] }).to_string();
+        let history = vec![
+            user_message("fix it"),
+            function_call(
+                "plan-1",
+                "update_plan",
+                r#"{"plan":[{"step":"Old","status":"pending"}]}"#,
+            ),
+            function_call("shell-1", "shell", &shell_args),
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "patch-1".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch\n*** Delete File: old.rs\n*** Update File: src/main.rs\n*** End Patch".to_string(),
+            },
+            function_call(
+                "plan-2",
+                "update_plan",
+                r#"{"explanation":"Narrowed scope","plan":[{"step":"Fix parser","status":"completed"},{"step":"Add tests","status":"in_progress"}]}"#,
+            ),
+        ];
+
+        let context = PinnedContext::collect(&history, &["Keep it small.".to_string()]);
+
+        assert_eq!(
+            context,
+            PinnedContext {
+                plan: Some(
+                    "Narrowed scope\n- [completed] Fix parser\n- [in_progress] Add tests"
+                        .to_string()
+                ),
+                messages: vec!["Keep it small.".to_string()],
+                files: vec![
+                    "src/main.rs".to_string(),
+                    "docs/new.md".to_string(),
+                    "old.rs".to_string(),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn collect_carries_forward_previous_pinned_context() {
+        let previous = PinnedContext {
+            plan: Some("- [pending] Ship it".to_string()),
+            messages: vec!["stale pin".to_string()],
+            files: vec!["a.rs".to_string()],
+        };
+        let history = vec![
+            previous.into(),
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "patch-1".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch\n*** Update File: b.rs\n*** End Patch".to_string(),
+            },
+        ];
+
+        let context = PinnedContext::collect(&history, &[]);
+
+        assert_eq!(
+            context,
+            PinnedContext {
+                plan: Some("- [pending] Ship it".to_string()),
+                messages: Vec::new(),
+                files: vec!["a.rs".to_string(), "b.rs".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn elide_old_tool_outputs_keeps_recent_outputs_intact() {
+        let long_output = "line of output\n".repeat(2_000);
+        let mut items: Vec<ResponseItem> = (0..INTACT_TOOL_OUTPUTS + 2)
+            .flat_map(|i| {
+                let call_id = format!("call-{i}");
+                [
+                    function_call(&call_id, "shell", "{}"),
+                    function_output(&call_id, &long_output),
+                ]
+            })
+            .collect();
+
+        elide_old_tool_outputs(&mut items, Tokenizer::Approximate);
+
+        let lengths: Vec<usize> = items
+            .iter()
+            .filter_map(|item| match item {
+                ResponseItem::FunctionCallOutput { output, .. } => Some(output.content.len()),
+                _ => None,
+            })
+            .collect();
+        assert!(lengths[..2].iter().all(|len| *len < long_output.len() / 10));
+        assert!(lengths[2..].iter().all(|len| *len == long_output.len()));
+    }
+
+    #[test]
+    fn recent_tool_calls_keeps_complete_pairs() {
+        let history = vec![
+            function_call("a", "shell", "{}"),
+            function_output("a", "a"),
+            function_call("b", "shell", "{}"),
+            function_output("b", "b"),
+            user_message("next"),
+            function_call("c", "shell", "{}"),
+            function_output("c", "c"),
+            function_output("orphan", "no call"),
+            function_call("d", "shell", "{}"),
+            function_output("d", "d"),
+            function_call("pending", "shell", "{}"),
+        ];
+
+        let kept = recent_tool_calls(&history);
+
+        assert_eq!(call_ids(&kept), vec!["b", "b", "c", "c", "d", "d"]);
+    }
+
+    #[test]
+    fn structured_history_places_pinned_context_before_summary() {
+        let pinned = PinnedContext {
+            plan: None,
+            messages: vec!["pinned".to_string()],
+            files: Vec::new(),
+        };
+        let history = build_structured_compacted_history(
+            vec![user_message("initial context")],
+            &["first request".to_string()],
+            pinned.clone(),
+            vec![
+                function_call("a", "shell", "{}"),
+                function_output("a", "ok"),
+            ],
+            "summary",
+            Tokenizer::Approximate,
+        );
+
+        assert_eq!(
+            history,
+            vec![
+                user_message("initial context"),
+                user_message("first request"),
+                function_call("a", "shell", "{}"),
+                function_output("a", "ok"),
+                pinned.into(),
+                user_message("summary"),
+            ]
+        );
+    }
+}
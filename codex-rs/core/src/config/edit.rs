@@ -22,6 +22,8 @@ pub enum ConfigEdit {
         model: Option<String>,
         effort: Option<ReasoningEffort>,
     },
+    /// Update the active (or default) model provider.
+    SetModelProvider(String),
     /// Toggle the acknowledgement flag under `[notice]`.
     SetNoticeHideFullAccessWarning(bool),
     /// Toggle the Windows world-writable directories warning acknowledgement flag.
@@ -265,6 +267,9 @@ impl ConfigDocument {
                 );
                 mutated
             }),
+            ConfigEdit::SetModelProvider(provider_id) => {
+                Ok(self.write_profile_value(&["model_provider"], Some(value(provider_id.clone()))))
+            }
             ConfigEdit::SetNoticeHideFullAccessWarning(acknowledged) => Ok(self.write_value(
                 Scope::Global,
                 &[Notice::TABLE_KEY, "hide_full_access_warning"],
@@ -596,6 +601,12 @@ impl ConfigEditsBuilder {
         self
     }
 
+    pub fn set_model_provider(mut self, provider_id: &str) -> Self {
+        self.edits
+            .push(ConfigEdit::SetModelProvider(provider_id.to_string()));
+        self
+    }
+
     pub fn set_hide_full_access_warning(mut self, acknowledged: bool) -> Self {
         self.edits
             .push(ConfigEdit::SetNoticeHideFullAccessWarning(acknowledged));
@@ -916,6 +927,37 @@ model = "o5-preview"
         assert_eq!(contents, expected);
     }
 
+    #[test]
+    fn blocking_set_model_provider_scopes_to_active_profile() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        std::fs::write(
+            codex_home.join(CONFIG_TOML_FILE),
+            r#"profile = "team"
+
+[profiles.team]
+model = "gpt-5.1"
+"#,
+        )
+        .expect("seed");
+
+        ConfigEditsBuilder::new(codex_home)
+            .set_model(Some("qwen2.5-coder"), None)
+            .set_model_provider("ollama")
+            .apply_blocking()
+            .expect("persist");
+
+        let contents =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let expected = r#"profile = "team"
+
+[profiles.team]
+model = "qwen2.5-coder"
+model_provider = "ollama"
+"#;
+        assert_eq!(contents, expected);
+    }
+
     #[test]
     fn blocking_set_model_with_explicit_profile() {
         let tmp = tempdir().expect("tmpdir");
@@ -1,8 +1,14 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::types::AuxiliaryTask;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::FallbackTarget;
+use crate::config::types::GrepBackend;
 use crate::config::types::History;
+use crate::config::types::KeyChords;
+use crate::config::types::LspServerConfig;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerTransportConfig;
+use crate::config::types::ModelsToml;
 use crate::config::types::Notice;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
@@ -11,7 +17,10 @@ use crate::config::types::OtelExporterKind;
 use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
+use crate::config::types::SyntaxHighlighting;
+use crate::config::types::TaskModelRoute;
 use crate::config::types::Tui;
+use crate::config::types::TuiTheme;
 use crate::config::types::UriBasedFileOpener;
 use crate::config_loader::ConfigRequirements;
 use crate::config_loader::LoaderOverrides;
@@ -128,6 +137,14 @@ pub struct Config {
     /// Info needed to make an API request to the model.
     pub model_provider: ModelProviderInfo,
 
+    /// Providers to retry a turn on, in order, when `model_provider` keeps
+    /// failing with rate limits, outages or stream timeouts.
+    pub fallback: Vec<FallbackTarget>,
+
+    /// Models that auxiliary tasks such as compaction and review run on
+    /// instead of the session model.
+    pub task_models: HashMap<AuxiliaryTask, TaskModelRoute>,
+
     /// Approval policy for executing commands.
     pub approval_policy: Constrained<AskForApproval>,
 
@@ -196,6 +213,15 @@ pub struct Config {
     /// Show startup tooltips in the TUI welcome screen.
     pub show_tooltips: bool,
 
+    /// Syntax highlighting level for code blocks and diffs in the TUI.
+    pub tui_syntax_highlighting: SyntaxHighlighting,
+
+    /// User overrides from `[tui.keymap]`, keyed by action name.
+    pub tui_keymap: BTreeMap<String, KeyChords>,
+
+    /// Palette selection and overrides from `[tui.theme]`.
+    pub tui_theme: TuiTheme,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -207,9 +233,16 @@ pub struct Config {
     /// auto: Use the OS-specific keyring service if available, otherwise use a file.
     pub cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
 
+    /// Stored account to authenticate with. `None` uses the account selected
+    /// with `codex account use` (or the default account).
+    pub account: Option<String>,
+
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     pub mcp_servers: HashMap<String, McpServerConfig>,
 
+    /// Language servers consulted for diagnostics after edits, keyed by name.
+    pub lsp_servers: HashMap<String, LspServerConfig>,
+
     /// Preferred store for MCP OAuth credentials.
     /// keyring: Use an OS-specific keyring service.
     ///          Credentials stored in the keyring will only be readable by Codex unless the user explicitly grants access via OS-level keyring access.
@@ -268,6 +301,10 @@ pub struct Config {
     /// Optional verbosity control for GPT-5 models (Responses API `text.verbosity`).
     pub model_verbosity: Option<Verbosity>,
 
+    /// How long Ollama keeps the model loaded after a request made through the
+    /// native `wire_api = "ollama"` provider, e.g. "30m" or "-1".
+    pub ollama_keep_alive: Option<String>,
+
     /// Base URL for requests to ChatGPT (as opposed to the OpenAI API).
     pub chatgpt_base_url: String,
 
@@ -287,6 +324,10 @@ pub struct Config {
 
     pub tools_web_search_request: bool,
 
+    /// Engine used by the `grep_files` tool. `rg` is only spawned when this
+    /// is explicitly set to [`GrepBackend::Ripgrep`].
+    pub tools_grep_backend: GrepBackend,
+
     /// If set to `true`, used only the experimental unified exec tool.
     pub use_experimental_unified_exec_tool: bool,
 
@@ -419,6 +460,21 @@ impl Config {
             .await
     }
 
+    /// Points this config at the model `[models.tasks]` routes `task` to.
+    /// Returns `false`, leaving the config untouched, when there is no route.
+    pub fn apply_task_model(&mut self, task: &AuxiliaryTask) -> bool {
+        let Some(route) = self.task_models.get(task).cloned() else {
+            return false;
+        };
+        self.model = Some(route.model);
+        self.model_provider_id = route.model_provider_id;
+        self.model_provider = route.model_provider;
+        if let Some(effort) = route.model_reasoning_effort {
+            self.model_reasoning_effort = Some(effort);
+        }
+        true
+    }
+
     /// Determine if switching to a different model should also switch the
     /// configured model provider.
     ///
@@ -721,6 +777,13 @@ pub struct ConfigToml {
     /// Provider to use from the model_providers map.
     pub model_provider: Option<String>,
 
+    /// Names of profiles whose model and provider are tried, in order, when
+    /// the active provider keeps failing.
+    pub fallback: Option<Vec<String>>,
+
+    /// Model selection for auxiliary tasks (`[models.tasks]`).
+    pub models: Option<ModelsToml>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<i64>,
 
@@ -768,10 +831,19 @@ pub struct ConfigToml {
     #[serde(default)]
     pub cli_auth_credentials_store: Option<AuthCredentialsStoreMode>,
 
+    /// Stored account to authenticate with, as listed by `codex account list`.
+    /// Profiles may override it. Defaults to the account selected with
+    /// `codex account use`.
+    pub account: Option<String>,
+
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     #[serde(default)]
     pub mcp_servers: HashMap<String, McpServerConfig>,
 
+    /// Language servers consulted for diagnostics after edits, keyed by name.
+    #[serde(default)]
+    pub lsp_servers: HashMap<String, LspServerConfig>,
+
     /// Preferred backend for storing MCP OAuth credentials.
     /// keyring: Use an OS-specific keyring service.
     ///          https://github.com/openai/codex/blob/main/codex-rs/rmcp-client/src/oauth.rs#L2
@@ -824,6 +896,9 @@ pub struct ConfigToml {
     /// Optional verbosity control for GPT-5 models (Responses API `text.verbosity`).
     pub model_verbosity: Option<Verbosity>,
 
+    /// `keep_alive` sent with native Ollama chat requests (e.g. "30m", "-1").
+    pub ollama_keep_alive: Option<String>,
+
     /// Override to force-enable reasoning summaries for the configured model.
     pub model_supports_reasoning_summaries: Option<bool>,
 
@@ -927,6 +1002,10 @@ pub struct ToolsToml {
     /// Enable the `view_image` tool that lets the agent attach local images.
     #[serde(default)]
     pub view_image: Option<bool>,
+
+    /// Search engine used by the `grep_files` tool (default: builtin).
+    #[serde(default)]
+    pub grep_backend: Option<GrepBackend>,
 }
 
 impl From<ToolsToml> for Tools {
@@ -1368,6 +1447,9 @@ impl Config {
             })?
             .clone();
 
+        let fallback =
+            resolve_fallback_targets(&cfg, config_profile.fallback.as_deref(), &model_providers)?;
+
         let shell_environment_policy = cfg.shell_environment_policy.into();
 
         let history = cfg.history.unwrap_or_default();
@@ -1416,6 +1498,11 @@ impl Config {
 
         let include_apply_patch_tool_flag = features.enabled(Feature::ApplyPatchFreeform);
         let tools_web_search_request = features.enabled(Feature::WebSearchRequest);
+        let tools_grep_backend = cfg
+            .tools
+            .as_ref()
+            .and_then(|tools| tools.grep_backend)
+            .unwrap_or_default();
         let use_experimental_unified_exec_tool = features.enabled(Feature::UnifiedExec);
         let use_experimental_use_rmcp_client = features.enabled(Feature::RmcpClient);
 
@@ -1470,6 +1557,9 @@ impl Config {
             }
         }
 
+        let task_models =
+            resolve_task_models(cfg.models.as_ref(), &model_provider_id, &model_providers)?;
+
         let model_context_window = cfg.model_context_window;
         let model_auto_compact_token_limit = cfg.model_auto_compact_token_limit;
 
@@ -1530,6 +1620,8 @@ impl Config {
             model_auto_compact_token_limit,
             model_provider_id,
             model_provider,
+            fallback,
+            task_models,
             cwd: resolved_cwd,
             approval_policy: constrained_approval_policy,
             sandbox_policy,
@@ -1544,7 +1636,9 @@ impl Config {
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
             cli_auth_credentials_store_mode: cfg.cli_auth_credentials_store.unwrap_or_default(),
+            account: config_profile.account.or(cfg.account),
             mcp_servers: Self::add_builtin_mcp_servers(cfg.mcp_servers, &codex_home),
+            lsp_servers: cfg.lsp_servers,
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
             mcp_oauth_credentials_store_mode: cfg.mcp_oauth_credentials_store.unwrap_or_default(),
@@ -1584,6 +1678,7 @@ impl Config {
             model_supports_reasoning_summaries: cfg.model_supports_reasoning_summaries,
             model_reasoning_summary_format: cfg.model_reasoning_summary_format.clone(),
             model_verbosity: config_profile.model_verbosity.or(cfg.model_verbosity),
+            ollama_keep_alive: cfg.ollama_keep_alive,
             chatgpt_base_url: config_profile
                 .chatgpt_base_url
                 .or(cfg.chatgpt_base_url)
@@ -1593,6 +1688,7 @@ impl Config {
             include_delegate_tool: include_delegate_tool_flag,
             include_apply_patch_tool: include_apply_patch_tool_flag,
             tools_web_search_request,
+            tools_grep_backend,
             use_experimental_unified_exec_tool,
             use_experimental_use_rmcp_client,
             ghost_snapshot,
@@ -1611,6 +1707,21 @@ impl Config {
                 .unwrap_or_default(),
             animations: cfg.tui.as_ref().map(|t| t.animations).unwrap_or(false),
             show_tooltips: cfg.tui.as_ref().map(|t| t.show_tooltips).unwrap_or(true),
+            tui_syntax_highlighting: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.syntax_highlighting)
+                .unwrap_or_default(),
+            tui_keymap: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.keymap.clone())
+                .unwrap_or_default(),
+            tui_theme: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.theme.clone())
+                .unwrap_or_default(),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1619,11 +1730,13 @@ impl Config {
                     .unwrap_or(DEFAULT_OTEL_ENVIRONMENT.to_string());
                 let exporter = t.exporter.unwrap_or(OtelExporterKind::None);
                 let trace_exporter = t.trace_exporter.unwrap_or_else(|| exporter.clone());
+                let metrics_exporter = t.metrics_exporter.unwrap_or(OtelExporterKind::None);
                 OtelConfig {
                     log_user_prompt,
                     environment,
                     exporter,
                     trace_exporter,
+                    metrics_exporter,
                 }
             },
         };
@@ -1704,6 +1817,98 @@ fn normalize_multi_agent_agents(raw_agents: Vec<String>) -> Vec<String> {
     normalized
 }
 
+/// Resolves the `fallback` profile names (the active profile's list wins over
+/// the top-level one) into concrete providers. Each profile must name its
+/// model, since the session's model rarely exists on another provider;
+/// `model_provider` falls back to the top-level value.
+fn resolve_fallback_targets(
+    cfg: &ConfigToml,
+    profile_fallback: Option<&[String]>,
+    model_providers: &HashMap<String, ModelProviderInfo>,
+) -> std::io::Result<Vec<FallbackTarget>> {
+    let names = profile_fallback
+        .or(cfg.fallback.as_deref())
+        .unwrap_or_default();
+    names
+        .iter()
+        .map(|name| {
+            let profile = cfg.profiles.get(name).ok_or_else(|| {
+                std::io::Error::new(
+                    ErrorKind::NotFound,
+                    format!("fallback profile `{name}` not found"),
+                )
+            })?;
+            let model_provider_id = profile
+                .model_provider
+                .clone()
+                .or_else(|| cfg.model_provider.clone())
+                .unwrap_or_else(|| "openai".to_string());
+            let model_provider = model_providers
+                .get(&model_provider_id)
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        ErrorKind::NotFound,
+                        format!(
+                            "Model provider `{model_provider_id}` for fallback profile `{name}` not found"
+                        ),
+                    )
+                })?
+                .clone();
+            let model = profile.model.clone().ok_or_else(|| {
+                std::io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("fallback profile `{name}` must set `model`"),
+                )
+            })?;
+            Ok(FallbackTarget {
+                profile: name.clone(),
+                model,
+                model_provider_id,
+                model_provider,
+            })
+        })
+        .collect()
+}
+
+fn resolve_task_models(
+    models: Option<&ModelsToml>,
+    session_provider_id: &str,
+    model_providers: &HashMap<String, ModelProviderInfo>,
+) -> std::io::Result<HashMap<AuxiliaryTask, TaskModelRoute>> {
+    let Some(models) = models else {
+        return Ok(HashMap::new());
+    };
+    models
+        .tasks
+        .iter()
+        .map(|(name, entry)| {
+            let task = name.parse::<AuxiliaryTask>().map_err(|err| {
+                std::io::Error::new(ErrorKind::InvalidData, format!("[models.tasks]: {err}"))
+            })?;
+            let model_provider_id = entry
+                .model_provider
+                .clone()
+                .unwrap_or_else(|| session_provider_id.to_string());
+            let model_provider = model_providers
+                .get(&model_provider_id)
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        ErrorKind::NotFound,
+                        format!("Model provider `{model_provider_id}` for task `{task}` not found"),
+                    )
+                })?
+                .clone();
+            let route = TaskModelRoute {
+                model: entry.model.clone(),
+                model_provider_id,
+                model_provider,
+                model_reasoning_effort: entry.model_reasoning_effort,
+            };
+            Ok((task, route))
+        })
+        .collect()
+}
+
 fn default_review_model() -> String {
     OPENAI_DEFAULT_REVIEW_MODEL.to_string()
 }
@@ -1751,6 +1956,7 @@ mod tests {
     use crate::config::types::HistoryPersistence;
     use crate::config::types::McpServerTransportConfig;
     use crate::config::types::Notifications;
+    use crate::config::types::ThemeName;
     use crate::features::Feature;
 
     use super::*;
@@ -1804,6 +2010,48 @@ persistence = "none"
 
         assert_eq!(tui.notifications, Notifications::Enabled(true));
         assert!(tui.show_tooltips);
+        assert_eq!(tui.syntax_highlighting, SyntaxHighlighting::Auto);
+        assert!(tui.keymap.is_empty());
+        assert_eq!(tui.theme, TuiTheme::default());
+    }
+
+    #[test]
+    fn tui_config_parses_keymap_and_theme() {
+        let cfg = r##"
+[tui.keymap]
+open-transcript = "ctrl+o"
+pager-down = ["down", "ctrl+n"]
+
+[tui.theme]
+name = "high-contrast"
+
+[tui.theme.colors]
+accent = "#5fafff"
+"##;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("TUI keymap and theme should parse");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        assert_eq!(
+            tui.keymap,
+            BTreeMap::from([
+                (
+                    "open-transcript".to_string(),
+                    KeyChords::One("ctrl+o".to_string())
+                ),
+                (
+                    "pager-down".to_string(),
+                    KeyChords::Many(vec!["down".to_string(), "ctrl+n".to_string()])
+                ),
+            ])
+        );
+        assert_eq!(
+            tui.theme,
+            TuiTheme {
+                name: ThemeName::HighContrast,
+                colors: BTreeMap::from([("accent".to_string(), "#5fafff".to_string())]),
+            }
+        );
     }
 
     #[test]
@@ -2120,6 +2368,227 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn profile_account_overrides_base() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "work".to_string(),
+            ConfigProfile {
+                account: Some("work".to_string()),
+                ..Default::default()
+            },
+        );
+        let cfg = ConfigToml {
+            profiles,
+            profile: Some("work".to_string()),
+            account: Some("personal".to_string()),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(config.account.as_deref(), Some("work"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn fallback_profiles_resolve_to_providers() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "work".to_string(),
+            ConfigProfile {
+                fallback: Some(vec!["gemini-pro".to_string(), "backup".to_string()]),
+                ..Default::default()
+            },
+        );
+        profiles.insert(
+            "gemini-pro".to_string(),
+            ConfigProfile {
+                model: Some("gemini-2.5-pro".to_string()),
+                model_provider: Some("gemini".to_string()),
+                ..Default::default()
+            },
+        );
+        profiles.insert(
+            "backup".to_string(),
+            ConfigProfile {
+                model: Some("gpt-5-mini".to_string()),
+                ..Default::default()
+            },
+        );
+        profiles.insert("no-model".to_string(), ConfigProfile::default());
+        let cfg = ConfigToml {
+            profiles: profiles.clone(),
+            profile: Some("work".to_string()),
+            model: Some("gpt-5".to_string()),
+            fallback: Some(vec!["backup".to_string()]),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        let targets: Vec<(&str, &str, &str)> = config
+            .fallback
+            .iter()
+            .map(|target| {
+                (
+                    target.profile.as_str(),
+                    target.model.as_str(),
+                    target.model_provider_id.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                ("gemini-pro", "gemini-2.5-pro", "gemini"),
+                ("backup", "gpt-5-mini", "openai"),
+            ]
+        );
+
+        let cfg = ConfigToml {
+            profiles: profiles.clone(),
+            model: Some("gpt-5".to_string()),
+            fallback: Some(vec!["no-model".to_string()]),
+            ..Default::default()
+        };
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("fallback profile without a model should be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let cfg = ConfigToml {
+            profiles,
+            fallback: Some(vec!["missing".to_string()]),
+            ..Default::default()
+        };
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("unknown fallback profile should be rejected");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        Ok(())
+    }
+
+    #[test]
+    fn task_models_resolve_to_providers() -> std::io::Result<()> {
+        use crate::config::types::TaskModelToml;
+
+        let codex_home = TempDir::new()?;
+        let tasks = BTreeMap::from([
+            (
+                "compact".to_string(),
+                TaskModelToml {
+                    model: "gpt-5-mini".to_string(),
+                    model_provider: None,
+                    model_reasoning_effort: Some(ReasoningEffort::Low),
+                },
+            ),
+            (
+                "delegate:researcher".to_string(),
+                TaskModelToml {
+                    model: "gemini-2.5-pro".to_string(),
+                    model_provider: Some("gemini".to_string()),
+                    model_reasoning_effort: None,
+                },
+            ),
+        ]);
+        let cfg = ConfigToml {
+            model: Some("gpt-5".to_string()),
+            models: Some(ModelsToml {
+                tasks: tasks.clone(),
+            }),
+            ..Default::default()
+        };
+        let mut config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        let compact = config
+            .task_models
+            .get(&AuxiliaryTask::Compact)
+            .expect("compact route");
+        assert_eq!(compact.model_provider_id, "openai");
+        assert_eq!(compact.model_reasoning_effort, Some(ReasoningEffort::Low));
+        let delegate = config
+            .task_models
+            .get(&AuxiliaryTask::Delegate("researcher".to_string()))
+            .expect("delegate route");
+        assert_eq!(delegate.model_provider_id, "gemini");
+        assert!(!config.task_models.contains_key(&AuxiliaryTask::Review));
+
+        assert!(!config.apply_task_model(&AuxiliaryTask::Review));
+        assert_eq!(config.model.as_deref(), Some("gpt-5"));
+        assert!(config.apply_task_model(&AuxiliaryTask::Delegate("researcher".to_string())));
+        assert_eq!(config.model.as_deref(), Some("gemini-2.5-pro"));
+        assert_eq!(config.model_provider_id, "gemini");
+
+        let mut unknown_task = tasks.clone();
+        unknown_task.insert(
+            "summarize".to_string(),
+            TaskModelToml {
+                model: "gpt-5-mini".to_string(),
+                model_provider: None,
+                model_reasoning_effort: None,
+            },
+        );
+        let err = Config::load_from_base_config_with_overrides(
+            ConfigToml {
+                models: Some(ModelsToml {
+                    tasks: unknown_task,
+                }),
+                ..Default::default()
+            },
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("unknown task should be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let mut unknown_provider = tasks;
+        unknown_provider.insert(
+            "review".to_string(),
+            TaskModelToml {
+                model: "gpt-5".to_string(),
+                model_provider: Some("missing".to_string()),
+                model_reasoning_effort: None,
+            },
+        );
+        let err = Config::load_from_base_config_with_overrides(
+            ConfigToml {
+                models: Some(ModelsToml {
+                    tasks: unknown_provider,
+                }),
+                ..Default::default()
+            },
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("unknown provider should be rejected");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        Ok(())
+    }
+
     #[test]
     fn cli_override_takes_precedence_over_profile_sandbox_mode() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -3350,6 +3819,8 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            fallback: Vec::new(),
+            task_models: HashMap::new(),
             approval_policy: Constrained::allow_any(AskForApproval::Never),
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3359,7 +3830,9 @@ model_verbosity = "high"
             notify: None,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
+            account: None,
             mcp_servers: HashMap::new(),
+            lsp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
@@ -3376,6 +3849,7 @@ model_verbosity = "high"
             model_supports_reasoning_summaries: None,
             model_reasoning_summary_format: None,
             model_verbosity: None,
+            ollama_keep_alive: None,
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
             developer_instructions: None,
@@ -3385,6 +3859,7 @@ model_verbosity = "high"
             include_delegate_tool: false,
             include_apply_patch_tool: false,
             tools_web_search_request: false,
+            tools_grep_backend: GrepBackend::Builtin,
             use_experimental_unified_exec_tool: false,
             use_experimental_use_rmcp_client: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
@@ -3399,6 +3874,9 @@ model_verbosity = "high"
             tui_notifications: Default::default(),
             animations: false,
             show_tooltips: true,
+            tui_syntax_highlighting: SyntaxHighlighting::Auto,
+            tui_keymap: BTreeMap::new(),
+            tui_theme: TuiTheme::default(),
             otel: OtelConfig::default(),
         };
 
@@ -3432,6 +3910,8 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai-chat-completions".to_string(),
             model_provider: fixture.openai_chat_completions_provider.clone(),
+            fallback: Vec::new(),
+            task_models: HashMap::new(),
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3441,7 +3921,9 @@ model_verbosity = "high"
             notify: None,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
+            account: None,
             mcp_servers: HashMap::new(),
+            lsp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
@@ -3458,6 +3940,7 @@ model_verbosity = "high"
             model_supports_reasoning_summaries: None,
             model_reasoning_summary_format: None,
             model_verbosity: None,
+            ollama_keep_alive: None,
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
             developer_instructions: None,
@@ -3467,6 +3950,7 @@ model_verbosity = "high"
             include_delegate_tool: false,
             include_apply_patch_tool: false,
             tools_web_search_request: false,
+            tools_grep_backend: GrepBackend::Builtin,
             use_experimental_unified_exec_tool: false,
             use_experimental_use_rmcp_client: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
@@ -3481,6 +3965,9 @@ model_verbosity = "high"
             tui_notifications: Default::default(),
             animations: false,
             show_tooltips: true,
+            tui_syntax_highlighting: SyntaxHighlighting::Auto,
+            tui_keymap: BTreeMap::new(),
+            tui_theme: TuiTheme::default(),
             otel: OtelConfig::default(),
         };
 
@@ -3529,6 +4016,8 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            fallback: Vec::new(),
+            task_models: HashMap::new(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3538,7 +4027,9 @@ model_verbosity = "high"
             notify: None,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
+            account: None,
             mcp_servers: HashMap::new(),
+            lsp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
@@ -3555,6 +4046,7 @@ model_verbosity = "high"
             model_supports_reasoning_summaries: None,
             model_reasoning_summary_format: None,
             model_verbosity: None,
+            ollama_keep_alive: None,
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
             developer_instructions: None,
@@ -3564,6 +4056,7 @@ model_verbosity = "high"
             include_delegate_tool: false,
             include_apply_patch_tool: false,
             tools_web_search_request: false,
+            tools_grep_backend: GrepBackend::Builtin,
             use_experimental_unified_exec_tool: false,
             use_experimental_use_rmcp_client: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
@@ -3578,6 +4071,9 @@ model_verbosity = "high"
             tui_notifications: Default::default(),
             animations: false,
             show_tooltips: true,
+            tui_syntax_highlighting: SyntaxHighlighting::Auto,
+            tui_keymap: BTreeMap::new(),
+            tui_theme: TuiTheme::default(),
             otel: OtelConfig::default(),
         };
 
@@ -3612,6 +4108,8 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            fallback: Vec::new(),
+            task_models: HashMap::new(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3621,7 +4119,9 @@ model_verbosity = "high"
             notify: None,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
+            account: None,
             mcp_servers: HashMap::new(),
+            lsp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
@@ -3638,6 +4138,7 @@ model_verbosity = "high"
             model_supports_reasoning_summaries: None,
             model_reasoning_summary_format: None,
             model_verbosity: Some(Verbosity::High),
+            ollama_keep_alive: None,
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
             developer_instructions: None,
@@ -3647,6 +4148,7 @@ model_verbosity = "high"
             include_delegate_tool: false,
             include_apply_patch_tool: false,
             tools_web_search_request: false,
+            tools_grep_backend: GrepBackend::Builtin,
             use_experimental_unified_exec_tool: false,
             use_experimental_use_rmcp_client: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
@@ -3661,6 +4163,9 @@ model_verbosity = "high"
             tui_notifications: Default::default(),
             animations: false,
             show_tooltips: true,
+            tui_syntax_highlighting: SyntaxHighlighting::Auto,
+            tui_keymap: BTreeMap::new(),
+            tui_theme: TuiTheme::default(),
             otel: OtelConfig::default(),
         };
 
@@ -21,6 +21,12 @@ pub struct ConfigProfile {
     pub model_reasoning_summary: Option<ReasoningSummary>,
     pub model_verbosity: Option<Verbosity>,
     pub chatgpt_base_url: Option<String>,
+    /// Stored account (see `codex account list`) to use with this profile
+    /// instead of the active one.
+    pub account: Option<String>,
+    /// Profiles to retry a turn with, in order, when this profile's provider
+    /// keeps failing. Overrides the top-level `fallback`.
+    pub fallback: Option<Vec<String>>,
     pub experimental_instructions_file: Option<AbsolutePathBuf>,
     pub experimental_compact_prompt_file: Option<AbsolutePathBuf>,
     pub include_apply_patch_tool: Option<bool>,
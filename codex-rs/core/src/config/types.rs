@@ -3,10 +3,14 @@
 // Note this file should generally be restricted to simple struct/enum
 // definitions that do not contain business logic.
 
+use crate::model_provider_info::ModelProviderInfo;
+use codex_protocol::openai_models::ReasoningEffort;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::Duration;
 use wildmatch::WildMatchPattern;
 
@@ -273,6 +277,43 @@ pub enum HistoryPersistence {
     None,
 }
 
+/// Search engine used by the `grep_files` tool.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GrepBackend {
+    /// Search in-process. Does not depend on any external binary.
+    #[default]
+    Builtin,
+    /// Spawn `rg` from `PATH` for every search.
+    Ripgrep,
+}
+
+/// A language server that reports diagnostics for edited files.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct LspServerConfig {
+    /// Executable that speaks LSP over stdio, e.g. `rust-analyzer`.
+    pub command: String,
+
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    #[serde(default)]
+    pub env: Option<HashMap<String, String>>,
+
+    /// File extensions (without the leading dot) routed to this server.
+    pub extensions: Vec<String>,
+
+    /// `languageId` sent in `textDocument/didOpen`. Derived from the file
+    /// extension when omitted.
+    #[serde(default)]
+    pub language_id: Option<String>,
+
+    /// How long to wait for fresh diagnostics after an edit before reporting
+    /// whatever the server has published so far. Defaults to 3 seconds.
+    #[serde(default, with = "option_duration_secs")]
+    pub diagnostics_timeout_sec: Option<Duration>,
+}
+
 // ===== OTEL configuration =====
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -312,6 +353,13 @@ pub enum OtelExporterKind {
         #[serde(default)]
         tls: Option<OtelTlsConfig>,
     },
+    /// Append OTLP-JSON lines to a local file. Relative paths are resolved
+    /// against `CODEX_HOME`.
+    File {
+        path: AbsolutePathBuf,
+        #[serde(default)]
+        rotate_bytes: Option<u64>,
+    },
 }
 
 /// OTEL settings loaded from config.toml. Fields are optional so we can apply defaults.
@@ -328,6 +376,100 @@ pub struct OtelConfigToml {
 
     /// Optional trace exporter
     pub trace_exporter: Option<OtelExporterKind>,
+
+    /// Optional metrics exporter. Unlike traces this does not fall back to
+    /// `exporter`, since OTLP endpoints are usually per signal.
+    pub metrics_exporter: Option<OtelExporterKind>,
+}
+
+/// Provider and model a turn is retried on once the primary provider keeps
+/// failing. Resolved from a profile named in `fallback`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FallbackTarget {
+    /// Profile the target was resolved from.
+    pub profile: String,
+    /// Model to request from `model_provider`.
+    pub model: String,
+    pub model_provider_id: String,
+    pub model_provider: ModelProviderInfo,
+}
+
+/// `[models]` table in config.toml.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ModelsToml {
+    /// Models for auxiliary tasks, keyed by task name (see [`AuxiliaryTask`]).
+    #[serde(default)]
+    pub tasks: BTreeMap<String, TaskModelToml>,
+}
+
+/// One `[models.tasks.<task>]` entry.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct TaskModelToml {
+    pub model: String,
+    /// Key into `model_providers`; defaults to the session's provider.
+    pub model_provider: Option<String>,
+    /// Defaults to the effort the task would otherwise use.
+    pub model_reasoning_effort: Option<ReasoningEffort>,
+}
+
+/// Bookkeeping work that can run on a different model than the session.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AuxiliaryTask {
+    /// Summarizing the conversation when it is compacted.
+    Compact,
+    /// `/review` sessions.
+    Review,
+    /// Naming and summarizing sessions.
+    Title,
+    /// Writing commit messages.
+    CommitMessage,
+    /// The TUMIX meta-agent that designs the agent team.
+    MetaAgent,
+    /// Runs delegated to the named agent.
+    Delegate(String),
+}
+
+impl FromStr for AuxiliaryTask {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "compact" => Ok(Self::Compact),
+            "review" => Ok(Self::Review),
+            "title" => Ok(Self::Title),
+            "commit_message" => Ok(Self::CommitMessage),
+            "meta_agent" => Ok(Self::MetaAgent),
+            _ => match s.strip_prefix("delegate:") {
+                Some(agent) if !agent.is_empty() => Ok(Self::Delegate(agent.to_string())),
+                _ => Err(format!(
+                    "unknown task `{s}`; expected compact, review, title, commit_message, meta_agent or delegate:<agent>"
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for AuxiliaryTask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Compact => f.write_str("compact"),
+            Self::Review => f.write_str("review"),
+            Self::Title => f.write_str("title"),
+            Self::CommitMessage => f.write_str("commit_message"),
+            Self::MetaAgent => f.write_str("meta_agent"),
+            Self::Delegate(agent) => write!(f, "delegate:{agent}"),
+        }
+    }
+}
+
+/// Provider, model and reasoning effort an [`AuxiliaryTask`] runs on.
+/// Resolved from `[models.tasks]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskModelRoute {
+    pub model: String,
+    pub model_provider_id: String,
+    pub model_provider: ModelProviderInfo,
+    pub model_reasoning_effort: Option<ReasoningEffort>,
 }
 
 /// Effective OTEL settings after defaults are applied.
@@ -337,6 +479,7 @@ pub struct OtelConfig {
     pub environment: String,
     pub exporter: OtelExporterKind,
     pub trace_exporter: OtelExporterKind,
+    pub metrics_exporter: OtelExporterKind,
 }
 
 impl Default for OtelConfig {
@@ -346,6 +489,7 @@ impl Default for OtelConfig {
             environment: DEFAULT_OTEL_ENVIRONMENT.to_owned(),
             exporter: OtelExporterKind::None,
             trace_exporter: OtelExporterKind::None,
+            metrics_exporter: OtelExporterKind::None,
         }
     }
 }
@@ -380,6 +524,75 @@ pub struct Tui {
     /// Defaults to `true`.
     #[serde(default = "default_true")]
     pub show_tooltips: bool,
+
+    /// Syntax highlighting for code blocks and diffs.
+    /// Defaults to `auto`.
+    #[serde(default)]
+    pub syntax_highlighting: SyntaxHighlighting,
+
+    /// Key chords for named TUI actions, e.g. `open-transcript = "ctrl+t"`.
+    /// Actions that are not listed keep their default bindings.
+    #[serde(default)]
+    pub keymap: BTreeMap<String, KeyChords>,
+
+    /// Color palette used by the TUI.
+    #[serde(default)]
+    pub theme: TuiTheme,
+}
+
+/// One or more key chords bound to an action.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum KeyChords {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyChords {
+    pub fn chords(&self) -> &[String] {
+        match self {
+            Self::One(chord) => std::slice::from_ref(chord),
+            Self::Many(chords) => chords,
+        }
+    }
+}
+
+/// `[tui.theme]`: a built-in palette plus per-role color overrides.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct TuiTheme {
+    /// Built-in palette to start from. Defaults to `auto`.
+    #[serde(default)]
+    pub name: ThemeName,
+
+    /// Colors replacing the palette's, keyed by role (e.g. `accent`). Values
+    /// are ANSI color names such as `"light-blue"` or `"#rrggbb"`.
+    #[serde(default)]
+    pub colors: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    /// Pick `dark` or `light` from the terminal's background color.
+    #[default]
+    Auto,
+    Dark,
+    Light,
+    HighContrast,
+}
+
+/// How much color the TUI spends on syntax highlighting.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SyntaxHighlighting {
+    /// Use the full palette when the terminal supports 256 colors or more and
+    /// fall back to `basic` otherwise.
+    #[default]
+    Auto,
+    /// Only use the terminal's ANSI colors and text modifiers.
+    Basic,
+    /// Render code without syntax highlighting.
+    Off,
 }
 
 const fn default_true() -> bool {
@@ -1,7 +1,6 @@
 use crate::codex::TurnContext;
 use crate::context_manager::normalize;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::approx_token_count;
 use crate::truncate::approx_tokens_from_byte_count;
 use crate::truncate::truncate_function_output_items_with_policy;
 use crate::truncate::truncate_text;
@@ -11,6 +10,7 @@ use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TokenUsageInfo;
+use codex_utils_tokenizer::Tokenizer;
 use std::ops::Deref;
 
 /// Transcript of conversation history
@@ -19,6 +19,10 @@ pub(crate) struct ContextManager {
     /// The oldest items are at the beginning of the vector.
     items: Vec<ResponseItem>,
     token_info: Option<TokenUsageInfo>,
+    /// Token estimate of each item in `items`, under `counted_with`. Filled in
+    /// by `estimate_token_count`; items past its end have not been counted.
+    item_tokens: Vec<Option<i64>>,
+    counted_with: Option<Tokenizer>,
 }
 
 impl ContextManager {
@@ -26,6 +30,8 @@ impl ContextManager {
         Self {
             items: Vec::new(),
             token_info: TokenUsageInfo::new_or_append(&None, &None, None),
+            item_tokens: Vec::new(),
+            counted_with: None,
         }
     }
 
@@ -47,8 +53,12 @@ impl ContextManager {
     }
 
     /// `items` is ordered from oldest to newest.
-    pub(crate) fn record_items<I>(&mut self, items: I, policy: TruncationPolicy)
-    where
+    pub(crate) fn record_items<I>(
+        &mut self,
+        items: I,
+        policy: TruncationPolicy,
+        tokenizer: Tokenizer,
+    ) where
         I: IntoIterator,
         I::Item: std::ops::Deref<Target = ResponseItem>,
     {
@@ -59,7 +69,7 @@ impl ContextManager {
                 continue;
             }
 
-            let processed = self.process_item(item_ref, policy);
+            let processed = self.process_item(item_ref, policy, tokenizer);
             self.items.push(processed);
         }
     }
@@ -77,51 +87,63 @@ impl ContextManager {
         history
     }
 
-    // Estimate token usage using byte-based heuristics from the truncation helpers.
-    // This is a coarse lower bound, not a tokenizer-accurate count.
-    pub(crate) fn estimate_token_count(&self, turn_context: &TurnContext) -> Option<i64> {
+    // Estimate token usage with the model family's tokenizer. Items are counted
+    // in their serialized form, and encrypted reasoning is estimated from its size.
+    // Each item is counted once and the count is kept until the item changes.
+    pub(crate) fn estimate_token_count(&mut self, turn_context: &TurnContext) -> Option<i64> {
         let model_family = turn_context.client.get_model_family();
-        let base_tokens =
-            i64::try_from(approx_token_count(model_family.base_instructions.as_str()))
-                .unwrap_or(i64::MAX);
-
-        let items_tokens = self.items.iter().fold(0i64, |acc, item| {
-            acc + match item {
-                ResponseItem::GhostSnapshot { .. } => 0,
-                ResponseItem::Reasoning {
-                    encrypted_content: Some(content),
-                    ..
-                }
-                | ResponseItem::Compaction {
-                    encrypted_content: content,
-                } => estimate_reasoning_length(content.len()) as i64,
-                item => {
-                    let serialized = serde_json::to_string(item).unwrap_or_default();
-                    i64::try_from(approx_token_count(&serialized)).unwrap_or(i64::MAX)
-                }
-            }
-        });
+        let tokenizer = model_family.tokenizer;
+        let base_tokens = i64::try_from(tokenizer.count(model_family.base_instructions.as_str()))
+            .unwrap_or(i64::MAX);
+
+        let items_tokens = self.estimate_items_tokens(tokenizer);
 
         Some(base_tokens.saturating_add(items_tokens))
     }
 
+    fn estimate_items_tokens(&mut self, tokenizer: Tokenizer) -> i64 {
+        if self.counted_with != Some(tokenizer) {
+            self.item_tokens.clear();
+            self.counted_with = Some(tokenizer);
+        }
+        self.item_tokens.resize(self.items.len(), None);
+        self.items
+            .iter()
+            .zip(self.item_tokens.iter_mut())
+            .fold(0i64, |acc, (item, tokens)| {
+                acc.saturating_add(
+                    *tokens.get_or_insert_with(|| estimate_item_tokens(item, tokenizer)),
+                )
+            })
+    }
+
     pub(crate) fn remove_first_item(&mut self) {
         if !self.items.is_empty() {
             // Remove the oldest item (front of the list). Items are ordered from
             // oldest → newest, so index 0 is the first entry recorded.
             let removed = self.items.remove(0);
+            if !self.item_tokens.is_empty() {
+                self.item_tokens.remove(0);
+            }
             // If the removed item participates in a call/output pair, also remove
             // its corresponding counterpart to keep the invariants intact without
             // running a full normalization pass.
+            let len = self.items.len();
             normalize::remove_corresponding_for(&mut self.items, &removed);
+            if self.items.len() != len {
+                self.item_tokens.clear();
+            }
         }
     }
 
     pub(crate) fn replace(&mut self, items: Vec<ResponseItem>) {
         self.items = items;
+        self.item_tokens.clear();
     }
 
     pub(crate) fn replace_last_turn_images(&mut self, placeholder: &str) {
+        self.item_tokens
+            .truncate(self.items.len().saturating_sub(1));
         let Some(last_item) = self.items.last_mut() else {
             return;
         };
@@ -156,6 +178,7 @@ impl ContextManager {
     ///
     /// Returns the number of images replaced.
     pub(crate) fn replace_all_images(&mut self, placeholder: &str) -> i64 {
+        self.item_tokens.clear();
         let mut replaced_images = 0_i64;
         for item in self.items.iter_mut() {
             match item {
@@ -249,11 +272,20 @@ impl ContextManager {
     /// 1. every call (function/custom) has a corresponding output entry
     /// 2. every output has a corresponding call entry
     fn normalize_history(&mut self) {
+        // Inserted or removed items shift the rest, so their counts no longer
+        // line up and are dropped.
+        let len = self.items.len();
+
         // all function/tool calls must have a corresponding output
         normalize::ensure_call_outputs_present(&mut self.items);
+        let inserted = self.items.len() != len;
 
         // all outputs must have a corresponding function/tool call
+        let len = self.items.len();
         normalize::remove_orphan_outputs(&mut self.items);
+        if inserted || self.items.len() != len {
+            self.item_tokens.clear();
+        }
     }
 
     /// Returns a clone of the contents in the transcript.
@@ -265,16 +297,25 @@ impl ContextManager {
         items.retain(|item| !matches!(item, ResponseItem::GhostSnapshot { .. }));
     }
 
-    fn process_item(&self, item: &ResponseItem, policy: TruncationPolicy) -> ResponseItem {
+    fn process_item(
+        &self,
+        item: &ResponseItem,
+        policy: TruncationPolicy,
+        tokenizer: Tokenizer,
+    ) -> ResponseItem {
         let policy_with_serialization_budget = policy.mul(1.2);
         match item {
             ResponseItem::FunctionCallOutput { call_id, output } => {
-                let truncated =
-                    truncate_text(output.content.as_str(), policy_with_serialization_budget);
+                let truncated = truncate_text(
+                    output.content.as_str(),
+                    policy_with_serialization_budget,
+                    tokenizer,
+                );
                 let truncated_items = output.content_items.as_ref().map(|items| {
                     truncate_function_output_items_with_policy(
                         items,
                         policy_with_serialization_budget,
+                        tokenizer,
                     )
                 });
                 ResponseItem::FunctionCallOutput {
@@ -287,7 +328,7 @@ impl ContextManager {
                 }
             }
             ResponseItem::CustomToolCallOutput { call_id, output } => {
-                let truncated = truncate_text(output, policy_with_serialization_budget);
+                let truncated = truncate_text(output, policy_with_serialization_budget, tokenizer);
                 ResponseItem::CustomToolCallOutput {
                     call_id: call_id.clone(),
                     output: truncated,
@@ -324,6 +365,23 @@ fn is_api_message(message: &ResponseItem) -> bool {
     }
 }
 
+fn estimate_item_tokens(item: &ResponseItem, tokenizer: Tokenizer) -> i64 {
+    match item {
+        ResponseItem::GhostSnapshot { .. } => 0,
+        ResponseItem::Reasoning {
+            encrypted_content: Some(content),
+            ..
+        }
+        | ResponseItem::Compaction {
+            encrypted_content: content,
+        } => estimate_reasoning_length(content.len()) as i64,
+        item => {
+            let serialized = serde_json::to_string(item).unwrap_or_default();
+            i64::try_from(tokenizer.count(&serialized)).unwrap_or(i64::MAX)
+        }
+    }
+}
+
 fn estimate_reasoning_length(encoded_len: usize) -> usize {
     encoded_len
         .saturating_mul(3)
@@ -10,6 +10,7 @@ use codex_protocol::models::LocalShellExecAction;
 use codex_protocol::models::LocalShellStatus;
 use codex_protocol::models::ReasoningItemContent;
 use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_utils_tokenizer::Tokenizer;
 use pretty_assertions::assert_eq;
 use regex_lite::Regex;
 
@@ -31,7 +32,11 @@ fn create_history_with_items(items: Vec<ResponseItem>) -> ContextManager {
     let mut h = ContextManager::new();
     // Use a generous but fixed token budget; tests only rely on truncation
     // behavior, not on a specific model's token limit.
-    h.record_items(items.iter(), TruncationPolicy::Tokens(10_000));
+    h.record_items(
+        items.iter(),
+        TruncationPolicy::Tokens(10_000),
+        Tokenizer::Approximate,
+    );
     h
 }
 
@@ -71,7 +76,11 @@ fn reasoning_with_encrypted_content(len: usize) -> ResponseItem {
 }
 
 fn truncate_exec_output(content: &str) -> String {
-    truncate::truncate_text(content, TruncationPolicy::Tokens(EXEC_FORMAT_MAX_TOKENS))
+    truncate::truncate_text(
+        content,
+        TruncationPolicy::Tokens(EXEC_FORMAT_MAX_TOKENS),
+        Tokenizer::Approximate,
+    )
 }
 
 #[test]
@@ -88,12 +97,16 @@ fn filters_non_api_messages() {
         thought_signature: None,
     };
     let reasoning = reasoning_msg("thinking...");
-    h.record_items([&system, &reasoning, &ResponseItem::Other], policy);
+    h.record_items(
+        [&system, &reasoning, &ResponseItem::Other],
+        policy,
+        Tokenizer::Approximate,
+    );
 
     // User and assistant should be retained.
     let u = user_msg("hi");
     let a = assistant_msg("hello");
-    h.record_items([&u, &a], policy);
+    h.record_items([&u, &a], policy, Tokenizer::Approximate);
 
     let items = h.contents();
     assert_eq!(
@@ -194,7 +207,7 @@ fn replace_all_images_replaces_images_and_returns_count() {
         },
     ];
 
-    history.record_items(items.iter(), policy);
+    history.record_items(items.iter(), policy, Tokenizer::Approximate);
 
     let replaced = history.replace_all_images("image removed");
     assert_eq!(replaced, 3);
@@ -232,6 +245,51 @@ fn replace_all_images_replaces_images_and_returns_count() {
     );
 }
 
+#[test]
+fn item_token_counts_follow_history_changes() {
+    let call = ResponseItem::FunctionCall {
+        id: None,
+        name: "do_it".to_string(),
+        arguments: "{}".to_string(),
+        call_id: "call-1".to_string(),
+        thought_signature: None,
+    };
+    let output = ResponseItem::FunctionCallOutput {
+        call_id: "call-1".to_string(),
+        output: FunctionCallOutputPayload {
+            content: "ok ".repeat(100),
+            ..Default::default()
+        },
+    };
+    let fresh_count = |items: Vec<ResponseItem>| {
+        create_history_with_items(items).estimate_items_tokens(Tokenizer::O200kBase)
+    };
+
+    let mut h = create_history_with_items(vec![user_msg("first"), call.clone(), output.clone()]);
+    h.estimate_items_tokens(Tokenizer::Approximate);
+    assert_eq!(
+        h.estimate_items_tokens(Tokenizer::O200kBase),
+        fresh_count(vec![user_msg("first"), call.clone(), output.clone()])
+    );
+
+    h.record_items(
+        [assistant_msg("done")].iter(),
+        TruncationPolicy::Tokens(10_000),
+        Tokenizer::O200kBase,
+    );
+    h.remove_first_item();
+    assert_eq!(
+        h.estimate_items_tokens(Tokenizer::O200kBase),
+        fresh_count(vec![call.clone(), output.clone(), assistant_msg("done")])
+    );
+
+    h.replace(vec![user_msg("second")]);
+    assert_eq!(
+        h.estimate_items_tokens(Tokenizer::O200kBase),
+        fresh_count(vec![user_msg("second")])
+    );
+}
+
 #[test]
 fn remove_first_item_removes_matching_output_for_function_call() {
     let items = vec![
@@ -372,7 +430,7 @@ fn record_items_truncates_function_call_output_content() {
         },
     };
 
-    history.record_items([&item], policy);
+    history.record_items([&item], policy, Tokenizer::Approximate);
 
     assert_eq!(history.items.len(), 1);
     match &history.items[0] {
@@ -404,7 +462,7 @@ fn record_items_truncates_custom_tool_call_output_content() {
         output: long_output.clone(),
     };
 
-    history.record_items([&item], policy);
+    history.record_items([&item], policy, Tokenizer::Approximate);
 
     assert_eq!(history.items.len(), 1);
     match &history.items[0] {
@@ -437,7 +495,7 @@ fn record_items_respects_custom_token_limit() {
         },
     };
 
-    history.record_items([&item], policy);
+    history.record_items([&item], policy, Tokenizer::Approximate);
 
     let stored = match &history.items[0] {
         ResponseItem::FunctionCallOutput { output, .. } => output,
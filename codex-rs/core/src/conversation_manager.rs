@@ -12,6 +12,7 @@ use crate::delegate_tool::DelegateToolAdapter;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::openai_models::models_manager::ModelsManager;
+use crate::openai_models::models_manager::ProviderModels;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::SessionConfiguredEvent;
@@ -311,6 +312,10 @@ impl ConversationManager {
         self.models_manager.list_models(config).await
     }
 
+    pub async fn list_provider_models(&self, config: &Config) -> Vec<ProviderModels> {
+        self.models_manager.list_provider_models(config).await
+    }
+
     pub fn get_models_manager(&self) -> Arc<ModelsManager> {
         self.models_manager.clone()
     }
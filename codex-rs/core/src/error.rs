@@ -12,6 +12,7 @@ use codex_protocol::ConversationId;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::ErrorEvent;
 use codex_protocol::protocol::RateLimitSnapshot;
+use codex_utils_tokenizer::Tokenizer;
 use reqwest::StatusCode;
 use serde_json;
 use std::io;
@@ -539,6 +540,7 @@ pub fn get_error_message_ui(e: &CodexErr) -> String {
     truncate_text(
         &message,
         TruncationPolicy::Bytes(ERROR_MESSAGE_UI_MAX_BYTES),
+        Tokenizer::Approximate,
     )
 }
 
@@ -13,6 +13,7 @@ use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
 use codex_execpolicy::blocking_append_allow_prefix_rule;
 use codex_protocol::approvals::ExecPolicyAmendment;
+use codex_protocol::approvals::ExecPolicyAmendmentTarget;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::SandboxPolicy;
 use thiserror::Error;
@@ -21,8 +22,10 @@ use tokio::sync::RwLock;
 use tokio::task::spawn_blocking;
 
 use crate::bash::parse_shell_lc_plain_commands;
+use crate::config::Config;
 use crate::features::Feature;
 use crate::features::Features;
+use crate::git_info::get_git_repo_root;
 use crate::sandboxing::SandboxPermissions;
 use crate::tools::sandboxing::ExecApprovalRequirement;
 
@@ -32,6 +35,9 @@ const PROMPT_CONFLICT_REASON: &str =
 const PROMPT_REASON: &str = "execpolicy requires approval for this command";
 const RULES_DIR_NAME: &str = "rules";
 const RULE_EXTENSION: &str = "rules";
+/// Project rules live in `<repo>/.codex/rules` and may use either extension.
+const PROJECT_CONFIG_DIR_NAME: &str = ".codex";
+const PROJECT_RULE_EXTENSIONS: &[&str] = &[RULE_EXTENSION, "codexpolicy"];
 const DEFAULT_POLICY_FILE: &str = "default.rules";
 
 fn is_policy_match(rule_match: &RuleMatch) -> bool {
@@ -78,22 +84,174 @@ pub enum ExecPolicyUpdateError {
 
     #[error("cannot append execpolicy rule because execpolicy feature is disabled")]
     FeatureDisabled,
+
+    #[error("cannot append execpolicy rule to project rules because the project is not trusted")]
+    ProjectRulesUnavailable,
+}
+
+/// Execpolicy rules for a session: the user's rules from `CODEX_HOME/rules`
+/// plus, for trusted projects, the rules checked into the repository under
+/// `.codex/rules`.
+///
+/// Commands are evaluated against the merged rule set, where the strictest
+/// matching decision wins. A user `forbidden` rule therefore can never be
+/// relaxed by a project rule.
+#[derive(Clone, Debug)]
+pub struct ExecPolicy {
+    merged: Policy,
+    user: Policy,
+    project: Option<ProjectRules>,
+}
+
+#[derive(Clone, Debug)]
+struct ProjectRules {
+    dir: PathBuf,
+    policy: Policy,
+}
+
+impl ExecPolicy {
+    pub fn empty() -> Self {
+        Policy::empty().into()
+    }
+
+    pub(crate) fn with_project_rules(user: Policy, project_dir: PathBuf, project: Policy) -> Self {
+        let mut merged = user.clone();
+        merged.merge(&project);
+        Self {
+            merged,
+            user,
+            project: Some(ProjectRules {
+                dir: project_dir,
+                policy: project,
+            }),
+        }
+    }
+
+    /// The merged policy used to evaluate commands.
+    pub fn policy(&self) -> &Policy {
+        &self.merged
+    }
+
+    /// Directory holding the project's rules, if project rules are active.
+    pub fn project_rules_dir(&self) -> Option<&Path> {
+        self.project.as_ref().map(|project| project.dir.as_path())
+    }
+
+    fn add_prefix_rule(
+        &mut self,
+        prefix: &[String],
+        decision: Decision,
+        target: ExecPolicyAmendmentTarget,
+    ) -> Result<(), ExecPolicyRuleError> {
+        match (target, self.project.as_mut()) {
+            (ExecPolicyAmendmentTarget::Project, Some(project)) => {
+                project.policy.add_prefix_rule(prefix, decision)?;
+            }
+            _ => self.user.add_prefix_rule(prefix, decision)?,
+        }
+        self.merged.add_prefix_rule(prefix, decision)
+    }
+
+    /// Describes where the rule behind `rule_match` was loaded from. Only
+    /// reported when project rules are active, since otherwise every rule
+    /// comes from the user's rules directory.
+    fn rule_source(&self, rule_match: &RuleMatch) -> Option<String> {
+        let project = self.project.as_ref()?;
+        let RuleMatch::PrefixRuleMatch { matched_prefix, .. } = rule_match else {
+            return None;
+        };
+        let defined_in = |policy: &Policy| {
+            policy
+                .matches_for_command(matched_prefix, None)
+                .contains(rule_match)
+        };
+        if defined_in(&self.user) {
+            Some("user rules".to_string())
+        } else if defined_in(&project.policy) {
+            Some(format!("project rules in {}", project.dir.display()))
+        } else {
+            None
+        }
+    }
+
+    fn reason_for(&self, reason: &str, evaluation: &Evaluation) -> String {
+        let source = evaluation
+            .matched_rules
+            .iter()
+            .find(|rule_match| {
+                is_policy_match(rule_match) && rule_match.decision() == evaluation.decision
+            })
+            .and_then(|rule_match| self.rule_source(rule_match));
+        match source {
+            Some(source) => format!("{reason} ({source})"),
+            None => reason.to_string(),
+        }
+    }
+}
+
+impl From<Policy> for ExecPolicy {
+    fn from(user: Policy) -> Self {
+        Self {
+            merged: user.clone(),
+            user,
+            project: None,
+        }
+    }
+}
+
+/// Directory with the project's checked-in rules. Only returned when the
+/// project is trusted, so an untrusted checkout cannot allow commands.
+pub(crate) fn project_rules_dir(config: &Config) -> Option<PathBuf> {
+    if !config.active_project.is_trusted() {
+        return None;
+    }
+    Some(project_rules_dir_for_cwd(&config.cwd))
+}
+
+/// Where the rules for the project containing `cwd` are checked in: the
+/// repository root, or `cwd` itself outside a repository.
+pub(crate) fn project_rules_dir_for_cwd(cwd: &Path) -> PathBuf {
+    let root = get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+    root.join(PROJECT_CONFIG_DIR_NAME).join(RULES_DIR_NAME)
 }
 
 pub(crate) async fn load_exec_policy_for_features(
     features: &Features,
     codex_home: &Path,
-) -> Result<Policy, ExecPolicyError> {
+    project_rules_dir: Option<&Path>,
+) -> Result<ExecPolicy, ExecPolicyError> {
     if !features.enabled(Feature::ExecPolicy) {
-        Ok(Policy::empty())
-    } else {
-        load_exec_policy(codex_home).await
+        return Ok(ExecPolicy::empty());
+    }
+    let user = load_exec_policy(codex_home).await?;
+    match project_rules_dir {
+        Some(dir) => {
+            let project = load_policy_dir(dir, PROJECT_RULE_EXTENSIONS).await?;
+            Ok(ExecPolicy::with_project_rules(
+                user,
+                dir.to_path_buf(),
+                project,
+            ))
+        }
+        None => Ok(user.into()),
     }
 }
 
 pub async fn load_exec_policy(codex_home: &Path) -> Result<Policy, ExecPolicyError> {
-    let policy_dir = codex_home.join(RULES_DIR_NAME);
-    let policy_paths = collect_policy_files(&policy_dir).await?;
+    load_policy_dir(&codex_home.join(RULES_DIR_NAME), &[RULE_EXTENSION]).await
+}
+
+/// Loads every `.rules` or `.codexpolicy` file in `policy_dir`, for example
+/// a candidate set of rules that is not installed yet.
+pub async fn load_exec_policy_dir(policy_dir: &Path) -> Result<Policy, ExecPolicyError> {
+    load_policy_dir(policy_dir, PROJECT_RULE_EXTENSIONS).await
+}
+
+async fn load_policy_dir(
+    policy_dir: &Path,
+    extensions: &[&str],
+) -> Result<Policy, ExecPolicyError> {
+    let policy_paths = collect_policy_files(policy_dir, extensions).await?;
 
     let mut parser = PolicyParser::new();
     for policy_path in &policy_paths {
@@ -129,10 +287,33 @@ pub(crate) fn default_policy_path(codex_home: &Path) -> PathBuf {
 
 pub(crate) async fn append_execpolicy_amendment_and_update(
     codex_home: &Path,
-    current_policy: &Arc<RwLock<Policy>>,
+    current_policy: &Arc<RwLock<ExecPolicy>>,
     prefix: &[String],
+    target: ExecPolicyAmendmentTarget,
 ) -> Result<(), ExecPolicyUpdateError> {
-    let policy_path = default_policy_path(codex_home);
+    let policy_path = match target {
+        ExecPolicyAmendmentTarget::User => default_policy_path(codex_home),
+        ExecPolicyAmendmentTarget::Project => {
+            let policy = current_policy.read().await;
+            let dir = policy
+                .project_rules_dir()
+                .ok_or(ExecPolicyUpdateError::ProjectRulesUnavailable)?;
+            // The append helper only creates the rules directory itself, and
+            // the repository may not have a `.codex` directory yet.
+            if let Some(parent) = dir.parent() {
+                fs::create_dir_all(parent).await.map_err(|source| {
+                    ExecPolicyUpdateError::AppendRule {
+                        path: dir.to_path_buf(),
+                        source: AmendError::CreatePolicyDir {
+                            dir: parent.to_path_buf(),
+                            source,
+                        },
+                    }
+                })?;
+            }
+            dir.join(DEFAULT_POLICY_FILE)
+        }
+    };
     let prefix = prefix.to_vec();
     spawn_blocking({
         let policy_path = policy_path.clone();
@@ -149,7 +330,7 @@ pub(crate) async fn append_execpolicy_amendment_and_update(
     current_policy
         .write()
         .await
-        .add_prefix_rule(&prefix, Decision::Allow)?;
+        .add_prefix_rule(&prefix, Decision::Allow, target)?;
 
     Ok(())
 }
@@ -207,24 +388,24 @@ fn try_derive_execpolicy_amendment_for_allow_rules(
 }
 
 /// Only return PROMPT_REASON when an execpolicy rule drove the prompt decision.
-fn derive_prompt_reason(evaluation: &Evaluation) -> Option<String> {
-    evaluation.matched_rules.iter().find_map(|rule_match| {
-        if is_policy_match(rule_match) && rule_match.decision() == Decision::Prompt {
-            Some(PROMPT_REASON.to_string())
-        } else {
-            None
-        }
-    })
+fn derive_prompt_reason(exec_policy: &ExecPolicy, evaluation: &Evaluation) -> Option<String> {
+    evaluation
+        .matched_rules
+        .iter()
+        .any(|rule_match| is_policy_match(rule_match) && rule_match.decision() == Decision::Prompt)
+        .then(|| exec_policy.reason_for(PROMPT_REASON, evaluation))
 }
 
-pub(crate) async fn create_exec_approval_requirement_for_command(
-    exec_policy: &Arc<RwLock<Policy>>,
-    features: &Features,
+/// Evaluates `command` the way a session does: every command of a plain
+/// `bash -lc` script is checked against `policy`, and commands no rule
+/// matches fall back to the approval heuristics.
+pub(crate) fn evaluate_command(
+    policy: &Policy,
     command: &[String],
     approval_policy: AskForApproval,
     sandbox_policy: &SandboxPolicy,
     sandbox_permissions: SandboxPermissions,
-) -> ExecApprovalRequirement {
+) -> Evaluation {
     let commands = parse_shell_lc_plain_commands(command).unwrap_or_else(|| vec![command.to_vec()]);
     let heuristics_fallback = |cmd: &[String]| {
         if requires_initial_appoval(approval_policy, sandbox_policy, cmd, sandbox_permissions) {
@@ -233,21 +414,38 @@ pub(crate) async fn create_exec_approval_requirement_for_command(
             Decision::Allow
         }
     };
-    let policy = exec_policy.read().await;
-    let evaluation = policy.check_multiple(commands.iter(), &heuristics_fallback);
+    policy.check_multiple(commands.iter(), &heuristics_fallback)
+}
+
+pub(crate) async fn create_exec_approval_requirement_for_command(
+    exec_policy: &Arc<RwLock<ExecPolicy>>,
+    features: &Features,
+    command: &[String],
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+    sandbox_permissions: SandboxPermissions,
+) -> ExecApprovalRequirement {
+    let exec_policy = exec_policy.read().await;
+    let evaluation = evaluate_command(
+        exec_policy.policy(),
+        command,
+        approval_policy,
+        sandbox_policy,
+        sandbox_permissions,
+    );
 
     match evaluation.decision {
         Decision::Forbidden => ExecApprovalRequirement::Forbidden {
-            reason: FORBIDDEN_REASON.to_string(),
+            reason: exec_policy.reason_for(FORBIDDEN_REASON, &evaluation),
         },
         Decision::Prompt => {
             if matches!(approval_policy, AskForApproval::Never) {
                 ExecApprovalRequirement::Forbidden {
-                    reason: PROMPT_CONFLICT_REASON.to_string(),
+                    reason: exec_policy.reason_for(PROMPT_CONFLICT_REASON, &evaluation),
                 }
             } else {
                 ExecApprovalRequirement::NeedsApproval {
-                    reason: derive_prompt_reason(&evaluation),
+                    reason: derive_prompt_reason(&exec_policy, &evaluation),
                     proposed_execpolicy_amendment: if features.enabled(Feature::ExecPolicy) {
                         try_derive_execpolicy_amendment_for_prompt_rules(&evaluation.matched_rules)
                     } else {
@@ -270,7 +468,10 @@ pub(crate) async fn create_exec_approval_requirement_for_command(
     }
 }
 
-async fn collect_policy_files(dir: &Path) -> Result<Vec<PathBuf>, ExecPolicyError> {
+async fn collect_policy_files(
+    dir: &Path,
+    extensions: &[&str],
+) -> Result<Vec<PathBuf>, ExecPolicyError> {
     let mut read_dir = match fs::read_dir(dir).await {
         Ok(read_dir) => read_dir,
         Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
@@ -304,7 +505,7 @@ async fn collect_policy_files(dir: &Path) -> Result<Vec<PathBuf>, ExecPolicyErro
         if path
             .extension()
             .and_then(|ext| ext.to_str())
-            .is_some_and(|ext| ext == RULE_EXTENSION)
+            .is_some_and(|ext| extensions.contains(&ext))
             && file_type.is_file()
         {
             policy_paths.push(path);
@@ -334,7 +535,7 @@ mod tests {
         features.disable(Feature::ExecPolicy);
         let temp_dir = tempdir().expect("create temp dir");
 
-        let policy = load_exec_policy_for_features(&features, temp_dir.path())
+        let policy = load_exec_policy_for_features(&features, temp_dir.path(), None)
             .await
             .expect("policy result");
 
@@ -347,7 +548,9 @@ mod tests {
                     decision: Decision::Allow
                 }],
             },
-            policy.check_multiple(commands.iter(), &|_| Decision::Allow)
+            policy
+                .policy()
+                .check_multiple(commands.iter(), &|_| Decision::Allow)
         );
         assert!(!temp_dir.path().join(RULES_DIR_NAME).exists());
     }
@@ -357,7 +560,7 @@ mod tests {
         let temp_dir = tempdir().expect("create temp dir");
 
         let policy_dir = temp_dir.path().join(RULES_DIR_NAME);
-        let files = collect_policy_files(&policy_dir)
+        let files = collect_policy_files(&policy_dir, &[RULE_EXTENSION])
             .await
             .expect("collect policy files");
 
@@ -425,7 +628,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         parser
             .parse("test.rules", policy_src)
             .expect("parse policy");
-        let policy = Arc::new(RwLock::new(parser.build()));
+        let policy = Arc::new(RwLock::new(ExecPolicy::from(parser.build())));
 
         let forbidden_script = vec![
             "bash".to_string(),
@@ -458,7 +661,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         parser
             .parse("test.rules", policy_src)
             .expect("parse policy");
-        let policy = Arc::new(RwLock::new(parser.build()));
+        let policy = Arc::new(RwLock::new(ExecPolicy::from(parser.build())));
         let command = vec!["rm".to_string()];
 
         let requirement = create_exec_approval_requirement_for_command(
@@ -487,7 +690,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         parser
             .parse("test.rules", policy_src)
             .expect("parse policy");
-        let policy = Arc::new(RwLock::new(parser.build()));
+        let policy = Arc::new(RwLock::new(ExecPolicy::from(parser.build())));
         let command = vec!["rm".to_string()];
 
         let requirement = create_exec_approval_requirement_for_command(
@@ -512,7 +715,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
     async fn exec_approval_requirement_falls_back_to_heuristics() {
         let command = vec!["cargo".to_string(), "build".to_string()];
 
-        let empty_policy = Arc::new(RwLock::new(Policy::empty()));
+        let empty_policy = Arc::new(RwLock::new(ExecPolicy::empty()));
         let requirement = create_exec_approval_requirement_for_command(
             &empty_policy,
             &Features::with_defaults(),
@@ -539,7 +742,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         parser
             .parse("test.rules", policy_src)
             .expect("parse policy");
-        let policy = Arc::new(RwLock::new(parser.build()));
+        let policy = Arc::new(RwLock::new(ExecPolicy::from(parser.build())));
         let command = vec![
             "bash".to_string(),
             "-lc".to_string(),
@@ -568,14 +771,19 @@ prefix_rule(pattern=["rm"], decision="forbidden")
     #[tokio::test]
     async fn append_execpolicy_amendment_updates_policy_and_file() {
         let codex_home = tempdir().expect("create temp dir");
-        let current_policy = Arc::new(RwLock::new(Policy::empty()));
+        let current_policy = Arc::new(RwLock::new(ExecPolicy::empty()));
         let prefix = vec!["echo".to_string(), "hello".to_string()];
 
-        append_execpolicy_amendment_and_update(codex_home.path(), &current_policy, &prefix)
-            .await
-            .expect("update policy");
+        append_execpolicy_amendment_and_update(
+            codex_home.path(),
+            &current_policy,
+            &prefix,
+            ExecPolicyAmendmentTarget::User,
+        )
+        .await
+        .expect("update policy");
 
-        let evaluation = current_policy.read().await.check(
+        let evaluation = current_policy.read().await.policy().check(
             &["echo".to_string(), "hello".to_string(), "world".to_string()],
             &|_| Decision::Allow,
         );
@@ -599,10 +807,15 @@ prefix_rule(pattern=["rm"], decision="forbidden")
     #[tokio::test]
     async fn append_execpolicy_amendment_rejects_empty_prefix() {
         let codex_home = tempdir().expect("create temp dir");
-        let current_policy = Arc::new(RwLock::new(Policy::empty()));
+        let current_policy = Arc::new(RwLock::new(ExecPolicy::empty()));
 
-        let result =
-            append_execpolicy_amendment_and_update(codex_home.path(), &current_policy, &[]).await;
+        let result = append_execpolicy_amendment_and_update(
+            codex_home.path(),
+            &current_policy,
+            &[],
+            ExecPolicyAmendmentTarget::User,
+        )
+        .await;
 
         assert!(matches!(
             result,
@@ -613,11 +826,141 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         ));
     }
 
+    #[tokio::test]
+    async fn user_forbidden_rule_wins_over_project_allow() {
+        let codex_home = tempdir().expect("create temp dir");
+        let user_rules = codex_home.path().join(RULES_DIR_NAME);
+        fs::create_dir_all(&user_rules).expect("create user rules dir");
+        fs::write(
+            user_rules.join("deny.rules"),
+            r#"prefix_rule(pattern=["git", "push"], decision="forbidden")"#,
+        )
+        .expect("write user rules");
+
+        let repo = tempdir().expect("create repo dir");
+        let project_rules = repo.path().join(".codex").join(RULES_DIR_NAME);
+        fs::create_dir_all(&project_rules).expect("create project rules dir");
+        fs::write(
+            project_rules.join("repo.codexpolicy"),
+            r#"prefix_rule(pattern=["git"], decision="allow")
+prefix_rule(pattern=["make"], decision="prompt")"#,
+        )
+        .expect("write project rules");
+
+        let policy = load_exec_policy_for_features(
+            &Features::with_defaults(),
+            codex_home.path(),
+            Some(&project_rules),
+        )
+        .await
+        .expect("policy result");
+        let policy = Arc::new(RwLock::new(policy));
+
+        let requirement = |command: Vec<String>| {
+            let policy = Arc::clone(&policy);
+            async move {
+                create_exec_approval_requirement_for_command(
+                    &policy,
+                    &Features::with_defaults(),
+                    &command,
+                    AskForApproval::OnRequest,
+                    &SandboxPolicy::DangerFullAccess,
+                    SandboxPermissions::UseDefault,
+                )
+                .await
+            }
+        };
+
+        assert_eq!(
+            requirement(vec!["git".to_string(), "push".to_string()]).await,
+            ExecApprovalRequirement::Forbidden {
+                reason: format!("{FORBIDDEN_REASON} (user rules)"),
+            }
+        );
+        assert_eq!(
+            requirement(vec!["git".to_string(), "status".to_string()]).await,
+            ExecApprovalRequirement::Skip {
+                bypass_sandbox: true,
+                proposed_execpolicy_amendment: None,
+            }
+        );
+        assert_eq!(
+            requirement(vec!["make".to_string()]).await,
+            ExecApprovalRequirement::NeedsApproval {
+                reason: Some(format!(
+                    "{PROMPT_REASON} (project rules in {})",
+                    project_rules.display()
+                )),
+                proposed_execpolicy_amendment: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn append_execpolicy_amendment_writes_project_rules() {
+        let codex_home = tempdir().expect("create temp dir");
+        let repo = tempdir().expect("create repo dir");
+        let project_rules = repo.path().join(".codex").join(RULES_DIR_NAME);
+        let current_policy = Arc::new(RwLock::new(ExecPolicy::with_project_rules(
+            Policy::empty(),
+            project_rules.clone(),
+            Policy::empty(),
+        )));
+
+        append_execpolicy_amendment_and_update(
+            codex_home.path(),
+            &current_policy,
+            &["cargo".to_string(), "test".to_string()],
+            ExecPolicyAmendmentTarget::Project,
+        )
+        .await
+        .expect("update policy");
+
+        let contents = fs::read_to_string(project_rules.join(DEFAULT_POLICY_FILE))
+            .expect("project policy file should have been created");
+        assert_eq!(
+            contents,
+            r#"prefix_rule(pattern=["cargo", "test"], decision="allow")
+"#
+        );
+        assert!(!default_policy_path(codex_home.path()).exists());
+        assert_eq!(
+            current_policy
+                .read()
+                .await
+                .policy()
+                .check(&["cargo".to_string(), "test".to_string()], &|_| {
+                    Decision::Prompt
+                })
+                .decision,
+            Decision::Allow
+        );
+    }
+
+    #[tokio::test]
+    async fn project_amendment_requires_project_rules() {
+        let codex_home = tempdir().expect("create temp dir");
+        let current_policy = Arc::new(RwLock::new(ExecPolicy::empty()));
+
+        let result = append_execpolicy_amendment_and_update(
+            codex_home.path(),
+            &current_policy,
+            &["cargo".to_string()],
+            ExecPolicyAmendmentTarget::Project,
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ExecPolicyUpdateError::ProjectRulesUnavailable)
+        ));
+    }
+
     #[tokio::test]
     async fn proposed_execpolicy_amendment_is_present_for_single_command_without_policy_match() {
         let command = vec!["cargo".to_string(), "build".to_string()];
 
-        let empty_policy = Arc::new(RwLock::new(Policy::empty()));
+        let empty_policy = Arc::new(RwLock::new(ExecPolicy::empty()));
         let requirement = create_exec_approval_requirement_for_command(
             &empty_policy,
             &Features::with_defaults(),
@@ -645,7 +988,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         features.disable(Feature::ExecPolicy);
 
         let requirement = create_exec_approval_requirement_for_command(
-            &Arc::new(RwLock::new(Policy::empty())),
+            &Arc::new(RwLock::new(ExecPolicy::empty())),
             &features,
             &command,
             AskForApproval::UnlessTrusted,
@@ -670,7 +1013,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         parser
             .parse("test.rules", policy_src)
             .expect("parse policy");
-        let policy = Arc::new(RwLock::new(parser.build()));
+        let policy = Arc::new(RwLock::new(ExecPolicy::from(parser.build())));
         let command = vec!["rm".to_string()];
 
         let requirement = create_exec_approval_requirement_for_command(
@@ -700,7 +1043,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             "cargo build && echo ok".to_string(),
         ];
         let requirement = create_exec_approval_requirement_for_command(
-            &Arc::new(RwLock::new(Policy::empty())),
+            &Arc::new(RwLock::new(ExecPolicy::empty())),
             &Features::with_defaults(),
             &command,
             AskForApproval::UnlessTrusted,
@@ -728,7 +1071,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         parser
             .parse("test.rules", policy_src)
             .expect("parse policy");
-        let policy = Arc::new(RwLock::new(parser.build()));
+        let policy = Arc::new(RwLock::new(ExecPolicy::from(parser.build())));
 
         let command = vec![
             "bash".to_string(),
@@ -760,7 +1103,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let command = vec!["echo".to_string(), "safe".to_string()];
 
         let requirement = create_exec_approval_requirement_for_command(
-            &Arc::new(RwLock::new(Policy::empty())),
+            &Arc::new(RwLock::new(ExecPolicy::empty())),
             &Features::with_defaults(),
             &command,
             AskForApproval::OnRequest,
@@ -785,7 +1128,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         parser
             .parse("test.rules", policy_src)
             .expect("parse policy");
-        let policy = Arc::new(RwLock::new(parser.build()));
+        let policy = Arc::new(RwLock::new(ExecPolicy::from(parser.build())));
         let command = vec!["echo".to_string(), "safe".to_string()];
 
         let requirement = create_exec_approval_requirement_for_command(
@@ -0,0 +1,667 @@
+//! Replays commands recorded in rollout files against a candidate execpolicy.
+//!
+//! Used by `codex execpolicy audit` to show how a set of rules would have
+//! changed past sessions before the rules are put in place.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_execpolicy::Decision;
+use codex_execpolicy::Policy;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::ApprovalDecisionEvent;
+use codex_protocol::protocol::ApprovalDecisionSource;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::TurnContextItem;
+use serde::Serialize;
+
+use crate::RolloutRecorder;
+use crate::bash::parse_shell_lc_plain_commands;
+use crate::config::ConfigToml;
+use crate::exec_policy::evaluate_command;
+use crate::exec_policy::load_exec_policy_dir;
+use crate::exec_policy::project_rules_dir_for_cwd;
+use crate::sandboxing::SandboxPermissions;
+
+const PAGE_SIZE: usize = 50;
+
+/// What happened to a command in the recorded session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordedOutcome {
+    /// The command ran without asking.
+    Ran,
+    /// The user approved the command.
+    Approved,
+    /// The user declined the command.
+    Denied,
+    /// The command was refused without asking.
+    Forbidden,
+}
+
+impl From<&ApprovalDecisionEvent> for RecordedOutcome {
+    fn from(event: &ApprovalDecisionEvent) -> Self {
+        let denied = matches!(
+            event.decision,
+            ReviewDecision::Denied | ReviewDecision::Abort
+        );
+        match (event.source, denied) {
+            (ApprovalDecisionSource::Config, false) => RecordedOutcome::Ran,
+            (ApprovalDecisionSource::Config, true) => RecordedOutcome::Forbidden,
+            (ApprovalDecisionSource::User, false) => RecordedOutcome::Approved,
+            (ApprovalDecisionSource::User, true) => RecordedOutcome::Denied,
+        }
+    }
+}
+
+/// A command extracted from a rollout, with the recorded approval outcome and
+/// the settings the session evaluated it under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedCommand {
+    pub command: Vec<String>,
+    pub outcome: RecordedOutcome,
+    /// Working directory of the session, which selects its project rules.
+    pub cwd: PathBuf,
+    pub approval_policy: AskForApproval,
+    pub sandbox_policy: SandboxPolicy,
+    pub sandbox_permissions: SandboxPermissions,
+}
+
+/// A distinct command whose decision differs under the candidate policy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditChange {
+    pub command: Vec<String>,
+    /// Decision under the current rules and approval heuristics.
+    pub current: Decision,
+    /// Decision under the candidate rules and approval heuristics.
+    pub candidate: Decision,
+    pub outcome: RecordedOutcome,
+    /// Number of times the command was recorded.
+    pub occurrences: usize,
+}
+
+/// A prefix rule that would cover commands the user kept approving.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SuggestedRule {
+    pub prefix: Vec<String>,
+    /// Number of recorded approvals the rule would have covered.
+    pub occurrences: usize,
+}
+
+impl SuggestedRule {
+    /// Renders the suggestion in the syntax accepted by `.rules` files.
+    pub fn to_rule(&self) -> String {
+        let tokens = self
+            .prefix
+            .iter()
+            .map(|token| serde_json::to_string(token).unwrap_or_else(|_| format!("{token:?}")))
+            .collect::<Vec<_>>();
+        format!(
+            r#"prefix_rule(pattern=[{}], decision="allow")"#,
+            tokens.join(", ")
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AuditReport {
+    pub sessions_scanned: usize,
+    pub commands_scanned: usize,
+    pub newly_allowed: Vec<AuditChange>,
+    pub newly_prompted: Vec<AuditChange>,
+    pub newly_forbidden: Vec<AuditChange>,
+    pub suggested_rules: Vec<SuggestedRule>,
+}
+
+/// Extracts shell commands from rollout items and pairs each with the last
+/// approval decision recorded for its call. Calls without a recorded
+/// decision or turn context (for example from rollouts written before
+/// decisions were recorded) are skipped.
+pub fn extract_commands(items: &[RolloutItem]) -> Vec<RecordedCommand> {
+    let mut calls: Vec<(String, Vec<String>, SandboxPermissions, &TurnContextItem)> = Vec::new();
+    let mut decisions: HashMap<&str, RecordedOutcome> = HashMap::new();
+    let mut turn_context: Option<&TurnContextItem> = None;
+
+    for item in items {
+        match item {
+            RolloutItem::TurnContext(context) => turn_context = Some(context),
+            RolloutItem::EventMsg(EventMsg::ApprovalDecision(event)) => {
+                decisions.insert(event.call_id.as_str(), RecordedOutcome::from(event));
+            }
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            }) => {
+                if let Some(context) = turn_context
+                    && let Some((command, permissions)) =
+                        command_from_function_call(name, arguments)
+                {
+                    calls.push((call_id.clone(), command, permissions, context));
+                }
+            }
+            RolloutItem::ResponseItem(ResponseItem::LocalShellCall {
+                call_id: Some(call_id),
+                action: LocalShellAction::Exec(action),
+                ..
+            }) => {
+                if let Some(context) = turn_context {
+                    calls.push((
+                        call_id.clone(),
+                        action.command.clone(),
+                        SandboxPermissions::UseDefault,
+                        context,
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    calls
+        .into_iter()
+        .filter_map(|(call_id, command, sandbox_permissions, context)| {
+            Some(RecordedCommand {
+                command,
+                outcome: *decisions.get(call_id.as_str())?,
+                cwd: context.cwd.clone(),
+                approval_policy: context.approval_policy,
+                sandbox_policy: context.sandbox_policy.clone(),
+                sandbox_permissions,
+            })
+        })
+        .collect()
+}
+
+fn command_from_function_call(
+    name: &str,
+    arguments: &str,
+) -> Option<(Vec<String>, SandboxPermissions)> {
+    let arguments: serde_json::Value = serde_json::from_str(arguments).ok()?;
+    let sandbox_permissions = arguments
+        .get("sandbox_permissions")
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default();
+    let script = match name {
+        "shell" | "container.exec" | "local_shell" => {
+            let command = serde_json::from_value(arguments.get("command")?.clone()).ok()?;
+            return Some((command, sandbox_permissions));
+        }
+        "shell_command" => arguments.get("command")?.as_str()?,
+        "exec_command" => arguments.get("cmd")?.as_str()?,
+        _ => return None,
+    };
+    let command = vec!["bash".to_string(), "-lc".to_string(), script.to_string()];
+    Some((command, sandbox_permissions))
+}
+
+/// Decision a session would reach for `recorded` under `policy`, including
+/// the approval heuristics for commands no rule matches. A prompt under
+/// `AskForApproval::Never` is refused, as in the session.
+fn effective_decision(policy: &Policy, recorded: &RecordedCommand) -> Decision {
+    let evaluation = evaluate_command(
+        policy,
+        &recorded.command,
+        recorded.approval_policy,
+        &recorded.sandbox_policy,
+        recorded.sandbox_permissions,
+    );
+    match evaluation.decision {
+        Decision::Prompt if matches!(recorded.approval_policy, AskForApproval::Never) => {
+            Decision::Forbidden
+        }
+        decision => decision,
+    }
+}
+
+/// Prefix suggested for a command: the program plus its first non-flag
+/// argument, which is usually the subcommand (`cargo test`, `git status`).
+fn suggested_prefix(command: &[String]) -> Option<Vec<String>> {
+    let commands = parse_shell_lc_plain_commands(command).unwrap_or_else(|| vec![command.to_vec()]);
+    // Scripts chaining several commands would need one rule per command.
+    let [command] = commands.as_slice() else {
+        return None;
+    };
+    let (program, args) = command.split_first()?;
+    let mut prefix = vec![program.clone()];
+    if let Some(subcommand) = args.first().filter(|arg| !arg.starts_with('-')) {
+        prefix.push(subcommand.clone());
+    }
+    Some(prefix)
+}
+
+fn with_project_rules(policy: &Policy, project: &Policy) -> Policy {
+    let mut merged = policy.clone();
+    merged.merge(project);
+    merged
+}
+
+/// Compares the decisions of `current` and `candidate` for every recorded
+/// command. `project_rules` maps the cwd of sessions in trusted projects to
+/// the rules checked into that project, which apply under both policies.
+/// Suggestions cover commands the user approved, that the candidate rules
+/// would still prompt for, and that share a prefix the user never declined.
+pub fn audit_commands(
+    commands: &[RecordedCommand],
+    current: &Policy,
+    candidate: &Policy,
+    project_rules: &HashMap<PathBuf, Policy>,
+    max_suggestions: usize,
+) -> AuditReport {
+    let mut report = AuditReport {
+        commands_scanned: commands.len(),
+        ..Default::default()
+    };
+
+    let mut merged_policies: HashMap<&Path, (Policy, Policy)> = HashMap::new();
+    let mut changes: BTreeMap<(Vec<String>, RecordedOutcome), AuditChange> = BTreeMap::new();
+    let mut approved_prefixes: HashMap<Vec<String>, usize> = HashMap::new();
+    let mut denied_prefixes: HashSet<Vec<String>> = HashSet::new();
+
+    for recorded in commands {
+        let (current_policy, candidate_policy) = match project_rules.get(&recorded.cwd) {
+            Some(project) => {
+                let (merged_current, merged_candidate) = merged_policies
+                    .entry(recorded.cwd.as_path())
+                    .or_insert_with(|| {
+                        (
+                            with_project_rules(current, project),
+                            with_project_rules(candidate, project),
+                        )
+                    });
+                (&*merged_current, &*merged_candidate)
+            }
+            None => (current, candidate),
+        };
+        let current_decision = effective_decision(current_policy, recorded);
+        let candidate_decision = effective_decision(candidate_policy, recorded);
+
+        if current_decision != candidate_decision {
+            changes
+                .entry((recorded.command.clone(), recorded.outcome))
+                .or_insert_with(|| AuditChange {
+                    command: recorded.command.clone(),
+                    current: current_decision,
+                    candidate: candidate_decision,
+                    outcome: recorded.outcome,
+                    occurrences: 0,
+                })
+                .occurrences += 1;
+        }
+
+        let Some(prefix) = suggested_prefix(&recorded.command) else {
+            continue;
+        };
+        match recorded.outcome {
+            RecordedOutcome::Approved if candidate_decision == Decision::Prompt => {
+                *approved_prefixes.entry(prefix).or_default() += 1;
+            }
+            RecordedOutcome::Denied => {
+                denied_prefixes.insert(prefix);
+            }
+            RecordedOutcome::Ran | RecordedOutcome::Approved | RecordedOutcome::Forbidden => {}
+        }
+    }
+
+    for change in changes.into_values() {
+        match change.candidate {
+            Decision::Allow => report.newly_allowed.push(change),
+            Decision::Prompt => report.newly_prompted.push(change),
+            Decision::Forbidden => report.newly_forbidden.push(change),
+        }
+    }
+
+    let mut suggested_rules = approved_prefixes
+        .into_iter()
+        .filter(|(prefix, _)| !denied_prefixes.contains(prefix))
+        .map(|(prefix, occurrences)| SuggestedRule {
+            prefix,
+            occurrences,
+        })
+        .collect::<Vec<_>>();
+    suggested_rules.sort_by(|a, b| {
+        b.occurrences
+            .cmp(&a.occurrences)
+            .then_with(|| a.prefix.cmp(&b.prefix))
+    });
+    suggested_rules.truncate(max_suggestions);
+    report.suggested_rules = suggested_rules;
+
+    report
+}
+
+/// Audits up to `max_sessions` of the most recent sessions in `codex_home`.
+/// Sessions in projects `config` trusts are also checked against the rules
+/// checked into the project. Rollouts and project rules that cannot be read
+/// are skipped with a warning.
+pub async fn audit_rollouts(
+    codex_home: &Path,
+    config: &ConfigToml,
+    current: &Policy,
+    candidate: &Policy,
+    max_sessions: usize,
+    max_suggestions: usize,
+) -> std::io::Result<AuditReport> {
+    let paths = list_rollout_paths(codex_home, max_sessions).await?;
+
+    let mut commands = Vec::new();
+    for path in &paths {
+        match RolloutRecorder::get_rollout_history(path).await {
+            Ok(history) => commands.extend(extract_commands(&history.get_rollout_items())),
+            Err(err) => tracing::warn!("skipping rollout {}: {err}", path.display()),
+        }
+    }
+
+    let project_rules = load_project_rules(config, &commands).await;
+    let mut report = audit_commands(
+        &commands,
+        current,
+        candidate,
+        &project_rules,
+        max_suggestions,
+    );
+    report.sessions_scanned = paths.len();
+    Ok(report)
+}
+
+/// Loads the checked-in rules of every trusted project a recorded command ran
+/// in, keyed by the session cwd. Each rules directory is read once.
+async fn load_project_rules(
+    config: &ConfigToml,
+    commands: &[RecordedCommand],
+) -> HashMap<PathBuf, Policy> {
+    let cwds = commands
+        .iter()
+        .map(|recorded| recorded.cwd.as_path())
+        .collect::<HashSet<_>>();
+
+    let mut by_dir: HashMap<PathBuf, Option<Policy>> = HashMap::new();
+    let mut project_rules = HashMap::new();
+    for cwd in cwds {
+        if !config
+            .get_active_project(cwd)
+            .is_some_and(|project| project.is_trusted())
+        {
+            continue;
+        }
+        let dir = project_rules_dir_for_cwd(cwd);
+        if !by_dir.contains_key(&dir) {
+            let policy = match load_exec_policy_dir(&dir).await {
+                Ok(policy) => Some(policy),
+                Err(err) => {
+                    tracing::warn!("skipping project rules in {}: {err}", dir.display());
+                    None
+                }
+            };
+            by_dir.insert(dir.clone(), policy);
+        }
+        if let Some(Some(policy)) = by_dir.get(&dir) {
+            project_rules.insert(cwd.to_path_buf(), policy.clone());
+        }
+    }
+    project_rules
+}
+
+async fn list_rollout_paths(
+    codex_home: &Path,
+    max_sessions: usize,
+) -> std::io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    let mut cursor = None;
+    while paths.len() < max_sessions {
+        let page = RolloutRecorder::list_conversations(
+            codex_home,
+            PAGE_SIZE.min(max_sessions - paths.len()),
+            cursor.as_ref(),
+            &[],
+            None,
+            "",
+        )
+        .await?;
+        paths.extend(page.items.into_iter().map(|item| item.path));
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_execpolicy::PolicyParser;
+    use codex_protocol::config_types::ReasoningSummary;
+    use pretty_assertions::assert_eq;
+
+    fn strings(tokens: &[&str]) -> Vec<String> {
+        tokens.iter().map(ToString::to_string).collect()
+    }
+
+    fn turn_context(approval_policy: AskForApproval) -> RolloutItem {
+        RolloutItem::TurnContext(TurnContextItem {
+            cwd: PathBuf::from("/work"),
+            approval_policy,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            model: "gpt-5".to_string(),
+            effort: None,
+            summary: ReasoningSummary::Auto,
+        })
+    }
+
+    fn call(call_id: &str, name: &str, arguments: serde_json::Value) -> RolloutItem {
+        RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+            call_id: call_id.to_string(),
+            thought_signature: None,
+        })
+    }
+
+    fn decision(
+        call_id: &str,
+        decision: ReviewDecision,
+        source: ApprovalDecisionSource,
+    ) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::ApprovalDecision(ApprovalDecisionEvent {
+            call_id: call_id.to_string(),
+            decision,
+            source,
+        }))
+    }
+
+    fn recorded(command: &[&str], outcome: RecordedOutcome) -> RecordedCommand {
+        RecordedCommand {
+            command: strings(command),
+            outcome,
+            cwd: PathBuf::from("/work"),
+            approval_policy: AskForApproval::UnlessTrusted,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            sandbox_permissions: SandboxPermissions::UseDefault,
+        }
+    }
+
+    fn policy(src: &str) -> Policy {
+        let mut parser = PolicyParser::new();
+        parser.parse("test.rules", src).expect("parse policy");
+        parser.build()
+    }
+
+    #[test]
+    fn extracts_commands_with_recorded_decisions() {
+        let items = vec![
+            call("0", "shell", serde_json::json!({"command": ["ls"]})),
+            turn_context(AskForApproval::OnRequest),
+            call(
+                "1",
+                "shell",
+                serde_json::json!({
+                    "command": ["cargo", "test"],
+                    "sandbox_permissions": "require_escalated",
+                }),
+            ),
+            decision("1", ReviewDecision::Approved, ApprovalDecisionSource::User),
+            call(
+                "2",
+                "shell_command",
+                serde_json::json!({"command": "rm -rf target"}),
+            ),
+            decision("2", ReviewDecision::Denied, ApprovalDecisionSource::User),
+            call("3", "exec_command", serde_json::json!({"cmd": "git push"})),
+            decision("3", ReviewDecision::Denied, ApprovalDecisionSource::Config),
+            call("4", "read_file", serde_json::json!({"path": "a.rs"})),
+            decision(
+                "4",
+                ReviewDecision::Approved,
+                ApprovalDecisionSource::Config,
+            ),
+            call("5", "shell", serde_json::json!({"command": ["ls"]})),
+            decision(
+                "5",
+                ReviewDecision::Approved,
+                ApprovalDecisionSource::Config,
+            ),
+            // A sandbox denial followed by an approved retry counts as approved.
+            decision("5", ReviewDecision::Approved, ApprovalDecisionSource::User),
+            call("6", "shell", serde_json::json!({"command": ["pwd"]})),
+        ];
+
+        let on_request = |command: &[&str], outcome, sandbox_permissions| RecordedCommand {
+            approval_policy: AskForApproval::OnRequest,
+            sandbox_permissions,
+            ..recorded(command, outcome)
+        };
+        assert_eq!(
+            extract_commands(&items),
+            vec![
+                on_request(
+                    &["cargo", "test"],
+                    RecordedOutcome::Approved,
+                    SandboxPermissions::RequireEscalated,
+                ),
+                on_request(
+                    &["bash", "-lc", "rm -rf target"],
+                    RecordedOutcome::Denied,
+                    SandboxPermissions::UseDefault,
+                ),
+                on_request(
+                    &["bash", "-lc", "git push"],
+                    RecordedOutcome::Forbidden,
+                    SandboxPermissions::UseDefault,
+                ),
+                on_request(
+                    &["ls"],
+                    RecordedOutcome::Approved,
+                    SandboxPermissions::UseDefault,
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_changed_decisions_and_suggestions() {
+        let approved = |command: &[&str]| recorded(command, RecordedOutcome::Approved);
+        let in_project = RecordedCommand {
+            cwd: PathBuf::from("/repo"),
+            ..approved(&["make", "build"])
+        };
+        let never = RecordedCommand {
+            approval_policy: AskForApproval::Never,
+            ..recorded(&["npm", "test"], RecordedOutcome::Ran)
+        };
+        let commands = vec![
+            approved(&["cargo", "test", "--all"]),
+            approved(&["cargo", "test"]),
+            recorded(&["npm", "install"], RecordedOutcome::Ran),
+            approved(&["git", "push", "origin"]),
+            recorded(&["bash", "-lc", "ls -la"], RecordedOutcome::Ran),
+            approved(&["make", "build"]),
+            approved(&["bash", "-lc", "make build"]),
+            in_project,
+            recorded(&["rm", "-rf", "build"], RecordedOutcome::Denied),
+            approved(&["rm", "-f", "old.txt"]),
+            never,
+        ];
+        let current = policy(r#"prefix_rule(pattern=["npm"], decision="allow")"#);
+        let candidate = policy(
+            r#"
+prefix_rule(pattern=["cargo", "test"], decision="allow")
+prefix_rule(pattern=["git", "push"], decision="forbidden")
+prefix_rule(pattern=["npm", "test"], decision="prompt")
+"#,
+        );
+        let project_rules = HashMap::from([(
+            PathBuf::from("/repo"),
+            policy(r#"prefix_rule(pattern=["make"], decision="allow")"#),
+        )]);
+
+        let report = audit_commands(&commands, &current, &candidate, &project_rules, 10);
+
+        let change = |command: &[&str], current, candidate, outcome| AuditChange {
+            command: strings(command),
+            current,
+            candidate,
+            outcome,
+            occurrences: 1,
+        };
+        assert_eq!(
+            report,
+            AuditReport {
+                sessions_scanned: 0,
+                commands_scanned: 11,
+                newly_allowed: vec![
+                    change(
+                        &["cargo", "test"],
+                        Decision::Prompt,
+                        Decision::Allow,
+                        RecordedOutcome::Approved,
+                    ),
+                    change(
+                        &["cargo", "test", "--all"],
+                        Decision::Prompt,
+                        Decision::Allow,
+                        RecordedOutcome::Approved,
+                    ),
+                ],
+                newly_prompted: vec![change(
+                    &["npm", "install"],
+                    Decision::Allow,
+                    Decision::Prompt,
+                    RecordedOutcome::Ran,
+                )],
+                newly_forbidden: vec![
+                    change(
+                        &["git", "push", "origin"],
+                        Decision::Prompt,
+                        Decision::Forbidden,
+                        RecordedOutcome::Approved,
+                    ),
+                    change(
+                        &["npm", "test"],
+                        Decision::Allow,
+                        Decision::Forbidden,
+                        RecordedOutcome::Ran,
+                    ),
+                ],
+                suggested_rules: vec![SuggestedRule {
+                    prefix: strings(&["make", "build"]),
+                    occurrences: 2,
+                }],
+            }
+        );
+        assert_eq!(
+            report.suggested_rules[0].to_rule(),
+            r#"prefix_rule(pattern=["make", "build"], decision="allow")"#
+        );
+    }
+}
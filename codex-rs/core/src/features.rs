@@ -83,7 +83,14 @@ pub enum Feature {
     WindowsSandboxElevated,
     /// Remote compaction enabled (only for ChatGPT auth)
     RemoteCompaction,
-    /// Refresh remote models and emit AppReady once the list is available.
+    /// Keep the plan, pinned messages and recent tool outputs across local
+    /// compaction and ask for a structured summary.
+    StructuredCompaction,
+    /// Generate a title and summary for interactive sessions and record them
+    /// in the rollout.
+    SessionTitles,
+    /// Refresh remote models, list the models of other configured providers,
+    /// and emit AppReady once the list is available.
     RemoteModels,
     /// Allow model to call multiple tools in parallel (only for models supporting it).
     ParallelToolCalls,
@@ -93,6 +100,10 @@ pub enum Feature {
     Tui2,
     /// Enable discovery and injection of skills.
     Skills,
+    /// Include the tree-sitter backed code_outline and find_symbol tools.
+    CodeOutline,
+    /// Report language server diagnostics after edits and expose the diagnostics tool.
+    LspDiagnostics,
 }
 
 impl Feature {
@@ -385,6 +396,18 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Experimental,
         default_enabled: true,
     },
+    FeatureSpec {
+        id: Feature::StructuredCompaction,
+        key: "structured_compaction",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::SessionTitles,
+        key: "session_titles",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::RemoteModels,
         key: "remote_models",
@@ -397,6 +420,18 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Experimental,
         default_enabled: !cfg!(windows),
     },
+    FeatureSpec {
+        id: Feature::CodeOutline,
+        key: "code_outline",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::LspDiagnostics,
+        key: "lsp_diagnostics",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::Tui2,
         key: "tui2",
@@ -0,0 +1,964 @@
+//! Content-addressed file snapshots used for undo outside of Git repositories.
+//!
+//! Ghost commits only work inside a Git work tree. For every other workspace
+//! root (a plain directory, or an `--add-dir` root that lives outside the
+//! repository) we record a JSON manifest under `CODEX_HOME/file_snapshots`
+//! mapping each captured path to a blob keyed by the SHA-256 of its contents.
+//! Blobs are shared between snapshots, and files whose size and modification
+//! time match the previous manifest for the same root are not read again, so
+//! only the files touched by `apply_patch` or shell commands since the last
+//! turn cost anything to capture.
+//!
+//! When a turn ends, the paths that differ from its manifest are recorded in
+//! it. Restoring a manifest only touches those paths, so files the user
+//! created or edited outside of agent turns are left alone.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+use codex_git::DEFAULT_IGNORED_DIR_NAMES;
+use codex_git::GhostSnapshotConfig;
+use codex_git::GhostSnapshotReport;
+use codex_git::IgnoredUntrackedFile;
+use codex_git::LargeUntrackedDir;
+use ignore::WalkBuilder;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use tempfile::NamedTempFile;
+
+use crate::git_info::get_git_repo_root;
+use crate::protocol::SandboxPolicy;
+
+const STORE_DIR: &str = "file_snapshots";
+const MANIFESTS_DIR: &str = "manifests";
+const OBJECTS_DIR: &str = "objects";
+const LATEST_DIR: &str = "latest";
+/// Pruning starts once more than this many manifests are on disk.
+const MAX_MANIFESTS: usize = 200;
+/// Number of most recent manifests kept when pruning.
+const RETAINED_MANIFESTS: usize = 100;
+
+/// Snapshot store rooted at `CODEX_HOME/file_snapshots`.
+#[derive(Debug)]
+pub(crate) struct FileSnapshotStore {
+    codex_home: PathBuf,
+    dir: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    id: String,
+    roots: Vec<RootManifest>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RootManifest {
+    path: PathBuf,
+    #[serde(default)]
+    dirs: BTreeSet<PathBuf>,
+    #[serde(default)]
+    files: BTreeMap<PathBuf, FileEntry>,
+    /// Files above the size threshold. Their contents are not captured and
+    /// undo leaves them untouched.
+    #[serde(default)]
+    ignored_files: BTreeSet<PathBuf>,
+    /// Directories above the file count threshold. Undo leaves everything
+    /// beneath them untouched.
+    #[serde(default)]
+    ignored_dirs: BTreeSet<PathBuf>,
+    /// Paths that differed from this manifest when a turn started from it
+    /// ended. `None` until a turn records its changes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    changed: Option<BTreeSet<PathBuf>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum FileEntry {
+    File {
+        hash: String,
+        size: u64,
+        modified_ns: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mode: Option<u32>,
+    },
+    Symlink {
+        target: PathBuf,
+    },
+}
+
+/// Files, symlinks and directories found under a root, relative to it.
+#[derive(Default)]
+struct Scan {
+    dirs: BTreeSet<PathBuf>,
+    entries: BTreeMap<PathBuf, fs::Metadata>,
+}
+
+/// Workspace roots that need a file snapshot because no Git repository
+/// covers them: the session cwd plus any extra writable roots.
+pub(crate) fn snapshot_roots(cwd: &Path, sandbox_policy: &SandboxPolicy) -> Vec<PathBuf> {
+    let mut candidates = vec![cwd.to_path_buf()];
+    if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = sandbox_policy {
+        candidates.extend(
+            writable_roots
+                .iter()
+                .map(|root| root.as_path().to_path_buf()),
+        );
+    }
+
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for candidate in candidates {
+        if !candidate.is_dir() || get_git_repo_root(&candidate).is_some() {
+            continue;
+        }
+        if roots.iter().any(|root| candidate.starts_with(root)) {
+            continue;
+        }
+        roots.retain(|root| !root.starts_with(&candidate));
+        roots.push(candidate);
+    }
+    roots
+}
+
+impl FileSnapshotStore {
+    pub(crate) fn new(codex_home: &Path) -> Self {
+        Self {
+            codex_home: codex_home.to_path_buf(),
+            dir: codex_home.join(STORE_DIR),
+        }
+    }
+
+    /// Captures `roots` into a manifest named `id`. Paths in the returned
+    /// report are relative for the first root and absolute for the others.
+    pub(crate) fn capture(
+        &self,
+        id: &str,
+        roots: &[PathBuf],
+        config: &GhostSnapshotConfig,
+    ) -> io::Result<GhostSnapshotReport> {
+        validate_id(id)?;
+        let mut report = GhostSnapshotReport::default();
+        let mut manifest = Manifest {
+            id: id.to_string(),
+            roots: Vec::with_capacity(roots.len()),
+        };
+        for (idx, root) in roots.iter().enumerate() {
+            let previous = self.load_latest(root);
+            let display_root = (idx > 0).then_some(root.as_path());
+            manifest.roots.push(self.capture_root(
+                root,
+                previous.as_ref(),
+                config,
+                display_root,
+                &mut report,
+            )?);
+        }
+
+        self.write_manifest(&manifest)?;
+        for root in roots {
+            write_atomic(&self.latest_path(root), id.as_bytes())?;
+        }
+        if let Err(err) = self.prune() {
+            tracing::warn!("failed to prune file snapshots: {err}");
+        }
+        Ok(report)
+    }
+
+    /// Whether a manifest named `id` exists in the store.
+    pub(crate) fn contains(&self, id: &str) -> bool {
+        validate_id(id).is_ok() && self.manifest_path(id).is_file()
+    }
+
+    /// Records the paths that changed under each root of the manifest named
+    /// `id` since it was captured. Called when the turn that captured it ends.
+    pub(crate) fn record_changes(&self, id: &str) -> io::Result<()> {
+        validate_id(id)?;
+        let mut manifest = self.read_manifest(id)?;
+        for root in &mut manifest.roots {
+            let changed = self.diff_root(root)?;
+            root.changed.get_or_insert_default().extend(changed);
+        }
+        self.write_manifest(&manifest)
+    }
+
+    /// Restores the paths recorded as changed in the manifest named `id`:
+    /// captured files get their recorded contents back and files created
+    /// since are removed. Without recorded changes only captured files that
+    /// differ are rewritten and nothing is deleted.
+    pub(crate) fn restore(&self, id: &str) -> io::Result<()> {
+        validate_id(id)?;
+        let manifest = self.read_manifest(id)?;
+        for root in &manifest.roots {
+            self.restore_root(root)?;
+        }
+        Ok(())
+    }
+
+    /// Regular files whose contents differ between the manifest named `id`
+    /// and the workspace, as absolute paths paired with their snapshot
+    /// contents (`None` when the file did not exist yet).
+    pub(crate) fn changed_files(&self, id: &str) -> io::Result<Vec<(PathBuf, Option<Vec<u8>>)>> {
+        validate_id(id)?;
+        let manifest = self.read_manifest(id)?;
+        let mut changed = Vec::new();
+        for root_manifest in &manifest.roots {
+            let root = &root_manifest.path;
+            let current = self.scan(root)?;
+            for (rel, metadata) in &current.entries {
+                if metadata.is_file()
+                    && !root_manifest.files.contains_key(rel)
+                    && !root_manifest.ignored_files.contains(rel)
+                    && !is_under_any(rel, &root_manifest.ignored_dirs)
+                {
+                    changed.push((root.join(rel), None));
+                }
+            }
+            for (rel, entry) in &root_manifest.files {
+                let FileEntry::File {
+                    hash,
+                    size,
+                    modified_ns: recorded_modified,
+                    ..
+                } = entry
+                else {
+                    continue;
+                };
+                if let Some(metadata) = current.entries.get(rel)
+                    && metadata.is_file()
+                    && metadata.len() == *size
+                    && (modified_ns(metadata) == *recorded_modified
+                        || format!("{:x}", Sha256::digest(fs::read(root.join(rel))?)) == *hash)
+                {
+                    continue;
+                }
+                changed.push((root.join(rel), Some(fs::read(self.object_path(hash))?)));
+            }
+        }
+        Ok(changed)
+    }
+
+    fn capture_root(
+        &self,
+        root: &Path,
+        previous: Option<&RootManifest>,
+        config: &GhostSnapshotConfig,
+        display_root: Option<&Path>,
+        report: &mut GhostSnapshotReport,
+    ) -> io::Result<RootManifest> {
+        let scan = self.scan(root)?;
+        let display = |rel: &Path| match display_root {
+            Some(display_root) => display_root.join(rel),
+            None => rel.to_path_buf(),
+        };
+
+        let mut manifest = RootManifest {
+            path: root.to_path_buf(),
+            ..Default::default()
+        };
+
+        if let Some(threshold) = config.ignore_large_untracked_dirs.filter(|n| *n > 0) {
+            let mut counts: BTreeMap<&Path, i64> = BTreeMap::new();
+            for rel in scan.entries.keys() {
+                if let Some(parent) = rel.parent().filter(|p| !p.as_os_str().is_empty()) {
+                    *counts.entry(parent).or_insert(0) += 1;
+                }
+            }
+            for (dir, file_count) in counts {
+                if file_count >= threshold && !is_under_any(dir, &manifest.ignored_dirs) {
+                    manifest.ignored_dirs.insert(dir.to_path_buf());
+                    report.large_untracked_dirs.push(LargeUntrackedDir {
+                        path: display(dir),
+                        file_count,
+                    });
+                }
+            }
+        }
+
+        manifest.dirs = scan
+            .dirs
+            .into_iter()
+            .filter(|dir| !is_under_any(dir, &manifest.ignored_dirs))
+            .collect();
+
+        let max_file_size = config
+            .ignore_large_untracked_files
+            .filter(|bytes| *bytes > 0)
+            .and_then(|bytes| u64::try_from(bytes).ok());
+        for (rel, metadata) in scan.entries {
+            if is_under_any(&rel, &manifest.ignored_dirs) {
+                continue;
+            }
+            let path = root.join(&rel);
+            if metadata.file_type().is_symlink() {
+                let target = fs::read_link(&path)?;
+                manifest.files.insert(rel, FileEntry::Symlink { target });
+                continue;
+            }
+
+            let size = metadata.len();
+            if let Some(max_file_size) = max_file_size
+                && size > max_file_size
+            {
+                report.ignored_untracked_files.push(IgnoredUntrackedFile {
+                    path: display(&rel),
+                    byte_size: i64::try_from(size).unwrap_or(i64::MAX),
+                });
+                manifest.ignored_files.insert(rel);
+                continue;
+            }
+
+            let modified_ns = modified_ns(&metadata);
+            let reused = previous
+                .and_then(|previous| previous.files.get(&rel))
+                .and_then(|entry| match entry {
+                    FileEntry::File {
+                        hash,
+                        size: prev_size,
+                        modified_ns: prev_modified,
+                        ..
+                    } if *prev_size == size
+                        && *prev_modified == modified_ns
+                        && self.object_path(hash).is_file() =>
+                    {
+                        Some(hash.clone())
+                    }
+                    _ => None,
+                });
+            let hash = match reused {
+                Some(hash) => hash,
+                None => self.store_object(&fs::read(&path)?)?,
+            };
+            manifest.files.insert(
+                rel,
+                FileEntry::File {
+                    hash,
+                    size,
+                    modified_ns,
+                    mode: file_mode(&metadata),
+                },
+            );
+        }
+
+        Ok(manifest)
+    }
+
+    /// Paths under the manifest's root that were added, removed or modified
+    /// since it was captured.
+    fn diff_root(&self, manifest: &RootManifest) -> io::Result<BTreeSet<PathBuf>> {
+        let root = &manifest.path;
+        let current = self.scan(root)?;
+        let preserved = |rel: &Path| {
+            manifest.ignored_files.contains(rel) || is_under_any(rel, &manifest.ignored_dirs)
+        };
+
+        let mut changed = BTreeSet::new();
+        for (rel, metadata) in &current.entries {
+            let unchanged = manifest
+                .files
+                .get(rel)
+                .is_some_and(|entry| entry_matches(entry, metadata, &root.join(rel)));
+            if !unchanged && !preserved(rel) {
+                changed.insert(rel.clone());
+            }
+        }
+        for rel in manifest.files.keys() {
+            if !current.entries.contains_key(rel) {
+                changed.insert(rel.clone());
+            }
+        }
+        for rel in current.dirs.symmetric_difference(&manifest.dirs) {
+            if !preserved(rel) {
+                changed.insert(rel.clone());
+            }
+        }
+        Ok(changed)
+    }
+
+    fn restore_root(&self, manifest: &RootManifest) -> io::Result<()> {
+        let root = &manifest.path;
+        fs::create_dir_all(root)?;
+        let changed = match &manifest.changed {
+            Some(changed) => changed.clone(),
+            None => self
+                .diff_root(manifest)?
+                .into_iter()
+                .filter(|rel| manifest.files.contains_key(rel) || manifest.dirs.contains(rel))
+                .collect(),
+        };
+
+        // Deepest paths first so directories created since are empty by the
+        // time we reach them. Directories still holding files nobody recorded
+        // are kept.
+        for rel in changed.iter().rev() {
+            if manifest.files.contains_key(rel) || manifest.dirs.contains(rel) {
+                continue;
+            }
+            let path = root.join(rel);
+            match fs::symlink_metadata(&path) {
+                Ok(metadata) if metadata.is_dir() => {
+                    if fs::read_dir(&path)?.next().is_none() {
+                        fs::remove_dir(&path)?;
+                    }
+                }
+                Ok(_) => fs::remove_file(&path)?,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        for rel in changed.iter().filter(|rel| manifest.dirs.contains(*rel)) {
+            let path = root.join(rel);
+            if !path.is_dir() {
+                remove_path(&path)?;
+                fs::create_dir_all(&path)?;
+            }
+        }
+
+        for rel in &changed {
+            let Some(entry) = manifest.files.get(rel) else {
+                continue;
+            };
+            let path = root.join(rel);
+            let existing = fs::symlink_metadata(&path).ok();
+            match entry {
+                FileEntry::File {
+                    hash,
+                    size,
+                    modified_ns: recorded_modified,
+                    mode,
+                } => {
+                    if let Some(metadata) = &existing
+                        && metadata.is_file()
+                        && metadata.len() == *size
+                        && modified_ns(metadata) == *recorded_modified
+                        && (mode.is_none() || file_mode(metadata) == *mode)
+                    {
+                        continue;
+                    }
+                    let contents = fs::read(self.object_path(hash))?;
+                    if existing.is_some() {
+                        remove_path(&path)?;
+                    }
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(&path, contents)?;
+                    if let Some(mode) = mode {
+                        set_file_mode(&path, *mode)?;
+                    }
+                }
+                FileEntry::Symlink { target } => {
+                    if existing
+                        .as_ref()
+                        .is_some_and(|metadata| metadata.file_type().is_symlink())
+                        && fs::read_link(&path).is_ok_and(|current| &current == target)
+                    {
+                        continue;
+                    }
+                    if existing.is_some() {
+                        remove_path(&path)?;
+                    }
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    // Windows needs the resolved target to pick a file or directory link.
+                    let source = path
+                        .parent()
+                        .map_or_else(|| target.clone(), |parent| parent.join(target));
+                    codex_git::create_symlink(&source, target, &path).map_err(io::Error::other)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Walks `root` without following symlinks, honoring ignore files and
+    /// skipping the same dependency and cache directories as ghost commits,
+    /// `.git`, and `CODEX_HOME` itself.
+    fn scan(&self, root: &Path) -> io::Result<Scan> {
+        let root = dunce::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+        let codex_home =
+            dunce::canonicalize(&self.codex_home).unwrap_or_else(|_| self.codex_home.clone());
+        let walker = WalkBuilder::new(&root)
+            .hidden(false)
+            .follow_links(false)
+            .require_git(false)
+            .filter_entry(move |entry| {
+                // Non UTF-8 names cannot be stored in the manifest; leave them alone.
+                let Some(name) = entry.file_name().to_str() else {
+                    return false;
+                };
+                name != ".git"
+                    && !DEFAULT_IGNORED_DIR_NAMES.contains(&name)
+                    && entry.path() != codex_home
+            })
+            .build();
+
+        let mut scan = Scan::default();
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    tracing::debug!("skipping entry while scanning {}: {err}", root.display());
+                    continue;
+                }
+            };
+            let Ok(rel) = entry.path().strip_prefix(&root) else {
+                continue;
+            };
+            if rel.as_os_str().is_empty() {
+                continue;
+            }
+            let metadata = match fs::symlink_metadata(entry.path()) {
+                Ok(metadata) => metadata,
+                // Removed while we were walking.
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+            if metadata.is_dir() {
+                scan.dirs.insert(rel.to_path_buf());
+            } else {
+                scan.entries.insert(rel.to_path_buf(), metadata);
+            }
+        }
+        Ok(scan)
+    }
+
+    fn store_object(&self, contents: &[u8]) -> io::Result<String> {
+        let hash = format!("{:x}", Sha256::digest(contents));
+        let path = self.object_path(&hash);
+        if !path.is_file() {
+            write_atomic(&path, contents)?;
+        }
+        Ok(hash)
+    }
+
+    fn load_latest(&self, root: &Path) -> Option<RootManifest> {
+        let id = fs::read_to_string(self.latest_path(root)).ok()?;
+        let manifest = self.read_manifest(id.trim()).ok()?;
+        manifest
+            .roots
+            .into_iter()
+            .find(|candidate| candidate.path == root)
+    }
+
+    fn read_manifest(&self, id: &str) -> io::Result<Manifest> {
+        let contents = fs::read(self.manifest_path(id))?;
+        serde_json::from_slice(&contents).map_err(io::Error::other)
+    }
+
+    fn write_manifest(&self, manifest: &Manifest) -> io::Result<()> {
+        let contents = serde_json::to_vec(manifest).map_err(io::Error::other)?;
+        write_atomic(&self.manifest_path(&manifest.id), &contents)
+    }
+
+    /// Drops the oldest manifests once the store grows past `MAX_MANIFESTS`
+    /// and deletes objects no remaining manifest refers to.
+    fn prune(&self) -> io::Result<()> {
+        let mut manifests = Vec::new();
+        for entry in fs::read_dir(self.dir.join(MANIFESTS_DIR))? {
+            let entry = entry?;
+            let modified = entry.metadata()?.modified()?;
+            manifests.push((modified, entry.path()));
+        }
+        if manifests.len() <= MAX_MANIFESTS {
+            return Ok(());
+        }
+
+        manifests.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+        for (_, path) in manifests.split_off(RETAINED_MANIFESTS) {
+            remove_path(&path)?;
+        }
+
+        let mut referenced: HashSet<String> = HashSet::new();
+        for (_, path) in &manifests {
+            let Ok(contents) = fs::read(path) else {
+                continue;
+            };
+            let Ok(manifest) = serde_json::from_slice::<Manifest>(&contents) else {
+                continue;
+            };
+            for root in manifest.roots {
+                for entry in root.files.into_values() {
+                    if let FileEntry::File { hash, .. } = entry {
+                        referenced.insert(hash);
+                    }
+                }
+            }
+        }
+
+        for shard in fs::read_dir(self.dir.join(OBJECTS_DIR))? {
+            let shard = shard?.path();
+            let Some(prefix) = shard.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            for object in fs::read_dir(&shard)? {
+                let object = object?.path();
+                let Some(rest) = object.file_name().and_then(|name| name.to_str()) else {
+                    continue;
+                };
+                if !referenced.contains(&format!("{prefix}{rest}")) {
+                    remove_path(&object)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn manifest_path(&self, id: &str) -> PathBuf {
+        self.dir.join(MANIFESTS_DIR).join(format!("{id}.json"))
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        let (prefix, rest) = hash.split_at(hash.len().min(2));
+        self.dir.join(OBJECTS_DIR).join(prefix).join(rest)
+    }
+
+    fn latest_path(&self, root: &Path) -> PathBuf {
+        let key = format!("{:x}", Sha256::digest(root.to_string_lossy().as_bytes()));
+        self.dir.join(LATEST_DIR).join(key)
+    }
+}
+
+/// Snapshot ids come from conversation history and end up in file names.
+fn validate_id(id: &str) -> io::Result<()> {
+    if !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Ok(());
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("invalid file snapshot id {id:?}"),
+    ))
+}
+
+/// Whether the entry at `path` still matches what the manifest recorded.
+fn entry_matches(entry: &FileEntry, metadata: &fs::Metadata, path: &Path) -> bool {
+    match entry {
+        FileEntry::File {
+            size,
+            modified_ns: recorded_modified,
+            mode,
+            ..
+        } => {
+            metadata.is_file()
+                && metadata.len() == *size
+                && modified_ns(metadata) == *recorded_modified
+                && (mode.is_none() || file_mode(metadata) == *mode)
+        }
+        FileEntry::Symlink { target } => {
+            metadata.file_type().is_symlink()
+                && fs::read_link(path).is_ok_and(|current| &current == target)
+        }
+    }
+}
+
+fn is_under_any(path: &Path, dirs: &BTreeSet<PathBuf>) -> bool {
+    dirs.iter().any(|dir| path.starts_with(dir))
+}
+
+fn modified_ns(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .and_then(|duration| u64::try_from(duration.as_nanos()).ok())
+        .unwrap_or_default()
+}
+
+fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let Some(parent) = path.parent() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} has no parent directory", path.display()),
+        ));
+    };
+    fs::create_dir_all(parent)?;
+    let mut file = NamedTempFile::new_in(parent)?;
+    file.write_all(contents)?;
+    file.persist(path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+/// Deletes the file or directory at `path`, ignoring it if already absent.
+fn remove_path(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn file_mode(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn set_file_mode(path: &Path, mode: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(unix))]
+fn set_file_mode(_path: &Path, _mode: u32) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn store() -> (TempDir, FileSnapshotStore) {
+        let codex_home = TempDir::new().expect("codex home");
+        let store = FileSnapshotStore::new(codex_home.path());
+        (codex_home, store)
+    }
+
+    #[test]
+    fn restore_reverts_edits_deletions_and_new_files() {
+        let (_home, store) = store();
+        let workspace = TempDir::new().expect("workspace");
+        let root = workspace.path().to_path_buf();
+        fs::create_dir_all(root.join("src")).expect("mkdir");
+        fs::write(root.join("src/lib.rs"), "fn original() {}\n").expect("write");
+        fs::write(root.join("README.md"), "readme\n").expect("write");
+
+        let report = store
+            .capture(
+                "snap-1",
+                std::slice::from_ref(&root),
+                &GhostSnapshotConfig::default(),
+            )
+            .expect("capture");
+        assert_eq!(report, GhostSnapshotReport::default());
+        assert!(store.contains("snap-1"));
+
+        fs::write(root.join("src/lib.rs"), "fn edited() {}\n").expect("edit");
+        fs::remove_file(root.join("README.md")).expect("delete");
+        fs::create_dir_all(root.join("generated/nested")).expect("mkdir");
+        fs::write(root.join("generated/nested/out.txt"), "new").expect("write");
+        fs::write(root.join("notes.txt"), "new").expect("write");
+        store.record_changes("snap-1").expect("record changes");
+        // Created after the turn ended, so undo must leave it alone.
+        fs::write(root.join("user.txt"), "mine").expect("write");
+
+        store.restore("snap-1").expect("restore");
+
+        assert_eq!(
+            fs::read_to_string(root.join("src/lib.rs")).expect("read"),
+            "fn original() {}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("README.md")).expect("read"),
+            "readme\n"
+        );
+        assert!(!root.join("generated").exists());
+        assert!(!root.join("notes.txt").exists());
+        assert_eq!(
+            fs::read_to_string(root.join("user.txt")).expect("read"),
+            "mine"
+        );
+    }
+
+    #[test]
+    fn restore_without_recorded_changes_only_rewrites_captured_files() {
+        let (_home, store) = store();
+        let workspace = TempDir::new().expect("workspace");
+        let root = workspace.path().to_path_buf();
+        fs::write(root.join("a.txt"), "original").expect("write");
+
+        store
+            .capture(
+                "snap-1",
+                std::slice::from_ref(&root),
+                &GhostSnapshotConfig::default(),
+            )
+            .expect("capture");
+        fs::write(root.join("a.txt"), "edited").expect("edit");
+        fs::write(root.join("new.txt"), "new").expect("write");
+
+        store.restore("snap-1").expect("restore");
+
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).expect("read"),
+            "original"
+        );
+        assert!(root.join("new.txt").exists());
+    }
+
+    #[test]
+    fn gitignored_paths_are_not_captured_or_restored() {
+        let (_home, store) = store();
+        let workspace = TempDir::new().expect("workspace");
+        let root = workspace.path().to_path_buf();
+        fs::write(root.join(".gitignore"), "build/\n").expect("write");
+        fs::create_dir_all(root.join("build")).expect("mkdir");
+        fs::write(root.join("build/out.o"), "old").expect("write");
+
+        store
+            .capture(
+                "snap-1",
+                std::slice::from_ref(&root),
+                &GhostSnapshotConfig::default(),
+            )
+            .expect("capture");
+        fs::write(root.join("build/out.o"), "rebuilt").expect("write");
+        fs::write(root.join("build/extra.o"), "new").expect("write");
+        store.record_changes("snap-1").expect("record changes");
+
+        store.restore("snap-1").expect("restore");
+
+        assert_eq!(
+            fs::read_to_string(root.join("build/out.o")).expect("read"),
+            "rebuilt"
+        );
+        assert!(root.join("build/extra.o").exists());
+    }
+
+    #[test]
+    fn large_files_and_directories_are_reported_and_preserved() {
+        let (_home, store) = store();
+        let workspace = TempDir::new().expect("workspace");
+        let root = workspace.path().to_path_buf();
+        fs::write(root.join("big.bin"), vec![0u8; 64]).expect("write");
+        fs::create_dir_all(root.join("data")).expect("mkdir");
+        for idx in 0..3 {
+            fs::write(root.join(format!("data/{idx}.csv")), "x").expect("write");
+        }
+        fs::create_dir_all(root.join("node_modules/pkg")).expect("mkdir");
+        fs::write(root.join("node_modules/pkg/index.js"), "x").expect("write");
+
+        let config = GhostSnapshotConfig {
+            ignore_large_untracked_files: Some(16),
+            ignore_large_untracked_dirs: Some(3),
+            disable_warnings: false,
+        };
+        let report = store
+            .capture("snap-1", std::slice::from_ref(&root), &config)
+            .expect("capture");
+        assert_eq!(
+            report,
+            GhostSnapshotReport {
+                large_untracked_dirs: vec![LargeUntrackedDir {
+                    path: PathBuf::from("data"),
+                    file_count: 3,
+                }],
+                ignored_untracked_files: vec![IgnoredUntrackedFile {
+                    path: PathBuf::from("big.bin"),
+                    byte_size: 64,
+                }],
+            }
+        );
+
+        fs::write(root.join("big.bin"), "changed").expect("write");
+        fs::write(root.join("data/new.csv"), "x").expect("write");
+        store.restore("snap-1").expect("restore");
+
+        assert_eq!(
+            fs::read_to_string(root.join("big.bin")).expect("read"),
+            "changed"
+        );
+        assert!(root.join("data/new.csv").exists());
+        assert!(root.join("node_modules/pkg/index.js").exists());
+    }
+
+    #[test]
+    fn unchanged_files_share_objects_across_snapshots() {
+        let (home, store) = store();
+        let workspace = TempDir::new().expect("workspace");
+        let root = workspace.path().to_path_buf();
+        fs::write(root.join("a.txt"), "same").expect("write");
+        fs::write(root.join("b.txt"), "same").expect("write");
+
+        let roots = std::slice::from_ref(&root);
+        let config = GhostSnapshotConfig::default();
+        store.capture("snap-1", roots, &config).expect("capture");
+        fs::write(root.join("b.txt"), "different").expect("write");
+        store.capture("snap-2", roots, &config).expect("capture");
+
+        let objects: usize = fs::read_dir(home.path().join(STORE_DIR).join(OBJECTS_DIR))
+            .expect("objects dir")
+            .map(|shard| {
+                fs::read_dir(shard.expect("shard").path())
+                    .expect("shard dir")
+                    .count()
+            })
+            .sum();
+        assert_eq!(objects, 2);
+
+        store.restore("snap-1").expect("restore");
+        assert_eq!(
+            fs::read_to_string(root.join("b.txt")).expect("read"),
+            "same"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn restore_recreates_symlinks_and_modes() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (_home, store) = store();
+        let workspace = TempDir::new().expect("workspace");
+        let root = workspace.path().to_path_buf();
+        fs::write(root.join("run.sh"), "#!/bin/sh\n").expect("write");
+        fs::set_permissions(root.join("run.sh"), fs::Permissions::from_mode(0o755)).expect("chmod");
+        std::os::unix::fs::symlink("run.sh", root.join("link")).expect("symlink");
+
+        store
+            .capture(
+                "snap-1",
+                std::slice::from_ref(&root),
+                &GhostSnapshotConfig::default(),
+            )
+            .expect("capture");
+
+        fs::remove_file(root.join("link")).expect("remove link");
+        fs::write(root.join("link"), "not a link").expect("write");
+        fs::remove_file(root.join("run.sh")).expect("remove");
+
+        store.restore("snap-1").expect("restore");
+
+        assert_eq!(
+            fs::read_link(root.join("link")).expect("read link"),
+            PathBuf::from("run.sh")
+        );
+        let mode = fs::metadata(root.join("run.sh"))
+            .expect("metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[test]
+    fn invalid_ids_are_rejected() {
+        let (_home, store) = store();
+        assert!(!store.contains("../escape"));
+        assert!(store.restore("../escape").is_err());
+    }
+}
@@ -9,13 +9,16 @@ pub mod api_bridge;
 mod apply_patch;
 pub mod auth;
 pub mod bash;
+mod checkpoints;
 pub(crate) mod client;
 pub use client::GeminiAspectRatio;
 pub use client::GeminiImageSize;
 mod client_common;
+mod code_outline;
 pub mod codex;
 mod codex_conversation;
 mod compact_remote;
+mod compact_structured;
 pub use codex_conversation::CodexConversation;
 mod codex_delegate;
 mod command_safety;
@@ -30,10 +33,13 @@ pub mod error;
 pub mod exec;
 pub mod exec_env;
 mod exec_policy;
+pub mod execpolicy_audit;
 pub mod features;
+mod file_snapshots;
 mod flags;
 pub mod git_info;
 pub mod landlock;
+mod lsp;
 pub mod mcp;
 mod mcp_connection_manager;
 pub mod openai_models;
@@ -43,7 +49,9 @@ pub use mcp_connection_manager::SandboxState;
 mod mcp_tool_call;
 mod message_history;
 mod model_provider_info;
+mod ollama;
 pub mod parse_command;
+mod patch_review;
 pub mod path_utils;
 pub mod powershell;
 pub mod sandboxing;
@@ -77,6 +85,7 @@ pub mod project_doc;
 mod rollout;
 pub(crate) mod safety;
 pub mod seatbelt;
+mod session_title;
 pub mod shell;
 pub mod shell_snapshot;
 pub mod skills;
@@ -107,6 +116,7 @@ pub use command_safety::is_dangerous_command;
 pub use command_safety::is_safe_command;
 pub use exec_policy::ExecPolicyError;
 pub use exec_policy::load_exec_policy;
+pub use exec_policy::load_exec_policy_dir;
 pub use safety::get_platform_sandbox;
 pub use safety::set_windows_sandbox_enabled;
 // Re-export the protocol types from the standalone `codex-protocol` crate so existing
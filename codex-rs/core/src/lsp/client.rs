@@ -0,0 +1,553 @@
+//! Minimal LSP client over stdio: JSON-RPC framing, document sync and
+//! `textDocument/publishDiagnostics` collection. Everything else the server
+//! sends is acknowledged and otherwise ignored.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use serde_json::Value;
+use serde_json::json;
+use tokio::io::AsyncBufRead;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::process::Child;
+use tokio::process::ChildStdin;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use tokio::sync::oneshot;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use url::Url;
+
+use crate::config::types::LspServerConfig;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+type PendingRequests = Arc<StdMutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+impl DiagnosticSeverity {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Information => "info",
+            Self::Hint => "hint",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Diagnostic {
+    /// 1-based.
+    pub line: u32,
+    /// 1-based, in UTF-16 code units as reported by the server.
+    pub column: u32,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    pub code: Option<String>,
+    pub source: Option<String>,
+}
+
+/// Diagnostics last published for each file, tagged with the sequence number
+/// of the notification that carried them.
+#[derive(Default)]
+struct PublishedDiagnostics {
+    seq: u64,
+    by_path: HashMap<PathBuf, (u64, Vec<Diagnostic>)>,
+}
+
+pub(crate) struct LspClient {
+    name: String,
+    stdin: Arc<Mutex<ChildStdin>>,
+    next_id: AtomicI64,
+    pending: PendingRequests,
+    published: Arc<StdMutex<PublishedDiagnostics>>,
+    /// Carries the latest publish sequence number to waiters.
+    publish_rx: watch::Receiver<u64>,
+    /// Open documents and their current version.
+    documents: Mutex<HashMap<PathBuf, i32>>,
+    reader: JoinHandle<()>,
+    _child: Child,
+}
+
+impl Drop for LspClient {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+impl LspClient {
+    /// Spawns the server, performs the `initialize` handshake and returns a
+    /// ready client.
+    pub(crate) async fn start(
+        name: &str,
+        config: &LspServerConfig,
+        root: &Path,
+    ) -> Result<Self, String> {
+        let mut command = Command::new(&config.command);
+        command
+            .args(&config.args)
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+        if let Some(env) = &config.env {
+            command.envs(env);
+        }
+        let mut child = command
+            .spawn()
+            .map_err(|err| format!("failed to start `{}`: {err}", config.command))?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| format!("`{}` has no stdin", config.command))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| format!("`{}` has no stdout", config.command))?;
+
+        let stdin = Arc::new(Mutex::new(stdin));
+        let pending: PendingRequests = Arc::new(StdMutex::new(HashMap::new()));
+        let published = Arc::new(StdMutex::new(PublishedDiagnostics::default()));
+        let (publish_tx, publish_rx) = watch::channel(0);
+        let reader = tokio::spawn(read_loop(
+            name.to_string(),
+            BufReader::new(stdout),
+            Arc::clone(&stdin),
+            Arc::clone(&pending),
+            Arc::clone(&published),
+            publish_tx,
+        ));
+
+        let client = Self {
+            name: name.to_string(),
+            stdin,
+            next_id: AtomicI64::new(1),
+            pending,
+            published,
+            publish_rx,
+            documents: Mutex::new(HashMap::new()),
+            reader,
+            _child: child,
+        };
+
+        let root_uri = Url::from_directory_path(root)
+            .map_err(|()| format!("`{}` is not an absolute path", root.display()))?
+            .to_string();
+        client
+            .request(
+                "initialize",
+                json!({
+                    "processId": std::process::id(),
+                    "clientInfo": { "name": "codex" },
+                    "rootUri": root_uri,
+                    "workspaceFolders": [{ "uri": root_uri, "name": name }],
+                    "capabilities": {
+                        "textDocument": {
+                            "synchronization": { "didSave": true },
+                            "publishDiagnostics": { "versionSupport": false },
+                        },
+                        "workspace": { "configuration": true, "workspaceFolders": true },
+                    },
+                }),
+            )
+            .await?;
+        client.notify("initialized", json!({})).await?;
+        Ok(client)
+    }
+
+    /// Sequence number of the most recent `publishDiagnostics` notification.
+    pub(crate) fn publish_seq(&self) -> u64 {
+        *self.publish_rx.borrow()
+    }
+
+    /// Sends the on-disk contents of `path` to the server: `didOpen` the first
+    /// time, `didChange` + `didSave` afterwards, and `didClose` once the file
+    /// no longer exists.
+    pub(crate) async fn sync_document(&self, path: &Path, language_id: &str) -> Result<(), String> {
+        let uri = file_uri(path)?;
+        let mut documents = self.documents.lock().await;
+        match tokio::fs::read_to_string(path).await {
+            Ok(text) => match documents.get_mut(path) {
+                Some(version) => {
+                    *version += 1;
+                    let version = *version;
+                    self.notify(
+                        "textDocument/didChange",
+                        json!({
+                            "textDocument": { "uri": uri, "version": version },
+                            "contentChanges": [{ "text": text }],
+                        }),
+                    )
+                    .await?;
+                    self.notify(
+                        "textDocument/didSave",
+                        json!({ "textDocument": { "uri": uri } }),
+                    )
+                    .await
+                }
+                None => {
+                    documents.insert(path.to_path_buf(), 1);
+                    self.notify(
+                        "textDocument/didOpen",
+                        json!({
+                            "textDocument": {
+                                "uri": uri,
+                                "languageId": language_id,
+                                "version": 1,
+                                "text": text,
+                            },
+                        }),
+                    )
+                    .await
+                }
+            },
+            Err(_) => {
+                if documents.remove(path).is_none() {
+                    return Ok(());
+                }
+                if let Ok(mut published) = self.published.lock() {
+                    published.by_path.remove(path);
+                }
+                self.notify(
+                    "textDocument/didClose",
+                    json!({ "textDocument": { "uri": uri } }),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Waits until every path in `paths` has diagnostics published after
+    /// sequence number `after`, or until `timeout` elapses.
+    pub(crate) async fn wait_for_diagnostics(
+        &self,
+        paths: &[PathBuf],
+        after: u64,
+        timeout: Duration,
+    ) {
+        let deadline = Instant::now() + timeout;
+        let mut rx = self.publish_rx.clone();
+        loop {
+            let fresh = self.published.lock().is_ok_and(|published| {
+                paths.iter().all(|path| {
+                    published
+                        .by_path
+                        .get(path)
+                        .is_some_and(|(seq, _)| *seq > after)
+                })
+            });
+            if fresh {
+                return;
+            }
+            match tokio::time::timeout_at(deadline, rx.changed()).await {
+                Ok(Ok(())) => {}
+                Ok(Err(_)) | Err(_) => return,
+            }
+        }
+    }
+
+    /// Diagnostics most recently published for `path`.
+    pub(crate) fn diagnostics(&self, path: &Path) -> Vec<Diagnostic> {
+        self.published
+            .lock()
+            .ok()
+            .and_then(|published| published.by_path.get(path).map(|(_, diags)| diags.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Paths currently open on this server.
+    pub(crate) async fn open_documents(&self) -> Vec<PathBuf> {
+        self.documents.lock().await.keys().cloned().collect()
+    }
+
+    async fn request(&self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(id, tx);
+        }
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        if let Err(err) = write_message(&self.stdin, &message).await {
+            if let Ok(mut pending) = self.pending.lock() {
+                pending.remove(&id);
+            }
+            return Err(format!("{}: failed to send {method}: {err}", self.name));
+        }
+        match tokio::time::timeout(REQUEST_TIMEOUT, rx).await {
+            Ok(Ok(result)) => {
+                result.map_err(|err| format!("{}: {method} failed: {err}", self.name))
+            }
+            Ok(Err(_)) => Err(format!("{}: server exited during {method}", self.name)),
+            Err(_) => Err(format!("{}: {method} timed out", self.name)),
+        }
+    }
+
+    async fn notify(&self, method: &str, params: Value) -> Result<(), String> {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        write_message(&self.stdin, &message)
+            .await
+            .map_err(|err| format!("{}: failed to send {method}: {err}", self.name))
+    }
+}
+
+async fn read_loop<R>(
+    name: String,
+    mut reader: R,
+    stdin: Arc<Mutex<ChildStdin>>,
+    pending: PendingRequests,
+    published: Arc<StdMutex<PublishedDiagnostics>>,
+    publish_tx: watch::Sender<u64>,
+) where
+    R: AsyncBufRead + Unpin,
+{
+    loop {
+        let message = match read_message(&mut reader).await {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(err) => {
+                tracing::warn!("language server {name} sent an invalid message: {err}");
+                break;
+            }
+        };
+
+        let method = message.get("method").and_then(Value::as_str);
+        match (message.get("id"), method) {
+            // Server -> client request. Answer so the server does not stall;
+            // `workspace/configuration` expects one entry per requested item.
+            (Some(id), Some(method)) => {
+                let result = if method == "workspace/configuration" {
+                    let items = message
+                        .pointer("/params/items")
+                        .and_then(Value::as_array)
+                        .map_or(0, Vec::len);
+                    Value::Array(vec![Value::Null; items])
+                } else {
+                    Value::Null
+                };
+                let reply = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+                if let Err(err) = write_message(&stdin, &reply).await {
+                    tracing::warn!("failed to answer {method} from language server {name}: {err}");
+                }
+            }
+            (None, Some("textDocument/publishDiagnostics")) => {
+                if let Some((path, diagnostics)) =
+                    message.get("params").and_then(parse_publish_diagnostics)
+                    && let Ok(mut published) = published.lock()
+                {
+                    published.seq += 1;
+                    let seq = published.seq;
+                    published.by_path.insert(path, (seq, diagnostics));
+                    publish_tx.send_replace(seq);
+                }
+            }
+            (Some(id), None) => {
+                let Some(id) = id.as_i64() else {
+                    continue;
+                };
+                let sender = pending
+                    .lock()
+                    .ok()
+                    .and_then(|mut pending| pending.remove(&id));
+                if let Some(sender) = sender {
+                    let result = match message.get("error") {
+                        Some(error) => Err(error
+                            .get("message")
+                            .and_then(Value::as_str)
+                            .unwrap_or("unknown error")
+                            .to_string()),
+                        None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+                    };
+                    let _ = sender.send(result);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Fail outstanding requests instead of leaving them to time out.
+    if let Ok(mut pending) = pending.lock() {
+        pending.clear();
+    }
+}
+
+async fn read_message<R>(reader: &mut R) -> io::Result<Option<Value>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((header, value)) = line.split_once(':')
+            && header.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let Some(content_length) = content_length else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "missing Content-Length header",
+        ));
+    };
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+async fn write_message(stdin: &Mutex<ChildStdin>, message: &Value) -> io::Result<()> {
+    let body = serde_json::to_vec(message)?;
+    let mut stdin = stdin.lock().await;
+    stdin
+        .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+        .await?;
+    stdin.write_all(&body).await?;
+    stdin.flush().await
+}
+
+fn file_uri(path: &Path) -> Result<String, String> {
+    Url::from_file_path(path)
+        .map(String::from)
+        .map_err(|()| format!("`{}` is not an absolute path", path.display()))
+}
+
+fn parse_publish_diagnostics(params: &Value) -> Option<(PathBuf, Vec<Diagnostic>)> {
+    let uri = params.get("uri")?.as_str()?;
+    let path = Url::parse(uri).ok()?.to_file_path().ok()?;
+    let diagnostics = params
+        .get("diagnostics")?
+        .as_array()?
+        .iter()
+        .filter_map(parse_diagnostic)
+        .collect();
+    Some((path, diagnostics))
+}
+
+fn parse_diagnostic(value: &Value) -> Option<Diagnostic> {
+    let start = value.pointer("/range/start")?;
+    let line = u32::try_from(start.get("line")?.as_u64()?).ok()?;
+    let column = u32::try_from(start.get("character")?.as_u64()?).ok()?;
+    // The spec leaves a missing severity up to the client; treat it as an error.
+    let severity = match value.get("severity").and_then(Value::as_u64) {
+        None | Some(1) => DiagnosticSeverity::Error,
+        Some(2) => DiagnosticSeverity::Warning,
+        Some(3) => DiagnosticSeverity::Information,
+        Some(_) => DiagnosticSeverity::Hint,
+    };
+    let code = match value.get("code") {
+        Some(Value::String(code)) => Some(code.clone()),
+        Some(Value::Number(code)) => Some(code.to_string()),
+        _ => None,
+    };
+    Some(Diagnostic {
+        line: line.saturating_add(1),
+        column: column.saturating_add(1),
+        severity,
+        message: value.get("message")?.as_str()?.to_string(),
+        code,
+        source: value
+            .get("source")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn reads_framed_messages_until_eof() {
+        let body = r#"{"jsonrpc":"2.0","id":1,"result":null}"#;
+        let input = format!(
+            "Content-Length: {}\r\nContent-Type: application/vscode-jsonrpc\r\n\r\n{body}",
+            body.len()
+        );
+        let mut reader = BufReader::new(input.as_bytes());
+
+        let message = read_message(&mut reader).await.unwrap();
+        assert_eq!(
+            message,
+            Some(json!({ "jsonrpc": "2.0", "id": 1, "result": null }))
+        );
+        assert_eq!(read_message(&mut reader).await.unwrap(), None);
+    }
+
+    #[test]
+    fn parses_publish_diagnostics() {
+        let path = std::env::temp_dir().join("lib.rs");
+        let uri = Url::from_file_path(&path).unwrap().to_string();
+        let params = json!({
+            "uri": uri,
+            "diagnostics": [
+                {
+                    "range": { "start": { "line": 4, "character": 8 }, "end": { "line": 4, "character": 9 } },
+                    "severity": 1,
+                    "code": "E0308",
+                    "source": "rustc",
+                    "message": "mismatched types",
+                },
+                {
+                    "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 0, "character": 1 } },
+                    "severity": 2,
+                    "code": 42,
+                    "message": "unused import",
+                },
+            ],
+        });
+
+        let (parsed_path, diagnostics) = parse_publish_diagnostics(&params).unwrap();
+        assert_eq!(parsed_path, path);
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    line: 5,
+                    column: 9,
+                    severity: DiagnosticSeverity::Error,
+                    message: "mismatched types".to_string(),
+                    code: Some("E0308".to_string()),
+                    source: Some("rustc".to_string()),
+                },
+                Diagnostic {
+                    line: 1,
+                    column: 1,
+                    severity: DiagnosticSeverity::Warning,
+                    message: "unused import".to_string(),
+                    code: Some("42".to_string()),
+                    source: None,
+                },
+            ]
+        );
+    }
+}
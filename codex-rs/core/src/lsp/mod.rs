@@ -0,0 +1,400 @@
+//! Optional Language Server Protocol bridge.
+//!
+//! Configured servers (`[lsp_servers.<name>]`) are started lazily, the first
+//! time a file with one of their extensions is edited, and live for the rest
+//! of the session. Before `apply_patch` runs, the files it targets are opened
+//! so their existing problems are known; afterwards the touched files are
+//! pushed to their servers and any diagnostics that were not there before the
+//! edit are appended to the tool output. The `diagnostics` tool exposes the
+//! same data on demand.
+
+mod client;
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::join_all;
+use tokio::sync::OnceCell;
+
+use crate::config::Config;
+use crate::config::types::LspServerConfig;
+use crate::features::Feature;
+
+pub(crate) use client::Diagnostic;
+pub(crate) use client::DiagnosticSeverity;
+use client::LspClient;
+
+const DEFAULT_DIAGNOSTICS_TIMEOUT: Duration = Duration::from_secs(3);
+const MAX_DIAGNOSTICS_PER_FILE: usize = 20;
+const MAX_DIAGNOSTICS_TOTAL: usize = 50;
+
+pub(crate) type DiagnosticsByPath = BTreeMap<PathBuf, Vec<Diagnostic>>;
+
+pub(crate) struct LspManager {
+    root: PathBuf,
+    servers: Vec<(String, LspServerConfig)>,
+    /// Clients by server name, started on first use. `None` records a server
+    /// that failed to start so we do not respawn it on every edit. Each server
+    /// has its own cell, so a slow start only blocks callers of that server.
+    clients: HashMap<String, OnceCell<Option<Arc<LspClient>>>>,
+}
+
+impl LspManager {
+    pub(crate) fn new(servers: HashMap<String, LspServerConfig>, root: PathBuf) -> Self {
+        let mut servers: Vec<(String, LspServerConfig)> = servers.into_iter().collect();
+        servers.sort_by(|a, b| a.0.cmp(&b.0));
+        let clients = servers
+            .iter()
+            .map(|(name, _)| (name.clone(), OnceCell::new()))
+            .collect();
+        Self {
+            root,
+            servers,
+            clients,
+        }
+    }
+
+    /// Uses the configured `lsp_servers` when the `lsp_diagnostics` feature is
+    /// enabled; otherwise returns a manager with no servers.
+    pub(crate) fn from_config(config: &Config) -> Self {
+        let servers = if config.features.enabled(Feature::LspDiagnostics) {
+            config.lsp_servers.clone()
+        } else {
+            HashMap::new()
+        };
+        Self::new(servers, config.cwd.clone())
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self.servers.is_empty()
+    }
+
+    /// Whether some configured server handles `path`.
+    pub(crate) fn handles(&self, path: &Path) -> bool {
+        self.server_for(path).is_some()
+    }
+
+    /// Pushes the current on-disk contents of `paths` to their servers, waits
+    /// briefly for fresh diagnostics and returns what is now reported for
+    /// each path that still exists.
+    pub(crate) async fn refresh(&self, paths: &[PathBuf]) -> DiagnosticsByPath {
+        let mut groups: BTreeMap<&str, Vec<PathBuf>> = BTreeMap::new();
+        for path in paths {
+            if let Some((name, _)) = self.server_for(path) {
+                groups.entry(name.as_str()).or_default().push(path.clone());
+            }
+        }
+
+        let refreshes = groups.into_iter().map(|(name, paths)| async move {
+            let Some((_, config)) = self.servers.iter().find(|(n, _)| n == name) else {
+                return Vec::new();
+            };
+            let Some(client) = self.client(name, config).await else {
+                return Vec::new();
+            };
+
+            let seq = client.publish_seq();
+            let mut synced = Vec::new();
+            for path in &paths {
+                match client.sync_document(path, &language_id(config, path)).await {
+                    Ok(()) if path.exists() => synced.push(path.clone()),
+                    Ok(()) => {}
+                    Err(err) => tracing::warn!("{err}"),
+                }
+            }
+            let timeout = config
+                .diagnostics_timeout_sec
+                .unwrap_or(DEFAULT_DIAGNOSTICS_TIMEOUT);
+            client.wait_for_diagnostics(&synced, seq, timeout).await;
+            synced
+                .into_iter()
+                .map(|path| {
+                    let diagnostics = client.diagnostics(&path);
+                    (path, diagnostics)
+                })
+                .collect()
+        });
+
+        join_all(refreshes).await.into_iter().flatten().collect()
+    }
+
+    /// Diagnostics for `paths` as they are before an edit. Documents a
+    /// running server already has open use what it last reported; the others
+    /// are opened first so that problems predating the edit are not later
+    /// reported as new.
+    pub(crate) async fn baseline(&self, paths: &[PathBuf]) -> DiagnosticsByPath {
+        let open: HashSet<PathBuf> = self.open_documents().await.into_iter().collect();
+        let (known, unopened): (Vec<PathBuf>, Vec<PathBuf>) = paths
+            .iter()
+            .filter(|path| self.handles(path) && path.exists())
+            .cloned()
+            .partition(|path| open.contains(path));
+        let mut baseline = self.known(&known);
+        if !unopened.is_empty() {
+            baseline.extend(self.refresh(&unopened).await);
+        }
+        baseline
+    }
+
+    /// Diagnostics already known for `paths`, without contacting any server.
+    pub(crate) fn known(&self, paths: &[PathBuf]) -> DiagnosticsByPath {
+        let mut known = DiagnosticsByPath::new();
+        for path in paths {
+            let Some((name, _)) = self.server_for(path) else {
+                continue;
+            };
+            if let Some(client) = self.running_client(name) {
+                known.insert(path.clone(), client.diagnostics(path));
+            }
+        }
+        known
+    }
+
+    /// Every document opened on a running server so far.
+    pub(crate) async fn open_documents(&self) -> Vec<PathBuf> {
+        let mut documents = Vec::new();
+        for (name, _) in &self.servers {
+            if let Some(client) = self.running_client(name) {
+                documents.extend(client.open_documents().await);
+            }
+        }
+        documents.sort();
+        documents
+    }
+
+    fn server_for(&self, path: &Path) -> Option<&(String, LspServerConfig)> {
+        let extension = path.extension()?.to_str()?;
+        self.servers.iter().find(|(_, config)| {
+            config
+                .extensions
+                .iter()
+                .any(|ext| ext.trim_start_matches('.').eq_ignore_ascii_case(extension))
+        })
+    }
+
+    /// The client for `name` if it has already been started.
+    fn running_client(&self, name: &str) -> Option<&Arc<LspClient>> {
+        self.clients.get(name)?.get()?.as_ref()
+    }
+
+    async fn client(&self, name: &str, config: &LspServerConfig) -> Option<Arc<LspClient>> {
+        self.clients
+            .get(name)?
+            .get_or_init(|| async {
+                match LspClient::start(name, config, &self.root).await {
+                    Ok(client) => Some(Arc::new(client)),
+                    Err(err) => {
+                        tracing::warn!("language server {name} unavailable: {err}");
+                        None
+                    }
+                }
+            })
+            .await
+            .clone()
+    }
+}
+
+fn language_id(config: &LspServerConfig, path: &Path) -> String {
+    if let Some(language_id) = &config.language_id {
+        return language_id.clone();
+    }
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "typescriptreact",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "javascriptreact",
+        "go" => "go",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => "cpp",
+        "java" => "java",
+        "rb" => "ruby",
+        other => other,
+    }
+    .to_string()
+}
+
+/// Errors and warnings in `after` that were not already reported in `before`.
+/// Diagnostics are compared by severity, code and message so that unrelated
+/// edits shifting line numbers do not resurface old problems.
+pub(crate) fn new_diagnostics(
+    before: &DiagnosticsByPath,
+    after: DiagnosticsByPath,
+) -> DiagnosticsByPath {
+    after
+        .into_iter()
+        .filter_map(|(path, diagnostics)| {
+            let mut previous: Vec<&Diagnostic> = before
+                .get(&path)
+                .map(|d| d.iter().collect())
+                .unwrap_or_default();
+            let fresh: Vec<Diagnostic> = diagnostics
+                .into_iter()
+                .filter(|diagnostic| diagnostic.severity <= DiagnosticSeverity::Warning)
+                .filter(|diagnostic| {
+                    match previous
+                        .iter()
+                        .position(|old| same_problem(old, diagnostic))
+                    {
+                        Some(index) => {
+                            previous.swap_remove(index);
+                            false
+                        }
+                        None => true,
+                    }
+                })
+                .collect();
+            (!fresh.is_empty()).then_some((path, fresh))
+        })
+        .collect()
+}
+
+fn same_problem(a: &Diagnostic, b: &Diagnostic) -> bool {
+    a.severity == b.severity && a.code == b.code && a.message == b.message
+}
+
+/// Renders diagnostics as `path:line:col: severity[code]: message (source)`,
+/// with paths relative to `cwd` and output capped for the model.
+pub(crate) fn format_diagnostics(diagnostics: &DiagnosticsByPath, cwd: &Path) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut omitted = 0;
+    for (path, file_diagnostics) in diagnostics {
+        let display = path.strip_prefix(cwd).unwrap_or(path).display();
+        let mut sorted: Vec<&Diagnostic> = file_diagnostics.iter().collect();
+        sorted.sort_by_key(|d| (d.severity, d.line, d.column));
+        for (index, diagnostic) in sorted.into_iter().enumerate() {
+            if index >= MAX_DIAGNOSTICS_PER_FILE || lines.len() >= MAX_DIAGNOSTICS_TOTAL {
+                omitted += 1;
+                continue;
+            }
+            let code = diagnostic
+                .code
+                .as_ref()
+                .map(|code| format!("[{code}]"))
+                .unwrap_or_default();
+            let source = diagnostic
+                .source
+                .as_ref()
+                .map(|source| format!(" ({source})"))
+                .unwrap_or_default();
+            let message = diagnostic
+                .message
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            lines.push(format!(
+                "{display}:{}:{}: {}{code}: {message}{source}",
+                diagnostic.line,
+                diagnostic.column,
+                diagnostic.severity.label()
+            ));
+        }
+    }
+    if omitted > 0 {
+        lines.push(format!("... {omitted} more diagnostics omitted"));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn diagnostic(line: u32, severity: DiagnosticSeverity, message: &str) -> Diagnostic {
+        Diagnostic {
+            line,
+            column: 1,
+            severity,
+            message: message.to_string(),
+            code: None,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn new_diagnostics_ignores_known_problems_and_hints() {
+        let path = PathBuf::from("/repo/src/lib.rs");
+        let before = DiagnosticsByPath::from([(
+            path.clone(),
+            vec![diagnostic(3, DiagnosticSeverity::Error, "old error")],
+        )]);
+        let after = DiagnosticsByPath::from([(
+            path.clone(),
+            vec![
+                // Same problem, shifted by the edit.
+                diagnostic(7, DiagnosticSeverity::Error, "old error"),
+                diagnostic(9, DiagnosticSeverity::Error, "new error"),
+                diagnostic(9, DiagnosticSeverity::Hint, "consider this"),
+            ],
+        )]);
+
+        assert_eq!(
+            new_diagnostics(&before, after),
+            DiagnosticsByPath::from([(
+                path,
+                vec![diagnostic(9, DiagnosticSeverity::Error, "new error")]
+            )])
+        );
+    }
+
+    #[test]
+    fn format_diagnostics_sorts_and_relativizes() {
+        let cwd = PathBuf::from("/repo");
+        let diagnostics = DiagnosticsByPath::from([(
+            cwd.join("src/lib.rs"),
+            vec![
+                diagnostic(2, DiagnosticSeverity::Warning, "unused variable"),
+                Diagnostic {
+                    code: Some("E0308".to_string()),
+                    source: Some("rustc".to_string()),
+                    ..diagnostic(
+                        5,
+                        DiagnosticSeverity::Error,
+                        "mismatched types\nexpected `u32`",
+                    )
+                },
+            ],
+        )]);
+
+        assert_eq!(
+            format_diagnostics(&diagnostics, &cwd),
+            vec![
+                "src/lib.rs:5:1: error[E0308]: mismatched types expected `u32` (rustc)",
+                "src/lib.rs:2:1: warning: unused variable",
+            ]
+        );
+    }
+
+    #[test]
+    fn routes_files_by_extension() {
+        let config = LspServerConfig {
+            command: "rust-analyzer".to_string(),
+            args: Vec::new(),
+            env: None,
+            extensions: vec![".rs".to_string()],
+            language_id: None,
+            diagnostics_timeout_sec: None,
+        };
+        let manager = LspManager::new(
+            HashMap::from([("rust".to_string(), config)]),
+            PathBuf::from("/repo"),
+        );
+
+        assert!(manager.is_enabled());
+        assert!(manager.handles(Path::new("/repo/src/main.RS")));
+        assert!(!manager.handles(Path::new("/repo/app.py")));
+        assert!(!LspManager::new(HashMap::new(), PathBuf::from("/repo")).is_enabled());
+    }
+}
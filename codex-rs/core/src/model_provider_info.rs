@@ -48,6 +48,10 @@ pub enum WireApi {
 
     /// Google Gemini JSON API exposed via `:generateContent` endpoints.
     Gemini,
+
+    /// Ollama's native `/api/chat` endpoint, which honors `num_ctx` and
+    /// `keep_alive` unlike its OpenAI-compatible shim.
+    Ollama,
 }
 
 /// Serializable representation of a provider definition.
@@ -163,7 +167,7 @@ impl ModelProviderInfo {
             query_params: self.query_params.clone(),
             wire: match self.wire_api {
                 WireApi::Responses => ApiWireApi::Responses,
-                WireApi::Chat | WireApi::Gemini => ApiWireApi::Chat,
+                WireApi::Chat | WireApi::Gemini | WireApi::Ollama => ApiWireApi::Chat,
             },
             headers,
             retry,
@@ -0,0 +1,658 @@
+//! Native Ollama API support.
+//!
+//! Ollama's OpenAI-compatible `/v1/chat/completions` shim ignores per-request
+//! `options`, so models always run with the server's default context length.
+//! Providers configured with `wire_api = "ollama"` talk to the native
+//! `/api/chat` endpoint instead, which lets us pass `options.num_ctx` and
+//! `keep_alive`, and stream thinking output and tool calls. `/api/show`
+//! (via `codex-ollama`) is used to learn each model's context length and
+//! capabilities.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use bytes::Bytes;
+use codex_ollama::OllamaModelInfo;
+use codex_ollama::base_url_to_host_root;
+use codex_ollama::show_model;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tracing::debug;
+
+use crate::client_common::ResponseEvent;
+use crate::client_common::ResponseStream;
+use crate::default_client::build_reqwest_client;
+use crate::error::CodexErr;
+use crate::error::ConnectionFailedError;
+use crate::error::Result;
+use crate::error::UnexpectedResponseError;
+use crate::model_provider_info::ModelProviderInfo;
+use crate::protocol::TokenUsage;
+
+const SHOW_TIMEOUT: Duration = Duration::from_secs(5);
+
+static CALL_ID_COUNTER: AtomicI64 = AtomicI64::new(0);
+
+/// `/api/show` results keyed by `(host root, model)`. Only successful lookups
+/// are cached so a server started mid-session is picked up on the next turn.
+static MODEL_INFO_CACHE: LazyLock<Mutex<HashMap<(String, String), OllamaModelInfo>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Returns `/api/show` metadata for `model`, or `None` when the server cannot
+/// be asked (no base URL, unreachable, unknown model).
+pub(crate) async fn model_info(
+    provider: &ModelProviderInfo,
+    model: &str,
+) -> Option<OllamaModelInfo> {
+    let root = base_url_to_host_root(provider.base_url.as_deref()?);
+    let key = (root, model.to_string());
+    if let Some(info) = cached_model_info(&key) {
+        return Some(info);
+    }
+    match fetch_model_info(provider, &key.0, model).await {
+        Ok(info) => {
+            if let Ok(mut cache) = MODEL_INFO_CACHE.lock() {
+                cache.insert(key, info.clone());
+            }
+            Some(info)
+        }
+        Err(err) => {
+            debug!("failed to query Ollama model info for {model}: {err}");
+            None
+        }
+    }
+}
+
+fn cached_model_info(key: &(String, String)) -> Option<OllamaModelInfo> {
+    MODEL_INFO_CACHE.lock().ok()?.get(key).cloned()
+}
+
+async fn fetch_model_info(
+    provider: &ModelProviderInfo,
+    root: &str,
+    model: &str,
+) -> std::io::Result<OllamaModelInfo> {
+    let client = build_reqwest_client();
+    let request = client
+        .post(format!("{root}/api/show"))
+        .timeout(SHOW_TIMEOUT);
+    show_model(provider.apply_http_headers(request), model).await
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ChatRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<Value>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    think: Option<Think>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keep_alive: Option<Value>,
+    options: ChatOptions,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum Think {
+    Enabled(bool),
+    /// gpt-oss only accepts an effort level instead of a boolean.
+    Level(&'static str),
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ChatOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_ctx: Option<i64>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ChatMessage {
+    role: String,
+    content: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    images: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tool_calls: Vec<ToolCall>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ToolCall {
+    function: ToolCallFunction,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ToolCallFunction {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+/// Inputs for a single `/api/chat` request.
+pub(crate) struct ChatRequestParams<'a> {
+    pub model: &'a str,
+    pub instructions: &'a str,
+    pub input: &'a [ResponseItem],
+    /// Tools in the Chat Completions shape, which `/api/chat` accepts as is.
+    pub tools: Vec<Value>,
+    pub info: Option<&'a OllamaModelInfo>,
+    pub effort: Option<ReasoningEffort>,
+    pub num_ctx: Option<i64>,
+    pub keep_alive: Option<&'a str>,
+}
+
+pub(crate) fn build_chat_request(params: ChatRequestParams<'_>) -> ChatRequest {
+    let ChatRequestParams {
+        model,
+        instructions,
+        input,
+        tools,
+        info,
+        effort,
+        num_ctx,
+        keep_alive,
+    } = params;
+
+    // Only drop tools when the server told us the model cannot call them;
+    // an unknown model is better served by Ollama's own error.
+    let tools = match info {
+        Some(info) if !info.supports_tools => Vec::new(),
+        _ => tools,
+    };
+    let think = info
+        .filter(|info| info.supports_thinking)
+        .map(|_| think_for(model, effort));
+
+    ChatRequest {
+        model: model.to_string(),
+        messages: build_messages(instructions, input),
+        tools,
+        stream: true,
+        think,
+        keep_alive: keep_alive.map(keep_alive_value),
+        options: ChatOptions { num_ctx },
+    }
+}
+
+fn think_for(model: &str, effort: Option<ReasoningEffort>) -> Think {
+    if matches!(effort, Some(ReasoningEffort::None)) {
+        return Think::Enabled(false);
+    }
+    if model.starts_with("gpt-oss") {
+        return Think::Level(match effort {
+            Some(ReasoningEffort::Minimal | ReasoningEffort::Low) => "low",
+            Some(ReasoningEffort::High | ReasoningEffort::XHigh) => "high",
+            _ => "medium",
+        });
+    }
+    Think::Enabled(true)
+}
+
+/// Ollama parses string `keep_alive` values as Go durations, which rejects
+/// bare numbers such as "-1"; send those as numbers (seconds) instead.
+fn keep_alive_value(keep_alive: &str) -> Value {
+    match keep_alive.trim().parse::<i64>() {
+        Ok(seconds) => Value::from(seconds),
+        Err(_) => Value::from(keep_alive.trim()),
+    }
+}
+
+fn build_messages(instructions: &str, input: &[ResponseItem]) -> Vec<ChatMessage> {
+    let mut messages = Vec::new();
+    if !instructions.trim().is_empty() {
+        messages.push(ChatMessage {
+            role: "system".to_string(),
+            content: instructions.to_string(),
+            ..Default::default()
+        });
+    }
+
+    // Tool results are matched to their call by name on this API.
+    let mut tool_names: HashMap<&str, &str> = HashMap::new();
+    for item in input {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let mut text = String::new();
+                let mut images = Vec::new();
+                for part in content {
+                    match part {
+                        ContentItem::InputText { text: t }
+                        | ContentItem::OutputText { text: t } => text.push_str(t),
+                        ContentItem::InputImage { image_url } => {
+                            images.extend(image_data(image_url));
+                        }
+                    }
+                }
+                let role = if role == "developer" { "system" } else { role };
+                messages.push(ChatMessage {
+                    role: role.to_string(),
+                    content: text,
+                    images,
+                    ..Default::default()
+                });
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                tool_names.insert(call_id, name);
+                let arguments = serde_json::from_str(arguments)
+                    .unwrap_or_else(|_| Value::String(arguments.clone()));
+                push_tool_call(&mut messages, name, arguments);
+            }
+            ResponseItem::CustomToolCall {
+                name,
+                input,
+                call_id,
+                ..
+            } => {
+                tool_names.insert(call_id, name);
+                push_tool_call(&mut messages, name, serde_json::json!({ "input": input }));
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                let mut content = output.content.clone();
+                let mut images = Vec::new();
+                if let Some(items) = &output.content_items {
+                    content.clear();
+                    for item in items {
+                        match item {
+                            FunctionCallOutputContentItem::InputText { text } => {
+                                content.push_str(text)
+                            }
+                            FunctionCallOutputContentItem::InputImage { image_url } => {
+                                images.extend(image_data(image_url));
+                            }
+                        }
+                    }
+                }
+                messages.push(ChatMessage {
+                    role: "tool".to_string(),
+                    content,
+                    images,
+                    tool_name: tool_names.get(call_id.as_str()).map(ToString::to_string),
+                    ..Default::default()
+                });
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => {
+                messages.push(ChatMessage {
+                    role: "tool".to_string(),
+                    content: output.clone(),
+                    tool_name: tool_names.get(call_id.as_str()).map(ToString::to_string),
+                    ..Default::default()
+                });
+            }
+            ResponseItem::Reasoning { .. }
+            | ResponseItem::LocalShellCall { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+    messages
+}
+
+/// Appends a tool call, grouping parallel calls into one assistant message.
+fn push_tool_call(messages: &mut Vec<ChatMessage>, name: &str, arguments: Value) {
+    let call = ToolCall {
+        function: ToolCallFunction {
+            name: name.to_string(),
+            arguments,
+        },
+    };
+    match messages.last_mut() {
+        Some(last) if last.role == "assistant" => last.tool_calls.push(call),
+        _ => messages.push(ChatMessage {
+            role: "assistant".to_string(),
+            tool_calls: vec![call],
+            ..Default::default()
+        }),
+    }
+}
+
+/// Ollama takes raw base64 image data rather than data URLs.
+fn image_data(image_url: &str) -> Option<String> {
+    let (_, data) = image_url.strip_prefix("data:")?.split_once(";base64,")?;
+    Some(data.to_string())
+}
+
+/// Sends `request` to `/api/chat` and streams the NDJSON reply as
+/// [`ResponseEvent`]s.
+pub(crate) async fn stream_chat(
+    provider: &ModelProviderInfo,
+    request: &ChatRequest,
+) -> Result<ResponseStream> {
+    let base_url = provider.base_url.as_deref().ok_or_else(|| {
+        CodexErr::UnsupportedOperation("Ollama providers must define a base_url".to_string())
+    })?;
+    let url = format!("{}/api/chat", base_url_to_host_root(base_url));
+
+    let client = build_reqwest_client();
+    let mut builder = provider.apply_http_headers(client.post(&url));
+    if let Ok(Some(api_key)) = provider.api_key() {
+        builder = builder.bearer_auth(api_key);
+    }
+    let response = builder
+        .json(request)
+        .send()
+        .await
+        .map_err(|source| CodexErr::ConnectionFailed(ConnectionFailedError { source }))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(CodexErr::UnexpectedStatus(UnexpectedResponseError {
+            status,
+            body: response.text().await.unwrap_or_default(),
+            request_id: None,
+        }));
+    }
+
+    let idle_timeout = provider.stream_idle_timeout();
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent>>(1600);
+    let byte_stream = response.bytes_stream();
+    tokio::spawn(async move {
+        process_chat_stream(byte_stream, tx_event, idle_timeout).await;
+    });
+    Ok(ResponseStream { rx_event })
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChunk {
+    #[serde(default)]
+    message: Option<ChunkMessage>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    prompt_eval_count: Option<i64>,
+    #[serde(default)]
+    eval_count: Option<i64>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkMessage {
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    thinking: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ToolCall>,
+}
+
+async fn process_chat_stream<S>(
+    mut stream: S,
+    tx_event: mpsc::Sender<Result<ResponseEvent>>,
+    idle_timeout: Duration,
+) where
+    S: futures::Stream<Item = std::result::Result<Bytes, reqwest::Error>> + Unpin,
+{
+    if tx_event.send(Ok(ResponseEvent::Created)).await.is_err() {
+        return;
+    }
+
+    let response_id = format!("ollama-{}", CALL_ID_COUNTER.fetch_add(1, Ordering::Relaxed));
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut text = String::new();
+    let mut thinking = String::new();
+    let mut tool_calls: Vec<ToolCall> = Vec::new();
+    let mut token_usage = None;
+    let mut done = false;
+
+    while !done {
+        let chunk = match timeout(idle_timeout, stream.next()).await {
+            Ok(Some(Ok(bytes))) => bytes,
+            Ok(Some(Err(err))) => {
+                let _ = tx_event
+                    .send(Err(CodexErr::Stream(err.to_string(), None)))
+                    .await;
+                return;
+            }
+            Ok(None) => break,
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(CodexErr::Stream(
+                        "idle timeout waiting for Ollama".to_string(),
+                        None,
+                    )))
+                    .await;
+                return;
+            }
+        };
+        buffer.extend_from_slice(&chunk);
+
+        while let Some(newline) = buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&line);
+            if line.trim().is_empty() {
+                continue;
+            }
+            let chunk: ChatChunk = match serde_json::from_str(line.trim()) {
+                Ok(chunk) => chunk,
+                Err(err) => {
+                    debug!("failed to parse Ollama chat chunk: {err}, data: {line}");
+                    continue;
+                }
+            };
+            if let Some(error) = chunk.error {
+                let _ = tx_event.send(Err(CodexErr::Stream(error, None))).await;
+                return;
+            }
+            if let Some(message) = chunk.message {
+                if let Some(delta) = message.thinking.filter(|delta| !delta.is_empty()) {
+                    if thinking.is_empty() {
+                        let item = ResponseItem::Reasoning {
+                            id: format!("{response_id}-thinking"),
+                            summary: Vec::new(),
+                            content: None,
+                            encrypted_content: None,
+                        };
+                        if tx_event
+                            .send(Ok(ResponseEvent::OutputItemAdded(item)))
+                            .await
+                            .is_err()
+                        {
+                            return;
+                        }
+                    }
+                    thinking.push_str(&delta);
+                    if tx_event
+                        .send(Ok(ResponseEvent::ReasoningContentDelta {
+                            delta,
+                            content_index: 0,
+                        }))
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+                if !message.content.is_empty() {
+                    if text.is_empty() {
+                        let item = ResponseItem::Message {
+                            id: None,
+                            role: "assistant".to_string(),
+                            content: Vec::new(),
+                            thought_signature: None,
+                        };
+                        if tx_event
+                            .send(Ok(ResponseEvent::OutputItemAdded(item)))
+                            .await
+                            .is_err()
+                        {
+                            return;
+                        }
+                    }
+                    text.push_str(&message.content);
+                    if tx_event
+                        .send(Ok(ResponseEvent::OutputTextDelta(message.content)))
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+                tool_calls.extend(message.tool_calls);
+            }
+            if chunk.done {
+                let input_tokens = chunk.prompt_eval_count.unwrap_or(0);
+                let output_tokens = chunk.eval_count.unwrap_or(0);
+                token_usage = Some(TokenUsage {
+                    input_tokens,
+                    cached_input_tokens: 0,
+                    output_tokens,
+                    reasoning_output_tokens: 0,
+                    total_tokens: input_tokens + output_tokens,
+                });
+                done = true;
+                break;
+            }
+        }
+    }
+
+    if !thinking.is_empty() {
+        let item = ResponseItem::Reasoning {
+            id: format!("{response_id}-thinking"),
+            summary: Vec::new(),
+            content: Some(vec![ReasoningItemContent::ReasoningText { text: thinking }]),
+            encrypted_content: None,
+        };
+        let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+    }
+    if !text.is_empty() {
+        let item = ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: vec![ContentItem::OutputText { text }],
+            thought_signature: None,
+        };
+        let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+    }
+    for call in tool_calls {
+        let arguments = match call.function.arguments {
+            Value::Null => "{}".to_string(),
+            Value::String(arguments) => arguments,
+            arguments => arguments.to_string(),
+        };
+        let item = ResponseItem::FunctionCall {
+            id: None,
+            name: call.function.name,
+            arguments,
+            call_id: format!(
+                "ollama-call-{}",
+                CALL_ID_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ),
+            thought_signature: None,
+        };
+        let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+    }
+
+    if !done {
+        let _ = tx_event
+            .send(Err(CodexErr::Stream(
+                "Ollama stream closed before the response finished".to_string(),
+                None,
+            )))
+            .await;
+        return;
+    }
+    let _ = tx_event
+        .send(Ok(ResponseEvent::Completed {
+            response_id,
+            token_usage,
+        }))
+        .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn chat_request_carries_options_and_tool_history() {
+        let input = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "list files".to_string(),
+                }],
+                thought_signature: None,
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["ls"]}"#.to_string(),
+                call_id: "call-1".to_string(),
+                thought_signature: None,
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "README.md".to_string(),
+                    ..Default::default()
+                },
+            },
+        ];
+        let info = OllamaModelInfo {
+            context_length: Some(131_072),
+            num_ctx: None,
+            supports_tools: false,
+            supports_thinking: true,
+        };
+
+        let request = build_chat_request(ChatRequestParams {
+            model: "gpt-oss:20b",
+            instructions: "be brief",
+            input: &input,
+            tools: vec![json!({"type": "function", "function": {"name": "shell"}})],
+            info: Some(&info),
+            effort: Some(ReasoningEffort::High),
+            num_ctx: Some(131_072),
+            keep_alive: Some("-1"),
+        });
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "model": "gpt-oss:20b",
+                "messages": [
+                    {"role": "system", "content": "be brief"},
+                    {"role": "user", "content": "list files"},
+                    {
+                        "role": "assistant",
+                        "content": "",
+                        "tool_calls": [
+                            {"function": {"name": "shell", "arguments": {"command": ["ls"]}}}
+                        ]
+                    },
+                    {"role": "tool", "content": "README.md", "tool_name": "shell"}
+                ],
+                "stream": true,
+                "think": "high",
+                "keep_alive": -1,
+                "options": {"num_ctx": 131072}
+            })
+        );
+    }
+}
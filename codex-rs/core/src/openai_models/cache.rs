@@ -3,12 +3,15 @@ use chrono::Utc;
 use codex_protocol::openai_models::ModelInfo;
 use serde::Deserialize;
 use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::io;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::time::Duration;
 use tokio::fs;
 
+use super::discovery::DiscoveredModel;
+
 /// Serialized snapshot of models and metadata cached on disk.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct ModelsCache {
@@ -21,19 +24,41 @@ pub(crate) struct ModelsCache {
 impl ModelsCache {
     /// Returns `true` when the cache entry has not exceeded the configured TTL.
     pub(crate) fn is_fresh(&self, ttl: Duration) -> bool {
-        if ttl.is_zero() {
-            return false;
-        }
-        let Ok(ttl_duration) = chrono::Duration::from_std(ttl) else {
-            return false;
-        };
-        let age = Utc::now().signed_duration_since(self.fetched_at);
-        age <= ttl_duration
+        is_fresh(self.fetched_at, ttl)
+    }
+}
+
+/// Models discovered from a single provider, cached on disk per provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ProviderModelsCache {
+    pub(crate) fetched_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) etag: Option<String>,
+    /// Base URL the models were listed from; a different URL invalidates the entry.
+    pub(crate) base_url: String,
+    pub(crate) models: Vec<DiscoveredModel>,
+}
+
+impl ProviderModelsCache {
+    /// Returns `true` when the cache entry has not exceeded the configured TTL.
+    pub(crate) fn is_fresh(&self, ttl: Duration) -> bool {
+        is_fresh(self.fetched_at, ttl)
+    }
+}
+
+fn is_fresh(fetched_at: DateTime<Utc>, ttl: Duration) -> bool {
+    if ttl.is_zero() {
+        return false;
     }
+    let Ok(ttl_duration) = chrono::Duration::from_std(ttl) else {
+        return false;
+    };
+    let age = Utc::now().signed_duration_since(fetched_at);
+    age <= ttl_duration
 }
 
 /// Read and deserialize the cache file if it exists.
-pub(crate) async fn load_cache(path: &Path) -> io::Result<Option<ModelsCache>> {
+pub(crate) async fn load_cache<T: DeserializeOwned>(path: &Path) -> io::Result<Option<T>> {
     match fs::read(path).await {
         Ok(contents) => {
             let cache = serde_json::from_slice(&contents)
@@ -46,7 +71,7 @@ pub(crate) async fn load_cache(path: &Path) -> io::Result<Option<ModelsCache>> {
 }
 
 /// Persist the cache contents to disk, creating parent directories as needed.
-pub(crate) async fn save_cache(path: &Path, cache: &ModelsCache) -> io::Result<()> {
+pub(crate) async fn save_cache<T: Serialize>(path: &Path, cache: &T) -> io::Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).await?;
     }
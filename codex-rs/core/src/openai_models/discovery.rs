@@ -0,0 +1,522 @@
+//! Lists the models served by a configured provider.
+//!
+//! Chat Completions and Responses providers are asked for the
+//! OpenAI-compatible `GET {base_url}/models` listing, Gemini providers for
+//! `models.list`, and native Ollama providers for `/api/tags` plus `/api/show`
+//! per model. When a previous listing returned an ETag the request is
+//! sent with `If-None-Match` so an unchanged listing costs a `304`.
+
+use std::time::Duration;
+
+use codex_protocol::openai_models::ModelPreset;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::openai_models::ReasoningEffortPreset;
+use http::header::ETAG;
+use http::header::IF_NONE_MATCH;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::client::ModelClient;
+use crate::default_client::build_reqwest_client;
+use crate::error::CodexErr;
+use crate::error::ConnectionFailedError;
+use crate::error::Result;
+use crate::error::UnexpectedResponseError;
+use crate::model_provider_info::ModelProviderInfo;
+use crate::model_provider_info::WireApi;
+
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(10);
+const GEMINI_PAGE_SIZE: u32 = 1000;
+const GEMINI_GENERATE_CONTENT: &str = "generateContent";
+
+/// A model reported by a provider's model listing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiscoveredModel {
+    /// Identifier to send as `model` in requests.
+    pub slug: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Context window in tokens, when the listing exposes it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<i64>,
+    /// Whether the model accepts reasoning/thinking controls, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_reasoning: Option<bool>,
+    /// Whether the model accepts tool definitions, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_tools: Option<bool>,
+}
+
+impl DiscoveredModel {
+    /// Picker entry for this model. The id is prefixed with the provider so
+    /// the same slug served by two providers stays distinguishable.
+    pub fn to_preset(&self, provider_id: &str) -> ModelPreset {
+        let supported_reasoning_efforts = if self.supports_reasoning == Some(true) {
+            vec![
+                ReasoningEffortPreset {
+                    effort: ReasoningEffort::Low,
+                    description: "Fast responses with lighter reasoning".to_string(),
+                },
+                ReasoningEffortPreset {
+                    effort: ReasoningEffort::Medium,
+                    description: "Balances speed and reasoning depth".to_string(),
+                },
+                ReasoningEffortPreset {
+                    effort: ReasoningEffort::High,
+                    description: "Greater reasoning depth for complex problems".to_string(),
+                },
+            ]
+        } else {
+            Vec::new()
+        };
+        let mut description = self.description.clone().unwrap_or_default();
+        if let Some(context_window) = self.context_window {
+            if !description.is_empty() {
+                description.push(' ');
+            }
+            description.push_str(&format!("({} context)", format_token_count(context_window)));
+        }
+        ModelPreset {
+            id: format!("{provider_id}/{}", self.slug),
+            model: self.slug.clone(),
+            display_name: self
+                .display_name
+                .clone()
+                .unwrap_or_else(|| self.slug.clone()),
+            description,
+            default_reasoning_effort: ReasoningEffort::Medium,
+            supported_reasoning_efforts,
+            is_default: false,
+            upgrade: None,
+            show_in_picker: true,
+            supported_in_api: true,
+        }
+    }
+}
+
+fn format_token_count(tokens: i64) -> String {
+    if tokens >= 1_000_000 {
+        format!("{}M", tokens / 1_000_000)
+    } else if tokens >= 1_000 {
+        format!("{}K", tokens / 1_000)
+    } else {
+        tokens.to_string()
+    }
+}
+
+/// Result of listing a provider's models.
+#[derive(Debug)]
+pub(crate) enum ModelListing {
+    /// The listing matches the ETag that was sent.
+    NotModified,
+    Models {
+        models: Vec<DiscoveredModel>,
+        etag: Option<String>,
+    },
+}
+
+/// Credential attached to discovery requests.
+#[derive(Debug, Clone)]
+pub(crate) enum DiscoveryAuth {
+    Bearer(String),
+    GoogleApiKey(String),
+}
+
+/// Base URL the listing is requested from, or `None` when the provider
+/// relies on an implicit default that discovery should not guess.
+pub(crate) fn discovery_base_url(provider: &ModelProviderInfo) -> Option<String> {
+    let base_url = provider.base_url.as_deref()?;
+    Some(match provider.wire_api {
+        WireApi::Gemini => ModelClient::normalize_gemini_base_url(base_url).into_owned(),
+        WireApi::Ollama => codex_ollama::base_url_to_host_root(base_url),
+        WireApi::Chat | WireApi::Responses => base_url.trim_end_matches('/').to_string(),
+    })
+}
+
+/// Lists the models served by `provider` at `base_url`.
+pub(crate) async fn fetch_provider_models(
+    provider: &ModelProviderInfo,
+    base_url: &str,
+    auth: Option<&DiscoveryAuth>,
+    etag: Option<&str>,
+) -> Result<ModelListing> {
+    match provider.wire_api {
+        WireApi::Gemini => fetch_gemini_models(provider, base_url, auth, etag).await,
+        WireApi::Ollama => fetch_ollama_models(provider, base_url, auth, etag).await,
+        WireApi::Chat | WireApi::Responses => {
+            let url = with_query_params(format!("{base_url}/models"), provider, &[]);
+            let Some((body, etag)) = get_listing(provider, &url, auth, etag).await? else {
+                return Ok(ModelListing::NotModified);
+            };
+            Ok(ModelListing::Models {
+                models: parse_openai_models(&body)?,
+                etag,
+            })
+        }
+    }
+}
+
+async fn fetch_gemini_models(
+    provider: &ModelProviderInfo,
+    base_url: &str,
+    auth: Option<&DiscoveryAuth>,
+    etag: Option<&str>,
+) -> Result<ModelListing> {
+    let mut models = Vec::new();
+    let mut first_etag = None;
+    let mut page_token: Option<String> = None;
+    loop {
+        let page_size = GEMINI_PAGE_SIZE.to_string();
+        let mut extra = vec![("pageSize", page_size.as_str())];
+        if let Some(token) = page_token.as_deref() {
+            extra.push(("pageToken", token));
+        }
+        let url = with_query_params(format!("{base_url}/models"), provider, &extra);
+        // Only the first page is revalidated; a changed first page means the
+        // whole listing is fetched again.
+        let if_none_match = if page_token.is_none() { etag } else { None };
+        let Some((body, page_etag)) = get_listing(provider, &url, auth, if_none_match).await?
+        else {
+            return Ok(ModelListing::NotModified);
+        };
+        if page_token.is_none() {
+            first_etag = page_etag;
+        }
+        let page: GeminiModelsPage = serde_json::from_slice(&body)?;
+        models.extend(
+            page.models
+                .into_iter()
+                .filter_map(GeminiModel::into_discovered),
+        );
+        match page.next_page_token.filter(|token| !token.is_empty()) {
+            Some(token) => page_token = Some(token),
+            None => break,
+        }
+    }
+    Ok(ModelListing::Models {
+        models,
+        etag: first_etag,
+    })
+}
+
+async fn fetch_ollama_models(
+    provider: &ModelProviderInfo,
+    base_url: &str,
+    auth: Option<&DiscoveryAuth>,
+    etag: Option<&str>,
+) -> Result<ModelListing> {
+    let url = with_query_params(format!("{base_url}/api/tags"), provider, &[]);
+    let Some((body, etag)) = get_listing(provider, &url, auth, etag).await? else {
+        return Ok(ModelListing::NotModified);
+    };
+    let tags: OllamaTags = serde_json::from_slice(&body)?;
+    let mut models = Vec::with_capacity(tags.models.len());
+    for tag in tags.models {
+        // `/api/tags` only names the models; context length and capabilities
+        // come from `/api/show`.
+        let info = crate::ollama::model_info(provider, &tag.name).await;
+        models.push(DiscoveredModel {
+            context_window: info
+                .as_ref()
+                .and_then(codex_ollama::OllamaModelInfo::context_window),
+            supports_reasoning: info.as_ref().map(|info| info.supports_thinking),
+            supports_tools: info.as_ref().map(|info| info.supports_tools),
+            slug: tag.name,
+            display_name: None,
+            description: None,
+        });
+    }
+    Ok(ModelListing::Models { models, etag })
+}
+
+/// Performs the GET and returns the body plus ETag, or `None` on `304`.
+async fn get_listing(
+    provider: &ModelProviderInfo,
+    url: &str,
+    auth: Option<&DiscoveryAuth>,
+    etag: Option<&str>,
+) -> Result<Option<(Vec<u8>, Option<String>)>> {
+    let client = build_reqwest_client();
+    let mut builder = provider.apply_http_headers(client.get(url).timeout(DISCOVERY_TIMEOUT));
+    builder = match auth {
+        Some(DiscoveryAuth::Bearer(token)) => builder.bearer_auth(token),
+        Some(DiscoveryAuth::GoogleApiKey(key)) => builder.header("x-goog-api-key", key),
+        None => builder,
+    };
+    if let Some(etag) = etag {
+        builder = builder.header(IF_NONE_MATCH, etag);
+    }
+
+    let response = builder
+        .send()
+        .await
+        .map_err(|source| CodexErr::ConnectionFailed(ConnectionFailedError { source }))?;
+    let status = response.status();
+    if status == StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string);
+    let body = response
+        .bytes()
+        .await
+        .map_err(|source| CodexErr::ConnectionFailed(ConnectionFailedError { source }))?;
+    if !status.is_success() {
+        return Err(CodexErr::UnexpectedStatus(UnexpectedResponseError {
+            status,
+            body: String::from_utf8_lossy(&body).into_owned(),
+            request_id: None,
+        }));
+    }
+    Ok(Some((body.to_vec(), etag)))
+}
+
+fn with_query_params(
+    mut url: String,
+    provider: &ModelProviderInfo,
+    extra: &[(&str, &str)],
+) -> String {
+    let mut params = provider
+        .query_params
+        .iter()
+        .flatten()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .chain(extra.iter().copied())
+        .peekable();
+    if params.peek().is_some() {
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(params)
+            .finish();
+        url.push('?');
+        url.push_str(&query);
+    }
+    url
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiModelsPage {
+    data: Vec<Value>,
+}
+
+/// Parses an OpenAI-compatible listing. Only `id` is standard; context
+/// windows and capabilities are picked up from the extensions that
+/// OpenRouter, vLLM, LM Studio and similar servers add.
+fn parse_openai_models(body: &[u8]) -> Result<Vec<DiscoveredModel>> {
+    let page: OpenAiModelsPage = serde_json::from_slice(body)?;
+    Ok(page
+        .data
+        .into_iter()
+        .filter_map(|entry| {
+            let slug = entry.get("id")?.as_str()?.to_string();
+            let string_field = |key: &str| {
+                entry
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .filter(|value| !value.is_empty())
+                    .map(ToString::to_string)
+            };
+            let context_window = [
+                "context_length",
+                "context_window",
+                "max_context_length",
+                "max_model_len",
+            ]
+            .iter()
+            .find_map(|key| entry.get(*key).and_then(Value::as_i64))
+            .filter(|tokens| *tokens > 0);
+            let supported_parameters = entry
+                .get("supported_parameters")
+                .and_then(Value::as_array)
+                .map(|params| params.iter().filter_map(Value::as_str).collect::<Vec<_>>());
+            let supports = |param: &str| {
+                supported_parameters
+                    .as_ref()
+                    .map(|params| params.contains(&param))
+            };
+            Some(DiscoveredModel {
+                display_name: string_field("name").filter(|name| name != &slug),
+                description: string_field("description"),
+                context_window,
+                supports_reasoning: supports("reasoning"),
+                supports_tools: supports("tools"),
+                slug,
+            })
+        })
+        .collect())
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTags {
+    #[serde(default)]
+    models: Vec<OllamaTag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTag {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiModelsPage {
+    #[serde(default)]
+    models: Vec<GeminiModel>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiModel {
+    name: String,
+    display_name: Option<String>,
+    description: Option<String>,
+    input_token_limit: Option<i64>,
+    #[serde(default)]
+    supported_generation_methods: Vec<String>,
+    thinking: Option<bool>,
+}
+
+impl GeminiModel {
+    /// Keeps only models that can generate content; embedding and other
+    /// special-purpose models cannot drive a session.
+    fn into_discovered(self) -> Option<DiscoveredModel> {
+        if !self
+            .supported_generation_methods
+            .iter()
+            .any(|method| method == GEMINI_GENERATE_CONTENT)
+        {
+            return None;
+        }
+        let slug = self
+            .name
+            .strip_prefix("models/")
+            .unwrap_or(&self.name)
+            .to_string();
+        Some(DiscoveredModel {
+            slug,
+            display_name: self.display_name,
+            description: self.description,
+            context_window: self.input_token_limit.filter(|tokens| *tokens > 0),
+            supports_reasoning: self.thinking,
+            supports_tools: Some(true),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn openai_listing_picks_up_known_extensions() {
+        let body = json!({
+            "object": "list",
+            "data": [
+                {"id": "gpt-oss-20b", "object": "model", "owned_by": "organization"},
+                {
+                    "id": "qwen/qwen3-coder",
+                    "name": "Qwen3 Coder",
+                    "context_length": 262144,
+                    "supported_parameters": ["tools", "temperature"]
+                },
+                {"id": "llama-3.1-8b", "max_model_len": 131072}
+            ]
+        });
+
+        let models = parse_openai_models(body.to_string().as_bytes()).expect("parse listing");
+
+        assert_eq!(
+            models,
+            vec![
+                DiscoveredModel {
+                    slug: "gpt-oss-20b".to_string(),
+                    display_name: None,
+                    description: None,
+                    context_window: None,
+                    supports_reasoning: None,
+                    supports_tools: None,
+                },
+                DiscoveredModel {
+                    slug: "qwen/qwen3-coder".to_string(),
+                    display_name: Some("Qwen3 Coder".to_string()),
+                    description: None,
+                    context_window: Some(262_144),
+                    supports_reasoning: Some(false),
+                    supports_tools: Some(true),
+                },
+                DiscoveredModel {
+                    slug: "llama-3.1-8b".to_string(),
+                    display_name: None,
+                    description: None,
+                    context_window: Some(131_072),
+                    supports_reasoning: None,
+                    supports_tools: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn gemini_listing_skips_models_that_cannot_generate_content() {
+        let page: GeminiModelsPage = serde_json::from_value(json!({
+            "models": [
+                {
+                    "name": "models/gemini-2.5-pro",
+                    "displayName": "Gemini 2.5 Pro",
+                    "inputTokenLimit": 1048576,
+                    "supportedGenerationMethods": ["generateContent", "countTokens"],
+                    "thinking": true
+                },
+                {
+                    "name": "models/text-embedding-004",
+                    "supportedGenerationMethods": ["embedContent"]
+                }
+            ]
+        }))
+        .expect("parse page");
+
+        let models: Vec<DiscoveredModel> = page
+            .models
+            .into_iter()
+            .filter_map(GeminiModel::into_discovered)
+            .collect();
+
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].slug, "gemini-2.5-pro");
+        assert_eq!(models[0].context_window, Some(1_048_576));
+        assert_eq!(models[0].supports_reasoning, Some(true));
+        assert_eq!(
+            models[0].to_preset("gemini").description,
+            "(1M context)".to_string()
+        );
+    }
+
+    #[test]
+    fn query_params_are_url_encoded() {
+        let mut provider =
+            crate::create_oss_provider_with_base_url("http://localhost:1234/v1", WireApi::Chat);
+        provider.query_params = Some(
+            [("api-version".to_string(), "2024-10-01 preview".to_string())]
+                .into_iter()
+                .collect(),
+        );
+
+        assert_eq!(
+            with_query_params(
+                "http://localhost:1234/v1/models".to_string(),
+                &provider,
+                &[("pageToken", "a+b/c=&d")],
+            ),
+            "http://localhost:1234/v1/models?api-version=2024-10-01+preview&pageToken=a%2Bb%2Fc%3D%26d"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 mod cache;
+pub mod discovery;
 pub mod model_family;
 pub mod model_presets;
 pub mod models_manager;
@@ -4,6 +4,7 @@ use codex_protocol::openai_models::ConfigShellToolType;
 use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::openai_models::ReasoningSummaryFormat;
+use codex_utils_tokenizer::Tokenizer;
 
 use crate::config::Config;
 use crate::truncate::TruncationPolicy;
@@ -83,6 +84,9 @@ pub struct ModelFamily {
     pub shell_type: ConfigShellToolType,
 
     pub truncation_policy: TruncationPolicy,
+
+    /// Tokenizer used to count tokens for truncation and context accounting.
+    pub tokenizer: Tokenizer,
 }
 
 impl ModelFamily {
@@ -189,6 +193,7 @@ macro_rules! model_family {
             default_verbosity: None,
             default_reasoning_effort: None,
             truncation_policy: TruncationPolicy::Bytes(10_000),
+            tokenizer: Tokenizer::O200kBase,
         };
 
         // apply overrides
@@ -248,6 +253,7 @@ pub(super) fn find_family_for_model(slug: &str) -> ModelFamily {
             slug, "gpt-3.5",
             needs_special_apply_patch_instructions: true,
             context_window: Some(16_385),
+            tokenizer: Tokenizer::Cl100kBase,
         )
     } else if slug == "gemini-3-flash-preview-gemini" {
         model_family!(
@@ -263,6 +269,7 @@ pub(super) fn find_family_for_model(slug: &str) -> ModelFamily {
             supports_parallel_tool_calls: true,
             shell_type: ConfigShellToolType::ShellCommand,
             context_window: Some(CONTEXT_WINDOW_1M),
+            tokenizer: Tokenizer::Calibrated,
         )
     } else if slug == "gemini-3-pro-preview-codex" {
         model_family!(
@@ -278,6 +285,7 @@ pub(super) fn find_family_for_model(slug: &str) -> ModelFamily {
             supports_parallel_tool_calls: true,
             shell_type: ConfigShellToolType::ShellCommand,
             context_window: Some(CONTEXT_WINDOW_1M),
+            tokenizer: Tokenizer::Calibrated,
         )
     } else if slug.starts_with("gemini-") && slug.ends_with("-codex") {
         model_family!(
@@ -293,6 +301,7 @@ pub(super) fn find_family_for_model(slug: &str) -> ModelFamily {
             supports_parallel_tool_calls: true,
             shell_type: ConfigShellToolType::ShellCommand,
             context_window: Some(CONTEXT_WINDOW_1M),
+            tokenizer: Tokenizer::Calibrated,
         )
     } else if slug.starts_with("gemini-") {
         model_family!(
@@ -306,6 +315,7 @@ pub(super) fn find_family_for_model(slug: &str) -> ModelFamily {
                 "read_file".to_string(),
             ],
             context_window: Some(CONTEXT_WINDOW_1M),
+            tokenizer: Tokenizer::Calibrated,
         )
     } else if slug.starts_with("test-gpt-5") {
         model_family!(
@@ -488,6 +498,7 @@ fn derive_default_model_family(model: &str) -> ModelFamily {
         default_verbosity: None,
         default_reasoning_effort: None,
         truncation_policy: TruncationPolicy::Bytes(10_000),
+        tokenizer: Tokenizer::Approximate,
     }
 }
 
@@ -642,4 +653,24 @@ mod tests {
         );
         assert_eq!(updated.base_instructions, "Remote instructions");
     }
+
+    #[test]
+    fn families_pick_their_tokenizer() {
+        assert_eq!(
+            find_family_for_model("gpt-5.1-codex").tokenizer,
+            Tokenizer::O200kBase
+        );
+        assert_eq!(
+            find_family_for_model("gpt-3.5-turbo").tokenizer,
+            Tokenizer::Cl100kBase
+        );
+        assert_eq!(
+            find_family_for_model("gemini-2.5-pro").tokenizer,
+            Tokenizer::Calibrated
+        );
+        assert_eq!(
+            find_family_for_model("some-local-model").tokenizer,
+            Tokenizer::Approximate
+        );
+    }
 }
@@ -5,17 +5,26 @@ use codex_app_server_protocol::AuthMode;
 use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ModelsResponse;
+use futures::future::join_all;
 use http::HeaderMap;
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tokio::sync::TryLockError;
+use tracing::debug;
 use tracing::error;
 
 use super::cache;
 use super::cache::ModelsCache;
+use super::cache::ProviderModelsCache;
+use super::discovery::DiscoveredModel;
+use super::discovery::DiscoveryAuth;
+use super::discovery::ModelListing;
+use super::discovery::discovery_base_url;
+use super::discovery::fetch_provider_models;
 use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use crate::auth::AuthManager;
@@ -24,21 +33,36 @@ use crate::default_client::build_reqwest_client;
 use crate::error::Result as CoreResult;
 use crate::features::Feature;
 use crate::model_provider_info::ModelProviderInfo;
+use crate::model_provider_info::OLLAMA_OSS_PROVIDER_ID;
+use crate::model_provider_info::WireApi;
 use crate::openai_models::model_family::ModelFamily;
 use crate::openai_models::model_presets::builtin_model_presets;
 
 const MODEL_CACHE_FILE: &str = "models_cache.json";
+const PROVIDER_MODELS_CACHE_DIR: &str = "models_cache";
+/// Provider whose models come from `/models` with ChatGPT metadata instead of discovery.
+const OPENAI_PROVIDER_ID: &str = "openai";
 const DEFAULT_MODEL_CACHE_TTL: Duration = Duration::from_secs(300);
 const OPENAI_DEFAULT_API_MODEL: &str = "gpt-5.1-codex-max";
 const OPENAI_DEFAULT_CHATGPT_MODEL: &str = "gpt-5.2-codex";
 const CODEX_AUTO_BALANCED_MODEL: &str = "codex-auto-balanced";
 
+/// Models discovered from one configured provider, ready for a picker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderModels {
+    pub provider_id: String,
+    pub provider_name: String,
+    pub models: Vec<ModelPreset>,
+}
+
 /// Coordinates remote model discovery plus cached metadata on disk.
 #[derive(Debug)]
 pub struct ModelsManager {
     // todo(aibrahim) merge available_models and model family creation into one struct
     local_models: Vec<ModelPreset>,
     remote_models: RwLock<Vec<ModelInfo>>,
+    /// Models listed by each non-OpenAI provider, keyed by provider id.
+    discovered_models: RwLock<BTreeMap<String, Vec<DiscoveredModel>>>,
     auth_manager: Arc<AuthManager>,
     etag: RwLock<Option<String>>,
     codex_home: PathBuf,
@@ -53,6 +77,7 @@ impl ModelsManager {
         Self {
             local_models: builtin_model_presets(auth_manager.get_auth_mode()),
             remote_models: RwLock::new(Self::load_remote_models_from_file().unwrap_or_default()),
+            discovered_models: RwLock::new(BTreeMap::new()),
             auth_manager,
             etag: RwLock::new(None),
             codex_home,
@@ -68,6 +93,7 @@ impl ModelsManager {
         Self {
             local_models: builtin_model_presets(auth_manager.get_auth_mode()),
             remote_models: RwLock::new(Self::load_remote_models_from_file().unwrap_or_default()),
+            discovered_models: RwLock::new(BTreeMap::new()),
             auth_manager,
             etag: RwLock::new(None),
             codex_home,
@@ -120,15 +146,192 @@ impl ModelsManager {
         Ok(self.build_available_models(remote_models))
     }
 
+    /// Refresh the models listed by every configured provider other than
+    /// OpenAI, using the per-provider cache when it is still fresh.
+    pub async fn refresh_provider_models(&self, config: &Config) {
+        if !config.features.enabled(Feature::RemoteModels) {
+            return;
+        }
+        let refreshes = config
+            .model_providers
+            .iter()
+            .filter(|(provider_id, _)| provider_id.as_str() != OPENAI_PROVIDER_ID)
+            .map(|(provider_id, provider)| async move {
+                let result = self.refresh_provider(config, provider_id, provider).await;
+                (provider_id.clone(), result)
+            });
+        let mut discovered = BTreeMap::new();
+        for (provider_id, result) in join_all(refreshes).await {
+            match result {
+                Ok(Some(models)) if !models.is_empty() => {
+                    discovered.insert(provider_id, models);
+                }
+                Ok(_) => {}
+                // Local servers that are not running are the common case here.
+                Err(err) => debug!("failed to list models for provider {provider_id}: {err}"),
+            }
+        }
+        *self.discovered_models.write().await = discovered;
+    }
+
+    /// Models discovered from configured providers, grouped by provider.
+    pub async fn list_provider_models(&self, config: &Config) -> Vec<ProviderModels> {
+        self.refresh_provider_models(config).await;
+        let discovered = self.discovered_models.read().await.clone();
+        Self::build_provider_models(config, discovered)
+    }
+
+    pub fn try_list_provider_models(
+        &self,
+        config: &Config,
+    ) -> Result<Vec<ProviderModels>, TryLockError> {
+        let discovered = self.discovered_models.try_read()?.clone();
+        Ok(Self::build_provider_models(config, discovered))
+    }
+
+    fn build_provider_models(
+        config: &Config,
+        discovered: BTreeMap<String, Vec<DiscoveredModel>>,
+    ) -> Vec<ProviderModels> {
+        if !config.features.enabled(Feature::RemoteModels) {
+            return Vec::new();
+        }
+        discovered
+            .into_iter()
+            .filter_map(|(provider_id, models)| {
+                let provider = config.model_providers.get(&provider_id)?;
+                Some(ProviderModels {
+                    provider_name: provider.name.clone(),
+                    models: models
+                        .iter()
+                        .map(|model| model.to_preset(&provider_id))
+                        .collect(),
+                    provider_id,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns `None` when the provider cannot be listed, for example because
+    /// it has no base URL or its credentials are missing.
+    async fn refresh_provider(
+        &self,
+        config: &Config,
+        provider_id: &str,
+        provider: &ModelProviderInfo,
+    ) -> CoreResult<Option<Vec<DiscoveredModel>>> {
+        let Some(base_url) = discovery_base_url(provider) else {
+            return Ok(None);
+        };
+        let Some(auth) = self.discovery_auth(config, provider).await else {
+            return Ok(None);
+        };
+
+        let cache_path = self.provider_cache_path(provider_id);
+        let cached = match cache::load_cache::<ProviderModelsCache>(&cache_path).await {
+            Ok(cached) => cached.filter(|cached| cached.base_url == base_url),
+            Err(err) => {
+                error!("failed to load models cache for provider {provider_id}: {err}");
+                None
+            }
+        };
+        if let Some(cached) = cached.as_ref()
+            && cached.is_fresh(self.cache_ttl)
+        {
+            return Ok(Some(cached.models.clone()));
+        }
+
+        let etag = cached.as_ref().and_then(|cached| cached.etag.as_deref());
+        let (models, etag) =
+            match fetch_provider_models(provider, &base_url, auth.as_ref(), etag).await {
+                Ok(ModelListing::Models { models, etag }) => (models, etag),
+                Ok(ModelListing::NotModified) => match cached {
+                    Some(cached) => (cached.models, cached.etag),
+                    None => return Ok(None),
+                },
+                // Keep offering the last known listing while the provider is unreachable.
+                Err(err) => match cached {
+                    Some(cached) => {
+                        debug!("using stale model listing for provider {provider_id}: {err}");
+                        return Ok(Some(cached.models));
+                    }
+                    None => return Err(err),
+                },
+            };
+
+        let cache = ProviderModelsCache {
+            fetched_at: Utc::now(),
+            etag,
+            base_url,
+            models: models.clone(),
+        };
+        if let Err(err) = cache::save_cache(&cache_path, &cache).await {
+            error!("failed to write models cache for provider {provider_id}: {err}");
+        }
+        Ok(Some(models))
+    }
+
+    /// Credential for listing `provider`'s models. The outer `None` means the
+    /// provider needs a credential that is not configured.
+    async fn discovery_auth(
+        &self,
+        config: &Config,
+        provider: &ModelProviderInfo,
+    ) -> Option<Option<DiscoveryAuth>> {
+        if provider.wire_api == WireApi::Gemini {
+            let account =
+                crate::auth::resolve_account(&config.codex_home, config.account.as_deref());
+            let api_key = crate::auth::read_gemini_api_key_from_env().or_else(|| {
+                crate::auth::read_account_gemini_api_key(
+                    &config.codex_home,
+                    account.as_deref(),
+                    config.cli_auth_credentials_store_mode,
+                )
+            })?;
+            return Some(Some(DiscoveryAuth::GoogleApiKey(api_key)));
+        }
+        match provider.api_key() {
+            Ok(Some(api_key)) => return Some(Some(DiscoveryAuth::Bearer(api_key))),
+            Ok(None) => {}
+            Err(_) => return None,
+        }
+        if let Some(token) = provider.experimental_bearer_token.clone() {
+            return Some(Some(DiscoveryAuth::Bearer(token)));
+        }
+        if provider.requires_openai_auth {
+            let token = self.auth_manager.auth()?.get_token().await.ok()?;
+            return Some(Some(DiscoveryAuth::Bearer(token)));
+        }
+        Some(None)
+    }
+
     fn find_family_for_model(slug: &str) -> ModelFamily {
         super::model_family::find_family_for_model(slug)
     }
 
     /// Look up the requested model family while applying remote metadata overrides.
     pub async fn construct_model_family(&self, model: &str, config: &Config) -> ModelFamily {
-        Self::find_family_for_model(model)
-            .with_remote_overrides(self.remote_models(config).await)
-            .with_config_overrides(config)
+        let mut family = Self::find_family_for_model(model)
+            .with_remote_overrides(self.remote_models(config).await);
+        // Ollama reports the context the model will actually run with; an
+        // explicit `model_context_window` still wins below. The built-in
+        // provider's OpenAI-compatible endpoint ignores `num_ctx` in requests,
+        // so only a Modelfile `num_ctx` applies there.
+        let ollama_context_window = if config.model_provider.wire_api == WireApi::Ollama {
+            crate::ollama::model_info(&config.model_provider, model)
+                .await
+                .and_then(|info| info.context_window())
+        } else if config.model_provider_id == OLLAMA_OSS_PROVIDER_ID {
+            crate::ollama::model_info(&config.model_provider, model)
+                .await
+                .and_then(|info| info.num_ctx)
+        } else {
+            None
+        };
+        if let Some(context_window) = ollama_context_window {
+            family.context_window = Some(context_window);
+        }
+        family.with_config_overrides(config)
     }
 
     pub async fn get_model(&self, model: &Option<String>, config: &Config) -> String {
@@ -180,7 +383,7 @@ impl ModelsManager {
     async fn try_load_cache(&self) -> bool {
         // todo(aibrahim): think if we should store fetched_at in ModelsManager so we don't always need to read the disk
         let cache_path = self.cache_path();
-        let cache = match cache::load_cache(&cache_path).await {
+        let cache = match cache::load_cache::<ModelsCache>(&cache_path).await {
             Ok(cache) => cache,
             Err(err) => {
                 error!("failed to load models cache: {err}");
@@ -284,6 +487,22 @@ impl ModelsManager {
     fn cache_path(&self) -> PathBuf {
         self.codex_home.join(MODEL_CACHE_FILE)
     }
+
+    fn provider_cache_path(&self, provider_id: &str) -> PathBuf {
+        let file_stem: String = provider_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.codex_home
+            .join(PROVIDER_MODELS_CACHE_DIR)
+            .join(format!("{file_stem}.json"))
+    }
 }
 
 /// Convert a client version string to a whole version string (e.g. "1.2.3-alpha.4" -> "1.2.3")
@@ -322,7 +541,12 @@ mod tests {
     use pretty_assertions::assert_eq;
     use serde_json::json;
     use tempfile::tempdir;
+    use wiremock::Mock;
     use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
 
     fn remote_model(slug: &str, display: &str, priority: i32) -> ModelInfo {
         remote_model_with_visibility(slug, display, priority, "list")
@@ -639,6 +863,65 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn provider_models_are_discovered_and_revalidated_with_etag() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .and(header("if-none-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"v1\"")
+                    .set_body_json(json!({
+                        "object": "list",
+                        "data": [{"id": "qwen2.5-coder", "context_length": 32768}]
+                    })),
+            )
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let codex_home = tempdir().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("load default test config");
+        config.features.enable(Feature::RemoteModels);
+        config.model_providers = [(
+            "local".to_string(),
+            ModelProviderInfo {
+                name: "Local".to_string(),
+                wire_api: WireApi::Chat,
+                ..provider_for(format!("{}/v1", server.uri()))
+            },
+        )]
+        .into_iter()
+        .collect();
+        let auth_manager =
+            AuthManager::from_auth_for_testing(CodexAuth::from_api_key("Test API Key"));
+        let provider = provider_for("http://example.test".to_string());
+        let mut manager = ModelsManager::with_provider(auth_manager, provider);
+        manager.cache_ttl = Duration::ZERO;
+
+        let first = manager.list_provider_models(&config).await;
+        let second = manager.list_provider_models(&config).await;
+
+        assert_eq!(first, second, "a 304 should keep the cached listing");
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].provider_id, "local");
+        assert_eq!(first[0].provider_name, "Local");
+        assert_eq!(first[0].models[0].id, "local/qwen2.5-coder");
+        assert_eq!(first[0].models[0].description, "(32K context)");
+    }
+
     #[test]
     fn build_available_models_picks_default_after_hiding_hidden_models() {
         let auth_manager =
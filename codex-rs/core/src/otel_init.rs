@@ -59,10 +59,15 @@ pub fn build_provider(
                 client_private_key: config.client_private_key.clone(),
             }),
         },
+        Kind::File { path, rotate_bytes } => OtelExporter::File {
+            path: path.clone(),
+            rotate_bytes: *rotate_bytes,
+        },
     };
 
     let exporter = to_otel_exporter(&config.otel.exporter);
     let trace_exporter = to_otel_exporter(&config.otel.trace_exporter);
+    let metrics_exporter = to_otel_exporter(&config.otel.metrics_exporter);
 
     OtelProvider::from(&OtelSettings {
         service_name: originator().value.to_owned(),
@@ -71,6 +76,7 @@ pub fn build_provider(
         environment: config.otel.environment.to_string(),
         exporter,
         trace_exporter,
+        metrics_exporter,
     })
 }
 
@@ -0,0 +1,506 @@
+//! Hunk-level review of `apply_patch` calls.
+//!
+//! When the user answers a patch approval with
+//! `ReviewDecision::ApprovedWithHunkReview`, the reviewed hunks are folded
+//! into a fresh patch that only carries the accepted and edited hunks, and the
+//! model is told which hunks were rejected or rewritten. A mode change made to
+//! an added or updated file goes with that file's contents.
+
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use codex_apply_patch::ApplyPatchAction;
+use codex_apply_patch::ApplyPatchFileChange;
+use codex_apply_patch::MaybeApplyPatchVerified;
+use codex_apply_patch::maybe_parse_apply_patch_verified;
+use codex_protocol::protocol::HunkDecision;
+use codex_protocol::protocol::HunkReview;
+use similar::ChangeTag;
+use similar::TextDiff;
+
+/// Context lines around each hunk of the diffs sent for approval. Hunk indexes
+/// in a `HunkReview` refer to the hunks of a diff rendered with this radius.
+const REVIEW_CONTEXT_LINES: usize = 1;
+/// Context lines kept around each chunk of the rebuilt patch so it still
+/// anchors to the right place in the file.
+const PATCH_CONTEXT_LINES: usize = 3;
+const BASE64_LINE_WIDTH: usize = 76;
+
+pub(crate) struct ReviewedPatch {
+    /// What is left to apply, `None` when the user rejected everything.
+    pub(crate) action: Option<ApplyPatchAction>,
+    /// Which hunks were rejected or edited, phrased for the model.
+    pub(crate) summary: String,
+}
+
+/// Applies the user's hunk review to `action`.
+pub(crate) fn apply_hunk_review(
+    action: &ApplyPatchAction,
+    hunks: &[HunkReview],
+) -> Result<ReviewedPatch, String> {
+    let mut decisions: HashMap<&Path, HashMap<usize, &HunkDecision>> = HashMap::new();
+    for review in hunks {
+        if !action.changes().contains_key(&review.path) {
+            return Err(format!(
+                "{} is not part of this patch",
+                review.path.display()
+            ));
+        }
+        decisions
+            .entry(review.path.as_path())
+            .or_default()
+            .insert(review.hunk, &review.decision);
+    }
+
+    let mut paths: Vec<&PathBuf> = action.changes().keys().collect();
+    paths.sort();
+    let mut sections = Vec::new();
+    let mut notes = Vec::new();
+    for path in paths {
+        let change = &action.changes()[path];
+        let file_decisions = decisions.remove(path.as_path()).unwrap_or_default();
+        let display = display_path(path, &action.cwd);
+        match change {
+            ApplyPatchFileChange::Update {
+                move_path,
+                new_content,
+                mode,
+                ..
+            } => {
+                let old = fs::read_to_string(path)
+                    .map_err(|err| format!("failed to read {display}: {err}"))?;
+                let reviewed =
+                    review_update(&old, new_content, &file_decisions, &display, &mut notes)?;
+                if reviewed != old {
+                    // Before the update so a move carries the new mode along.
+                    if let Some(mode) = mode {
+                        sections.push(mode_section(path, *mode));
+                    }
+                    sections.push(update_section(path, move_path.as_deref(), &old, &reviewed));
+                } else {
+                    if move_path.is_some() {
+                        notes.push(format!(
+                            "- {display} was not moved because none of its hunks were kept"
+                        ));
+                    }
+                    if mode.is_some() {
+                        notes.push(format!(
+                            "- the mode of {display} was not changed because none of its hunks were kept"
+                        ));
+                    }
+                }
+            }
+            other => {
+                if let Some(idx) = file_decisions.keys().find(|idx| **idx > 0) {
+                    return Err(format!(
+                        "{display} has no hunk {idx}; it is a single change"
+                    ));
+                }
+                match (file_decisions.get(&0), other) {
+                    (None | Some(HunkDecision::Accept), _) => {
+                        sections.push(whole_file_section(path, other));
+                    }
+                    (Some(HunkDecision::Reject), _) => {
+                        notes.push(format!("- rejected the whole change to {display}"));
+                    }
+                    (
+                        Some(HunkDecision::Edit { replacement }),
+                        ApplyPatchFileChange::Add { mode, .. },
+                    ) => {
+                        notes.push(format!(
+                            "- edited the new file {display}; it was created as:\n{}",
+                            replacement.trim_end()
+                        ));
+                        sections.push(add_section(path, replacement));
+                        if let Some(mode) = mode {
+                            sections.push(mode_section(path, *mode));
+                        }
+                    }
+                    (Some(HunkDecision::Edit { .. }), _) => {
+                        return Err(format!(
+                            "{display} has no text to edit; accept or reject it instead"
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    let summary = if notes.is_empty() {
+        "The user reviewed this patch hunk by hunk and accepted every hunk.".to_string()
+    } else {
+        format!(
+            "The user reviewed this patch hunk by hunk. Only the accepted and edited hunks were applied:\n{}",
+            notes.join("\n")
+        )
+    };
+    if sections.is_empty() {
+        return Ok(ReviewedPatch {
+            action: None,
+            summary,
+        });
+    }
+
+    let patch = format!("*** Begin Patch\n{}*** End Patch", sections.concat());
+    let command = vec!["apply_patch".to_string(), patch];
+    match maybe_parse_apply_patch_verified(&command, &action.cwd) {
+        MaybeApplyPatchVerified::Body(reviewed) => Ok(ReviewedPatch {
+            action: Some(reviewed),
+            summary,
+        }),
+        MaybeApplyPatchVerified::CorrectnessError(err) => {
+            Err(format!("the reviewed patch no longer applies: {err}"))
+        }
+        MaybeApplyPatchVerified::ShellParseError(_) | MaybeApplyPatchVerified::NotApplyPatch => {
+            Err("the reviewed patch could not be parsed".to_string())
+        }
+    }
+}
+
+/// Returns `old` with every hunk of the `old` -> `new` diff resolved according
+/// to `decisions`; hunks without a decision are accepted.
+fn review_update(
+    old: &str,
+    new: &str,
+    decisions: &HashMap<usize, &HunkDecision>,
+    display: &str,
+    notes: &mut Vec<String>,
+) -> Result<String, String> {
+    let diff = TextDiff::from_lines(old, new);
+    let groups = diff.grouped_ops(REVIEW_CONTEXT_LINES);
+    if let Some(idx) = decisions.keys().find(|idx| **idx >= groups.len()) {
+        return Err(format!(
+            "{display} has no hunk {idx}; its diff has {} hunks",
+            groups.len()
+        ));
+    }
+
+    let old_lines = diff.old_slices();
+    let new_lines = diff.new_slices();
+    let mut reviewed = String::with_capacity(new.len());
+    let mut old_pos = 0;
+    for (idx, group) in groups.iter().enumerate() {
+        let (Some(first), Some(last)) = (group.first(), group.last()) else {
+            continue;
+        };
+        let old_range = first.old_range().start..last.old_range().end;
+        let new_range = first.new_range().start..last.new_range().end;
+        reviewed.extend(old_lines[old_pos..old_range.start].iter().copied());
+        let header = hunk_header(&old_range, &new_range);
+        match decisions.get(&idx) {
+            None | Some(HunkDecision::Accept) => {
+                reviewed.extend(new_lines[new_range].iter().copied());
+            }
+            Some(HunkDecision::Reject) => {
+                reviewed.extend(old_lines[old_range.clone()].iter().copied());
+                notes.push(format!(
+                    "- rejected hunk {} of {display} ({header})",
+                    idx + 1
+                ));
+            }
+            Some(HunkDecision::Edit { replacement }) => {
+                reviewed.push_str(replacement);
+                if !replacement.is_empty() && !replacement.ends_with('\n') {
+                    reviewed.push('\n');
+                }
+                notes.push(format!(
+                    "- edited hunk {} of {display} ({header}); those lines now read:\n{}",
+                    idx + 1,
+                    replacement.trim_end()
+                ));
+            }
+        }
+        old_pos = old_range.end;
+    }
+    reviewed.extend(old_lines[old_pos..].iter().copied());
+    Ok(reviewed)
+}
+
+/// Renders a hunk header the way the unified diff shown to the user does.
+fn hunk_header(old: &Range<usize>, new: &Range<usize>) -> String {
+    let start = |range: &Range<usize>| {
+        if range.is_empty() {
+            range.start
+        } else {
+            range.start + 1
+        }
+    };
+    format!(
+        "@@ -{},{} +{},{} @@",
+        start(old),
+        old.len(),
+        start(new),
+        new.len()
+    )
+}
+
+fn update_section(path: &Path, move_path: Option<&Path>, old: &str, new: &str) -> String {
+    let mut section = format!("*** Update File: {}\n", path.display());
+    if let Some(dest) = move_path {
+        section.push_str(&format!("*** Move to: {}\n", dest.display()));
+    }
+    let diff = TextDiff::from_lines(old, new);
+    let old_len = diff.old_slices().len();
+    for group in diff.grouped_ops(PATCH_CONTEXT_LINES) {
+        section.push_str("@@\n");
+        for op in &group {
+            for change in diff.iter_changes(op) {
+                let sign = match change.tag() {
+                    ChangeTag::Equal => ' ',
+                    ChangeTag::Delete => '-',
+                    ChangeTag::Insert => '+',
+                };
+                let line = change.value();
+                section.push(sign);
+                section.push_str(line.strip_suffix('\n').unwrap_or(line));
+                section.push('\n');
+            }
+        }
+        if group.last().is_some_and(|op| op.old_range().end == old_len) {
+            section.push_str("*** End of File\n");
+        }
+    }
+    section
+}
+
+fn add_section(path: &Path, content: &str) -> String {
+    let mut section = format!("*** Add File: {}\n", path.display());
+    for line in content.lines() {
+        section.push('+');
+        section.push_str(line);
+        section.push('\n');
+    }
+    section
+}
+
+fn mode_section(path: &Path, mode: u32) -> String {
+    format!(
+        "*** Chmod File: {}\n*** Mode: {:03o}\n",
+        path.display(),
+        mode & 0o777
+    )
+}
+
+fn whole_file_section(path: &Path, change: &ApplyPatchFileChange) -> String {
+    let display = path.display();
+    match change {
+        ApplyPatchFileChange::Add { content, mode } => {
+            let mut section = add_section(path, content);
+            if let Some(mode) = mode {
+                section.push_str(&mode_section(path, *mode));
+            }
+            section
+        }
+        ApplyPatchFileChange::Delete { .. } => format!("*** Delete File: {display}\n"),
+        ApplyPatchFileChange::Update { .. } => {
+            unreachable!("updates are rebuilt from their reviewed contents")
+        }
+        ApplyPatchFileChange::Binary {
+            old_size,
+            new_content,
+        } => {
+            let marker = if old_size.is_some() {
+                "*** Update Binary File: "
+            } else {
+                "*** Add Binary File: "
+            };
+            let encoded = BASE64_STANDARD.encode(new_content);
+            let mut section = format!("{marker}{display}\n");
+            for chunk in encoded.as_bytes().chunks(BASE64_LINE_WIDTH) {
+                section.push('+');
+                section.push_str(&String::from_utf8_lossy(chunk));
+                section.push('\n');
+            }
+            section
+        }
+        ApplyPatchFileChange::Symlink { target } => format!(
+            "*** Add Symlink: {display}\n*** Link to: {}\n",
+            target.display()
+        ),
+        ApplyPatchFileChange::Mode { new_mode, .. } => mode_section(path, *new_mode),
+    }
+}
+
+fn display_path(path: &Path, cwd: &Path) -> String {
+    path.strip_prefix(cwd).unwrap_or(path).display().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn verified(cwd: &Path, patch: &str) -> ApplyPatchAction {
+        let command = vec!["apply_patch".to_string(), patch.to_string()];
+        match maybe_parse_apply_patch_verified(&command, cwd) {
+            MaybeApplyPatchVerified::Body(action) => action,
+            other => panic!("expected a verified patch, got {other:?}"),
+        }
+    }
+
+    fn new_content(action: &ApplyPatchAction, path: &Path) -> String {
+        match action.changes().get(path) {
+            Some(ApplyPatchFileChange::Update { new_content, .. }) => new_content.clone(),
+            other => panic!("expected an update for {}, got {other:?}", path.display()),
+        }
+    }
+
+    fn carried_mode(action: &ApplyPatchAction, path: &Path) -> Option<u32> {
+        match action.changes().get(path) {
+            Some(
+                ApplyPatchFileChange::Add { mode, .. } | ApplyPatchFileChange::Update { mode, .. },
+            ) => *mode,
+            other => panic!(
+                "expected an add or update for {}, got {other:?}",
+                path.display()
+            ),
+        }
+    }
+
+    #[test]
+    fn mode_changes_follow_their_file_through_the_review() {
+        let dir = TempDir::new().expect("tempdir");
+        let script = dir.path().join("run.sh");
+        let added = dir.path().join("new.sh");
+        fs::write(&script, "echo hi\n").expect("write");
+        let action = verified(
+            dir.path(),
+            "*** Begin Patch\n*** Update File: run.sh\n@@\n-echo hi\n+echo hello\n*** Chmod File: run.sh\n*** Mode: 755\n*** Add File: new.sh\n+echo new\n*** Chmod File: new.sh\n*** Mode: 700\n*** End Patch",
+        );
+
+        let accepted = apply_hunk_review(
+            &action,
+            &[HunkReview {
+                path: added.clone(),
+                hunk: 0,
+                decision: HunkDecision::Edit {
+                    replacement: "echo edited\n".to_string(),
+                },
+            }],
+        )
+        .expect("review");
+        let accepted_action = accepted.action.expect("patch left to apply");
+        assert_eq!(carried_mode(&accepted_action, &script), Some(0o755));
+        assert_eq!(carried_mode(&accepted_action, &added), Some(0o700));
+
+        let rejected = apply_hunk_review(
+            &action,
+            &[HunkReview {
+                path: script.clone(),
+                hunk: 0,
+                decision: HunkDecision::Reject,
+            }],
+        )
+        .expect("review");
+        let rejected_action = rejected.action.expect("patch left to apply");
+        assert!(!rejected_action.changes().contains_key(&script));
+        assert_eq!(
+            rejected.summary,
+            "The user reviewed this patch hunk by hunk. Only the accepted and edited hunks were applied:\n\
+             - rejected hunk 1 of run.sh (@@ -1,1 +1,1 @@)\n\
+             - the mode of run.sh was not changed because none of its hunks were kept"
+        );
+    }
+
+    #[test]
+    fn rejected_and_edited_hunks_are_left_out_of_the_patch() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("lines.txt");
+        let original = (1..=20).map(|n| format!("line {n}\n")).collect::<String>();
+        fs::write(&path, &original).expect("write");
+        let action = verified(
+            dir.path(),
+            "*** Begin Patch\n*** Update File: lines.txt\n@@\n-line 2\n+line two\n@@\n-line 10\n+line ten\n@@\n-line 18\n+line eighteen\n*** End Patch",
+        );
+
+        let reviewed = apply_hunk_review(
+            &action,
+            &[
+                HunkReview {
+                    path: path.clone(),
+                    hunk: 1,
+                    decision: HunkDecision::Reject,
+                },
+                HunkReview {
+                    path: path.clone(),
+                    hunk: 2,
+                    decision: HunkDecision::Edit {
+                        replacement: "line 17\nline EIGHTEEN\nline 19\n".to_string(),
+                    },
+                },
+            ],
+        )
+        .expect("review");
+
+        let reviewed_action = reviewed.action.expect("patch left to apply");
+        assert_eq!(
+            new_content(&reviewed_action, &path),
+            original
+                .replace("line 2\n", "line two\n")
+                .replace("line 18\n", "line EIGHTEEN\n")
+        );
+        assert_eq!(
+            reviewed.summary,
+            "The user reviewed this patch hunk by hunk. Only the accepted and edited hunks were applied:\n\
+             - rejected hunk 2 of lines.txt (@@ -9,3 +9,3 @@)\n\
+             - edited hunk 3 of lines.txt (@@ -17,3 +17,3 @@); those lines now read:\n\
+             line 17\nline EIGHTEEN\nline 19"
+        );
+    }
+
+    #[test]
+    fn rejecting_everything_leaves_nothing_to_apply() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("new.txt");
+        let action = verified(
+            dir.path(),
+            "*** Begin Patch\n*** Add File: new.txt\n+hello\n*** End Patch",
+        );
+
+        let reviewed = apply_hunk_review(
+            &action,
+            &[HunkReview {
+                path,
+                hunk: 0,
+                decision: HunkDecision::Reject,
+            }],
+        )
+        .expect("review");
+
+        assert!(reviewed.action.is_none());
+        assert!(
+            reviewed
+                .summary
+                .contains("rejected the whole change to new.txt")
+        );
+    }
+
+    #[test]
+    fn unknown_hunks_are_reported() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("a.txt");
+        fs::write(&path, "one\ntwo\n").expect("write");
+        let action = verified(
+            dir.path(),
+            "*** Begin Patch\n*** Update File: a.txt\n@@\n-one\n+uno\n*** End Patch",
+        );
+
+        let err = apply_hunk_review(
+            &action,
+            &[HunkReview {
+                path,
+                hunk: 3,
+                decision: HunkDecision::Reject,
+            }],
+        )
+        .err()
+        .expect("unknown hunk");
+        assert_eq!(err, "a.txt has no hunk 3; its diff has 1 hunks");
+    }
+}
@@ -12,11 +12,22 @@
 //!     current working directory (inclusive) and concatenate their contents in
 //!     that order.
 //! 3.  We do **not** walk past the Git root.
+//!
+//! Docs in directories below the current working directory are loaded lazily:
+//! [`NestedProjectDocs`] tracks the directories the agent touches and picks up
+//! their docs, within what is left of `project_doc_max_bytes`.
 
+use crate::bash::parse_shell_lc_plain_commands;
 use crate::config::Config;
 use crate::skills::SkillMetadata;
 use crate::skills::render_skills_section;
+use crate::user_instructions::NestedUserInstructions;
+use codex_protocol::models::ResponseItem;
 use dunce::canonicalize as normalize_path;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
 use std::path::PathBuf;
 use tokio::io::AsyncReadExt;
 use tracing::error;
@@ -94,29 +105,12 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
             break;
         }
 
-        let file = match tokio::fs::File::open(&p).await {
-            Ok(f) => f,
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
-            Err(e) => return Err(e),
+        let Some((text, bytes_read)) = read_doc_within_budget(&p, remaining).await? else {
+            continue;
         };
-
-        let size = file.metadata().await?.len();
-        let mut reader = tokio::io::BufReader::new(file).take(remaining);
-        let mut data: Vec<u8> = Vec::new();
-        reader.read_to_end(&mut data).await?;
-
-        if size > remaining {
-            tracing::warn!(
-                "Project doc `{}` exceeds remaining budget ({} bytes) - truncating.",
-                p.display(),
-                remaining,
-            );
-        }
-
-        let text = String::from_utf8_lossy(&data).to_string();
         if !text.trim().is_empty() {
             parts.push(text);
-            remaining = remaining.saturating_sub(data.len() as u64);
+            remaining = remaining.saturating_sub(bytes_read);
         }
     }
 
@@ -127,12 +121,56 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
     }
 }
 
+/// Reads at most `remaining` bytes of the doc at `path`. Returns the text and
+/// the number of bytes read, or `None` when the file does not exist.
+async fn read_doc_within_budget(
+    path: &Path,
+    remaining: u64,
+) -> std::io::Result<Option<(String, u64)>> {
+    let file = match tokio::fs::File::open(path).await {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let size = file.metadata().await?.len();
+    let mut reader = tokio::io::BufReader::new(file).take(remaining);
+    let mut data: Vec<u8> = Vec::new();
+    reader.read_to_end(&mut data).await?;
+
+    if size > remaining {
+        tracing::warn!(
+            "Project doc `{}` exceeds remaining budget ({} bytes) - truncating.",
+            path.display(),
+            remaining,
+        );
+    }
+
+    let text = String::from_utf8_lossy(&data).to_string();
+    Ok(Some((text, data.len() as u64)))
+}
+
 /// Discover the list of AGENTS.md files using the same search rules as
 /// `read_project_docs`, but return the file paths instead of concatenated
 /// contents. The list is ordered from repository root to the current working
 /// directory (inclusive). Symlinks are allowed. When `project_doc_max_bytes`
 /// is zero, returns an empty list.
 pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBuf>> {
+    let candidate_filenames = candidate_filenames(config);
+    let mut found: Vec<PathBuf> = Vec::new();
+    for d in project_doc_search_dirs(config)? {
+        if let Some(doc) = find_doc_in_dir(&d, &candidate_filenames)? {
+            found.push(doc);
+        }
+    }
+
+    Ok(found)
+}
+
+/// Directories searched for project docs at startup, ordered from the Git
+/// root down to the current working directory (inclusive). Outside a Git
+/// repository only the current working directory is searched.
+fn project_doc_search_dirs(config: &Config) -> std::io::Result<Vec<PathBuf>> {
     let mut dir = config.cwd.clone();
     if let Ok(canon) = normalize_path(&dir) {
         dir = canon;
@@ -177,27 +215,26 @@ pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBu
         vec![config.cwd.clone()]
     };
 
-    let mut found: Vec<PathBuf> = Vec::new();
-    let candidate_filenames = candidate_filenames(config);
-    for d in search_dirs {
-        for name in &candidate_filenames {
-            let candidate = d.join(name);
-            match std::fs::symlink_metadata(&candidate) {
-                Ok(md) => {
-                    let ft = md.file_type();
-                    // Allow regular files and symlinks; opening will later fail for dangling links.
-                    if ft.is_file() || ft.is_symlink() {
-                        found.push(candidate);
-                        break;
-                    }
+    Ok(search_dirs)
+}
+
+/// Returns the first of `names` present in `dir`, if any.
+fn find_doc_in_dir(dir: &Path, names: &[&str]) -> std::io::Result<Option<PathBuf>> {
+    for name in names {
+        let candidate = dir.join(name);
+        match std::fs::symlink_metadata(&candidate) {
+            Ok(md) => {
+                let ft = md.file_type();
+                // Allow regular files and symlinks; opening will later fail for dangling links.
+                if ft.is_file() || ft.is_symlink() {
+                    return Ok(Some(candidate));
                 }
-                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
-                Err(e) => return Err(e),
             }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
         }
     }
-
-    Ok(found)
+    Ok(None)
 }
 
 fn candidate_filenames<'a>(config: &'a Config) -> Vec<&'a str> {
@@ -217,6 +254,218 @@ fn candidate_filenames<'a>(config: &'a Config) -> Vec<&'a str> {
     names
 }
 
+/// Tracks the directories the agent touches below the project root and loads
+/// the docs found there that were not part of the startup instructions.
+#[derive(Debug, Default)]
+pub(crate) struct NestedProjectDocs {
+    /// Git root (or cwd outside a repository); `None` disables lazy loading.
+    root: Option<PathBuf>,
+    candidate_filenames: Vec<String>,
+    /// Bytes of `project_doc_max_bytes` not yet used by loaded docs.
+    remaining: u64,
+    /// Directories already searched, including the startup chain.
+    searched: HashSet<PathBuf>,
+    /// Directories whose doc is in the history, with the doc's size.
+    loaded: HashMap<PathBuf, u64>,
+    /// Directories touched since docs were last collected.
+    pending: BTreeSet<PathBuf>,
+}
+
+/// Directories to search for nested docs, taken from [`NestedProjectDocs`] so
+/// the files can be read without holding the session state lock.
+#[derive(Debug)]
+pub(crate) struct NestedDocSearch {
+    dirs: Vec<PathBuf>,
+    candidate_filenames: Vec<String>,
+    remaining: u64,
+}
+
+impl NestedProjectDocs {
+    pub(crate) fn new(config: &Config) -> Self {
+        if config.project_doc_max_bytes == 0 {
+            return Self::default();
+        }
+        let search_dirs = match project_doc_search_dirs(config) {
+            Ok(dirs) => dirs,
+            Err(e) => {
+                error!("error trying to find project doc directories: {e:#}");
+                return Self::default();
+            }
+        };
+        let startup_bytes: u64 = discover_project_doc_paths(config)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|path| std::fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+
+        Self {
+            root: search_dirs.first().cloned(),
+            candidate_filenames: candidate_filenames(config)
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            remaining: (config.project_doc_max_bytes as u64).saturating_sub(startup_bytes),
+            searched: search_dirs.into_iter().collect(),
+            loaded: HashMap::new(),
+            pending: BTreeSet::new(),
+        }
+    }
+
+    /// Notes that the agent touched `path`, a file or a directory.
+    pub(crate) fn note_path(&mut self, path: &Path) {
+        let Some(root) = self.root.as_ref() else {
+            return;
+        };
+        let dir = if path.is_dir() {
+            path
+        } else {
+            match path.parent() {
+                Some(parent) => parent,
+                None => return,
+            }
+        };
+        let dir = normalize_path(dir).unwrap_or_else(|_| dir.to_path_buf());
+        if dir.starts_with(root) && !self.searched.contains(&dir) {
+            self.pending.insert(dir);
+        }
+    }
+
+    /// Marks the nested docs found in `history` as already part of the
+    /// conversation, as when a session is resumed.
+    pub(crate) fn mark_loaded_from_history(&mut self, history: &[ResponseItem]) {
+        for item in history {
+            if let ResponseItem::Message { role, content, .. } = item
+                && let Some((directory, text)) = NestedUserInstructions::parse(role, content)
+            {
+                self.mark_loaded(PathBuf::from(directory), text.len() as u64);
+            }
+        }
+    }
+
+    fn mark_loaded(&mut self, directory: PathBuf, bytes: u64) {
+        self.pending.remove(&directory);
+        self.searched.insert(directory.clone());
+        if self.loaded.insert(directory, bytes).is_none() {
+            self.remaining = self.remaining.saturating_sub(bytes);
+        }
+    }
+
+    /// Forgets the docs loaded so far, as when compaction drops them from the
+    /// history, and queues their directories to be searched again.
+    pub(crate) fn forget_loaded(&mut self) {
+        for (directory, bytes) in self.loaded.drain() {
+            self.remaining = self.remaining.saturating_add(bytes);
+            self.searched.remove(&directory);
+            self.pending.insert(directory);
+        }
+    }
+
+    /// Takes the directories touched since the last call that have not been
+    /// searched yet, along with their unsearched ancestors below the root.
+    pub(crate) fn take_search(&mut self) -> Option<NestedDocSearch> {
+        let root = self.root.as_ref()?;
+        if self.remaining == 0 {
+            self.pending.clear();
+            return None;
+        }
+
+        let mut dirs: BTreeSet<PathBuf> = BTreeSet::new();
+        for dir in std::mem::take(&mut self.pending) {
+            let mut cursor = Some(dir.as_path());
+            while let Some(current) = cursor.filter(|current| current.starts_with(root)) {
+                if self.searched.insert(current.to_path_buf()) {
+                    dirs.insert(current.to_path_buf());
+                }
+                cursor = current.parent();
+            }
+        }
+        if dirs.is_empty() {
+            return None;
+        }
+
+        // Parents first, so broader instructions precede more specific ones.
+        let mut dirs: Vec<PathBuf> = dirs.into_iter().collect();
+        dirs.sort_by_key(|dir| dir.components().count());
+        Some(NestedDocSearch {
+            dirs,
+            candidate_filenames: self.candidate_filenames.clone(),
+            remaining: self.remaining,
+        })
+    }
+
+    /// Subtracts the size of newly loaded docs from the remaining budget.
+    pub(crate) fn record_loaded(&mut self, docs: &[NestedUserInstructions]) {
+        for doc in docs {
+            self.mark_loaded(PathBuf::from(&doc.directory), doc.text.len() as u64);
+        }
+    }
+}
+
+/// Reads the docs found in the directories of `search`, within its budget.
+pub(crate) async fn read_nested_project_docs(
+    search: NestedDocSearch,
+) -> std::io::Result<Vec<NestedUserInstructions>> {
+    let NestedDocSearch {
+        dirs,
+        candidate_filenames,
+        mut remaining,
+    } = search;
+    let names: Vec<&str> = candidate_filenames.iter().map(String::as_str).collect();
+
+    let mut docs = Vec::new();
+    for dir in dirs {
+        if remaining == 0 {
+            break;
+        }
+        let Some(path) = find_doc_in_dir(&dir, &names)? else {
+            continue;
+        };
+        let Some((text, bytes_read)) = read_doc_within_budget(&path, remaining).await? else {
+            continue;
+        };
+        if !text.trim().is_empty() {
+            remaining = remaining.saturating_sub(bytes_read);
+            docs.push(NestedUserInstructions {
+                directory: dir.to_string_lossy().to_string(),
+                text,
+            });
+        }
+    }
+    Ok(docs)
+}
+
+/// Directories a shell command works in: `cwd`, plus the directory after
+/// every `cd` in a plain `bash -lc` script. `~` is expanded to the home
+/// directory; after a `cd` whose target cannot be resolved (`cd -`,
+/// `cd ~user`) later directories are unknown, so tracking stops there.
+pub(crate) fn shell_command_dirs(command: &[String], cwd: &Path) -> Vec<PathBuf> {
+    let mut visited = vec![cwd.to_path_buf()];
+    let mut current = cwd.to_path_buf();
+    for words in parse_shell_lc_plain_commands(command).unwrap_or_default() {
+        let [cd, target] = words.as_slice() else {
+            continue;
+        };
+        if cd != "cd" {
+            continue;
+        }
+        let target = match target.strip_prefix('~') {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+                let Some(home) = dirs::home_dir() else {
+                    break;
+                };
+                home.join(rest.trim_start_matches('/'))
+            }
+            Some(_) => break,
+            None if target == "-" => break,
+            None => PathBuf::from(target),
+        };
+        current = current.join(target);
+        visited.push(current.clone());
+    }
+    visited
+}
+
 fn merge_project_docs_with_skills(
     project_doc: Option<String>,
     skills_section: Option<String>,
@@ -423,6 +672,112 @@ mod tests {
         assert_eq!(res, "root doc\n\ncrate doc");
     }
 
+    /// Docs below the cwd are loaded once the agent touches their directory,
+    /// parents first, and only once until compaction drops them.
+    #[tokio::test]
+    async fn nested_docs_load_when_directory_is_touched() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            repo.path().join(".git"),
+            "gitdir: /path/to/actual/git/dir\n",
+        )
+        .unwrap();
+        fs::write(repo.path().join("AGENTS.md"), "root doc").unwrap();
+        let services = repo.path().join("services");
+        let foo = services.join("foo");
+        fs::create_dir_all(foo.join("src")).unwrap();
+        fs::write(services.join("AGENTS.md"), "services doc").unwrap();
+        fs::write(foo.join("AGENTS.md"), "foo doc").unwrap();
+
+        let cfg = make_config(&repo, 4096, None).await;
+        let mut nested = NestedProjectDocs::new(&cfg);
+        assert!(nested.take_search().is_none());
+
+        nested.note_path(&foo.join("src/lib.rs"));
+        let search = nested.take_search().expect("search expected");
+        let docs = read_nested_project_docs(search).await.unwrap();
+        nested.record_loaded(&docs);
+
+        let texts: Vec<&str> = docs.iter().map(|doc| doc.text.as_str()).collect();
+        assert_eq!(texts, vec!["services doc", "foo doc"]);
+
+        nested.note_path(&foo.join("src"));
+        nested.note_path(&foo.join("AGENTS.md"));
+        assert!(nested.take_search().is_none());
+        // Compaction drops the docs from the history, so they load again.
+        nested.forget_loaded();
+        let search = nested.take_search().expect("search after compaction");
+        let docs = read_nested_project_docs(search).await.unwrap();
+        let texts: Vec<&str> = docs.iter().map(|doc| doc.text.as_str()).collect();
+        assert_eq!(texts, vec!["services doc", "foo doc"]);
+    }
+
+    /// Nested docs share the `project_doc_max_bytes` budget with the startup
+    /// docs, and docs already restored from history are not loaded again.
+    #[tokio::test]
+    async fn nested_docs_respect_budget_and_restored_docs() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            repo.path().join(".git"),
+            "gitdir: /path/to/actual/git/dir\n",
+        )
+        .unwrap();
+        fs::write(repo.path().join("AGENTS.md"), "root doc").unwrap();
+        let a = repo.path().join("a");
+        let b = repo.path().join("b");
+        fs::create_dir_all(&a).unwrap();
+        fs::create_dir_all(&b).unwrap();
+        fs::write(a.join("AGENTS.md"), "a doc").unwrap();
+        fs::write(b.join("AGENTS.md"), "b doc that is long").unwrap();
+
+        let cfg = make_config(&repo, 12, None).await;
+        let mut nested = NestedProjectDocs::new(&cfg);
+        nested.mark_loaded(normalize_path(&a).unwrap(), 1);
+
+        nested.note_path(&a);
+        nested.note_path(&b);
+        let search = nested.take_search().expect("search expected");
+        let docs = read_nested_project_docs(search).await.unwrap();
+
+        let texts: Vec<&str> = docs.iter().map(|doc| doc.text.as_str()).collect();
+        assert_eq!(texts, vec!["b d"]);
+    }
+
+    #[test]
+    fn shell_command_dirs_include_cd_targets() {
+        let cwd = Path::new("/repo");
+        let command = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "cd services/foo && cargo test".to_string(),
+        ];
+        assert_eq!(
+            shell_command_dirs(&command, cwd),
+            vec![PathBuf::from("/repo"), PathBuf::from("/repo/services/foo")]
+        );
+
+        let command = vec!["ls".to_string()];
+        assert_eq!(
+            shell_command_dirs(&command, cwd),
+            vec![PathBuf::from("/repo")]
+        );
+        let command = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "cd services && cd foo && cd ~ && cd - && cd bar".to_string(),
+        ];
+        let home = dirs::home_dir().expect("home dir");
+        assert_eq!(
+            shell_command_dirs(&command, cwd),
+            vec![
+                PathBuf::from("/repo"),
+                PathBuf::from("/repo/services"),
+                PathBuf::from("/repo/services/foo"),
+                home,
+            ]
+        );
+    }
+
     /// AGENTS.override.md is preferred over AGENTS.md when both are present.
     #[tokio::test]
     async fn agents_local_md_preferred() {
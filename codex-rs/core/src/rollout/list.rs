@@ -18,6 +18,7 @@ use crate::protocol::EventMsg;
 use codex_file_search as file_search;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SessionMeta;
 use codex_protocol::protocol::SessionSource;
 
 /// Returned page of conversation summaries.
@@ -39,6 +40,7 @@ pub struct ConversationItem {
     /// Absolute path to the rollout file.
     pub path: PathBuf,
     /// First up to `HEAD_RECORD_LIMIT` JSONL records parsed as JSON (includes meta line).
+    /// The meta line carries the latest generated title and summary, if any.
     pub head: Vec<serde_json::Value>,
     /// RFC3339 timestamp string for when the session was created, if available.
     pub created_at: Option<String>,
@@ -60,6 +62,9 @@ struct HeadTailSummary {
 /// Hard cap to bound worst‑case work per request.
 const MAX_SCAN_FILES: usize = 10000;
 const HEAD_RECORD_LIMIT: usize = 10;
+/// How far back from the end of a rollout to look for a refreshed title. The
+/// recorder repeats the title line often enough to keep it in this window.
+pub(crate) const TITLE_TAIL_BYTES: u64 = 64 * 1024;
 
 /// Pagination cursor identifying a file by timestamp and UUID.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -221,11 +226,12 @@ async fn traverse_directories_for_paths(
                     // Apply filters: must have session meta and at least one user message event
                     if summary.saw_session_meta && summary.saw_user_event {
                         let HeadTailSummary {
-                            head,
+                            mut head,
                             created_at,
                             mut updated_at,
                             ..
                         } = summary;
+                        apply_latest_title(&mut head, &path).await;
                         if updated_at.is_none() {
                             updated_at = file_modified_rfc3339(&path)
                                 .await
@@ -441,10 +447,63 @@ async fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadTai
 /// Read up to `HEAD_RECORD_LIMIT` records from the start of the rollout file at `path`.
 /// This should be enough to produce a summary including the session meta line.
 pub async fn read_head_for_summary(path: &Path) -> io::Result<Vec<serde_json::Value>> {
-    let summary = read_head_summary(path, HEAD_RECORD_LIMIT).await?;
+    let mut summary = read_head_summary(path, HEAD_RECORD_LIMIT).await?;
+    if summary.saw_session_meta {
+        apply_latest_title(&mut summary.head, path).await;
+    }
     Ok(summary.head)
 }
 
+/// Copies the title and summary of the latest titled `session_meta` line onto
+/// the meta line in `head`.
+async fn apply_latest_title(head: &mut [serde_json::Value], path: &Path) {
+    let Some(latest) = read_latest_titled_meta(path).await.unwrap_or_default() else {
+        return;
+    };
+    let Some(serde_json::Value::Object(meta)) = head.first_mut() else {
+        return;
+    };
+    for (key, value) in [("title", latest.title), ("summary", latest.summary)] {
+        if let Some(value) = value {
+            meta.insert(key.to_string(), serde_json::Value::String(value));
+        }
+    }
+}
+
+/// Reads the last [`TITLE_TAIL_BYTES`] of the rollout and returns the most
+/// recent `session_meta` line in it that carries a title.
+pub(crate) async fn read_latest_titled_meta(path: &Path) -> io::Result<Option<SessionMeta>> {
+    use tokio::io::AsyncReadExt;
+    use tokio::io::AsyncSeekExt;
+
+    let mut file = tokio::fs::File::open(path).await?;
+    let len = file.metadata().await?.len();
+    let start = len.saturating_sub(TITLE_TAIL_BYTES);
+    file.seek(io::SeekFrom::Start(start)).await?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail).await?;
+
+    let mut lines = tail.split(|byte| *byte == b'\n');
+    if start > 0 {
+        // The window most likely starts in the middle of a line.
+        lines.next();
+    }
+    Ok(lines.rev().find_map(titled_meta_from_line))
+}
+
+fn titled_meta_from_line(line: &[u8]) -> Option<SessionMeta> {
+    let line = std::str::from_utf8(line).ok()?;
+    if !line.contains("\"session_meta\"") {
+        return None;
+    }
+    match serde_json::from_str::<RolloutLine>(line).ok()?.item {
+        RolloutItem::SessionMeta(meta_line) if meta_line.meta.title.is_some() => {
+            Some(meta_line.meta)
+        }
+        _ => None,
+    }
+}
+
 async fn file_modified_rfc3339(path: &Path) -> io::Result<Option<String>> {
     let meta = tokio::fs::metadata(path).await?;
     let modified = meta.modified().ok();
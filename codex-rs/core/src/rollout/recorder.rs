@@ -21,7 +21,10 @@ use tracing::warn;
 use super::SESSIONS_SUBDIR;
 use super::list::ConversationsPage;
 use super::list::Cursor;
+use super::list::TITLE_TAIL_BYTES;
 use super::list::get_conversations;
+use super::list::read_head_for_summary;
+use super::list::read_latest_titled_meta;
 use super::policy::is_persisted_response_item;
 use crate::config::Config;
 use crate::default_client::originator;
@@ -63,6 +66,11 @@ pub enum RolloutRecorderParams {
 
 enum RolloutCmd {
     AddItems(Vec<RolloutItem>),
+    /// Append a copy of the session meta carrying a new title and summary.
+    SetTitle {
+        title: String,
+        summary: Option<String>,
+    },
     /// Ensure all prior writes are processed; respond when flushed.
     Flush {
         ack: oneshot::Sender<()>,
@@ -148,6 +156,8 @@ impl RolloutRecorder {
                         instructions,
                         source,
                         model_provider: Some(config.model_provider_id.clone()),
+                        title: None,
+                        summary: None,
                     }),
                 )
             }
@@ -160,6 +170,18 @@ impl RolloutRecorder {
                 None,
             ),
         };
+        // Title updates repeat the session meta, which a resumed rollout only
+        // has on its first line.
+        let base_meta = match &meta {
+            Some(meta) => Some(meta.clone()),
+            None => read_session_meta_line(&rollout_path)
+                .await
+                .map(|line| line.meta),
+        };
+        let title = match &meta {
+            Some(_) => None,
+            None => read_latest_titled_meta(&rollout_path).await.ok().flatten(),
+        };
 
         // Clone the cwd for the spawned task to collect git info asynchronously
         let cwd = config.cwd.clone();
@@ -172,7 +194,7 @@ impl RolloutRecorder {
         // Spawn a Tokio task that owns the file handle and performs async
         // writes. Using `tokio::fs::File` keeps everything on the async I/O
         // driver instead of blocking the runtime.
-        tokio::task::spawn(rollout_writer(file, rx, meta, cwd));
+        tokio::task::spawn(rollout_writer(file, rx, meta, base_meta, title, cwd));
 
         Ok(Self { tx, rollout_path })
     }
@@ -196,6 +218,19 @@ impl RolloutRecorder {
             .map_err(|e| IoError::other(format!("failed to queue rollout items: {e}")))
     }
 
+    /// Record a generated title and summary by appending a `session_meta`
+    /// line that repeats the session meta with the new values.
+    pub(crate) async fn record_session_title(
+        &self,
+        title: String,
+        summary: Option<String>,
+    ) -> std::io::Result<()> {
+        self.tx
+            .send(RolloutCmd::SetTitle { title, summary })
+            .await
+            .map_err(|e| IoError::other(format!("failed to queue session title: {e}")))
+    }
+
     /// Flush all queued writes and wait until they are committed by the writer task.
     pub async fn flush(&self) -> std::io::Result<()> {
         let (tx, rx) = oneshot::channel();
@@ -350,9 +385,16 @@ async fn rollout_writer(
     file: tokio::fs::File,
     mut rx: mpsc::Receiver<RolloutCmd>,
     mut meta: Option<SessionMeta>,
+    base_meta: Option<SessionMeta>,
+    title: Option<SessionMeta>,
     cwd: std::path::PathBuf,
 ) -> std::io::Result<()> {
-    let mut writer = JsonlWriter { file };
+    let mut writer = JsonlWriter { file, written: 0 };
+    // Latest title line and where it was last written. Listings only read the
+    // tail of the rollout, so the line is repeated before it falls out of it.
+    // A title found on resume is repeated with the first new items.
+    let mut title_line = title.map(|meta| SessionMetaLine { meta, git: None });
+    let mut title_written_at: Option<u64> = None;
 
     // If we have a meta, collect git info asynchronously and write meta first
     if let Some(session_meta) = meta.take() {
@@ -372,11 +414,40 @@ async fn rollout_writer(
     while let Some(cmd) = rx.recv().await {
         match cmd {
             RolloutCmd::AddItems(items) => {
+                let before = writer.written;
                 for item in items {
                     if is_persisted_response_item(&item) {
                         writer.write_rollout_item(item).await?;
                     }
                 }
+                if let Some(title_line) = &title_line
+                    && writer.written > before
+                    && title_written_at.is_none_or(|at| writer.written - at > TITLE_TAIL_BYTES / 2)
+                {
+                    writer
+                        .write_rollout_item(RolloutItem::SessionMeta(title_line.clone()))
+                        .await?;
+                    title_written_at = Some(writer.written);
+                }
+            }
+            RolloutCmd::SetTitle { title, summary } => {
+                let Some(base_meta) = base_meta.as_ref() else {
+                    warn!("cannot record session title without session meta");
+                    continue;
+                };
+                let session_meta_line = SessionMetaLine {
+                    meta: SessionMeta {
+                        title: Some(title),
+                        summary,
+                        ..base_meta.clone()
+                    },
+                    git: None,
+                };
+                writer
+                    .write_rollout_item(RolloutItem::SessionMeta(session_meta_line.clone()))
+                    .await?;
+                title_line = Some(session_meta_line);
+                title_written_at = Some(writer.written);
             }
             RolloutCmd::Flush { ack } => {
                 // Ensure underlying file is flushed and then ack.
@@ -395,8 +466,16 @@ async fn rollout_writer(
     Ok(())
 }
 
+async fn read_session_meta_line(path: &Path) -> Option<SessionMetaLine> {
+    let head = read_head_for_summary(path).await.ok()?;
+    let first = head.into_iter().next()?;
+    serde_json::from_value(first).ok()
+}
+
 struct JsonlWriter {
     file: tokio::fs::File,
+    /// Bytes written through this writer.
+    written: u64,
 }
 
 impl JsonlWriter {
@@ -419,6 +498,7 @@ impl JsonlWriter {
         json.push('\n');
         self.file.write_all(json.as_bytes()).await?;
         self.file.flush().await?;
+        self.written += json.len() as u64;
         Ok(())
     }
 }
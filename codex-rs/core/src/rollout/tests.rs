@@ -7,10 +7,14 @@ use std::io::Write;
 use std::path::Path;
 
 use crate::rollout::INTERACTIVE_SESSION_SOURCES;
+use crate::rollout::RolloutRecorder;
+use crate::rollout::RolloutRecorderParams;
 use crate::rollout::list::ConversationItem;
 use crate::rollout::list::ConversationsPage;
 use crate::rollout::list::Cursor;
+use crate::rollout::list::TITLE_TAIL_BYTES;
 use crate::rollout::list::get_conversations;
+use crate::rollout::list::read_head_for_summary;
 use anyhow::Result;
 use codex_protocol::ConversationId;
 use codex_protocol::models::ContentItem;
@@ -636,6 +640,8 @@ async fn test_updated_at_uses_file_mtime() -> Result<()> {
                 cli_version: "test_version".into(),
                 source: SessionSource::VSCode,
                 model_provider: Some("test-provider".into()),
+                title: None,
+                summary: None,
             },
             git: None,
         }),
@@ -717,6 +723,8 @@ async fn test_tail_handles_short_sessions() -> Result<()> {
                 cli_version: "test_version".into(),
                 source: SessionSource::VSCode,
                 model_provider: Some("test-provider".into()),
+                title: None,
+                summary: None,
             },
             git: None,
         }),
@@ -816,6 +824,8 @@ async fn test_tail_skips_trailing_non_responses() -> Result<()> {
                 cli_version: "test_version".into(),
                 source: SessionSource::VSCode,
                 model_provider: Some("test-provider".into()),
+                title: None,
+                summary: None,
             },
             git: None,
         }),
@@ -1171,3 +1181,90 @@ async fn test_model_provider_filter_selects_only_matching_sessions() -> Result<(
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_listing_uses_latest_session_title() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path();
+    let ts = "2025-09-02T12-00-00";
+    let uuid = Uuid::from_u128(42);
+    write_session_file(home, ts, uuid, 1, Some(SessionSource::Cli))?;
+
+    let page = get_conversations(home, 10, None, NO_SOURCE_FILTER, None, TEST_PROVIDER).await?;
+    let path = page.items[0].path.clone();
+    assert_eq!(page.items[0].head[0].get("title"), None);
+
+    let mut file = fs::OpenOptions::new().append(true).open(&path)?;
+    for (title, summary) in [("First title", "Early summary"), ("Fix listing", "Later")] {
+        let line = RolloutLine {
+            timestamp: ts.to_string(),
+            item: RolloutItem::SessionMeta(SessionMetaLine {
+                meta: SessionMeta {
+                    id: ConversationId::from_string(&uuid.to_string())?,
+                    title: Some(title.to_string()),
+                    summary: Some(summary.to_string()),
+                    ..SessionMeta::default()
+                },
+                git: None,
+            }),
+        };
+        writeln!(file, "{}", serde_json::to_string(&line)?)?;
+    }
+    writeln!(file, "{}", serde_json::json!({"record_type": "response"}))?;
+
+    let page = get_conversations(home, 10, None, NO_SOURCE_FILTER, None, TEST_PROVIDER).await?;
+    let meta = &page.items[0].head[0];
+    assert_eq!(meta["title"], "Fix listing");
+    assert_eq!(meta["summary"], "Later");
+    assert_eq!(meta["cwd"], ".");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn recorder_keeps_session_title_in_the_listed_tail() -> Result<()> {
+    let home = TempDir::new().unwrap();
+    let mut config = crate::config::test_config();
+    config.codex_home = home.path().to_path_buf();
+    let filler: Vec<RolloutItem> = (0..3 * TITLE_TAIL_BYTES / 1024)
+        .map(|_| {
+            RolloutItem::ResponseItem(ResponseItem::Message {
+                id: None,
+                role: "assistant".into(),
+                content: vec![ContentItem::OutputText {
+                    text: "x".repeat(1024),
+                }],
+                thought_signature: None,
+            })
+        })
+        .collect();
+
+    let recorder = RolloutRecorder::new(
+        &config,
+        RolloutRecorderParams::new(ConversationId::new(), None, SessionSource::Cli),
+    )
+    .await?;
+    recorder
+        .record_session_title("Fix listing".to_string(), Some("Later".to_string()))
+        .await?;
+    for item in &filler {
+        recorder.record_items(std::slice::from_ref(item)).await?;
+    }
+    recorder.flush().await?;
+    let path = recorder.rollout_path.clone();
+    let head = read_head_for_summary(&path).await?;
+    assert_eq!(head[0]["title"], "Fix listing");
+    assert_eq!(head[0]["summary"], "Later");
+
+    // A resumed session repeats the title it finds in the tail.
+    let resumed =
+        RolloutRecorder::new(&config, RolloutRecorderParams::resume(path.clone())).await?;
+    for item in &filler {
+        resumed.record_items(std::slice::from_ref(item)).await?;
+    }
+    resumed.flush().await?;
+    let head = read_head_for_summary(&path).await?;
+    assert_eq!(head[0]["title"], "Fix listing");
+
+    Ok(())
+}
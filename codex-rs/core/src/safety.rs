@@ -189,11 +189,25 @@ fn is_write_patch_constrained_to_writable_paths(
 
     for (path, change) in action.changes() {
         match change {
-            ApplyPatchFileChange::Add { .. } | ApplyPatchFileChange::Delete { .. } => {
+            ApplyPatchFileChange::Add { .. }
+            | ApplyPatchFileChange::Delete { .. }
+            | ApplyPatchFileChange::Binary { .. }
+            | ApplyPatchFileChange::Mode { .. } => {
                 if !is_path_writable(path) {
                     return false;
                 }
             }
+            ApplyPatchFileChange::Symlink { target } => {
+                if !is_path_writable(path) {
+                    return false;
+                }
+                // Writes through the link land on its target, so the target
+                // must be writable too.
+                let link_dir = path.parent().unwrap_or(path);
+                if !is_path_writable(&link_dir.join(target)) {
+                    return false;
+                }
+            }
             ApplyPatchFileChange::Update { move_path, .. } => {
                 if !is_path_writable(path) {
                     return false;
@@ -266,6 +280,41 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn symlink_target_must_be_writable() {
+        let tmp = TempDir::new().unwrap();
+        let cwd = tmp.path().to_path_buf();
+        let policy_workspace_only = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+        let symlink_action = |target: &str| {
+            let argv = vec![
+                "apply_patch".to_string(),
+                format!(
+                    "*** Begin Patch\n*** Add Symlink: link\n*** Link to: {target}\n*** End Patch"
+                ),
+            ];
+            match codex_apply_patch::maybe_parse_apply_patch_verified(&argv, &cwd) {
+                codex_apply_patch::MaybeApplyPatchVerified::Body(action) => action,
+                other => panic!("expected a verified patch, got {other:?}"),
+            }
+        };
+
+        assert!(is_write_patch_constrained_to_writable_paths(
+            &symlink_action("src/main.rs"),
+            &policy_workspace_only,
+            &cwd,
+        ));
+        assert!(!is_write_patch_constrained_to_writable_paths(
+            &symlink_action("../outside.txt"),
+            &policy_workspace_only,
+            &cwd,
+        ));
+    }
+
     #[test]
     fn external_sandbox_auto_approves_in_on_request() {
         let tmp = TempDir::new().unwrap();
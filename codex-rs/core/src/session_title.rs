@@ -0,0 +1,263 @@
+//! Generated session titles and summaries.
+//!
+//! After the first and third user turn, and after every compaction, the
+//! conversation is sent to the `title` model from `[models.tasks]` (or the
+//! session's own model). The answer is appended to the rollout as a new
+//! `session_meta` line, which session listings read back.
+
+use std::sync::Arc;
+
+use codex_protocol::items::TurnItem;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_utils_tokenizer::Tokenizer;
+use futures::prelude::*;
+use tracing::warn;
+
+use crate::Prompt;
+use crate::client::ModelClient;
+use crate::client_common::ResponseEvent;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::codex::get_last_assistant_message_from_turn;
+use crate::compact::collect_user_messages;
+use crate::compact::content_items_to_text;
+use crate::config::types::AuxiliaryTask;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::event_mapping::parse_turn_item;
+use crate::features::Feature;
+use crate::rollout::INTERACTIVE_SESSION_SOURCES;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+pub(crate) const SESSION_TITLE_PROMPT: &str = include_str!("../templates/session_title/prompt.md");
+
+/// User turns after which a title is generated. Later turns keep it until the
+/// next compaction.
+const TITLE_AFTER_USER_TURNS: [usize; 2] = [1, 3];
+const MESSAGE_MAX_TOKENS: usize = 400;
+/// Messages from the end of the conversation sent along with the first one.
+const RECENT_MESSAGES: usize = 20;
+const TITLE_MAX_CHARS: usize = 80;
+const SUMMARY_MAX_CHARS: usize = 600;
+
+/// Refreshes the title when the conversation has just reached one of
+/// [`TITLE_AFTER_USER_TURNS`].
+pub(crate) async fn refresh_after_turn(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) {
+    let history = sess.clone_history().await.get_history();
+    if TITLE_AFTER_USER_TURNS.contains(&collect_user_messages(&history).len()) {
+        refresh_session_title(sess, turn_context);
+    }
+}
+
+/// Generates a title and summary in the background and records them in the
+/// rollout. Only interactive sessions, which are the ones listed for resume,
+/// get a title.
+pub(crate) fn refresh_session_title(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) {
+    if !sess.enabled(Feature::SessionTitles)
+        || !INTERACTIVE_SESSION_SOURCES.contains(&turn_context.client.get_session_source())
+    {
+        return;
+    }
+    let sess = Arc::clone(sess);
+    let turn_context = Arc::clone(turn_context);
+    tokio::spawn(async move {
+        match generate_session_title(&sess, &turn_context).await {
+            Ok(Some((title, summary))) => sess.record_session_title(title, summary).await,
+            Ok(None) => warn!("session title response did not contain a title"),
+            Err(err) => warn!("failed to generate session title: {err}"),
+        }
+    });
+}
+
+async fn generate_session_title(
+    sess: &Session,
+    turn_context: &TurnContext,
+) -> CodexResult<Option<(String, Option<String>)>> {
+    let history = sess.clone_history().await.get_history();
+    let transcript = render_transcript(&history, turn_context.tokenizer);
+    if transcript.is_empty() {
+        return Ok(None);
+    }
+
+    let client = sess
+        .new_task_turn(turn_context, &AuxiliaryTask::Title)
+        .await
+        .map(|turn| turn.client.clone())
+        .unwrap_or_else(|| turn_context.client.clone());
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText { text: transcript }],
+            thought_signature: None,
+        }],
+        base_instructions_override: Some(SESSION_TITLE_PROMPT.to_string()),
+        ..Default::default()
+    };
+    let output = collect_output(&client, &prompt).await?;
+    Ok(get_last_assistant_message_from_turn(&output).and_then(|text| parse_title_response(&text)))
+}
+
+/// Streams `prompt` without touching the session's history or token usage.
+async fn collect_output(client: &ModelClient, prompt: &Prompt) -> CodexResult<Vec<ResponseItem>> {
+    let mut stream = client.stream(prompt).await?;
+    let mut output = Vec::new();
+    while let Some(event) = stream.next().await {
+        match event? {
+            ResponseEvent::OutputItemDone(item) => output.push(item),
+            ResponseEvent::Completed { .. } => return Ok(output),
+            _ => {}
+        }
+    }
+    Err(CodexErr::Stream(
+        "stream closed before response.completed".into(),
+        None,
+    ))
+}
+
+/// User and assistant messages as plain text: the first message, which
+/// usually states the task, and the most recent ones.
+fn render_transcript(history: &[ResponseItem], tokenizer: Tokenizer) -> String {
+    let mut messages: Vec<String> = history
+        .iter()
+        .filter_map(|item| {
+            let (speaker, text) = match item {
+                ResponseItem::Message { role, content, .. } if role == "assistant" => {
+                    ("Assistant", content_items_to_text(content)?)
+                }
+                _ => match parse_turn_item(item)? {
+                    TurnItem::UserMessage(user) => ("User", user.message()),
+                    _ => return None,
+                },
+            };
+            let text = truncate_text(
+                text.trim(),
+                TruncationPolicy::Tokens(MESSAGE_MAX_TOKENS),
+                tokenizer,
+            );
+            Some(format!("{speaker}: {text}"))
+        })
+        .collect();
+    if messages.len() > RECENT_MESSAGES + 1 {
+        messages.drain(1..messages.len() - RECENT_MESSAGES);
+    }
+    messages.join("\n\n")
+}
+
+/// Reads the `Title:` and `Summary:` lines of the model's answer. A first line
+/// without a label is taken as the title.
+fn parse_title_response(text: &str) -> Option<(String, Option<String>)> {
+    let mut title: Option<String> = None;
+    let mut summary: Option<String> = None;
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        if let Some(rest) = strip_label(line, "title") {
+            title = Some(rest);
+        } else if let Some(rest) = strip_label(line, "summary") {
+            summary = Some(rest);
+        } else if let Some(summary) = summary.as_mut() {
+            summary.push(' ');
+            summary.push_str(line);
+        } else if title.is_none() {
+            title = Some(line.to_string());
+        }
+    }
+
+    let title = clean(&title?, TITLE_MAX_CHARS)
+        .trim_end_matches('.')
+        .to_string();
+    if title.is_empty() {
+        return None;
+    }
+    let summary = summary
+        .map(|summary| clean(&summary, SUMMARY_MAX_CHARS))
+        .filter(|summary| !summary.is_empty());
+    Some((title, summary))
+}
+
+/// The text after `label:`, ignoring case and Markdown emphasis or headings.
+fn strip_label(line: &str, label: &str) -> Option<String> {
+    let (head, rest) = line.trim_start_matches(['*', '#', ' ']).split_once(':')?;
+    head.trim_end_matches('*')
+        .eq_ignore_ascii_case(label)
+        .then(|| rest.trim().trim_start_matches('*').trim().to_string())
+}
+
+/// Collapses whitespace, drops surrounding quotes and caps the length.
+fn clean(text: &str, max_chars: usize) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let text = text.trim_matches(['"', '\'', '`', '*']).trim();
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        let text = text.to_string();
+        let content = if role == "assistant" {
+            ContentItem::OutputText { text }
+        } else {
+            ContentItem::InputText { text }
+        };
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![content],
+            thought_signature: None,
+        }
+    }
+
+    #[test]
+    fn parses_labelled_lines() {
+        let parsed = parse_title_response(
+            "**Title:** \"Fix resume picker search.\"\nSummary: The user wants titles\nin the picker.",
+        );
+        assert_eq!(
+            parsed,
+            Some((
+                "Fix resume picker search".to_string(),
+                Some("The user wants titles in the picker.".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn takes_unlabelled_first_line_as_title() {
+        assert_eq!(
+            parse_title_response("Port tokenizer to tiktoken\n"),
+            Some(("Port tokenizer to tiktoken".to_string(), None))
+        );
+        assert_eq!(parse_title_response("Title:   \n"), None);
+        assert_eq!(parse_title_response(""), None);
+    }
+
+    #[test]
+    fn caps_long_titles() {
+        let (title, _) =
+            parse_title_response(&format!("Title: {}", "word ".repeat(40))).expect("title");
+        assert_eq!(title.chars().count(), TITLE_MAX_CHARS);
+        assert!(title.ends_with('…'));
+    }
+
+    #[test]
+    fn transcript_keeps_first_and_recent_messages() {
+        let mut history = vec![message("user", "first task")];
+        for idx in 0..30 {
+            history.push(message("assistant", &format!("reply {idx}")));
+        }
+        let transcript = render_transcript(&history, Tokenizer::Approximate);
+        let lines: Vec<&str> = transcript.split("\n\n").collect();
+        assert_eq!(lines.len(), RECENT_MESSAGES + 1);
+        assert_eq!(lines[0], "User: first task");
+        assert_eq!(lines[1], "Assistant: reply 10");
+        assert_eq!(lines.last().copied(), Some("Assistant: reply 29"));
+    }
+}
@@ -3,6 +3,8 @@ use std::sync::Arc;
 use crate::AuthManager;
 use crate::RolloutRecorder;
 use crate::delegate_tool::DelegateToolAdapter;
+use crate::file_snapshots::FileSnapshotStore;
+use crate::lsp::LspManager;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::openai_models::models_manager::ModelsManager;
 use crate::skills::SkillsManager;
@@ -28,4 +30,6 @@ pub(crate) struct SessionServices {
     pub(crate) tool_approvals: Mutex<ApprovalStore>,
     pub(crate) delegate_adapter: Option<Arc<dyn DelegateToolAdapter>>,
     pub(crate) skills_manager: Arc<SkillsManager>,
+    pub(crate) lsp_manager: Arc<LspManager>,
+    pub(crate) file_snapshots: Arc<FileSnapshotStore>,
 }
@@ -1,11 +1,14 @@
 //! Session-wide mutable state.
 
 use codex_protocol::models::ResponseItem;
+use codex_utils_tokenizer::Tokenizer;
 
 use crate::client::GeminiAspectRatio;
 use crate::client::GeminiImageSize;
 use crate::codex::SessionConfiguration;
+use crate::codex::TurnContext;
 use crate::context_manager::ContextManager;
+use crate::project_doc::NestedProjectDocs;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
@@ -24,6 +27,10 @@ pub(crate) struct SessionState {
     pub(crate) image_size: Option<GeminiImageSize>,
     /// Aspect ratio for Gemini image generation.
     pub(crate) aspect_ratio: Option<GeminiAspectRatio>,
+    /// AGENTS.md files below the cwd, loaded as the agent touches directories.
+    pub(crate) nested_project_docs: NestedProjectDocs,
+    /// Messages the user pinned so structured compaction keeps them verbatim.
+    pub(crate) pinned_messages: Vec<String>,
 }
 
 impl SessionState {
@@ -37,16 +44,22 @@ impl SessionState {
             active_reference_images: Vec::new(),
             image_size: None,
             aspect_ratio: None,
+            nested_project_docs: NestedProjectDocs::default(),
+            pinned_messages: Vec::new(),
         }
     }
 
     // History helpers
-    pub(crate) fn record_items<I>(&mut self, items: I, policy: TruncationPolicy)
-    where
+    pub(crate) fn record_items<I>(
+        &mut self,
+        items: I,
+        policy: TruncationPolicy,
+        tokenizer: Tokenizer,
+    ) where
         I: IntoIterator,
         I::Item: std::ops::Deref<Target = ResponseItem>,
     {
-        self.history.record_items(items, policy);
+        self.history.record_items(items, policy, tokenizer);
     }
 
     pub(crate) fn clone_history(&self) -> ContextManager {
@@ -95,6 +108,12 @@ impl SessionState {
         self.history.get_total_token_usage()
     }
 
+    /// Estimates the tokens of the history, reusing the counts of items
+    /// counted by earlier calls.
+    pub(crate) fn estimate_token_count(&mut self, turn_context: &TurnContext) -> Option<i64> {
+        self.history.estimate_token_count(turn_context)
+    }
+
     pub(crate) fn set_reference_images(&mut self, images: Vec<String>) {
         self.active_reference_images = images;
     }
@@ -107,6 +126,16 @@ impl SessionState {
         &self.active_reference_images
     }
 
+    pub(crate) fn pin_message(&mut self, message: String) {
+        if !self.pinned_messages.contains(&message) {
+            self.pinned_messages.push(message);
+        }
+    }
+
+    pub(crate) fn unpin_message(&mut self, message: &str) {
+        self.pinned_messages.retain(|pinned| pinned != message);
+    }
+
     // Image generation config helpers
 
     pub(crate) fn set_image_size(&mut self, size: Option<GeminiImageSize>) {
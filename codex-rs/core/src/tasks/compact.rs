@@ -25,14 +25,13 @@ impl SessionTask for CompactTask {
         _cancellation_token: CancellationToken,
     ) -> Option<String> {
         let session = session.clone_session();
-        if crate::compact::should_use_remote_compact_task(
-            session.as_ref(),
-            &ctx.client.get_provider(),
-        ) {
-            crate::compact_remote::run_remote_compact_task(session, ctx).await
+        if crate::compact::should_use_remote_compact_task(session.as_ref(), ctx.as_ref()) {
+            crate::compact_remote::run_remote_compact_task(Arc::clone(&session), Arc::clone(&ctx))
+                .await
         } else {
-            crate::compact::run_compact_task(session, ctx, input).await
+            crate::compact::run_compact_task(Arc::clone(&session), Arc::clone(&ctx), input).await
         }
+        crate::session_title::refresh_session_title(&session, &ctx);
 
         None
     }
@@ -1,4 +1,9 @@
+use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::features::Feature;
+use crate::file_snapshots::FileSnapshotStore;
+use crate::file_snapshots::snapshot_roots;
+use crate::git_info::get_git_repo_root;
 use crate::protocol::EventMsg;
 use crate::protocol::WarningEvent;
 use crate::state::TaskKind;
@@ -6,6 +11,8 @@ use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
 use async_trait::async_trait;
 use codex_git::CreateGhostCommitOptions;
+use codex_git::GhostCommit;
+use codex_git::GhostSnapshotConfig;
 use codex_git::GhostSnapshotReport;
 use codex_git::GitToolingError;
 use codex_git::create_ghost_commit_with_report;
@@ -13,12 +20,15 @@ use codex_protocol::models::ResponseItem;
 use codex_protocol::user_input::UserInput;
 use codex_utils_readiness::Readiness;
 use codex_utils_readiness::Token;
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::oneshot;
 use tokio_util::sync::CancellationToken;
 use tracing::info;
 use tracing::warn;
+use uuid::Uuid;
 
 pub(crate) struct GhostSnapshotTask {
     token: Token,
@@ -77,13 +87,18 @@ impl SessionTask for GhostSnapshotTask {
                 _ = cancellation_token.cancelled() => true,
                 _ = async {
                     let repo_path = ctx_for_task.cwd.clone();
+                    let file_roots = snapshot_roots(&repo_path, &ctx_for_task.sandbox_policy);
+                    let file_snapshots = Arc::clone(&session.session.services.file_snapshots);
                     let ghost_snapshot = ctx_for_task.ghost_snapshot.clone();
                     let ghost_snapshot_for_commit = ghost_snapshot.clone();
                     // Required to run in a dedicated blocking pool.
                     match tokio::task::spawn_blocking(move || {
-                        let options =
-                            CreateGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot_for_commit);
-                        create_ghost_commit_with_report(&options)
+                        capture_snapshot(
+                            &repo_path,
+                            &file_roots,
+                            &file_snapshots,
+                            ghost_snapshot_for_commit,
+                        )
                     })
                     .await
                     {
@@ -162,6 +177,75 @@ impl GhostSnapshotTask {
     }
 }
 
+/// Records which files changed since the latest file snapshot so undo only
+/// restores what the turn touched. Called when a regular turn ends.
+pub(crate) async fn record_file_snapshot_changes(session: &Session) {
+    if !session.enabled(Feature::GhostCommit) {
+        return;
+    }
+    let Some(id) = session
+        .clone_history()
+        .await
+        .get_history()
+        .iter()
+        .rev()
+        .find_map(|item| match item {
+            ResponseItem::GhostSnapshot { ghost_commit } => Some(ghost_commit.id().to_string()),
+            _ => None,
+        })
+    else {
+        return;
+    };
+    let file_snapshots = Arc::clone(&session.services.file_snapshots);
+    let result = tokio::task::spawn_blocking(move || {
+        if file_snapshots.contains(&id) {
+            file_snapshots.record_changes(&id)
+        } else {
+            Ok(())
+        }
+    })
+    .await;
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(err)) => warn!("failed to record file snapshot changes: {err}"),
+        Err(err) => warn!("recording file snapshot changes panicked: {err}"),
+    }
+}
+
+/// Captures a ghost commit when the cwd is inside a Git repository and a file
+/// snapshot of every workspace root that is not. Both are stored under the same
+/// id so undo can restore them together.
+fn capture_snapshot(
+    repo_path: &Path,
+    file_roots: &[PathBuf],
+    file_snapshots: &FileSnapshotStore,
+    ghost_snapshot: GhostSnapshotConfig,
+) -> Result<(GhostCommit, GhostSnapshotReport), GitToolingError> {
+    if get_git_repo_root(repo_path).is_none() {
+        if file_roots.is_empty() {
+            return Err(GitToolingError::NotAGitRepository {
+                path: repo_path.to_path_buf(),
+            });
+        }
+        let id = Uuid::new_v4().to_string();
+        let report = file_snapshots.capture(&id, file_roots, &ghost_snapshot)?;
+        return Ok((GhostCommit::new(id, None, Vec::new(), Vec::new()), report));
+    }
+
+    let options = CreateGhostCommitOptions::new(repo_path).ghost_snapshot(ghost_snapshot.clone());
+    let (ghost_commit, mut report) = create_ghost_commit_with_report(&options)?;
+    if !file_roots.is_empty() {
+        let extra = file_snapshots.capture(ghost_commit.id(), file_roots, &ghost_snapshot)?;
+        report
+            .large_untracked_dirs
+            .extend(extra.large_untracked_dirs);
+        report
+            .ignored_untracked_files
+            .extend(extra.ignored_untracked_files);
+    }
+    Ok((ghost_commit, report))
+}
+
 fn format_snapshot_warnings(
     ignore_large_untracked_files: Option<i64>,
     ignore_large_untracked_dirs: Option<i64>,
@@ -250,7 +334,6 @@ mod tests {
     use super::*;
     use codex_git::LargeUntrackedDir;
     use pretty_assertions::assert_eq;
-    use std::path::PathBuf;
 
     #[test]
     fn large_untracked_warning_includes_threshold() {
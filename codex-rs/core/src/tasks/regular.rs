@@ -2,7 +2,9 @@ use std::sync::Arc;
 
 use crate::codex::TurnContext;
 use crate::codex::run_task;
+use crate::session_title;
 use crate::state::TaskKind;
+use crate::tasks::ghost_snapshot::record_file_snapshot_changes;
 use async_trait::async_trait;
 use codex_protocol::user_input::UserInput;
 use tokio_util::sync::CancellationToken;
@@ -31,8 +33,20 @@ impl SessionTask for RegularTask {
         let sess = session.clone_session();
         let run_task_span =
             trace_span!(parent: sess.services.otel_manager.current_span(), "run_task");
-        run_task(sess, ctx, input, cancellation_token)
-            .instrument(run_task_span)
-            .await
+        let last_agent_message = run_task(
+            Arc::clone(&sess),
+            Arc::clone(&ctx),
+            input,
+            cancellation_token,
+        )
+        .instrument(run_task_span)
+        .await;
+        record_file_snapshot_changes(&sess).await;
+        session_title::refresh_after_turn(&sess, &ctx).await;
+        last_agent_message
+    }
+
+    async fn abort(&self, session: Arc<SessionTaskContext>, _ctx: Arc<TurnContext>) {
+        record_file_snapshot_changes(&session.clone_session()).await;
     }
 }
@@ -16,6 +16,7 @@ use tokio_util::sync::CancellationToken;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::codex_delegate::run_codex_conversation_one_shot;
+use crate::config::types::AuxiliaryTask;
 use crate::review_format::format_review_findings_block;
 use crate::review_format::render_review_output_text;
 use crate::state::TaskKind;
@@ -91,7 +92,9 @@ async fn start_review_conversation(
     // Set explicit review rubric for the sub-agent
     sub_agent_config.base_instructions = Some(crate::REVIEW_PROMPT.to_string());
 
-    sub_agent_config.model = Some(config.review_model.clone());
+    if !sub_agent_config.apply_task_model(&AuxiliaryTask::Review) {
+        sub_agent_config.model = Some(config.review_model.clone());
+    }
     (run_codex_conversation_one_shot(
         sub_agent_config,
         session.auth_manager(),
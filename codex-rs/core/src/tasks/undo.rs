@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use crate::codex::TurnContext;
+use crate::git_info::get_git_repo_root;
 use crate::protocol::EventMsg;
 use crate::protocol::UndoCompletedEvent;
 use crate::protocol::UndoStartedEvent;
@@ -8,6 +9,7 @@ use crate::state::TaskKind;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
 use async_trait::async_trait;
+use codex_git::GitToolingError;
 use codex_git::RestoreGhostCommitOptions;
 use codex_git::restore_ghost_commit_with_options;
 use codex_protocol::models::ResponseItem;
@@ -87,9 +89,22 @@ impl SessionTask for UndoTask {
         let commit_id = ghost_commit.id().to_string();
         let repo_path = ctx.cwd.clone();
         let ghost_snapshot = ctx.ghost_snapshot.clone();
-        let restore_result = tokio::task::spawn_blocking(move || {
-            let options = RestoreGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot);
-            restore_ghost_commit_with_options(&options, &ghost_commit)
+        let file_snapshots = Arc::clone(&sess.services.file_snapshots);
+        let restore_result = tokio::task::spawn_blocking(move || -> Result<(), GitToolingError> {
+            // Snapshots taken outside a Git repository only exist in the file
+            // snapshot store; ones taken inside may have extra roots stored there.
+            let in_git_repo = get_git_repo_root(&repo_path).is_some();
+            if in_git_repo {
+                let options =
+                    RestoreGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot);
+                restore_ghost_commit_with_options(&options, &ghost_commit)?;
+            }
+            if file_snapshots.contains(ghost_commit.id()) {
+                file_snapshots.restore(ghost_commit.id())?;
+            } else if !in_git_repo {
+                return Err(GitToolingError::NotAGitRepository { path: repo_path });
+            }
+            Ok(())
         })
         .await;
 
@@ -179,6 +179,7 @@ impl SessionTask for UserShellCommandTask {
                             formatted_output: format_exec_output_str(
                                 &output,
                                 turn_context.truncation_policy,
+                                turn_context.tokenizer,
                             ),
                         }),
                     )
@@ -224,6 +225,7 @@ impl SessionTask for UserShellCommandTask {
                             formatted_output: format_exec_output_str(
                                 &exec_output,
                                 turn_context.truncation_policy,
+                                turn_context.tokenizer,
                             ),
                         }),
                     )
@@ -67,6 +67,11 @@ pub(crate) async fn emit_exec_command_begin(
     interaction_input: Option<String>,
     process_id: Option<&str>,
 ) {
+    for parsed in parsed_cmd {
+        if let ParsedCommand::Read { path, .. } = parsed {
+            codex_file_search::record_recent_file(&cwd.join(path));
+        }
+    }
     ctx.session
         .send_event(
             ctx.turn,
@@ -178,6 +183,9 @@ impl ToolEmitter {
                     let mut guard = tracker.lock().await;
                     guard.on_patch_begin(changes);
                 }
+                for path in changes.keys() {
+                    codex_file_search::record_recent_file(&ctx.turn.cwd.join(path));
+                }
                 ctx.session
                     .send_event(
                         ctx.turn,
@@ -264,10 +272,16 @@ impl ToolEmitter {
         ctx: ToolEventCtx<'_>,
     ) -> String {
         match self {
-            Self::Shell { freeform: true, .. } => {
-                super::format_exec_output_for_model_freeform(output, ctx.turn.truncation_policy)
-            }
-            _ => super::format_exec_output_for_model_structured(output, ctx.turn.truncation_policy),
+            Self::Shell { freeform: true, .. } => super::format_exec_output_for_model_freeform(
+                output,
+                ctx.turn.truncation_policy,
+                ctx.turn.tokenizer,
+            ),
+            _ => super::format_exec_output_for_model_structured(
+                output,
+                ctx.turn.truncation_policy,
+                ctx.turn.tokenizer,
+            ),
         }
     }
 
@@ -383,7 +397,11 @@ async fn emit_exec_stage(
                 aggregated_output: output.aggregated_output.text.clone(),
                 exit_code: output.exit_code,
                 duration: output.duration,
-                formatted_output: format_exec_output_str(&output, ctx.turn.truncation_policy),
+                formatted_output: format_exec_output_str(
+                    &output,
+                    ctx.turn.truncation_policy,
+                    ctx.turn.tokenizer,
+                ),
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
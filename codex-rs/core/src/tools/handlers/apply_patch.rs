@@ -1,5 +1,6 @@
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::path::PathBuf;
 
 use crate::apply_patch;
 use crate::apply_patch::InternalApplyPatchInvocation;
@@ -11,6 +12,9 @@ use crate::client_common::tools::ToolSpec;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
+use crate::lsp::DiagnosticsByPath;
+use crate::lsp::format_diagnostics;
+use crate::lsp::new_diagnostics;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
@@ -26,6 +30,8 @@ use crate::tools::sandboxing::ToolCtx;
 use crate::tools::spec::ApplyPatchToolArgs;
 use crate::tools::spec::JsonSchema;
 use async_trait::async_trait;
+use codex_apply_patch::ApplyPatchAction;
+use codex_apply_patch::ApplyPatchFileChange;
 
 pub struct ApplyPatchHandler;
 
@@ -81,6 +87,7 @@ impl ToolHandler for ApplyPatchHandler {
         let command = vec!["apply_patch".to_string(), patch_input.clone()];
         match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &cwd) {
             codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
+                session.note_touched_paths(changes.changes().keys()).await;
                 match apply_patch::apply_patch(session.as_ref(), turn.as_ref(), &call_id, changes)
                     .await
                 {
@@ -113,6 +120,7 @@ impl ToolHandler for ApplyPatchHandler {
                             codex_exe: turn.codex_linux_sandbox_exe.clone(),
                         };
 
+                        let baseline = diagnostics_baseline(session.as_ref(), &apply.action).await;
                         let mut orchestrator = ToolOrchestrator::new();
                         let mut runtime = ApplyPatchRuntime::new();
                         let tool_ctx = ToolCtx {
@@ -131,6 +139,16 @@ impl ToolHandler for ApplyPatchHandler {
                             Some(&tracker),
                         );
                         let content = emitter.finish(event_ctx, out).await?;
+                        let content = append_new_diagnostics(
+                            session.as_ref(),
+                            turn.as_ref(),
+                            Some(&tracker),
+                            &apply.action,
+                            baseline,
+                            content,
+                        )
+                        .await;
+                        let content = append_hunk_review(content, apply.hunk_review_summary);
                         Ok(ToolOutput::Function {
                             content,
                             content_items: None,
@@ -172,6 +190,7 @@ pub(crate) async fn intercept_apply_patch(
 ) -> Result<Option<ToolOutput>, FunctionCallError> {
     match codex_apply_patch::maybe_parse_apply_patch_verified(command, cwd) {
         codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
+            session.note_touched_paths(changes.changes().keys()).await;
             session
                 .record_model_warning(
                     format!("apply_patch was requested via {tool_name}. Use the apply_patch tool instead of exec_command."),
@@ -204,6 +223,7 @@ pub(crate) async fn intercept_apply_patch(
                         codex_exe: turn.codex_linux_sandbox_exe.clone(),
                     };
 
+                    let baseline = diagnostics_baseline(session, &apply.action).await;
                     let mut orchestrator = ToolOrchestrator::new();
                     let mut runtime = ApplyPatchRuntime::new();
                     let tool_ctx = ToolCtx {
@@ -218,6 +238,16 @@ pub(crate) async fn intercept_apply_patch(
                     let event_ctx =
                         ToolEventCtx::new(session, turn, call_id, tracker.as_ref().copied());
                     let content = emitter.finish(event_ctx, out).await?;
+                    let content = append_new_diagnostics(
+                        session,
+                        turn,
+                        tracker,
+                        &apply.action,
+                        baseline,
+                        content,
+                    )
+                    .await;
+                    let content = append_hunk_review(content, apply.hunk_review_summary);
                     Ok(Some(ToolOutput::Function {
                         content,
                         content_items: None,
@@ -239,6 +269,83 @@ pub(crate) async fn intercept_apply_patch(
     }
 }
 
+/// Files written by `action` that some configured language server handles.
+fn lsp_targets(session: &Session, action: &ApplyPatchAction) -> Vec<PathBuf> {
+    let lsp = &session.services.lsp_manager;
+    if !lsp.is_enabled() {
+        return Vec::new();
+    }
+    let mut targets: Vec<PathBuf> = Vec::new();
+    for (path, change) in action.changes() {
+        targets.push(path.clone());
+        if let ApplyPatchFileChange::Update {
+            move_path: Some(dest),
+            ..
+        } = change
+        {
+            targets.push(dest.clone());
+        }
+    }
+    targets.retain(|path| lsp.handles(path));
+    targets
+}
+
+/// Diagnostics for the files `action` is about to write, collected before the
+/// patch is applied.
+async fn diagnostics_baseline(session: &Session, action: &ApplyPatchAction) -> DiagnosticsByPath {
+    let targets = lsp_targets(session, action);
+    if targets.is_empty() {
+        return DiagnosticsByPath::new();
+    }
+    session.services.lsp_manager.baseline(&targets).await
+}
+
+/// Pushes the files touched this turn to the configured language servers and
+/// appends any errors or warnings the patch introduced to `content`.
+async fn append_new_diagnostics(
+    session: &Session,
+    turn: &TurnContext,
+    tracker: Option<&SharedTurnDiffTracker>,
+    action: &ApplyPatchAction,
+    before: DiagnosticsByPath,
+    mut content: String,
+) -> String {
+    let targets = lsp_targets(session, action);
+    if targets.is_empty() {
+        return content;
+    }
+    let lsp = &session.services.lsp_manager;
+
+    let mut paths = match tracker {
+        Some(tracker) => tracker.lock().await.touched_paths(),
+        None => Vec::new(),
+    };
+    paths.extend(targets.iter().cloned());
+    paths.sort();
+    paths.dedup();
+
+    let mut after = lsp.refresh(&paths).await;
+    after.retain(|path, _| targets.contains(path));
+    let fresh = new_diagnostics(&before, after);
+    if fresh.is_empty() {
+        return content;
+    }
+
+    content.push_str("\n\nNew diagnostics reported by language servers after this edit:\n");
+    content.push_str(&format_diagnostics(&fresh, &turn.cwd).join("\n"));
+    content
+}
+
+/// Tells the model which hunks the user rejected or edited before the patch
+/// was applied.
+fn append_hunk_review(mut content: String, summary: Option<String>) -> String {
+    if let Some(summary) = summary {
+        content.push_str("\n\n");
+        content.push_str(&summary);
+    }
+    content
+}
+
 /// Returns a custom tool that can be used to edit files. Well-suited for GPT-5 models
 /// https://platform.openai.com/docs/guides/function-calling#custom-tools
 pub(crate) fn create_apply_patch_freeform_tool() -> ToolSpec {
@@ -274,13 +381,20 @@ Your patch language is a stripped‑down, file‑oriented diff format designed t
 
 Within that envelope, you get a sequence of file operations.
 You MUST include a header to specify the action you are taking.
-Each operation starts with one of three headers:
+Each text file operation starts with one of three headers:
 
 *** Add File: <path> - create a new file. Every following line is a + line (the initial contents).
 *** Delete File: <path> - remove an existing file. Nothing follows.
 *** Update File: <path> - patch an existing file in place (optionally with a rename).
 
 May be immediately followed by *** Move to: <new path> if you want to rename the file.
+
+Files that are not plain text use these headers instead:
+
+*** Add Binary File: <path> - create a binary file. Every following line is a + line holding base64 (standard alphabet, padded).
+*** Update Binary File: <path> - replace the full contents of an existing binary file, in the same format.
+*** Add Symlink: <path> - create a symbolic link. Must be followed by *** Link to: <target>.
+*** Chmod File: <path> - change permissions of an existing file. Must be followed by *** Mode: <octal mode>, e.g. 755 or 644.
 Then one or more “hunks”, each introduced by @@ (optionally followed by a hunk header).
 Within a hunk each line starts with:
 
@@ -306,11 +420,15 @@ The full grammar definition is below:
 Patch := Begin { FileOp } End
 Begin := "*** Begin Patch" NEWLINE
 End := "*** End Patch" NEWLINE
-FileOp := AddFile | DeleteFile | UpdateFile
+FileOp := AddFile | DeleteFile | UpdateFile | AddBinaryFile | UpdateBinaryFile | AddSymlink | ChmodFile
 AddFile := "*** Add File: " path NEWLINE { "+" line NEWLINE }
 DeleteFile := "*** Delete File: " path NEWLINE
 UpdateFile := "*** Update File: " path NEWLINE [ MoveTo ] { Hunk }
 MoveTo := "*** Move to: " newPath NEWLINE
+AddBinaryFile := "*** Add Binary File: " path NEWLINE { "+" base64 NEWLINE }
+UpdateBinaryFile := "*** Update Binary File: " path NEWLINE { "+" base64 NEWLINE }
+AddSymlink := "*** Add Symlink: " path NEWLINE "*** Link to: " target NEWLINE
+ChmodFile := "*** Chmod File: " path NEWLINE "*** Mode: " mode NEWLINE
 Hunk := "@@" [ header ] NEWLINE { HunkLine } [ "*** End of File" NEWLINE ]
 HunkLine := (" " | "-" | "+") text NEWLINE
 
@@ -329,7 +447,7 @@ A full patch can combine several operations:
 
 It is important to remember:
 
-- You must include a header with your intended action (Add/Delete/Update)
+- You must include a header with your intended action (Add/Delete/Update, or one of the binary, symlink and chmod headers)
 - You must prefix new lines with `+` even when creating a new file
 - File references can only be relative, NEVER ABSOLUTE.
 "#
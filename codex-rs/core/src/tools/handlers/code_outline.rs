@@ -0,0 +1,480 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ignore::WalkBuilder;
+use serde::Deserialize;
+use tokio::time::timeout;
+
+use crate::code_outline::OutlineLanguage;
+use crate::code_outline::SymbolKind;
+use crate::code_outline::flatten_symbols;
+use crate::code_outline::outline_source;
+use crate::code_outline::render_outline;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct CodeOutlineHandler;
+
+pub struct FindSymbolHandler;
+
+const DEFAULT_DEPTH: usize = 3;
+const DEFAULT_FILE_LIMIT: usize = 50;
+const DEFAULT_SYMBOL_LIMIT: usize = 50;
+const MAX_SYMBOL_LIMIT: usize = 500;
+/// Files larger than this are skipped; they are almost always generated.
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+/// Upper bound on files parsed by a single `find_symbol` call.
+const MAX_SCANNED_FILES: usize = 5_000;
+const TOOL_TIMEOUT: Duration = Duration::from_secs(30);
+const INDENTATION_SPACES: usize = 2;
+
+fn default_depth() -> usize {
+    DEFAULT_DEPTH
+}
+
+fn default_file_limit() -> usize {
+    DEFAULT_FILE_LIMIT
+}
+
+fn default_symbol_limit() -> usize {
+    DEFAULT_SYMBOL_LIMIT
+}
+
+#[derive(Deserialize)]
+struct CodeOutlineArgs {
+    path: String,
+    #[serde(default = "default_depth")]
+    depth: usize,
+    #[serde(default = "default_file_limit")]
+    limit: usize,
+}
+
+#[derive(Deserialize)]
+struct FindSymbolArgs {
+    name: String,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    kind: Option<String>,
+    #[serde(default)]
+    substring: bool,
+    #[serde(default = "default_symbol_limit")]
+    limit: usize,
+}
+
+/// Normalized `find_symbol` query.
+#[derive(Debug, Clone)]
+struct SymbolQuery {
+    /// Name with `::`/`#` scope separators rewritten to `.`.
+    name: String,
+    kind: Option<SymbolKind>,
+    substring: bool,
+    limit: usize,
+}
+
+impl SymbolQuery {
+    fn new(name: &str, kind: Option<SymbolKind>, substring: bool, limit: usize) -> Self {
+        let name = normalize_scope(name);
+        Self {
+            name: if substring { name.to_lowercase() } else { name },
+            kind,
+            substring,
+            limit,
+        }
+    }
+
+    /// Cheap pre-filter so only files mentioning the name get parsed.
+    fn may_match_source(&self, source: &str) -> bool {
+        let leaf = self.name.rsplit('.').next().unwrap_or(&self.name);
+        if self.substring {
+            source.to_lowercase().contains(leaf)
+        } else {
+            source.contains(leaf)
+        }
+    }
+
+    fn matches(&self, kind: SymbolKind, qualified_name: &str) -> bool {
+        match self.kind {
+            Some(filter) if filter != kind => return false,
+            // Impl blocks are not definitions; only report them on request.
+            None if kind == SymbolKind::Impl => return false,
+            _ => {}
+        }
+        let qualified_name = normalize_scope(qualified_name);
+        if self.substring {
+            qualified_name.to_lowercase().contains(&self.name)
+        } else {
+            qualified_name == self.name || qualified_name.ends_with(&format!(".{}", self.name))
+        }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for CodeOutlineHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation { payload, turn, .. } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "code_outline handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: CodeOutlineArgs = serde_json::from_str(&arguments).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to parse function arguments: {err:?}"
+            ))
+        })?;
+
+        if args.depth == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "depth must be greater than zero".to_string(),
+            ));
+        }
+        if args.limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+        let path = args.path.trim();
+        if path.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "path must not be empty".to_string(),
+            ));
+        }
+
+        let path = turn.resolve_path(Some(path.to_string()));
+        let cwd = turn.cwd.clone();
+        let depth = args.depth;
+        let limit = args.limit;
+        let lines = run_blocking(move || outline_path(&path, &cwd, depth, limit)).await?;
+
+        Ok(ToolOutput::Function {
+            content: lines.join("\n"),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+#[async_trait]
+impl ToolHandler for FindSymbolHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation { payload, turn, .. } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "find_symbol handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: FindSymbolArgs = serde_json::from_str(&arguments).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to parse function arguments: {err:?}"
+            ))
+        })?;
+
+        let name = args.name.trim();
+        if name.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "name must not be empty".to_string(),
+            ));
+        }
+        if args.limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+        let kind = match args.kind.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(label) => Some(SymbolKind::from_label(label).ok_or_else(|| {
+                FunctionCallError::RespondToModel(format!("unknown symbol kind `{label}`"))
+            })?),
+        };
+
+        let query = SymbolQuery::new(name, kind, args.substring, args.limit.min(MAX_SYMBOL_LIMIT));
+        let path = turn.resolve_path(args.path);
+        let cwd = turn.cwd.clone();
+        let results = run_blocking(move || find_definitions(&path, &cwd, &query)).await?;
+
+        if results.is_empty() {
+            Ok(ToolOutput::Function {
+                content: "No matching symbols found.".to_string(),
+                content_items: None,
+                success: Some(false),
+            })
+        } else {
+            Ok(ToolOutput::Function {
+                content: results.join("\n"),
+                content_items: None,
+                success: Some(true),
+            })
+        }
+    }
+}
+
+async fn run_blocking<F>(task: F) -> Result<Vec<String>, FunctionCallError>
+where
+    F: FnOnce() -> Result<Vec<String>, String> + Send + 'static,
+{
+    timeout(TOOL_TIMEOUT, tokio::task::spawn_blocking(task))
+        .await
+        .map_err(|_| {
+            FunctionCallError::RespondToModel("symbol scan timed out after 30 seconds".to_string())
+        })?
+        .map_err(|err| FunctionCallError::RespondToModel(format!("symbol scan failed: {err}")))?
+        .map_err(FunctionCallError::RespondToModel)
+}
+
+fn outline_path(
+    path: &Path,
+    cwd: &Path,
+    depth: usize,
+    limit: usize,
+) -> Result<Vec<String>, String> {
+    let metadata = std::fs::metadata(path)
+        .map_err(|err| format!("unable to access `{}`: {err}", path.display()))?;
+
+    if metadata.is_file() {
+        let language = OutlineLanguage::from_path(path)
+            .ok_or_else(|| format!("unsupported language for `{}`", path.display()))?;
+        let source = read_source(path)?;
+        let symbols = outline_source(language, &source)
+            .ok_or_else(|| format!("failed to parse `{}`", path.display()))?;
+        if symbols.is_empty() {
+            return Ok(vec!["No symbols found.".to_string()]);
+        }
+        return Ok(render_outline(&symbols, depth, 0));
+    }
+
+    let (files, truncated) = source_files(path, limit);
+    let mut lines = Vec::new();
+    for (file, language) in &files {
+        let Ok(source) = read_source(file) else {
+            continue;
+        };
+        let Some(symbols) = outline_source(*language, &source) else {
+            continue;
+        };
+        if symbols.is_empty() {
+            continue;
+        }
+        lines.push(display_path(file, cwd));
+        lines.extend(render_outline(&symbols, depth, INDENTATION_SPACES));
+    }
+
+    if lines.is_empty() {
+        lines.push("No symbols found.".to_string());
+    }
+    if truncated {
+        lines.push(format!(
+            "Outlined the first {limit} source files; narrow `path` or raise `limit` to see more."
+        ));
+    }
+    Ok(lines)
+}
+
+fn find_definitions(path: &Path, cwd: &Path, query: &SymbolQuery) -> Result<Vec<String>, String> {
+    let metadata = std::fs::metadata(path)
+        .map_err(|err| format!("unable to access `{}`: {err}", path.display()))?;
+    let files = if metadata.is_file() {
+        OutlineLanguage::from_path(path)
+            .map(|language| vec![(path.to_path_buf(), language)])
+            .unwrap_or_default()
+    } else {
+        source_files(path, MAX_SCANNED_FILES).0
+    };
+
+    let mut results = Vec::new();
+    for (file, language) in &files {
+        let Ok(source) = read_source(file) else {
+            continue;
+        };
+        if !query.may_match_source(&source) {
+            continue;
+        }
+        let Some(symbols) = outline_source(*language, &source) else {
+            continue;
+        };
+        let display = display_path(file, cwd);
+        for entry in flatten_symbols(*language, &symbols) {
+            if !query.matches(entry.symbol.kind, &entry.qualified_name) {
+                continue;
+            }
+            if results.len() == query.limit {
+                results.push(format!(
+                    "More than {} definitions found; refine `name`, `kind` or `path`.",
+                    query.limit
+                ));
+                return Ok(results);
+            }
+            results.push(format!(
+                "{display}:{}-{} {} {}  {}",
+                entry.symbol.start_line,
+                entry.symbol.end_line,
+                entry.symbol.kind.label(),
+                entry.qualified_name,
+                entry.symbol.signature
+            ));
+        }
+    }
+    Ok(results)
+}
+
+/// Collects supported source files under `root` in path order, honoring
+/// ignore files. Returns at most `limit` files and whether more were found.
+fn source_files(root: &Path, limit: usize) -> (Vec<(PathBuf, OutlineLanguage)>, bool) {
+    let mut files: Vec<(PathBuf, OutlineLanguage)> = WalkBuilder::new(root)
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_some_and(|ty| ty.is_file()))
+        .filter(|entry| {
+            entry
+                .metadata()
+                .is_ok_and(|metadata| metadata.len() <= MAX_FILE_BYTES)
+        })
+        .filter_map(|entry| {
+            let language = OutlineLanguage::from_path(entry.path())?;
+            Some((entry.into_path(), language))
+        })
+        .collect();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    let truncated = files.len() > limit;
+    files.truncate(limit);
+    (files, truncated)
+}
+
+fn read_source(path: &Path) -> Result<String, String> {
+    let bytes =
+        std::fs::read(path).map_err(|err| format!("failed to read `{}`: {err}", path.display()))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn display_path(path: &Path, cwd: &Path) -> String {
+    path.strip_prefix(cwd).unwrap_or(path).display().to_string()
+}
+
+fn normalize_scope(name: &str) -> String {
+    name.replace("::", ".").replace('#', ".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    const LIB_RS: &str = "pub struct Config {\n    name: String,\n}\n\nimpl Config {\n    pub fn new() -> Self {\n        todo!()\n    }\n}\n\npub fn new() {}\n";
+    const APP_PY: &str = "class App:\n    def new(self):\n        pass\n";
+
+    fn write_fixture(dir: &Path) {
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/lib.rs"), LIB_RS).unwrap();
+        std::fs::write(dir.join("app.py"), APP_PY).unwrap();
+        std::fs::write(dir.join("notes.txt"), "fn new() {}\n").unwrap();
+    }
+
+    #[test]
+    fn outlines_directory_grouped_by_file() {
+        let temp = tempdir().unwrap();
+        write_fixture(temp.path());
+
+        let lines = outline_path(temp.path(), temp.path(), DEFAULT_DEPTH, 10).unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                "app.py",
+                "  class App [1-3]",
+                "    method new [2-3]",
+                "src/lib.rs",
+                "  struct Config [1-3]",
+                "  impl Config [5-9]",
+                "    method new [6-8]",
+                "  fn new [11-11]",
+            ]
+        );
+    }
+
+    #[test]
+    fn outline_respects_depth_and_file_limit() {
+        let temp = tempdir().unwrap();
+        write_fixture(temp.path());
+
+        let lines = outline_path(temp.path(), temp.path(), 1, 1).unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                "app.py".to_string(),
+                "  class App [1-3]".to_string(),
+                "Outlined the first 1 source files; narrow `path` or raise `limit` to see more."
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn outline_rejects_unsupported_file() {
+        let temp = tempdir().unwrap();
+        write_fixture(temp.path());
+
+        let err = outline_path(&temp.path().join("notes.txt"), temp.path(), 1, 1).unwrap_err();
+        assert!(err.starts_with("unsupported language"), "{err}");
+    }
+
+    #[test]
+    fn finds_definitions_by_bare_and_qualified_name() {
+        let temp = tempdir().unwrap();
+        write_fixture(temp.path());
+
+        let bare = SymbolQuery::new("new", None, false, 10);
+        assert_eq!(
+            find_definitions(temp.path(), temp.path(), &bare).unwrap(),
+            vec![
+                "app.py:2-3 method App.new  def new(self)",
+                "src/lib.rs:6-8 method Config::new  pub fn new() -> Self",
+                "src/lib.rs:11-11 fn new  pub fn new() {}",
+            ]
+        );
+
+        let qualified = SymbolQuery::new("Config::new", Some(SymbolKind::Method), false, 10);
+        assert_eq!(
+            find_definitions(temp.path(), temp.path(), &qualified).unwrap(),
+            vec!["src/lib.rs:6-8 method Config::new  pub fn new() -> Self"]
+        );
+    }
+
+    #[test]
+    fn find_symbol_substring_is_case_insensitive_and_limited() {
+        let temp = tempdir().unwrap();
+        write_fixture(temp.path());
+
+        let query = SymbolQuery::new("CONF", None, true, 1);
+        assert_eq!(
+            find_definitions(temp.path(), temp.path(), &query).unwrap(),
+            vec![
+                "src/lib.rs:1-3 struct Config  pub struct Config".to_string(),
+                "More than 1 definitions found; refine `name`, `kind` or `path`.".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::lsp::format_diagnostics;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct DiagnosticsHandler;
+
+#[derive(Deserialize)]
+struct DiagnosticsArgs {
+    #[serde(default)]
+    path: Option<String>,
+}
+
+#[async_trait]
+impl ToolHandler for DiagnosticsHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "diagnostics handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: DiagnosticsArgs = serde_json::from_str(&arguments).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to parse function arguments: {err:?}"
+            ))
+        })?;
+
+        let lsp = &session.services.lsp_manager;
+        if !lsp.is_enabled() {
+            return Err(FunctionCallError::RespondToModel(
+                "no language servers are configured".to_string(),
+            ));
+        }
+
+        let path = args
+            .path
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty());
+        let diagnostics = match path {
+            Some(path) => {
+                let path = turn.resolve_path(Some(path));
+                if !lsp.handles(&path) {
+                    return Err(FunctionCallError::RespondToModel(format!(
+                        "no language server is configured for {}",
+                        path.display()
+                    )));
+                }
+                if !path.is_file() {
+                    return Err(FunctionCallError::RespondToModel(format!(
+                        "file not found: {}",
+                        path.display()
+                    )));
+                }
+                lsp.refresh(&[path]).await
+            }
+            None => lsp.known(&lsp.open_documents().await),
+        };
+
+        let lines = format_diagnostics(&diagnostics, &turn.cwd);
+        if lines.is_empty() {
+            return Ok(ToolOutput::Function {
+                content: "No diagnostics reported.".to_string(),
+                content_items: None,
+                success: Some(true),
+            });
+        }
+
+        Ok(ToolOutput::Function {
+            content: lines.join("\n"),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
@@ -1,12 +1,19 @@
+use std::num::NonZero;
 use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use codex_file_search::ContentLineKind;
+use codex_file_search::ContentSearchOptions;
 use codex_utils_string::take_bytes_at_char_boundary;
 use serde::Deserialize;
 use tokio::process::Command;
 use tokio::time::timeout;
 
+use crate::config::types::GrepBackend;
 use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
@@ -18,6 +25,7 @@ pub struct GrepFilesHandler;
 
 const DEFAULT_LIMIT: usize = 100;
 const MAX_LIMIT: usize = 2000;
+const MAX_CONTEXT_LINES: usize = 20;
 const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
 const MAX_OUTPUT_LINE_LENGTH: usize = 500;
 
@@ -29,13 +37,16 @@ fn default_mode() -> GrepOutputMode {
     GrepOutputMode::FirstMatch
 }
 
-#[derive(Debug, Deserialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 enum GrepOutputMode {
     /// Return only file paths that contain at least one match.
     Files,
     /// Return one representative match line per file (`path:line:snippet`).
     FirstMatch,
+    /// Return every matching line (`path:line:snippet`); context lines are
+    /// rendered as `path-line-snippet`.
+    Content,
 }
 
 #[derive(Deserialize)]
@@ -44,11 +55,67 @@ struct GrepFilesArgs {
     #[serde(default)]
     include: Option<String>,
     #[serde(default)]
+    exclude: Option<String>,
+    #[serde(default)]
     path: Option<String>,
     #[serde(default = "default_limit")]
     limit: usize,
     #[serde(default = "default_mode")]
     mode: GrepOutputMode,
+    #[serde(default)]
+    context: usize,
+    #[serde(default)]
+    max_count: Option<usize>,
+    #[serde(default)]
+    case_insensitive: bool,
+    #[serde(default)]
+    fixed_strings: bool,
+}
+
+/// Normalized search parameters shared by the builtin and `rg` backends.
+#[derive(Debug, Clone)]
+struct GrepRequest {
+    pattern: String,
+    include: Option<String>,
+    exclude: Option<String>,
+    mode: GrepOutputMode,
+    context: usize,
+    max_count: Option<NonZero<usize>>,
+    case_insensitive: bool,
+    fixed_strings: bool,
+    limit: usize,
+}
+
+impl GrepRequest {
+    #[cfg(test)]
+    fn new(pattern: &str, mode: GrepOutputMode, limit: usize) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            include: None,
+            exclude: None,
+            mode,
+            context: 0,
+            max_count: None,
+            case_insensitive: false,
+            fixed_strings: false,
+            limit,
+        }
+    }
+
+    /// Per-file match cap implied by the output mode.
+    fn per_file_limit(&self) -> Option<NonZero<usize>> {
+        match self.mode {
+            GrepOutputMode::Files | GrepOutputMode::FirstMatch => Some(NonZero::<usize>::MIN),
+            GrepOutputMode::Content => self.max_count,
+        }
+    }
+
+    fn context_lines(&self) -> usize {
+        match self.mode {
+            GrepOutputMode::Files => 0,
+            GrepOutputMode::FirstMatch | GrepOutputMode::Content => self.context,
+        }
+    }
 }
 
 #[async_trait]
@@ -88,28 +155,32 @@ impl ToolHandler for GrepFilesHandler {
             ));
         }
 
-        let limit = args.limit.min(MAX_LIMIT);
+        if args.max_count == Some(0) {
+            return Err(FunctionCallError::RespondToModel(
+                "max_count must be greater than zero".to_string(),
+            ));
+        }
+
         let search_path = turn.resolve_path(args.path.clone());
 
         verify_path_exists(&search_path).await?;
 
-        let include = args.include.as_deref().map(str::trim).and_then(|val| {
-            if val.is_empty() {
-                None
-            } else {
-                Some(val.to_string())
-            }
-        });
+        let request = GrepRequest {
+            pattern: pattern.to_string(),
+            include: non_empty_glob(args.include.as_deref()),
+            exclude: non_empty_glob(args.exclude.as_deref()),
+            mode: args.mode,
+            context: args.context.min(MAX_CONTEXT_LINES),
+            max_count: args.max_count.and_then(NonZero::new),
+            case_insensitive: args.case_insensitive,
+            fixed_strings: args.fixed_strings,
+            limit: args.limit.min(MAX_LIMIT),
+        };
 
-        let search_results = run_rg_search(
-            pattern,
-            include.as_deref(),
-            &search_path,
-            limit,
-            &turn.cwd,
-            args.mode,
-        )
-        .await?;
+        let search_results = match turn.client.config().tools_grep_backend {
+            GrepBackend::Builtin => run_builtin_search(&request, &search_path).await?,
+            GrepBackend::Ripgrep => run_rg_search(&request, &search_path, &turn.cwd).await?,
+        };
 
         if search_results.is_empty() {
             Ok(ToolOutput::Function {
@@ -127,6 +198,13 @@ impl ToolHandler for GrepFilesHandler {
     }
 }
 
+fn non_empty_glob(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|val| !val.is_empty())
+        .map(str::to_string)
+}
+
 async fn verify_path_exists(path: &Path) -> Result<(), FunctionCallError> {
     tokio::fs::metadata(path).await.map_err(|err| {
         FunctionCallError::RespondToModel(format!("unable to access `{}`: {err}", path.display()))
@@ -134,13 +212,73 @@ async fn verify_path_exists(path: &Path) -> Result<(), FunctionCallError> {
     Ok(())
 }
 
+/// Sets the shared cancel flag when dropped so the blocking search stops as
+/// soon as the tool call is aborted or times out.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+async fn run_builtin_search(
+    request: &GrepRequest,
+    search_path: &Path,
+) -> Result<Vec<String>, FunctionCallError> {
+    let mut options = ContentSearchOptions::new(request.pattern.clone());
+    options.fixed_strings = request.fixed_strings;
+    options.case_insensitive = request.case_insensitive;
+    options.include = request.include.iter().cloned().collect();
+    options.exclude = request.exclude.iter().cloned().collect();
+    options.context_lines = request.context_lines();
+    options.max_matches_per_file = request.per_file_limit();
+
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let _cancel_guard = CancelOnDrop(Arc::clone(&cancel_flag));
+    let search_path = search_path.to_path_buf();
+    let search = tokio::task::spawn_blocking(move || {
+        codex_file_search::search_contents(&search_path, &options, cancel_flag)
+    });
+
+    let results = timeout(COMMAND_TIMEOUT, search)
+        .await
+        .map_err(|_| {
+            FunctionCallError::RespondToModel("grep_files timed out after 30 seconds".to_string())
+        })?
+        .map_err(|err| FunctionCallError::RespondToModel(format!("grep_files failed: {err}")))?
+        .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+
+    let mut lines = Vec::new();
+    for file in results.files {
+        if lines.len() >= request.limit {
+            break;
+        }
+        let path = file.path.display().to_string();
+        if request.mode == GrepOutputMode::Files {
+            lines.push(path);
+            continue;
+        }
+        for line in file.lines {
+            let separator = match line.kind {
+                ContentLineKind::Match => ':',
+                ContentLineKind::Context => '-',
+            };
+            let formatted = format!(
+                "{path}{separator}{}{separator}{}",
+                line.line_number, line.text
+            );
+            lines.push(clip_line(&formatted));
+        }
+    }
+    lines.truncate(request.limit);
+    Ok(lines)
+}
+
 async fn run_rg_search(
-    pattern: &str,
-    include: Option<&str>,
+    request: &GrepRequest,
     search_path: &Path,
-    limit: usize,
     cwd: &Path,
-    mode: GrepOutputMode,
 ) -> Result<Vec<String>, FunctionCallError> {
     let mut command = Command::new("rg");
     command
@@ -148,27 +286,41 @@ async fn run_rg_search(
         .arg("--color")
         .arg("never")
         .arg("--regexp")
-        .arg(pattern)
+        .arg(&request.pattern)
         .arg("--no-messages");
 
-    match mode {
+    match request.mode {
         GrepOutputMode::Files => {
             command.arg("--files-with-matches").arg("--sortr=modified");
         }
-        GrepOutputMode::FirstMatch => {
+        GrepOutputMode::FirstMatch | GrepOutputMode::Content => {
             command
                 .arg("--line-number")
                 .arg("--no-heading")
                 .arg("--with-filename")
-                .arg("--max-count")
-                .arg("1")
                 .arg("--sortr=modified");
+            if let Some(max_count) = request.per_file_limit() {
+                command.arg("--max-count").arg(max_count.to_string());
+            }
+            let context = request.context_lines();
+            if context > 0 {
+                command.arg("--context").arg(context.to_string());
+            }
         }
     }
 
-    if let Some(glob) = include {
+    if request.fixed_strings {
+        command.arg("--fixed-strings");
+    }
+    if request.case_insensitive {
+        command.arg("--ignore-case");
+    }
+    if let Some(glob) = &request.include {
         command.arg("--glob").arg(glob);
     }
+    if let Some(glob) = &request.exclude {
+        command.arg("--glob").arg(format!("!{glob}"));
+    }
 
     command.arg("--").arg(search_path);
 
@@ -179,12 +331,13 @@ async fn run_rg_search(
         })?
         .map_err(|err| {
             FunctionCallError::RespondToModel(format!(
-                "failed to launch rg: {err}. Ensure ripgrep is installed and on PATH."
+                "failed to launch rg: {err}. Ensure ripgrep is installed and on PATH, or set \
+                 `tools.grep_backend = \"builtin\"`."
             ))
         })?;
 
     match output.status.code() {
-        Some(0) => Ok(parse_results(&output.stdout, limit)),
+        Some(0) => Ok(parse_results(&output.stdout, request.limit)),
         Some(1) => Ok(Vec::new()),
         _ => {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -206,15 +359,11 @@ fn parse_results(stdout: &[u8], limit: usize) -> Vec<String> {
                 continue;
             }
             let trimmed = text.trim_end_matches('\r');
-            if trimmed.is_empty() {
+            // rg separates non-contiguous context groups with `--`.
+            if trimmed.is_empty() || trimmed == "--" {
                 continue;
             }
-            let clipped = if trimmed.len() > MAX_OUTPUT_LINE_LENGTH {
-                take_bytes_at_char_boundary(trimmed, MAX_OUTPUT_LINE_LENGTH).to_string()
-            } else {
-                trimmed.to_string()
-            };
-            results.push(clipped);
+            results.push(clip_line(trimmed));
             if results.len() == limit {
                 break;
             }
@@ -223,6 +372,14 @@ fn parse_results(stdout: &[u8], limit: usize) -> Vec<String> {
     results
 }
 
+fn clip_line(line: &str) -> String {
+    if line.len() > MAX_OUTPUT_LINE_LENGTH {
+        take_bytes_at_char_boundary(line, MAX_OUTPUT_LINE_LENGTH).to_string()
+    } else {
+        line.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,7 +417,8 @@ mod tests {
         std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
         std::fs::write(dir.join("other.txt"), "omega").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 10, dir, GrepOutputMode::Files).await?;
+        let request = GrepRequest::new("alpha", GrepOutputMode::Files, 10);
+        let results = run_rg_search(&request, dir, dir).await?;
         assert_eq!(results.len(), 2);
         assert!(results.iter().any(|path| path.ends_with("match_one.txt")));
         assert!(results.iter().any(|path| path.ends_with("match_two.txt")));
@@ -278,8 +436,8 @@ mod tests {
         std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
         std::fs::write(dir.join("other.txt"), "omega").unwrap();
 
-        let results =
-            run_rg_search("alpha", None, dir, 10, dir, GrepOutputMode::FirstMatch).await?;
+        let request = GrepRequest::new("alpha", GrepOutputMode::FirstMatch, 10);
+        let results = run_rg_search(&request, dir, dir).await?;
         assert_eq!(results.len(), 2);
         assert!(results.iter().all(|line| line.contains("alpha")));
         assert!(results.iter().any(|line| line.contains("match_one.txt")));
@@ -297,8 +455,9 @@ mod tests {
         std::fs::write(dir.join("match_one.rs"), "alpha beta gamma").unwrap();
         std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
 
-        let results =
-            run_rg_search("alpha", Some("*.rs"), dir, 10, dir, GrepOutputMode::Files).await?;
+        let mut request = GrepRequest::new("alpha", GrepOutputMode::Files, 10);
+        request.include = Some("*.rs".to_string());
+        let results = run_rg_search(&request, dir, dir).await?;
         assert_eq!(results.len(), 1);
         assert!(results.iter().all(|path| path.ends_with("match_one.rs")));
         Ok(())
@@ -315,7 +474,8 @@ mod tests {
         std::fs::write(dir.join("two.txt"), "alpha two").unwrap();
         std::fs::write(dir.join("three.txt"), "alpha three").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 2, dir, GrepOutputMode::Files).await?;
+        let request = GrepRequest::new("alpha", GrepOutputMode::Files, 2);
+        let results = run_rg_search(&request, dir, dir).await?;
         assert_eq!(results.len(), 2);
         Ok(())
     }
@@ -329,11 +489,90 @@ mod tests {
         let dir = temp.path();
         std::fs::write(dir.join("one.txt"), "omega").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 5, dir, GrepOutputMode::Files).await?;
+        let request = GrepRequest::new("alpha", GrepOutputMode::Files, 5);
+        let results = run_rg_search(&request, dir, dir).await?;
         assert!(results.is_empty());
         Ok(())
     }
 
+    #[tokio::test]
+    async fn builtin_search_returns_files() -> anyhow::Result<()> {
+        let temp = tempdir().expect("create temp dir");
+        let dir = temp.path();
+        std::fs::write(dir.join("match_one.txt"), "alpha beta gamma").unwrap();
+        std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
+        std::fs::write(dir.join("binary.bin"), b"alpha\0").unwrap();
+        std::fs::write(dir.join("other.txt"), "omega").unwrap();
+
+        let request = GrepRequest::new("alpha", GrepOutputMode::Files, 10);
+        let results = run_builtin_search(&request, dir).await?;
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().any(|path| path.ends_with("match_one.txt")));
+        assert!(results.iter().any(|path| path.ends_with("match_two.txt")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn builtin_search_formats_first_matches() -> anyhow::Result<()> {
+        let temp = tempdir().expect("create temp dir");
+        let dir = temp.path();
+        std::fs::write(dir.join("match_one.rs"), "zero\nalpha one\nalpha two\n").unwrap();
+        std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
+
+        let mut request = GrepRequest::new("alpha", GrepOutputMode::FirstMatch, 10);
+        request.include = Some("*.rs".to_string());
+        let results = run_builtin_search(&request, dir).await?;
+        assert_eq!(
+            results,
+            vec![format!(
+                "{}:2:alpha one",
+                dir.join("match_one.rs").display()
+            )]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn builtin_search_content_mode_with_context() -> anyhow::Result<()> {
+        let temp = tempdir().expect("create temp dir");
+        let file = temp.path().join("lib.rs");
+        std::fs::write(&file, "fn a() {}\nfn needle() {}\nfn b() {}\nfn c() {}\n").unwrap();
+
+        let mut request = GrepRequest::new("NEEDLE", GrepOutputMode::Content, 10);
+        request.case_insensitive = true;
+        request.context = 1;
+        let results = run_builtin_search(&request, &file).await?;
+        let path = file.display();
+        assert_eq!(
+            results,
+            vec![
+                format!("{path}-1-fn a() {{}}"),
+                format!("{path}:2:fn needle() {{}}"),
+                format!("{path}-3-fn b() {{}}"),
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn builtin_search_respects_limit_and_reports_bad_patterns() -> anyhow::Result<()> {
+        let temp = tempdir().expect("create temp dir");
+        let dir = temp.path();
+        std::fs::write(dir.join("one.txt"), "alpha one").unwrap();
+        std::fs::write(dir.join("two.txt"), "alpha two").unwrap();
+        std::fs::write(dir.join("three.txt"), "alpha three").unwrap();
+
+        let request = GrepRequest::new("alpha", GrepOutputMode::Files, 2);
+        assert_eq!(run_builtin_search(&request, dir).await?.len(), 2);
+
+        let request = GrepRequest::new("(", GrepOutputMode::Files, 2);
+        let err = run_builtin_search(&request, dir)
+            .await
+            .expect_err("invalid regex should fail");
+        assert!(err.to_string().contains("invalid pattern"), "{err}");
+        Ok(())
+    }
+
     fn rg_available() -> bool {
         StdCommand::new("rg")
             .arg("--version")
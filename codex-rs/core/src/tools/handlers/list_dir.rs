@@ -51,7 +51,12 @@ impl ToolHandler for ListDirHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, turn, .. } = invocation;
+        let ToolInvocation {
+            session,
+            payload,
+            turn,
+            ..
+        } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -102,6 +107,7 @@ impl ToolHandler for ListDirHandler {
         let path = turn.resolve_path(Some(dir_path.to_string()));
 
         let entries = list_dir_slice(&path, offset, limit, depth).await?;
+        session.note_touched_paths([&path]).await;
         let mut output = Vec::with_capacity(entries.len() + 1);
         output.push(format!("Absolute path: {}", path.display()));
         output.extend(entries);
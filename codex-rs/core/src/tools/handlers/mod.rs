@@ -1,5 +1,7 @@
 pub mod apply_patch;
+mod code_outline;
 mod delegate;
+mod diagnostics;
 mod grep_files;
 mod list_dir;
 mod mcp;
@@ -14,8 +16,11 @@ mod view_image;
 pub use plan::PLAN_TOOL;
 
 pub use apply_patch::ApplyPatchHandler;
+pub use code_outline::CodeOutlineHandler;
+pub use code_outline::FindSymbolHandler;
 pub use delegate::DELEGATE_TOOL;
 pub use delegate::DelegateToolHandler;
+pub use diagnostics::DiagnosticsHandler;
 pub use grep_files::GrepFilesHandler;
 pub use list_dir::ListDirHandler;
 pub use mcp::McpHandler;
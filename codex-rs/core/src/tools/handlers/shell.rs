@@ -9,6 +9,7 @@ use crate::exec_env::create_env;
 use crate::exec_policy::create_exec_approval_requirement_for_command;
 use crate::function_tool::FunctionCallError;
 use crate::is_safe_command::is_known_safe_command;
+use crate::project_doc::shell_command_dirs;
 use crate::protocol::ExecCommandSource;
 use crate::shell::Shell;
 use crate::tools::context::ToolInvocation;
@@ -226,6 +227,10 @@ impl ShellHandler {
             )));
         }
 
+        session
+            .note_touched_paths(shell_command_dirs(&exec_params.command, &exec_params.cwd))
+            .await;
+
         // Intercept apply_patch if present.
         if let Some(output) = intercept_apply_patch(
             &exec_params.command,
@@ -1,5 +1,6 @@
 use crate::function_tool::FunctionCallError;
 use crate::is_safe_command::is_known_safe_command;
+use crate::project_doc::shell_command_dirs;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandSource;
 use crate::protocol::TerminalInteractionEvent;
@@ -157,6 +158,11 @@ impl ToolHandler for UnifiedExecHandler {
                 let workdir = workdir.map(|dir| context.turn.resolve_path(Some(dir)));
                 let cwd = workdir.clone().unwrap_or_else(|| context.turn.cwd.clone());
 
+                context
+                    .session
+                    .note_touched_paths(shell_command_dirs(&command, &cwd))
+                    .await;
+
                 if let Some(output) = intercept_apply_patch(
                     &command,
                     &cwd,
@@ -17,7 +17,10 @@ use crate::tools::sandboxing::ToolCtx;
 use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::default_exec_approval_requirement;
+use codex_protocol::protocol::ApprovalDecisionEvent;
+use codex_protocol::protocol::ApprovalDecisionSource;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ReviewDecision;
 
 pub(crate) struct ToolOrchestrator {
@@ -57,8 +60,22 @@ impl ToolOrchestrator {
         match requirement {
             ExecApprovalRequirement::Skip { .. } => {
                 otel.tool_decision(otel_tn, otel_ci, &ReviewDecision::Approved, otel_cfg);
+                record_decision(
+                    tool_ctx,
+                    turn_ctx,
+                    ReviewDecision::Approved,
+                    ApprovalDecisionSource::Config,
+                )
+                .await;
             }
             ExecApprovalRequirement::Forbidden { reason } => {
+                record_decision(
+                    tool_ctx,
+                    turn_ctx,
+                    ReviewDecision::Denied,
+                    ApprovalDecisionSource::Config,
+                )
+                .await;
                 return Err(ToolError::Rejected(reason));
             }
             ExecApprovalRequirement::NeedsApproval { reason, .. } => {
@@ -71,13 +88,25 @@ impl ToolOrchestrator {
                 let decision = tool.start_approval_async(req, approval_ctx).await;
 
                 otel.tool_decision(otel_tn, otel_ci, &decision, otel_user.clone());
+                record_decision(
+                    tool_ctx,
+                    turn_ctx,
+                    decision.clone(),
+                    ApprovalDecisionSource::User,
+                )
+                .await;
 
                 match decision {
                     ReviewDecision::Denied | ReviewDecision::Abort => {
                         return Err(ToolError::Rejected("rejected by user".to_string()));
                     }
+                    // Hunk review is only offered for patch approvals, which
+                    // `apply_patch` resolves before the orchestrator runs; any
+                    // hunks sent back for a command approval have nothing to
+                    // select, so the answer counts as a plain approval.
                     ReviewDecision::Approved
                     | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+                    | ReviewDecision::ApprovedWithHunkReview { .. }
                     | ReviewDecision::ApprovedForSession => {}
                 }
                 already_approved = true;
@@ -108,6 +137,7 @@ impl ToolOrchestrator {
                 Ok(out)
             }
             Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output }))) => {
+                otel.sandbox_denied(otel_tn);
                 if !tool.escalate_on_failure() {
                     return Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
                         output,
@@ -133,13 +163,23 @@ impl ToolOrchestrator {
 
                     let decision = tool.start_approval_async(req, approval_ctx).await;
                     otel.tool_decision(otel_tn, otel_ci, &decision, otel_user);
+                    record_decision(
+                        tool_ctx,
+                        turn_ctx,
+                        decision.clone(),
+                        ApprovalDecisionSource::User,
+                    )
+                    .await;
 
                     match decision {
                         ReviewDecision::Denied | ReviewDecision::Abort => {
                             return Err(ToolError::Rejected("rejected by user".to_string()));
                         }
+                        // The retry prompt is a command approval, so there are no
+                        // hunks to select; see the first approval above.
                         ReviewDecision::Approved
                         | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+                        | ReviewDecision::ApprovedWithHunkReview { .. }
                         | ReviewDecision::ApprovedForSession => {}
                     }
                 }
@@ -160,6 +200,25 @@ impl ToolOrchestrator {
     }
 }
 
+/// Records how the call's approval was settled so the rollout keeps the
+/// actual decision rather than only the tool output.
+async fn record_decision(
+    tool_ctx: &ToolCtx<'_>,
+    turn_ctx: &crate::codex::TurnContext,
+    decision: ReviewDecision,
+    source: ApprovalDecisionSource,
+) {
+    let event = ApprovalDecisionEvent {
+        call_id: tool_ctx.call_id.clone(),
+        decision,
+        source,
+    };
+    tool_ctx
+        .session
+        .send_event(turn_ctx, EventMsg::ApprovalDecision(event))
+        .await;
+}
+
 fn build_denial_reason_from_output(_output: &ExecToolCallOutput) -> String {
     // Keep approval reason terse and stable for UX/tests, but accept the
     // output so we can evolve heuristics later without touching call sites.
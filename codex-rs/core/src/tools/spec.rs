@@ -22,6 +22,8 @@ pub(crate) struct ToolsConfig {
     pub apply_patch_tool_type: Option<ApplyPatchToolType>,
     pub web_search_request: bool,
     pub include_view_image_tool: bool,
+    pub include_code_outline_tools: bool,
+    pub include_diagnostics_tool: bool,
     pub include_delegate_tool: bool,
     pub experimental_supported_tools: Vec<String>,
 }
@@ -42,6 +44,8 @@ impl ToolsConfig {
         let include_apply_patch_tool = features.enabled(Feature::ApplyPatchFreeform);
         let include_web_search_request = features.enabled(Feature::WebSearchRequest);
         let include_view_image_tool = features.enabled(Feature::ViewImageTool);
+        let include_code_outline_tools = features.enabled(Feature::CodeOutline);
+        let include_diagnostics_tool = features.enabled(Feature::LspDiagnostics);
 
         let shell_type = if !features.enabled(Feature::ShellTool) {
             ConfigShellToolType::Disabled
@@ -73,6 +77,8 @@ impl ToolsConfig {
             apply_patch_tool_type,
             web_search_request: include_web_search_request,
             include_view_image_tool,
+            include_code_outline_tools,
+            include_diagnostics_tool,
             include_delegate_tool: *include_delegate_tool,
             experimental_supported_tools: model_family.experimental_supported_tools.clone(),
         }
@@ -500,6 +506,15 @@ fn create_grep_files_tool() -> ToolSpec {
             ),
         },
     );
+    properties.insert(
+        "exclude".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Optional glob for files or directories to skip (e.g. \"vendor\" or \"*.min.js\")."
+                    .to_string(),
+            ),
+        },
+    );
     properties.insert(
         "path".to_string(),
         JsonSchema::String {
@@ -513,7 +528,48 @@ fn create_grep_files_tool() -> ToolSpec {
         "limit".to_string(),
         JsonSchema::Number {
             description: Some(
-                "Maximum number of file paths to return (defaults to 100).".to_string(),
+                "Maximum number of result lines to return (defaults to 100).".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "mode".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Output mode: \"files\" lists matching paths, \"first_match\" (default) shows \
+                 the first matching line per file, \"content\" shows every matching line."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "context".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Lines of context to include before and after each match (max 20).".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "max_count".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Maximum number of matching lines per file in \"content\" mode.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "case_insensitive".to_string(),
+        JsonSchema::Boolean {
+            description: Some("Match without regard to case.".to_string()),
+        },
+    );
+    properties.insert(
+        "fixed_strings".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "Treat the pattern as a literal string instead of a regular expression."
+                    .to_string(),
             ),
         },
     );
@@ -521,7 +577,7 @@ fn create_grep_files_tool() -> ToolSpec {
     ToolSpec::Function(ResponsesApiTool {
         name: "grep_files".to_string(),
         description: "Finds files whose contents match the pattern and lists them by modification \
-                      time."
+                      time. Respects .gitignore and skips binary files."
             .to_string(),
         strict: false,
         parameters: JsonSchema::Object {
@@ -675,6 +731,134 @@ fn create_list_dir_tool() -> ToolSpec {
     })
 }
 
+fn create_code_outline_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "File or directory to outline. Directories are walked recursively, honoring .gitignore."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "depth".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "How many levels of nested symbols to show (1 = top level only). Defaults to 3."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Maximum number of files to outline when `path` is a directory. Defaults to 50."
+                    .to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "code_outline".to_string(),
+        description: "Lists the functions, types, impls, classes and methods declared in a source file or directory, with 1-indexed line ranges. Supports Rust, Python, TypeScript, JavaScript, Go, Java, C, C++ and Ruby. Use it to find the lines worth reading instead of reading whole files."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["path".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_find_symbol_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "name".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Symbol to look for, either a bare name (`new`) or qualified with its container (`Config::new`, `Server.start`)."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "File or directory to search. Defaults to the session's working directory."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "kind".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Optional kind filter: function, method, class, struct, enum, interface, trait, impl, module, type, const or macro."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "substring".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "Match any symbol whose name contains `name`, case-insensitively. Defaults to false (exact match)."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Maximum number of definitions to return. Defaults to 50.".to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "find_symbol".to_string(),
+        description: "Finds where functions, types, classes and methods are defined. Returns `path:start-end kind qualified_name  signature` for each definition."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_diagnostics_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "File to check. When omitted, reports what is already known for every file the language servers have seen this session."
+                    .to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "diagnostics".to_string(),
+        description: "Reports compiler and linter diagnostics from the configured language servers as `path:line:col: severity[code]: message (source)`."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_list_mcp_resources_tool() -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
@@ -985,8 +1169,11 @@ pub(crate) fn build_specs(
     mcp_tools: Option<HashMap<String, mcp_types::Tool>>,
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
+    use crate::tools::handlers::CodeOutlineHandler;
     use crate::tools::handlers::DELEGATE_TOOL;
     use crate::tools::handlers::DelegateToolHandler;
+    use crate::tools::handlers::DiagnosticsHandler;
+    use crate::tools::handlers::FindSymbolHandler;
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::ListDirHandler;
     use crate::tools::handlers::McpHandler;
@@ -1090,6 +1277,18 @@ pub(crate) fn build_specs(
         builder.register_handler("list_dir", list_dir_handler);
     }
 
+    if config.include_code_outline_tools {
+        builder.push_spec_with_parallel_support(create_code_outline_tool(), true);
+        builder.push_spec_with_parallel_support(create_find_symbol_tool(), true);
+        builder.register_handler("code_outline", Arc::new(CodeOutlineHandler));
+        builder.register_handler("find_symbol", Arc::new(FindSymbolHandler));
+    }
+
+    if config.include_diagnostics_tool {
+        builder.push_spec_with_parallel_support(create_diagnostics_tool(), true);
+        builder.register_handler("diagnostics", Arc::new(DiagnosticsHandler));
+    }
+
     if config
         .experimental_supported_tools
         .contains(&"test_sync_tool".to_string())
@@ -1530,6 +1729,61 @@ mod tests {
         assert!(find_tool(&tools, "read_file").supports_parallel_tool_calls);
     }
 
+    #[test]
+    fn test_code_outline_tools_gated_by_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+            include_delegate_tool: false,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| matches!(tool_name(&tool.spec), "code_outline" | "find_symbol"))
+        );
+
+        features.enable(Feature::CodeOutline);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+            include_delegate_tool: false,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(find_tool(&tools, "code_outline").supports_parallel_tool_calls);
+        assert!(find_tool(&tools, "find_symbol").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn test_diagnostics_tool_gated_by_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+            include_delegate_tool: false,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| tool_name(&tool.spec) == "diagnostics")
+        );
+
+        features.enable(Feature::LspDiagnostics);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+            include_delegate_tool: false,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(find_tool(&tools, "diagnostics").supports_parallel_tool_calls);
+    }
+
     #[test]
     fn test_test_model_family_includes_sync_tool() {
         let config = test_config();
@@ -1,13 +1,22 @@
 //! Utilities for truncating large chunks of output while preserving a prefix
 //! and suffix on UTF-8 boundaries, and helpers for line/token‑based truncation
 //! used across the core crate.
+//!
+//! Token budgets are counted with the model family's [`Tokenizer`]; byte
+//! budgets ignore it.
 
 use crate::config::Config;
 use codex_protocol::models::FunctionCallOutputContentItem;
 use codex_protocol::openai_models::TruncationMode;
 use codex_protocol::openai_models::TruncationPolicyConfig;
+use codex_utils_string::take_bytes_at_char_boundary;
+use codex_utils_string::take_last_bytes_at_char_boundary;
+use codex_utils_tokenizer::APPROX_BYTES_PER_TOKEN;
+use codex_utils_tokenizer::Tokenizer;
 
-const APPROX_BYTES_PER_TOKEN: usize = 4;
+/// Rounds of shrinking when fitting text to a token budget; the byte guess
+/// is usually within budget after one.
+const MAX_FIT_ATTEMPTS: usize = 3;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TruncationPolicy {
@@ -85,30 +94,49 @@ impl TruncationPolicy {
     }
 }
 
-pub(crate) fn formatted_truncate_text(content: &str, policy: TruncationPolicy) -> String {
-    if content.len() <= policy.byte_budget() {
+pub(crate) fn formatted_truncate_text(
+    content: &str,
+    policy: TruncationPolicy,
+    tokenizer: Tokenizer,
+) -> String {
+    if fits_budget(content, policy, tokenizer) {
         return content.to_string();
     }
     let total_lines = content.lines().count();
-    let result = truncate_text(content, policy);
+    let result = truncate_text(content, policy, tokenizer);
     format!("Total output lines: {total_lines}\n\n{result}")
 }
 
-pub(crate) fn truncate_text(content: &str, policy: TruncationPolicy) -> String {
+pub(crate) fn truncate_text(
+    content: &str,
+    policy: TruncationPolicy,
+    tokenizer: Tokenizer,
+) -> String {
     match policy {
         TruncationPolicy::Bytes(_) => truncate_with_byte_estimate(content, policy),
         TruncationPolicy::Tokens(_) => {
-            let (truncated, _) = truncate_with_token_budget(content, policy);
+            let (truncated, _) = truncate_with_token_budget(content, policy, tokenizer);
             truncated
         }
     }
 }
+
+fn fits_budget(content: &str, policy: TruncationPolicy, tokenizer: Tokenizer) -> bool {
+    match policy {
+        TruncationPolicy::Bytes(bytes) => content.len() <= bytes,
+        // Every token covers at least one byte, so short content skips counting.
+        TruncationPolicy::Tokens(tokens) => {
+            content.len() <= tokens || tokenizer.count(content) <= tokens
+        }
+    }
+}
 /// Globally truncate function output items to fit within the given
 /// truncation policy's budget, preserving as many text/image items as
 /// possible and appending a summary for any omitted text items.
 pub(crate) fn truncate_function_output_items_with_policy(
     items: &[FunctionCallOutputContentItem],
     policy: TruncationPolicy,
+    tokenizer: Tokenizer,
 ) -> Vec<FunctionCallOutputContentItem> {
     let mut out: Vec<FunctionCallOutputContentItem> = Vec::with_capacity(items.len());
     let mut remaining_budget = match policy {
@@ -127,7 +155,7 @@ pub(crate) fn truncate_function_output_items_with_policy(
 
                 let cost = match policy {
                     TruncationPolicy::Bytes(_) => text.len(),
-                    TruncationPolicy::Tokens(_) => approx_token_count(text),
+                    TruncationPolicy::Tokens(_) => tokenizer.count(text),
                 };
 
                 if cost <= remaining_budget {
@@ -138,7 +166,7 @@ pub(crate) fn truncate_function_output_items_with_policy(
                         TruncationPolicy::Bytes(_) => TruncationPolicy::Bytes(remaining_budget),
                         TruncationPolicy::Tokens(_) => TruncationPolicy::Tokens(remaining_budget),
                     };
-                    let snippet = truncate_text(text, snippet_policy);
+                    let snippet = truncate_text(text, snippet_policy, tokenizer);
                     if snippet.is_empty() {
                         omitted_text_items += 1;
                     } else {
@@ -168,25 +196,90 @@ pub(crate) fn truncate_function_output_items_with_policy(
 /// preserving the beginning and the end. Returns the possibly truncated string
 /// and `Some(original_token_count)` if truncation occurred; otherwise returns
 /// the original string and `None`.
-fn truncate_with_token_budget(s: &str, policy: TruncationPolicy) -> (String, Option<u64>) {
+fn truncate_with_token_budget(
+    s: &str,
+    policy: TruncationPolicy,
+    tokenizer: Tokenizer,
+) -> (String, Option<u64>) {
     if s.is_empty() {
         return (String::new(), None);
     }
     let max_tokens = policy.token_budget();
 
-    let byte_len = s.len();
-    if max_tokens > 0 && byte_len <= approx_bytes_for_tokens(max_tokens) {
+    // A fixed ratio converts the token budget to bytes exactly, so the byte
+    // estimate needs no counting beyond the reported total.
+    if let Some(bytes_per_token) = tokenizer.fixed_bytes_per_token() {
+        if max_tokens > 0 && s.len() <= max_tokens.saturating_mul(bytes_per_token) {
+            return (s.to_string(), None);
+        }
+        let truncated = truncate_with_byte_estimate(s, policy);
+        let total = u64::try_from(tokenizer.count(s)).unwrap_or(u64::MAX);
+        return if truncated == s {
+            (truncated, None)
+        } else {
+            (truncated, Some(total))
+        };
+    }
+
+    let total_tokens = tokenizer.count(s);
+    if max_tokens > 0 && total_tokens <= max_tokens {
         return (s.to_string(), None);
     }
 
-    let truncated = truncate_with_byte_estimate(s, policy);
-    let approx_total_usize = approx_token_count(s);
-    let approx_total = u64::try_from(approx_total_usize).unwrap_or(u64::MAX);
-    if truncated == s {
-        (truncated, None)
-    } else {
-        (truncated, Some(approx_total))
+    let (left_tokens, right_tokens) = split_budget(max_tokens);
+    let left = fit_to_tokens(
+        s,
+        left_tokens,
+        proportional_byte_budget(s.len(), total_tokens, left_tokens),
+        tokenizer,
+        take_bytes_at_char_boundary,
+    );
+    let rest = &s[left.len()..];
+    let right = fit_to_tokens(
+        rest,
+        right_tokens,
+        proportional_byte_budget(s.len(), total_tokens, right_tokens),
+        tokenizer,
+        take_last_bytes_at_char_boundary,
+    );
+    let kept_tokens = tokenizer.count(left).saturating_add(tokenizer.count(right));
+    let removed_tokens =
+        u64::try_from(total_tokens.saturating_sub(kept_tokens)).unwrap_or(u64::MAX);
+    let marker = format_truncation_marker(policy, removed_tokens);
+
+    (
+        assemble_truncated_output(left, right, &marker),
+        Some(u64::try_from(total_tokens).unwrap_or(u64::MAX)),
+    )
+}
+
+/// Takes the part of `s` selected by `take` that fits in `max_tokens`,
+/// starting from `initial_bytes` and shrinking by the observed token density.
+fn fit_to_tokens<'a>(
+    s: &'a str,
+    max_tokens: usize,
+    initial_bytes: usize,
+    tokenizer: Tokenizer,
+    take: fn(&'a str, usize) -> &'a str,
+) -> &'a str {
+    let mut max_bytes = initial_bytes;
+    for _ in 0..MAX_FIT_ATTEMPTS {
+        let part = take(s, max_bytes);
+        let tokens = tokenizer.count(part);
+        if tokens <= max_tokens {
+            return part;
+        }
+        max_bytes = proportional_byte_budget(part.len(), tokens, max_tokens);
+    }
+    take(s, max_bytes)
+}
+
+fn proportional_byte_budget(total_bytes: usize, total_tokens: usize, max_tokens: usize) -> usize {
+    if total_tokens == 0 {
+        return total_bytes;
     }
+    let bytes = total_bytes as u128 * max_tokens as u128 / total_tokens as u128;
+    usize::try_from(bytes).unwrap_or(usize::MAX)
 }
 
 /// Truncate a string using a byte budget derived from the token budget, without
@@ -298,11 +391,6 @@ fn assemble_truncated_output(prefix: &str, suffix: &str, marker: &str) -> String
     out
 }
 
-pub(crate) fn approx_token_count(text: &str) -> usize {
-    let len = text.len();
-    len.saturating_add(APPROX_BYTES_PER_TOKEN.saturating_sub(1)) / APPROX_BYTES_PER_TOKEN
-}
-
 fn approx_bytes_for_tokens(tokens: usize) -> usize {
     tokens.saturating_mul(APPROX_BYTES_PER_TOKEN)
 }
@@ -317,13 +405,13 @@ pub(crate) fn approx_tokens_from_byte_count(bytes: usize) -> u64 {
 mod tests {
 
     use super::TruncationPolicy;
-    use super::approx_token_count;
     use super::formatted_truncate_text;
     use super::split_string;
     use super::truncate_function_output_items_with_policy;
     use super::truncate_text;
     use super::truncate_with_token_budget;
     use codex_protocol::models::FunctionCallOutputContentItem;
+    use codex_utils_tokenizer::Tokenizer;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -367,7 +455,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 1\n\n…13 chars truncated…t",
-            formatted_truncate_text(content, TruncationPolicy::Bytes(1)),
+            formatted_truncate_text(content, TruncationPolicy::Bytes(1), Tokenizer::Approximate),
         );
     }
 
@@ -377,7 +465,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 1\n\nex…3 tokens truncated…ut",
-            formatted_truncate_text(content, TruncationPolicy::Tokens(1)),
+            formatted_truncate_text(content, TruncationPolicy::Tokens(1), Tokenizer::Approximate),
         );
     }
 
@@ -387,7 +475,11 @@ mod tests {
 
         assert_eq!(
             content,
-            formatted_truncate_text(content, TruncationPolicy::Tokens(10)),
+            formatted_truncate_text(
+                content,
+                TruncationPolicy::Tokens(10),
+                Tokenizer::Approximate
+            ),
         );
     }
 
@@ -397,7 +489,7 @@ mod tests {
 
         assert_eq!(
             content,
-            formatted_truncate_text(content, TruncationPolicy::Bytes(20)),
+            formatted_truncate_text(content, TruncationPolicy::Bytes(20), Tokenizer::Approximate),
         );
     }
 
@@ -407,7 +499,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 1\n\nthis is an…10 tokens truncated… truncated",
-            formatted_truncate_text(content, TruncationPolicy::Tokens(5)),
+            formatted_truncate_text(content, TruncationPolicy::Tokens(5), Tokenizer::Approximate),
         );
     }
 
@@ -417,7 +509,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 1\n\nthis is an exam…30 chars truncated…ld be truncated",
-            formatted_truncate_text(content, TruncationPolicy::Bytes(30)),
+            formatted_truncate_text(content, TruncationPolicy::Bytes(30), Tokenizer::Approximate),
         );
     }
 
@@ -428,7 +520,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 2\n\nthis is an exam…51 chars truncated…some other line",
-            formatted_truncate_text(content, TruncationPolicy::Bytes(30)),
+            formatted_truncate_text(content, TruncationPolicy::Bytes(30), Tokenizer::Approximate),
         );
     }
 
@@ -439,7 +531,11 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 2\n\nthis is an example o…11 tokens truncated…also some other line",
-            formatted_truncate_text(content, TruncationPolicy::Tokens(10)),
+            formatted_truncate_text(
+                content,
+                TruncationPolicy::Tokens(10),
+                Tokenizer::Approximate
+            ),
         );
     }
 
@@ -447,7 +543,8 @@ mod tests {
     fn truncate_with_token_budget_returns_original_when_under_limit() {
         let s = "short output";
         let limit = 100;
-        let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(limit));
+        let (out, original) =
+            truncate_with_token_budget(s, TruncationPolicy::Tokens(limit), Tokenizer::Approximate);
         assert_eq!(out, s);
         assert_eq!(original, None);
     }
@@ -455,7 +552,8 @@ mod tests {
     #[test]
     fn truncate_with_token_budget_reports_truncation_at_zero_limit() {
         let s = "abcdef";
-        let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(0));
+        let (out, original) =
+            truncate_with_token_budget(s, TruncationPolicy::Tokens(0), Tokenizer::Approximate);
         assert_eq!(out, "…2 tokens truncated…");
         assert_eq!(original, Some(2));
     }
@@ -463,7 +561,8 @@ mod tests {
     #[test]
     fn truncate_middle_tokens_handles_utf8_content() {
         let s = "😀😀😀😀😀😀😀😀😀😀\nsecond line with text\n";
-        let (out, tokens) = truncate_with_token_budget(s, TruncationPolicy::Tokens(8));
+        let (out, tokens) =
+            truncate_with_token_budget(s, TruncationPolicy::Tokens(8), Tokenizer::Approximate);
         assert_eq!(out, "😀😀😀😀…8 tokens truncated… line with text\n");
         assert_eq!(tokens, Some(16));
     }
@@ -471,14 +570,39 @@ mod tests {
     #[test]
     fn truncate_middle_bytes_handles_utf8_content() {
         let s = "😀😀😀😀😀😀😀😀😀😀\nsecond line with text\n";
-        let out = truncate_text(s, TruncationPolicy::Bytes(20));
+        let out = truncate_text(s, TruncationPolicy::Bytes(20), Tokenizer::Approximate);
         assert_eq!(out, "😀😀…21 chars truncated…with text\n");
     }
 
+    #[test]
+    fn bpe_tokenizer_truncates_by_counted_tokens() {
+        let s = (1..=400).map(|i| format!("{i}\n")).collect::<String>();
+        let total = Tokenizer::O200kBase.count(&s);
+        assert!(total > Tokenizer::Approximate.count(&s));
+
+        let (out, original) =
+            truncate_with_token_budget(&s, TruncationPolicy::Tokens(100), Tokenizer::O200kBase);
+        assert_eq!(original, Some(total as u64));
+        assert!(out.starts_with("1\n2\n"));
+        assert!(out.ends_with("399\n400\n"));
+
+        let (kept, _) = out.split_once('…').unwrap_or_default();
+        assert!(Tokenizer::O200kBase.count(kept) <= 50);
+    }
+
+    #[test]
+    fn bpe_tokenizer_keeps_text_within_budget() {
+        let s = "short output";
+        assert_eq!(
+            formatted_truncate_text(s, TruncationPolicy::Tokens(2), Tokenizer::O200kBase),
+            s
+        );
+    }
+
     #[test]
     fn truncates_across_multiple_under_limit_texts_and_reports_omitted() {
         let chunk = "alpha beta gamma delta epsilon zeta eta theta iota kappa lambda mu nu xi omicron pi rho sigma tau upsilon phi chi psi omega.\n";
-        let chunk_tokens = approx_token_count(chunk);
+        let chunk_tokens = Tokenizer::Approximate.count(chunk);
         assert!(chunk_tokens > 0, "chunk must consume tokens");
         let limit = chunk_tokens * 3;
         let t1 = chunk.to_string();
@@ -498,8 +622,11 @@ mod tests {
             FunctionCallOutputContentItem::InputText { text: t5 },
         ];
 
-        let output =
-            truncate_function_output_items_with_policy(&items, TruncationPolicy::Tokens(limit));
+        let output = truncate_function_output_items_with_policy(
+            &items,
+            TruncationPolicy::Tokens(limit),
+            Tokenizer::Approximate,
+        );
 
         // Expect: t1 (full), t2 (full), image, t3 (truncated), summary mentioning 2 omitted.
         assert_eq!(output.len(), 5);
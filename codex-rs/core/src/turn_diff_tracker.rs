@@ -62,7 +62,7 @@ impl TurnDiffTracker {
                     .insert(internal.clone(), path.clone());
 
                 // If the file exists on disk now, snapshot as baseline; else leave missing to represent /dev/null.
-                let baseline_file_info = if path.exists() {
+                let baseline_file_info = if fs::symlink_metadata(path).is_ok() {
                     let mode = file_mode_for_path(path);
                     let mode_val = mode.unwrap_or(FileMode::Regular);
                     let content = blob_bytes(path, mode_val).unwrap_or_default();
@@ -127,6 +127,14 @@ impl TurnDiffTracker {
         }
     }
 
+    /// Current external paths of every file touched so far this turn, sorted.
+    pub fn touched_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.temp_name_to_current_path.values().cloned().collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
     fn get_path_for_internal(&self, internal: &str) -> Option<PathBuf> {
         self.temp_name_to_current_path
             .get(internal)
@@ -294,8 +302,9 @@ impl TurnDiffTracker {
             None
         };
 
-        // Fast path: identical bytes or both missing.
-        if left_bytes == right_bytes.as_deref() {
+        // Fast path: identical bytes and mode, or both missing.
+        let same_bytes = left_bytes == right_bytes.as_deref();
+        if same_bytes && (baseline_mode == current_mode || right_bytes.is_none()) {
             return aggregated;
         }
 
@@ -313,6 +322,11 @@ impl TurnDiffTracker {
             aggregated.push_str(&format!("new mode {current_mode}\n"));
         }
 
+        // Mode-only change: git prints no index line or content diff.
+        if same_bytes {
+            return aggregated;
+        }
+
         let left_text = left_bytes.and_then(|b| std::str::from_utf8(b).ok());
         let right_text = right_bytes
             .as_deref()
@@ -428,7 +442,8 @@ fn file_mode_for_path(_path: &Path) -> Option<FileMode> {
 }
 
 fn blob_bytes(path: &Path, mode: FileMode) -> Option<Vec<u8>> {
-    if path.exists() {
+    // `symlink_metadata` so dangling symlinks still count as present.
+    if fs::symlink_metadata(path).is_ok() {
         let contents = if mode == FileMode::Symlink {
             symlink_blob_bytes(path)
                 .ok_or_else(|| anyhow!("failed to read symlink target for {}", path.display()))
@@ -826,6 +841,34 @@ Binary files differ
         assert_eq!(diff, expected);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn mode_only_change_reports_old_and_new_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("run.sh");
+        fs::write(&file, "echo hi\n").unwrap();
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let mut acc = TurnDiffTracker::new();
+        acc.on_patch_begin(&HashMap::from([(
+            file.clone(),
+            FileChange::Mode {
+                old_mode: Some(0o644),
+                new_mode: 0o755,
+            },
+        )]));
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let diff = acc.get_unified_diff().unwrap().unwrap();
+        let diff = normalize_diff_for_test(&diff, dir.path());
+        assert_eq!(
+            diff,
+            "diff --git a/<TMP>/run.sh b/<TMP>/run.sh\nold mode 100644\nnew mode 100755\n"
+        );
+    }
+
     #[test]
     fn filenames_with_spaces_add_and_update() {
         let mut acc = TurnDiffTracker::new();
@@ -18,6 +18,7 @@ use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
 use codex_utils_pty::ExecCommandSession;
 use codex_utils_pty::SpawnedPty;
+use codex_utils_tokenizer::Tokenizer;
 
 use super::UNIFIED_EXEC_OUTPUT_MAX_BYTES;
 use super::UNIFIED_EXEC_OUTPUT_MAX_TOKENS;
@@ -206,6 +207,7 @@ impl UnifiedExecSession {
             let snippet = formatted_truncate_text(
                 text,
                 TruncationPolicy::Tokens(UNIFIED_EXEC_OUTPUT_MAX_TOKENS),
+                Tokenizer::Approximate,
             );
             let message = if snippet.is_empty() {
                 format!("Session exited with code {exit_code}")
@@ -24,7 +24,6 @@ use crate::tools::runtimes::unified_exec::UnifiedExecRequest as UnifiedExecToolR
 use crate::tools::runtimes::unified_exec::UnifiedExecRuntime;
 use crate::tools::sandboxing::ToolCtx;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::approx_token_count;
 use crate::truncate::formatted_truncate_text;
 
 use super::CommandTranscript;
@@ -166,7 +165,9 @@ impl UnifiedExecSessionManager {
         let wall_time = Instant::now().saturating_duration_since(start);
 
         let text = String::from_utf8_lossy(&collected).to_string();
-        let output = formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens));
+        let tokenizer = context.turn.tokenizer;
+        let output =
+            formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens), tokenizer);
         let exit_code = session.exit_code();
         let has_exited = session.has_exited() || exit_code.is_some();
         let chunk_id = generate_chunk_id();
@@ -211,7 +212,7 @@ impl UnifiedExecSessionManager {
             Self::emit_waiting_status(&context.session, &context.turn, &request.command).await;
         };
 
-        let original_token_count = approx_token_count(&text);
+        let original_token_count = tokenizer.count(&text);
         let response = UnifiedExecResponse {
             event_call_id: context.call_id.clone(),
             chunk_id,
@@ -270,8 +271,10 @@ impl UnifiedExecSessionManager {
         let wall_time = Instant::now().saturating_duration_since(start);
 
         let text = String::from_utf8_lossy(&collected).to_string();
-        let output = formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens));
-        let original_token_count = approx_token_count(&text);
+        let tokenizer = turn_ref.tokenizer;
+        let output =
+            formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens), tokenizer);
+        let original_token_count = tokenizer.count(&text);
         let chunk_id = generate_chunk_id();
 
         // After polling, refresh_session_state tells us whether the PTY is
@@ -42,6 +42,48 @@ impl From<UserInstructions> for ResponseItem {
     }
 }
 
+/// AGENTS.md instructions from a directory below the session's working
+/// directory, added as a developer message once the agent works there.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename = "nested_user_instructions", rename_all = "snake_case")]
+pub(crate) struct NestedUserInstructions {
+    pub directory: String,
+    pub text: String,
+}
+
+impl NestedUserInstructions {
+    /// Returns the directory and instructions of a nested instructions
+    /// message, e.g. when rebuilding session state from a rollout.
+    pub fn parse(role: &str, message: &[ContentItem]) -> Option<(String, String)> {
+        if role != "developer" {
+            return None;
+        }
+        let [ContentItem::InputText { text }] = message else {
+            return None;
+        };
+        let rest = text.strip_prefix(USER_INSTRUCTIONS_PREFIX)?;
+        let (directory, body) = rest.split_once("\n\n<INSTRUCTIONS>\n")?;
+        let instructions = body.strip_suffix("\n</INSTRUCTIONS>")?;
+        Some((directory.to_string(), instructions.to_string()))
+    }
+}
+
+impl From<NestedUserInstructions> for ResponseItem {
+    fn from(ni: NestedUserInstructions) -> Self {
+        let NestedUserInstructions { directory, text } = ni;
+        ResponseItem::Message {
+            id: None,
+            role: "developer".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!(
+                    "{USER_INSTRUCTIONS_PREFIX}{directory}\n\n<INSTRUCTIONS>\n{text}\n</INSTRUCTIONS>"
+                ),
+            }],
+            thought_signature: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename = "skill_instructions", rename_all = "snake_case")]
 pub(crate) struct SkillInstructions {
@@ -138,6 +180,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nested_user_instructions_round_trip() {
+        let item: ResponseItem = NestedUserInstructions {
+            directory: "/repo/services/foo".to_string(),
+            text: "Run `just test` here.".to_string(),
+        }
+        .into();
+
+        let ResponseItem::Message { role, content, .. } = item else {
+            panic!("expected ResponseItem::Message");
+        };
+
+        assert_eq!(role, "developer");
+        assert_eq!(
+            NestedUserInstructions::parse(&role, &content),
+            Some((
+                "/repo/services/foo".to_string(),
+                "Run `just test` here.".to_string()
+            ))
+        );
+        assert_eq!(NestedUserInstructions::parse("user", &content), None);
+    }
+
     #[test]
     fn test_is_user_instructions() {
         assert!(UserInstructions::is_user_instructions(
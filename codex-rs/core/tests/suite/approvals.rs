@@ -8,6 +8,7 @@ use codex_core::protocol::AskForApproval;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::ExecPolicyAmendment;
+use codex_core::protocol::ExecPolicyAmendmentTarget;
 use codex_core::protocol::Op;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::sandboxing::SandboxPermissions;
@@ -1629,6 +1630,7 @@ async fn approving_execpolicy_amendment_persists_policy_and_skips_future_prompts
             id: "0".into(),
             decision: ReviewDecision::ApprovedExecpolicyAmendment {
                 proposed_execpolicy_amendment: expected_execpolicy_amendment.clone(),
+                target: ExecPolicyAmendmentTarget::User,
             },
         })
         .await?;
@@ -0,0 +1,116 @@
+#![allow(clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_core::config::types::LspServerConfig;
+use codex_core::features::Feature;
+use core_test_support::responses::ev_apply_patch_call;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::ApplyPatchModelOutput;
+use core_test_support::test_codex::TestCodexHarness;
+use core_test_support::test_codex::test_codex;
+use escargot::CargoBuild;
+
+fn test_lsp_server_bin() -> Result<String> {
+    Ok(CargoBuild::new()
+        .package("codex-core")
+        .bin("test_lsp_server")
+        .run()?
+        .path()
+        .to_string_lossy()
+        .into_owned())
+}
+
+async fn lsp_harness() -> Result<TestCodexHarness> {
+    let server_bin = test_lsp_server_bin()?;
+    let builder = test_codex().with_config(move |config| {
+        config.include_apply_patch_tool = true;
+        config.features.enable(Feature::LspDiagnostics);
+        config.lsp_servers = HashMap::from([(
+            "test".to_string(),
+            LspServerConfig {
+                command: server_bin,
+                args: Vec::new(),
+                env: None,
+                extensions: vec!["rs".to_string()],
+                language_id: None,
+                diagnostics_timeout_sec: Some(Duration::from_secs(10)),
+            },
+        )]);
+    });
+    TestCodexHarness::with_builder(builder).await
+}
+
+async fn mount_patch(harness: &TestCodexHarness, call_id: &str, patch: &str) {
+    mount_sse_sequence(
+        harness.server(),
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_apply_patch_call(call_id, patch, ApplyPatchModelOutput::Function),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn apply_patch_reports_new_lsp_diagnostics() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = lsp_harness().await?;
+    let call_id = "add-broken-file";
+    let patch =
+        "*** Begin Patch\n*** Add File: src/lib.rs\n+fn ok() {}\n+   LSP_ERROR\n*** End Patch";
+    mount_patch(&harness, call_id, patch).await;
+
+    harness.submit("add a broken file").await?;
+
+    let out = harness
+        .apply_patch_output(call_id, ApplyPatchModelOutput::Function)
+        .await;
+    assert!(
+        out.contains("New diagnostics reported by language servers after this edit:"),
+        "missing diagnostics header: {out}"
+    );
+    assert!(
+        out.contains("src/lib.rs:2:4: error[T001]: found LSP_ERROR marker (test-lsp)"),
+        "missing diagnostic: {out}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn apply_patch_without_new_problems_has_no_diagnostics() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = lsp_harness().await?;
+    let call_id = "add-clean-file";
+    let patch = "*** Begin Patch\n*** Add File: src/lib.rs\n+fn ok() {}\n*** End Patch";
+    mount_patch(&harness, call_id, patch).await;
+
+    harness.submit("add a clean file").await?;
+
+    let out = harness
+        .apply_patch_output(call_id, ApplyPatchModelOutput::Function)
+        .await;
+    assert!(
+        !out.contains("New diagnostics"),
+        "unexpected diagnostics: {out}"
+    );
+
+    Ok(())
+}
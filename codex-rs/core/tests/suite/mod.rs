@@ -35,10 +35,12 @@ mod json_result;
 mod list_dir;
 mod list_models;
 mod live_cli;
+mod lsp_diagnostics;
 mod model_overrides;
 mod model_tools;
 mod otel;
 mod prompt_caching;
+mod provider_fallback;
 mod quota_exceeded;
 mod read_file;
 mod remote_models;
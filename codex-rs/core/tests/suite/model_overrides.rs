@@ -38,6 +38,7 @@ async fn override_turn_context_does_not_persist_when_config_exists() {
             approval_policy: None,
             sandbox_policy: None,
             model: Some("o3".to_string()),
+            model_provider: None,
             effort: Some(Some(ReasoningEffort::High)),
             summary: None,
         })
@@ -80,6 +81,7 @@ async fn override_turn_context_does_not_create_config_file() {
             approval_policy: None,
             sandbox_policy: None,
             model: Some("o3".to_string()),
+            model_provider: None,
             effort: Some(Some(ReasoningEffort::Medium)),
             summary: None,
         })
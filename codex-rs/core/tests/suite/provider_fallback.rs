@@ -0,0 +1,198 @@
+use codex_core::CodexConversation;
+use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
+use codex_core::config::types::FallbackTarget;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::load_sse_fixture_with_id;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_reasoning_item;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once_match;
+use core_test_support::responses::sse;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+fn mock_provider(name: &str, base_url: String) -> ModelProviderInfo {
+    ModelProviderInfo {
+        name: name.into(),
+        base_url: Some(base_url),
+        env_key: Some("PATH".into()),
+        env_key_instructions: None,
+        experimental_bearer_token: None,
+        auth_json_key: None,
+        wire_api: WireApi::Responses,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        stream_idle_timeout_ms: Some(2_000),
+        requires_openai_auth: false,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn failing_provider_falls_back_to_next_profile() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .respond_with(server_error())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/backup/v1/responses"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            load_sse_fixture_with_id("tests/fixtures/completed_template.json", "resp_backup"),
+            "text/event-stream",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let primary = mock_provider("primary", format!("{}/v1", server.uri()));
+    let backup = mock_provider("backup", format!("{}/backup/v1", server.uri()));
+
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(move |config| {
+            config.model_provider = primary;
+            config.fallback = vec![FallbackTarget {
+                profile: "backup".to_string(),
+                model: "backup-model".to_string(),
+                model_provider_id: "backup".to_string(),
+                model_provider: backup,
+            }];
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await
+        .unwrap();
+
+    let mut warnings = Vec::new();
+    loop {
+        match wait_for_event(&codex, |_| true).await {
+            EventMsg::Warning(warning) => warnings.push(warning.message),
+            EventMsg::Error(err) => panic!("unexpected error event: {}", err.message),
+            EventMsg::TaskComplete(_) => break,
+            _ => {}
+        }
+    }
+
+    assert!(
+        warnings
+            .iter()
+            .any(|message| message.contains("fallback profile `backup`")),
+        "expected a fallback warning, got {warnings:?}"
+    );
+}
+
+fn server_error() -> ResponseTemplate {
+    ResponseTemplate::new(500)
+        .insert_header("content-type", "application/json")
+        .set_body_string(
+            serde_json::json!({
+                "error": {"type": "server_error", "message": "synthetic outage"}
+            })
+            .to_string(),
+        )
+}
+
+async fn submit_and_wait(codex: &CodexConversation, text: &str) {
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text { text: text.into() }],
+        })
+        .await
+        .unwrap();
+    wait_for_event(codex, |event| matches!(event, EventMsg::TaskComplete(_))).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn fallback_requests_leave_out_reasoning_from_the_failed_provider() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    mount_sse_once_match(
+        &server,
+        path("/v1/responses"),
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_reasoning_item("rs-1", &["thinking"], &[]),
+            ev_assistant_message("msg-1", "hi"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/responses"))
+        .respond_with(server_error())
+        .expect(1)
+        .mount(&server)
+        .await;
+    let backup_mock = mount_sse_once_match(
+        &server,
+        path("/backup/v1/responses"),
+        sse(vec![
+            ev_response_created("resp-2"),
+            ev_assistant_message("msg-2", "from backup"),
+            ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+
+    let primary = mock_provider("primary", format!("{}/v1", server.uri()));
+    let backup = mock_provider("backup", format!("{}/backup/v1", server.uri()));
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(move |config| {
+            config.model_provider = primary;
+            config.fallback = vec![FallbackTarget {
+                profile: "backup".to_string(),
+                model: "backup-model".to_string(),
+                model_provider_id: "backup".to_string(),
+                model_provider: backup,
+            }];
+        })
+        .build(&server)
+        .await
+        .unwrap();
+
+    submit_and_wait(&codex, "first").await;
+    submit_and_wait(&codex, "second").await;
+
+    let request = backup_mock.single_request();
+    assert_eq!(request.body_json()["model"], "backup-model");
+    assert_eq!(
+        request.inputs_of_type("reasoning"),
+        Vec::<serde_json::Value>::new()
+    );
+    assert_eq!(
+        request
+            .message_input_texts("user")
+            .last()
+            .map(String::as_str),
+        Some("second")
+    );
+}
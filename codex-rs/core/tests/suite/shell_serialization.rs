@@ -434,7 +434,7 @@ Output:
 4
 5
 6
-.*…46 tokens truncated….*
+.*…606 tokens truncated….*
 396
 397
 398
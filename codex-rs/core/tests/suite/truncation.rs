@@ -311,7 +311,7 @@ Output:
 4
 5
 6
-.*…137224 tokens truncated.*
+.*…289112 tokens truncated.*
 99999
 100000
 $"#;
@@ -439,7 +439,7 @@ async fn mcp_tool_call_output_exceeds_limit_truncated_for_model() -> Result<()>
                 disabled_tools: None,
             },
         );
-        config.tool_output_token_limit = Some(500);
+        config.tool_output_token_limit = Some(350);
     });
     let fixture = builder.build(&server).await?;
 
@@ -568,7 +568,7 @@ async fn mcp_image_output_preserves_image_and_no_text_summary() -> Result<()> {
     Ok(())
 }
 
-// Token-based policy should report token counts even when truncation is byte-estimated.
+// Token-based policy should report token counts from the model's tokenizer.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn token_policy_marker_reports_tokens() -> Result<()> {
     skip_if_no_network!(Ok(()));
@@ -577,7 +577,7 @@ async fn token_policy_marker_reports_tokens() -> Result<()> {
     let mut builder = test_codex()
         .with_model("gpt-5.1-codex")
         .with_config(|config| {
-            config.tool_output_token_limit = Some(50); // small budget to force truncation
+            config.tool_output_token_limit = Some(100); // small budget to force truncation
         });
     let fixture = builder.build(&server).await?;
 
@@ -614,7 +614,7 @@ async fn token_policy_marker_reports_tokens() -> Result<()> {
         .function_call_output_text(call_id)
         .context("shell output present")?;
 
-    let pattern = r"(?s)^Exit code: 0\nWall time: [0-9]+(?:\.[0-9]+)? seconds\nTotal output lines: 150\nOutput:\n1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16\n17\n18\n19.*tokens truncated.*129\n130\n131\n132\n133\n134\n135\n136\n137\n138\n139\n140\n141\n142\n143\n144\n145\n146\n147\n148\n149\n150\n$";
+    let pattern = r"(?s)^Exit code: 0\nWall time: [0-9]+(?:\.[0-9]+)? seconds\nTotal output lines: 150\nOutput:\n1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16\n17\n18\n19\n20\n21\n22\n23\n24\n25.*tokens truncated.*131\n132\n133\n134\n135\n136\n137\n138\n139\n140\n141\n142\n143\n144\n145\n146\n147\n148\n149\n150\n$";
 
     assert_regex_match(pattern, &output);
 
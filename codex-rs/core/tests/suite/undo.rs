@@ -388,6 +388,31 @@ async fn undo_without_snapshot_reports_failure() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn undo_restores_files_outside_git_repository() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = undo_harness().await?;
+    let tracked = harness.path("notes.txt");
+    fs::write(&tracked, "original\n")?;
+
+    let call_id = "undo-plain-dir";
+    let patch = "*** Begin Patch\n*** Update File: notes.txt\n@@\n-original\n+edited\n*** Add File: new.txt\n+from turn\n*** End Patch";
+    run_apply_patch_turn(&harness, "edit outside git", call_id, patch, "ok").await?;
+
+    let new_path = harness.path("new.txt");
+    assert_eq!(fs::read_to_string(&tracked)?, "edited\n");
+    assert_eq!(fs::read_to_string(&new_path)?, "from turn\n");
+
+    let codex = Arc::clone(&harness.test().codex);
+    expect_successful_undo(&codex).await?;
+
+    assert_eq!(fs::read_to_string(&tracked)?, "original\n");
+    assert!(!new_path.exists());
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn undo_restores_moves_and_renames() -> Result<()> {
     skip_if_no_network!(Ok(()));
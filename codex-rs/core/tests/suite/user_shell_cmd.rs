@@ -258,10 +258,9 @@ async fn user_shell_command_output_is_truncated_in_history() -> anyhow::Result<(
         .expect("command message recorded in request");
     let command_message = command_message.replace("\r\n", "\n");
 
-    let head = (1..=69).map(|i| format!("{i}\n")).collect::<String>();
-    let tail = (352..=400).map(|i| format!("{i}\n")).collect::<String>();
-    let truncated_body =
-        format!("Total output lines: 400\n\n{head}70…273 tokens truncated…351\n{tail}");
+    let head = (1..=25).map(|i| format!("{i}\n")).collect::<String>();
+    let tail = (378..=400).map(|i| format!("{i}\n")).collect::<String>();
+    let truncated_body = format!("Total output lines: 400\n\n{head}…703 tokens truncated…\n{tail}");
     let escaped_command = escape(&command);
     let escaped_truncated_body = escape(&truncated_body);
     let expected_pattern = format!(
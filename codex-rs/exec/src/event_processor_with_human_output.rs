@@ -450,6 +450,17 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                                 }
                             }
                         }
+                        FileChange::Binary { .. }
+                        | FileChange::Symlink { .. }
+                        | FileChange::Mode { .. } => {
+                            let summary = change.non_text_summary().unwrap_or_default();
+                            let header = format!(
+                                "{} {} ({summary})",
+                                format_file_change(change),
+                                path.to_string_lossy()
+                            );
+                            eprintln!("{}", header.style(self.magenta));
+                        }
                     }
                 }
             }
@@ -585,6 +596,8 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             }
             EventMsg::WebSearchBegin(_)
             | EventMsg::ExecApprovalRequest(_)
+            | EventMsg::ApprovalDecision(_)
+            | EventMsg::PinnedMessagesUpdated(_)
             | EventMsg::ApplyPatchApprovalRequest(_)
             | EventMsg::TerminalInteraction(_)
             | EventMsg::ExecCommandOutputDelta(_)
@@ -592,6 +605,8 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::McpListToolsResponse(_)
             | EventMsg::ListCustomPromptsResponse(_)
             | EventMsg::ListSkillsResponse(_)
+            | EventMsg::ListCheckpointsResponse(_)
+            | EventMsg::CheckpointRestored(_)
             | EventMsg::UserMessage(_)
             | EventMsg::EnteredReviewMode(_)
             | EventMsg::ExitedReviewMode(_)
@@ -741,14 +756,20 @@ fn escape_command(command: &[String]) -> String {
 
 fn format_file_change(change: &FileChange) -> &'static str {
     match change {
-        FileChange::Add { .. } => "A",
+        FileChange::Add { .. }
+        | FileChange::Binary { old_size: None, .. }
+        | FileChange::Symlink { .. } => "A",
         FileChange::Delete { .. } => "D",
         FileChange::Update {
             move_path: Some(_), ..
         } => "R",
         FileChange::Update {
             move_path: None, ..
-        } => "M",
+        }
+        | FileChange::Binary {
+            old_size: Some(_), ..
+        }
+        | FileChange::Mode { .. } => "M",
     }
 }
 
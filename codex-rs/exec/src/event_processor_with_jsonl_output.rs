@@ -347,9 +347,15 @@ impl EventProcessorWithJsonOutput {
 
     fn map_change_kind(&self, kind: &FileChange) -> PatchChangeKind {
         match kind {
-            FileChange::Add { .. } => PatchChangeKind::Add,
+            FileChange::Add { .. }
+            | FileChange::Binary { old_size: None, .. }
+            | FileChange::Symlink { .. } => PatchChangeKind::Add,
             FileChange::Delete { .. } => PatchChangeKind::Delete,
-            FileChange::Update { .. } => PatchChangeKind::Update,
+            FileChange::Update { .. }
+            | FileChange::Binary {
+                old_size: Some(_), ..
+            }
+            | FileChange::Mode { .. } => PatchChangeKind::Update,
         }
     }
 
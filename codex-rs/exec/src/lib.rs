@@ -271,11 +271,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         std::process::exit(1);
     }
 
-    let auth_manager = AuthManager::shared(
-        config.codex_home.clone(),
-        true,
-        config.cli_auth_credentials_store_mode,
-    );
+    let auth_manager = AuthManager::shared_from_config(&config, true);
     let conversation_manager = ConversationManager::new(auth_manager.clone(), SessionSource::Exec);
     let default_model = conversation_manager
         .get_models_manager()
@@ -52,6 +52,18 @@ impl Policy {
         Ok(())
     }
 
+    /// Adds every rule from `other` to this policy. Evaluation picks the
+    /// strictest matching decision, so the order policies are merged in does
+    /// not affect the result.
+    pub fn merge(&mut self, other: &Policy) {
+        for (program, rules) in other.rules_by_program.iter_all() {
+            for rule in rules {
+                self.rules_by_program
+                    .insert(program.clone(), Arc::clone(rule));
+            }
+        }
+    }
+
     pub fn check<F>(&self, cmd: &[String], heuristics_fallback: &F) -> Evaluation
     where
         F: Fn(&[String]) -> Decision,
@@ -102,6 +102,42 @@ fn add_prefix_rule_extends_policy() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn merge_keeps_strictest_decision() -> Result<()> {
+    let mut user = Policy::empty();
+    user.add_prefix_rule(&tokens(&["git", "push"]), Decision::Forbidden)?;
+    let mut project = Policy::empty();
+    project.add_prefix_rule(&tokens(&["git"]), Decision::Allow)?;
+
+    let mut merged = project.clone();
+    merged.merge(&user);
+
+    let evaluation = merged.check(&tokens(&["git", "push", "origin"]), &allow_all);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Forbidden,
+            matched_rules: vec![
+                RuleMatch::PrefixRuleMatch {
+                    matched_prefix: tokens(&["git"]),
+                    decision: Decision::Allow,
+                },
+                RuleMatch::PrefixRuleMatch {
+                    matched_prefix: tokens(&["git", "push"]),
+                    decision: Decision::Forbidden,
+                },
+            ],
+        },
+        evaluation
+    );
+    assert_eq!(
+        Decision::Allow,
+        merged
+            .check(&tokens(&["git", "status"]), &prompt_all)
+            .decision
+    );
+    Ok(())
+}
+
 #[test]
 fn add_prefix_rule_rejects_empty_prefix() -> Result<()> {
     let mut policy = Policy::empty();
@@ -0,0 +1,446 @@
+//! In-process content search ("grep") over a directory tree.
+//!
+//! Walks the tree with the same `ignore` machinery used for fuzzy filename
+//! search (so `.gitignore`, `.ignore` and hidden-file rules match ripgrep) and
+//! scans each file with a `regex::Regex`. Files that look binary are skipped.
+
+use ignore::WalkBuilder;
+use ignore::overrides::OverrideBuilder;
+use regex::Regex;
+use regex::RegexBuilder;
+use std::cmp::Reverse;
+use std::io::Read;
+use std::num::NonZero;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::SystemTime;
+
+/// Number of leading bytes inspected for a NUL byte when deciding whether a
+/// file is binary. Mirrors the heuristic used by ripgrep and git.
+const BINARY_DETECTION_BYTES: usize = 8 * 1024;
+
+/// Options controlling a single content search.
+#[derive(Debug, Clone)]
+pub struct ContentSearchOptions {
+    /// Regular expression (or literal string when `fixed_strings` is set).
+    pub pattern: String,
+    /// Treat `pattern` as a literal string instead of a regular expression.
+    pub fixed_strings: bool,
+    /// Match case-insensitively.
+    pub case_insensitive: bool,
+    /// Globs that a file must match to be searched (e.g. `*.rs`).
+    pub include: Vec<String>,
+    /// Globs that exclude files or directories from the search.
+    pub exclude: Vec<String>,
+    /// Number of lines of context to report before and after each match.
+    pub context_lines: usize,
+    /// Stop scanning a file after this many matching lines.
+    pub max_matches_per_file: Option<NonZero<usize>>,
+    /// Honor `.gitignore`, `.ignore` and global git excludes.
+    pub respect_gitignore: bool,
+    /// Number of walker threads.
+    pub threads: NonZero<usize>,
+}
+
+impl ContentSearchOptions {
+    pub fn new(pattern: impl Into<String>) -> Self {
+        Self {
+            pattern: pattern.into(),
+            fixed_strings: false,
+            case_insensitive: false,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            context_lines: 0,
+            max_matches_per_file: None,
+            respect_gitignore: true,
+            threads: NonZero::new(2).unwrap_or(NonZero::<usize>::MIN),
+        }
+    }
+}
+
+/// Whether a reported line matched the pattern or is surrounding context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentLineKind {
+    Match,
+    Context,
+}
+
+/// A single line reported for a file, with its 1-based line number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentLine {
+    pub line_number: usize,
+    pub text: String,
+    pub kind: ContentLineKind,
+}
+
+/// All reported lines for one file that contained at least one match.
+#[derive(Debug, Clone)]
+pub struct FileContentMatches {
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+    pub lines: Vec<ContentLine>,
+}
+
+impl FileContentMatches {
+    pub fn match_count(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| line.kind == ContentLineKind::Match)
+            .count()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ContentSearchResults {
+    /// Matching files, most recently modified first.
+    pub files: Vec<FileContentMatches>,
+    /// `true` if the search stopped early because `cancel_flag` was set.
+    pub cancelled: bool,
+}
+
+/// Searches every non-ignored, non-binary file under `search_path` (which may
+/// also be a single file) for `options.pattern`.
+///
+/// The worker threads check `cancel_flag` between files; when it is set the
+/// walk stops and whatever was found so far is returned with `cancelled`.
+pub fn search_contents(
+    search_path: &Path,
+    options: &ContentSearchOptions,
+    cancel_flag: Arc<AtomicBool>,
+) -> anyhow::Result<ContentSearchResults> {
+    let matcher = build_matcher(options)?;
+
+    let mut walk_builder = WalkBuilder::new(search_path);
+    walk_builder
+        .threads(options.threads.get())
+        // Like `rg`, skip hidden files and do not follow symlinks.
+        .follow_links(false)
+        .require_git(false);
+    if !options.respect_gitignore {
+        walk_builder
+            .git_ignore(false)
+            .git_global(false)
+            .git_exclude(false)
+            .ignore(false)
+            .parents(false);
+    }
+
+    let overrides_root = if search_path.is_dir() {
+        search_path
+    } else {
+        search_path.parent().unwrap_or(search_path)
+    };
+    if !options.exclude.is_empty() {
+        let mut override_builder = OverrideBuilder::new(overrides_root);
+        for exclude in &options.exclude {
+            // The `!` prefix is used to indicate an exclude pattern.
+            override_builder.add(&format!("!{exclude}"))?;
+        }
+        walk_builder.overrides(override_builder.build()?);
+    }
+    // Include globs are checked per file rather than installed as walker
+    // overrides: whitelist overrides take precedence over `.gitignore`, which
+    // would make `include = "*.rs"` resurrect ignored build artifacts.
+    let include = if options.include.is_empty() {
+        None
+    } else {
+        let mut include_builder = OverrideBuilder::new(overrides_root);
+        for include in &options.include {
+            include_builder.add(include)?;
+        }
+        Some(include_builder.build()?)
+    };
+
+    let found: Mutex<Vec<FileContentMatches>> = Mutex::new(Vec::new());
+    walk_builder.build_parallel().run(|| {
+        let cancel = cancel_flag.clone();
+        let matcher = &matcher;
+        let include = include.as_ref();
+        let found = &found;
+        Box::new(move |entry| {
+            if cancel.load(Ordering::Relaxed) {
+                return ignore::WalkState::Quit;
+            }
+            let Ok(entry) = entry else {
+                return ignore::WalkState::Continue;
+            };
+            if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+                return ignore::WalkState::Continue;
+            }
+            if include.is_some_and(|include| !include.matched(entry.path(), false).is_whitelist()) {
+                return ignore::WalkState::Continue;
+            }
+            if let Some(file_matches) = search_file(entry.path(), matcher, options)
+                && let Ok(mut found) = found.lock()
+            {
+                found.push(file_matches);
+            }
+            ignore::WalkState::Continue
+        })
+    });
+
+    let mut files = found
+        .into_inner()
+        .map_err(|_| anyhow::anyhow!("content search worker panicked"))?;
+    // Most recently modified first (like `rg --sortr=modified`), then by path
+    // so that the output is deterministic.
+    files.sort_by(|a, b| {
+        Reverse(a.modified)
+            .cmp(&Reverse(b.modified))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    Ok(ContentSearchResults {
+        files,
+        cancelled: cancel_flag.load(Ordering::Relaxed),
+    })
+}
+
+fn build_matcher(options: &ContentSearchOptions) -> anyhow::Result<Regex> {
+    let pattern = if options.fixed_strings {
+        regex::escape(&options.pattern)
+    } else {
+        options.pattern.clone()
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(options.case_insensitive)
+        .build()
+        .map_err(|err| anyhow::anyhow!("invalid pattern: {err}"))
+}
+
+/// Returns `None` if the file cannot be read, looks binary, or has no match.
+fn search_file(
+    path: &Path,
+    matcher: &Regex,
+    options: &ContentSearchOptions,
+) -> Option<FileContentMatches> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let modified = file.metadata().ok().and_then(|meta| meta.modified().ok());
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).ok()?;
+    if is_binary(&bytes) {
+        return None;
+    }
+
+    let text = String::from_utf8_lossy(&bytes);
+    let lines = search_lines(&text, matcher, options);
+    if lines.is_empty() {
+        return None;
+    }
+
+    Some(FileContentMatches {
+        path: path.to_path_buf(),
+        modified,
+        lines,
+    })
+}
+
+fn is_binary(bytes: &[u8]) -> bool {
+    let head = &bytes[..bytes.len().min(BINARY_DETECTION_BYTES)];
+    head.contains(&0)
+}
+
+/// Collects matching lines plus `context_lines` of surrounding context,
+/// merging overlapping context windows so each line is reported once.
+fn search_lines(text: &str, matcher: &Regex, options: &ContentSearchOptions) -> Vec<ContentLine> {
+    let all_lines: Vec<&str> = text
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect();
+    // A trailing newline produces an empty final element that is not a line.
+    let line_count = if text.ends_with('\n') {
+        all_lines.len().saturating_sub(1)
+    } else {
+        all_lines.len()
+    };
+
+    let max_matches = options.max_matches_per_file.map(NonZero::get);
+    let context = options.context_lines;
+    let mut out: Vec<ContentLine> = Vec::new();
+    // Index (0-based) of the next line that has not been emitted yet.
+    let mut next_unemitted = 0;
+    let mut match_count = 0;
+    let mut pending_after: Option<usize> = None;
+
+    for (idx, line) in all_lines.iter().take(line_count).enumerate() {
+        if max_matches.is_some_and(|max| match_count >= max) {
+            break;
+        }
+        if !matcher.is_match(line) {
+            continue;
+        }
+
+        if let Some(end) = pending_after.take() {
+            let end = end.min(idx);
+            push_context(&mut out, &all_lines, next_unemitted, end);
+            next_unemitted = next_unemitted.max(end);
+        }
+        let start = idx.saturating_sub(context).max(next_unemitted);
+        push_context(&mut out, &all_lines, start, idx);
+        out.push(ContentLine {
+            line_number: idx + 1,
+            text: (*line).to_string(),
+            kind: ContentLineKind::Match,
+        });
+        next_unemitted = idx + 1;
+        match_count += 1;
+        pending_after = Some((idx + 1 + context).min(line_count));
+    }
+
+    if let Some(end) = pending_after {
+        push_context(&mut out, &all_lines, next_unemitted, end);
+    }
+
+    out
+}
+
+fn push_context(out: &mut Vec<ContentLine>, lines: &[&str], start: usize, end: usize) {
+    for (offset, line) in lines.iter().enumerate().take(end).skip(start) {
+        out.push(ContentLine {
+            line_number: offset + 1,
+            text: (*line).to_string(),
+            kind: ContentLineKind::Context,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn run(dir: &Path, options: &ContentSearchOptions) -> Vec<FileContentMatches> {
+        search_contents(dir, options, Arc::new(AtomicBool::new(false)))
+            .expect("search succeeds")
+            .files
+    }
+
+    fn file_names(files: &[FileContentMatches]) -> Vec<String> {
+        let mut names: Vec<String> = files
+            .iter()
+            .filter_map(|f| f.path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn finds_regex_matches_and_skips_binary_files() {
+        let temp = tempdir().expect("tempdir");
+        let dir = temp.path();
+        std::fs::write(dir.join("a.rs"), "fn alpha() {}\n").unwrap();
+        std::fs::write(dir.join("b.txt"), "beta\nalphabet\n").unwrap();
+        std::fs::write(dir.join("c.bin"), b"alpha\0binary").unwrap();
+
+        let files = run(dir, &ContentSearchOptions::new("alpha"));
+        assert_eq!(file_names(&files), vec!["a.rs", "b.txt"]);
+    }
+
+    #[test]
+    fn honors_gitignore_and_globs() {
+        let temp = tempdir().expect("tempdir");
+        let dir = temp.path();
+        std::fs::write(dir.join(".gitignore"), "ignored.rs\n").unwrap();
+        std::fs::write(dir.join("ignored.rs"), "needle\n").unwrap();
+        std::fs::write(dir.join("kept.rs"), "needle\n").unwrap();
+        std::fs::write(dir.join("kept.txt"), "needle\n").unwrap();
+        std::fs::create_dir(dir.join("vendor")).unwrap();
+        std::fs::write(dir.join("vendor").join("dep.rs"), "needle\n").unwrap();
+
+        let mut options = ContentSearchOptions::new("needle");
+        options.include = vec!["*.rs".to_string()];
+        options.exclude = vec!["vendor".to_string()];
+        assert_eq!(file_names(&run(dir, &options)), vec!["kept.rs"]);
+
+        options.include.clear();
+        options.exclude.clear();
+        options.respect_gitignore = false;
+        assert_eq!(
+            file_names(&run(dir, &options)),
+            vec!["dep.rs", "ignored.rs", "kept.rs", "kept.txt"]
+        );
+    }
+
+    #[test]
+    fn fixed_strings_and_case_insensitive() {
+        let temp = tempdir().expect("tempdir");
+        let dir = temp.path();
+        std::fs::write(dir.join("a.txt"), "call foo(bar)\n").unwrap();
+        std::fs::write(dir.join("b.txt"), "FOO(BAR)\n").unwrap();
+
+        let mut options = ContentSearchOptions::new("foo(bar)");
+        options.fixed_strings = true;
+        assert_eq!(file_names(&run(dir, &options)), vec!["a.txt"]);
+
+        options.case_insensitive = true;
+        assert_eq!(file_names(&run(dir, &options)), vec!["a.txt", "b.txt"]);
+    }
+
+    #[test]
+    fn reports_context_and_respects_per_file_limit() {
+        let temp = tempdir().expect("tempdir");
+        let file = temp.path().join("a.txt");
+        std::fs::write(
+            &file,
+            "one\nhit two\nthree\nfour\nhit five\nsix\nhit seven\n",
+        )
+        .unwrap();
+
+        let mut options = ContentSearchOptions::new("hit");
+        options.context_lines = 1;
+        options.max_matches_per_file = NonZero::new(2);
+        let files = run(&file, &options);
+        assert_eq!(files.len(), 1);
+
+        let lines: Vec<(usize, ContentLineKind)> = files[0]
+            .lines
+            .iter()
+            .map(|line| (line.line_number, line.kind))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (1, ContentLineKind::Context),
+                (2, ContentLineKind::Match),
+                (3, ContentLineKind::Context),
+                (4, ContentLineKind::Context),
+                (5, ContentLineKind::Match),
+                (6, ContentLineKind::Context),
+            ]
+        );
+        assert_eq!(files[0].match_count(), 2);
+    }
+
+    #[test]
+    fn cancelled_search_returns_early() {
+        let temp = tempdir().expect("tempdir");
+        std::fs::write(temp.path().join("a.txt"), "needle\n").unwrap();
+
+        let results = search_contents(
+            temp.path(),
+            &ContentSearchOptions::new("needle"),
+            Arc::new(AtomicBool::new(true)),
+        )
+        .expect("search succeeds");
+        assert!(results.cancelled);
+        assert!(results.files.is_empty());
+    }
+
+    #[test]
+    fn invalid_regex_is_an_error() {
+        let temp = tempdir().expect("tempdir");
+        let err = search_contents(
+            temp.path(),
+            &ContentSearchOptions::new("("),
+            Arc::new(AtomicBool::new(false)),
+        )
+        .expect_err("invalid regex");
+        assert!(err.to_string().starts_with("invalid pattern"));
+    }
+}
@@ -0,0 +1,640 @@
+//! Long-lived, incrementally updated file index used for fuzzy `@` searches.
+//!
+//! [`run`](crate::run) walks the whole tree on every query, which is slow in
+//! large repositories when it is called on every keystroke. A [`FileIndex`]
+//! walks the tree once in the background, keeps the list of paths warm with a
+//! file watcher, and scores queries against the in-memory list. Indexes are
+//! shared per workspace root within the process via [`FileIndex::for_root`],
+//! so the TUI and app-server reuse the same index for the same directory.
+//!
+//! Files recently read or edited by the agent can be reported through
+//! [`record_recent_file`]; matching queries rank them higher.
+//!
+//! On Linux, where inotify needs one watch per directory, only directories
+//! that survive the ignore rules are watched, so trees such as `target/` or
+//! `node_modules/` do not use up watch handles.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::num::NonZero;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::RwLock;
+use std::sync::Weak;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use ignore::WalkBuilder;
+use notify::EventKind;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use nucleo_matcher::Matcher;
+use tracing::warn;
+
+use crate::BestMatchesList;
+use crate::FileSearchResults;
+use crate::build_file_matches;
+use crate::create_pattern;
+
+/// Number of recently touched files remembered for recency boosting.
+const MAX_RECENT_FILES: usize = 64;
+
+/// Score bonus for the most recently touched file; older entries receive a
+/// linearly decreasing share of it.
+const MAX_RECENCY_BOOST: u32 = 64;
+
+/// How long to wait for more watcher events before applying a batch.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Threads used when falling back to a full walk before the index is ready.
+const FALLBACK_THREADS: NonZero<usize> = NonZero::new(2).unwrap();
+
+/// Names of ignore files whose modification invalidates the whole index.
+const IGNORE_FILE_NAMES: &[&str] = &[".gitignore", ".ignore"];
+
+/// Watch each indexed directory on its own instead of the root recursively.
+/// A recursive inotify watch registers every directory, ignored or not; the
+/// other backends watch a whole tree with a single handle.
+const WATCH_EACH_DIRECTORY: bool = cfg!(any(target_os = "linux", target_os = "android"));
+
+fn registry() -> &'static Mutex<HashMap<PathBuf, Weak<FileIndex>>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<PathBuf, Weak<FileIndex>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn recent_files() -> &'static Mutex<VecDeque<PathBuf>> {
+    static RECENT: OnceLock<Mutex<VecDeque<PathBuf>>> = OnceLock::new();
+    RECENT.get_or_init(|| Mutex::new(VecDeque::new()))
+}
+
+/// Records that `path` (absolute) was recently read or edited so that fuzzy
+/// searches in any workspace containing it rank it higher.
+pub fn record_recent_file(path: &Path) {
+    // Index roots are canonical, so resolve symlinks the same way before
+    // matching against them.
+    let path = dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let Ok(mut recent) = recent_files().lock() else {
+        return;
+    };
+    recent.retain(|existing| existing != &path);
+    recent.push_front(path);
+    recent.truncate(MAX_RECENT_FILES);
+}
+
+/// Relative path -> score boost for the recent files that live under `root`.
+fn recency_boosts(root: &Path) -> HashMap<String, u32> {
+    let Ok(recent) = recent_files().lock() else {
+        return HashMap::new();
+    };
+    let len = recent.len() as u32;
+    recent
+        .iter()
+        .enumerate()
+        .filter_map(|(position, path)| {
+            let rel = path.strip_prefix(root).ok()?.to_str()?.to_string();
+            let boost = MAX_RECENCY_BOOST * (len - position as u32) / len.max(1);
+            Some((rel, boost))
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct IndexState {
+    /// `false` until the initial walk has completed.
+    ready: bool,
+    /// Paths of all indexed files, relative to the root.
+    paths: BTreeSet<String>,
+}
+
+/// File watcher plus, when [`WATCH_EACH_DIRECTORY`] is set, the directories
+/// it currently watches. The `.git` directory is watched separately and is not
+/// tracked in `dirs`.
+struct DirWatcher {
+    watcher: RecommendedWatcher,
+    dirs: HashSet<PathBuf>,
+}
+
+impl DirWatcher {
+    /// Replaces the watched directories at or below `path` with `dirs`.
+    fn replace_under(&mut self, path: &Path, dirs: Vec<PathBuf>) {
+        if !WATCH_EACH_DIRECTORY {
+            return;
+        }
+        let dirs: HashSet<PathBuf> = dirs.into_iter().collect();
+        let stale: Vec<PathBuf> = self
+            .dirs
+            .iter()
+            .filter(|dir| dir.starts_with(path) && !dirs.contains(*dir))
+            .cloned()
+            .collect();
+        for dir in stale {
+            // Watches on deleted directories are already gone.
+            let _ = self.watcher.unwatch(&dir);
+            self.dirs.remove(&dir);
+        }
+        for dir in dirs {
+            if self.dirs.contains(&dir) {
+                continue;
+            }
+            match self.watcher.watch(&dir, RecursiveMode::NonRecursive) {
+                Ok(()) => {
+                    self.dirs.insert(dir);
+                }
+                Err(err) => warn!("failed to watch {}: {err}", dir.display()),
+            }
+        }
+    }
+}
+
+/// In-memory list of the files under a workspace root, kept up to date by a
+/// file watcher.
+pub struct FileIndex {
+    root: PathBuf,
+    state: Arc<RwLock<IndexState>>,
+    /// Dropping the watcher stops delivering events, which in turn ends the
+    /// background update thread.
+    _watcher: Option<Arc<Mutex<DirWatcher>>>,
+}
+
+impl FileIndex {
+    /// Returns the process-wide index for `root`, creating it (and starting
+    /// the initial background walk) if it does not exist yet.
+    pub fn for_root(root: &Path) -> Arc<FileIndex> {
+        // Canonicalize so that differently spelled paths share one index; keep
+        // the caller's spelling if the directory cannot be resolved.
+        let root = dunce::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+        let Ok(mut registry) = registry().lock() else {
+            return Arc::new(FileIndex::new(root));
+        };
+        if let Some(existing) = registry.get(&root).and_then(Weak::upgrade) {
+            return existing;
+        }
+        let index = Arc::new(FileIndex::new(root.clone()));
+        registry.retain(|_, index| index.strong_count() > 0);
+        registry.insert(root, Arc::downgrade(&index));
+        index
+    }
+
+    fn new(root: PathBuf) -> Self {
+        let state = Arc::new(RwLock::new(IndexState::default()));
+        let (tx, rx) = mpsc::channel::<PathBuf>();
+
+        let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res
+                && !matches!(event.kind, EventKind::Access(_))
+            {
+                for path in event.paths {
+                    let _ = tx.send(path);
+                }
+            }
+        })
+        .and_then(|mut watcher| {
+            if WATCH_EACH_DIRECTORY {
+                // The walk never enters `.git`, so watch it directly to notice
+                // checkouts rewriting HEAD.
+                let git_dir = root.join(".git");
+                if git_dir.is_dir()
+                    && let Err(err) = watcher.watch(&git_dir, RecursiveMode::NonRecursive)
+                {
+                    warn!("failed to watch {}: {err}", git_dir.display());
+                }
+            } else {
+                watcher.watch(&root, RecursiveMode::Recursive)?;
+            }
+            Ok(Arc::new(Mutex::new(DirWatcher {
+                watcher,
+                dirs: HashSet::new(),
+            })))
+        })
+        .map_err(|err| {
+            warn!(
+                "file index watcher unavailable for {}: {err}",
+                root.display()
+            )
+        })
+        .ok();
+
+        let thread_root = root.clone();
+        let thread_state = Arc::downgrade(&state);
+        let thread_watcher = watcher.as_ref().map(Arc::downgrade);
+        std::thread::spawn(move || {
+            if let Some(state) = thread_state.upgrade() {
+                let dirs = rebuild(&thread_root, &state);
+                update_watches(thread_watcher.as_ref(), &thread_root, dirs);
+            }
+            process_events(&thread_root, &thread_state, thread_watcher.as_ref(), &rx);
+        });
+
+        Self {
+            root,
+            state,
+            _watcher: watcher,
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// `true` once the initial walk has completed.
+    pub fn is_ready(&self) -> bool {
+        self.state.read().map(|state| state.ready).unwrap_or(false)
+    }
+
+    /// Number of indexed files.
+    pub fn len(&self) -> usize {
+        self.state
+            .read()
+            .map(|state| state.paths.len())
+            .unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Fuzzy-matches `pattern_text` against the indexed paths. Until the
+    /// initial walk completes this falls back to a one-off walk via
+    /// [`run`](crate::run), so callers always get results.
+    pub fn search(
+        &self,
+        pattern_text: &str,
+        limit: NonZero<usize>,
+        cancel_flag: Arc<AtomicBool>,
+        compute_indices: bool,
+    ) -> anyhow::Result<FileSearchResults> {
+        let state = self
+            .state
+            .read()
+            .map_err(|_| anyhow::anyhow!("file index lock poisoned"))?;
+        if !state.ready {
+            drop(state);
+            return crate::run(
+                pattern_text,
+                limit,
+                &self.root,
+                Vec::new(),
+                FALLBACK_THREADS,
+                cancel_flag,
+                compute_indices,
+                true,
+            );
+        }
+
+        let pattern = create_pattern(pattern_text);
+        let boosts = recency_boosts(&self.root);
+        let mut best = BestMatchesList::new(
+            limit.get(),
+            pattern.clone(),
+            Matcher::new(nucleo_matcher::Config::DEFAULT),
+        );
+
+        // Only read the atomic flag every N entries, as in `run`.
+        const CHECK_INTERVAL: usize = 1024;
+        for (processed, path) in state.paths.iter().enumerate() {
+            if processed % CHECK_INTERVAL == 0 && cancel_flag.load(Ordering::Relaxed) {
+                return Ok(FileSearchResults {
+                    matches: Vec::new(),
+                    total_match_count: 0,
+                });
+            }
+            let boost = boosts.get(path).copied().unwrap_or(0);
+            best.insert_with_boost(path, boost);
+        }
+        drop(state);
+
+        let total_match_count = best.num_matches;
+        let raw_matches: Vec<(u32, String)> =
+            best.binary_heap.into_iter().map(|entry| entry.0).collect();
+        Ok(FileSearchResults {
+            matches: build_file_matches(raw_matches, &pattern, compute_indices),
+            total_match_count,
+        })
+    }
+}
+
+fn walk_builder(path: &Path) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(path);
+    builder
+        // Match `run`: include hidden entries and respect ignore files even
+        // outside of git repositories.
+        .hidden(false)
+        .follow_links(true)
+        .require_git(false)
+        // Never index git internals; they churn constantly.
+        .filter_entry(|entry| entry.file_name() != ".git");
+    builder
+}
+
+/// Files and directories found by walking part of the tree.
+#[derive(Default)]
+struct Entries {
+    /// File paths relative to the root.
+    files: Vec<String>,
+    /// Absolute paths of the directories that were walked.
+    dirs: Vec<PathBuf>,
+}
+
+/// Walks `dir` (recursively), skipping ignored entries.
+fn collect_entries(root: &Path, dir: &Path) -> Entries {
+    let mut entries = Entries::default();
+    for entry in walk_builder(dir).build().filter_map(Result::ok) {
+        if entry.file_type().is_some_and(|ft| ft.is_dir()) {
+            entries.dirs.push(entry.into_path());
+        } else if let Some(rel) = relative_path(root, entry.path()) {
+            entries.files.push(rel);
+        }
+    }
+    entries
+}
+
+fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    path.strip_prefix(root)
+        .ok()
+        .and_then(Path::to_str)
+        .map(str::to_string)
+}
+
+/// Re-walks the whole tree and returns the directories it contains.
+fn rebuild(root: &Path, state: &RwLock<IndexState>) -> Vec<PathBuf> {
+    let Entries { files, dirs } = collect_entries(root, root);
+    if let Ok(mut state) = state.write() {
+        state.paths = files.into_iter().collect();
+        state.ready = true;
+    }
+    dirs
+}
+
+fn update_watches(watcher: Option<&Weak<Mutex<DirWatcher>>>, path: &Path, dirs: Vec<PathBuf>) {
+    if let Some(watcher) = watcher.and_then(Weak::upgrade)
+        && let Ok(mut watcher) = watcher.lock()
+    {
+        watcher.replace_under(path, dirs);
+    }
+}
+
+/// Blocks on watcher events until the index (and with it the watcher) is
+/// dropped, applying changes in small batches.
+fn process_events(
+    root: &Path,
+    state: &Weak<RwLock<IndexState>>,
+    watcher: Option<&Weak<Mutex<DirWatcher>>>,
+    rx: &mpsc::Receiver<PathBuf>,
+) {
+    while let Ok(first) = rx.recv() {
+        let mut batch: HashSet<PathBuf> = HashSet::from([first]);
+        while let Ok(path) = rx.recv_timeout(WATCH_DEBOUNCE) {
+            batch.insert(path);
+        }
+        let Some(state) = state.upgrade() else {
+            return;
+        };
+        for (path, dirs) in apply_changes(root, &state, batch) {
+            update_watches(watcher, &path, dirs);
+        }
+    }
+}
+
+/// Applies a batch of changed paths and returns, per refreshed path, the
+/// directories now found at or below it.
+fn apply_changes(
+    root: &Path,
+    state: &RwLock<IndexState>,
+    changed: HashSet<PathBuf>,
+) -> Vec<(PathBuf, Vec<PathBuf>)> {
+    let mut needs_rebuild = false;
+    let mut refresh: Vec<PathBuf> = Vec::new();
+    for path in changed {
+        let Ok(rel) = path.strip_prefix(root) else {
+            continue;
+        };
+        if rel.starts_with(".git") {
+            // A checkout rewrites HEAD; rebuild instead of trusting the flood
+            // of individual events that accompanies it.
+            if rel == Path::new(".git").join("HEAD") {
+                needs_rebuild = true;
+            }
+            continue;
+        }
+        let is_ignore_file = rel
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| IGNORE_FILE_NAMES.contains(&name));
+        if is_ignore_file {
+            needs_rebuild = true;
+        }
+        refresh.push(path);
+    }
+
+    if needs_rebuild {
+        let dirs = rebuild(root, state);
+        return vec![(root.to_path_buf(), dirs)];
+    }
+
+    refresh
+        .into_iter()
+        .map(|path| {
+            let dirs = refresh_path(root, state, &path);
+            (path, dirs)
+        })
+        .collect()
+}
+
+/// Re-synchronizes the index entries for `path` (a file or directory) with
+/// the file system and returns the directories now found at or below it.
+fn refresh_path(root: &Path, state: &RwLock<IndexState>, path: &Path) -> Vec<PathBuf> {
+    let Some(rel) = relative_path(root, path) else {
+        return Vec::new();
+    };
+    if rel.is_empty() {
+        return Vec::new();
+    }
+
+    // Walk the parent directory one level deep so that ignore rules are
+    // applied to `path` exactly as they are during a full walk.
+    let mut current = Entries::default();
+    if let Some(parent) = path.parent() {
+        let entry = walk_builder(parent)
+            .max_depth(Some(1))
+            .build()
+            .filter_map(Result::ok)
+            .find(|entry| entry.path() == path);
+        if let Some(entry) = entry {
+            if entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                current = collect_entries(root, path);
+            } else {
+                current.files.push(rel.clone());
+            }
+        }
+    }
+
+    let Ok(mut state) = state.write() else {
+        return current.dirs;
+    };
+    let dir_prefix = format!("{rel}{}", std::path::MAIN_SEPARATOR);
+    state
+        .paths
+        .retain(|existing| existing != &rel && !existing.starts_with(&dir_prefix));
+    state.paths.extend(current.files);
+    current.dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Instant;
+    use tempfile::tempdir;
+
+    fn wait_until(mut condition: impl FnMut() -> bool) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while Instant::now() < deadline {
+            if condition() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+
+    fn search_paths(index: &FileIndex, query: &str) -> Vec<String> {
+        index
+            .search(
+                query,
+                NonZero::new(10).unwrap(),
+                Arc::new(AtomicBool::new(false)),
+                false,
+            )
+            .unwrap()
+            .matches
+            .into_iter()
+            .map(|file_match| file_match.path)
+            .collect()
+    }
+
+    #[test]
+    fn for_root_shares_index_per_root() {
+        let temp = tempdir().unwrap();
+        let first = FileIndex::for_root(temp.path());
+        let second = FileIndex::for_root(temp.path());
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn indexes_files_and_respects_gitignore() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join("src").join("main.rs"), "").unwrap();
+        std::fs::write(root.join("target").join("main.o"), "").unwrap();
+
+        let index = FileIndex::for_root(root);
+        assert!(wait_until(|| index.is_ready()));
+        let main_rs = Path::new("src").join("main.rs").display().to_string();
+        assert_eq!(search_paths(&index, "main"), vec![main_rs]);
+    }
+
+    #[test]
+    fn collect_entries_skips_ignored_directories() {
+        let temp = tempdir().unwrap();
+        let root = dunce::canonicalize(temp.path()).unwrap();
+        std::fs::write(root.join(".gitignore"), "node_modules/\n").unwrap();
+        std::fs::create_dir_all(root.join("src").join("bin")).unwrap();
+        std::fs::create_dir_all(root.join("node_modules").join("left-pad")).unwrap();
+
+        let mut dirs = collect_entries(&root, &root).dirs;
+        dirs.sort();
+        assert_eq!(
+            dirs,
+            vec![root.clone(), root.join("src"), root.join("src").join("bin")]
+        );
+    }
+
+    #[test]
+    fn refresh_path_tracks_additions_and_removals() {
+        let temp = tempdir().unwrap();
+        let root = dunce::canonicalize(temp.path()).unwrap();
+        std::fs::create_dir_all(root.join("pkg")).unwrap();
+        std::fs::write(root.join("pkg").join("a.txt"), "").unwrap();
+
+        let state = RwLock::new(IndexState::default());
+        rebuild(&root, &state);
+        assert_eq!(state.read().unwrap().paths.len(), 1);
+
+        std::fs::write(root.join("pkg").join("b.txt"), "").unwrap();
+        refresh_path(&root, &state, &root.join("pkg").join("b.txt"));
+        assert_eq!(state.read().unwrap().paths.len(), 2);
+
+        std::fs::create_dir_all(root.join("pkg").join("nested")).unwrap();
+        assert_eq!(
+            refresh_path(&root, &state, &root.join("pkg")),
+            vec![root.join("pkg"), root.join("pkg").join("nested")]
+        );
+
+        std::fs::remove_dir_all(root.join("pkg")).unwrap();
+        assert!(refresh_path(&root, &state, &root.join("pkg")).is_empty());
+        assert!(state.read().unwrap().paths.is_empty());
+    }
+
+    #[test]
+    fn rewriting_git_head_rebuilds_index() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join(".git").join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        std::fs::write(root.join("lib.rs"), "").unwrap();
+
+        let index = FileIndex::for_root(root);
+        assert!(wait_until(|| index.is_ready()));
+        assert_eq!(index.len(), 1);
+
+        // Forget the indexed files so that only a full rebuild restores them.
+        index.state.write().unwrap().paths.clear();
+        std::fs::write(root.join(".git").join("HEAD"), "ref: refs/heads/other\n").unwrap();
+        assert!(wait_until(|| index.len() == 1));
+    }
+
+    #[test]
+    fn recent_files_rank_higher() {
+        let temp = tempdir().unwrap();
+        let root = dunce::canonicalize(temp.path()).unwrap();
+        std::fs::write(root.join("config_a.rs"), "").unwrap();
+        std::fs::write(root.join("config_b.rs"), "").unwrap();
+
+        let index = FileIndex::for_root(&root);
+        assert!(wait_until(|| index.is_ready()));
+        assert_eq!(search_paths(&index, "config")[0], "config_a.rs");
+
+        record_recent_file(&root.join("config_b.rs"));
+        assert_eq!(search_paths(&index, "config")[0], "config_b.rs");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn recent_files_recorded_through_symlink_rank_higher() {
+        let temp = tempdir().unwrap();
+        let root = dunce::canonicalize(temp.path()).unwrap().join("repo");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("setup_a.rs"), "").unwrap();
+        std::fs::write(root.join("setup_b.rs"), "").unwrap();
+        let link = root.with_file_name("link");
+        std::os::unix::fs::symlink(&root, &link).unwrap();
+
+        let index = FileIndex::for_root(&link);
+        assert!(wait_until(|| index.is_ready()));
+        assert_eq!(search_paths(&index, "setup")[0], "setup_a.rs");
+
+        record_recent_file(&link.join("setup_b.rs"));
+        assert_eq!(search_paths(&index, "setup")[0], "setup_b.rs");
+    }
+}
@@ -19,8 +19,18 @@ use std::sync::atomic::Ordering;
 use tokio::process::Command;
 
 mod cli;
+mod content_search;
+mod index;
 
 pub use cli::Cli;
+pub use content_search::ContentLine;
+pub use content_search::ContentLineKind;
+pub use content_search::ContentSearchOptions;
+pub use content_search::ContentSearchResults;
+pub use content_search::FileContentMatches;
+pub use content_search::search_contents;
+pub use index::FileIndex;
+pub use index::record_recent_file;
 
 /// A single match result returned from the search.
 ///
@@ -257,7 +267,22 @@ pub fn run(
         }
     }
 
-    let mut raw_matches: Vec<(u32, String)> = global_heap.into_iter().map(|r| r.0).collect();
+    let raw_matches: Vec<(u32, String)> = global_heap.into_iter().map(|r| r.0).collect();
+    let matches = build_file_matches(raw_matches, &pattern, compute_indices);
+
+    Ok(FileSearchResults {
+        matches,
+        total_match_count,
+    })
+}
+
+/// Sorts the raw `(score, path)` pairs and converts them into [`FileMatch`]es,
+/// optionally computing the matched character indices.
+fn build_file_matches(
+    mut raw_matches: Vec<(u32, String)>,
+    pattern: &Pattern,
+    compute_indices: bool,
+) -> Vec<FileMatch> {
     sort_matches(&mut raw_matches);
 
     // Transform into `FileMatch`, optionally computing indices.
@@ -267,7 +292,7 @@ pub fn run(
         None
     };
 
-    let matches: Vec<FileMatch> = raw_matches
+    raw_matches
         .into_iter()
         .map(|(score, path)| {
             let indices = if compute_indices {
@@ -291,12 +316,7 @@ pub fn run(
                 indices,
             }
         })
-        .collect();
-
-    Ok(FileSearchResults {
-        matches,
-        total_match_count,
-    })
+        .collect()
 }
 
 /// Sort matches in-place by descending score, then ascending path.
@@ -349,8 +369,15 @@ impl BestMatchesList {
     }
 
     fn insert(&mut self, line: &str) {
+        self.insert_with_boost(line, 0);
+    }
+
+    /// Like [`BestMatchesList::insert`], but adds `boost` to the score of a
+    /// matching line (used to rank recently touched files higher).
+    fn insert_with_boost(&mut self, line: &str, boost: u32) {
         let haystack: Utf32Str<'_> = Utf32Str::new(line, &mut self.utf32buf);
         if let Some(score) = self.pattern.score(haystack, &mut self.matcher) {
+            let score = score.saturating_add(boost);
             // In the tests below, we verify that score() returns None for a
             // non-match, so we can categorically increment the count here.
             self.num_matches += 1;
@@ -0,0 +1,324 @@
+//! Passphrase-encrypted credential files.
+//!
+//! Encrypted files hold a small JSON envelope instead of the plaintext
+//! document. The encryption key is derived with Argon2id from a passphrase
+//! taken from `CODEX_AUTH_KEY`, or from the contents of the file named by
+//! `CODEX_AUTH_KEY_FILE`, and the payload is sealed with XChaCha20-Poly1305.
+//! Every write uses a fresh salt and nonce.
+
+use std::fmt;
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use argon2::Algorithm;
+use argon2::Argon2;
+use argon2::Params;
+use argon2::Version;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chacha20poly1305::Key;
+use chacha20poly1305::KeyInit;
+use chacha20poly1305::XChaCha20Poly1305;
+use chacha20poly1305::XNonce;
+use chacha20poly1305::aead::Aead;
+use rand::RngCore;
+use serde::Deserialize;
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+/// Environment variable holding the passphrase used to encrypt credential files.
+pub const CODEX_AUTH_KEY_ENV_VAR: &str = "CODEX_AUTH_KEY";
+/// Environment variable naming a file whose contents are used as the passphrase.
+pub const CODEX_AUTH_KEY_FILE_ENV_VAR: &str = "CODEX_AUTH_KEY_FILE";
+
+const ENVELOPE_VERSION: u32 = 1;
+const KDF_ARGON2ID: &str = "argon2id";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+/// Upper bounds on the Argon2 parameters accepted from a file, so a crafted
+/// envelope cannot make key derivation take gigabytes of memory or minutes.
+/// Files are written with [`Params::default`], well below these caps.
+const MAX_M_COST_KIB: u32 = 256 * 1024;
+const MAX_T_COST: u32 = 16;
+const MAX_P_COST: u32 = 16;
+
+/// How a credential file should be written. Reads always accept both
+/// plaintext and encrypted files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileEncryption {
+    /// Write plaintext JSON.
+    Plaintext,
+    /// Encrypt when a key is configured; otherwise write plaintext.
+    WhenKeyAvailable,
+    /// Always encrypt; fail when no key is configured.
+    Required,
+}
+
+/// Passphrase used to derive the file encryption key.
+#[derive(Clone)]
+pub struct FileEncryptionKey {
+    passphrase: String,
+}
+
+impl fmt::Debug for FileEncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileEncryptionKey").finish_non_exhaustive()
+    }
+}
+
+impl FileEncryptionKey {
+    pub fn new(passphrase: impl Into<String>) -> Self {
+        Self {
+            passphrase: passphrase.into(),
+        }
+    }
+
+    /// Reads the passphrase from `CODEX_AUTH_KEY`, falling back to the file
+    /// named by `CODEX_AUTH_KEY_FILE`. Returns `None` when neither is set.
+    pub fn from_env() -> io::Result<Option<Self>> {
+        if let Ok(passphrase) = std::env::var(CODEX_AUTH_KEY_ENV_VAR)
+            && !passphrase.is_empty()
+        {
+            return Ok(Some(Self::new(passphrase)));
+        }
+        let Some(key_file) = std::env::var_os(CODEX_AUTH_KEY_FILE_ENV_VAR) else {
+            return Ok(None);
+        };
+        let path = Path::new(&key_file);
+        let contents = std::fs::read_to_string(path).map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!(
+                    "failed to read {CODEX_AUTH_KEY_FILE_ENV_VAR} key file {}: {err}",
+                    path.display()
+                ),
+            )
+        })?;
+        let passphrase = contents.trim();
+        if passphrase.is_empty() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "{CODEX_AUTH_KEY_FILE_ENV_VAR} key file {} is empty",
+                    path.display()
+                ),
+            ));
+        }
+        Ok(Some(Self::new(passphrase)))
+    }
+
+    fn encrypt(&self, plaintext: &str) -> io::Result<String> {
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        let mut rng = rand::rng();
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut nonce);
+
+        let params = Params::default();
+        let cipher = self.cipher(&salt, &params)?;
+        let ciphertext = cipher
+            .encrypt(XNonce::from_slice(&nonce), plaintext.as_bytes())
+            .map_err(|_| io::Error::other("failed to encrypt credentials"))?;
+
+        let envelope = Envelope {
+            codex_encrypted: ENVELOPE_VERSION,
+            kdf: KDF_ARGON2ID.to_string(),
+            m_cost: params.m_cost(),
+            t_cost: params.t_cost(),
+            p_cost: params.p_cost(),
+            salt: BASE64.encode(salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+        Ok(serde_json::to_string_pretty(&envelope)?)
+    }
+
+    fn decrypt(&self, envelope: &Envelope, path: &Path) -> io::Result<String> {
+        let invalid = |what: &str| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "encrypted credentials in {} are invalid: {what}",
+                    path.display()
+                ),
+            )
+        };
+        if envelope.codex_encrypted != ENVELOPE_VERSION || envelope.kdf != KDF_ARGON2ID {
+            return Err(invalid("unsupported format version"));
+        }
+        let salt = BASE64
+            .decode(&envelope.salt)
+            .map_err(|_| invalid("bad salt"))?;
+        let nonce = BASE64
+            .decode(&envelope.nonce)
+            .map_err(|_| invalid("bad nonce"))?;
+        if nonce.len() != NONCE_LEN {
+            return Err(invalid("bad nonce"));
+        }
+        let ciphertext = BASE64
+            .decode(&envelope.ciphertext)
+            .map_err(|_| invalid("bad ciphertext"))?;
+        if envelope.m_cost > MAX_M_COST_KIB
+            || envelope.t_cost > MAX_T_COST
+            || envelope.p_cost > MAX_P_COST
+        {
+            return Err(invalid("key derivation parameters are too expensive"));
+        }
+        let params = Params::new(envelope.m_cost, envelope.t_cost, envelope.p_cost, None)
+            .map_err(|_| invalid("bad key derivation parameters"))?;
+
+        let plaintext = self
+            .cipher(&salt, &params)?
+            .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| {
+                io::Error::new(
+                    ErrorKind::PermissionDenied,
+                    format!(
+                        "failed to decrypt credentials in {}: the key in {CODEX_AUTH_KEY_ENV_VAR} or {CODEX_AUTH_KEY_FILE_ENV_VAR} is wrong or the file is corrupted",
+                        path.display()
+                    ),
+                )
+            })?;
+        String::from_utf8(plaintext).map_err(|_| invalid("payload is not UTF-8"))
+    }
+
+    fn cipher(&self, salt: &[u8], params: &Params) -> io::Result<XChaCha20Poly1305> {
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone())
+            .hash_password_into(self.passphrase.as_bytes(), salt, key.as_mut_slice())
+            .map_err(|err| io::Error::other(format!("failed to derive encryption key: {err}")))?;
+        Ok(XChaCha20Poly1305::new(Key::from_slice(key.as_slice())))
+    }
+}
+
+/// On-disk format of an encrypted credential file.
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    codex_encrypted: u32,
+    kdf: String,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+fn parse_envelope(contents: &str) -> Option<Envelope> {
+    serde_json::from_str(contents).ok()
+}
+
+/// Whether `contents` is an encrypted credential file.
+pub fn is_encrypted(contents: &str) -> bool {
+    parse_envelope(contents).is_some()
+}
+
+/// Error returned when `path` must be encrypted or decrypted but no key is configured.
+pub fn missing_key_error(path: &Path) -> io::Error {
+    io::Error::new(
+        ErrorKind::PermissionDenied,
+        format!(
+            "no encryption key is configured for the credentials in {}; set {CODEX_AUTH_KEY_ENV_VAR} or {CODEX_AUTH_KEY_FILE_ENV_VAR}",
+            path.display()
+        ),
+    )
+}
+
+/// Prepares `plaintext` for writing to `path` according to `encryption`.
+pub fn seal(
+    encryption: FileEncryption,
+    key: Option<&FileEncryptionKey>,
+    plaintext: &str,
+    path: &Path,
+) -> io::Result<String> {
+    match (encryption, key) {
+        (FileEncryption::Plaintext, _) | (FileEncryption::WhenKeyAvailable, None) => {
+            Ok(plaintext.to_string())
+        }
+        (_, Some(key)) => key.encrypt(plaintext),
+        (FileEncryption::Required, None) => Err(missing_key_error(path)),
+    }
+}
+
+/// Decrypts `contents` read from `path` if it is encrypted. Returns the
+/// plaintext and whether the file was encrypted.
+pub fn open(
+    key: Option<&FileEncryptionKey>,
+    contents: String,
+    path: &Path,
+) -> io::Result<(String, bool)> {
+    let Some(envelope) = parse_envelope(&contents) else {
+        return Ok((contents, false));
+    };
+    let key = key.ok_or_else(|| missing_key_error(path))?;
+    Ok((key.decrypt(&envelope, path)?, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn path() -> PathBuf {
+        PathBuf::from("auth.json")
+    }
+
+    #[test]
+    fn sealed_contents_round_trip_with_the_same_key() {
+        let key = FileEncryptionKey::new("correct horse");
+        let sealed = seal(
+            FileEncryption::Required,
+            Some(&key),
+            "{\"secret\":1}",
+            &path(),
+        )
+        .expect("seal");
+
+        assert!(is_encrypted(&sealed));
+        assert!(!sealed.contains("secret"));
+        let (plaintext, was_encrypted) = open(Some(&key), sealed, &path()).expect("open");
+        assert_eq!(plaintext, "{\"secret\":1}");
+        assert!(was_encrypted);
+    }
+
+    #[test]
+    fn wrong_or_missing_key_is_reported() {
+        let key = FileEncryptionKey::new("correct horse");
+        let sealed = seal(FileEncryption::Required, Some(&key), "{}", &path()).expect("seal");
+
+        let wrong = FileEncryptionKey::new("battery staple");
+        let err = open(Some(&wrong), sealed.clone(), &path()).expect_err("wrong key");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let err = open(None, sealed, &path()).expect_err("missing key");
+        assert!(err.to_string().contains(CODEX_AUTH_KEY_ENV_VAR));
+    }
+
+    #[test]
+    fn expensive_key_derivation_parameters_are_rejected() {
+        let key = FileEncryptionKey::new("correct horse");
+        let sealed = seal(FileEncryption::Required, Some(&key), "{}", &path()).expect("seal");
+        let mut envelope = parse_envelope(&sealed).expect("envelope");
+        envelope.m_cost = MAX_M_COST_KIB + 1;
+        let tampered = serde_json::to_string(&envelope).expect("serialize");
+
+        let err = open(Some(&key), tampered, &path()).expect_err("too expensive");
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(err.to_string().contains("too expensive"));
+    }
+
+    #[test]
+    fn plaintext_passes_through_unless_encryption_is_required() {
+        let (plaintext, was_encrypted) = open(None, "{}".to_string(), &path()).expect("open");
+        assert_eq!(plaintext, "{}");
+        assert!(!was_encrypted);
+
+        assert_eq!(
+            seal(FileEncryption::WhenKeyAvailable, None, "{}", &path()).expect("seal"),
+            "{}"
+        );
+        assert!(seal(FileEncryption::Required, None, "{}", &path()).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+pub mod encrypted_file;
+
 use keyring::Entry;
 use keyring::Error as KeyringError;
 use std::error::Error;
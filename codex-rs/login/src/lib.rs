@@ -16,7 +16,10 @@ pub use codex_core::auth::AuthDotJson;
 pub use codex_core::auth::CLIENT_ID;
 pub use codex_core::auth::CODEX_API_KEY_ENV_VAR;
 pub use codex_core::auth::OPENAI_API_KEY_ENV_VAR;
+pub use codex_core::auth::login_account_with_api_key;
 pub use codex_core::auth::login_with_api_key;
 pub use codex_core::auth::logout;
+pub use codex_core::auth::logout_account;
+pub use codex_core::auth::save_account_auth;
 pub use codex_core::auth::save_auth;
 pub use codex_core::token_data::TokenData;
@@ -16,7 +16,7 @@ use base64::Engine;
 use chrono::Utc;
 use codex_core::auth::AuthCredentialsStoreMode;
 use codex_core::auth::AuthDotJson;
-use codex_core::auth::save_auth;
+use codex_core::auth::save_account_auth;
 use codex_core::default_client::originator;
 use codex_core::token_data::TokenData;
 use codex_core::token_data::parse_id_token;
@@ -41,6 +41,8 @@ pub struct ServerOptions {
     pub force_state: Option<String>,
     pub forced_chatgpt_workspace_id: Option<String>,
     pub cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
+    /// Stored account to save the credentials under; `None` is the default account.
+    pub account: Option<String>,
 }
 
 impl ServerOptions {
@@ -59,6 +61,7 @@ impl ServerOptions {
             force_state: None,
             forced_chatgpt_workspace_id,
             cli_auth_credentials_store_mode,
+            account: None,
         }
     }
 }
@@ -274,6 +277,7 @@ async fn process_request(
                         tokens.id_token.clone(),
                         tokens.access_token.clone(),
                         tokens.refresh_token.clone(),
+                        opts.account.as_deref(),
                         opts.cli_auth_credentials_store_mode,
                     )
                     .await
@@ -541,10 +545,12 @@ pub(crate) async fn persist_tokens_async(
     id_token: String,
     access_token: String,
     refresh_token: String,
+    account: Option<&str>,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
 ) -> io::Result<()> {
     // Reuse existing synchronous logic but run it off the async runtime.
     let codex_home = codex_home.to_path_buf();
+    let account = account.map(str::to_string);
     tokio::task::spawn_blocking(move || {
         let mut tokens = TokenData {
             id_token: parse_id_token(&id_token).map_err(io::Error::other)?,
@@ -564,7 +570,12 @@ pub(crate) async fn persist_tokens_async(
             tokens: Some(tokens),
             last_refresh: Some(Utc::now()),
         };
-        save_auth(&codex_home, &auth, auth_credentials_store_mode)
+        save_account_auth(
+            &codex_home,
+            account.as_deref(),
+            &auth,
+            auth_credentials_store_mode,
+        )
     })
     .await
     .map_err(|e| io::Error::other(format!("persist task failed: {e}")))?
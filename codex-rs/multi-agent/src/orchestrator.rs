@@ -11,6 +11,7 @@ use codex_core::CodexConversation;
 use codex_core::ConversationManager;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
+use codex_core::config::types::AuxiliaryTask;
 use codex_core::delegate_tool::DelegateEventReceiver as CoreDelegateEventReceiver;
 use codex_core::delegate_tool::DelegateInvocationMode;
 use codex_core::delegate_tool::DelegateToolAdapter;
@@ -529,7 +530,8 @@ impl AgentOrchestrator {
                 mode: session_mode,
             })?;
 
-        let config = context.into_config();
+        let mut config = context.into_config();
+        config.apply_task_model(&AuxiliaryTask::Delegate(agent_id.as_str().to_string()));
         let cwd = config.cwd.clone();
         let config_clone = config.clone();
         let delegate_adapter = crate::delegate_tool_adapter(Arc::clone(&self));
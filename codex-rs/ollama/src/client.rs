@@ -8,12 +8,10 @@ use std::io;
 use crate::parser::pull_events_from_value;
 use crate::pull::PullEvent;
 use crate::pull::PullProgressReporter;
+use crate::show::OllamaModelInfo;
+use crate::show::show_model;
 use crate::url::base_url_to_host_root;
 use crate::url::is_openai_compatible_base_url;
-use codex_core::ModelProviderInfo;
-use codex_core::OLLAMA_OSS_PROVIDER_ID;
-use codex_core::WireApi;
-use codex_core::config::Config;
 
 const OLLAMA_CONNECTION_ERROR: &str = "No running Ollama server detected. Start it with: `ollama serve` (after installing). Install instructions: https://github.com/ollama/ollama?tab=readme-ov-file#ollama";
 
@@ -25,43 +23,12 @@ pub struct OllamaClient {
 }
 
 impl OllamaClient {
-    /// Construct a client for the built‑in open‑source ("oss") model provider
-    /// and verify that a local Ollama server is reachable. If no server is
-    /// detected, returns an error with helpful installation/run instructions.
-    pub async fn try_from_oss_provider(config: &Config) -> io::Result<Self> {
-        // Note that we must look up the provider from the Config to ensure that
-        // any overrides the user has in their config.toml are taken into
-        // account.
-        let provider = config
-            .model_providers
-            .get(OLLAMA_OSS_PROVIDER_ID)
-            .ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::NotFound,
-                    format!("Built-in provider {OLLAMA_OSS_PROVIDER_ID} not found",),
-                )
-            })?;
-
-        Self::try_from_provider(provider).await
-    }
-
-    #[cfg(test)]
-    async fn try_from_provider_with_base_url(base_url: &str) -> io::Result<Self> {
-        let provider =
-            codex_core::create_oss_provider_with_base_url(base_url, codex_core::WireApi::Chat);
-        Self::try_from_provider(&provider).await
-    }
-
-    /// Build a client from a provider definition and verify the server is reachable.
-    async fn try_from_provider(provider: &ModelProviderInfo) -> io::Result<Self> {
-        #![expect(clippy::expect_used)]
-        let base_url = provider
-            .base_url
-            .as_ref()
-            .expect("oss provider must have a base_url");
-        let uses_openai_compat = is_openai_compatible_base_url(base_url)
-            || matches!(provider.wire_api, WireApi::Chat)
-                && is_openai_compatible_base_url(base_url);
+    /// Construct a client for an Ollama server at `base_url` (either the
+    /// native host root or its OpenAI-compatible `/v1` root) and verify that
+    /// it is reachable. If no server is detected, returns an error with
+    /// helpful installation/run instructions.
+    pub async fn try_from_base_url(base_url: &str) -> io::Result<Self> {
+        let uses_openai_compat = is_openai_compatible_base_url(base_url);
         let host_root = base_url_to_host_root(base_url);
         let client = reqwest::Client::builder()
             .connect_timeout(std::time::Duration::from_secs(5))
@@ -125,6 +92,12 @@ impl OllamaClient {
         Ok(names)
     }
 
+    /// Return the context length and capabilities `/api/show` reports for `model`.
+    pub async fn fetch_model_info(&self, model: &str) -> io::Result<OllamaModelInfo> {
+        let url = format!("{}/api/show", self.host_root.trim_end_matches('/'));
+        show_model(self.client.post(url), model).await
+    }
+
     /// Start a model pull and emit streaming events. The returned stream ends when
     /// a Success event is observed or the server closes the connection.
     pub async fn pull_model_stream(
@@ -269,6 +242,49 @@ mod tests {
         assert!(models.contains(&"mistral".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_fetch_model_info_reads_context_length() {
+        if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
+            tracing::info!(
+                "{} is set; skipping test_fetch_model_info_reads_context_length",
+                codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR
+            );
+            return;
+        }
+
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/api/show"))
+            .and(wiremock::matchers::body_json(
+                serde_json::json!({ "model": "llama3.2:3b" }),
+            ))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_raw(
+                    serde_json::json!({
+                        "model_info": {
+                            "general.architecture": "llama",
+                            "llama.context_length": 131072
+                        },
+                        "capabilities": ["completion", "tools"]
+                    })
+                    .to_string(),
+                    "application/json",
+                ),
+            )
+            .mount(&server)
+            .await;
+        let client = OllamaClient::from_host_root(server.uri());
+
+        let info = client
+            .fetch_model_info("llama3.2:3b")
+            .await
+            .expect("model info");
+
+        assert_eq!(info.context_window(), Some(131072));
+        assert!(info.supports_tools);
+        assert!(!info.supports_thinking);
+    }
+
     #[tokio::test]
     async fn test_probe_server_happy_path_openai_compat_and_native() {
         if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
@@ -296,10 +312,9 @@ mod tests {
             .respond_with(wiremock::ResponseTemplate::new(200))
             .mount(&server)
             .await;
-        let ollama_client =
-            OllamaClient::try_from_provider_with_base_url(&format!("{}/v1", server.uri()))
-                .await
-                .expect("probe OpenAI compat");
+        let ollama_client = OllamaClient::try_from_base_url(&format!("{}/v1", server.uri()))
+            .await
+            .expect("probe OpenAI compat");
         ollama_client
             .probe_server()
             .await
@@ -307,10 +322,10 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_try_from_oss_provider_ok_when_server_running() {
+    async fn test_try_from_base_url_ok_when_server_running() {
         if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
             tracing::info!(
-                "{} set; skipping test_try_from_oss_provider_ok_when_server_running",
+                "{} set; skipping test_try_from_base_url_ok_when_server_running",
                 codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR
             );
             return;
@@ -325,23 +340,23 @@ mod tests {
             .mount(&server)
             .await;
 
-        OllamaClient::try_from_provider_with_base_url(&format!("{}/v1", server.uri()))
+        OllamaClient::try_from_base_url(&format!("{}/v1", server.uri()))
             .await
             .expect("client should be created when probe succeeds");
     }
 
     #[tokio::test]
-    async fn test_try_from_oss_provider_err_when_server_missing() {
+    async fn test_try_from_base_url_err_when_server_missing() {
         if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
             tracing::info!(
-                "{} set; skipping test_try_from_oss_provider_err_when_server_missing",
+                "{} set; skipping test_try_from_base_url_err_when_server_missing",
                 codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR
             );
             return;
         }
 
         let server = wiremock::MockServer::start().await;
-        let err = OllamaClient::try_from_provider_with_base_url(&format!("{}/v1", server.uri()))
+        let err = OllamaClient::try_from_base_url(&format!("{}/v1", server.uri()))
             .await
             .err()
             .expect("expected error");
@@ -1,31 +1,30 @@
 mod client;
 mod parser;
 mod pull;
+mod show;
 mod url;
 
 pub use client::OllamaClient;
-use codex_core::config::Config;
 pub use pull::CliProgressReporter;
 pub use pull::PullEvent;
 pub use pull::PullProgressReporter;
 pub use pull::TuiProgressReporter;
+pub use show::OllamaModelInfo;
+pub use show::show_model;
+pub use url::base_url_to_host_root;
 
 /// Default OSS model to use when `--oss` is passed without an explicit `-m`.
 pub const DEFAULT_OSS_MODEL: &str = "gpt-oss:20b";
 
-/// Prepare the local OSS environment when `--oss` is selected.
+/// Prepare the local OSS environment at `base_url` for `model` when `--oss`
+/// is selected.
 ///
 /// - Ensures a local Ollama server is reachable.
 /// - Checks if the model exists locally and pulls it if missing.
-pub async fn ensure_oss_ready(config: &Config) -> std::io::Result<()> {
-    // Only download when the requested model is the default OSS model (or when -m is not provided).
-    let model = match config.model.as_ref() {
-        Some(model) => model,
-        None => DEFAULT_OSS_MODEL,
-    };
-
+/// - Warns when the model cannot call tools.
+pub async fn ensure_oss_ready(model: &str, base_url: &str) -> std::io::Result<()> {
     // Verify local Ollama is reachable.
-    let ollama_client = crate::OllamaClient::try_from_oss_provider(config).await?;
+    let ollama_client = crate::OllamaClient::try_from_base_url(base_url).await?;
 
     // If the model is not present locally, pull it.
     match ollama_client.fetch_models().await {
@@ -43,5 +42,15 @@ pub async fn ensure_oss_ready(config: &Config) -> std::io::Result<()> {
         }
     }
 
+    // Codex drives the model through tool calls; warn early rather than
+    // letting the first turn fail.
+    match ollama_client.fetch_model_info(model).await {
+        Ok(info) if !info.supports_tools => {
+            tracing::warn!("Ollama reports that {model} does not support tool calling.");
+        }
+        Ok(_) => {}
+        Err(err) => tracing::warn!("Failed to query model info from Ollama: {err}."),
+    }
+
     Ok(())
 }
@@ -0,0 +1,147 @@
+use std::io;
+
+use serde_json::Value as JsonValue;
+
+/// Metadata reported by Ollama's `/api/show` for a single model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OllamaModelInfo {
+    /// Context length the model was trained with (`<arch>.context_length`).
+    pub context_length: Option<i64>,
+    /// `num_ctx` pinned by the model's Modelfile, if any.
+    pub num_ctx: Option<i64>,
+    pub supports_tools: bool,
+    pub supports_thinking: bool,
+}
+
+impl OllamaModelInfo {
+    /// Context window to request: a Modelfile `num_ctx` wins over the trained
+    /// length because it reflects what the user built the model for.
+    pub fn context_window(&self) -> Option<i64> {
+        self.num_ctx.or(self.context_length)
+    }
+}
+
+/// Sends `request`, a POST to `<host root>/api/show` that may carry extra
+/// headers or a timeout, and parses the reply for `model`.
+pub async fn show_model(
+    request: reqwest::RequestBuilder,
+    model: &str,
+) -> io::Result<OllamaModelInfo> {
+    let resp = request
+        .json(&serde_json::json!({ "model": model }))
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    if !resp.status().is_success() {
+        return Err(io::Error::other(format!(
+            "failed to show model {model}: HTTP {}",
+            resp.status()
+        )));
+    }
+    let val = resp.json::<JsonValue>().await.map_err(io::Error::other)?;
+    Ok(parse_show_response(&val))
+}
+
+/// Parses an `/api/show` response body.
+fn parse_show_response(body: &JsonValue) -> OllamaModelInfo {
+    let model_info = body.get("model_info").and_then(JsonValue::as_object);
+    let architecture = model_info
+        .and_then(|info| info.get("general.architecture"))
+        .and_then(JsonValue::as_str);
+    let context_length = model_info.and_then(|info| {
+        architecture
+            .and_then(|arch| info.get(&format!("{arch}.context_length")))
+            .or_else(|| {
+                info.iter()
+                    .find(|(key, _)| key.ends_with(".context_length"))
+                    .map(|(_, value)| value)
+            })
+            .and_then(JsonValue::as_i64)
+    });
+
+    let num_ctx = body
+        .get("parameters")
+        .and_then(JsonValue::as_str)
+        .into_iter()
+        .flat_map(str::lines)
+        .find_map(|line| {
+            let mut parts = line.split_whitespace();
+            (parts.next() == Some("num_ctx"))
+                .then(|| parts.next()?.parse::<i64>().ok())
+                .flatten()
+        });
+
+    // Servers older than 0.6.4 do not report capabilities; fall back to
+    // checking whether the chat template renders tools.
+    let (supports_tools, supports_thinking) = match body.get("capabilities") {
+        Some(JsonValue::Array(capabilities)) => {
+            let has = |name: &str| capabilities.iter().any(|c| c.as_str() == Some(name));
+            (has("tools"), has("thinking"))
+        }
+        _ => {
+            let template = body
+                .get("template")
+                .and_then(JsonValue::as_str)
+                .unwrap_or("");
+            (template.contains(".Tools"), false)
+        }
+    };
+
+    OllamaModelInfo {
+        context_length,
+        num_ctx,
+        supports_tools,
+        supports_thinking,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn parses_context_length_and_capabilities_from_show() {
+        let body = json!({
+            "parameters": "stop                           \"<|eot_id|>\"\nnum_ctx                        16384",
+            "model_info": {
+                "general.architecture": "qwen3",
+                "qwen3.context_length": 40960,
+                "qwen3.embedding_length": 4096
+            },
+            "capabilities": ["completion", "tools", "thinking"]
+        });
+
+        let info = parse_show_response(&body);
+
+        assert_eq!(
+            info,
+            OllamaModelInfo {
+                context_length: Some(40960),
+                num_ctx: Some(16384),
+                supports_tools: true,
+                supports_thinking: true,
+            }
+        );
+        assert_eq!(info.context_window(), Some(16384));
+    }
+
+    #[test]
+    fn falls_back_to_the_template_on_servers_without_capabilities() {
+        let body = json!({
+            "template": "{{- if .Tools }}tools{{ end }}",
+            "model_info": {"llama.context_length": 8192}
+        });
+
+        assert_eq!(
+            parse_show_response(&body),
+            OllamaModelInfo {
+                context_length: Some(8192),
+                num_ctx: None,
+                supports_tools: true,
+                supports_thinking: false,
+            }
+        );
+    }
+}
@@ -11,6 +11,7 @@ pub struct OtelSettings {
     pub codex_home: PathBuf,
     pub exporter: OtelExporter,
     pub trace_exporter: OtelExporter,
+    pub metrics_exporter: OtelExporter,
 }
 
 #[derive(Clone, Debug)]
@@ -42,4 +43,10 @@ pub enum OtelExporter {
         protocol: OtelHttpProtocol,
         tls: Option<OtelTlsConfig>,
     },
+    /// Append OTLP-JSON lines to a local file, rotating it once it grows past
+    /// `rotate_bytes`.
+    File {
+        path: AbsolutePathBuf,
+        rotate_bytes: Option<u64>,
+    },
 }
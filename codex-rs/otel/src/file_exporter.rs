@@ -0,0 +1,345 @@
+//! Exporters that append OTLP-JSON lines to a local file.
+//!
+//! Every export call writes a single line holding the same JSON body an
+//! OTLP/HTTP collector would receive (`{"resourceLogs": ...}`,
+//! `{"resourceSpans": ...}` or `{"resourceMetrics": ...}`), so the files can
+//! be replayed into a collector or read back with `codex otel summarize`.
+//! Logs, spans and metrics configured with the same path share one writer.
+//!
+//! Several processes may write to the same file. When rotation is enabled,
+//! each line is written under an exclusive lock on `<path>.lock` through a
+//! freshly opened handle, so only one process rotates the file and nobody keeps
+//! appending to a file that was already rotated away.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use opentelemetry_proto::transform::common::tonic::ResourceAttributesWithSchema;
+use opentelemetry_proto::transform::logs::tonic::group_logs_by_resource_and_scope;
+use opentelemetry_proto::transform::trace::tonic::group_spans_by_resource_and_scope;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::error::OTelSdkError;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::logs::LogBatch;
+use opentelemetry_sdk::logs::LogExporter;
+use opentelemetry_sdk::metrics::Temporality;
+use opentelemetry_sdk::metrics::data::ResourceMetrics;
+use opentelemetry_sdk::metrics::exporter::PushMetricExporter;
+use opentelemetry_sdk::trace::SpanData;
+use opentelemetry_sdk::trace::SpanExporter;
+use serde::Serialize;
+
+/// Number of rotated files (`<path>.1` .. `<path>.N`) kept next to the live file.
+const ROTATED_FILES_KEPT: usize = 5;
+
+static SINKS: OnceLock<Mutex<HashMap<PathBuf, Arc<RotatingFile>>>> = OnceLock::new();
+
+/// Append-only JSON lines file that is rotated once it would grow past
+/// `rotate_bytes`.
+#[derive(Debug)]
+pub(crate) struct RotatingFile {
+    path: PathBuf,
+    rotate_bytes: Option<u64>,
+    file: Mutex<File>,
+}
+
+impl RotatingFile {
+    /// Returns the shared writer for `path`, opening it on first use.
+    pub(crate) fn shared(path: &Path, rotate_bytes: Option<u64>) -> io::Result<Arc<Self>> {
+        let sinks = SINKS.get_or_init(|| Mutex::new(HashMap::new()));
+        let mut sinks = sinks
+            .lock()
+            .map_err(|_| io::Error::other("otel file sinks poisoned"))?;
+        if let Some(sink) = sinks.get(path) {
+            return Ok(Arc::clone(sink));
+        }
+        let sink = Arc::new(Self::open(path, rotate_bytes)?);
+        sinks.insert(path.to_path_buf(), Arc::clone(&sink));
+        Ok(sink)
+    }
+
+    fn open(path: &Path, rotate_bytes: Option<u64>) -> io::Result<Self> {
+        let (file, _) = open_append(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            rotate_bytes: rotate_bytes.filter(|limit| *limit > 0),
+            file: Mutex::new(file),
+        })
+    }
+
+    fn write_line(&self, line: &[u8]) -> io::Result<()> {
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|_| io::Error::other("otel file writer poisoned"))?;
+        // One write per record so appends from other processes sharing the
+        // file cannot land between a record and its newline.
+        let mut record = Vec::with_capacity(line.len() + 1);
+        record.extend_from_slice(line);
+        record.push(b'\n');
+        let _rotation_lock = match self.rotate_bytes {
+            Some(limit) => {
+                Some(self.prepare_rotating_write(&mut file, limit, record.len() as u64)?)
+            }
+            None => None,
+        };
+        file.write_all(&record)
+    }
+
+    /// Takes the rotation lock shared with other processes, rotates the file
+    /// if `line_len` more bytes would push it past `limit`, and points `file`
+    /// at the live file. The lock is held until the returned handle is dropped.
+    fn prepare_rotating_write(
+        &self,
+        file: &mut File,
+        limit: u64,
+        line_len: u64,
+    ) -> io::Result<File> {
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(sibling_path(&self.path, "lock"))?;
+        lock.lock()?;
+        let (live, len) = open_append(&self.path)?;
+        *file = live;
+        if len > 0 && len + line_len > limit {
+            file.flush()?;
+            rotate(&self.path)?;
+            *file = open_append(&self.path)?.0;
+        }
+        Ok(lock)
+    }
+
+    fn write_json<T: Serialize>(&self, body: &T) -> OTelSdkResult {
+        let line = serde_json::to_vec(body)
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))?;
+        self.write_line(&line)
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))
+    }
+
+    fn flush(&self) -> OTelSdkResult {
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|_| OTelSdkError::InternalFailure("otel file writer poisoned".into()))?;
+        file.flush()
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<(File, u64)> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let len = file.metadata()?.len();
+    Ok((file, len))
+}
+
+/// Shifts `<path>.N-1` to `<path>.N` (dropping the oldest) and moves the live
+/// file to `<path>.1`.
+fn rotate(path: &Path) -> io::Result<()> {
+    for index in (1..ROTATED_FILES_KEPT).rev() {
+        let from = rotated_path(path, index);
+        if from.exists() {
+            fs::rename(&from, rotated_path(path, index + 1))?;
+        }
+    }
+    fs::rename(path, rotated_path(path, 1))
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    sibling_path(path, &index.to_string())
+}
+
+/// `<path>.<suffix>`.
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{suffix}"));
+    PathBuf::from(name)
+}
+
+/// Log, span and metric exporter backed by a [`RotatingFile`].
+pub(crate) struct FileExporter {
+    sink: Arc<RotatingFile>,
+    resource: ResourceAttributesWithSchema,
+}
+
+impl FileExporter {
+    pub(crate) fn new(
+        path: &Path,
+        rotate_bytes: Option<u64>,
+        resource: &Resource,
+    ) -> io::Result<Self> {
+        Ok(Self {
+            sink: RotatingFile::shared(path, rotate_bytes)?,
+            resource: resource.into(),
+        })
+    }
+}
+
+impl fmt::Debug for FileExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileExporter")
+            .field("path", &self.sink.path)
+            .field("rotate_bytes", &self.sink.rotate_bytes)
+            .finish()
+    }
+}
+
+impl LogExporter for FileExporter {
+    async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+        let resource_logs = group_logs_by_resource_and_scope(batch, &self.resource);
+        self.sink
+            .write_json(&ExportLogsServiceRequest { resource_logs })
+    }
+
+    fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+        self.sink.flush()
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.resource = resource.into();
+    }
+}
+
+impl SpanExporter for FileExporter {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        let resource_spans = group_spans_by_resource_and_scope(batch, &self.resource);
+        self.sink
+            .write_json(&ExportTraceServiceRequest { resource_spans })
+    }
+
+    fn shutdown_with_timeout(&mut self, _timeout: Duration) -> OTelSdkResult {
+        self.sink.flush()
+    }
+
+    fn force_flush(&mut self) -> OTelSdkResult {
+        self.sink.flush()
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.resource = resource.into();
+    }
+}
+
+impl PushMetricExporter for FileExporter {
+    async fn export(&self, metrics: &ResourceMetrics) -> OTelSdkResult {
+        self.sink
+            .write_json(&ExportMetricsServiceRequest::from(metrics))
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.sink.flush()
+    }
+
+    fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+        self.sink.flush()
+    }
+
+    fn temporality(&self) -> Temporality {
+        Temporality::Cumulative
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn rotates_when_the_next_line_would_exceed_the_limit() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("otel.jsonl");
+        let file = RotatingFile::open(&path, Some(16)).expect("open");
+
+        file.write_line(b"0123456789").expect("first line");
+        file.write_line(b"abcdefghij").expect("second line");
+        file.write_line(b"klmnopqrst").expect("third line");
+
+        assert_eq!(
+            fs::read_to_string(&path).expect("live file"),
+            "klmnopqrst\n"
+        );
+        assert_eq!(
+            fs::read_to_string(rotated_path(&path, 1)).expect("first rotation"),
+            "abcdefghij\n"
+        );
+        assert_eq!(
+            fs::read_to_string(rotated_path(&path, 2)).expect("second rotation"),
+            "0123456789\n"
+        );
+    }
+
+    #[test]
+    fn keeps_a_bounded_number_of_rotated_files() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("otel.jsonl");
+        let file = RotatingFile::open(&path, Some(1)).expect("open");
+
+        for index in 0..ROTATED_FILES_KEPT + 3 {
+            file.write_line(index.to_string().as_bytes())
+                .expect("write line");
+        }
+
+        assert!(rotated_path(&path, ROTATED_FILES_KEPT).exists());
+        assert!(!rotated_path(&path, ROTATED_FILES_KEPT + 1).exists());
+        assert_eq!(
+            fs::read_to_string(rotated_path(&path, ROTATED_FILES_KEPT)).expect("oldest"),
+            "2\n"
+        );
+    }
+
+    #[test]
+    fn writers_sharing_a_file_rotate_it_once() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("otel.jsonl");
+        let first = RotatingFile::open(&path, Some(16)).expect("open first");
+        let second = RotatingFile::open(&path, Some(16)).expect("open second");
+
+        first.write_line(b"0123456789").expect("first line");
+        first.write_line(b"abcdefghij").expect("second line");
+        second.write_line(b"kl").expect("third line");
+
+        assert_eq!(
+            fs::read_to_string(&path).expect("live file"),
+            "abcdefghij\nkl\n"
+        );
+        assert_eq!(
+            fs::read_to_string(rotated_path(&path, 1)).expect("first rotation"),
+            "0123456789\n"
+        );
+        assert!(!rotated_path(&path, 2).exists());
+    }
+
+    #[test]
+    fn without_a_limit_the_file_only_grows() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("nested").join("otel.jsonl");
+        let file = RotatingFile::open(&path, None).expect("open");
+
+        file.write_line(b"one").expect("write");
+        file.write_line(b"two").expect("write");
+
+        assert_eq!(fs::read_to_string(&path).expect("live file"), "one\ntwo\n");
+        assert!(!rotated_path(&path, 1).exists());
+    }
+}
@@ -1,4 +1,8 @@
 pub mod config;
 
+mod file_exporter;
+mod metrics;
+
 pub mod otel_manager;
 pub mod otel_provider;
+pub mod summarize;
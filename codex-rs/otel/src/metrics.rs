@@ -0,0 +1,201 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::Counter;
+use opentelemetry::metrics::Histogram;
+use opentelemetry::metrics::Meter;
+
+pub(crate) const METER_NAME: &str = "codex";
+
+static METRICS: OnceLock<CodexMetrics> = OnceLock::new();
+
+/// Instruments recorded by [`crate::otel_manager::OtelManager`]. They only
+/// exist once a metrics exporter has been configured, so recording is a no-op
+/// otherwise.
+#[derive(Debug)]
+pub(crate) struct CodexMetrics {
+    turns: Counter<u64>,
+    api_requests: Counter<u64>,
+    api_retries: Counter<u64>,
+    tool_calls: Counter<u64>,
+    approvals: Counter<u64>,
+    sandbox_denials: Counter<u64>,
+    time_to_first_token: Histogram<f64>,
+    sse_event_duration: Histogram<f64>,
+    tool_call_duration: Histogram<f64>,
+}
+
+impl CodexMetrics {
+    pub(crate) fn new(meter: &Meter) -> Self {
+        Self {
+            turns: meter
+                .u64_counter("codex.turns")
+                .with_description("User turns submitted")
+                .build(),
+            api_requests: meter
+                .u64_counter("codex.api_requests")
+                .with_description("Model API requests by HTTP status")
+                .build(),
+            api_retries: meter
+                .u64_counter("codex.api_retries")
+                .with_description("Model API requests that were retries of an earlier attempt")
+                .build(),
+            tool_calls: meter
+                .u64_counter("codex.tool_calls")
+                .with_description("Tool calls by tool name and outcome")
+                .build(),
+            approvals: meter
+                .u64_counter("codex.approvals")
+                .with_description("Approval decisions by decision and source")
+                .build(),
+            sandbox_denials: meter
+                .u64_counter("codex.sandbox_denials")
+                .with_description("Tool calls denied by the sandbox")
+                .build(),
+            time_to_first_token: meter
+                .f64_histogram("codex.time_to_first_token")
+                .with_description("Time from sending a model request to the first streamed output")
+                .with_unit("ms")
+                .build(),
+            sse_event_duration: meter
+                .f64_histogram("codex.sse_event.duration")
+                .with_description("Time spent waiting for each streamed SSE event")
+                .with_unit("ms")
+                .build(),
+            tool_call_duration: meter
+                .f64_histogram("codex.tool_call.duration")
+                .with_description("Tool call latency by tool name")
+                .with_unit("ms")
+                .build(),
+        }
+    }
+
+    pub(crate) fn turn(&self, model: &str) {
+        self.turns
+            .add(1, &[KeyValue::new("model", model.to_owned())]);
+    }
+
+    pub(crate) fn api_request(&self, model: &str, attempt: u64, status: Option<u16>) {
+        let status = status.map_or_else(|| "error".to_string(), |s| s.to_string());
+        let attributes = [
+            KeyValue::new("model", model.to_owned()),
+            KeyValue::new("status", status),
+        ];
+        self.api_requests.add(1, &attributes);
+        if attempt > 0 {
+            self.api_retries.add(1, &attributes);
+        }
+    }
+
+    pub(crate) fn tool_call(&self, tool_name: &str, success: bool, duration: Option<Duration>) {
+        let attributes = [
+            KeyValue::new("tool_name", tool_name.to_owned()),
+            KeyValue::new("success", success),
+        ];
+        self.tool_calls.add(1, &attributes);
+        if let Some(duration) = duration {
+            self.tool_call_duration
+                .record(millis(duration), &attributes[..1]);
+        }
+    }
+
+    pub(crate) fn approval(&self, tool_name: &str, decision: &str, source: &str) {
+        self.approvals.add(
+            1,
+            &[
+                KeyValue::new("tool_name", tool_name.to_owned()),
+                KeyValue::new("decision", decision.to_owned()),
+                KeyValue::new("source", source.to_owned()),
+            ],
+        );
+    }
+
+    pub(crate) fn sandbox_denial(&self, tool_name: &str) {
+        self.sandbox_denials
+            .add(1, &[KeyValue::new("tool_name", tool_name.to_owned())]);
+    }
+
+    pub(crate) fn time_to_first_token(&self, model: &str, duration: Duration) {
+        self.time_to_first_token.record(
+            millis(duration),
+            &[KeyValue::new("model", model.to_owned())],
+        );
+    }
+
+    pub(crate) fn sse_event(&self, kind: Option<&str>, success: bool, duration: Duration) {
+        self.sse_event_duration.record(
+            millis(duration),
+            &[
+                KeyValue::new("kind", kind.unwrap_or("unknown").to_owned()),
+                KeyValue::new("success", success),
+            ],
+        );
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Installs the instruments; later calls are ignored.
+pub(crate) fn init(meter: &Meter) {
+    let _ = METRICS.set(CodexMetrics::new(meter));
+}
+
+/// The installed instruments, or `None` when metrics export is disabled.
+pub(crate) fn metrics() -> Option<&'static CodexMetrics> {
+    METRICS.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::metrics::MeterProvider as _;
+    use opentelemetry_sdk::metrics::InMemoryMetricExporter;
+    use opentelemetry_sdk::metrics::PeriodicReader;
+    use opentelemetry_sdk::metrics::SdkMeterProvider;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn records_counters_and_histograms() {
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let metrics = CodexMetrics::new(&provider.meter(METER_NAME));
+
+        metrics.turn("gpt-5");
+        metrics.api_request("gpt-5", 1, Some(429));
+        metrics.tool_call("shell", false, Some(Duration::from_millis(12)));
+        metrics.approval("shell", "approved", "user");
+        metrics.sandbox_denial("shell");
+        metrics.time_to_first_token("gpt-5", Duration::from_millis(300));
+        metrics.sse_event(Some("response.completed"), true, Duration::from_millis(5));
+        provider.force_flush().expect("flush metrics");
+
+        let names: BTreeSet<String> = exporter
+            .get_finished_metrics()
+            .expect("finished metrics")
+            .iter()
+            .flat_map(|resource| resource.scope_metrics())
+            .flat_map(|scope| scope.metrics())
+            .map(|metric| metric.name().to_string())
+            .collect();
+        let expected: BTreeSet<String> = [
+            "codex.api_requests",
+            "codex.api_retries",
+            "codex.approvals",
+            "codex.sandbox_denials",
+            "codex.sse_event.duration",
+            "codex.time_to_first_token",
+            "codex.tool_call.duration",
+            "codex.tool_calls",
+            "codex.turns",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+        assert_eq!(names, expected);
+    }
+}
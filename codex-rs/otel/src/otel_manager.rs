@@ -1,3 +1,4 @@
+use crate::metrics::metrics;
 use crate::otel_provider::traceparent_context_from_env;
 use chrono::SecondsFormat;
 use chrono::Utc;
@@ -182,6 +183,9 @@ impl OtelManager {
         error: Option<&str>,
         duration: Duration,
     ) {
+        if let Some(metrics) = metrics() {
+            metrics.api_request(&self.metadata.model, attempt, status);
+        }
         tracing::event!(
             tracing::Level::INFO,
             event.name = "codex.api_request",
@@ -249,6 +253,9 @@ impl OtelManager {
     }
 
     fn sse_event(&self, kind: &str, duration: Duration) {
+        if let Some(metrics) = metrics() {
+            metrics.sse_event(Some(kind), true, duration);
+        }
         tracing::event!(
             tracing::Level::INFO,
             event.name = "codex.sse_event",
@@ -270,6 +277,9 @@ impl OtelManager {
     where
         T: Display,
     {
+        if let Some(metrics) = metrics() {
+            metrics.sse_event(kind.map(String::as_str), false, duration);
+        }
         match kind {
             Some(kind) => tracing::event!(
                 tracing::Level::INFO,
@@ -370,6 +380,10 @@ impl OtelManager {
             "[REDACTED]"
         };
 
+        if let Some(metrics) = metrics() {
+            metrics.turn(&self.metadata.model);
+        }
+
         tracing::event!(
             tracing::Level::INFO,
             event.name = "codex.user_prompt",
@@ -394,6 +408,10 @@ impl OtelManager {
         decision: &ReviewDecision,
         source: ToolDecisionSource,
     ) {
+        let decision = decision.clone().to_string().to_lowercase();
+        if let Some(metrics) = metrics() {
+            metrics.approval(tool_name, &decision, &source.to_string());
+        }
         tracing::event!(
             tracing::Level::INFO,
             event.name = "codex.tool_decision",
@@ -408,7 +426,7 @@ impl OtelManager {
             slug = %self.metadata.slug,
             tool_name = %tool_name,
             call_id = %call_id,
-            decision = %decision.clone().to_string().to_lowercase(),
+            decision = %decision,
             source = %source.to_string(),
         );
     }
@@ -447,6 +465,9 @@ impl OtelManager {
     }
 
     pub fn log_tool_failed(&self, tool_name: &str, error: &str) {
+        if let Some(metrics) = metrics() {
+            metrics.tool_call(tool_name, false, None);
+        }
         tracing::event!(
             tracing::Level::INFO,
             event.name = "codex.tool_result",
@@ -475,6 +496,9 @@ impl OtelManager {
         success: bool,
         output: &str,
     ) {
+        if let Some(metrics) = metrics() {
+            metrics.tool_call(tool_name, success, Some(duration));
+        }
         let success_str = if success { "true" } else { "false" };
 
         tracing::event!(
@@ -498,6 +522,21 @@ impl OtelManager {
         );
     }
 
+    /// Records a tool call that the sandbox refused to run. Metrics only.
+    pub fn sandbox_denied(&self, tool_name: &str) {
+        if let Some(metrics) = metrics() {
+            metrics.sandbox_denial(tool_name);
+        }
+    }
+
+    /// Records how long a model request took to produce its first streamed
+    /// output. Metrics only.
+    pub fn record_time_to_first_token(&self, duration: Duration) {
+        if let Some(metrics) = metrics() {
+            metrics.time_to_first_token(&self.metadata.model, duration);
+        }
+    }
+
     fn responses_type(event: &ResponseEvent) -> String {
         match event {
             ResponseEvent::Created => "created".into(),
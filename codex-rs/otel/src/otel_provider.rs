@@ -2,18 +2,22 @@ use crate::config::OtelExporter;
 use crate::config::OtelHttpProtocol;
 use crate::config::OtelSettings;
 use crate::config::OtelTlsConfig;
+use crate::file_exporter::FileExporter;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use http::Uri;
 use opentelemetry::Context;
 use opentelemetry::KeyValue;
 use opentelemetry::context::ContextGuard;
 use opentelemetry::global;
+use opentelemetry::metrics::MeterProvider as _;
 use opentelemetry::propagation::TextMapPropagator;
 use opentelemetry::trace::TraceContextExt;
 use opentelemetry::trace::TracerProvider as _;
 use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
 use opentelemetry_otlp::LogExporter;
+use opentelemetry_otlp::MetricExporter;
 use opentelemetry_otlp::OTEL_EXPORTER_OTLP_LOGS_TIMEOUT;
+use opentelemetry_otlp::OTEL_EXPORTER_OTLP_METRICS_TIMEOUT;
 use opentelemetry_otlp::OTEL_EXPORTER_OTLP_TIMEOUT;
 use opentelemetry_otlp::OTEL_EXPORTER_OTLP_TIMEOUT_DEFAULT;
 use opentelemetry_otlp::OTEL_EXPORTER_OTLP_TRACES_TIMEOUT;
@@ -24,6 +28,8 @@ use opentelemetry_otlp::WithHttpConfig;
 use opentelemetry_otlp::WithTonicConfig;
 use opentelemetry_sdk::Resource;
 use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::PeriodicReader;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use opentelemetry_sdk::trace::BatchSpanProcessor;
 use opentelemetry_sdk::trace::SdkTracerProvider;
@@ -67,6 +73,7 @@ pub struct OtelProvider {
     pub logger: Option<SdkLoggerProvider>,
     pub tracer_provider: Option<SdkTracerProvider>,
     pub tracer: Option<Tracer>,
+    pub meter_provider: Option<SdkMeterProvider>,
 }
 
 impl OtelProvider {
@@ -77,13 +84,17 @@ impl OtelProvider {
         if let Some(tracer_provider) = &self.tracer_provider {
             let _ = tracer_provider.shutdown();
         }
+        if let Some(meter_provider) = &self.meter_provider {
+            let _ = meter_provider.shutdown();
+        }
     }
 
     pub fn from(settings: &OtelSettings) -> Result<Option<Self>, Box<dyn Error>> {
         let log_enabled = !matches!(settings.exporter, OtelExporter::None);
         let trace_enabled = !matches!(settings.trace_exporter, OtelExporter::None);
+        let metrics_enabled = !matches!(settings.metrics_exporter, OtelExporter::None);
 
-        if !log_enabled && !trace_enabled {
+        if !log_enabled && !trace_enabled && !metrics_enabled {
             debug!("No exporter enabled in OTLP settings.");
             return Ok(None);
         }
@@ -109,10 +120,19 @@ impl OtelProvider {
             attach_traceparent_context();
         }
 
+        let meter_provider = metrics_enabled
+            .then(|| build_meter_provider(&resource, &settings.metrics_exporter))
+            .transpose()?;
+        if let Some(provider) = meter_provider.clone() {
+            crate::metrics::init(&provider.meter(crate::metrics::METER_NAME));
+            global::set_meter_provider(provider);
+        }
+
         Ok(Some(Self {
             logger,
             tracer_provider,
             tracer,
+            meter_provider,
         }))
     }
 
@@ -151,6 +171,9 @@ impl Drop for OtelProvider {
         if let Some(tracer_provider) = &self.tracer_provider {
             let _ = tracer_provider.shutdown();
         }
+        if let Some(meter_provider) = &self.meter_provider {
+            let _ = meter_provider.shutdown();
+        }
     }
 }
 
@@ -278,6 +301,13 @@ fn build_logger(
 
             let exporter = exporter_builder.build()?;
 
+            builder = builder.with_batch_exporter(exporter);
+        }
+        OtelExporter::File { path, rotate_bytes } => {
+            debug!("Using OTLP file exporter: {}", path.display());
+
+            let exporter = FileExporter::new(path.as_path(), *rotate_bytes, resource)?;
+
             builder = builder.with_batch_exporter(exporter);
         }
     }
@@ -342,6 +372,16 @@ fn build_tracer_provider(
 
             exporter_builder.build()?
         }
+        OtelExporter::File { path, rotate_bytes } => {
+            debug!("Using OTLP file exporter for traces: {}", path.display());
+
+            let exporter = FileExporter::new(path.as_path(), *rotate_bytes, resource)?;
+
+            return Ok(SdkTracerProvider::builder()
+                .with_resource(resource.clone())
+                .with_span_processor(BatchSpanProcessor::builder(exporter).build())
+                .build());
+        }
     };
 
     let processor = BatchSpanProcessor::builder(span_exporter).build();
@@ -352,6 +392,83 @@ fn build_tracer_provider(
         .build())
 }
 
+fn build_meter_provider(
+    resource: &Resource,
+    exporter: &OtelExporter,
+) -> Result<SdkMeterProvider, Box<dyn Error>> {
+    let metric_exporter = match exporter {
+        OtelExporter::None => return Ok(SdkMeterProvider::builder().build()),
+        OtelExporter::OtlpGrpc {
+            endpoint,
+            headers,
+            tls,
+        } => {
+            debug!("Using OTLP Grpc exporter for metrics: {endpoint}");
+
+            let header_map = build_header_map(headers);
+
+            let base_tls_config = ClientTlsConfig::new()
+                .with_enabled_roots()
+                .assume_http2(true);
+
+            let tls_config = match tls.as_ref() {
+                Some(tls) => build_grpc_tls_config(endpoint, base_tls_config, tls)?,
+                None => base_tls_config,
+            };
+
+            MetricExporter::builder()
+                .with_tonic()
+                .with_endpoint(endpoint)
+                .with_metadata(MetadataMap::from_headers(header_map))
+                .with_tls_config(tls_config)
+                .build()?
+        }
+        OtelExporter::OtlpHttp {
+            endpoint,
+            headers,
+            protocol,
+            tls,
+        } => {
+            debug!("Using OTLP Http exporter for metrics: {endpoint}");
+
+            let protocol = match protocol {
+                OtelHttpProtocol::Binary => Protocol::HttpBinary,
+                OtelHttpProtocol::Json => Protocol::HttpJson,
+            };
+
+            let mut exporter_builder = MetricExporter::builder()
+                .with_http()
+                .with_endpoint(endpoint)
+                .with_protocol(protocol)
+                .with_headers(headers.clone());
+
+            if let Some(tls) = tls.as_ref() {
+                let client = build_http_client(tls, OTEL_EXPORTER_OTLP_METRICS_TIMEOUT)?;
+                exporter_builder = exporter_builder.with_http_client(client);
+            }
+
+            exporter_builder.build()?
+        }
+        OtelExporter::File { path, rotate_bytes } => {
+            debug!("Using OTLP file exporter for metrics: {}", path.display());
+
+            let exporter = FileExporter::new(path.as_path(), *rotate_bytes, resource)?;
+
+            return Ok(SdkMeterProvider::builder()
+                .with_resource(resource.clone())
+                .with_reader(PeriodicReader::builder(exporter).build())
+                .build());
+        }
+    };
+
+    let reader = PeriodicReader::builder(metric_exporter).build();
+
+    Ok(SdkMeterProvider::builder()
+        .with_resource(resource.clone())
+        .with_reader(reader)
+        .build())
+}
+
 fn build_header_map(headers: &HashMap<String, String>) -> HeaderMap {
     let mut header_map = HeaderMap::new();
     for (key, value) in headers {
@@ -0,0 +1,341 @@
+//! Per-session summaries of the OTLP-JSON lines written by the `file` exporter.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::io::BufRead;
+use std::time::Duration;
+
+use serde_json::Value;
+
+/// Latency and token totals for one conversation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionSummary {
+    pub conversation_id: String,
+    pub model: Option<String>,
+    pub turns: u64,
+    pub api_requests: u64,
+    pub api_errors: u64,
+    pub api_latencies: Vec<Duration>,
+    pub input_tokens: u64,
+    pub cached_tokens: u64,
+    pub output_tokens: u64,
+    pub reasoning_tokens: u64,
+    pub tool_calls: u64,
+    pub tool_failures: u64,
+    pub tool_time: Duration,
+}
+
+impl SessionSummary {
+    /// API latency at `percentile` (0-100) using the nearest-rank method.
+    pub fn api_latency_percentile(&self, percentile: u8) -> Option<Duration> {
+        let mut latencies = self.api_latencies.clone();
+        latencies.sort();
+        let rank = (usize::from(percentile.min(100)) * latencies.len()).div_ceil(100);
+        latencies.get(rank.saturating_sub(1)).copied()
+    }
+}
+
+impl fmt::Display for SessionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Session {}", self.conversation_id)?;
+        if let Some(model) = &self.model {
+            write!(f, " ({model})")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "  turns:        {}", self.turns)?;
+        write!(
+            f,
+            "  api requests: {} ({} failed)",
+            self.api_requests, self.api_errors
+        )?;
+        if let (Some(p50), Some(p95), Some(max)) = (
+            self.api_latency_percentile(50),
+            self.api_latency_percentile(95),
+            self.api_latencies.iter().max(),
+        ) {
+            write!(
+                f,
+                ", latency p50 {}ms p95 {}ms max {}ms",
+                p50.as_millis(),
+                p95.as_millis(),
+                max.as_millis()
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "  tokens:       input {} (cached {}), output {} (reasoning {})",
+            self.input_tokens, self.cached_tokens, self.output_tokens, self.reasoning_tokens
+        )?;
+        write!(
+            f,
+            "  tool calls:   {} ({} failed), {}ms total",
+            self.tool_calls,
+            self.tool_failures,
+            self.tool_time.as_millis()
+        )
+    }
+}
+
+/// Accumulates log records from OTLP-JSON lines into per-session summaries.
+/// Span and metric lines are accepted but do not contribute.
+#[derive(Debug, Default)]
+pub struct Summarizer {
+    sessions: Vec<SessionSummary>,
+    index: HashMap<String, usize>,
+    skipped_lines: usize,
+}
+
+impl Summarizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds every line of `reader`. Lines that are not valid JSON (for
+    /// example a partial write cut off by a crash) are counted and skipped.
+    pub fn add_reader<R: BufRead>(&mut self, reader: R) -> io::Result<()> {
+        for line in reader.lines() {
+            self.add_line(&line?);
+        }
+        Ok(())
+    }
+
+    pub fn add_line(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+        let Ok(body) = serde_json::from_str::<Value>(line) else {
+            self.skipped_lines += 1;
+            return;
+        };
+        for record in log_records(&body) {
+            self.add_record(record);
+        }
+    }
+
+    /// Number of lines that could not be parsed.
+    pub fn skipped_lines(&self) -> usize {
+        self.skipped_lines
+    }
+
+    /// Sessions in the order they first appeared.
+    pub fn finish(self) -> Vec<SessionSummary> {
+        self.sessions
+    }
+
+    fn add_record(&mut self, record: &Value) {
+        let attributes = attributes(record);
+        let Some(conversation_id) = attributes.get("conversation.id") else {
+            return;
+        };
+        let event_name = attributes.get("event.name").cloned().or_else(|| {
+            record
+                .get("eventName")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        });
+        let Some(event_name) = event_name else {
+            return;
+        };
+
+        let index = *self
+            .index
+            .entry(conversation_id.clone())
+            .or_insert_with(|| {
+                self.sessions.push(SessionSummary {
+                    conversation_id: conversation_id.clone(),
+                    ..Default::default()
+                });
+                self.sessions.len() - 1
+            });
+        let session = &mut self.sessions[index];
+        if let Some(model) = attributes.get("model") {
+            session.model = Some(model.clone());
+        }
+
+        let number = |key: &str| -> u64 {
+            attributes
+                .get(key)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0)
+        };
+        let duration = Duration::from_millis(number("duration_ms"));
+
+        match event_name.as_str() {
+            "codex.user_prompt" => session.turns += 1,
+            "codex.api_request" => {
+                session.api_requests += 1;
+                session.api_latencies.push(duration);
+                let failed = attributes.contains_key("error.message")
+                    || number("http.response.status_code") >= 400;
+                if failed {
+                    session.api_errors += 1;
+                }
+            }
+            "codex.sse_event"
+                if attributes.get("event.kind").map(String::as_str)
+                    == Some("response.completed") =>
+            {
+                session.input_tokens += number("input_token_count");
+                session.cached_tokens += number("cached_token_count");
+                session.output_tokens += number("output_token_count");
+                session.reasoning_tokens += number("reasoning_token_count");
+            }
+            "codex.tool_result" => {
+                session.tool_calls += 1;
+                session.tool_time += duration;
+                if attributes.get("success").map(String::as_str) != Some("true") {
+                    session.tool_failures += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn log_records(body: &Value) -> impl Iterator<Item = &Value> {
+    array(body, "resourceLogs")
+        .flat_map(|resource| array(resource, "scopeLogs"))
+        .flat_map(|scope| array(scope, "logRecords"))
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+/// Flattens OTLP `KeyValue` attributes into strings. Integers may be encoded
+/// either as JSON numbers or as strings, depending on the producer.
+fn attributes(record: &Value) -> HashMap<String, String> {
+    array(record, "attributes")
+        .filter_map(|attribute| {
+            let key = attribute.get("key")?.as_str()?;
+            let value = attribute.get("value")?;
+            let value = ["stringValue", "intValue", "doubleValue", "boolValue"]
+                .iter()
+                .find_map(|kind| value.get(*kind))?;
+            let value = match value {
+                Value::String(value) => value.clone(),
+                other => other.to_string(),
+            };
+            Some((key.to_string(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn log_line(records: Vec<Value>) -> String {
+        json!({
+            "resourceLogs": [{
+                "resource": { "attributes": [] },
+                "scopeLogs": [{ "scope": {}, "logRecords": records }],
+            }]
+        })
+        .to_string()
+    }
+
+    fn record(attributes: &[(&str, Value)]) -> Value {
+        let attributes: Vec<Value> = attributes
+            .iter()
+            .map(|(key, value)| json!({ "key": key, "value": value }))
+            .collect();
+        json!({ "attributes": attributes })
+    }
+
+    fn string(value: &str) -> Value {
+        json!({ "stringValue": value })
+    }
+
+    #[test]
+    fn summarizes_requests_tokens_and_tools_per_session() {
+        let mut summarizer = Summarizer::new();
+        summarizer.add_line(&log_line(vec![
+            record(&[
+                ("event.name", string("codex.user_prompt")),
+                ("conversation.id", string("a")),
+                ("model", string("gpt-5")),
+            ]),
+            record(&[
+                ("event.name", string("codex.api_request")),
+                ("conversation.id", string("a")),
+                ("duration_ms", string("100")),
+                ("http.response.status_code", json!({ "intValue": "200" })),
+            ]),
+            record(&[
+                ("event.name", string("codex.api_request")),
+                ("conversation.id", string("a")),
+                ("duration_ms", string("300")),
+                ("http.response.status_code", json!({ "intValue": 500 })),
+            ]),
+            record(&[
+                ("event.name", string("codex.sse_event")),
+                ("event.kind", string("response.completed")),
+                ("conversation.id", string("a")),
+                ("input_token_count", string("1000")),
+                ("cached_token_count", json!({ "intValue": "400" })),
+                ("output_token_count", string("50")),
+                ("reasoning_token_count", json!({ "intValue": "20" })),
+            ]),
+        ]));
+        summarizer.add_line("{\"resourceSpans\": []}");
+        summarizer.add_line("{\"resourceLo");
+        summarizer.add_line(&log_line(vec![
+            record(&[
+                ("event.name", string("codex.tool_result")),
+                ("conversation.id", string("a")),
+                ("duration_ms", string("12")),
+                ("success", string("false")),
+            ]),
+            record(&[
+                ("event.name", string("codex.user_prompt")),
+                ("conversation.id", string("b")),
+            ]),
+        ]));
+
+        assert_eq!(summarizer.skipped_lines(), 1);
+        let sessions = summarizer.finish();
+        assert_eq!(
+            sessions,
+            vec![
+                SessionSummary {
+                    conversation_id: "a".to_string(),
+                    model: Some("gpt-5".to_string()),
+                    turns: 1,
+                    api_requests: 2,
+                    api_errors: 1,
+                    api_latencies: vec![Duration::from_millis(100), Duration::from_millis(300)],
+                    input_tokens: 1000,
+                    cached_tokens: 400,
+                    output_tokens: 50,
+                    reasoning_tokens: 20,
+                    tool_calls: 1,
+                    tool_failures: 1,
+                    tool_time: Duration::from_millis(12),
+                },
+                SessionSummary {
+                    conversation_id: "b".to_string(),
+                    turns: 1,
+                    ..Default::default()
+                },
+            ]
+        );
+        assert_eq!(
+            sessions[0].api_latency_percentile(50),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            sessions[0].api_latency_percentile(95),
+            Some(Duration::from_millis(300))
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 
 use crate::parse_command::ParsedCommand;
 use crate::protocol::FileChange;
+use crate::protocol::ReviewDecision;
 use mcp_types::RequestId;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -37,6 +38,43 @@ impl From<Vec<String>> for ExecPolicyAmendment {
     }
 }
 
+/// Rules file an approved [`ExecPolicyAmendment`] is written to.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecPolicyAmendmentTarget {
+    /// The user's `CODEX_HOME/rules/default.rules`, applied in every project.
+    #[default]
+    User,
+    /// The project's `.codex/rules/default.rules`, shared through the repository.
+    Project,
+}
+
+/// What to do with a single hunk of a proposed patch.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum HunkDecision {
+    Accept,
+    Reject,
+    /// Apply `replacement` in place of the lines the hunk covers, context
+    /// lines included.
+    Edit {
+        replacement: String,
+    },
+}
+
+/// Review of one hunk of an [`ApplyPatchApprovalRequestEvent`].
+///
+/// `path` is the key in `changes` and `hunk` is the zero-based position of the
+/// hunk within that file's unified diff. Changes without a textual diff (adds,
+/// deletes, binary, symlink and mode changes) are a single hunk at index 0.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct HunkReview {
+    pub path: PathBuf,
+    pub hunk: usize,
+    pub decision: HunkDecision,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ExecApprovalRequestEvent {
     /// Identifier for the associated exec call, if available.
@@ -56,9 +94,34 @@ pub struct ExecApprovalRequestEvent {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[ts(optional)]
     pub proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+    /// True when the amendment may also be saved to the project's rules
+    /// because the project is trusted.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub project_execpolicy_available: bool,
     pub parsed_cmd: Vec<ParsedCommand>,
 }
 
+/// Who settled a tool call's approval.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalDecisionSource {
+    /// The approval policy, execpolicy rules or sandbox settled it without
+    /// asking.
+    Config,
+    /// The user answered an approval request, now or earlier in the session.
+    User,
+}
+
+/// How the approval for a tool call was settled. Recorded in the rollout so
+/// past sessions can be replayed against other execpolicy rules.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct ApprovalDecisionEvent {
+    pub call_id: String,
+    /// `Approved` when the call may run, `Denied` when it was refused.
+    pub decision: ReviewDecision,
+    pub source: ApprovalDecisionSource,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ElicitationRequestEvent {
     pub server_name: String,
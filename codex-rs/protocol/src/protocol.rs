@@ -39,9 +39,14 @@ use tracing::error;
 use ts_rs::TS;
 
 pub use crate::approvals::ApplyPatchApprovalRequestEvent;
+pub use crate::approvals::ApprovalDecisionEvent;
+pub use crate::approvals::ApprovalDecisionSource;
 pub use crate::approvals::ElicitationAction;
 pub use crate::approvals::ExecApprovalRequestEvent;
 pub use crate::approvals::ExecPolicyAmendment;
+pub use crate::approvals::ExecPolicyAmendmentTarget;
+pub use crate::approvals::HunkDecision;
+pub use crate::approvals::HunkReview;
 
 /// Open/close tags for special user-input blocks. Used across crates to avoid
 /// duplicated hardcoded strings.
@@ -129,6 +134,12 @@ pub enum Op {
         #[serde(skip_serializing_if = "Option::is_none")]
         model: Option<String>,
 
+        /// Updated model provider, as a key of `model_providers`. Set it when
+        /// `model` is served by a different provider, e.g. a model discovered
+        /// from another configured provider.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        model_provider: Option<String>,
+
         /// Updated reasoning effort (honored only for reasoning-capable models).
         ///
         /// Use `Some(Some(_))` to set a specific effort, `Some(None)` to clear
@@ -204,9 +215,35 @@ pub enum Op {
     /// to generate a summary which will be returned as an AgentMessage event.
     Compact,
 
+    /// Pin a message so that compaction keeps it verbatim. Pins take effect
+    /// when the `structured_compaction` feature is enabled.
+    PinMessage {
+        /// Text of the message to keep.
+        message: String,
+    },
+
+    /// Remove a message previously pinned with `Op::PinMessage`.
+    UnpinMessage {
+        /// Text of the pinned message, as passed to `Op::PinMessage`.
+        message: String,
+    },
+
     /// Request Codex to undo a turn (turn are stacked so it is the same effect as CMD + Z).
     Undo,
 
+    /// Request every checkpoint (per-turn workspace snapshot) recorded in this
+    /// session. Reply is delivered via `EventMsg::ListCheckpointsResponse`.
+    ListCheckpoints,
+
+    /// Roll files back to an earlier checkpoint without touching the
+    /// conversation. An empty `paths` list restores every file changed since
+    /// the checkpoint. Reply is delivered via `EventMsg::CheckpointRestored`.
+    RestoreCheckpoint {
+        id: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        paths: Vec<CheckpointRestorePath>,
+    },
+
     /// Request a code review from the agent.
     Review { review_request: ReviewRequest },
 
@@ -560,6 +597,9 @@ pub enum EventMsg {
     /// Conversation history was compacted (either automatically or manually).
     ContextCompacted(ContextCompactedEvent),
 
+    /// The pinned messages after an `Op::PinMessage` or `Op::UnpinMessage`.
+    PinnedMessagesUpdated(PinnedMessagesUpdatedEvent),
+
     /// Agent has started a task
     TaskStarted(TaskStartedEvent),
 
@@ -626,6 +666,9 @@ pub enum EventMsg {
 
     ExecApprovalRequest(ExecApprovalRequestEvent),
 
+    /// How a tool call's approval was settled, with or without asking.
+    ApprovalDecision(ApprovalDecisionEvent),
+
     ElicitationRequest(ElicitationRequestEvent),
 
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
@@ -640,6 +683,11 @@ pub enum EventMsg {
 
     UndoCompleted(UndoCompletedEvent),
 
+    /// List of checkpoints available to `Op::RestoreCheckpoint`.
+    ListCheckpointsResponse(ListCheckpointsResponseEvent),
+
+    CheckpointRestored(CheckpointRestoredEvent),
+
     /// Notification that a model stream experienced an error or disconnect
     /// and the system is handling it (e.g., retrying with backoff).
     StreamError(StreamErrorEvent),
@@ -864,6 +912,12 @@ pub struct WarningEvent {
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ContextCompactedEvent;
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PinnedMessagesUpdatedEvent {
+    /// Every pinned message, oldest first.
+    pub messages: Vec<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TaskCompleteEvent {
     pub last_agent_message: Option<String>,
@@ -1291,6 +1345,13 @@ pub struct SessionMeta {
     #[serde(default)]
     pub source: SessionSource,
     pub model_provider: Option<String>,
+    /// Short generated title. Rollouts append a new `session_meta` line each
+    /// time it is refreshed; the last one wins.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// One-paragraph generated summary, refreshed together with `title`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
 }
 
 impl Default for SessionMeta {
@@ -1304,6 +1365,8 @@ impl Default for SessionMeta {
             instructions: None,
             source: SessionSource::default(),
             model_provider: None,
+            title: None,
+            summary: None,
         }
     }
 }
@@ -1609,6 +1672,48 @@ pub struct UndoCompletedEvent {
     pub message: Option<String>,
 }
 
+/// Workspace snapshot taken at the start of a turn.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CheckpointSummary {
+    pub id: String,
+    /// 1-based index of the user turn that created the checkpoint.
+    pub turn: u32,
+    /// First line of the prompt that started the turn.
+    pub prompt_preview: String,
+    /// Changes made to the workspace since the checkpoint was taken, keyed by
+    /// absolute path. Restoring a path reverts its change.
+    pub changes: HashMap<PathBuf, FileChange>,
+}
+
+/// Response payload for `Op::ListCheckpoints`, oldest checkpoint first.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListCheckpointsResponseEvent {
+    pub checkpoints: Vec<CheckpointSummary>,
+}
+
+/// A file to roll back with `Op::RestoreCheckpoint`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CheckpointRestorePath {
+    /// Absolute, or relative to the session `cwd`.
+    pub path: PathBuf,
+    /// Indexes of the hunks in the file's `FileChange::Update` diff to revert.
+    /// Reverts the whole file when omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub hunks: Option<Vec<usize>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CheckpointRestoredEvent {
+    pub id: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Absolute paths that were rolled back.
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamErrorEvent {
     pub message: String,
@@ -1837,6 +1942,9 @@ pub enum ReviewDecision {
     /// amendment so future matching commands are permitted.
     ApprovedExecpolicyAmendment {
         proposed_execpolicy_amendment: ExecPolicyAmendment,
+        /// Rules file the amendment is saved to.
+        #[serde(default)]
+        target: ExecPolicyAmendmentTarget,
     },
 
     /// User has approved this command and wants to automatically approve any
@@ -1844,6 +1952,10 @@ pub enum ReviewDecision {
     /// remainder of the session.
     ApprovedForSession,
 
+    /// User has reviewed a patch hunk by hunk. Only accepted and edited hunks
+    /// are applied; hunks missing from `hunks` count as accepted.
+    ApprovedWithHunkReview { hunks: Vec<HunkReview> },
+
     /// User has denied this command and the agent should not execute it, but
     /// it should continue the session and try something else.
     #[default]
@@ -1868,6 +1980,48 @@ pub enum FileChange {
         unified_diff: String,
         move_path: Option<PathBuf>,
     },
+    /// Binary contents written in full. Only sizes are carried; `old_size` is
+    /// `None` when the file is created.
+    Binary {
+        old_size: Option<u64>,
+        new_size: u64,
+    },
+    /// A symbolic link created at the path, pointing at `target`.
+    Symlink {
+        target: PathBuf,
+    },
+    /// Permission bits changed without touching the contents.
+    Mode {
+        old_mode: Option<u32>,
+        new_mode: u32,
+    },
+}
+
+impl FileChange {
+    /// One-line description of changes that have no textual diff (binary
+    /// contents, symlinks and permission changes).
+    pub fn non_text_summary(&self) -> Option<String> {
+        match self {
+            FileChange::Add { .. } | FileChange::Delete { .. } | FileChange::Update { .. } => None,
+            FileChange::Binary {
+                old_size: None,
+                new_size,
+            } => Some(format!("binary file ({new_size} bytes)")),
+            FileChange::Binary {
+                old_size: Some(old_size),
+                new_size,
+            } => Some(format!("binary file ({old_size} -> {new_size} bytes)")),
+            FileChange::Symlink { target } => Some(format!("symlink -> {}", target.display())),
+            FileChange::Mode {
+                old_mode: Some(old_mode),
+                new_mode,
+            } => Some(format!("mode {old_mode:o} -> {new_mode:o}")),
+            FileChange::Mode {
+                old_mode: None,
+                new_mode,
+            } => Some(format!("mode {new_mode:o}")),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -15,6 +15,7 @@
 //! file because we don't use the "vendored" feature.
 //!
 //! If the keyring is not available or fails, we fall back to CODEX_HOME/.credentials.json which is consistent with other coding CLI agents.
+//! That file is encrypted when `CODEX_AUTH_KEY` or `CODEX_AUTH_KEY_FILE` is set, and always encrypted in `Encrypted` mode.
 
 use anyhow::Context;
 use anyhow::Error;
@@ -44,6 +45,9 @@ use tracing::warn;
 
 use codex_keyring_store::DefaultKeyringStore;
 use codex_keyring_store::KeyringStore;
+use codex_keyring_store::encrypted_file;
+use codex_keyring_store::encrypted_file::FileEncryption;
+use codex_keyring_store::encrypted_file::FileEncryptionKey;
 use rmcp::transport::auth::AuthorizationManager;
 use tokio::sync::Mutex;
 
@@ -75,6 +79,9 @@ pub enum OAuthCredentialsStoreMode {
     File,
     /// Keyring when available, otherwise fail.
     Keyring,
+    /// CODEX_HOME/.credentials.json, encrypted with the key from `CODEX_AUTH_KEY` or `CODEX_AUTH_KEY_FILE`.
+    /// Fails if no key is configured.
+    Encrypted,
 }
 
 /// Wrap OAuthTokenResponse to allow for partial equality comparison.
@@ -105,6 +112,10 @@ pub(crate) fn load_oauth_tokens(
             load_oauth_tokens_from_keyring(&keyring_store, server_name, url)
                 .with_context(|| "failed to read OAuth tokens from keyring".to_string())
         }
+        OAuthCredentialsStoreMode::Encrypted => {
+            encrypt_plaintext_fallback_file()?;
+            load_oauth_tokens_from_file(server_name, url)
+        }
     }
 }
 
@@ -178,10 +189,15 @@ pub fn save_oauth_tokens(
             server_name,
             tokens,
         ),
-        OAuthCredentialsStoreMode::File => save_oauth_tokens_to_file(tokens),
+        OAuthCredentialsStoreMode::File => {
+            save_oauth_tokens_to_file(tokens, FileEncryption::Plaintext)
+        }
         OAuthCredentialsStoreMode::Keyring => {
             save_oauth_tokens_with_keyring(&keyring_store, server_name, tokens)
         }
+        OAuthCredentialsStoreMode::Encrypted => {
+            save_oauth_tokens_to_file(tokens, FileEncryption::Required)
+        }
     }
 }
 
@@ -221,7 +237,7 @@ fn save_oauth_tokens_with_keyring_with_fallback_to_file<K: KeyringStore>(
         Err(error) => {
             let message = error.to_string();
             warn!("falling back to file storage for OAuth tokens: {message}");
-            save_oauth_tokens_to_file(tokens)
+            save_oauth_tokens_to_file(tokens, FileEncryption::WhenKeyAvailable)
                 .with_context(|| format!("failed to write OAuth tokens to keyring: {message}"))
         }
     }
@@ -254,7 +270,7 @@ fn delete_oauth_tokens_from_keyring_and_file<K: KeyringStore>(
                     return Err(error.into_error())
                         .context("failed to delete OAuth tokens from keyring");
                 }
-                OAuthCredentialsStoreMode::File => false,
+                OAuthCredentialsStoreMode::File | OAuthCredentialsStoreMode::Encrypted => false,
             }
         }
     };
@@ -394,7 +410,7 @@ struct FallbackTokenEntry {
 }
 
 fn load_oauth_tokens_from_file(server_name: &str, url: &str) -> Result<Option<StoredOAuthTokens>> {
-    let Some(store) = read_fallback_file()? else {
+    let Some((store, _)) = read_fallback_file()? else {
         return Ok(None);
     };
 
@@ -436,9 +452,11 @@ fn load_oauth_tokens_from_file(server_name: &str, url: &str) -> Result<Option<St
     Ok(None)
 }
 
-fn save_oauth_tokens_to_file(tokens: &StoredOAuthTokens) -> Result<()> {
+fn save_oauth_tokens_to_file(tokens: &StoredOAuthTokens, encryption: FileEncryption) -> Result<()> {
     let key = compute_store_key(&tokens.server_name, &tokens.url)?;
-    let mut store = read_fallback_file()?.unwrap_or_default();
+    let mut store = read_fallback_file()?
+        .map(|(store, _)| store)
+        .unwrap_or_default();
 
     let token_response = &tokens.token_response.0;
     let expires_at = tokens
@@ -462,24 +480,33 @@ fn save_oauth_tokens_to_file(tokens: &StoredOAuthTokens) -> Result<()> {
     };
 
     store.insert(key, entry);
-    write_fallback_file(&store)
+    write_fallback_file(&store, encryption)
 }
 
 fn delete_oauth_tokens_from_file(key: &str) -> Result<bool> {
-    let mut store = match read_fallback_file()? {
-        Some(store) => store,
+    let (mut store, encryption) = match read_fallback_file()? {
+        Some(contents) => contents,
         None => return Ok(false),
     };
 
     let removed = store.remove(key).is_some();
 
     if removed {
-        write_fallback_file(&store)?;
+        write_fallback_file(&store, encryption)?;
     }
 
     Ok(removed)
 }
 
+/// Rewrites a plaintext fallback file in encrypted form.
+fn encrypt_plaintext_fallback_file() -> Result<()> {
+    if let Some((store, FileEncryption::Plaintext)) = read_fallback_file()? {
+        write_fallback_file(&store, FileEncryption::Required)
+            .context("failed to encrypt existing OAuth credentials file")?;
+    }
+    Ok(())
+}
+
 pub(crate) fn compute_expires_at_millis(response: &OAuthTokenResponse) -> Option<u64> {
     let expires_in = response.expires_in()?;
     let now = SystemTime::now()
@@ -539,7 +566,9 @@ fn fallback_file_path() -> Result<PathBuf> {
     Ok(path)
 }
 
-fn read_fallback_file() -> Result<Option<FallbackFile>> {
+/// Reads the fallback file, decrypting it if needed. Also returns the
+/// encryption to use when writing it back in the same format.
+fn read_fallback_file() -> Result<Option<(FallbackFile, FileEncryption)>> {
     let path = fallback_file_path()?;
     let contents = match fs::read_to_string(&path) {
         Ok(contents) => contents,
@@ -552,8 +581,20 @@ fn read_fallback_file() -> Result<Option<FallbackFile>> {
         }
     };
 
+    let key = if encrypted_file::is_encrypted(&contents) {
+        FileEncryptionKey::from_env()?
+    } else {
+        None
+    };
+    let (contents, was_encrypted) = encrypted_file::open(key.as_ref(), contents, &path)?;
+    let encryption = if was_encrypted {
+        FileEncryption::Required
+    } else {
+        FileEncryption::Plaintext
+    };
+
     match serde_json::from_str::<FallbackFile>(&contents) {
-        Ok(store) => Ok(Some(store)),
+        Ok(store) => Ok(Some((store, encryption))),
         Err(e) => Err(e).context(format!(
             "failed to parse credentials file at {}",
             path.display()
@@ -561,7 +602,7 @@ fn read_fallback_file() -> Result<Option<FallbackFile>> {
     }
 }
 
-fn write_fallback_file(store: &FallbackFile) -> Result<()> {
+fn write_fallback_file(store: &FallbackFile, encryption: FileEncryption) -> Result<()> {
     let path = fallback_file_path()?;
 
     if store.is_empty() {
@@ -576,6 +617,13 @@ fn write_fallback_file(store: &FallbackFile) -> Result<()> {
     }
 
     let serialized = serde_json::to_string(store)?;
+    let key = match encryption {
+        FileEncryption::Plaintext => None,
+        FileEncryption::WhenKeyAvailable | FileEncryption::Required => {
+            FileEncryptionKey::from_env()?
+        }
+    };
+    let serialized = encrypted_file::seal(encryption, key.as_ref(), &serialized, &path)?;
     fs::write(&path, serialized)?;
 
     #[cfg(unix)]
@@ -668,7 +716,7 @@ mod tests {
         let tokens = sample_tokens();
         let expected = tokens.clone();
 
-        super::save_oauth_tokens_to_file(&tokens)?;
+        super::save_oauth_tokens_to_file(&tokens, FileEncryption::Plaintext)?;
 
         let loaded = super::load_oauth_tokens_from_keyring_with_fallback_to_file(
             &store,
@@ -689,7 +737,7 @@ mod tests {
         let key = super::compute_store_key(&tokens.server_name, &tokens.url)?;
         store.set_error(&key, KeyringError::Invalid("error".into(), "load".into()));
 
-        super::save_oauth_tokens_to_file(&tokens)?;
+        super::save_oauth_tokens_to_file(&tokens, FileEncryption::Plaintext)?;
 
         let loaded = super::load_oauth_tokens_from_keyring_with_fallback_to_file(
             &store,
@@ -708,7 +756,7 @@ mod tests {
         let tokens = sample_tokens();
         let key = super::compute_store_key(&tokens.server_name, &tokens.url)?;
 
-        super::save_oauth_tokens_to_file(&tokens)?;
+        super::save_oauth_tokens_to_file(&tokens, FileEncryption::Plaintext)?;
 
         super::save_oauth_tokens_with_keyring_with_fallback_to_file(
             &store,
@@ -739,7 +787,7 @@ mod tests {
 
         let fallback_path = super::fallback_file_path()?;
         assert!(fallback_path.exists(), "fallback file should be created");
-        let saved = super::read_fallback_file()?.expect("fallback file should load");
+        let (saved, _) = super::read_fallback_file()?.expect("fallback file should load");
         let key = super::compute_store_key(&tokens.server_name, &tokens.url)?;
         let entry = saved.get(&key).expect("entry for key");
         assert_eq!(entry.server_name, tokens.server_name);
@@ -761,7 +809,7 @@ mod tests {
         let serialized = serde_json::to_string(&tokens)?;
         let key = super::compute_store_key(&tokens.server_name, &tokens.url)?;
         store.save(KEYRING_SERVICE, &key, &serialized)?;
-        super::save_oauth_tokens_to_file(&tokens)?;
+        super::save_oauth_tokens_to_file(&tokens, FileEncryption::Plaintext)?;
 
         let removed = super::delete_oauth_tokens_from_keyring_and_file(
             &store,
@@ -804,7 +852,7 @@ mod tests {
         let tokens = sample_tokens();
         let key = super::compute_store_key(&tokens.server_name, &tokens.url)?;
         store.set_error(&key, KeyringError::Invalid("error".into(), "delete".into()));
-        super::save_oauth_tokens_to_file(&tokens).unwrap();
+        super::save_oauth_tokens_to_file(&tokens, FileEncryption::Plaintext).unwrap();
 
         let result = super::delete_oauth_tokens_from_keyring_and_file(
             &store,
@@ -817,6 +865,46 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn encrypted_mode_migrates_plaintext_fallback_file() -> Result<()> {
+        let _env = TempCodexHome::new();
+        let tokens = sample_tokens();
+        super::save_oauth_tokens_to_file(&tokens, FileEncryption::Plaintext)?;
+
+        unsafe {
+            std::env::set_var(encrypted_file::CODEX_AUTH_KEY_ENV_VAR, "test passphrase");
+        }
+        let loaded = load_oauth_tokens(
+            &tokens.server_name,
+            &tokens.url,
+            OAuthCredentialsStoreMode::Encrypted,
+        );
+        unsafe {
+            std::env::remove_var(encrypted_file::CODEX_AUTH_KEY_ENV_VAR);
+        }
+
+        let loaded = loaded?.expect("tokens should load from encrypted file");
+        assert_tokens_match_without_expiry(&loaded, &tokens);
+        let contents = fs::read_to_string(super::fallback_file_path()?)?;
+        assert!(encrypted_file::is_encrypted(&contents));
+        assert!(
+            !contents.contains(tokens.token_response.0.access_token().secret().as_str()),
+            "access token should not be stored in plaintext"
+        );
+
+        let error = load_oauth_tokens(
+            &tokens.server_name,
+            &tokens.url,
+            OAuthCredentialsStoreMode::Encrypted,
+        )
+        .expect_err("loading without a key should fail");
+        assert!(
+            format!("{error:#}").contains(encrypted_file::CODEX_AUTH_KEY_ENV_VAR),
+            "unexpected error: {error:#}"
+        );
+        Ok(())
+    }
+
     #[test]
     fn refresh_expires_in_from_timestamp_restores_future_durations() {
         let mut tokens = sample_tokens();
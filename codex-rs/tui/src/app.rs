@@ -10,6 +10,9 @@ use crate::exec_command::strip_bash_lc_and_escape;
 use crate::file_search::FileSearchManager;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::UserHistoryCell;
+use crate::keymap::KeyAction;
+use crate::keymap::KeyScope;
+use crate::keymap::keymap;
 use crate::model_migration::ModelMigrationOutcome;
 use crate::model_migration::migration_copy_for_models;
 use crate::model_migration::run_model_migration_prompt;
@@ -78,6 +81,7 @@ use unicode_width::UnicodeWidthStr;
 
 #[cfg(not(debug_assertions))]
 use crate::history_cell::UpdateAvailableHistoryCell;
+use crate::theme::theme;
 
 const GPT_5_1_MIGRATION_AUTH_MODES: [AuthMode; 2] = [AuthMode::ChatGPT, AuthMode::ApiKey];
 const GPT_5_1_CODEX_MIGRATION_AUTH_MODES: [AuthMode; 1] = [AuthMode::ChatGPT];
@@ -265,6 +269,7 @@ async fn handle_model_migration_prompt_if_needed(
                 app_event_tx.send(AppEvent::PersistModelSelection {
                     model: target_model.clone(),
                     effort: mapped_effort,
+                    model_provider: None,
                 });
             }
             ModelMigrationOutcome::Rejected => {
@@ -458,6 +463,7 @@ impl App {
         initial_images: Vec<PathBuf>,
         resume_selection: ResumeSelection,
         feedback: codex_feedback::CodexFeedback,
+        config_warnings: Vec<String>,
     ) -> Result<AppExitInfo> {
         use tokio_stream::StreamExt;
         let (app_event_tx, mut app_event_rx) = unbounded_channel();
@@ -558,6 +564,10 @@ impl App {
 
         chat_widget.maybe_prompt_windows_sandbox_enable();
 
+        for warning in config_warnings {
+            chat_widget.add_error_message(warning);
+        }
+
         let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
         #[cfg(not(debug_assertions))]
         let upgrade_version = crate::updates::get_upgrade_version(&config);
@@ -911,7 +921,10 @@ impl App {
                 if let Some(summary) = summary {
                     let mut lines: Vec<Line<'static>> = vec![summary.usage_line.clone().into()];
                     if let Some(command) = summary.resume_command {
-                        let spans = vec!["To continue this session, run ".into(), command.cyan()];
+                        let spans = vec![
+                            "To continue this session, run ".into(),
+                            command.fg(theme().accent),
+                        ];
                         lines.push(spans.into());
                     }
                     self.chat_widget.add_plain_history_lines(lines);
@@ -1042,6 +1055,14 @@ impl App {
                 ));
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::OpenCheckpoints(checkpoints) => {
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::new_checkpoints(
+                    checkpoints,
+                    self.config.cwd.clone(),
+                ));
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::StartFileSearch(query) => {
                 if !query.is_empty() {
                     self.file_search.on_user_query(query);
@@ -1079,11 +1100,18 @@ impl App {
                     self.config.model_provider = provider.clone();
                 }
             }
-            AppEvent::OpenReasoningPopup { model } => {
-                self.chat_widget.open_reasoning_popup(model);
+            AppEvent::OpenReasoningPopup {
+                model,
+                model_provider,
+            } => {
+                self.chat_widget.open_reasoning_popup(model, model_provider);
             }
-            AppEvent::OpenAllModelsPopup { models } => {
-                self.chat_widget.open_all_models_popup(models);
+            AppEvent::OpenAllModelsPopup {
+                models,
+                model_provider,
+            } => {
+                self.chat_widget
+                    .open_all_models_popup(models, model_provider);
             }
             AppEvent::OpenFullAccessConfirmation { preset } => {
                 self.chat_widget.open_full_access_confirmation(preset);
@@ -1145,6 +1173,7 @@ impl App {
                                         approval_policy: Some(preset.approval),
                                         sandbox_policy: Some(preset.sandbox.clone()),
                                         model: None,
+                                        model_provider: None,
                                         effort: None,
                                         summary: None,
                                     },
@@ -1175,14 +1204,19 @@ impl App {
                     let _ = preset;
                 }
             }
-            AppEvent::PersistModelSelection { model, effort } => {
+            AppEvent::PersistModelSelection {
+                model,
+                effort,
+                model_provider,
+            } => {
                 let profile = self.active_profile.as_deref();
-                match ConfigEditsBuilder::new(&self.config.codex_home)
+                let mut edits = ConfigEditsBuilder::new(&self.config.codex_home)
                     .with_profile(profile)
-                    .set_model(Some(model.as_str()), effort)
-                    .apply()
-                    .await
-                {
+                    .set_model(Some(model.as_str()), effort);
+                if let Some(model_provider) = model_provider.as_deref() {
+                    edits = edits.set_model_provider(model_provider);
+                }
+                match edits.apply().await {
                     Ok(()) => {
                         let reasoning_label = Self::reasoning_label(effort);
                         if let Some(profile) = profile {
@@ -1753,6 +1787,7 @@ impl App {
             mode: DelegateSessionMode::Standard,
         });
 
+        let meta_model = codex_tumix::MetaAgentModel::from_config(&self.config);
         let tx = self.app_event_tx.clone();
         tokio::spawn(async move {
             let agent_id = agent_id_for_task;
@@ -1768,7 +1803,13 @@ impl App {
                 }));
             });
 
-            let result = codex_tumix::run_tumix(session_id, user_prompt, Some(progress_cb)).await;
+            let result = codex_tumix::run_tumix_with_meta_model(
+                session_id,
+                user_prompt,
+                Some(progress_cb),
+                meta_model,
+            )
+            .await;
 
             match result {
                 Ok(round_result) => {
@@ -1870,7 +1911,10 @@ impl App {
         if let Some(summary) = summary {
             let mut lines: Vec<Line<'static>> = vec![summary.usage_line.clone().into()];
             if let Some(command) = summary.resume_command {
-                let spans = vec!["To continue this session, run ".into(), command.cyan()];
+                let spans = vec![
+                    "To continue this session, run ".into(),
+                    command.fg(theme().accent),
+                ];
                 lines.push(spans.into());
             }
             self.chat_widget.add_plain_history_lines(lines);
@@ -1896,11 +1940,9 @@ impl App {
         match key_event {
             // F1 Toggle Bar disabled per product decision
             KeyEvent {
-                code: KeyCode::Char('t'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
                 kind: KeyEventKind::Press,
                 ..
-            } => {
+            } if keymap().is_pressed(KeyAction::OpenTranscript, &key_event) => {
                 // Enter alternate screen and set viewport to full size.
                 let _ = tui.enter_alt_screen();
                 self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
@@ -1908,11 +1950,9 @@ impl App {
             }
             // Ctrl+P - Quick session search/picker (and switch focus to Sessions)
             KeyEvent {
-                code: KeyCode::Char('p'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
                 kind: KeyEventKind::Press,
                 ..
-            } => {
+            } if keymap().is_pressed(KeyAction::FocusSessions, &key_event) => {
                 // Focus sessions (bar is always visible now)
                 self.panel_focus = PanelFocus::Sessions;
                 self.session_bar.set_focus(true);
@@ -1922,11 +1962,9 @@ impl App {
                 tui.frame_requester().schedule_frame();
             }
             KeyEvent {
-                code: KeyCode::Char('g'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
                 kind: KeyEventKind::Press,
                 ..
-            } => {
+            } if keymap().is_pressed(KeyAction::GitGraph, &key_event) => {
                 // Show git graph for current directory
                 match crate::git_graph_widget::create_git_graph_overlay(".") {
                     Ok(overlay) => {
@@ -1937,7 +1975,7 @@ impl App {
                     Err(err) => {
                         // Show error message to user via overlay
                         let error_lines = vec![
-                            "Failed to generate git graph:".red().into(),
+                            "Failed to generate git graph:".fg(theme().error).into(),
                             Line::from(""),
                             err.clone().dim().into(),
                             Line::from(""),
@@ -1954,11 +1992,9 @@ impl App {
                 }
             }
             KeyEvent {
-                code: KeyCode::Char('x' | 'q'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
                 kind: KeyEventKind::Press,
                 ..
-            } => {
+            } if keymap().is_pressed(KeyAction::SessionPicker, &key_event) => {
                 self.open_or_refresh_session_picker(tui);
             }
             // Esc primes/advances backtracking only in normal (not working) mode
@@ -1966,17 +2002,11 @@ impl App {
             // Esc so the active UI (e.g. status indicator, modals, popups)
             // handles it.
             KeyEvent {
-                code: KeyCode::Esc,
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
                 ..
-            } => {
-                // If in session tab mode, Esc exits selection without changing conversation
-                if self.panel_focus == PanelFocus::Sessions {
-                    self.panel_focus = PanelFocus::Chat;
-                    self.session_bar.set_focus(false);
-                    tui.frame_requester().schedule_frame();
-                    return;
-                }
+            } if self.panel_focus == PanelFocus::Chat
+                && keymap().is_pressed(KeyAction::Backtrack, &key_event) =>
+            {
                 if self.chat_widget.is_normal_backtrack_mode()
                     && self.chat_widget.composer_is_empty()
                 {
@@ -2003,10 +2033,10 @@ impl App {
             }
             // Enter confirms backtrack when primed + count > 0. Otherwise pass to widget.
             KeyEvent {
-                code: KeyCode::Enter,
                 kind: KeyEventKind::Press,
                 ..
-            } if self.backtrack.primed
+            } if keymap().is_pressed(KeyAction::ConfirmBacktrack, &key_event)
+                && self.backtrack.primed
                 && self.backtrack.nth_user_message != usize::MAX
                 && self.chat_widget.composer_is_empty() =>
             {
@@ -2020,7 +2050,7 @@ impl App {
                 // Any non-Esc key press should cancel a primed backtrack.
                 // This avoids stale "Esc-primed" state after the user starts typing
                 // (even if they later backspace to empty).
-                if key_event.code != KeyCode::Esc && self.backtrack.primed {
+                if !keymap().is_pressed(KeyAction::Backtrack, &key_event) && self.backtrack.primed {
                     self.reset_backtrack_state();
                 }
 
@@ -2028,20 +2058,20 @@ impl App {
                 match self.panel_focus {
                     PanelFocus::Sessions => {
                         // Handle session bar navigation (horizontal)
-                        match key_event.code {
-                            KeyCode::Left | KeyCode::Char('h') => {
+                        match keymap().action_for(KeyScope::Sessions, &key_event) {
+                            Some(KeyAction::SessionPrevious) => {
                                 self.session_bar.select_previous();
                                 tui.frame_requester().schedule_frame();
                             }
-                            KeyCode::Right | KeyCode::Char('l') => {
+                            Some(KeyAction::SessionNext) => {
                                 self.session_bar.select_next();
                                 tui.frame_requester().schedule_frame();
                             }
-                            KeyCode::Char('n') => {
+                            Some(KeyAction::SessionNew) => {
                                 // 快速新建会话
                                 self.app_event_tx.send(AppEvent::NewSession);
                             }
-                            KeyCode::Enter => {
+                            Some(KeyAction::SessionOpen) => {
                                 // Enter on New vs History session
                                 if self.session_bar.selected_is_new() {
                                     self.app_event_tx.send(AppEvent::NewSession);
@@ -2050,7 +2080,7 @@ impl App {
                                         .send(AppEvent::ResumeSession(session.path.clone()));
                                 }
                             }
-                            KeyCode::Char('r') => {
+                            Some(KeyAction::SessionRename) => {
                                 // Rename selected session (edit alias) - only works on existing sessions
                                 if !self.session_bar.selected_is_new()
                                     && let Some(session) = self.session_bar.selected_session()
@@ -2075,7 +2105,7 @@ impl App {
                                     tui.frame_requester().schedule_frame();
                                 }
                             }
-                            KeyCode::Char('x') => {
+                            Some(KeyAction::SessionDelete) => {
                                 // Delete selected history session rollout file (no confirmation)
                                 if !self.session_bar.selected_is_new()
                                     && let Some(session) = self.session_bar.selected_session()
@@ -2094,7 +2124,7 @@ impl App {
                                 }
                             }
                             // Exit sessions focus; Tab no longer toggles to avoid conflicts
-                            KeyCode::Esc => {
+                            Some(KeyAction::SessionExit) => {
                                 // Return focus to chat
                                 self.panel_focus = PanelFocus::Chat;
                                 self.session_bar.set_focus(false);
@@ -8,16 +8,18 @@ use crate::cxresume_picker_widget::SessionInfo;
 use crate::cxresume_picker_widget::{self};
 use crate::history_cell::SessionInfoCell;
 use crate::history_cell::UserHistoryCell;
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
 use crate::pager_overlay::Overlay;
 use crate::tui;
 use crate::tui::TuiEvent;
+use codex_core::protocol::CheckpointRestorePath;
 use codex_core::protocol::ConversationPathResponseEvent;
+use codex_core::protocol::Op;
 use codex_protocol::ConversationId;
 use color_eyre::eyre::Result;
-use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
-use crossterm::event::KeyModifiers;
 use tracing::warn;
 
 /// Aggregates all backtrack-related state used by the App.
@@ -45,13 +47,13 @@ impl App {
         tui: &mut tui::Tui,
         event: TuiEvent,
     ) -> Result<bool> {
-        if let TuiEvent::Key(KeyEvent {
-            code: KeyCode::Char(c),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            ..
-        }) = event
-            && matches!(c, 'x' | 'q')
+        if let TuiEvent::Key(
+            key_event @ KeyEvent {
+                kind: KeyEventKind::Press,
+                ..
+            },
+        ) = event
+            && keymap().is_pressed(KeyAction::SessionPicker, &key_event)
         {
             self.open_or_refresh_session_picker(tui);
             return Ok(true);
@@ -59,19 +61,18 @@ impl App {
 
         if self.backtrack.overlay_preview_active {
             match event {
-                TuiEvent::Key(KeyEvent {
-                    code: KeyCode::Esc,
-                    kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                    ..
-                }) => {
+                TuiEvent::Key(key_event)
+                    if keymap().is_pressed(KeyAction::Backtrack, &key_event) =>
+                {
                     self.overlay_step_backtrack(tui, event)?;
                     Ok(true)
                 }
-                TuiEvent::Key(KeyEvent {
-                    code: KeyCode::Enter,
-                    kind: KeyEventKind::Press,
-                    ..
-                }) => {
+                TuiEvent::Key(
+                    key_event @ KeyEvent {
+                        kind: KeyEventKind::Press,
+                        ..
+                    },
+                ) if keymap().is_pressed(KeyAction::ConfirmBacktrack, &key_event) => {
                     self.overlay_confirm_backtrack(tui);
                     Ok(true)
                 }
@@ -81,11 +82,9 @@ impl App {
                     Ok(true)
                 }
             }
-        } else if let TuiEvent::Key(KeyEvent {
-            code: KeyCode::Esc,
-            kind: KeyEventKind::Press | KeyEventKind::Repeat,
-            ..
-        }) = event
+        } else if let TuiEvent::Key(key_event) = event
+            && keymap().is_pressed(KeyAction::Backtrack, &key_event)
+            && !matches!(self.overlay, Some(Overlay::Checkpoints(_)))
         {
             // First Esc in transcript overlay: begin backtrack preview at latest user message.
             self.begin_overlay_backtrack_preview(tui);
@@ -273,6 +272,7 @@ impl App {
                 id: Option<String>,
                 session: Box<Option<SessionInfo>>,
             },
+            Checkpoints(Option<(String, Vec<CheckpointRestorePath>)>),
             Other,
         }
 
@@ -285,6 +285,7 @@ impl App {
                         id: overlay.get_selected_session_id(),
                         session: Box::new(overlay.get_selected_session()),
                     },
+                    Overlay::Checkpoints(o) => OverlayCloseAction::Checkpoints(o.restore_request()),
                     _ => OverlayCloseAction::Other,
                 });
             }
@@ -295,6 +296,13 @@ impl App {
                 OverlayCloseAction::Session { id, session } => {
                     self.close_session_picker(tui, id, *session)?;
                 }
+                OverlayCloseAction::Checkpoints(restore) => {
+                    self.close_transcript_overlay(tui);
+                    if let Some((id, paths)) = restore {
+                        self.chat_widget
+                            .submit_op(Op::RestoreCheckpoint { id, paths });
+                    }
+                }
                 OverlayCloseAction::Other => self.close_transcript_overlay(tui),
             }
             tui.frame_requester().schedule_frame();
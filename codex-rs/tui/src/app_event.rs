@@ -13,6 +13,7 @@ use crate::cxresume_picker_widget::PickerState;
 use crate::history_cell::HistoryCell;
 
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::CheckpointSummary;
 use codex_core::protocol::SandboxPolicy;
 use codex_protocol::openai_models::ReasoningEffort;
 
@@ -71,6 +72,9 @@ pub(crate) enum AppEvent {
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Open the checkpoint timeline returned by `Op::ListCheckpoints`.
+    OpenCheckpoints(Vec<CheckpointSummary>),
+
     InsertHistoryCell(Box<dyn HistoryCell>),
 
     StartCommitAnimation,
@@ -87,16 +91,22 @@ pub(crate) enum AppEvent {
     PersistModelSelection {
         model: String,
         effort: Option<ReasoningEffort>,
+        /// Provider to persist alongside a model discovered from another provider.
+        model_provider: Option<String>,
     },
 
     /// Open the reasoning selection popup after picking a model.
     OpenReasoningPopup {
         model: ModelPreset,
+        /// Provider serving `model`, for models discovered from a provider.
+        model_provider: Option<String>,
     },
 
     /// Open the full model list after selecting "All models".
     OpenAllModelsPopup {
         models: Vec<ModelPreset>,
+        /// Provider serving `models`, for models discovered from a provider.
+        model_provider: Option<String>,
     },
 
     /// Open the confirmation prompt before enabling full access mode.
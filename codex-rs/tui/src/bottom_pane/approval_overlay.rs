@@ -8,8 +8,11 @@ use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::list_selection_view::ListSelectionView;
 use crate::bottom_pane::list_selection_view::SelectionItem;
 use crate::bottom_pane::list_selection_view::SelectionViewParams;
+use crate::bottom_pane::patch_hunks::PatchHunk;
+use crate::bottom_pane::patch_hunks::split_into_hunks;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::external_editor::edit_in_external_editor;
 use crate::history_cell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
@@ -20,7 +23,10 @@ use codex_core::features::Feature;
 use codex_core::features::Features;
 use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ExecPolicyAmendment;
+use codex_core::protocol::ExecPolicyAmendmentTarget;
 use codex_core::protocol::FileChange;
+use codex_core::protocol::HunkDecision;
+use codex_core::protocol::HunkReview;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
 use crossterm::event::KeyCode;
@@ -44,6 +50,7 @@ pub(crate) enum ApprovalRequest {
         command: Vec<String>,
         reason: Option<String>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+        project_execpolicy_available: bool,
     },
     ApplyPatch {
         id: String,
@@ -69,6 +76,29 @@ pub(crate) struct ApprovalOverlay {
     current_complete: bool,
     done: bool,
     features: Features,
+    hunk_review: Option<HunkReviewState>,
+}
+
+/// Progress through a patch the user chose to review hunk by hunk.
+struct HunkReviewState {
+    id: String,
+    cwd: PathBuf,
+    hunks: Vec<PatchHunk>,
+    current: usize,
+    reviews: Vec<HunkReview>,
+}
+
+impl HunkReviewState {
+    fn record(&mut self, decision: HunkDecision) {
+        if let Some(hunk) = self.hunks.get(self.current) {
+            self.reviews.push(HunkReview {
+                path: hunk.path.clone(),
+                hunk: hunk.index,
+                decision,
+            });
+            self.current += 1;
+        }
+    }
 }
 
 impl ApprovalOverlay {
@@ -83,6 +113,7 @@ impl ApprovalOverlay {
             current_complete: false,
             done: false,
             features,
+            hunk_review: None,
         };
         view.set_current(request);
         view
@@ -97,6 +128,7 @@ impl ApprovalOverlay {
         let ApprovalRequestState { variant, header } = ApprovalRequestState::from(request);
         self.current_variant = Some(variant.clone());
         self.current_complete = false;
+        self.hunk_review = None;
         let (options, params) = Self::build_options(variant, header, &self.features);
         self.options = options;
         self.list = ListSelectionView::new(params, self.app_event_tx.clone());
@@ -110,9 +142,14 @@ impl ApprovalOverlay {
         let (options, title) = match &variant {
             ApprovalVariant::Exec {
                 proposed_execpolicy_amendment,
+                project_execpolicy_available,
                 ..
             } => (
-                exec_options(proposed_execpolicy_amendment.clone(), features),
+                exec_options(
+                    proposed_execpolicy_amendment.clone(),
+                    *project_execpolicy_available,
+                    features,
+                ),
                 "Would you like to run the following command?".to_string(),
             ),
             ApprovalVariant::ApplyPatch { .. } => (
@@ -125,6 +162,15 @@ impl ApprovalOverlay {
             ),
         };
 
+        let params = Self::selection_params(&options, title, header);
+        (options, params)
+    }
+
+    fn selection_params(
+        options: &[ApprovalOption],
+        title: String,
+        header: Box<dyn Renderable>,
+    ) -> SelectionViewParams {
         let header = Box::new(ColumnRenderable::with([
             Line::from(title.bold()).into(),
             Line::from("").into(),
@@ -143,7 +189,7 @@ impl ApprovalOverlay {
             })
             .collect();
 
-        let params = SelectionViewParams {
+        SelectionViewParams {
             footer_hint: Some(Line::from(vec![
                 "Press ".into(),
                 key_hint::plain(KeyCode::Enter).into(),
@@ -154,18 +200,28 @@ impl ApprovalOverlay {
             items,
             header,
             ..Default::default()
-        };
-
-        (options, params)
+        }
     }
 
     fn apply_selection(&mut self, actual_idx: usize) {
         if self.current_complete {
             return;
         }
-        let Some(option) = self.options.get(actual_idx) else {
+        let Some(option) = self.options.get(actual_idx).cloned() else {
             return;
         };
+        match option.decision {
+            ApprovalDecision::ReviewHunks => {
+                self.start_hunk_review();
+                return;
+            }
+            ApprovalDecision::Hunk(action) => {
+                if !self.apply_hunk_action(action) {
+                    return;
+                }
+            }
+            ApprovalDecision::Review(_) | ApprovalDecision::McpElicitation(_) => {}
+        }
         if let Some(variant) = self.current_variant.as_ref() {
             match (variant, &option.decision) {
                 (ApprovalVariant::Exec { id, command, .. }, ApprovalDecision::Review(decision)) => {
@@ -207,6 +263,104 @@ impl ApprovalOverlay {
         }));
     }
 
+    fn start_hunk_review(&mut self) {
+        let Some(ApprovalVariant::ApplyPatch { id, cwd, changes }) = self.current_variant.clone()
+        else {
+            return;
+        };
+        self.hunk_review = Some(HunkReviewState {
+            id,
+            cwd,
+            hunks: split_into_hunks(&changes),
+            current: 0,
+            reviews: Vec::new(),
+        });
+        self.show_current_hunk();
+    }
+
+    fn show_current_hunk(&mut self) {
+        let Some(state) = self.hunk_review.as_ref() else {
+            return;
+        };
+        let Some(hunk) = state.hunks.get(state.current) else {
+            return;
+        };
+        let title = format!("Apply hunk {} of {}?", state.current + 1, state.hunks.len());
+        let header = DiffSummary::new(
+            HashMap::from([(hunk.path.clone(), hunk.change.clone())]),
+            state.cwd.clone(),
+        )
+        .into();
+        let options = hunk_options(hunk.editable.is_some());
+        let params = Self::selection_params(&options, title, header);
+        self.options = options;
+        self.list = ListSelectionView::new(params, self.app_event_tx.clone());
+    }
+
+    /// Records the decision for the current hunk. Returns true once every hunk
+    /// has been reviewed and the decision was sent.
+    fn apply_hunk_action(&mut self, action: HunkAction) -> bool {
+        let Some(state) = self.hunk_review.as_mut() else {
+            return false;
+        };
+        match action {
+            HunkAction::Accept => state.record(HunkDecision::Accept),
+            HunkAction::Reject => state.record(HunkDecision::Reject),
+            HunkAction::Edit => {
+                let Some(original) = state
+                    .hunks
+                    .get(state.current)
+                    .and_then(|hunk| hunk.editable.clone())
+                else {
+                    return false;
+                };
+                match edit_in_external_editor(&original) {
+                    Ok(edited) if edited == original => state.record(HunkDecision::Accept),
+                    Ok(replacement) => state.record(HunkDecision::Edit { replacement }),
+                    Err(err) => {
+                        self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                            history_cell::new_error_event(format!(
+                                "Failed to open the hunk in your editor: {err}"
+                            )),
+                        )));
+                        return false;
+                    }
+                }
+            }
+            HunkAction::AcceptRemaining => {
+                while state.current < state.hunks.len() {
+                    state.record(HunkDecision::Accept);
+                }
+            }
+            HunkAction::RejectRemaining => {
+                while state.current < state.hunks.len() {
+                    state.record(HunkDecision::Reject);
+                }
+            }
+        }
+
+        if state.current < state.hunks.len() {
+            self.show_current_hunk();
+            return false;
+        }
+        let Some(state) = self.hunk_review.take() else {
+            return false;
+        };
+        let decision = if state
+            .reviews
+            .iter()
+            .all(|review| review.decision == HunkDecision::Accept)
+        {
+            ReviewDecision::Approved
+        } else {
+            ReviewDecision::ApprovedWithHunkReview {
+                hunks: state.reviews,
+            }
+        };
+        self.handle_patch_decision(&state.id, decision);
+        true
+    }
+
     fn handle_elicitation_decision(
         &self,
         server_name: &str,
@@ -343,6 +497,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                 command,
                 reason,
                 proposed_execpolicy_amendment,
+                project_execpolicy_available,
             } => {
                 let mut header: Vec<Line<'static>> = Vec::new();
                 if let Some(reason) = reason {
@@ -360,6 +515,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                         id,
                         command,
                         proposed_execpolicy_amendment,
+                        project_execpolicy_available,
                     },
                     header: Box::new(Paragraph::new(header).wrap(Wrap { trim: false })),
                 }
@@ -380,9 +536,9 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     ));
                     header.push(Box::new(Line::from("")));
                 }
-                header.push(DiffSummary::new(changes, cwd).into());
+                header.push(DiffSummary::new(changes.clone(), cwd.clone()).into());
                 Self {
-                    variant: ApprovalVariant::ApplyPatch { id },
+                    variant: ApprovalVariant::ApplyPatch { id, cwd, changes },
                     header: Box::new(ColumnRenderable::with(header)),
                 }
             }
@@ -415,9 +571,12 @@ enum ApprovalVariant {
         id: String,
         command: Vec<String>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+        project_execpolicy_available: bool,
     },
     ApplyPatch {
         id: String,
+        cwd: PathBuf,
+        changes: HashMap<PathBuf, FileChange>,
     },
     McpElicitation {
         server_name: String,
@@ -429,6 +588,18 @@ enum ApprovalVariant {
 enum ApprovalDecision {
     Review(ReviewDecision),
     McpElicitation(ElicitationAction),
+    /// Switch the patch approval to a hunk-by-hunk review.
+    ReviewHunks,
+    Hunk(HunkAction),
+}
+
+#[derive(Clone, Copy)]
+enum HunkAction {
+    Accept,
+    Reject,
+    Edit,
+    AcceptRemaining,
+    RejectRemaining,
 }
 
 #[derive(Clone)]
@@ -449,6 +620,7 @@ impl ApprovalOption {
 
 fn exec_options(
     proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+    project_execpolicy_available: bool,
     features: &Features,
 ) -> Vec<ApprovalOption> {
     let mut options = vec![
@@ -475,11 +647,27 @@ fn exec_options(
                 "Yes, and don't ask again for commands that start with `{rendered_prefix}`"
             ),
             decision: ApprovalDecision::Review(ReviewDecision::ApprovedExecpolicyAmendment {
-                proposed_execpolicy_amendment: prefix,
+                proposed_execpolicy_amendment: prefix.clone(),
+                target: ExecPolicyAmendmentTarget::User,
             }),
             display_shortcut: None,
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('p'))],
         });
+        if project_execpolicy_available {
+            options.push(ApprovalOption {
+                label: format!(
+                    "Yes, and don't ask again in this project for commands that start with `{rendered_prefix}`"
+                ),
+                decision: ApprovalDecision::Review(
+                    ReviewDecision::ApprovedExecpolicyAmendment {
+                        proposed_execpolicy_amendment: prefix,
+                        target: ExecPolicyAmendmentTarget::Project,
+                    },
+                ),
+                display_shortcut: None,
+                additional_shortcuts: vec![key_hint::plain(KeyCode::Char('o'))],
+            });
+        }
     }
 
     options.push(ApprovalOption {
@@ -500,6 +688,12 @@ fn patch_options() -> Vec<ApprovalOption> {
             display_shortcut: None,
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
         },
+        ApprovalOption {
+            label: "Review each hunk".to_string(),
+            decision: ApprovalDecision::ReviewHunks,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('r'))],
+        },
         ApprovalOption {
             label: "No, and tell Codex what to do differently".to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Abort),
@@ -509,6 +703,52 @@ fn patch_options() -> Vec<ApprovalOption> {
     ]
 }
 
+fn hunk_options(editable: bool) -> Vec<ApprovalOption> {
+    let mut options = vec![
+        ApprovalOption {
+            label: "Yes, apply this hunk".to_string(),
+            decision: ApprovalDecision::Hunk(HunkAction::Accept),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        },
+        ApprovalOption {
+            label: "No, skip this hunk".to_string(),
+            decision: ApprovalDecision::Hunk(HunkAction::Reject),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ];
+    if editable {
+        options.push(ApprovalOption {
+            label: "Edit this hunk in $EDITOR".to_string(),
+            decision: ApprovalDecision::Hunk(HunkAction::Edit),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('e'))],
+        });
+    }
+    options.extend([
+        ApprovalOption {
+            label: "Apply this and all remaining hunks".to_string(),
+            decision: ApprovalDecision::Hunk(HunkAction::AcceptRemaining),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
+        },
+        ApprovalOption {
+            label: "Skip this and all remaining hunks".to_string(),
+            decision: ApprovalDecision::Hunk(HunkAction::RejectRemaining),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('s'))],
+        },
+        ApprovalOption {
+            label: "No, and tell Codex what to do differently".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Abort),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: Vec::new(),
+        },
+    ]);
+    options
+}
+
 fn elicitation_options() -> Vec<ApprovalOption> {
     vec![
         ApprovalOption {
@@ -545,6 +785,7 @@ mod tests {
             command: vec!["echo".to_string(), "hi".to_string()],
             reason: Some("reason".to_string()),
             proposed_execpolicy_amendment: None,
+            project_execpolicy_available: false,
         }
     }
 
@@ -589,6 +830,7 @@ mod tests {
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
                 ])),
+                project_execpolicy_available: false,
             },
             tx,
             Features::with_defaults(),
@@ -602,7 +844,8 @@ mod tests {
                     ReviewDecision::ApprovedExecpolicyAmendment {
                         proposed_execpolicy_amendment: ExecPolicyAmendment::new(vec![
                             "echo".to_string()
-                        ])
+                        ]),
+                        target: ExecPolicyAmendmentTarget::User,
                     }
                 );
                 saw_op = true;
@@ -615,6 +858,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exec_project_prefix_option_targets_project_rules() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::Exec {
+                id: "test".to_string(),
+                command: vec!["echo".to_string()],
+                reason: None,
+                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
+                    "echo".to_string(),
+                ])),
+                project_execpolicy_available: true,
+            },
+            tx,
+            Features::with_defaults(),
+        );
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE));
+        let mut saw_op = false;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::ExecApproval { decision, .. }) = ev {
+                assert_eq!(
+                    decision,
+                    ReviewDecision::ApprovedExecpolicyAmendment {
+                        proposed_execpolicy_amendment: ExecPolicyAmendment::new(vec![
+                            "echo".to_string()
+                        ]),
+                        target: ExecPolicyAmendmentTarget::Project,
+                    }
+                );
+                saw_op = true;
+                break;
+            }
+        }
+        assert!(
+            saw_op,
+            "expected project approval decision to emit an op with command prefix"
+        );
+    }
+
     #[test]
     fn exec_prefix_option_hidden_when_execpolicy_disabled() {
         let (tx, mut rx) = unbounded_channel::<AppEvent>();
@@ -627,6 +910,7 @@ mod tests {
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
                 ])),
+                project_execpolicy_available: false,
             },
             tx,
             {
@@ -651,6 +935,7 @@ mod tests {
             command,
             reason: None,
             proposed_execpolicy_amendment: None,
+            project_execpolicy_available: false,
         };
 
         let view = ApprovalOverlay::new(exec_request, tx, Features::with_defaults());
@@ -699,6 +984,62 @@ mod tests {
         assert_eq!(rendered, expected);
     }
 
+    #[test]
+    fn hunk_review_sends_per_hunk_decisions() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let original = (1..=12).map(|n| format!("line {n}\n")).collect::<String>();
+        let modified = original
+            .replace("line 2\n", "line two\n")
+            .replace("line 11\n", "line eleven\n");
+        let path = PathBuf::from("/tmp/a.txt");
+        let request = ApprovalRequest::ApplyPatch {
+            id: "patch".to_string(),
+            reason: None,
+            cwd: PathBuf::from("/tmp"),
+            changes: HashMap::from([(
+                path.clone(),
+                FileChange::Update {
+                    unified_diff: diffy::create_patch(&original, &modified).to_string(),
+                    move_path: None,
+                },
+            )]),
+        };
+        let mut view = ApprovalOverlay::new(request, tx, Features::with_defaults());
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        assert!(!view.is_complete());
+        assert!(rx.try_recv().is_err());
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert!(view.is_complete());
+
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::PatchApproval { decision: d, .. }) = ev {
+                decision = Some(d);
+                break;
+            }
+        }
+        assert_eq!(
+            decision,
+            Some(ReviewDecision::ApprovedWithHunkReview {
+                hunks: vec![
+                    HunkReview {
+                        path: path.clone(),
+                        hunk: 0,
+                        decision: HunkDecision::Reject,
+                    },
+                    HunkReview {
+                        path,
+                        hunk: 1,
+                        decision: HunkDecision::Accept,
+                    },
+                ],
+            })
+        );
+    }
+
     #[test]
     fn enter_sets_last_selected_index_without_dismissing() {
         let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
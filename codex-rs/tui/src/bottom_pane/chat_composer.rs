@@ -53,6 +53,8 @@ use crate::bottom_pane::textarea::TextAreaState;
 use crate::clipboard_paste::normalize_pasted_path;
 use crate::clipboard_paste::pasted_image_format;
 use crate::history_cell;
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use codex_file_search::FileMatch;
 use std::cell::RefCell;
@@ -928,11 +930,9 @@ impl ChatComposer {
         }
         match key_event {
             KeyEvent {
-                code: KeyCode::Char('d'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
                 kind: KeyEventKind::Press,
                 ..
-            } if self.is_empty() => {
+            } if self.is_empty() && keymap().is_pressed(KeyAction::ComposerExit, &key_event) => {
                 self.app_event_tx.send(AppEvent::ExitRequest);
                 (InputResult::None, true)
             }
@@ -941,26 +941,19 @@ impl ChatComposer {
             // empty or when the cursor is at the correct position, to avoid
             // interfering with normal cursor movement.
             // -------------------------------------------------------------
-            KeyEvent {
-                code: KeyCode::Up | KeyCode::Down,
-                ..
-            }
-            | KeyEvent {
-                code: KeyCode::Char('p') | KeyCode::Char('n'),
-                modifiers: KeyModifiers::CONTROL,
-                ..
-            } => {
+            _ if keymap().is_pressed(KeyAction::ComposerHistoryPrevious, &key_event)
+                || keymap().is_pressed(KeyAction::ComposerHistoryNext, &key_event) =>
+            {
                 if self
                     .history
                     .should_handle_navigation(self.textarea.text(), self.textarea.cursor())
                 {
-                    let replace_text = match key_event.code {
-                        KeyCode::Up => self.history.navigate_up(&self.app_event_tx),
-                        KeyCode::Down => self.history.navigate_down(&self.app_event_tx),
-                        KeyCode::Char('p') => self.history.navigate_up(&self.app_event_tx),
-                        KeyCode::Char('n') => self.history.navigate_down(&self.app_event_tx),
-                        _ => unreachable!(),
-                    };
+                    let replace_text =
+                        if keymap().is_pressed(KeyAction::ComposerHistoryPrevious, &key_event) {
+                            self.history.navigate_up(&self.app_event_tx)
+                        } else {
+                            self.history.navigate_down(&self.app_event_tx)
+                        };
                     if let Some(text) = replace_text {
                         self.set_text_content(text);
                         return (InputResult::None, true);
@@ -968,11 +961,7 @@ impl ChatComposer {
                 }
                 self.handle_input_basic(key_event)
             }
-            KeyEvent {
-                code: KeyCode::Enter,
-                modifiers: KeyModifiers::NONE,
-                ..
-            } => {
+            _ if keymap().is_pressed(KeyAction::ComposerSubmit, &key_event) => {
                 // If the first line is a bare built-in slash command (no args),
                 // dispatch it even when the slash popup isn't visible. This preserves
                 // the workflow: type a prefix ("/di"), press Tab to complete to
@@ -1423,14 +1412,8 @@ impl ChatComposer {
             return false;
         }
 
-        let toggles = matches!(
-            key_event,
-            KeyEvent {
-                code: KeyCode::Char('?'),
-                modifiers: KeyModifiers::NONE,
-                ..
-            } if self.is_empty()
-        );
+        let toggles =
+            self.is_empty() && keymap().is_pressed(KeyAction::ComposerShortcuts, key_event);
 
         if !toggles {
             return false;
@@ -13,6 +13,7 @@ use ratatui::widgets::Widget;
 use std::cell::RefCell;
 
 use crate::render::renderable::Renderable;
+use crate::theme::theme;
 
 use super::popup_consts::standard_popup_hint_line;
 
@@ -137,7 +138,8 @@ impl Renderable for CustomPromptView {
                 width: area.width,
                 height: 1,
             };
-            let spans: Vec<Span<'static>> = vec![gutter(), context_label.clone().cyan()];
+            let spans: Vec<Span<'static>> =
+                vec![gutter(), context_label.clone().fg(theme().accent)];
             Paragraph::new(Line::from(spans)).render(context_area, buf);
             input_y = input_y.saturating_add(1);
         }
@@ -243,5 +245,5 @@ impl CustomPromptView {
 }
 
 fn gutter() -> Span<'static> {
-    "▌ ".cyan()
+    "▌ ".fg(theme().accent)
 }
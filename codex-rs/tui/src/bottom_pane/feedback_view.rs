@@ -19,6 +19,7 @@ use crate::app_event::FeedbackCategory;
 use crate::app_event_sender::AppEventSender;
 use crate::history_cell;
 use crate::render::renderable::Renderable;
+use crate::theme::theme;
 use codex_core::protocol::SessionSource;
 
 use super::CancellationEvent;
@@ -104,7 +105,7 @@ impl FeedbackNoteView {
                 if let Some(url) = issue_url {
                     lines.extend([
                         "".into(),
-                        Line::from(vec!["  ".into(), url.cyan().underlined()]),
+                        Line::from(vec!["  ".into(), url.fg(theme().accent).underlined()]),
                         "".into(),
                         Line::from(vec![
                             "  Or mention your thread ID ".into(),
@@ -302,7 +303,7 @@ impl FeedbackNoteView {
 }
 
 fn gutter() -> Span<'static> {
-    "▌ ".cyan()
+    "▌ ".fg(theme().accent)
 }
 
 fn feedback_title_and_placeholder(category: FeedbackCategory) -> (String, String) {
@@ -2,6 +2,7 @@ use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::render::line_utils::prefix_lines;
 use crate::status::format_tokens_compact;
+use crate::theme::theme;
 use crate::ui_consts::FOOTER_INDENT_COLS;
 use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
@@ -86,7 +87,7 @@ fn footer_lines(props: &FooterProps) -> Vec<Line<'static>> {
         FooterMode::ShortcutSummary => {
             let mut spans: Vec<Span<'static>> = Vec::new();
             if let Some(label) = props.delegate_label.as_ref() {
-                spans.push(format!("In {label}").cyan());
+                spans.push(format!("In {label}").fg(theme().accent));
                 spans.push(" · ".dim());
             }
             let context_line = context_window_line(
@@ -111,7 +112,7 @@ fn footer_lines(props: &FooterProps) -> Vec<Line<'static>> {
         FooterMode::ContextOnly => {
             let mut spans: Vec<Span<'static>> = Vec::new();
             if let Some(label) = props.delegate_label.as_ref() {
-                spans.push(format!("In {label}").cyan());
+                spans.push(format!("In {label}").fg(theme().accent));
                 spans.push(" · ".dim());
             }
             let context_line = context_window_line(
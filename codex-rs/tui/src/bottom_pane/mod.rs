@@ -34,6 +34,7 @@ mod session_alias_input;
 pub(crate) use feedback_view::feedback_selection_params;
 pub(crate) use feedback_view::feedback_upload_consent_params;
 mod paste_burst;
+mod patch_hunks;
 pub mod popup_consts;
 mod queued_user_messages;
 mod scroll_state;
@@ -596,6 +597,7 @@ mod tests {
             command: vec!["echo".into(), "ok".into()],
             reason: None,
             proposed_execpolicy_amendment: None,
+            project_execpolicy_available: false,
         }
     }
 
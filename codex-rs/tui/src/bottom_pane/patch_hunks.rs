@@ -0,0 +1,165 @@
+//! Splits a proposed patch into the hunks the approval overlay walks through.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use codex_core::protocol::FileChange;
+
+/// One reviewable piece of a proposed patch.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PatchHunk {
+    /// Key of the file in the approval request's `changes`.
+    pub(crate) path: PathBuf,
+    /// Position of the hunk within the file's unified diff; `0` for changes
+    /// without a textual diff.
+    pub(crate) index: usize,
+    /// The file change narrowed down to this hunk, for rendering.
+    pub(crate) change: FileChange,
+    /// Text offered in `$EDITOR`: the lines the hunk produces, context lines
+    /// included. `None` when the hunk cannot be edited.
+    pub(crate) editable: Option<String>,
+}
+
+/// Returns the hunks of `changes`, ordered by path and then by position.
+pub(crate) fn split_into_hunks(changes: &HashMap<PathBuf, FileChange>) -> Vec<PatchHunk> {
+    let mut paths: Vec<&PathBuf> = changes.keys().collect();
+    paths.sort();
+
+    let mut hunks = Vec::new();
+    for path in paths {
+        let change = &changes[path];
+        match change {
+            FileChange::Update {
+                unified_diff,
+                move_path,
+            } => {
+                let Ok(patch) = diffy::Patch::from_str(unified_diff) else {
+                    hunks.push(PatchHunk {
+                        path: path.clone(),
+                        index: 0,
+                        change: change.clone(),
+                        editable: None,
+                    });
+                    continue;
+                };
+                for (index, hunk) in patch.hunks().iter().enumerate() {
+                    let (diff, editable) = render_hunk(hunk);
+                    hunks.push(PatchHunk {
+                        path: path.clone(),
+                        index,
+                        change: FileChange::Update {
+                            unified_diff: diff,
+                            move_path: move_path.clone(),
+                        },
+                        editable: Some(editable),
+                    });
+                }
+            }
+            FileChange::Add { content } => hunks.push(PatchHunk {
+                path: path.clone(),
+                index: 0,
+                change: change.clone(),
+                editable: Some(content.clone()),
+            }),
+            FileChange::Delete { .. }
+            | FileChange::Binary { .. }
+            | FileChange::Symlink { .. }
+            | FileChange::Mode { .. } => hunks.push(PatchHunk {
+                path: path.clone(),
+                index: 0,
+                change: change.clone(),
+                editable: None,
+            }),
+        }
+    }
+    hunks
+}
+
+/// Returns the hunk as a standalone unified diff together with the lines it
+/// produces.
+fn render_hunk(hunk: &diffy::Hunk<'_, str>) -> (String, String) {
+    let old = hunk.old_range();
+    let new = hunk.new_range();
+    let mut diff = format!(
+        "@@ -{},{} +{},{} @@\n",
+        old.start(),
+        old.len(),
+        new.start(),
+        new.len()
+    );
+    let mut produced = String::new();
+    for line in hunk.lines() {
+        let (sign, text) = match line {
+            diffy::Line::Context(text) => (' ', *text),
+            diffy::Line::Delete(text) => ('-', *text),
+            diffy::Line::Insert(text) => ('+', *text),
+        };
+        diff.push(sign);
+        diff.push_str(text);
+        if !text.ends_with('\n') {
+            diff.push('\n');
+        }
+        if sign != '-' {
+            produced.push_str(text);
+        }
+    }
+    (diff, produced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn updates_split_per_hunk_and_other_changes_stay_whole() {
+        let original = (1..=12).map(|n| format!("line {n}\n")).collect::<String>();
+        let modified = original
+            .replace("line 2\n", "line two\n")
+            .replace("line 11\n", "line eleven\n");
+        let unified_diff = diffy::create_patch(&original, &modified).to_string();
+        let changes = HashMap::from([
+            (
+                PathBuf::from("a.txt"),
+                FileChange::Update {
+                    unified_diff,
+                    move_path: None,
+                },
+            ),
+            (
+                PathBuf::from("b.txt"),
+                FileChange::Delete {
+                    content: "gone\n".to_string(),
+                },
+            ),
+        ]);
+
+        let hunks = split_into_hunks(&changes);
+        let summary: Vec<(String, usize, Option<String>)> = hunks
+            .iter()
+            .map(|hunk| {
+                (
+                    hunk.path.display().to_string(),
+                    hunk.index,
+                    hunk.editable.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "a.txt".to_string(),
+                    0,
+                    Some("line 1\nline two\nline 3\nline 4\nline 5\n".to_string())
+                ),
+                (
+                    "a.txt".to_string(),
+                    1,
+                    Some("line 8\nline 9\nline 10\nline eleven\nline 12\n".to_string())
+                ),
+                ("b.txt".to_string(), 0, None),
+            ]
+        );
+    }
+}
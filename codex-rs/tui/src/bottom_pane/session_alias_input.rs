@@ -17,6 +17,7 @@ use ratatui::widgets::Widget;
 use super::CancellationEvent;
 use super::bottom_pane_view::BottomPaneView;
 use crate::render::renderable::Renderable;
+use crate::theme::theme;
 
 /// 回调函数类型，当用户提交别名时调用
 pub(crate) type AliasSubmitted = Box<dyn Fn(String, String) + Send + Sync>;
@@ -211,8 +212,8 @@ impl Renderable for SessionAliasInput {
         let mut lines = vec![
             Line::from(""),
             Line::from(vec![
-                Span::from("✨ ").magenta(),
-                Span::from(title_text).cyan().bold(),
+                Span::from("✨ ").fg(theme().highlight),
+                Span::from(title_text).fg(theme().accent).bold(),
             ]),
             Line::from(""),
         ];
@@ -249,9 +250,9 @@ impl Renderable for SessionAliasInput {
         // 提示行
         lines.push(Line::from(vec![
             Span::from("  按 ").dim(),
-            Span::from("Enter").green().bold(),
+            Span::from("Enter").fg(theme().success).bold(),
             Span::from(" 确认 · ").dim(),
-            Span::from("Esc").cyan(),
+            Span::from("Esc").fg(theme().accent),
             Span::from(" 跳过").dim(),
         ]));
 
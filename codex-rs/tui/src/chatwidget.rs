@@ -11,6 +11,7 @@ use codex_app_server_protocol::AuthMode;
 use codex_backend_client::Client as BackendClient;
 use codex_core::config::Config;
 use codex_core::config::types::Notifications;
+use codex_core::features::Feature;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::local_git_branches;
 use codex_core::openai_models::model_family::ModelFamily;
@@ -24,6 +25,7 @@ use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::CheckpointRestoredEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -34,6 +36,7 @@ use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::ExitedReviewModeEvent;
+use codex_core::protocol::ListCheckpointsResponseEvent;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
 use codex_core::protocol::ListSkillsResponseEvent;
 use codex_core::protocol::McpListToolsResponseEvent;
@@ -83,7 +86,6 @@ use crossterm::event::KeyModifiers;
 use rand::Rng;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::Paragraph;
@@ -115,6 +117,7 @@ use crate::history_cell::AgentMessageCell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::McpToolCallCell;
 use crate::history_cell::PlainHistoryCell;
+use crate::keymap::keymap;
 use crate::markdown::append_markdown;
 use crate::render::Insets;
 use crate::render::renderable::ColumnRenderable;
@@ -134,6 +137,7 @@ use self::agent::spawn_agent_from_existing;
 mod session_header;
 use self::session_header::SessionHeader;
 use crate::streaming::controller::StreamController;
+use crate::theme::theme;
 use std::fmt::Write;
 use std::path::Path;
 use std::time::SystemTime;
@@ -355,6 +359,8 @@ pub(crate) struct ChatWidget {
     queued_turn_pending_start: bool,
     // Last non-empty user message text (used by commands that default to "repeat last prompt").
     last_user_message: Option<String>,
+    // Messages pinned via `/pin` in this session, oldest first.
+    pinned_messages: Vec<String>,
     // Active Ralph loop state (if enabled via `/ralph-loop`).
     ralph_loop_state: Option<RalphLoopState>,
     // Track if current turn had errors (for ralph loop delay logic)
@@ -816,6 +822,50 @@ impl ChatWidget {
         }
     }
 
+    /// Handle `/pin [text]`: keep `text`, or the last message sent, verbatim
+    /// across compaction.
+    pub(crate) fn handle_pin_command(&mut self, args: Option<String>) {
+        let message = args
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty())
+            .or_else(|| self.last_user_message.clone());
+        let Some(message) = message else {
+            self.add_info_message(
+                "Usage: /pin <text>, or send a message first to pin it.".to_string(),
+                None,
+            );
+            return;
+        };
+
+        if !self.pinned_messages.contains(&message) {
+            self.pinned_messages.push(message.clone());
+        }
+        let preview = truncate_text(&message, 60);
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::PinMessage { message }));
+        let hint = (!self.config.features.enabled(Feature::StructuredCompaction)).then(|| {
+            "Pins are kept when the structured_compaction feature is enabled.".to_string()
+        });
+        self.add_info_message(format!("Pinned: {preview}"), hint);
+    }
+
+    /// Handle `/unpin [text]`: drop the pin for `text`, or the most recent pin.
+    pub(crate) fn handle_unpin_command(&mut self, args: Option<String>) {
+        let requested = args
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+        let Some(message) = requested.or_else(|| self.pinned_messages.last().cloned()) else {
+            self.add_info_message("No messages are pinned.".to_string(), None);
+            return;
+        };
+
+        self.pinned_messages.retain(|pinned| pinned != &message);
+        let preview = truncate_text(&message, 60);
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::UnpinMessage { message }));
+        self.add_info_message(format!("Unpinned: {preview}"), None);
+    }
+
     /// Handle `/ref-image-batch <path> -- <prompt>` command for batch image processing.
     pub(crate) fn handle_ref_image_batch_command(&mut self, args: Option<String>) {
         let raw = args.unwrap_or_default();
@@ -2292,6 +2342,31 @@ if __name__ == "__main__":
         }
     }
 
+    fn on_list_checkpoints(&mut self, event: ListCheckpointsResponseEvent) {
+        if event.checkpoints.is_empty() {
+            self.add_info_message(
+                "No checkpoints yet. One is taken at the start of every turn.".to_string(),
+                None,
+            );
+            return;
+        }
+        self.app_event_tx
+            .send(AppEvent::OpenCheckpoints(event.checkpoints));
+    }
+
+    fn on_checkpoint_restored(&mut self, event: CheckpointRestoredEvent) {
+        let CheckpointRestoredEvent {
+            success, message, ..
+        } = event;
+        if success {
+            let message = message.unwrap_or_else(|| "Checkpoint restored.".to_string());
+            self.add_info_message(message, None);
+        } else {
+            let message = message.unwrap_or_else(|| "Checkpoint restore failed.".to_string());
+            self.add_error_message(message);
+        }
+    }
+
     fn on_stream_error(&mut self, message: String) {
         if self.retry_status_header.is_none() {
             self.retry_status_header = Some(self.current_status_header.clone());
@@ -2465,6 +2540,7 @@ if __name__ == "__main__":
             command: ev.command,
             reason: ev.reason,
             proposed_execpolicy_amendment: ev.proposed_execpolicy_amendment,
+            project_execpolicy_available: ev.project_execpolicy_available,
         };
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
@@ -2692,6 +2768,7 @@ if __name__ == "__main__":
             queued_user_messages: VecDeque::new(),
             queued_turn_pending_start: false,
             last_user_message: None,
+            pinned_messages: Vec::new(),
             ralph_loop_state: None,
             ralph_loop_turn_had_error: false,
             show_welcome_banner: is_first_run,
@@ -2801,6 +2878,7 @@ if __name__ == "__main__":
             queued_user_messages: VecDeque::new(),
             queued_turn_pending_start: false,
             last_user_message: None,
+            pinned_messages: Vec::new(),
             ralph_loop_state: None,
             ralph_loop_turn_had_error: false,
             show_welcome_banner: false,
@@ -2977,6 +3055,12 @@ if __name__ == "__main__":
                 self.clear_token_usage();
                 self.app_event_tx.send(AppEvent::CodexOp(Op::Compact));
             }
+            SlashCommand::Pin => {
+                self.handle_pin_command(args);
+            }
+            SlashCommand::Unpin => {
+                self.handle_unpin_command(args);
+            }
             SlashCommand::Review => {
                 self.open_review_popup();
             }
@@ -2996,10 +3080,7 @@ if __name__ == "__main__":
                 self.request_exit();
             }
             SlashCommand::Logout => {
-                if let Err(e) = codex_core::auth::logout(
-                    &self.config.codex_home,
-                    self.config.cli_auth_credentials_store_mode,
-                ) {
+                if let Err(e) = self.auth_manager.logout() {
                     tracing::error!("failed to logout: {e}");
                 }
                 self.request_exit();
@@ -3007,6 +3088,10 @@ if __name__ == "__main__":
             SlashCommand::Undo => {
                 self.app_event_tx.send(AppEvent::CodexOp(Op::Undo));
             }
+            SlashCommand::Checkpoints => {
+                self.app_event_tx
+                    .send(AppEvent::CodexOp(Op::ListCheckpoints));
+            }
             SlashCommand::Diff => {
                 self.add_diff_in_progress();
                 let tx = self.app_event_tx.clone();
@@ -3057,6 +3142,9 @@ if __name__ == "__main__":
             SlashCommand::Mcp => {
                 self.add_mcp_output();
             }
+            SlashCommand::Keys => {
+                self.add_plain_history_lines(keymap().help_lines());
+            }
             SlashCommand::Rollout => {
                 if let Some(path) = self.rollout_path() {
                     self.add_info_message(
@@ -3365,6 +3453,8 @@ if __name__ == "__main__":
             }
             EventMsg::UndoStarted(ev) => self.on_undo_started(ev),
             EventMsg::UndoCompleted(ev) => self.on_undo_completed(ev),
+            EventMsg::ListCheckpointsResponse(ev) => self.on_list_checkpoints(ev),
+            EventMsg::CheckpointRestored(ev) => self.on_checkpoint_restored(ev),
             EventMsg::StreamError(StreamErrorEvent { message, .. }) => {
                 self.on_stream_error(message)
             }
@@ -3381,11 +3471,13 @@ if __name__ == "__main__":
             }
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
             EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
+            EventMsg::PinnedMessagesUpdated(ev) => self.pinned_messages = ev.messages,
             EventMsg::RalphLoopContinue(ev) => self.on_ralph_loop_continue(ev),
             EventMsg::RalphLoopStatus(ev) => self.on_ralph_loop_status(ev),
             EventMsg::RalphLoopComplete(ev) => self.on_ralph_loop_complete(ev),
             EventMsg::ItemStarted(_)
             | EventMsg::ItemCompleted(_)
+            | EventMsg::ApprovalDecision(_)
             | EventMsg::AgentMessageContentDelta(_)
             | EventMsg::ReasoningContentDelta(_)
             | EventMsg::ReasoningRawContentDelta(_) => {}
@@ -3808,6 +3900,7 @@ if __name__ == "__main__":
                 approval_policy: None,
                 sandbox_policy: None,
                 model: Some(switch_model.clone()),
+                model_provider: None,
                 effort: Some(Some(default_effort)),
                 summary: None,
             }));
@@ -3891,11 +3984,20 @@ if __name__ == "__main__":
         let (mut auto_presets, other_presets): (Vec<ModelPreset>, Vec<ModelPreset>) = presets
             .into_iter()
             .partition(|preset| Self::is_auto_model(&preset.model));
+        let provider_groups = self
+            .models_manager
+            .try_list_provider_models(&self.config)
+            .unwrap_or_default();
 
-        if auto_presets.is_empty() {
-            self.open_all_models_popup(other_presets);
+        if auto_presets.is_empty() && provider_groups.is_empty() {
+            self.open_all_models_popup(other_presets, None);
             return;
         }
+        let subtitle = if auto_presets.is_empty() {
+            "Browse all models or the models of another provider."
+        } else {
+            "Pick a quick auto mode or browse all models."
+        };
 
         auto_presets.sort_by_key(|preset| Self::auto_model_order(&preset.model));
 
@@ -3925,6 +4027,7 @@ if __name__ == "__main__":
             let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
                 tx.send(AppEvent::OpenAllModelsPopup {
                     models: all_models.clone(),
+                    model_provider: None,
                 });
             })];
 
@@ -3943,9 +4046,38 @@ if __name__ == "__main__":
             });
         }
 
+        for group in provider_groups {
+            let is_current = group.provider_id == self.config.model_provider_id
+                && group
+                    .models
+                    .iter()
+                    .any(|preset| preset.model == current_model);
+            let description = Some(format!(
+                "{} models listed by the `{}` provider",
+                group.models.len(),
+                group.provider_id
+            ));
+            let models = group.models;
+            let model_provider = group.provider_id;
+            let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                tx.send(AppEvent::OpenAllModelsPopup {
+                    models: models.clone(),
+                    model_provider: Some(model_provider.clone()),
+                });
+            })];
+            items.push(SelectionItem {
+                name: format!("{} models", group.provider_name),
+                description,
+                is_current,
+                actions,
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+
         self.bottom_pane.show_selection_view(SelectionViewParams {
             title: Some("Select Model".to_string()),
-            subtitle: Some("Pick a quick auto mode or browse all models.".to_string()),
+            subtitle: Some(subtitle.to_string()),
             footer_hint: Some(standard_popup_hint_line()),
             items,
             ..Default::default()
@@ -3965,7 +4097,13 @@ if __name__ == "__main__":
         }
     }
 
-    pub(crate) fn open_all_models_popup(&mut self, presets: Vec<ModelPreset>) {
+    /// Open the model list; `model_provider` is the provider serving
+    /// `presets` when they were discovered from another provider.
+    pub(crate) fn open_all_models_popup(
+        &mut self,
+        presets: Vec<ModelPreset>,
+        model_provider: Option<String>,
+    ) {
         if presets.is_empty() {
             self.add_info_message(
                 "No additional models are available right now.".to_string(),
@@ -3982,10 +4120,12 @@ if __name__ == "__main__":
             let is_current = preset.model == current_model;
             let single_supported_effort = preset.supported_reasoning_efforts.len() == 1;
             let preset_for_action = preset.clone();
+            let model_provider_for_action = model_provider.clone();
             let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
                 let preset_for_event = preset_for_action.clone();
                 tx.send(AppEvent::OpenReasoningPopup {
                     model: preset_for_event,
+                    model_provider: model_provider_for_action.clone(),
                 });
             })];
             items.push(SelectionItem {
@@ -4023,6 +4163,7 @@ if __name__ == "__main__":
                 approval_policy: None,
                 sandbox_policy: None,
                 model: Some(model_for_action.clone()),
+                model_provider: None,
                 effort: Some(effort_for_action),
                 summary: None,
             }));
@@ -4031,6 +4172,7 @@ if __name__ == "__main__":
             tx.send(AppEvent::PersistModelSelection {
                 model: model_for_action.clone(),
                 effort: effort_for_action,
+                model_provider: None,
             });
             tracing::info!(
                 "Selected model: {}, Selected effort: {}",
@@ -4041,7 +4183,11 @@ if __name__ == "__main__":
     }
 
     /// Open a popup to choose the reasoning effort (stage 2) for the given model.
-    pub(crate) fn open_reasoning_popup(&mut self, preset: ModelPreset) {
+    pub(crate) fn open_reasoning_popup(
+        &mut self,
+        preset: ModelPreset,
+        model_provider: Option<String>,
+    ) {
         let default_effort: ReasoningEffortConfig = preset.default_reasoning_effort;
         let supported = preset.supported_reasoning_efforts;
 
@@ -4087,9 +4233,9 @@ if __name__ == "__main__":
 
         if choices.len() == 1 {
             if let Some(effort) = choices.first().and_then(|c| c.stored) {
-                self.apply_model_and_effort(preset.model, Some(effort));
+                self.apply_model_and_effort(preset.model, Some(effort), model_provider);
             } else {
-                self.apply_model_and_effort(preset.model, None);
+                self.apply_model_and_effort(preset.model, None, model_provider);
             }
             return;
         }
@@ -4148,6 +4294,7 @@ if __name__ == "__main__":
             };
 
             let model_for_action = model_slug.clone();
+            let model_provider_for_action = model_provider.clone();
             let effort_for_action = choice.stored;
             let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
                 tx.send(AppEvent::CodexOp(Op::OverrideTurnContext {
@@ -4155,6 +4302,7 @@ if __name__ == "__main__":
                     approval_policy: None,
                     sandbox_policy: None,
                     model: Some(model_for_action.clone()),
+                    model_provider: model_provider_for_action.clone(),
                     effort: Some(effort_for_action),
                     summary: None,
                 }));
@@ -4163,6 +4311,7 @@ if __name__ == "__main__":
                 tx.send(AppEvent::PersistModelSelection {
                     model: model_for_action.clone(),
                     effort: effort_for_action,
+                    model_provider: model_provider_for_action.clone(),
                 });
                 tracing::info!(
                     "Selected model: {}, Selected effort: {}",
@@ -4355,13 +4504,19 @@ if __name__ == "__main__":
         }
     }
 
-    fn apply_model_and_effort(&self, model: String, effort: Option<ReasoningEffortConfig>) {
+    fn apply_model_and_effort(
+        &self,
+        model: String,
+        effort: Option<ReasoningEffortConfig>,
+        model_provider: Option<String>,
+    ) {
         self.app_event_tx
             .send(AppEvent::CodexOp(Op::OverrideTurnContext {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
                 model: Some(model.clone()),
+                model_provider: model_provider.clone(),
                 effort: Some(effort),
                 summary: None,
             }));
@@ -4371,6 +4526,7 @@ if __name__ == "__main__":
         self.app_event_tx.send(AppEvent::PersistModelSelection {
             model: model.clone(),
             effort,
+            model_provider,
         });
         tracing::info!(
             "Selected model: {}, Selected effort: {}",
@@ -4469,6 +4625,7 @@ if __name__ == "__main__":
                 approval_policy: Some(approval),
                 sandbox_policy: Some(sandbox_clone.clone()),
                 model: None,
+                model_provider: None,
                 effort: None,
                 summary: None,
             }));
@@ -4538,7 +4695,7 @@ if __name__ == "__main__":
             "When Codex runs with full access, it can edit any file on your computer and run commands with network, without your approval. "
                 .into(),
             "Exercise caution when enabling full access. This significantly increases the risk of data loss, leaks, or unexpected behavior."
-                .fg(Color::Red),
+                .fg(theme().error),
         ]);
         header_children.push(Box::new(title_line));
         header_children.push(Box::new(
@@ -4620,7 +4777,7 @@ if __name__ == "__main__":
                 "We couldn't complete the world-writable scan, so protections cannot be verified. "
                     .into(),
                 format!("The Windows sandbox cannot guarantee protection in {mode_label}.")
-                    .fg(Color::Red),
+                    .fg(theme().error),
             ])
         } else {
             Line::from(vec![
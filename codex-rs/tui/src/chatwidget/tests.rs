@@ -405,6 +405,7 @@ async fn make_chatwidget_manual(
         queued_user_messages: VecDeque::new(),
         queued_turn_pending_start: false,
         last_user_message: None,
+        pinned_messages: Vec::new(),
         ralph_loop_state: None,
         suppress_session_configured_redraw: false,
         pending_notification: None,
@@ -742,6 +743,7 @@ async fn exec_approval_emits_proposed_command_and_decision_history() {
             "this is a test reason such as one that would be produced by the model".into(),
         ),
         proposed_execpolicy_amendment: None,
+        project_execpolicy_available: false,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -786,6 +788,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
             "this is a test reason such as one that would be produced by the model".into(),
         ),
         proposed_execpolicy_amendment: None,
+        project_execpolicy_available: false,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -836,6 +839,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
         reason: None,
         proposed_execpolicy_amendment: None,
+        project_execpolicy_available: false,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -1439,6 +1443,53 @@ async fn slash_undo_sends_op() {
     }
 }
 
+#[tokio::test]
+async fn slash_checkpoints_sends_op() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command(SlashCommand::Checkpoints, None);
+
+    match rx.try_recv() {
+        Ok(AppEvent::CodexOp(Op::ListCheckpoints)) => {}
+        other => panic!("expected AppEvent::CodexOp(Op::ListCheckpoints), got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn slash_pin_and_unpin_send_ops() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.last_user_message = Some("Keep the public API stable.".to_string());
+
+    chat.dispatch_command(SlashCommand::Pin, None);
+
+    match rx.try_recv() {
+        Ok(AppEvent::CodexOp(Op::PinMessage { message })) => {
+            assert_eq!(message, "Keep the public API stable.");
+        }
+        other => panic!("expected AppEvent::CodexOp(Op::PinMessage), got {other:?}"),
+    }
+    let _ = drain_insert_history(&mut rx);
+
+    chat.dispatch_command(SlashCommand::Unpin, None);
+
+    match rx.try_recv() {
+        Ok(AppEvent::CodexOp(Op::UnpinMessage { message })) => {
+            assert_eq!(message, "Keep the public API stable.");
+        }
+        other => panic!("expected AppEvent::CodexOp(Op::UnpinMessage), got {other:?}"),
+    }
+    let _ = drain_insert_history(&mut rx);
+
+    chat.dispatch_command(SlashCommand::Unpin, None);
+
+    let cells = drain_insert_history(&mut rx);
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("No messages are pinned."),
+        "expected empty pin notice: {rendered:?}"
+    );
+}
+
 #[tokio::test]
 async fn slash_rollout_displays_current_path() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -1983,7 +2034,7 @@ async fn model_reasoning_selection_popup_snapshot() {
     chat.config.model_reasoning_effort = Some(ReasoningEffortConfig::High);
 
     let preset = get_available_model(&chat, "gpt-5.1-codex-max");
-    chat.open_reasoning_popup(preset);
+    chat.open_reasoning_popup(preset, None);
 
     let popup = render_bottom_popup(&chat, 80);
     assert_snapshot!("model_reasoning_selection_popup", popup);
@@ -1997,7 +2048,7 @@ async fn model_reasoning_selection_popup_extra_high_warning_snapshot() {
     chat.config.model_reasoning_effort = Some(ReasoningEffortConfig::XHigh);
 
     let preset = get_available_model(&chat, "gpt-5.1-codex-max");
-    chat.open_reasoning_popup(preset);
+    chat.open_reasoning_popup(preset, None);
 
     let popup = render_bottom_popup(&chat, 80);
     assert_snapshot!("model_reasoning_selection_popup_extra_high_warning", popup);
@@ -2010,7 +2061,7 @@ async fn reasoning_popup_shows_extra_high_with_space() {
     set_chatgpt_auth(&mut chat);
 
     let preset = get_available_model(&chat, "gpt-5.1-codex-max");
-    chat.open_reasoning_popup(preset);
+    chat.open_reasoning_popup(preset, None);
 
     let popup = render_bottom_popup(&chat, 120);
     assert!(
@@ -2043,7 +2094,7 @@ async fn single_reasoning_option_skips_selection() {
         show_in_picker: true,
         supported_in_api: true,
     };
-    chat.open_reasoning_popup(preset);
+    chat.open_reasoning_popup(preset, None);
 
     let popup = render_bottom_popup(&chat, 80);
     assert!(
@@ -2092,7 +2143,7 @@ async fn reasoning_popup_escape_returns_to_model_popup() {
     chat.open_model_popup();
 
     let preset = get_available_model(&chat, "gpt-5.1-codex-max");
-    chat.open_reasoning_popup(preset);
+    chat.open_reasoning_popup(preset, None);
 
     let before_escape = render_bottom_popup(&chat, 80);
     assert!(before_escape.contains("Select Reasoning Level"));
@@ -2201,6 +2252,7 @@ async fn approval_modal_exec_snapshot() {
             "hello".into(),
             "world".into(),
         ])),
+        project_execpolicy_available: false,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2252,6 +2304,7 @@ async fn approval_modal_exec_without_reason_snapshot() {
             "hello".into(),
             "world".into(),
         ])),
+        project_execpolicy_available: false,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2469,6 +2522,7 @@ async fn status_widget_and_approval_modal_snapshot() {
             "echo".into(),
             "hello world".into(),
         ])),
+        project_execpolicy_available: false,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -1,5 +1,6 @@
 use crate::pager_overlay::Overlay;
 use crate::render::line_utils;
+use crate::theme::theme;
 use codex_ansi_escape::ansi_escape_line;
 use ratatui::layout::Constraint;
 use ratatui::layout::Direction;
@@ -32,13 +33,7 @@ use arboard::Clipboard;
 pub const NEW_SESSION_SENTINEL: &str = "__cxresume_new_session__";
 const FULL_PREVIEW_WRAP_WIDTH: usize = 76;
 const THEME_GRAY: Color = Color::Gray;
-const THEME_GREEN: Color = Color::Green;
-const THEME_YELLOW: Color = Color::Cyan;
-const THEME_ORANGE: Color = Color::Magenta;
-const THEME_PURPLE: Color = Color::Magenta;
-const THEME_CYAN: Color = Color::Cyan;
 const THEME_BLUE: Color = Color::Blue;
-const THEME_PINK: Color = Color::Magenta;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TumixState {
@@ -50,29 +45,29 @@ pub enum TumixState {
 
 fn last_role_color(role: &str) -> Color {
     match role {
-        "Assistant" => THEME_GREEN,
-        "User" => THEME_PINK,
+        "Assistant" => theme().success,
+        "User" => theme().highlight,
         _ => THEME_GRAY,
     }
 }
 
 fn dialog_role_color(role: &str) -> Color {
     match role {
-        "User" => THEME_ORANGE,
-        "Assistant" => THEME_GREEN,
+        "User" => theme().highlight,
+        "Assistant" => theme().success,
         _ => THEME_GRAY,
     }
 }
 
 fn stylize_session_id(id: &str) -> String {
-    format!("{}", id.fg(THEME_ORANGE).bold())
+    format!("{}", id.fg(theme().highlight).bold())
 }
 
 fn session_id_span(id: &str) -> Span<'static> {
     Span::styled(
         id.to_string(),
         Style::default()
-            .fg(THEME_ORANGE)
+            .fg(theme().highlight)
             .add_modifier(Modifier::BOLD),
     )
 }
@@ -94,7 +89,7 @@ fn stylize_label(label: &str) -> String {
 }
 
 fn stylize_messages_count(count: usize) -> String {
-    count.to_string().fg(THEME_YELLOW).to_string()
+    count.to_string().fg(theme().accent).to_string()
 }
 
 fn stylize_separator() -> String {
@@ -114,11 +109,11 @@ fn stylize_cwd(cwd: &str) -> String {
     };
 
     if let Some(rest) = display.strip_prefix("~/") {
-        format!("{}{}", "~/".fg(THEME_PURPLE), rest.fg(THEME_CYAN))
+        format!("{}{}", "~/".fg(theme().highlight), rest.fg(theme().accent))
     } else if display == "~" {
-        "~".fg(THEME_PURPLE).to_string()
+        "~".fg(theme().highlight).to_string()
     } else {
-        display.fg(THEME_CYAN).to_string()
+        display.fg(theme().accent).to_string()
     }
 }
 
@@ -128,10 +123,10 @@ fn colored_bar_span(color: Color) -> Span<'static> {
 
 fn tumix_state_color(state: TumixState) -> Color {
     match state {
-        TumixState::Running => THEME_YELLOW,
-        TumixState::Completed => THEME_GREEN,
-        TumixState::Failed => THEME_PINK,
-        TumixState::Stalled => THEME_PURPLE,
+        TumixState::Running => theme().accent,
+        TumixState::Completed => theme().success,
+        TumixState::Failed => theme().error,
+        TumixState::Stalled => theme().highlight,
     }
 }
 
@@ -139,7 +134,7 @@ fn tumix_badge_span() -> Span<'static> {
     Span::styled(
         "[Tumix]".to_string(),
         Style::default()
-            .fg(THEME_PURPLE)
+            .fg(theme().highlight)
             .add_modifier(Modifier::BOLD),
     )
 }
@@ -187,6 +182,8 @@ pub struct SessionInfo {
     #[allow(dead_code)]
     pub total_tokens: usize,
     pub model: String,
+    /// Latest generated session title, if any.
+    pub title: Option<String>,
     pub tumix: Option<TumixIndicator>,
 }
 
@@ -1103,7 +1100,7 @@ fn get_sessions_dir() -> Result<PathBuf, String> {
 /// Extract enhanced session metadata from .jsonl file
 fn extract_session_meta(
     path: &PathBuf,
-) -> Result<(String, String, usize, String, usize, String), String> {
+) -> Result<(String, String, usize, String, usize, String, Option<String>), String> {
     let file = fs::File::open(path).map_err(|e| e.to_string())?;
     let reader = std::io::BufReader::new(file);
     let mut lines = reader.lines();
@@ -1171,6 +1168,7 @@ fn extract_session_meta(
         last_role,
         total_tokens,
         model,
+        parsed.title,
     ))
 }
 
@@ -1185,6 +1183,7 @@ struct ParsedMessage {
 pub(crate) struct ParsedSessionData {
     messages: Vec<ParsedMessage>,
     total_tokens: Option<usize>,
+    title: Option<String>,
 }
 
 pub(crate) fn collect_session_messages(path: &PathBuf) -> ParsedSessionData {
@@ -1208,6 +1207,15 @@ pub(crate) fn collect_session_messages(path: &PathBuf) -> ParsedSessionData {
         }
 
         if let Ok(json) = serde_json::from_str::<serde_json::Value>(&line) {
+            // Generated titles are appended as later session_meta lines.
+            if json.get("type").and_then(|v| v.as_str()) == Some("session_meta")
+                && let Some(title) = json
+                    .get("payload")
+                    .and_then(|payload| payload.get("title"))
+                    .and_then(|v| v.as_str())
+            {
+                data.title = Some(title.to_string());
+            }
             if first_line {
                 first_line = false;
                 if json.get("type").and_then(|v| v.as_str()) == Some("session_meta") {
@@ -1532,7 +1540,7 @@ pub(crate) fn get_cwd_sessions_for(
             for entry in entries.flatten() {
                 let path = entry.path();
                 if path.is_file() && path.extension().is_some_and(|ext| ext == "jsonl") {
-                    if let Ok((id, session_cwd, msg_count, last_role, tokens, model)) =
+                    if let Ok((id, session_cwd, msg_count, last_role, tokens, model, title)) =
                         extract_session_meta(&path)
                         && should_include_session(&session_cwd, cwd)
                     {
@@ -1556,6 +1564,7 @@ pub(crate) fn get_cwd_sessions_for(
                             last_role,
                             total_tokens: tokens,
                             model,
+                            title,
                             tumix: None,
                         });
                     }
@@ -1764,7 +1773,7 @@ fn format_left_panel_sessions(
     let mut lines = Vec::new();
 
     if sessions.is_empty() {
-        lines.push("No sessions".cyan().into());
+        lines.push("No sessions".fg(theme().accent).into());
         return lines;
     }
 
@@ -1852,7 +1861,7 @@ fn tumix_line_two_text(session: &SessionInfo) -> Option<String> {
         parts.push(agent.as_str().bold().to_string());
     }
     if let Some(branch) = &indicator.branch {
-        parts.push(branch.as_str().cyan().to_string());
+        parts.push(branch.as_str().fg(theme().accent).to_string());
     }
     parts.push(stylize_cwd(session.cwd.as_str()));
     Some(format!("   {}", parts.join(&stylize_separator())))
@@ -1872,7 +1881,7 @@ fn tumix_line_three_suffix(session: &SessionInfo) -> String {
         && let Some(error) = indicator.error.as_ref()
     {
         let truncated = truncate_error(error);
-        parts.push(truncated.as_str().red().to_string());
+        parts.push(truncated.as_str().fg(theme().error).to_string());
     }
 
     if parts.is_empty() {
@@ -1906,10 +1915,10 @@ fn truncate_error(error: &str) -> String {
 
 fn tumix_state_text(state: TumixState) -> String {
     match state {
-        TumixState::Completed => "completed".green().bold().to_string(),
-        TumixState::Failed => "failed".red().bold().to_string(),
-        TumixState::Running => "running".cyan().bold().to_string(),
-        TumixState::Stalled => "stalled".magenta().bold().to_string(),
+        TumixState::Completed => "completed".fg(theme().success).bold().to_string(),
+        TumixState::Failed => "failed".fg(theme().error).bold().to_string(),
+        TumixState::Running => "running".fg(theme().accent).bold().to_string(),
+        TumixState::Stalled => "stalled".fg(theme().highlight).bold().to_string(),
     }
 }
 /// Format left panel with pagination state  - displays paginated session list with pagination info
@@ -1922,7 +1931,7 @@ fn format_left_panel_sessions_paginated(
     let mut lines = Vec::new();
 
     if sessions.is_empty() {
-        lines.push("No sessions".cyan().into());
+        lines.push("No sessions".fg(theme().accent).into());
         return lines;
     }
 
@@ -2107,32 +2116,32 @@ fn format_session_details(session: &SessionInfo) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
 
     lines.push(Line::from(""));
-    lines.push("SESSION DETAILS".bold().cyan().into());
+    lines.push("SESSION DETAILS".bold().fg(theme().accent).into());
     lines.push(Line::from(""));
 
     // Session ID
     lines.push(ansi_escape_line(&format!(
         "  ID:             {}",
-        session.id.as_str().cyan()
+        session.id.as_str().fg(theme().accent)
     )));
 
     // Model
     lines.push(ansi_escape_line(&format!(
         "  Model:          {}",
-        session.model.as_str().cyan()
+        session.model.as_str().fg(theme().accent)
     )));
 
     // Messages
     lines.push(ansi_escape_line(&format!(
         "  Messages:       {} ({} last)",
-        session.message_count.to_string().cyan(),
-        session.last_role.as_str().green()
+        session.message_count.to_string().fg(theme().accent),
+        session.last_role.as_str().fg(theme().success)
     )));
 
     // Tokens
     lines.push(ansi_escape_line(&format!(
         "  Tokens Used:    {}",
-        session.total_tokens.to_string().cyan()
+        session.total_tokens.to_string().fg(theme().accent)
     )));
 
     // Age
@@ -2157,7 +2166,7 @@ fn format_session_details(session: &SessionInfo) -> Vec<Line<'static>> {
 
     if let Some(tumix) = &session.tumix {
         lines.push(Line::from(""));
-        lines.push("TUMIX".bold().magenta().into());
+        lines.push("TUMIX".bold().fg(theme().highlight).into());
         lines.push(ansi_escape_line(&format!(
             "  State:          {}",
             tumix_state_text(tumix.state)
@@ -2168,7 +2177,7 @@ fn format_session_details(session: &SessionInfo) -> Vec<Line<'static>> {
         )));
         lines.push(ansi_escape_line(&format!(
             "  Agent ID:       {}",
-            tumix.agent_id.as_str().cyan()
+            tumix.agent_id.as_str().fg(theme().accent)
         )));
         if let Some(agent) = tumix.agent_name.as_deref() {
             lines.push(ansi_escape_line(&format!(
@@ -2179,14 +2188,14 @@ fn format_session_details(session: &SessionInfo) -> Vec<Line<'static>> {
         if let Some(branch) = tumix.branch.as_deref() {
             lines.push(ansi_escape_line(&format!(
                 "  Branch:         {}",
-                branch.cyan()
+                branch.fg(theme().accent)
             )));
         }
         if let Some(error) = tumix.error.as_deref() {
             let truncated = truncate_error(error);
             lines.push(ansi_escape_line(&format!(
                 "  Notes:          {}",
-                truncated.as_str().red()
+                truncated.as_str().fg(theme().error)
             )));
         }
     }
@@ -2195,7 +2204,7 @@ fn format_session_details(session: &SessionInfo) -> Vec<Line<'static>> {
     lines
         .push(Line::from("────────────────────────────────────────────────────────────────").dim());
     lines.push(Line::from(""));
-    lines.push("STATISTICS".bold().cyan().into());
+    lines.push("STATISTICS".bold().fg(theme().accent).into());
     lines.push(Line::from(""));
 
     lines.push(ansi_escape_line(&format!(
@@ -2206,7 +2215,7 @@ fn format_session_details(session: &SessionInfo) -> Vec<Line<'static>> {
             "N/A".to_string()
         }
         .as_str()
-        .cyan()
+        .fg(theme().accent)
     )));
 
     lines.push(Line::from(""));
@@ -2257,7 +2266,7 @@ fn format_message_blocks(session: &SessionInfo, width: u16) -> Vec<Line<'static>
     // Info box header
     let info_text = format!(
         "Session: {} • Path: {} • Started: {}",
-        session.id.as_str().cyan(),
+        session.id.as_str().fg(theme().accent),
         session.cwd.as_str().dim(),
         session.age.as_str().dim()
     );
@@ -2266,14 +2275,18 @@ fn format_message_blocks(session: &SessionInfo, width: u16) -> Vec<Line<'static>
 
     let messages = extract_recent_messages_with_timestamps(&session.path, 8);
     if messages.is_empty() {
-        lines.push("No messages found in this session.".cyan().into());
+        lines.push(
+            "No messages found in this session."
+                .fg(theme().accent)
+                .into(),
+        );
     } else {
         for (role, content, _timestamp) in messages.iter() {
             // Message header with bar indicator (┃)
             let role_color = if role == "User" {
-                role.as_str().red()
+                role.as_str().fg(theme().error)
             } else {
-                role.as_str().green()
+                role.as_str().fg(theme().success)
             };
 
             let header_text = format!("┃ {role_color}");
@@ -2309,15 +2322,20 @@ fn format_session_preview(session: &SessionInfo) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
 
     lines.push(Line::from(""));
-    lines.push("SESSION PREVIEW - Recent Messages".bold().cyan().into());
+    lines.push(
+        "SESSION PREVIEW - Recent Messages"
+            .bold()
+            .fg(theme().accent)
+            .into(),
+    );
     lines.push(Line::from(""));
     lines.push(ansi_escape_line(&format!(
         "Session: {}",
-        session.id.as_str().cyan()
+        session.id.as_str().fg(theme().accent)
     )));
     lines.push(ansi_escape_line(&format!(
         "Model: {}",
-        session.model.as_str().cyan()
+        session.model.as_str().fg(theme().accent)
     )));
     lines.push(Line::from(""));
     lines
@@ -2332,9 +2350,9 @@ fn format_session_preview(session: &SessionInfo) -> Vec<Line<'static>> {
         for (idx, (role, content)) in messages.iter().enumerate() {
             // Role header
             let role_line = if role == "User" {
-                format!("{}. {} (User)", idx + 1, "▶".cyan())
+                format!("{}. {} (User)", idx + 1, "▶".fg(theme().accent))
             } else {
-                format!("{}. {} (Assistant)", idx + 1, "◀".green())
+                format!("{}. {} (Assistant)", idx + 1, "◀".fg(theme().success))
             };
             lines.push(role_line.into());
 
@@ -2429,7 +2447,7 @@ fn render_body(frame: &mut crate::custom_terminal::Frame, area: Rect, state: &Pi
     if state.sessions.is_empty() {
         let lines = vec![
             "No sessions found in current working directory"
-                .cyan()
+                .fg(theme().accent)
                 .into(),
             Line::from(""),
             "Press q to close".dim().into(),
@@ -2472,7 +2490,7 @@ fn render_split_body(frame: &mut crate::custom_terminal::Frame, area: Rect, stat
     );
     let left_visible = slice_left_panel_lines(&left_lines_full, regions[0].height as usize);
     let left_border_style = if state.focus == FocusPane::LeftList {
-        Style::default().fg(Color::Cyan)
+        Style::default().fg(theme().accent)
     } else {
         Style::default()
     };
@@ -2495,7 +2513,7 @@ fn render_split_body(frame: &mut crate::custom_terminal::Frame, area: Rect, stat
         state.scroll_offset_right,
     );
     let right_border_style = if state.focus == FocusPane::RightPreview {
-        Style::default().fg(Color::Cyan)
+        Style::default().fg(theme().accent)
     } else {
         Style::default()
     };
@@ -2516,7 +2534,7 @@ fn render_full_preview_body(
     let lines = state
         .selected_session()
         .map(format_session_preview)
-        .unwrap_or_else(|| vec!["No session selected".cyan().into()]);
+        .unwrap_or_else(|| vec!["No session selected".fg(theme().accent).into()]);
     let offset = if state.focus == FocusPane::RightPreview {
         state.scroll_offset_right
     } else {
@@ -2652,7 +2670,7 @@ fn build_header_lines(state: &PickerState) -> Vec<Line<'static>> {
         mode = state.view_mode,
         focus = focus_label
     );
-    lines.push(ansi_escape_line(&title).bold().cyan());
+    lines.push(ansi_escape_line(&title).bold().fg(theme().accent));
     lines.push(Line::from(""));
     lines
 }
@@ -1,7 +1,6 @@
 use diffy::Hunk;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
@@ -14,10 +13,13 @@ use std::path::PathBuf;
 
 use crate::exec_command::relativize_to_home;
 use crate::render::Insets;
+use crate::render::highlight::SyntaxLanguage;
+use crate::render::highlight::highlight_code_to_lines;
 use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
+use crate::theme::theme;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::protocol::FileChange;
 
@@ -39,16 +41,22 @@ impl DiffSummary {
     }
 }
 
-impl Renderable for FileChange {
+/// A file change rendered with syntax highlighting for its language.
+struct FileDiff {
+    change: FileChange,
+    language: Option<SyntaxLanguage>,
+}
+
+impl Renderable for FileDiff {
     fn render(&self, area: Rect, buf: &mut Buffer) {
         let mut lines = vec![];
-        render_change(self, &mut lines, area.width as usize);
+        render_change(&self.change, &mut lines, area.width as usize, self.language);
         Paragraph::new(lines).render(area, buf);
     }
 
     fn desired_height(&self, width: u16) -> u16 {
         let mut lines = vec![];
-        render_change(self, &mut lines, width as usize);
+        render_change(&self.change, &mut lines, width as usize, self.language);
         lines.len() as u16
     }
 }
@@ -66,8 +74,12 @@ impl From<DiffSummary> for Box<dyn Renderable> {
             path.extend(render_line_count_summary(row.added, row.removed));
             rows.push(Box::new(path));
             rows.push(Box::new(RtLine::from("")));
+            let language = row.language();
             rows.push(Box::new(InsetRenderable::new(
-                Box::new(row.change) as Box<dyn Renderable>,
+                Box::new(FileDiff {
+                    change: row.change,
+                    language,
+                }) as Box<dyn Renderable>,
                 Insets::tlbr(0, 2, 0, 0),
             )));
         }
@@ -88,7 +100,6 @@ pub(crate) fn create_diff_summary(
 // Shared row for per-file presentation
 #[derive(Clone)]
 struct Row {
-    #[allow(dead_code)]
     path: PathBuf,
     move_path: Option<PathBuf>,
     added: usize,
@@ -96,6 +107,14 @@ struct Row {
     change: FileChange,
 }
 
+impl Row {
+    /// Language used to highlight the change, preferring the destination of
+    /// a move.
+    fn language(&self) -> Option<SyntaxLanguage> {
+        SyntaxLanguage::from_path(self.move_path.as_deref().unwrap_or(&self.path))
+    }
+}
+
 fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
     let mut rows: Vec<Row> = Vec::new();
     for (path, change) in changes.iter() {
@@ -103,6 +122,9 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
             FileChange::Add { content } => (content.lines().count(), 0),
             FileChange::Delete { content } => (0, content.lines().count()),
             FileChange::Update { unified_diff, .. } => calculate_add_remove_from_diff(unified_diff),
+            FileChange::Binary { .. } | FileChange::Symlink { .. } | FileChange::Mode { .. } => {
+                (0, 0)
+            }
         };
         let move_path = match change {
             FileChange::Update {
@@ -126,9 +148,9 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
 fn render_line_count_summary(added: usize, removed: usize) -> Vec<RtSpan<'static>> {
     let mut spans = Vec::new();
     spans.push("(".into());
-    spans.push(format!("+{added}").green());
+    spans.push(format!("+{added}").fg(theme().success));
     spans.push(" ".into());
-    spans.push(format!("-{removed}").red());
+    spans.push(format!("-{removed}").fg(theme().error));
     spans.push(")".into());
     spans
 }
@@ -153,7 +175,9 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
     let mut header_spans: Vec<RtSpan<'static>> = vec!["• ".dim()];
     if let [row] = &rows[..] {
         let verb = match &row.change {
-            FileChange::Add { .. } => "Added",
+            FileChange::Add { .. }
+            | FileChange::Binary { old_size: None, .. }
+            | FileChange::Symlink { .. } => "Added",
             FileChange::Delete { .. } => "Deleted",
             _ => "Edited",
         };
@@ -186,22 +210,29 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
         }
 
         let mut lines = vec![];
-        render_change(&r.change, &mut lines, wrap_cols - 4);
+        render_change(&r.change, &mut lines, wrap_cols - 4, r.language());
         out.extend(prefix_lines(lines, "    ".into(), "    ".into()));
     }
 
     out
 }
 
-fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usize) {
+fn render_change(
+    change: &FileChange,
+    out: &mut Vec<RtLine<'static>>,
+    width: usize,
+    language: Option<SyntaxLanguage>,
+) {
     match change {
         FileChange::Add { content } => {
             let line_number_width = line_number_width(content.lines().count());
+            let highlighted = highlight_source(content, language);
             for (i, raw) in content.lines().enumerate() {
                 out.extend(push_wrapped_diff_line(
                     i + 1,
                     DiffLineType::Insert,
                     raw,
+                    highlighted_line(highlighted.as_deref(), i),
                     width,
                     line_number_width,
                 ));
@@ -209,11 +240,13 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
         }
         FileChange::Delete { content } => {
             let line_number_width = line_number_width(content.lines().count());
+            let highlighted = highlight_source(content, language);
             for (i, raw) in content.lines().enumerate() {
                 out.extend(push_wrapped_diff_line(
                     i + 1,
                     DiffLineType::Delete,
                     raw,
+                    highlighted_line(highlighted.as_deref(), i),
                     width,
                     line_number_width,
                 ));
@@ -253,6 +286,15 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                     }
                     is_first_hunk = false;
 
+                    // Highlight each side of the hunk as a whole so constructs
+                    // spanning several lines (strings, comments) keep their
+                    // styling.
+                    let (old_source, new_source) = hunk_sources(h);
+                    let old_highlighted = highlight_source(&old_source, language);
+                    let new_highlighted = highlight_source(&new_source, language);
+                    let mut old_idx = 0;
+                    let mut new_idx = 0;
+
                     let mut old_ln = h.old_range().start();
                     let mut new_ln = h.new_range().start();
                     for l in h.lines() {
@@ -263,10 +305,12 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     new_ln,
                                     DiffLineType::Insert,
                                     s,
+                                    highlighted_line(new_highlighted.as_deref(), new_idx),
                                     width,
                                     line_number_width,
                                 ));
                                 new_ln += 1;
+                                new_idx += 1;
                             }
                             diffy::Line::Delete(text) => {
                                 let s = text.trim_end_matches('\n');
@@ -274,10 +318,12 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     old_ln,
                                     DiffLineType::Delete,
                                     s,
+                                    highlighted_line(old_highlighted.as_deref(), old_idx),
                                     width,
                                     line_number_width,
                                 ));
                                 old_ln += 1;
+                                old_idx += 1;
                             }
                             diffy::Line::Context(text) => {
                                 let s = text.trim_end_matches('\n');
@@ -285,18 +331,61 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     new_ln,
                                     DiffLineType::Context,
                                     s,
+                                    highlighted_line(new_highlighted.as_deref(), new_idx),
                                     width,
                                     line_number_width,
                                 ));
                                 old_ln += 1;
                                 new_ln += 1;
+                                old_idx += 1;
+                                new_idx += 1;
                             }
                         }
                     }
                 }
             }
         }
+        FileChange::Binary { .. } | FileChange::Symlink { .. } | FileChange::Mode { .. } => {
+            if let Some(summary) = change.non_text_summary() {
+                out.push(RtLine::from(summary.dim()));
+            }
+        }
+    }
+}
+
+/// Returns the text of the hunk before and after the change, one source line
+/// per hunk line on each side.
+fn hunk_sources(hunk: &Hunk<'_, str>) -> (String, String) {
+    let mut old_source = String::new();
+    let mut new_source = String::new();
+    for line in hunk.lines() {
+        let (old, new) = match line {
+            diffy::Line::Context(text) => (Some(*text), Some(*text)),
+            diffy::Line::Delete(text) => (Some(*text), None),
+            diffy::Line::Insert(text) => (None, Some(*text)),
+        };
+        for (source, text) in [(&mut old_source, old), (&mut new_source, new)] {
+            if let Some(text) = text {
+                source.push_str(text.trim_end_matches('\n'));
+                source.push('\n');
+            }
+        }
     }
+    (old_source, new_source)
+}
+
+fn highlight_source(
+    source: &str,
+    language: Option<SyntaxLanguage>,
+) -> Option<Vec<RtLine<'static>>> {
+    highlight_code_to_lines(source, language?)
+}
+
+fn highlighted_line(
+    highlighted: Option<&[RtLine<'static>]>,
+    index: usize,
+) -> Option<&RtLine<'static>> {
+    highlighted.and_then(|lines| lines.get(index))
 }
 
 pub(crate) fn display_path_for(path: &Path, cwd: &Path) -> String {
@@ -331,63 +420,83 @@ fn calculate_add_remove_from_diff(diff: &str) -> (usize, usize) {
     }
 }
 
+/// Renders one diff line, wrapped to `width`. When `syntax` is given its
+/// spans supply the content, and the diff style is patched over the syntax
+/// styles so added and deleted lines keep their color.
 fn push_wrapped_diff_line(
     line_number: usize,
     kind: DiffLineType,
     text: &str,
+    syntax: Option<&RtLine<'static>>,
     width: usize,
     line_number_width: usize,
 ) -> Vec<RtLine<'static>> {
     let ln_str = line_number.to_string();
-    let mut remaining_text: &str = text;
 
     // Reserve a fixed number of spaces (equal to the widest line number plus a
     // trailing spacer) so the sign column stays aligned across the diff block.
     let gutter_width = line_number_width.max(1);
     let prefix_cols = gutter_width + 1;
 
-    let mut first = true;
     let (sign_char, line_style) = match kind {
         DiffLineType::Insert => ('+', style_add()),
         DiffLineType::Delete => ('-', style_del()),
         DiffLineType::Context => (' ', style_context()),
     };
-    let mut lines: Vec<RtLine<'static>> = Vec::new();
+    let content: Vec<RtSpan<'static>> = match syntax {
+        Some(line) => line
+            .spans
+            .iter()
+            .map(|span| RtSpan::styled(span.content.to_string(), span.style.patch(line_style)))
+            .collect(),
+        None => vec![RtSpan::styled(text.to_string(), line_style)],
+    };
+
+    // Fit the content for each terminal row: compute how many columns are
+    // available after the prefix, then split spans at UTF-8 character
+    // boundaries so every row's chunk fits exactly.
+    let available_content_cols = width.saturating_sub(prefix_cols + 1).max(1);
+    let mut rows: Vec<Vec<RtSpan<'static>>> = vec![Vec::new()];
+    let mut row_cols = 0;
+    for span in content {
+        let mut remaining_text: &str = &span.content;
+        while !remaining_text.is_empty() {
+            if row_cols == available_content_cols {
+                rows.push(Vec::new());
+                row_cols = 0;
+            }
+            let split_at_byte_index = remaining_text
+                .char_indices()
+                .nth(available_content_cols - row_cols)
+                .map(|(i, _)| i)
+                .unwrap_or_else(|| remaining_text.len());
+            let (chunk, rest) = remaining_text.split_at(split_at_byte_index);
+            row_cols += chunk.chars().count();
+            if let Some(row) = rows.last_mut() {
+                row.push(RtSpan::styled(chunk.to_string(), span.style));
+            }
+            remaining_text = rest;
+        }
+    }
 
-    loop {
-        // Fit the content for the current terminal row:
-        // compute how many columns are available after the prefix, then split
-        // at a UTF-8 character boundary so this row's chunk fits exactly.
-        let available_content_cols = width.saturating_sub(prefix_cols + 1).max(1);
-        let split_at_byte_index = remaining_text
-            .char_indices()
-            .nth(available_content_cols)
-            .map(|(i, _)| i)
-            .unwrap_or_else(|| remaining_text.len());
-        let (chunk, rest) = remaining_text.split_at(split_at_byte_index);
-        remaining_text = rest;
-
-        if first {
-            // Build gutter (right-aligned line number plus spacer) as a dimmed span
-            let gutter = format!("{ln_str:>gutter_width$} ");
-            // Content with a sign ('+'/'-'/' ') styled per diff kind
-            let content = format!("{sign_char}{chunk}");
-            lines.push(RtLine::from(vec![
-                RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(content, line_style),
-            ]));
-            first = false;
+    let mut lines: Vec<RtLine<'static>> = Vec::new();
+    for (i, row) in rows.into_iter().enumerate() {
+        let mut spans = if i == 0 {
+            // Gutter (right-aligned line number plus spacer) and the sign
+            // ('+'/'-'/' ') styled per diff kind
+            vec![
+                RtSpan::styled(format!("{ln_str:>gutter_width$} "), style_gutter()),
+                RtSpan::styled(sign_char.to_string(), line_style),
+            ]
         } else {
             // Continuation lines keep a space for the sign column so content aligns
-            let gutter = format!("{:gutter_width$}  ", "");
-            lines.push(RtLine::from(vec![
-                RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(chunk.to_string(), line_style),
-            ]));
-        }
-        if remaining_text.is_empty() {
-            break;
-        }
+            vec![RtSpan::styled(
+                format!("{:gutter_width$}  ", ""),
+                style_gutter(),
+            )]
+        };
+        spans.extend(row);
+        lines.push(RtLine::from(spans));
     }
     lines
 }
@@ -409,11 +518,11 @@ fn style_context() -> Style {
 }
 
 fn style_add() -> Style {
-    Style::default().fg(Color::Green)
+    Style::default().fg(theme().success)
 }
 
 fn style_del() -> Style {
-    Style::default().fg(Color::Red)
+    Style::default().fg(theme().error)
 }
 
 #[cfg(test)]
@@ -422,6 +531,7 @@ mod tests {
     use insta::assert_snapshot;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
+    use ratatui::style::Color;
     use ratatui::text::Text;
     use ratatui::widgets::Paragraph;
     use ratatui::widgets::WidgetRef;
@@ -465,8 +575,14 @@ mod tests {
         let long_line = "this is a very long line that should wrap across multiple terminal columns and continue";
 
         // Call the wrapping function directly so we can precisely control the width
-        let lines =
-            push_wrapped_diff_line(1, DiffLineType::Insert, long_line, 80, line_number_width(1));
+        let lines = push_wrapped_diff_line(
+            1,
+            DiffLineType::Insert,
+            long_line,
+            None,
+            80,
+            line_number_width(1),
+        );
 
         // Render into a small terminal to capture the visual layout
         snapshot_lines("wrap_behavior_insert", lines, 90, 8);
@@ -540,6 +656,48 @@ mod tests {
         snapshot_lines("apply_multiple_files_block", lines, 80, 14);
     }
 
+    #[test]
+    fn non_text_changes_render_summaries() {
+        let changes = HashMap::from([
+            (
+                PathBuf::from("logo.png"),
+                FileChange::Binary {
+                    old_size: None,
+                    new_size: 42,
+                },
+            ),
+            (
+                PathBuf::from("run.sh"),
+                FileChange::Mode {
+                    old_mode: Some(0o644),
+                    new_mode: 0o755,
+                },
+            ),
+        ]);
+
+        let text: Vec<String> = diff_summary_for_tests(&changes)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect();
+
+        assert_eq!(
+            text,
+            vec![
+                "• Edited 2 files (+0 -0)",
+                "  └ logo.png (+0 -0)",
+                "    binary file (42 bytes)",
+                "",
+                "  └ run.sh (+0 -0)",
+                "    mode 644 -> 755",
+            ]
+        );
+    }
+
     #[test]
     fn ui_snapshot_apply_add_block() {
         let mut changes: HashMap<PathBuf, FileChange> = HashMap::new();
@@ -670,4 +828,39 @@ mod tests {
 
         snapshot_lines("apply_update_block_relativizes_path", lines, 80, 10);
     }
+
+    #[test]
+    fn update_highlights_context_and_keeps_diff_colors_on_changes() {
+        let original = "fn main() {\n    let x = 1;\n}\n";
+        let modified = "fn main() {\n    let x = 2;\n}\n";
+        let changes = HashMap::from([(
+            PathBuf::from("main.rs"),
+            FileChange::Update {
+                unified_diff: diffy::create_patch(original, modified).to_string(),
+                move_path: None,
+            },
+        )]);
+
+        let lines = diff_summary_for_tests(&changes);
+        let span_in_line = |line_text: &str, token: &str| {
+            lines
+                .iter()
+                .find(|line| {
+                    line.spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect::<String>()
+                        .contains(line_text)
+                })
+                .and_then(|line| line.spans.iter().find(|span| span.content == token))
+                .map(|span| span.style)
+                .unwrap_or_else(|| panic!("missing {token:?} in {line_text:?}"))
+        };
+
+        let context_keyword = span_in_line("fn main", "fn");
+        assert!(context_keyword.fg.is_some());
+        assert_ne!(context_keyword.fg, Some(Color::Green));
+        assert_eq!(span_in_line("let x = 2", "let").fg, Some(Color::Green));
+        assert_eq!(span_in_line("let x = 1", "let").fg, Some(Color::Red));
+    }
 }
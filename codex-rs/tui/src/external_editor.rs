@@ -0,0 +1,46 @@
+//! Hands a piece of text to the user's `$VISUAL` / `$EDITOR` while the TUI
+//! steps out of raw mode.
+
+use std::io;
+use std::process::Command;
+
+use tempfile::Builder;
+
+#[cfg(windows)]
+const DEFAULT_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+const DEFAULT_EDITOR: &str = "vi";
+
+/// Opens `initial` in the user's editor and returns the saved contents.
+///
+/// Blocks until the editor exits; the terminal modes set up by the TUI are
+/// restored for the duration of the edit.
+pub(crate) fn edit_in_external_editor(initial: &str) -> io::Result<String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+    let argv = shlex::split(&editor)
+        .filter(|argv| !argv.is_empty())
+        .ok_or_else(|| io::Error::other(format!("could not parse editor command `{editor}`")))?;
+
+    let file = Builder::new()
+        .prefix("codex-edit-")
+        .suffix(".txt")
+        .tempfile()?;
+    std::fs::write(file.path(), initial)?;
+
+    crate::tui::restore()?;
+    let status = Command::new(&argv[0])
+        .args(&argv[1..])
+        .arg(file.path())
+        .status();
+    crate::tui::set_modes()?;
+
+    let status = status?;
+    if !status.success() {
+        return Err(io::Error::other(format!("`{editor}` exited with {status}")));
+    }
+    std::fs::read_to_string(file.path())
+}
@@ -17,6 +17,10 @@
 //!    recent query.
 //! 4. If there is a in-flight search that is not a prefix of the latest thing
 //!    the user typed, it is cancelled.
+//!
+//! Searches run against a shared [`file_search::FileIndex`] for the search
+//! directory, which is built once in the background and kept up to date by a
+//! file watcher instead of walking the tree on every keystroke.
 
 use codex_file_search as file_search;
 use std::num::NonZeroUsize;
@@ -32,7 +36,6 @@ use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 
 const MAX_FILE_SEARCH_RESULTS: NonZeroUsize = NonZeroUsize::new(20).unwrap();
-const NUM_FILE_SEARCH_THREADS: NonZeroUsize = NonZeroUsize::new(2).unwrap();
 
 /// How long to wait after a keystroke before firing the first search when none
 /// is currently running. Keeps early queries more meaningful.
@@ -45,7 +48,7 @@ pub(crate) struct FileSearchManager {
     /// Unified state guarded by one mutex.
     state: Arc<Mutex<SearchState>>,
 
-    search_dir: PathBuf,
+    index: Arc<file_search::FileIndex>,
     app_tx: AppEventSender,
 }
 
@@ -73,7 +76,7 @@ impl FileSearchManager {
                 is_search_scheduled: false,
                 active_search: None,
             })),
-            search_dir,
+            index: file_search::FileIndex::for_root(&search_dir),
             app_tx: tx,
         }
     }
@@ -115,7 +118,7 @@ impl FileSearchManager {
         // dropping the lock. This means we are the only thread that can spawn a
         // debounce timer.
         let state = self.state.clone();
-        let search_dir = self.search_dir.clone();
+        let index = self.index.clone();
         let tx_clone = self.app_tx.clone();
         thread::spawn(move || {
             // Always do a minimum debounce, but then poll until the
@@ -145,37 +148,28 @@ impl FileSearchManager {
                 query
             };
 
-            FileSearchManager::spawn_file_search(
-                query,
-                search_dir,
-                tx_clone,
-                cancellation_token,
-                state,
-            );
+            FileSearchManager::spawn_file_search(query, index, tx_clone, cancellation_token, state);
         });
     }
 
     fn spawn_file_search(
         query: String,
-        search_dir: PathBuf,
+        index: Arc<file_search::FileIndex>,
         tx: AppEventSender,
         cancellation_token: Arc<AtomicBool>,
         search_state: Arc<Mutex<SearchState>>,
     ) {
         let compute_indices = true;
         std::thread::spawn(move || {
-            let matches = file_search::run(
-                &query,
-                MAX_FILE_SEARCH_RESULTS,
-                &search_dir,
-                Vec::new(),
-                NUM_FILE_SEARCH_THREADS,
-                cancellation_token.clone(),
-                compute_indices,
-                true,
-            )
-            .map(|res| res.matches)
-            .unwrap_or_default();
+            let matches = index
+                .search(
+                    &query,
+                    MAX_FILE_SEARCH_RESULTS,
+                    cancellation_token.clone(),
+                    compute_indices,
+                )
+                .map(|res| res.matches)
+                .unwrap_or_default();
 
             let is_cancelled = cancellation_token.load(Ordering::Relaxed);
             if !is_cancelled {
@@ -15,6 +15,7 @@ use crate::render::renderable::Renderable;
 use crate::style::user_message_style;
 use crate::text_formatting::format_and_truncate_tool_result;
 use crate::text_formatting::truncate_text;
+use crate::theme::theme;
 use crate::tooltips;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use crate::update_action::UpdateAction;
@@ -304,19 +305,25 @@ impl HistoryCell for UpdateAvailableHistoryCell {
         use ratatui_macros::line;
         use ratatui_macros::text;
         let update_instruction = if let Some(update_action) = self.update_action {
-            line!["Run ", update_action.command_str().cyan(), " to update."]
+            line![
+                "Run ",
+                update_action.command_str().fg(theme().accent),
+                " to update."
+            ]
         } else {
             line![
                 "See ",
-                "https://github.com/openai/codex".cyan().underlined(),
+                "https://github.com/openai/codex"
+                    .fg(theme().accent)
+                    .underlined(),
                 " for installation options."
             ]
         };
 
         let content = text![
             line![
-                padded_emoji("✨").bold().cyan(),
-                "Update available!".bold().cyan(),
+                padded_emoji("✨").bold().fg(theme().accent),
+                "Update available!".bold().fg(theme().accent),
                 " ",
                 format!("{CODEX_CLI_VERSION} -> {}", self.latest_version).bold(),
             ],
@@ -324,7 +331,7 @@ impl HistoryCell for UpdateAvailableHistoryCell {
             "",
             "See full release notes:",
             "https://github.com/openai/codex/releases/latest"
-                .cyan()
+                .fg(theme().accent)
                 .underlined(),
         ];
 
@@ -397,10 +404,10 @@ pub fn new_approval_decision_cell(
     use codex_core::protocol::ReviewDecision::*;
 
     let (symbol, summary): (Span<'static>, Vec<Span<'static>>) = match decision {
-        Approved => {
+        Approved | ApprovedWithHunkReview { .. } => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
-                "✔ ".green(),
+                "✔ ".fg(theme().success),
                 vec![
                     "You ".into(),
                     "approved".bold(),
@@ -413,7 +420,7 @@ pub fn new_approval_decision_cell(
         ApprovedExecpolicyAmendment { .. } => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
-                "✔ ".green(),
+                "✔ ".fg(theme().success),
                 vec![
                     "You ".into(),
                     "approved".bold(),
@@ -426,7 +433,7 @@ pub fn new_approval_decision_cell(
         ApprovedForSession => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
-                "✔ ".green(),
+                "✔ ".fg(theme().success),
                 vec![
                     "You ".into(),
                     "approved".bold(),
@@ -439,7 +446,7 @@ pub fn new_approval_decision_cell(
         Denied => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
-                "✗ ".red(),
+                "✗ ".fg(theme().error),
                 vec![
                     "You ".into(),
                     "did not approve".bold(),
@@ -451,7 +458,7 @@ pub fn new_approval_decision_cell(
         Abort => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
-                "✗ ".red(),
+                "✗ ".fg(theme().error),
                 vec![
                     "You ".into(),
                     "canceled".bold(),
@@ -472,7 +479,7 @@ pub fn new_approval_decision_cell(
 /// Cyan history cell line showing the current review status.
 pub(crate) fn new_review_status_line(message: String) -> PlainHistoryCell {
     PlainHistoryCell {
-        lines: vec![Line::from(message.cyan())],
+        lines: vec![Line::from(message.fg(theme().accent))],
     }
 }
 
@@ -683,7 +690,7 @@ pub(crate) fn new_session_info(
         }
         if requested_model != model {
             let lines = vec![
-                "model changed:".magenta().bold().into(),
+                "model changed:".fg(theme().highlight).bold().into(),
                 format!("requested: {requested_model}").into(),
                 format!("used: {model}").into(),
             ];
@@ -800,7 +807,7 @@ impl HistoryCell for SessionHeaderHistoryCell {
             model_spans.push(Span::from(reasoning));
         }
         model_spans.push("   ".dim());
-        model_spans.push(CHANGE_MODEL_HINT_COMMAND.cyan());
+        model_spans.push(CHANGE_MODEL_HINT_COMMAND.fg(theme().accent));
         model_spans.push(CHANGE_MODEL_HINT_EXPLANATION.dim());
 
         let dir_label = format!("{DIR_LABEL:<label_width$}");
@@ -932,8 +939,8 @@ impl HistoryCell for McpToolCallCell {
         let mut lines: Vec<Line<'static>> = Vec::new();
         let status = self.success();
         let bullet = match status {
-            Some(true) => "•".green().bold(),
-            Some(false) => "•".red().bold(),
+            Some(true) => "•".fg(theme().success).bold(),
+            Some(false) => "•".fg(theme().error).bold(),
             None => spinner(Some(self.start_time), self.animations_enabled),
         };
         let header_text = if status.is_some() {
@@ -1093,7 +1100,13 @@ pub(crate) fn new_deprecation_notice(
 impl HistoryCell for DeprecationNoticeCell {
     fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
         let mut lines: Vec<Line<'static>> = Vec::new();
-        lines.push(vec!["⚠ ".red().bold(), self.summary.clone().red()].into());
+        lines.push(
+            vec![
+                "⚠ ".fg(theme().error).bold(),
+                self.summary.clone().fg(theme().error),
+            ]
+            .into(),
+        );
 
         let wrap_width = width.saturating_sub(4).max(1) as usize;
 
@@ -1112,7 +1125,7 @@ impl HistoryCell for DeprecationNoticeCell {
 /// Render a summary of configured MCP servers from the current `Config`.
 pub(crate) fn empty_mcp_output() -> PlainHistoryCell {
     let lines: Vec<Line<'static>> = vec![
-        "/mcp".magenta().into(),
+        "/mcp".fg(theme().highlight).into(),
         "".into(),
         vec!["🔌  ".into(), "MCP Tools".bold()].into(),
         "".into(),
@@ -1137,7 +1150,7 @@ pub(crate) fn new_mcp_tools_output(
     auth_statuses: &HashMap<String, McpAuthStatus>,
 ) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = vec![
-        "/mcp".magenta().into(),
+        "/mcp".fg(theme().highlight).into(),
         "".into(),
         vec!["🔌  ".into(), "MCP Tools".bold()].into(),
         "".into(),
@@ -1168,13 +1181,13 @@ pub(crate) fn new_mcp_tools_output(
         let mut header: Vec<Span<'static>> = vec!["  • ".into(), server.clone().into()];
         if !cfg.enabled {
             header.push(" ".into());
-            header.push("(disabled)".red());
+            header.push("(disabled)".fg(theme().error));
             lines.push(header.into());
             lines.push(Line::from(""));
             continue;
         }
         lines.push(header.into());
-        lines.push(vec!["    • Status: ".into(), "enabled".green()].into());
+        lines.push(vec!["    • Status: ".into(), "enabled".fg(theme().success)].into());
         lines.push(vec!["    • Auth: ".into(), auth_status.to_string().into()].into());
 
         match &cfg.transport {
@@ -1349,7 +1362,7 @@ pub(crate) fn new_error_event(message: String) -> PlainHistoryCell {
     // Use a hair space (U+200A) to create a subtle, near-invisible separation
     // before the text. VS16 is intentionally omitted to keep spacing tighter
     // in terminals like Ghostty.
-    let lines: Vec<Line<'static>> = vec![vec![format!("■ {message}").red()].into()];
+    let lines: Vec<Line<'static>> = vec![vec![format!("■ {message}").fg(theme().error)].into()];
     PlainHistoryCell { lines }
 }
 
@@ -1378,7 +1391,7 @@ impl HistoryCell for PlanUpdateCell {
         let render_step = |status: &StepStatus, text: &str| -> Vec<Line<'static>> {
             let (box_str, step_style) = match status {
                 StepStatus::Completed => ("✔ ", Style::default().crossed_out().dim()),
-                StepStatus::InProgress => ("□ ", Style::default().cyan().bold()),
+                StepStatus::InProgress => ("□ ", Style::default().fg(theme().accent).bold()),
                 StepStatus::Pending => ("□ ", Style::default().dim()),
             };
             let wrap_width = (width as usize)
@@ -1436,7 +1449,9 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = Vec::new();
 
     // Failure title
-    lines.push(Line::from("✘ Failed to apply patch".magenta().bold()));
+    lines.push(Line::from(
+        "✘ Failed to apply patch".fg(theme().highlight).bold(),
+    ));
 
     if !stderr.trim().is_empty() {
         let output = output_lines(
@@ -1552,9 +1567,9 @@ fn format_mcp_invocation<'a>(invocation: McpInvocation) -> Line<'a> {
         .unwrap_or_default();
 
     let invocation_spans = vec![
-        invocation.server.clone().cyan(),
+        invocation.server.clone().fg(theme().accent),
         ".".into(),
-        invocation.tool.cyan(),
+        invocation.tool.fg(theme().accent),
         "(".into(),
         args_str.dim(),
         ")".into(),
@@ -3,8 +3,8 @@ use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
 use crossterm::event::KeyModifiers;
 use ratatui::style::Style;
-use ratatui::style::Stylize;
 use ratatui::text::Span;
+use std::str::FromStr;
 
 #[cfg(test)]
 const ALT_PREFIX: &str = "⌥ + ";
@@ -31,6 +31,87 @@ impl KeyBinding {
             && self.modifiers == event.modifiers
             && (event.kind == KeyEventKind::Press || event.kind == KeyEventKind::Repeat)
     }
+
+    /// Like [`Self::is_press`], but tolerant of terminals that report shifted
+    /// characters with or without the shift modifier: `G` matches both.
+    pub(crate) fn matches(&self, event: &KeyEvent) -> bool {
+        if self.key != event.code
+            || !(event.kind == KeyEventKind::Press || event.kind == KeyEventKind::Repeat)
+        {
+            return false;
+        }
+        match self.key {
+            KeyCode::Char(_) => {
+                self.modifiers.difference(KeyModifiers::SHIFT)
+                    == event.modifiers.difference(KeyModifiers::SHIFT)
+            }
+            _ => self.modifiers == event.modifiers,
+        }
+    }
+}
+
+/// Parses chords written as `ctrl+t`, `alt+shift+left`, `pgdn` or `G`.
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    fn from_str(chord: &str) -> Result<Self, Self::Err> {
+        let chord = chord.trim();
+        let (modifier_part, key_part) = if chord == "+" {
+            ("", "+")
+        } else if let Some(prefix) = chord.strip_suffix("++") {
+            (prefix, "+")
+        } else {
+            chord.rsplit_once('+').unwrap_or(("", chord))
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in modifier_part.split('+').map(str::trim) {
+            match modifier.to_ascii_lowercase().as_str() {
+                "" => {}
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" | "option" | "meta" => modifiers |= KeyModifiers::ALT,
+                "shift" => modifiers |= KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier `{modifier}` in `{chord}`")),
+            }
+        }
+
+        let key_part = key_part.trim();
+        let mut chars = key_part.chars();
+        let key = match (chars.next(), chars.next()) {
+            (Some(c), None) => {
+                // Shifted letters are matched by their uppercase character.
+                if c.is_ascii_alphabetic() && modifiers.contains(KeyModifiers::SHIFT) {
+                    modifiers.remove(KeyModifiers::SHIFT);
+                    KeyCode::Char(c.to_ascii_uppercase())
+                } else {
+                    KeyCode::Char(c)
+                }
+            }
+            _ => match key_part.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pgup" | "pageup" => KeyCode::PageUp,
+                "pgdn" | "pagedown" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "space" => KeyCode::Char(' '),
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=24) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key `{key_part}` in `{chord}`")),
+                },
+            },
+        };
+        Ok(Self::new(key, modifiers))
+    }
 }
 
 pub(crate) const fn plain(key: KeyCode) -> KeyBinding {
@@ -80,6 +161,8 @@ impl From<&KeyBinding> for Span<'static> {
             KeyCode::Right => "→".to_string(),
             KeyCode::PageUp => "pgup".to_string(),
             KeyCode::PageDown => "pgdn".to_string(),
+            KeyCode::Char(' ') => "space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
             _ => format!("{key}").to_ascii_lowercase(),
         };
         Span::styled(format!("{modifiers}{key}"), key_hint_style())
@@ -87,7 +170,7 @@ impl From<&KeyBinding> for Span<'static> {
 }
 
 fn key_hint_style() -> Style {
-    Style::default().dim()
+    crate::theme::theme().key_hint
 }
 
 pub(crate) fn has_ctrl_or_alt(mods: KeyModifiers) -> bool {
@@ -0,0 +1,477 @@
+//! Named TUI actions and the key chords that trigger them.
+//!
+//! Defaults live in [`KeyAction::default_chords`]; `[tui.keymap]` replaces
+//! the chords of individual actions. Bindings are validated once at startup
+//! and installed globally so widgets can look them up without threading the
+//! config through every constructor.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use codex_core::config::types::KeyChords;
+use crossterm::event::KeyEvent;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use strum::IntoEnumIterator;
+use strum_macros::AsRefStr;
+use strum_macros::EnumIter;
+use strum_macros::EnumString;
+use strum_macros::IntoStaticStr;
+
+use crate::key_hint::KeyBinding;
+use crate::theme::theme;
+
+/// Where an action is handled. Actions only conflict when their scopes can
+/// receive the same key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter)]
+pub(crate) enum KeyScope {
+    /// Handled by the app before any widget sees the key.
+    Global,
+    /// The prompt composer.
+    Composer,
+    /// Esc-Esc backtracking over the conversation.
+    Backtrack,
+    /// The session bar while it has focus.
+    Sessions,
+    /// Full-screen pagers such as the transcript.
+    Pager,
+}
+
+impl KeyScope {
+    fn title(self) -> &'static str {
+        match self {
+            Self::Global => "Global",
+            Self::Composer => "Composer",
+            Self::Backtrack => "Backtrack",
+            Self::Sessions => "Session bar",
+            Self::Pager => "Pager",
+        }
+    }
+
+    /// Global chords are checked before the composer and session bar get a
+    /// chance to handle the key, so they shadow bindings in those scopes.
+    fn overlaps(self, other: Self) -> bool {
+        self == other
+            || matches!(
+                (self, other),
+                (Self::Global, Self::Composer | Self::Sessions)
+                    | (Self::Composer | Self::Sessions, Self::Global)
+            )
+    }
+}
+
+/// Actions that can be rebound from `[tui.keymap]`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    EnumString,
+    AsRefStr,
+    IntoStaticStr,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub(crate) enum KeyAction {
+    OpenTranscript,
+    FocusSessions,
+    GitGraph,
+    SessionPicker,
+    ComposerSubmit,
+    ComposerHistoryPrevious,
+    ComposerHistoryNext,
+    ComposerShortcuts,
+    ComposerExit,
+    Backtrack,
+    ConfirmBacktrack,
+    SessionPrevious,
+    SessionNext,
+    SessionNew,
+    SessionOpen,
+    SessionRename,
+    SessionDelete,
+    SessionExit,
+    PagerClose,
+    PagerUp,
+    PagerDown,
+    PagerLeft,
+    PagerRight,
+    PagerPageUp,
+    PagerPageDown,
+    PagerTop,
+    PagerBottom,
+    PagerSearch,
+    PagerNextMatch,
+    PagerPreviousMatch,
+}
+
+impl KeyAction {
+    /// Name used in `[tui.keymap]`.
+    pub(crate) fn name(self) -> &'static str {
+        self.into()
+    }
+
+    pub(crate) fn scope(self) -> KeyScope {
+        match self {
+            Self::OpenTranscript | Self::FocusSessions | Self::GitGraph | Self::SessionPicker => {
+                KeyScope::Global
+            }
+            Self::ComposerSubmit
+            | Self::ComposerHistoryPrevious
+            | Self::ComposerHistoryNext
+            | Self::ComposerShortcuts
+            | Self::ComposerExit => KeyScope::Composer,
+            Self::Backtrack | Self::ConfirmBacktrack => KeyScope::Backtrack,
+            Self::SessionPrevious
+            | Self::SessionNext
+            | Self::SessionNew
+            | Self::SessionOpen
+            | Self::SessionRename
+            | Self::SessionDelete
+            | Self::SessionExit => KeyScope::Sessions,
+            Self::PagerClose
+            | Self::PagerUp
+            | Self::PagerDown
+            | Self::PagerLeft
+            | Self::PagerRight
+            | Self::PagerPageUp
+            | Self::PagerPageDown
+            | Self::PagerTop
+            | Self::PagerBottom
+            | Self::PagerSearch
+            | Self::PagerNextMatch
+            | Self::PagerPreviousMatch => KeyScope::Pager,
+        }
+    }
+
+    /// User-visible description shown in `/keys`.
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            Self::OpenTranscript => "open the transcript",
+            Self::FocusSessions => "focus the session bar",
+            Self::GitGraph => "show the git graph",
+            Self::SessionPicker => "open the session picker",
+            Self::ComposerSubmit => "send the message",
+            Self::ComposerHistoryPrevious => "previous prompt from history",
+            Self::ComposerHistoryNext => "next prompt from history",
+            Self::ComposerShortcuts => "toggle the shortcut list (empty composer)",
+            Self::ComposerExit => "exit Codex (empty composer)",
+            Self::Backtrack => "edit a previous message (press twice)",
+            Self::ConfirmBacktrack => "fork from the selected message",
+            Self::SessionPrevious => "select the previous session",
+            Self::SessionNext => "select the next session",
+            Self::SessionNew => "start a new session",
+            Self::SessionOpen => "open the selected session",
+            Self::SessionRename => "rename the selected session",
+            Self::SessionDelete => "delete the selected session",
+            Self::SessionExit => "return to the chat",
+            Self::PagerClose => "close",
+            Self::PagerUp => "scroll up",
+            Self::PagerDown => "scroll down",
+            Self::PagerLeft => "scroll left",
+            Self::PagerRight => "scroll right",
+            Self::PagerPageUp => "page up",
+            Self::PagerPageDown => "page down",
+            Self::PagerTop => "jump to the top",
+            Self::PagerBottom => "jump to the bottom",
+            Self::PagerSearch => "search",
+            Self::PagerNextMatch => "next search match",
+            Self::PagerPreviousMatch => "previous search match",
+        }
+    }
+
+    fn default_chords(self) -> &'static [&'static str] {
+        match self {
+            Self::OpenTranscript => &["ctrl+t"],
+            Self::FocusSessions => &["ctrl+p"],
+            Self::GitGraph => &["ctrl+g"],
+            Self::SessionPicker => &["ctrl+x", "ctrl+q"],
+            Self::ComposerSubmit => &["enter"],
+            Self::ComposerHistoryPrevious => &["up"],
+            Self::ComposerHistoryNext => &["down", "ctrl+n"],
+            Self::ComposerShortcuts => &["?"],
+            Self::ComposerExit => &["ctrl+d"],
+            Self::Backtrack => &["esc"],
+            Self::ConfirmBacktrack => &["enter"],
+            Self::SessionPrevious => &["left", "h"],
+            Self::SessionNext => &["right", "l"],
+            Self::SessionNew => &["n"],
+            Self::SessionOpen => &["enter"],
+            Self::SessionRename => &["r"],
+            Self::SessionDelete => &["x"],
+            Self::SessionExit => &["esc"],
+            Self::PagerClose => &["q", "ctrl+c"],
+            Self::PagerUp => &["up", "k"],
+            Self::PagerDown => &["down", "j"],
+            Self::PagerLeft => &["h"],
+            Self::PagerRight => &["l"],
+            Self::PagerPageUp => &["pgup"],
+            Self::PagerPageDown => &["pgdn", "space"],
+            Self::PagerTop => &["home"],
+            Self::PagerBottom => &["end"],
+            Self::PagerSearch => &["/"],
+            Self::PagerNextMatch => &["n"],
+            Self::PagerPreviousMatch => &["N"],
+        }
+    }
+}
+
+/// Resolved bindings for every [`KeyAction`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Keymap {
+    bindings: BTreeMap<KeyAction, Vec<KeyBinding>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = KeyAction::iter()
+            .map(|action| {
+                let chords = action
+                    .default_chords()
+                    .iter()
+                    .filter_map(|chord| chord.parse().ok())
+                    .collect();
+                (action, chords)
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Applies `[tui.keymap]` on top of the defaults.
+    ///
+    /// Unknown actions and unparsable chords are skipped. An override that
+    /// collides with another action in an overlapping scope is dropped and
+    /// the action keeps its default chords. Every problem is reported in the
+    /// returned warnings.
+    pub(crate) fn from_overrides(overrides: &BTreeMap<String, KeyChords>) -> (Self, Vec<String>) {
+        let defaults = Self::default();
+        let mut keymap = defaults.clone();
+        let mut warnings = Vec::new();
+        let mut overridden = Vec::new();
+
+        for (name, chords) in overrides {
+            let Ok(action) = name.parse::<KeyAction>() else {
+                warnings.push(format!("[tui.keymap] unknown action `{name}`"));
+                continue;
+            };
+            let mut bindings = Vec::new();
+            for chord in chords.chords() {
+                match chord.parse::<KeyBinding>() {
+                    Ok(binding) => bindings.push(binding),
+                    Err(err) => warnings.push(format!("[tui.keymap] {name}: {err}")),
+                }
+            }
+            keymap.bindings.insert(action, bindings);
+            overridden.push(action);
+        }
+
+        for action in overridden {
+            let conflicts = keymap.conflicts_for(action);
+            if !conflicts.is_empty() {
+                let others = conflicts
+                    .iter()
+                    .map(|other| format!("`{}`", other.name()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                warnings.push(format!(
+                    "[tui.keymap] {}: conflicts with {others}; keeping the default keys",
+                    action.name()
+                ));
+                keymap
+                    .bindings
+                    .insert(action, defaults.bindings_for(action).to_vec());
+            }
+        }
+
+        (keymap, warnings)
+    }
+
+    pub(crate) fn bindings_for(&self, action: KeyAction) -> &[KeyBinding] {
+        self.bindings.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Returns true if `event` is one of the chords bound to `action`.
+    pub(crate) fn is_pressed(&self, action: KeyAction, event: &KeyEvent) -> bool {
+        self.bindings_for(action)
+            .iter()
+            .any(|binding| binding.matches(event))
+    }
+
+    /// Returns the action in `scope` bound to `event`, if any.
+    pub(crate) fn action_for(&self, scope: KeyScope, event: &KeyEvent) -> Option<KeyAction> {
+        self.bindings
+            .iter()
+            .filter(|(action, _)| action.scope() == scope)
+            .find(|(_, bindings)| bindings.iter().any(|binding| binding.matches(event)))
+            .map(|(action, _)| *action)
+    }
+
+    /// Other actions sharing a chord with `action` in an overlapping scope.
+    fn conflicts_for(&self, action: KeyAction) -> Vec<KeyAction> {
+        let bindings = self.bindings_for(action);
+        self.bindings
+            .iter()
+            .filter(|(other, other_bindings)| {
+                **other != action
+                    && action.scope().overlaps(other.scope())
+                    && other_bindings.iter().any(|b| bindings.contains(b))
+            })
+            .map(|(other, _)| *other)
+            .collect()
+    }
+
+    /// First chord bound to `action` as a key hint span.
+    pub(crate) fn hint(&self, action: KeyAction) -> Span<'static> {
+        self.bindings_for(action)
+            .first()
+            .map(Span::from)
+            .unwrap_or_else(|| "unbound".dim())
+    }
+
+    /// Lines for the `/keys` help view, grouped by scope.
+    pub(crate) fn help_lines(&self) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line<'static>> = vec!["/keys".fg(theme().highlight).into(), "".into()];
+        for scope in KeyScope::iter() {
+            lines.push(scope.title().bold().into());
+            for (action, bindings) in self.bindings.iter().filter(|(a, _)| a.scope() == scope) {
+                let mut spans: Vec<Span<'static>> = vec!["  ".into()];
+                if bindings.is_empty() {
+                    spans.push("unbound".dim());
+                }
+                for (i, binding) in bindings.iter().enumerate() {
+                    if i > 0 {
+                        spans.push(" / ".dim());
+                    }
+                    spans.push(binding.into());
+                }
+                spans.push("  ".into());
+                spans.push(action.description().into());
+                spans.push(format!(" ({})", action.name()).dim());
+                lines.push(spans.into());
+            }
+            lines.push("".into());
+        }
+        lines.push(
+            "Rebind keys in the [tui.keymap] section of config.toml."
+                .dim()
+                .into(),
+        );
+        lines
+    }
+}
+
+static KEYMAP: OnceLock<Keymap> = OnceLock::new();
+
+/// Installs the keymap built from `[tui.keymap]` and returns any problems
+/// found in it. Only the first call has an effect.
+pub(crate) fn init(overrides: &BTreeMap<String, KeyChords>) -> Vec<String> {
+    let (keymap, warnings) = Keymap::from_overrides(overrides);
+    let _ = KEYMAP.set(keymap);
+    warnings
+}
+
+/// The active keymap; the defaults until [`init`] runs.
+pub(crate) fn keymap() -> &'static Keymap {
+    KEYMAP.get_or_init(Keymap::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn defaults_parse_and_do_not_conflict() {
+        let keymap = Keymap::default();
+        for action in KeyAction::iter() {
+            assert_eq!(
+                keymap.bindings_for(action).len(),
+                action.default_chords().len(),
+                "{action:?} has an unparsable default chord"
+            );
+            assert_eq!(keymap.conflicts_for(action), Vec::<KeyAction>::new());
+        }
+    }
+
+    #[test]
+    fn overrides_replace_default_chords() {
+        let overrides = BTreeMap::from([(
+            "open-transcript".to_string(),
+            KeyChords::Many(vec!["ctrl+o".to_string(), "f2".to_string()]),
+        )]);
+        let (keymap, warnings) = Keymap::from_overrides(&overrides);
+
+        assert_eq!(warnings, Vec::<String>::new());
+        let open = KeyAction::OpenTranscript;
+        assert!(keymap.is_pressed(open, &press(KeyCode::Char('o'), KeyModifiers::CONTROL)));
+        assert!(keymap.is_pressed(open, &press(KeyCode::F(2), KeyModifiers::NONE)));
+        assert!(!keymap.is_pressed(open, &press(KeyCode::Char('t'), KeyModifiers::CONTROL)));
+    }
+
+    #[test]
+    fn conflicting_override_keeps_defaults_and_warns() {
+        let overrides = BTreeMap::from([
+            (
+                "git-graph".to_string(),
+                KeyChords::One("ctrl+t".to_string()),
+            ),
+            (
+                "no-such-action".to_string(),
+                KeyChords::One("x".to_string()),
+            ),
+        ]);
+        let (keymap, warnings) = Keymap::from_overrides(&overrides);
+
+        assert_eq!(
+            warnings,
+            vec![
+                "[tui.keymap] unknown action `no-such-action`".to_string(),
+                "[tui.keymap] git-graph: conflicts with `open-transcript`; keeping the default keys"
+                    .to_string(),
+            ]
+        );
+        assert!(keymap.is_pressed(
+            KeyAction::GitGraph,
+            &press(KeyCode::Char('g'), KeyModifiers::CONTROL)
+        ));
+    }
+
+    #[test]
+    fn same_chord_in_disjoint_scopes_is_allowed() {
+        let overrides =
+            BTreeMap::from([("pager-close".to_string(), KeyChords::One("esc".to_string()))]);
+        let (_, warnings) = Keymap::from_overrides(&overrides);
+        assert_eq!(warnings, Vec::<String>::new());
+    }
+
+    #[test]
+    fn shifted_letters_match_with_or_without_shift() {
+        let keymap = Keymap::default();
+        let previous = KeyAction::PagerPreviousMatch;
+        assert!(keymap.is_pressed(previous, &press(KeyCode::Char('N'), KeyModifiers::SHIFT)));
+        assert!(keymap.is_pressed(previous, &press(KeyCode::Char('N'), KeyModifiers::NONE)));
+        assert_eq!(
+            keymap.action_for(
+                KeyScope::Pager,
+                &press(KeyCode::Char('n'), KeyModifiers::NONE)
+            ),
+            Some(KeyAction::PagerNextMatch)
+        );
+        assert_eq!(
+            "shift+n".parse::<KeyBinding>(),
+            Ok(KeyBinding::new(KeyCode::Char('N'), KeyModifiers::NONE))
+        );
+    }
+}
@@ -53,6 +53,7 @@ mod cxresume_picker_widget;
 mod diff_render;
 mod exec_cell;
 mod exec_command;
+mod external_editor;
 mod file_search;
 mod frames;
 mod get_git_diff;
@@ -60,6 +61,7 @@ mod git_graph_widget;
 mod history_cell;
 pub mod insert_history;
 mod key_hint;
+mod keymap;
 pub mod live_wrap;
 mod markdown;
 mod markdown_render;
@@ -83,6 +85,7 @@ mod streaming;
 mod style;
 mod terminal_palette;
 mod text_formatting;
+mod theme;
 mod tooltips;
 mod tui;
 mod ui_consts;
@@ -366,6 +369,11 @@ async fn run_ratatui_app(
 ) -> color_eyre::Result<AppExitInfo> {
     color_eyre::install()?;
     let mut global_codex_home = global_codex_home;
+
+    // Install the keymap and theme before anything renders so every screen,
+    // onboarding included, uses them.
+    let mut config_warnings = keymap::init(&initial_config.tui_keymap);
+    config_warnings.extend(theme::init(&initial_config.tui_theme));
     let mut allowed_agents = allowed_agents;
 
     // Forward panic reports through tracing so they appear in the UI status
@@ -405,12 +413,9 @@ async fn run_ratatui_app(
 
     // Initialize high-fidelity session event logging if enabled.
     session_log::maybe_init(&initial_config);
+    render::highlight::init_syntax_highlighting(initial_config.tui_syntax_highlighting);
 
-    let mut auth_manager = AuthManager::shared(
-        initial_config.codex_home.clone(),
-        false,
-        initial_config.cli_auth_credentials_store_mode,
-    );
+    let mut auth_manager = AuthManager::shared_from_config(&initial_config, false);
     let login_status = get_login_status(&initial_config);
     let should_show_trust_screen = should_show_trust_screen(&initial_config);
     let should_show_onboarding =
@@ -555,6 +560,7 @@ async fn run_ratatui_app(
         images,
         resume_selection,
         feedback,
+        config_warnings,
     )
     .await;
 
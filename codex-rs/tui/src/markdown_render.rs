@@ -1,3 +1,5 @@
+use crate::render::highlight::SyntaxLanguage;
+use crate::render::highlight::highlight_code_to_lines;
 use crate::render::line_utils::line_to_static;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
@@ -35,6 +37,7 @@ impl Default for MarkdownStyles {
     fn default() -> Self {
         use ratatui::style::Stylize;
 
+        let theme = crate::theme::theme();
         Self {
             h1: Style::new().bold().underlined(),
             h2: Style::new().bold(),
@@ -42,14 +45,14 @@ impl Default for MarkdownStyles {
             h4: Style::new().italic(),
             h5: Style::new().italic(),
             h6: Style::new().italic(),
-            code: Style::new().cyan(),
+            code: Style::new().fg(theme.accent),
             emphasis: Style::new().italic(),
             strong: Style::new().bold(),
             strikethrough: Style::new().crossed_out(),
-            ordered_list_marker: Style::new().light_blue(),
+            ordered_list_marker: Style::new().fg(theme.list_marker),
             unordered_list_marker: Style::new(),
-            link: Style::new().cyan().underlined(),
-            blockquote: Style::new().green(),
+            link: Style::new().fg(theme.accent).underlined(),
+            blockquote: Style::new().fg(theme.quote),
         }
     }
 }
@@ -99,6 +102,9 @@ where
     pending_marker_line: bool,
     in_paragraph: bool,
     in_code_block: bool,
+    /// Source of the current fenced code block, collected until the fence
+    /// closes so it can be highlighted as a whole.
+    highlighted_code_block: Option<(SyntaxLanguage, String)>,
     wrap_width: Option<usize>,
     current_line_content: Option<Line<'static>>,
     current_initial_indent: Vec<Span<'static>>,
@@ -124,6 +130,7 @@ where
             pending_marker_line: false,
             in_paragraph: false,
             in_code_block: false,
+            highlighted_code_block: None,
             wrap_width,
             current_line_content: None,
             current_initial_indent: Vec::new(),
@@ -274,6 +281,10 @@ where
     }
 
     fn text(&mut self, text: CowStr<'a>) {
+        if let Some((_, code)) = self.highlighted_code_block.as_mut() {
+            code.push_str(&text);
+            return;
+        }
         if self.pending_marker_line {
             self.push_line(Line::default());
         }
@@ -394,12 +405,16 @@ where
         self.needs_newline = false;
     }
 
-    fn start_codeblock(&mut self, _lang: Option<String>, indent: Option<Span<'static>>) {
+    fn start_codeblock(&mut self, lang: Option<String>, indent: Option<Span<'static>>) {
         self.flush_current_line();
         if !self.text.lines.is_empty() {
             self.push_blank_line();
         }
         self.in_code_block = true;
+        self.highlighted_code_block = lang
+            .as_deref()
+            .and_then(SyntaxLanguage::from_fence)
+            .map(|language| (language, String::new()));
         self.indent_stack.push(IndentContext::new(
             vec![indent.unwrap_or_default()],
             None,
@@ -409,11 +424,39 @@ where
     }
 
     fn end_codeblock(&mut self) {
+        if let Some((language, code)) = self.highlighted_code_block.take()
+            && !code.is_empty()
+        {
+            match highlight_code_to_lines(code.trim_end_matches('\n'), language) {
+                Some(lines) => self.push_code_lines(lines),
+                None => self.text(code.into()),
+            }
+        }
         self.needs_newline = true;
         self.in_code_block = false;
         self.indent_stack.pop();
     }
 
+    fn push_code_lines(&mut self, lines: Vec<Line<'static>>) {
+        if self.pending_marker_line {
+            self.push_line(Line::default());
+        }
+        self.pending_marker_line = false;
+        for (i, line) in lines.into_iter().enumerate() {
+            if self.needs_newline {
+                self.push_line(Line::default());
+                self.needs_newline = false;
+            }
+            if i > 0 {
+                self.push_line(Line::default());
+            }
+            for span in line.spans {
+                self.push_span(span);
+            }
+        }
+        self.needs_newline = false;
+    }
+
     fn push_inline_style(&mut self, style: Style) {
         let current = self.inline_styles.last().copied().unwrap_or_default();
         let merged = current.patch(style);
@@ -653,11 +653,34 @@ fn link() {
 
 #[test]
 fn code_block_unhighlighted() {
-    let text = render_markdown_text("```rust\nfn main() {}\n```\n");
+    let text = render_markdown_text("```text\nfn main() {}\n```\n");
     let expected = Text::from_iter([Line::from_iter(["", "fn main() {}"])]);
     assert_eq!(text, expected);
 }
 
+#[test]
+fn code_block_highlighted_from_fence_language() {
+    let text = render_markdown_text("```rust\nfn main() {\n    // hi\n}\n```\n");
+    let lines: Vec<String> = text
+        .lines
+        .iter()
+        .map(|l| {
+            l.spans
+                .iter()
+                .map(|s| s.content.clone())
+                .collect::<String>()
+        })
+        .collect();
+    assert_eq!(lines, vec!["fn main() {", "    // hi", "}"]);
+
+    let keyword = text.lines[0]
+        .spans
+        .iter()
+        .find(|span| span.content == "fn")
+        .expect("keyword span");
+    assert!(keyword.style.fg.is_some());
+}
+
 #[test]
 fn code_block_multiple_lines_root() {
     let md = "```\nfirst\nsecond\n```\n";
@@ -1,6 +1,7 @@
 use std::io;
 use std::sync::LazyLock;
 
+use crate::theme::theme;
 use codex_core::DEFAULT_LMSTUDIO_PORT;
 use codex_core::DEFAULT_OLLAMA_PORT;
 use codex_core::LMSTUDIO_OSS_PROVIDER_ID;
@@ -239,7 +240,7 @@ impl WidgetRef for &OssSelectionWidget<'_> {
             .enumerate()
             .map(|(idx, opt)| {
                 let style = if idx == self.selected_option {
-                    Style::new().bg(Color::Cyan).fg(Color::Black)
+                    Style::new().bg(theme().accent).fg(Color::Black)
                 } else {
                     Style::new().bg(Color::DarkGray)
                 };
@@ -277,8 +278,8 @@ impl WidgetRef for &OssSelectionWidget<'_> {
 
 fn get_status_symbol_and_color(status: &ProviderStatus) -> (&'static str, Color) {
     match status {
-        ProviderStatus::Running => ("●", Color::Green),
-        ProviderStatus::NotRunning => ("○", Color::Red),
+        ProviderStatus::Running => ("●", theme().success),
+        ProviderStatus::NotRunning => ("○", theme().error),
         ProviderStatus::Unknown => ("?", Color::Yellow),
     }
 }
@@ -1,19 +1,30 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::io::Result;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 
 use crate::cxresume_picker_widget::SessionInfo;
+use crate::diff_render::create_diff_summary;
+use crate::diff_render::display_path_for;
 use crate::history_cell::HistoryCell;
+use crate::keymap::KeyAction;
+use crate::keymap::KeyScope;
+use crate::keymap::keymap;
 use crate::render::line_utils::push_owned_lines;
+use crate::theme::theme;
 use crate::tui;
 use crate::tui::TuiEvent;
+use codex_core::protocol::CheckpointRestorePath;
+use codex_core::protocol::CheckpointSummary;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Styled;
 use ratatui::style::Stylize;
@@ -29,6 +40,7 @@ pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
     SessionPicker(Box<SessionPickerOverlay>),
+    Checkpoints(Box<CheckpointOverlay>),
 }
 
 /// Session picker overlay integrating PickerState for interactive navigation
@@ -44,6 +56,10 @@ impl Overlay {
         Self::Transcript(TranscriptOverlay::new(cells))
     }
 
+    pub(crate) fn new_checkpoints(checkpoints: Vec<CheckpointSummary>, cwd: PathBuf) -> Self {
+        Self::Checkpoints(Box::new(CheckpointOverlay::new(checkpoints, cwd)))
+    }
+
     pub(crate) fn new_static_with_title(lines: Vec<Line<'static>>, title: String) -> Self {
         Self::Static(StaticOverlay::with_title(lines, title))
     }
@@ -139,6 +155,7 @@ impl Overlay {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
             Overlay::SessionPicker(o) => o.handle_event(tui, event),
+            Overlay::Checkpoints(o) => o.handle_event(tui, event),
         }
     }
 
@@ -147,6 +164,7 @@ impl Overlay {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
             Overlay::SessionPicker(o) => o.is_done(),
+            Overlay::Checkpoints(o) => o.is_done(),
         }
     }
 
@@ -184,7 +202,7 @@ const PAGER_KEY_HINTS: &[(&str, &str)] = &[
 
 // Render a single line of key hints from (key, description) pairs.
 fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&str, &str)]) {
-    let key_hint_style = Style::default().fg(Color::Cyan);
+    let key_hint_style = Style::default().fg(theme().accent);
     let mut spans: Vec<Span<'static>> = vec![" ".into()];
     let mut first = true;
     for (key, desc) in pairs {
@@ -199,6 +217,15 @@ fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&str, &str)]) {
     Paragraph::new(vec![Line::from(spans).dim()]).render_ref(area, buf);
 }
 
+// Plain characters are search input while the search prompt is open, even
+// when they are bound to a pager action.
+fn is_plain_char(key_event: &KeyEvent) -> bool {
+    matches!(key_event.code, KeyCode::Char(_))
+        && !key_event
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+}
+
 /// Generic widget for rendering a pager view.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum WrapMode {
@@ -415,7 +442,7 @@ impl PagerView {
             let w = (prompt.chars().count() as u16).min(max_w);
             if w > 0 {
                 Span::from(prompt)
-                    .cyan()
+                    .fg(theme().accent)
                     .render_ref(Rect::new(sep_rect.x + 1, sep_rect.y, w, 1), buf);
             }
         }
@@ -485,25 +512,18 @@ impl PagerView {
             }
         }
 
+        // Rebindable actions take precedence over the fixed vim-style keys below.
+        let action = keymap().action_for(KeyScope::Pager, &key_event);
         match key_event {
             // Ignore Enter/Esc for mode toggling; commit mode is active by default in Git Graph overlay
             KeyEvent {
                 code: KeyCode::Enter | KeyCode::Esc,
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
                 ..
-            } => {
+            } if action.is_none() => {
                 return Ok(());
             }
-            KeyEvent {
-                code: KeyCode::Up,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            }
-            | KeyEvent {
-                code: KeyCode::Char('k'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            _ if action == Some(KeyAction::PagerUp) => {
                 if self.commit_mode {
                     self.move_commit_vertical(-1, area.width, area.height);
                 } else {
@@ -514,16 +534,7 @@ impl PagerView {
                 }
                 self.g_pending = false;
             }
-            KeyEvent {
-                code: KeyCode::Down,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            }
-            | KeyEvent {
-                code: KeyCode::Char('j'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            _ if action == Some(KeyAction::PagerDown) => {
                 if self.commit_mode {
                     self.move_commit_vertical(1, area.width, area.height);
                 } else {
@@ -537,22 +548,14 @@ impl PagerView {
                 self.g_pending = false;
             }
             // Horizontal scroll or commit-branch navigation
-            KeyEvent {
-                code: KeyCode::Char('h'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            _ if action == Some(KeyAction::PagerLeft) => {
                 if self.commit_mode {
                     self.move_commit_horizontal(-1, area.width, area.height);
                 } else {
                     self.horiz_offset = self.horiz_offset.saturating_sub(1);
                 }
             }
-            KeyEvent {
-                code: KeyCode::Char('l'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            _ if action == Some(KeyAction::PagerRight) => {
                 if self.commit_mode {
                     self.move_commit_horizontal(1, area.width, area.height);
                 } else {
@@ -563,7 +566,7 @@ impl PagerView {
                 code: KeyCode::Char('0'),
                 kind: KeyEventKind::Press,
                 ..
-            } => {
+            } if action.is_none() => {
                 self.horiz_offset = 0;
             }
             // Vim-like jumps: gg to top, G to bottom
@@ -571,7 +574,7 @@ impl PagerView {
                 code: KeyCode::Char('g'),
                 kind: KeyEventKind::Press,
                 ..
-            } => {
+            } if action.is_none() => {
                 if self.g_pending {
                     self.g_pending = false;
                     // Auto-activate commit mode and jump to first commit
@@ -602,7 +605,7 @@ impl PagerView {
                 code: KeyCode::Char('G'),
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
                 ..
-            } => {
+            } if action.is_none() => {
                 self.g_pending = false;
                 if let Some(cache) = self.wrap_cache.as_ref()
                     && !cache.wrapped.is_empty()
@@ -628,11 +631,7 @@ impl PagerView {
                     }
                 }
             }
-            KeyEvent {
-                code: KeyCode::PageUp,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            _ if action == Some(KeyAction::PagerPageUp) => {
                 let page = area.height as usize;
                 if self.commit_mode {
                     self.move_to_nearby_commit(-(page as isize) as i32, 0, area.width, area.height);
@@ -642,11 +641,7 @@ impl PagerView {
                 }
                 self.g_pending = false;
             }
-            KeyEvent {
-                code: KeyCode::PageDown | KeyCode::Char(' '),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            _ if action == Some(KeyAction::PagerPageDown) => {
                 if let Some(cache) = self.wrap_cache.as_ref() {
                     let page = area.height as usize;
                     let last = cache.wrapped.len().saturating_sub(1);
@@ -659,22 +654,14 @@ impl PagerView {
                 }
                 self.g_pending = false;
             }
-            KeyEvent {
-                code: KeyCode::Home,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            _ if action == Some(KeyAction::PagerTop) => {
                 self.cursor_idx = 0;
                 self.commit_cursor_line = None;
                 self.commit_mode = false;
                 self.ensure_cursor_visible(area.height as usize);
                 self.g_pending = false;
             }
-            KeyEvent {
-                code: KeyCode::End,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            _ if action == Some(KeyAction::PagerBottom) => {
                 if let Some(cache) = self.wrap_cache.as_ref()
                     && !cache.wrapped.is_empty()
                 {
@@ -686,19 +673,11 @@ impl PagerView {
                 self.g_pending = false;
             }
             // Enter search mode with '/'; then 'Enter' to confirm; 'n'/'N' to navigate.
-            KeyEvent {
-                code: KeyCode::Char('/'),
-                kind: KeyEventKind::Press,
-                ..
-            } => {
+            _ if action == Some(KeyAction::PagerSearch) => {
                 self.g_pending = false;
                 self.search_input = Some(String::new());
             }
-            KeyEvent {
-                code: KeyCode::Char('n'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            _ if action == Some(KeyAction::PagerNextMatch) => {
                 if let Some(q) = self.last_search.clone() {
                     let start = self
                         .last_match_idx
@@ -712,11 +691,7 @@ impl PagerView {
                 }
                 self.g_pending = false;
             }
-            KeyEvent {
-                code: KeyCode::Char('N'),
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            _ if action == Some(KeyAction::PagerPreviousMatch) => {
                 if let Some(q) = self.last_search.clone() {
                     let start = self.last_match_idx.unwrap_or(self.scroll_offset);
                     if let Some(idx) = self.find_prev_match(&q, start) {
@@ -1579,31 +1554,13 @@ impl TranscriptOverlay {
         match event {
             TuiEvent::Key(key_event) => match key_event {
                 KeyEvent {
-                    code: KeyCode::Char('q'),
-                    kind: KeyEventKind::Press,
-                    ..
-                }
-                | KeyEvent {
-                    code: KeyCode::Char('t'),
-                    modifiers: crossterm::event::KeyModifiers::CONTROL,
                     kind: KeyEventKind::Press,
                     ..
-                }
-                | KeyEvent {
-                    code: KeyCode::Char('c'),
-                    modifiers: crossterm::event::KeyModifiers::CONTROL,
-                    kind: KeyEventKind::Press,
-                    ..
-                } => {
-                    // Don't treat plain 'q' as quit when in search input mode; forward to view.
-                    if matches!(
-                        key_event,
-                        KeyEvent {
-                            code: KeyCode::Char('q'),
-                            ..
-                        }
-                    ) && self.view.search_input.is_some()
-                    {
+                } if keymap().is_pressed(KeyAction::PagerClose, &key_event)
+                    || keymap().is_pressed(KeyAction::OpenTranscript, &key_event) =>
+                {
+                    // Don't treat plain characters as quit when in search input mode; forward to view.
+                    if is_plain_char(&key_event) && self.view.search_input.is_some() {
                         self.view.handle_key_event(tui, key_event)
                     } else {
                         self.is_done = true;
@@ -1756,7 +1713,7 @@ impl StaticOverlay {
                     let error_text = vec![Text::from(vec![
                         Line::from(""),
                         Line::from(""),
-                        Line::from(format!("  Failed to refresh: {e}").red()),
+                        Line::from(format!("  Failed to refresh: {e}").fg(theme().error)),
                     ])];
                     self.view.texts = error_text;
                     self.view.wrap_cache = None;
@@ -1809,25 +1766,11 @@ impl StaticOverlay {
                     Ok(())
                 }
                 KeyEvent {
-                    code: KeyCode::Char('q'),
-                    kind: KeyEventKind::Press,
-                    ..
-                }
-                | KeyEvent {
-                    code: KeyCode::Char('c'),
-                    modifiers: crossterm::event::KeyModifiers::CONTROL,
                     kind: KeyEventKind::Press,
                     ..
-                } => {
-                    // When search input is active, treat 'q' as input, not quit.
-                    if matches!(
-                        key_event,
-                        KeyEvent {
-                            code: KeyCode::Char('q'),
-                            ..
-                        }
-                    ) && self.view.search_input.is_some()
-                    {
+                } if keymap().is_pressed(KeyAction::PagerClose, &key_event) => {
+                    // When search input is active, treat plain characters as input, not quit.
+                    if is_plain_char(&key_event) && self.view.search_input.is_some() {
                         self.view.handle_key_event(tui, key_event)
                     } else {
                         self.is_done = true;
@@ -1932,6 +1875,243 @@ impl SessionPickerOverlay {
     }
 }
 
+/// Browses the checkpoint timeline from `Op::ListCheckpoints`, previews the
+/// changes made since each checkpoint and picks files to roll back.
+pub(crate) struct CheckpointOverlay {
+    checkpoints: Vec<CheckpointSummary>,
+    cwd: PathBuf,
+    selected: usize,
+    files: Vec<PathBuf>,
+    file_cursor: usize,
+    marked: HashSet<PathBuf>,
+    view: PagerView,
+    /// Width the diff in `view` was rendered for.
+    diff_width: Option<u16>,
+    restore_request: Option<(String, Vec<CheckpointRestorePath>)>,
+    is_done: bool,
+}
+
+impl CheckpointOverlay {
+    pub(crate) fn new(checkpoints: Vec<CheckpointSummary>, cwd: PathBuf) -> Self {
+        let selected = checkpoints.len().saturating_sub(1);
+        let mut overlay = Self {
+            checkpoints,
+            cwd,
+            selected,
+            files: Vec::new(),
+            file_cursor: 0,
+            marked: HashSet::new(),
+            view: PagerView::new(Vec::new(), String::new(), 0),
+            diff_width: None,
+            restore_request: None,
+            is_done: false,
+        };
+        overlay.select_checkpoint(selected);
+        overlay
+    }
+
+    /// Checkpoint id and files chosen with Enter, if any.
+    pub(crate) fn restore_request(&self) -> Option<(String, Vec<CheckpointRestorePath>)> {
+        self.restore_request.clone()
+    }
+
+    fn select_checkpoint(&mut self, idx: usize) {
+        self.selected = idx;
+        self.files = self
+            .checkpoints
+            .get(idx)
+            .map(|checkpoint| {
+                let mut files: Vec<PathBuf> = checkpoint.changes.keys().cloned().collect();
+                files.sort();
+                files
+            })
+            .unwrap_or_default();
+        self.file_cursor = 0;
+        self.marked.clear();
+        self.diff_width = None;
+    }
+
+    fn move_file_cursor(&mut self, delta: isize) {
+        let last = self.files.len().saturating_sub(1);
+        self.file_cursor = self.file_cursor.saturating_add_signed(delta).min(last);
+        self.diff_width = None;
+    }
+
+    fn toggle_marked(&mut self) {
+        if let Some(path) = self.files.get(self.file_cursor)
+            && !self.marked.remove(path)
+        {
+            self.marked.insert(path.clone());
+        }
+    }
+
+    /// Restores the marked files, or every changed file when none are marked.
+    fn request_restore(&mut self) {
+        let Some(checkpoint) = self.checkpoints.get(self.selected) else {
+            return;
+        };
+        if self.files.is_empty() {
+            return;
+        }
+        let paths = self
+            .files
+            .iter()
+            .filter(|path| self.marked.is_empty() || self.marked.contains(*path))
+            .map(|path| CheckpointRestorePath {
+                path: path.clone(),
+                hunks: None,
+            })
+            .collect();
+        self.restore_request = Some((checkpoint.id.clone(), paths));
+        self.is_done = true;
+    }
+
+    /// Handles the keys owned by the overlay. Returns `false` for keys that
+    /// should scroll the diff instead.
+    fn handle_key(&mut self, key_event: KeyEvent) -> bool {
+        if !matches!(key_event.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+            return true;
+        }
+        match key_event.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.is_done = true,
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
+                self.is_done = true;
+            }
+            KeyCode::Left | KeyCode::Char('h') if self.selected > 0 => {
+                self.select_checkpoint(self.selected - 1);
+            }
+            KeyCode::Right | KeyCode::Char('l') if self.selected + 1 < self.checkpoints.len() => {
+                self.select_checkpoint(self.selected + 1);
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Char('h') | KeyCode::Char('l') => {}
+            KeyCode::Up | KeyCode::Char('k') => self.move_file_cursor(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_file_cursor(1),
+            KeyCode::Char(' ') => self.toggle_marked(),
+            KeyCode::Enter if key_event.kind == KeyEventKind::Press => self.request_restore(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn refresh_diff(&mut self, width: u16) {
+        if self.diff_width == Some(width) {
+            return;
+        }
+        self.diff_width = Some(width);
+        let focused = self.files.get(self.file_cursor).and_then(|path| {
+            let change = self.checkpoints.get(self.selected)?.changes.get(path)?;
+            Some((path.clone(), change.clone()))
+        });
+        let (title, lines) = match focused {
+            Some((path, change)) => {
+                let title = display_path_for(&path, &self.cwd);
+                let changes = HashMap::from([(path, change)]);
+                let lines = create_diff_summary(&changes, &self.cwd, width as usize);
+                (title, lines)
+            }
+            None => (
+                "C H E C K P O I N T S".to_string(),
+                vec!["No changes since this checkpoint.".italic().into()],
+            ),
+        };
+        self.view = PagerView::new(vec![Text::from(lines)], title, 0);
+    }
+
+    fn render_summary(&self, area: Rect, buf: &mut Buffer) {
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        if let Some(checkpoint) = self.checkpoints.get(self.selected) {
+            let position = format!("  ({}/{})", self.selected + 1, self.checkpoints.len());
+            lines.push(Line::from(vec![
+                format!("Turn {}", checkpoint.turn).bold(),
+                " · ".dim(),
+                checkpoint.prompt_preview.clone().into(),
+                position.dim(),
+            ]));
+        }
+        let list_height = (area.height as usize).saturating_sub(lines.len());
+        let start = (self.file_cursor + 1).saturating_sub(list_height);
+        for (idx, path) in self.files.iter().enumerate().skip(start).take(list_height) {
+            let mark = if self.marked.contains(path) {
+                "[x] "
+            } else {
+                "[ ] "
+            };
+            let label = format!("{mark}{}", display_path_for(path, &self.cwd));
+            lines.push(if idx == self.file_cursor {
+                Line::from(vec!["› ".fg(theme().accent), label.fg(theme().accent)])
+            } else {
+                Line::from(vec!["  ".into(), label.into()])
+            });
+        }
+        Paragraph::new(lines).render_ref(area, buf);
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let hints_h = 2.min(area.height);
+        let body_h = area.height - hints_h;
+        let list_h = (self.files.len().max(1) as u16 + 1)
+            .min(body_h / 3)
+            .max(2.min(body_h));
+        let summary = Rect::new(area.x, area.y, area.width, list_h);
+        let diff = Rect::new(area.x, area.y + list_h, area.width, body_h - list_h);
+        let hints = Rect::new(area.x, area.y + body_h, area.width, hints_h);
+
+        self.render_summary(summary, buf);
+        self.refresh_diff(diff.width);
+        self.view.render(diff, buf);
+        let line1 = Rect::new(hints.x, hints.y, hints.width, 1.min(hints.height));
+        let line2 = Rect::new(
+            hints.x,
+            hints.y.saturating_add(1),
+            hints.width,
+            hints.height.saturating_sub(1),
+        );
+        render_key_hints(
+            line1,
+            buf,
+            &[
+                ("←/→", "checkpoint"),
+                ("↑/↓", "file"),
+                ("PgUp/PgDn", "scroll"),
+            ],
+        );
+        let restore_hint = if self.marked.is_empty() {
+            "restore all"
+        } else {
+            "restore marked"
+        };
+        render_key_hints(
+            line2,
+            buf,
+            &[("space", "mark"), ("⏎", restore_hint), ("q", "close")],
+        );
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => {
+                if !self.handle_key(key_event) {
+                    self.view.handle_key_event(tui, key_event)?;
+                }
+                tui.frame_requester().schedule_frame();
+                Ok(())
+            }
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2220,4 +2400,82 @@ mod tests {
             "wrapped length should grow or stay same after append"
         );
     }
+
+    fn checkpoint(id: &str, turn: u32, files: &[&str]) -> CheckpointSummary {
+        CheckpointSummary {
+            id: id.to_string(),
+            turn,
+            prompt_preview: format!("prompt {turn}"),
+            changes: files
+                .iter()
+                .map(|file| {
+                    (
+                        PathBuf::from("/repo").join(file),
+                        FileChange::Add {
+                            content: format!("{file}\n"),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn press(overlay: &mut CheckpointOverlay, code: KeyCode) {
+        assert!(overlay.handle_key(KeyEvent::new(code, KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn checkpoint_overlay_restores_marked_files_of_selected_checkpoint() {
+        let mut overlay = CheckpointOverlay::new(
+            vec![
+                checkpoint("first", 1, &["a.txt", "b.txt", "c.txt"]),
+                checkpoint("second", 2, &["c.txt"]),
+            ],
+            PathBuf::from("/repo"),
+        );
+
+        let area = Rect::new(0, 0, 60, 16);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let text = buffer_to_text(&buf, area);
+        assert!(text.contains("Turn 2"), "latest checkpoint first: {text}");
+
+        press(&mut overlay, KeyCode::Left);
+        press(&mut overlay, KeyCode::Down);
+        press(&mut overlay, KeyCode::Char(' '));
+        press(&mut overlay, KeyCode::Down);
+        press(&mut overlay, KeyCode::Down);
+        press(&mut overlay, KeyCode::Char(' '));
+        press(&mut overlay, KeyCode::Enter);
+
+        assert!(overlay.is_done());
+        let (id, paths) = overlay.restore_request().expect("restore request");
+        assert_eq!(id, "first");
+        assert_eq!(
+            paths,
+            vec![
+                CheckpointRestorePath {
+                    path: PathBuf::from("/repo/b.txt"),
+                    hunks: None,
+                },
+                CheckpointRestorePath {
+                    path: PathBuf::from("/repo/c.txt"),
+                    hunks: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn checkpoint_overlay_closes_without_restoring() {
+        let mut overlay = CheckpointOverlay::new(
+            vec![checkpoint("only", 1, &["a.txt"])],
+            PathBuf::from("/repo"),
+        );
+
+        press(&mut overlay, KeyCode::Esc);
+
+        assert!(overlay.is_done());
+        assert_eq!(overlay.restore_request(), None);
+    }
 }
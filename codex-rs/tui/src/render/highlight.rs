@@ -1,90 +1,335 @@
+//! Tree-sitter based syntax highlighting for shell commands, fenced code blocks
+//! and patch previews.
+
+use crate::terminal_palette::best_color;
+use crate::theme::theme;
+use codex_core::config::types::SyntaxHighlighting;
+use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
+use std::path::Path;
 use std::sync::OnceLock;
 use tree_sitter_highlight::Highlight;
 use tree_sitter_highlight::HighlightConfiguration;
 use tree_sitter_highlight::HighlightEvent;
 use tree_sitter_highlight::Highlighter;
 
-// Ref: https://github.com/tree-sitter/tree-sitter-bash/blob/master/queries/highlights.scm
+/// Capture names we style. Grammars emit dotted names such as
+/// `function.method`; tree-sitter-highlight resolves those to the longest
+/// matching prefix listed here.
 #[derive(Copy, Clone)]
-enum BashHighlight {
+enum SyntaxHighlight {
+    Attribute,
     Comment,
     Constant,
+    ConstantBuiltin,
+    Constructor,
     Embedded,
+    Escape,
     Function,
     Keyword,
+    Label,
     Number,
     Operator,
     Property,
     String,
+    Tag,
+    Type,
+    VariableBuiltin,
 }
 
-impl BashHighlight {
-    const ALL: [Self; 9] = [
+impl SyntaxHighlight {
+    const ALL: [Self; 17] = [
+        Self::Attribute,
         Self::Comment,
         Self::Constant,
+        Self::ConstantBuiltin,
+        Self::Constructor,
         Self::Embedded,
+        Self::Escape,
         Self::Function,
         Self::Keyword,
+        Self::Label,
         Self::Number,
         Self::Operator,
         Self::Property,
         Self::String,
+        Self::Tag,
+        Self::Type,
+        Self::VariableBuiltin,
     ];
 
     const fn as_str(self) -> &'static str {
         match self {
+            Self::Attribute => "attribute",
             Self::Comment => "comment",
             Self::Constant => "constant",
+            Self::ConstantBuiltin => "constant.builtin",
+            Self::Constructor => "constructor",
             Self::Embedded => "embedded",
+            Self::Escape => "escape",
             Self::Function => "function",
             Self::Keyword => "keyword",
+            Self::Label => "label",
             Self::Number => "number",
             Self::Operator => "operator",
             Self::Property => "property",
             Self::String => "string",
+            Self::Tag => "tag",
+            Self::Type => "type",
+            Self::VariableBuiltin => "variable.builtin",
         }
     }
 
-    fn style(self) -> Style {
-        match self {
-            Self::Comment | Self::Operator | Self::String => Style::default().dim(),
-            _ => Style::default(),
+    fn style(self, palette: Palette) -> Style {
+        match palette {
+            Palette::Shell => match self {
+                Self::Comment | Self::Operator | Self::String => Style::default().dim(),
+                _ => Style::default(),
+            },
+            Palette::Basic => match self {
+                Self::Comment => Style::default().dim(),
+                Self::Keyword => Style::default().fg(theme().highlight),
+                Self::String | Self::Escape => Style::default().fg(theme().accent),
+                Self::Constant | Self::ConstantBuiltin | Self::Number => {
+                    Style::default().fg(theme().highlight)
+                }
+                Self::Type | Self::Constructor => Style::default().bold(),
+                _ => Style::default(),
+            },
+            Palette::Full => {
+                let rgb = match self {
+                    Self::Comment => return Style::default().dim().italic(),
+                    Self::Keyword | Self::Operator => (197, 134, 192),
+                    Self::String => (206, 145, 120),
+                    Self::Escape => (215, 186, 125),
+                    Self::Number => (181, 206, 168),
+                    Self::Constant | Self::ConstantBuiltin | Self::VariableBuiltin => {
+                        (86, 156, 214)
+                    }
+                    Self::Function => (220, 220, 170),
+                    Self::Type | Self::Constructor => (78, 201, 176),
+                    Self::Attribute | Self::Label | Self::Property | Self::Tag => (156, 220, 254),
+                    Self::Embedded => return Style::default(),
+                };
+                match best_color(rgb) {
+                    Color::Reset => Self::style(self, Palette::Basic),
+                    color => Style::default().fg(color),
+                }
+            }
+        }
+    }
+}
+
+/// How captures are turned into styles.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Palette {
+    /// Modifier-only styling used for shell commands in exec cells and
+    /// approval prompts.
+    Shell,
+    /// ANSI colors and modifiers only, for terminals with limited color.
+    Basic,
+    /// RGB palette mapped onto the closest color the terminal can display.
+    Full,
+}
+
+static SYNTAX_HIGHLIGHTING: OnceLock<SyntaxHighlighting> = OnceLock::new();
+
+/// Records the `tui.syntax_highlighting` setting. Only the first call wins.
+pub(crate) fn init_syntax_highlighting(level: SyntaxHighlighting) {
+    let _ = SYNTAX_HIGHLIGHTING.set(level);
+}
+
+/// Palette for code blocks and diffs, or `None` when highlighting is off.
+fn code_palette() -> Option<Palette> {
+    match SYNTAX_HIGHLIGHTING
+        .get()
+        .copied()
+        .unwrap_or(SyntaxHighlighting::Auto)
+    {
+        SyntaxHighlighting::Off => None,
+        SyntaxHighlighting::Basic => Some(Palette::Basic),
+        SyntaxHighlighting::Auto => {
+            let has_256 = supports_color::on_cached(supports_color::Stream::Stdout)
+                .is_some_and(|level| level.has_256 || level.has_16m);
+            Some(if has_256 {
+                Palette::Full
+            } else {
+                Palette::Basic
+            })
         }
     }
 }
 
-static HIGHLIGHT_CONFIG: OnceLock<HighlightConfiguration> = OnceLock::new();
+/// Languages with a bundled tree-sitter grammar.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum SyntaxLanguage {
+    Bash,
+    C,
+    Cpp,
+    Go,
+    Java,
+    JavaScript,
+    Python,
+    Ruby,
+    Rust,
+    Tsx,
+    TypeScript,
+}
+
+impl SyntaxLanguage {
+    const COUNT: usize = 11;
+
+    /// Detects the language named by a fenced code block's info string, e.g.
+    /// `rust`, `py` or `ts title="x.ts"`.
+    pub(crate) fn from_fence(info: &str) -> Option<Self> {
+        let name = info
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '{' || c == '}')
+            .find(|token| !token.is_empty())?;
+        Self::from_name(name.trim_start_matches('.'))
+    }
+
+    /// Detects the language of a file from its extension or well-known name.
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        match file_name {
+            "Gemfile" | "Rakefile" | "Guardfile" | "Podfile" => return Some(Self::Ruby),
+            ".bashrc" | ".bash_profile" | ".profile" | ".zshrc" => return Some(Self::Bash),
+            _ => {}
+        }
+        Self::from_name(path.extension()?.to_str()?)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        let language = match name.to_ascii_lowercase().as_str() {
+            "bash" | "sh" | "shell" | "zsh" | "ksh" => Self::Bash,
+            "c" | "h" => Self::C,
+            "cpp" | "c++" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => Self::Cpp,
+            "go" | "golang" => Self::Go,
+            "java" => Self::Java,
+            "javascript" | "js" | "jsx" | "mjs" | "cjs" => Self::JavaScript,
+            "python" | "py" | "python3" | "pyi" => Self::Python,
+            "ruby" | "rb" | "rake" | "gemspec" => Self::Ruby,
+            "rust" | "rs" => Self::Rust,
+            "tsx" => Self::Tsx,
+            "typescript" | "ts" | "mts" | "cts" => Self::TypeScript,
+            _ => return None,
+        };
+        Some(language)
+    }
+
+    /// Returns the highlight configuration for this language, building it on
+    /// first use. `None` if the bundled queries fail to load.
+    fn config(self) -> Option<&'static HighlightConfiguration> {
+        static CONFIGS: [OnceLock<Option<HighlightConfiguration>>; SyntaxLanguage::COUNT] =
+            [const { OnceLock::new() }; SyntaxLanguage::COUNT];
+        CONFIGS[self as usize]
+            .get_or_init(|| {
+                let (language, highlights) = self.grammar();
+                let mut config =
+                    HighlightConfiguration::new(language, self.name(), &highlights, "", "").ok()?;
+                config.configure(highlight_names());
+                Some(config)
+            })
+            .as_ref()
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Bash => "bash",
+            Self::C => "c",
+            Self::Cpp => "cpp",
+            Self::Go => "go",
+            Self::Java => "java",
+            Self::JavaScript => "javascript",
+            Self::Python => "python",
+            Self::Ruby => "ruby",
+            Self::Rust => "rust",
+            Self::Tsx => "tsx",
+            Self::TypeScript => "typescript",
+        }
+    }
+
+    /// Grammar and highlight query. Grammars that extend another one list
+    /// their own patterns first so they take precedence.
+    fn grammar(self) -> (tree_sitter::Language, String) {
+        match self {
+            Self::Bash => (
+                tree_sitter_bash::LANGUAGE.into(),
+                tree_sitter_bash::HIGHLIGHT_QUERY.to_string(),
+            ),
+            Self::C => (
+                tree_sitter_c::LANGUAGE.into(),
+                tree_sitter_c::HIGHLIGHT_QUERY.to_string(),
+            ),
+            Self::Cpp => (
+                tree_sitter_cpp::LANGUAGE.into(),
+                [
+                    tree_sitter_cpp::HIGHLIGHT_QUERY,
+                    tree_sitter_c::HIGHLIGHT_QUERY,
+                ]
+                .join("\n"),
+            ),
+            Self::Go => (
+                tree_sitter_go::LANGUAGE.into(),
+                tree_sitter_go::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::Java => (
+                tree_sitter_java::LANGUAGE.into(),
+                tree_sitter_java::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::JavaScript => (
+                tree_sitter_javascript::LANGUAGE.into(),
+                [
+                    tree_sitter_javascript::JSX_HIGHLIGHT_QUERY,
+                    tree_sitter_javascript::HIGHLIGHT_QUERY,
+                ]
+                .join("\n"),
+            ),
+            Self::Python => (
+                tree_sitter_python::LANGUAGE.into(),
+                tree_sitter_python::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::Ruby => (
+                tree_sitter_ruby::LANGUAGE.into(),
+                tree_sitter_ruby::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::Rust => (
+                tree_sitter_rust::LANGUAGE.into(),
+                tree_sitter_rust::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::Tsx => (
+                tree_sitter_typescript::LANGUAGE_TSX.into(),
+                [
+                    tree_sitter_typescript::HIGHLIGHTS_QUERY,
+                    tree_sitter_javascript::JSX_HIGHLIGHT_QUERY,
+                    tree_sitter_javascript::HIGHLIGHT_QUERY,
+                ]
+                .join("\n"),
+            ),
+            Self::TypeScript => (
+                tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+                [
+                    tree_sitter_typescript::HIGHLIGHTS_QUERY,
+                    tree_sitter_javascript::HIGHLIGHT_QUERY,
+                ]
+                .join("\n"),
+            ),
+        }
+    }
+}
 
 fn highlight_names() -> &'static [&'static str] {
-    static NAMES: OnceLock<[&'static str; BashHighlight::ALL.len()]> = OnceLock::new();
+    static NAMES: OnceLock<[&'static str; SyntaxHighlight::ALL.len()]> = OnceLock::new();
     NAMES
-        .get_or_init(|| BashHighlight::ALL.map(BashHighlight::as_str))
+        .get_or_init(|| SyntaxHighlight::ALL.map(SyntaxHighlight::as_str))
         .as_slice()
 }
 
-fn highlight_config() -> &'static HighlightConfiguration {
-    HIGHLIGHT_CONFIG.get_or_init(|| {
-        let language = tree_sitter_bash::LANGUAGE.into();
-        #[expect(clippy::expect_used)]
-        let mut config = HighlightConfiguration::new(
-            language,
-            "bash",
-            tree_sitter_bash::HIGHLIGHT_QUERY,
-            "",
-            "",
-        )
-        .expect("load bash highlight query");
-        config.configure(highlight_names());
-        config
-    })
-}
-
-fn highlight_for(highlight: Highlight) -> BashHighlight {
-    BashHighlight::ALL[highlight.0]
+fn highlight_for(highlight: Highlight) -> SyntaxHighlight {
+    SyntaxHighlight::ALL[highlight.0]
 }
 
 fn push_segment(lines: &mut Vec<Line<'static>>, segment: &str, style: Option<Style>) {
@@ -105,42 +350,58 @@ fn push_segment(lines: &mut Vec<Line<'static>>, segment: &str, style: Option<Sty
     }
 }
 
-/// Convert a bash script into per-line styled content using tree-sitter's
-/// bash highlight query. The highlighter is streamed so multi-line content is
-/// split into `Line`s while preserving style boundaries.
-pub(crate) fn highlight_bash_to_lines(script: &str) -> Vec<Line<'static>> {
+/// Streams `source` through the highlighter so multi-line content is split
+/// into `Line`s while preserving style boundaries. `None` if highlighting
+/// fails.
+fn highlight_to_lines(
+    source: &str,
+    language: SyntaxLanguage,
+    palette: Palette,
+) -> Option<Vec<Line<'static>>> {
     let mut highlighter = Highlighter::new();
-    let iterator =
-        match highlighter.highlight(highlight_config(), script.as_bytes(), None, |_| None) {
-            Ok(iter) => iter,
-            Err(_) => return vec![script.to_string().into()],
-        };
+    let iterator = highlighter
+        .highlight(language.config()?, source.as_bytes(), None, |_| None)
+        .ok()?;
 
     let mut lines: Vec<Line<'static>> = vec![Line::from("")];
     let mut highlight_stack: Vec<Highlight> = Vec::new();
 
     for event in iterator {
-        match event {
-            Ok(HighlightEvent::HighlightStart(highlight)) => highlight_stack.push(highlight),
-            Ok(HighlightEvent::HighlightEnd) => {
+        match event.ok()? {
+            HighlightEvent::HighlightStart(highlight) => highlight_stack.push(highlight),
+            HighlightEvent::HighlightEnd => {
                 highlight_stack.pop();
             }
-            Ok(HighlightEvent::Source { start, end }) => {
+            HighlightEvent::Source { start, end } => {
                 if start == end {
                     continue;
                 }
-                let style = highlight_stack.last().map(|h| highlight_for(*h).style());
-                push_segment(&mut lines, &script[start..end], style);
+                let style = highlight_stack
+                    .last()
+                    .map(|h| highlight_for(*h).style(palette));
+                push_segment(&mut lines, &source[start..end], style);
             }
-            Err(_) => return vec![script.to_string().into()],
         }
     }
 
-    if lines.is_empty() {
-        vec![Line::from("")]
-    } else {
-        lines
-    }
+    Some(lines)
+}
+
+/// Convert a bash script into per-line styled content using tree-sitter's
+/// bash highlight query.
+pub(crate) fn highlight_bash_to_lines(script: &str) -> Vec<Line<'static>> {
+    highlight_to_lines(script, SyntaxLanguage::Bash, Palette::Shell)
+        .unwrap_or_else(|| vec![script.to_string().into()])
+}
+
+/// Highlights `code` as `language` using the configured palette, one `Line`
+/// per source line. Returns `None` when highlighting is turned off or the
+/// source cannot be highlighted, in which case callers render plain text.
+pub(crate) fn highlight_code_to_lines(
+    code: &str,
+    language: SyntaxLanguage,
+) -> Option<Vec<Line<'static>>> {
+    highlight_to_lines(code, language, code_palette()?)
 }
 
 #[cfg(test)]
@@ -233,4 +494,75 @@ mod tests {
         let body_style = body_style.expect("missing heredoc span");
         assert!(body_style.add_modifier.contains(Modifier::DIM));
     }
+
+    #[test]
+    fn detects_languages_from_fences_and_paths() {
+        assert_eq!(
+            SyntaxLanguage::from_fence("rust"),
+            Some(SyntaxLanguage::Rust)
+        );
+        assert_eq!(
+            SyntaxLanguage::from_fence("py title=\"x.py\""),
+            Some(SyntaxLanguage::Python)
+        );
+        assert_eq!(
+            SyntaxLanguage::from_fence("{.tsx}"),
+            Some(SyntaxLanguage::Tsx)
+        );
+        assert_eq!(SyntaxLanguage::from_fence("text"), None);
+        assert_eq!(SyntaxLanguage::from_fence(""), None);
+
+        assert_eq!(
+            SyntaxLanguage::from_path(Path::new("src/lib.rs")),
+            Some(SyntaxLanguage::Rust)
+        );
+        assert_eq!(
+            SyntaxLanguage::from_path(Path::new("web/App.TS")),
+            Some(SyntaxLanguage::TypeScript)
+        );
+        assert_eq!(
+            SyntaxLanguage::from_path(Path::new("Gemfile")),
+            Some(SyntaxLanguage::Ruby)
+        );
+        assert_eq!(SyntaxLanguage::from_path(Path::new("README.md")), None);
+    }
+
+    #[test]
+    fn every_bundled_grammar_loads() {
+        for language in [
+            SyntaxLanguage::Bash,
+            SyntaxLanguage::C,
+            SyntaxLanguage::Cpp,
+            SyntaxLanguage::Go,
+            SyntaxLanguage::Java,
+            SyntaxLanguage::JavaScript,
+            SyntaxLanguage::Python,
+            SyntaxLanguage::Ruby,
+            SyntaxLanguage::Rust,
+            SyntaxLanguage::Tsx,
+            SyntaxLanguage::TypeScript,
+        ] {
+            assert!(language.config().is_some(), "{language:?} failed to load");
+        }
+    }
+
+    #[test]
+    fn highlights_rust_keywords_strings_and_comments() {
+        let s = "fn main() {\n    // greet\n    println!(\"hi\");\n}";
+        let lines =
+            highlight_to_lines(s, SyntaxLanguage::Rust, Palette::Basic).expect("rust highlighting");
+        assert_eq!(reconstructed(&lines), s);
+
+        let style_of = |text: &str| {
+            lines
+                .iter()
+                .flat_map(|l| l.spans.iter())
+                .find(|sp| sp.content.as_ref() == text)
+                .map(|sp| sp.style)
+                .unwrap_or_else(|| panic!("missing span {text:?}"))
+        };
+        assert_eq!(style_of("fn").fg, Some(Color::Magenta));
+        assert_eq!(style_of("\"hi\"").fg, Some(Color::Cyan));
+        assert!(style_of("// greet").add_modifier.contains(Modifier::DIM));
+    }
 }
@@ -29,6 +29,7 @@ use unicode_width::UnicodeWidthStr;
 use crate::diff_render::display_path_for;
 use crate::key_hint;
 use crate::text_formatting::truncate_text;
+use crate::theme::theme;
 use crate::tui::FrameRequester;
 use crate::tui::Tui;
 use crate::tui::TuiEvent;
@@ -65,8 +66,9 @@ enum BackgroundEvent {
 }
 
 /// Interactive session picker that lists recorded rollout files with simple
-/// search and pagination. Shows the first user input as the preview, relative
-/// time (e.g., "5 seconds ago"), and the absolute path.
+/// search and pagination. Shows the generated session title, or else the first
+/// user input, as the preview, relative time (e.g., "5 seconds ago"), and the
+/// absolute path.
 pub async fn run_resume_picker(
     tui: &mut Tui,
     codex_home: &Path,
@@ -133,7 +135,8 @@ pub async fn run_resume_picker(
                     }
                     TuiEvent::Draw => {
                         if let Ok(size) = alt.tui.terminal.size() {
-                            let list_height = size.height.saturating_sub(4) as usize;
+                            let reserved = 4 + u16::from(state.selected_summary().is_some());
+                            let list_height = size.height.saturating_sub(reserved) as usize;
                             state.update_view_rows(list_height);
                             state.ensure_minimum_rows_for_view(list_height);
                         }
@@ -244,6 +247,8 @@ impl SearchState {
 struct Row {
     path: PathBuf,
     preview: String,
+    /// Generated session summary, shown for the selected row.
+    summary: Option<String>,
     created_at: Option<DateTime<Utc>>,
     updated_at: Option<DateTime<Utc>>,
     cwd: Option<PathBuf>,
@@ -445,7 +450,12 @@ impl PickerState {
         } else {
             let q = self.query.to_lowercase();
             self.filtered_rows = base_iter
-                .filter(|r| r.preview.to_lowercase().contains(&q))
+                .filter(|r| {
+                    r.preview.to_lowercase().contains(&q)
+                        || r.summary
+                            .as_ref()
+                            .is_some_and(|summary| summary.to_lowercase().contains(&q))
+                })
                 .cloned()
                 .collect();
         }
@@ -562,6 +572,13 @@ impl PickerState {
         }
     }
 
+    /// Summary of the selected row, drawn on its own line below the list.
+    fn selected_summary(&self) -> Option<&str> {
+        self.filtered_rows
+            .get(self.selected)
+            .and_then(|row| row.summary.as_deref())
+    }
+
     fn update_view_rows(&mut self, rows: usize) {
         self.view_rows = if rows == 0 { None } else { Some(rows) };
         self.ensure_selected_visible();
@@ -639,8 +656,14 @@ fn head_to_row(item: &ConversationItem) -> Row {
         .and_then(parse_timestamp_str)
         .or(created_at);
 
-    let (cwd, git_branch) = extract_session_meta_from_head(&item.head);
-    let preview = preview_from_head(&item.head)
+    let SessionMetaSummary {
+        cwd,
+        git_branch,
+        title,
+        summary,
+    } = extract_session_meta_from_head(&item.head);
+    let preview = title
+        .or_else(|| preview_from_head(&item.head))
         .map(|s| s.trim().to_string())
         .filter(|s| !s.is_empty())
         .unwrap_or_else(|| String::from("(no message yet)"));
@@ -648,6 +671,7 @@ fn head_to_row(item: &ConversationItem) -> Row {
     Row {
         path: item.path.clone(),
         preview,
+        summary,
         created_at,
         updated_at,
         cwd,
@@ -655,15 +679,26 @@ fn head_to_row(item: &ConversationItem) -> Row {
     }
 }
 
-fn extract_session_meta_from_head(head: &[serde_json::Value]) -> (Option<PathBuf>, Option<String>) {
+#[derive(Default)]
+struct SessionMetaSummary {
+    cwd: Option<PathBuf>,
+    git_branch: Option<String>,
+    title: Option<String>,
+    summary: Option<String>,
+}
+
+fn extract_session_meta_from_head(head: &[serde_json::Value]) -> SessionMetaSummary {
     for value in head {
         if let Ok(meta_line) = serde_json::from_value::<SessionMetaLine>(value.clone()) {
-            let cwd = Some(meta_line.meta.cwd);
-            let git_branch = meta_line.git.and_then(|git| git.branch);
-            return (cwd, git_branch);
+            return SessionMetaSummary {
+                cwd: Some(meta_line.meta.cwd),
+                git_branch: meta_line.git.and_then(|git| git.branch),
+                title: meta_line.meta.title,
+                summary: meta_line.meta.summary,
+            };
         }
     }
-    (None, None)
+    SessionMetaSummary::default()
 }
 
 fn paths_match(a: &Path, b: &Path) -> bool {
@@ -701,18 +736,21 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
     let height = tui.terminal.size()?.height;
     tui.draw(height, |frame| {
         let area = frame.area();
-        let [header, search, columns, list, hint] = Layout::vertical([
+        let selected_summary = state.selected_summary();
+        let summary_height = u16::from(selected_summary.is_some());
+        let [header, search, columns, list, summary, hint] = Layout::vertical([
             Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Length(1),
-            Constraint::Min(area.height.saturating_sub(4)),
+            Constraint::Min(area.height.saturating_sub(4 + summary_height)),
+            Constraint::Length(summary_height),
             Constraint::Length(1),
         ])
         .areas(area);
 
         // Header
         frame.render_widget_ref(
-            Line::from(vec!["Resume a previous session".bold().cyan()]),
+            Line::from(vec!["Resume a previous session".bold().fg(theme().accent)]),
             header,
         );
 
@@ -730,6 +768,11 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
         render_column_headers(frame, columns, &metrics);
         render_list(frame, list, state, &metrics);
 
+        if let Some(selected_summary) = selected_summary {
+            let text = truncate_text(selected_summary, summary.width as usize);
+            frame.render_widget_ref(Line::from(text.dim()), summary);
+        }
+
         // Hint line
         let hint_line: Line = vec![
             key_hint::plain(KeyCode::Enter).into(),
@@ -803,7 +846,7 @@ fn render_list(
                 .dim(),
             )
         } else {
-            Some(Span::from(format!("{branch_label:<max_branch_width$}")).cyan())
+            Some(Span::from(format!("{branch_label:<max_branch_width$}")).fg(theme().accent))
         };
         let cwd_span = if max_cwd_width == 0 {
             None
@@ -1178,6 +1221,48 @@ mod tests {
         assert_eq!(row.updated_at, Some(expected_updated));
     }
 
+    #[test]
+    fn row_prefers_session_title_and_searches_summary() {
+        let mut head = head_with_ts_and_user_text("2025-01-01T00:00:00Z", &["first message"]);
+        head.insert(
+            0,
+            json!({
+                "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                "timestamp": "2025-01-01T00:00:00Z",
+                "cwd": "/tmp/project",
+                "originator": "codex_cli_rs",
+                "cli_version": "0.0.0",
+                "instructions": null,
+                "model_provider": "openai",
+                "title": "Fix resume picker search",
+                "summary": "Titles and summaries are shown in the picker.",
+            }),
+        );
+        let row = head_to_row(&ConversationItem {
+            path: PathBuf::from("/tmp/a.jsonl"),
+            head,
+            created_at: Some("2025-01-01T00:00:00Z".into()),
+            updated_at: Some("2025-01-01T00:00:00Z".into()),
+        });
+        assert_eq!(row.preview, "Fix resume picker search");
+        assert_eq!(row.cwd, Some(PathBuf::from("/tmp/project")));
+
+        let loader: PageLoader = Arc::new(|_| {});
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+            true,
+            None,
+        );
+        state.all_rows = vec![row];
+        state.set_query("SUMMARIES".to_string());
+        assert_eq!(state.filtered_rows.len(), 1);
+        state.set_query("first message".to_string());
+        assert!(state.filtered_rows.is_empty());
+    }
+
     #[test]
     fn resume_table_snapshot() {
         use crate::custom_terminal::Terminal;
@@ -1200,6 +1285,7 @@ mod tests {
             Row {
                 path: PathBuf::from("/tmp/a.jsonl"),
                 preview: String::from("Fix resume picker timestamps"),
+                summary: None,
                 created_at: Some(now - Duration::minutes(16)),
                 updated_at: Some(now - Duration::seconds(42)),
                 cwd: None,
@@ -1208,6 +1294,7 @@ mod tests {
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
                 preview: String::from("Investigate lazy pagination cap"),
+                summary: None,
                 created_at: Some(now - Duration::hours(1)),
                 updated_at: Some(now - Duration::minutes(35)),
                 cwd: None,
@@ -1216,6 +1303,7 @@ mod tests {
             Row {
                 path: PathBuf::from("/tmp/c.jsonl"),
                 preview: String::from("Explain the codebase"),
+                summary: None,
                 created_at: Some(now - Duration::hours(2)),
                 updated_at: Some(now - Duration::hours(2)),
                 cwd: None,
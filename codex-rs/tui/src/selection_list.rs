@@ -1,5 +1,6 @@
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RowRenderable;
+use crate::theme::theme;
 use ratatui::style::Style;
 use ratatui::style::Styled as _;
 use ratatui::style::Stylize as _;
@@ -18,7 +19,7 @@ pub(crate) fn selection_option_row(
         format!("  {}. ", index + 1)
     };
     let style = if is_selected {
-        Style::default().cyan()
+        Style::default().fg(theme().accent)
     } else {
         Style::default()
     };
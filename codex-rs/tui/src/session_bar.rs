@@ -21,9 +21,10 @@ use crate::cxresume_picker_widget::TumixState;
 use crate::cxresume_picker_widget::get_cwd_sessions_for;
 use crate::cxresume_picker_widget::last_user_snippet;
 use crate::cxresume_picker_widget::load_tumix_status_index;
-use crate::key_hint; // Unify key-hint rendering
+use crate::keymap::KeyAction;
+use crate::keymap::keymap;
 use crate::session_alias_manager::SessionAliasManager;
-use crossterm::event::KeyCode;
+use crate::theme::theme;
 
 /// Bottom session bar (similar to tmux)
 pub struct SessionBar {
@@ -109,14 +110,18 @@ impl SessionBar {
 
                 // Compute labels lazily for visible sessions (cache by path)
                 for s in &self.sessions {
-                    // Always recompute for the current session so alias follows latest user message
+                    // Always recompute for the current session so alias follows latest user message.
+                    // A generated session title takes precedence over the snippet.
                     let must_update = self
                         .current_session_id
                         .as_ref()
                         .map(|id| *id == s.id)
                         .unwrap_or(false)
                         || !self.label_cache.contains_key(&s.path);
-                    if must_update && let Some(snippet) = last_user_snippet(&s.path, 5) {
+                    if must_update
+                        && let Some(snippet) =
+                            s.title.clone().or_else(|| last_user_snippet(&s.path, 5))
+                    {
                         // Unicode-safe truncation to keep bar compact
                         let short = if snippet.chars().count() > 10 {
                             let truncated: String = snippet.chars().take(10).collect();
@@ -260,8 +265,8 @@ impl SessionBar {
         if let Some(error) = &self.error {
             return (
                 Line::from(vec![
-                    Span::from(" Error: ").red().bold(),
-                    Span::from(error.clone()).red(),
+                    Span::from(" Error: ").fg(theme().error).bold(),
+                    Span::from(error.clone()).fg(theme().error),
                 ]),
                 Line::from(""),
                 Line::from(""),
@@ -290,7 +295,9 @@ impl SessionBar {
         if !current_in_history {
             // Focused + selected → cyan + bold; otherwise dim to let theme drive appearance.
             let new_style = if self.has_focus && self.selected_on_new {
-                Style::default().cyan().add_modifier(Modifier::BOLD)
+                Style::default()
+                    .fg(theme().accent)
+                    .add_modifier(Modifier::BOLD)
             } else {
                 Style::default().dim()
             };
@@ -353,15 +360,19 @@ impl SessionBar {
                 // - Otherwise: default
                 let style = if is_current {
                     // 当前会话始终用绿色高亮
-                    Style::default().green().add_modifier(Modifier::BOLD)
+                    Style::default()
+                        .fg(theme().success)
+                        .add_modifier(Modifier::BOLD)
                 } else if self.has_focus && is_selected {
                     // 非当前会话但被选中时用青色
-                    Style::default().cyan().add_modifier(Modifier::BOLD)
+                    Style::default()
+                        .fg(theme().accent)
+                        .add_modifier(Modifier::BOLD)
                 } else {
                     Style::default()
                 };
 
-                // Get display name: prefer alias, fall back to snippet or ID
+                // Get display name: prefer alias, fall back to title/snippet or ID
                 let display_name = if let Some(alias) = self.alias_manager.get_alias(&session.id) {
                     alias
                 } else if let Some(snippet) = self.label_cache.get(&session.path) {
@@ -385,9 +396,9 @@ impl SessionBar {
                 if let Some(ind) = session.tumix.as_ref() {
                     let (label, color) = match ind.state {
                         TumixState::Running => ("运行", Color::Yellow),
-                        TumixState::Completed => ("完成", Color::Green),
-                        TumixState::Failed => ("失败", Color::Red),
-                        TumixState::Stalled => ("停滞", Color::Magenta),
+                        TumixState::Completed => ("完成", theme().success),
+                        TumixState::Failed => ("失败", theme().error),
+                        TumixState::Stalled => ("停滞", theme().highlight),
                     };
                     left_spans.push(Span::styled(
                         format!(" · {label}"),
@@ -422,7 +433,7 @@ impl SessionBar {
         } else {
             ("就绪".to_string(), "新建".to_string())
         };
-        status_spans.push(Span::from(status_label).green().bold());
+        status_spans.push(Span::from(status_label).fg(theme().success).bold());
         status_spans.push(Span::from("  "));
         status_spans.push(Span::from("会话:").dim());
         status_spans.push(Span::from(" "));
@@ -432,28 +443,29 @@ impl SessionBar {
         let mut help_spans: Vec<Span<'static>> = Vec::new();
         if self.has_focus {
             // Shared key-hint style; all hint texts are dim like the rest of Codex UI
-            help_spans.push(key_hint::plain(KeyCode::Left).into());
+            let keymap = keymap();
+            help_spans.push(keymap.hint(KeyAction::SessionPrevious));
             help_spans.push(Span::from("/".to_string()).dim());
-            help_spans.push(key_hint::plain(KeyCode::Right).into());
+            help_spans.push(keymap.hint(KeyAction::SessionNext));
             help_spans.push(Span::from(" move  ").dim());
 
-            help_spans.push(key_hint::plain(KeyCode::Enter).into());
+            help_spans.push(keymap.hint(KeyAction::SessionOpen));
             help_spans.push(Span::from(" open  ").dim());
 
-            help_spans.push(key_hint::plain(KeyCode::Char('n')).into());
+            help_spans.push(keymap.hint(KeyAction::SessionNew));
             help_spans.push(Span::from(" new  ").dim());
 
-            help_spans.push(key_hint::plain(KeyCode::Char('r')).into());
+            help_spans.push(keymap.hint(KeyAction::SessionRename));
             help_spans.push(Span::from(" rename  ").dim());
 
-            help_spans.push(key_hint::plain(KeyCode::Char('x')).into());
+            help_spans.push(keymap.hint(KeyAction::SessionDelete));
             help_spans.push(Span::from(" delete  ").dim());
 
             // Use Esc to exit session focus; Tab is reserved elsewhere and disabled here
-            help_spans.push(key_hint::plain(KeyCode::Esc).into());
+            help_spans.push(keymap.hint(KeyAction::SessionExit));
             help_spans.push(Span::from(" exit").dim());
         } else {
-            help_spans.push(key_hint::ctrl(KeyCode::Char('p')).into());
+            help_spans.push(keymap().hint(KeyAction::FocusSessions));
             help_spans.push(Span::from(" Sessions").dim());
         }
 
@@ -23,7 +23,10 @@ pub enum SlashCommand {
     Tumix,
     TumixStop,
     Compact,
+    Pin,
+    Unpin,
     Undo,
+    Checkpoints,
     Diff,
     OpenImage,
     RefImage,
@@ -35,6 +38,7 @@ pub enum SlashCommand {
     Agent,
     Status,
     Mcp,
+    Keys,
     Logout,
     Quit,
     Exit,
@@ -56,8 +60,11 @@ impl SlashCommand {
             SlashCommand::RalphLoop => "start a Ralph loop that repeats the same prompt until done",
             SlashCommand::CancelRalph => "cancel the active Ralph loop",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
+            SlashCommand::Pin => "pin a message so compaction keeps it verbatim",
+            SlashCommand::Unpin => "stop keeping a pinned message across compaction",
             SlashCommand::Review => "review my current changes and find issues",
             SlashCommand::Undo => "ask Codex to undo a turn",
+            SlashCommand::Checkpoints => "browse turn checkpoints and restore files",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
             SlashCommand::OpenImage => "open the most recently generated image",
@@ -74,6 +81,7 @@ impl SlashCommand {
             SlashCommand::Model => "choose what model and reasoning effort to use",
             SlashCommand::Approvals => "choose what Codex can do without approval",
             SlashCommand::Mcp => "list configured MCP tools",
+            SlashCommand::Keys => "show key bindings",
             SlashCommand::Logout => "log out of Codex",
             SlashCommand::Rollout => "print the rollout file path",
             SlashCommand::TestApproval => "test approval request",
@@ -95,6 +103,8 @@ impl SlashCommand {
                 | SlashCommand::RefImageBatch
                 | SlashCommand::PdfUpdate
                 | SlashCommand::ImageQuality
+                | SlashCommand::Pin
+                | SlashCommand::Unpin
         )
     }
 
@@ -114,6 +124,7 @@ impl SlashCommand {
             | SlashCommand::RalphLoop
             | SlashCommand::Compact
             | SlashCommand::Undo
+            | SlashCommand::Checkpoints
             | SlashCommand::Model
             | SlashCommand::Approvals
             | SlashCommand::Review
@@ -125,10 +136,13 @@ impl SlashCommand {
             | SlashCommand::ImageQuality
             | SlashCommand::RefImage
             | SlashCommand::ClearRef
+            | SlashCommand::Pin
+            | SlashCommand::Unpin
             | SlashCommand::Mention
             | SlashCommand::Agent
             | SlashCommand::Status
             | SlashCommand::Mcp
+            | SlashCommand::Keys
             | SlashCommand::TumixStop
             | SlashCommand::CancelRalph
             | SlashCommand::Feedback
@@ -34,6 +34,7 @@ use super::rate_limits::StatusRateLimitValue;
 use super::rate_limits::compose_rate_limit_data;
 use super::rate_limits::format_status_limit_summary;
 use super::rate_limits::render_status_limit_progress_bar;
+use crate::theme::theme;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_lines;
 use codex_core::AuthManager;
@@ -80,7 +81,7 @@ pub(crate) fn new_status_output(
     now: DateTime<Local>,
     model_name: &str,
 ) -> CompositeHistoryCell {
-    let command = PlainHistoryCell::new(vec!["/status".magenta().into()]);
+    let command = PlainHistoryCell::new(vec!["/status".fg(theme().highlight).into()]);
     let card = StatusHistoryCell::new(
         config,
         auth_manager,
@@ -353,14 +354,14 @@ impl HistoryCell for StatusHistoryCell {
         let value_width = formatter.value_width(available_inner_width);
 
         let note_first_line = Line::from(vec![
-            Span::from("Visit ").cyan(),
+            Span::from("Visit ").fg(theme().accent),
             "https://chatgpt.com/codex/settings/usage"
-                .cyan()
+                .fg(theme().accent)
                 .underlined(),
-            Span::from(" for up-to-date").cyan(),
+            Span::from(" for up-to-date").fg(theme().accent),
         ]);
         let note_second_line = Line::from(vec![
-            Span::from("information on rate limits and credits").cyan(),
+            Span::from("information on rate limits and credits").fg(theme().accent),
         ]);
         let note_lines = word_wrap_lines(
             [note_first_line, note_second_line],
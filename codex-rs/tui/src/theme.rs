@@ -0,0 +1,216 @@
+//! Color palettes for the TUI.
+//!
+//! `[tui.theme]` picks one of the built-in palettes and may override
+//! individual roles. Like the keymap, the resolved theme is installed once at
+//! startup and read from anywhere through [`theme`].
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use codex_core::config::types::ThemeName;
+use codex_core::config::types::TuiTheme;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+
+use crate::color::blend;
+use crate::color::is_light;
+use crate::terminal_palette::best_color;
+
+/// Resolved colors for each themed role.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Theme {
+    /// Links, prompts and other interactive accents.
+    pub(crate) accent: Color,
+    /// Success states and added diff lines.
+    pub(crate) success: Color,
+    /// Errors and removed diff lines.
+    pub(crate) error: Color,
+    /// Headers and slash command names.
+    pub(crate) highlight: Color,
+    /// Numbers of ordered markdown lists.
+    pub(crate) list_marker: Color,
+    /// Markdown blockquotes.
+    pub(crate) quote: Color,
+    /// Key hints such as `ctrl + t`.
+    pub(crate) key_hint: Style,
+    /// Background behind user messages; derived from the terminal
+    /// background when `None`.
+    user_message_bg: Option<Color>,
+    /// Whether the user message background is blended toward black.
+    light: Option<bool>,
+    /// How strongly the user message background stands out.
+    user_message_alpha: f32,
+}
+
+impl Theme {
+    fn builtin(name: ThemeName) -> Self {
+        let standard = Self {
+            accent: Color::Cyan,
+            success: Color::Green,
+            error: Color::Red,
+            highlight: Color::Magenta,
+            list_marker: Color::LightBlue,
+            quote: Color::Green,
+            key_hint: Style::default().add_modifier(Modifier::DIM),
+            user_message_bg: None,
+            light: None,
+            user_message_alpha: 0.1,
+        };
+        match name {
+            ThemeName::Auto => standard,
+            ThemeName::Dark => Self {
+                light: Some(false),
+                ..standard
+            },
+            ThemeName::Light => Self {
+                light: Some(true),
+                ..standard
+            },
+            ThemeName::HighContrast => Self {
+                accent: Color::LightCyan,
+                success: Color::LightGreen,
+                error: Color::LightRed,
+                highlight: Color::LightMagenta,
+                quote: Color::LightGreen,
+                key_hint: Style::default().add_modifier(Modifier::BOLD),
+                user_message_alpha: 0.25,
+                ..standard
+            },
+        }
+    }
+
+    /// Builds the theme described by `[tui.theme]`. Unknown roles and
+    /// unparsable colors are skipped and reported in the returned warnings.
+    pub(crate) fn from_config(config: &TuiTheme) -> (Self, Vec<String>) {
+        let mut theme = Self::builtin(config.name);
+        let mut warnings = Vec::new();
+        for (role, value) in &config.colors {
+            let Some(color) = parse_color(value) else {
+                warnings.push(format!("[tui.theme] {role}: unknown color `{value}`"));
+                continue;
+            };
+            match role.as_str() {
+                "accent" => theme.accent = color,
+                "success" => theme.success = color,
+                "error" => theme.error = color,
+                "highlight" => theme.highlight = color,
+                "list-marker" => theme.list_marker = color,
+                "quote" => theme.quote = color,
+                "key-hint" => theme.key_hint = Style::default().fg(color),
+                "user-message-bg" => theme.user_message_bg = Some(color),
+                _ => warnings.push(format!("[tui.theme] unknown color role `{role}`")),
+            }
+        }
+        (theme, warnings)
+    }
+
+    /// Background for user messages on a terminal whose background is
+    /// `terminal_bg`, if one should be drawn.
+    pub(crate) fn user_message_bg(&self, terminal_bg: Option<(u8, u8, u8)>) -> Option<Color> {
+        if let Some(color) = self.user_message_bg {
+            return Some(color);
+        }
+        let light = self.light.or_else(|| terminal_bg.map(is_light))?;
+        let bg = terminal_bg.unwrap_or(if light { (255, 255, 255) } else { (0, 0, 0) });
+        let top = if light { (0, 0, 0) } else { (255, 255, 255) };
+        Some(best_color(blend(top, bg, self.user_message_alpha)))
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::builtin(ThemeName::Auto)
+    }
+}
+
+/// Parses an ANSI color name (`red`, `light-blue`, `default`) or `#rrggbb`.
+fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        return Some(best_color((channel(0)?, channel(2)?, channel(4)?)));
+    }
+    let names: BTreeMap<&str, Color> = BTreeMap::from([
+        ("default", Color::Reset),
+        ("black", Color::Black),
+        ("red", Color::Red),
+        ("green", Color::Green),
+        ("yellow", Color::Yellow),
+        ("blue", Color::Blue),
+        ("magenta", Color::Magenta),
+        ("cyan", Color::Cyan),
+        ("gray", Color::Gray),
+        ("dark-gray", Color::DarkGray),
+        ("light-red", Color::LightRed),
+        ("light-green", Color::LightGreen),
+        ("light-yellow", Color::LightYellow),
+        ("light-blue", Color::LightBlue),
+        ("light-magenta", Color::LightMagenta),
+        ("light-cyan", Color::LightCyan),
+        ("white", Color::White),
+    ]);
+    names
+        .get(value.to_ascii_lowercase().replace('_', "-").as_str())
+        .copied()
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Installs the theme built from `[tui.theme]` and returns any problems
+/// found in it. Only the first call has an effect.
+pub(crate) fn init(config: &TuiTheme) -> Vec<String> {
+    let (theme, warnings) = Theme::from_config(config);
+    let _ = THEME.set(theme);
+    warnings
+}
+
+/// The active theme; the `auto` palette until [`init`] runs.
+pub(crate) fn theme() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn overrides_replace_builtin_roles() {
+        let config = TuiTheme {
+            name: ThemeName::HighContrast,
+            colors: BTreeMap::from([
+                ("accent".to_string(), "light-blue".to_string()),
+                ("error".to_string(), "not-a-color".to_string()),
+                ("border".to_string(), "red".to_string()),
+                ("quote".to_string(), "yellow".to_string()),
+            ]),
+        };
+        let (theme, warnings) = Theme::from_config(&config);
+
+        assert_eq!(theme.accent, Color::LightBlue);
+        assert_eq!(theme.error, Color::LightRed);
+        assert_eq!(theme.quote, Color::Yellow);
+        assert_eq!(theme.list_marker, Color::LightBlue);
+        assert_eq!(
+            warnings,
+            vec![
+                "[tui.theme] unknown color role `border`".to_string(),
+                "[tui.theme] error: unknown color `not-a-color`".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn user_message_bg_follows_terminal_unless_forced() {
+        let auto = Theme::default();
+        assert_eq!(auto.user_message_bg(None), None);
+        assert!(auto.user_message_bg(Some((0, 0, 0))).is_some());
+
+        let light = Theme::builtin(ThemeName::Light);
+        assert!(light.user_message_bg(None).is_some());
+    }
+}
@@ -1994,6 +1994,7 @@ impl App {
                                         approval_policy: Some(preset.approval),
                                         sandbox_policy: Some(preset.sandbox.clone()),
                                         model: None,
+                                        model_provider: None,
                                         effort: None,
                                         summary: None,
                                     },
@@ -20,6 +20,7 @@ use codex_core::features::Feature;
 use codex_core::features::Features;
 use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ExecPolicyAmendment;
+use codex_core::protocol::ExecPolicyAmendmentTarget;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
@@ -44,6 +45,7 @@ pub(crate) enum ApprovalRequest {
         command: Vec<String>,
         reason: Option<String>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+        project_execpolicy_available: bool,
     },
     ApplyPatch {
         id: String,
@@ -110,9 +112,14 @@ impl ApprovalOverlay {
         let (options, title) = match &variant {
             ApprovalVariant::Exec {
                 proposed_execpolicy_amendment,
+                project_execpolicy_available,
                 ..
             } => (
-                exec_options(proposed_execpolicy_amendment.clone(), features),
+                exec_options(
+                    proposed_execpolicy_amendment.clone(),
+                    *project_execpolicy_available,
+                    features,
+                ),
                 "Would you like to run the following command?".to_string(),
             ),
             ApprovalVariant::ApplyPatch { .. } => (
@@ -343,6 +350,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                 command,
                 reason,
                 proposed_execpolicy_amendment,
+                project_execpolicy_available,
             } => {
                 let mut header: Vec<Line<'static>> = Vec::new();
                 if let Some(reason) = reason {
@@ -360,6 +368,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                         id,
                         command,
                         proposed_execpolicy_amendment,
+                        project_execpolicy_available,
                     },
                     header: Box::new(Paragraph::new(header).wrap(Wrap { trim: false })),
                 }
@@ -415,6 +424,7 @@ enum ApprovalVariant {
         id: String,
         command: Vec<String>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+        project_execpolicy_available: bool,
     },
     ApplyPatch {
         id: String,
@@ -449,6 +459,7 @@ impl ApprovalOption {
 
 fn exec_options(
     proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+    project_execpolicy_available: bool,
     features: &Features,
 ) -> Vec<ApprovalOption> {
     vec![ApprovalOption {
@@ -463,19 +474,36 @@ fn exec_options(
             .filter(|_| features.enabled(Feature::ExecPolicy))
             .map(|prefix| {
                 let rendered_prefix = strip_bash_lc_and_escape(prefix.command());
-                ApprovalOption {
+                let user = ApprovalOption {
                     label: format!(
                         "Yes, and don't ask again for commands that start with `{rendered_prefix}`"
                     ),
                     decision: ApprovalDecision::Review(
                         ReviewDecision::ApprovedExecpolicyAmendment {
-                            proposed_execpolicy_amendment: prefix,
+                            proposed_execpolicy_amendment: prefix.clone(),
+                            target: ExecPolicyAmendmentTarget::User,
                         },
                     ),
                     display_shortcut: None,
                     additional_shortcuts: vec![key_hint::plain(KeyCode::Char('p'))],
-                }
-            }),
+                };
+                let project = project_execpolicy_available.then(|| ApprovalOption {
+                    label: format!(
+                        "Yes, and don't ask again in this project for commands that start with `{rendered_prefix}`"
+                    ),
+                    decision: ApprovalDecision::Review(
+                        ReviewDecision::ApprovedExecpolicyAmendment {
+                            proposed_execpolicy_amendment: prefix,
+                            target: ExecPolicyAmendmentTarget::Project,
+                        },
+                    ),
+                    display_shortcut: None,
+                    additional_shortcuts: vec![key_hint::plain(KeyCode::Char('o'))],
+                });
+                std::iter::once(user).chain(project)
+            })
+            .into_iter()
+            .flatten(),
     )
     .chain([ApprovalOption {
         label: "No, and tell Codex what to do differently".to_string(),
@@ -539,6 +567,7 @@ mod tests {
             command: vec!["echo".to_string(), "hi".to_string()],
             reason: Some("reason".to_string()),
             proposed_execpolicy_amendment: None,
+            project_execpolicy_available: false,
         }
     }
 
@@ -583,6 +612,7 @@ mod tests {
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
                 ])),
+                project_execpolicy_available: false,
             },
             tx,
             Features::with_defaults(),
@@ -596,7 +626,8 @@ mod tests {
                     ReviewDecision::ApprovedExecpolicyAmendment {
                         proposed_execpolicy_amendment: ExecPolicyAmendment::new(vec![
                             "echo".to_string()
-                        ])
+                        ]),
+                        target: ExecPolicyAmendmentTarget::User,
                     }
                 );
                 saw_op = true;
@@ -609,6 +640,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exec_project_prefix_option_targets_project_rules() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::Exec {
+                id: "test".to_string(),
+                command: vec!["echo".to_string()],
+                reason: None,
+                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
+                    "echo".to_string(),
+                ])),
+                project_execpolicy_available: true,
+            },
+            tx,
+            Features::with_defaults(),
+        );
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE));
+        let mut saw_op = false;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::ExecApproval { decision, .. }) = ev {
+                assert_eq!(
+                    decision,
+                    ReviewDecision::ApprovedExecpolicyAmendment {
+                        proposed_execpolicy_amendment: ExecPolicyAmendment::new(vec![
+                            "echo".to_string()
+                        ]),
+                        target: ExecPolicyAmendmentTarget::Project,
+                    }
+                );
+                saw_op = true;
+                break;
+            }
+        }
+        assert!(
+            saw_op,
+            "expected project approval decision to emit an op with command prefix"
+        );
+    }
+
     #[test]
     fn exec_prefix_option_hidden_when_execpolicy_disabled() {
         let (tx, mut rx) = unbounded_channel::<AppEvent>();
@@ -621,6 +692,7 @@ mod tests {
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
                 ])),
+                project_execpolicy_available: false,
             },
             tx,
             {
@@ -645,6 +717,7 @@ mod tests {
             command,
             reason: None,
             proposed_execpolicy_amendment: None,
+            project_execpolicy_available: false,
         };
 
         let view = ApprovalOverlay::new(exec_request, tx, Features::with_defaults());
@@ -606,6 +606,7 @@ mod tests {
             command: vec!["echo".into(), "ok".into()],
             reason: None,
             proposed_execpolicy_amendment: None,
+            project_execpolicy_available: false,
         }
     }
 
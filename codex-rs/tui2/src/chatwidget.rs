@@ -12,6 +12,7 @@ use codex_app_server_protocol::AuthMode;
 use codex_backend_client::Client as BackendClient;
 use codex_core::config::Config;
 use codex_core::config::types::Notifications;
+use codex_core::features::Feature;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::local_git_branches;
 use codex_core::openai_models::model_family::ModelFamily;
@@ -332,6 +333,10 @@ pub(crate) struct ChatWidget {
     /// observed the corresponding `TaskStarted` event. This prevents the session
     /// status from briefly flipping to "ready" between auto-queued turns.
     queued_turn_pending_start: bool,
+    // Last non-empty user message text (used by `/pin` without arguments).
+    last_user_message: Option<String>,
+    // Messages pinned via `/pin` in this session, oldest first.
+    pinned_messages: Vec<String>,
     // Pending notification to show when unfocused on next Draw
     pending_notification: Option<Notification>,
     // Simple review mode flag; used to adjust layout and banners.
@@ -1289,6 +1294,7 @@ impl ChatWidget {
             command: ev.command,
             reason: ev.reason,
             proposed_execpolicy_amendment: ev.proposed_execpolicy_amendment,
+            project_execpolicy_available: ev.project_execpolicy_available,
         };
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
@@ -1509,6 +1515,8 @@ impl ChatWidget {
             conversation_id: None,
             queued_user_messages: VecDeque::new(),
             queued_turn_pending_start: false,
+            last_user_message: None,
+            pinned_messages: Vec::new(),
             show_welcome_banner: is_first_run,
             suppress_session_configured_redraw: false,
             pending_notification: None,
@@ -1599,6 +1607,8 @@ impl ChatWidget {
             conversation_id: None,
             queued_user_messages: VecDeque::new(),
             queued_turn_pending_start: false,
+            last_user_message: None,
+            pinned_messages: Vec::new(),
             show_welcome_banner: false,
             suppress_session_configured_redraw: true,
             pending_notification: None,
@@ -1765,6 +1775,12 @@ impl ChatWidget {
                 self.clear_token_usage();
                 self.app_event_tx.send(AppEvent::CodexOp(Op::Compact));
             }
+            SlashCommand::Pin => {
+                self.handle_pin_command(args);
+            }
+            SlashCommand::Unpin => {
+                self.handle_unpin_command(args);
+            }
             SlashCommand::Review => {
                 self.open_review_popup();
             }
@@ -1778,10 +1794,7 @@ impl ChatWidget {
                 self.request_exit();
             }
             SlashCommand::Logout => {
-                if let Err(e) = codex_core::auth::logout(
-                    &self.config.codex_home,
-                    self.config.cli_auth_credentials_store_mode,
-                ) {
+                if let Err(e) = self.auth_manager.logout() {
                     tracing::error!("failed to logout: {e}");
                 }
                 self.request_exit();
@@ -1996,6 +2009,9 @@ impl ChatWidget {
                 });
         }
 
+        if !text.trim().is_empty() {
+            self.last_user_message = Some(text.clone());
+        }
         // Only show the text portion in conversation history.
         if !text.is_empty() {
             self.add_to_history(history_cell::new_user_prompt(text));
@@ -2136,15 +2152,19 @@ impl ChatWidget {
             }
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
             EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
+            EventMsg::PinnedMessagesUpdated(ev) => self.pinned_messages = ev.messages,
             EventMsg::RawResponseItem(ev) => self.on_raw_response_item(ev),
             EventMsg::ItemStarted(_)
             | EventMsg::ItemCompleted(_)
+            | EventMsg::ApprovalDecision(_)
             | EventMsg::AgentMessageContentDelta(_)
             | EventMsg::ReasoningContentDelta(_)
             | EventMsg::ReasoningRawContentDelta(_)
             | EventMsg::RalphLoopContinue(_)
             | EventMsg::RalphLoopStatus(_)
-            | EventMsg::RalphLoopComplete(_) => {}
+            | EventMsg::RalphLoopComplete(_)
+            | EventMsg::ListCheckpointsResponse(_)
+            | EventMsg::CheckpointRestored(_) => {}
         }
     }
 
@@ -2464,6 +2484,7 @@ impl ChatWidget {
                 approval_policy: None,
                 sandbox_policy: None,
                 model: Some(switch_model.clone()),
+                model_provider: None,
                 effort: Some(Some(default_effort)),
                 summary: None,
             }));
@@ -2684,6 +2705,7 @@ impl ChatWidget {
                 approval_policy: None,
                 sandbox_policy: None,
                 model: Some(model_for_action.clone()),
+                model_provider: None,
                 effort: Some(effort_for_action),
                 summary: None,
             }));
@@ -2855,6 +2877,7 @@ impl ChatWidget {
                 approval_policy: None,
                 sandbox_policy: None,
                 model: Some(model.clone()),
+                model_provider: None,
                 effort: Some(effort),
                 summary: None,
             }));
@@ -2962,6 +2985,7 @@ impl ChatWidget {
                 approval_policy: Some(approval),
                 sandbox_policy: Some(sandbox_clone.clone()),
                 model: None,
+                model_provider: None,
                 effort: None,
                 summary: None,
             }));
@@ -3412,6 +3436,50 @@ impl ChatWidget {
         }
     }
 
+    /// Handle `/pin [text]`: keep `text`, or the last message sent, verbatim
+    /// across compaction.
+    fn handle_pin_command(&mut self, args: Option<String>) {
+        let message = args
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty())
+            .or_else(|| self.last_user_message.clone());
+        let Some(message) = message else {
+            self.add_info_message(
+                "Usage: /pin <text>, or send a message first to pin it.".to_string(),
+                None,
+            );
+            return;
+        };
+
+        if !self.pinned_messages.contains(&message) {
+            self.pinned_messages.push(message.clone());
+        }
+        let preview = truncate_text(&message, 60);
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::PinMessage { message }));
+        let hint = (!self.config.features.enabled(Feature::StructuredCompaction)).then(|| {
+            "Pins are kept when the structured_compaction feature is enabled.".to_string()
+        });
+        self.add_info_message(format!("Pinned: {preview}"), hint);
+    }
+
+    /// Handle `/unpin [text]`: drop the pin for `text`, or the most recent pin.
+    fn handle_unpin_command(&mut self, args: Option<String>) {
+        let requested = args
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+        let Some(message) = requested.or_else(|| self.pinned_messages.last().cloned()) else {
+            self.add_info_message("No messages are pinned.".to_string(), None);
+            return;
+        };
+
+        self.pinned_messages.retain(|pinned| pinned != &message);
+        let preview = truncate_text(&message, 60);
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::UnpinMessage { message }));
+        self.add_info_message(format!("Unpinned: {preview}"), None);
+    }
+
     fn handle_image_quality_command(&mut self, args: Option<String>) {
         let valid_options = "1K, 2K, 4K";
 
@@ -404,6 +404,8 @@ async fn make_chatwidget_manual(
         show_welcome_banner: true,
         queued_user_messages: VecDeque::new(),
         queued_turn_pending_start: false,
+        last_user_message: None,
+        pinned_messages: Vec::new(),
         suppress_session_configured_redraw: false,
         pending_notification: None,
         is_review_mode: false,
@@ -730,6 +732,7 @@ async fn exec_approval_emits_proposed_command_and_decision_history() {
             "this is a test reason such as one that would be produced by the model".into(),
         ),
         proposed_execpolicy_amendment: None,
+        project_execpolicy_available: false,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -774,6 +777,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
             "this is a test reason such as one that would be produced by the model".into(),
         ),
         proposed_execpolicy_amendment: None,
+        project_execpolicy_available: false,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -824,6 +828,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
         reason: None,
         proposed_execpolicy_amendment: None,
+        project_execpolicy_available: false,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -1427,6 +1432,41 @@ async fn slash_undo_sends_op() {
     }
 }
 
+#[tokio::test]
+async fn slash_pin_and_unpin_send_ops() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.last_user_message = Some("Keep the public API stable.".to_string());
+
+    chat.dispatch_command(SlashCommand::Pin, None);
+
+    match rx.try_recv() {
+        Ok(AppEvent::CodexOp(Op::PinMessage { message })) => {
+            assert_eq!(message, "Keep the public API stable.");
+        }
+        other => panic!("expected AppEvent::CodexOp(Op::PinMessage), got {other:?}"),
+    }
+    let _ = drain_insert_history(&mut rx);
+
+    chat.dispatch_command(SlashCommand::Unpin, None);
+
+    match rx.try_recv() {
+        Ok(AppEvent::CodexOp(Op::UnpinMessage { message })) => {
+            assert_eq!(message, "Keep the public API stable.");
+        }
+        other => panic!("expected AppEvent::CodexOp(Op::UnpinMessage), got {other:?}"),
+    }
+    let _ = drain_insert_history(&mut rx);
+
+    chat.dispatch_command(SlashCommand::Unpin, None);
+
+    let cells = drain_insert_history(&mut rx);
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("No messages are pinned."),
+        "expected empty pin notice: {rendered:?}"
+    );
+}
+
 #[tokio::test]
 async fn slash_rollout_displays_current_path() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -2189,6 +2229,7 @@ async fn approval_modal_exec_snapshot() {
             "hello".into(),
             "world".into(),
         ])),
+        project_execpolicy_available: false,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2240,6 +2281,7 @@ async fn approval_modal_exec_without_reason_snapshot() {
             "hello".into(),
             "world".into(),
         ])),
+        project_execpolicy_available: false,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2457,6 +2499,7 @@ async fn status_widget_and_approval_modal_snapshot() {
             "echo".into(),
             "hello world".into(),
         ])),
+        project_execpolicy_available: false,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -103,6 +103,9 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
             FileChange::Add { content } => (content.lines().count(), 0),
             FileChange::Delete { content } => (0, content.lines().count()),
             FileChange::Update { unified_diff, .. } => calculate_add_remove_from_diff(unified_diff),
+            FileChange::Binary { .. } | FileChange::Symlink { .. } | FileChange::Mode { .. } => {
+                (0, 0)
+            }
         };
         let move_path = match change {
             FileChange::Update {
@@ -153,7 +156,9 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
     let mut header_spans: Vec<RtSpan<'static>> = vec!["• ".dim()];
     if let [row] = &rows[..] {
         let verb = match &row.change {
-            FileChange::Add { .. } => "Added",
+            FileChange::Add { .. }
+            | FileChange::Binary { old_size: None, .. }
+            | FileChange::Symlink { .. } => "Added",
             FileChange::Delete { .. } => "Deleted",
             _ => "Edited",
         };
@@ -296,6 +301,11 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                 }
             }
         }
+        FileChange::Binary { .. } | FileChange::Symlink { .. } | FileChange::Mode { .. } => {
+            if let Some(summary) = change.non_text_summary() {
+                out.push(RtLine::from(summary.dim()));
+            }
+        }
     }
 }
 
@@ -540,6 +550,48 @@ mod tests {
         snapshot_lines("apply_multiple_files_block", lines, 80, 14);
     }
 
+    #[test]
+    fn non_text_changes_render_summaries() {
+        let changes = HashMap::from([
+            (
+                PathBuf::from("logo.png"),
+                FileChange::Binary {
+                    old_size: None,
+                    new_size: 42,
+                },
+            ),
+            (
+                PathBuf::from("run.sh"),
+                FileChange::Mode {
+                    old_mode: Some(0o644),
+                    new_mode: 0o755,
+                },
+            ),
+        ]);
+
+        let text: Vec<String> = diff_summary_for_tests(&changes)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect();
+
+        assert_eq!(
+            text,
+            vec![
+                "• Edited 2 files (+0 -0)",
+                "  └ logo.png (+0 -0)",
+                "    binary file (42 bytes)",
+                "",
+                "  └ run.sh (+0 -0)",
+                "    mode 644 -> 755",
+            ]
+        );
+    }
+
     #[test]
     fn ui_snapshot_apply_add_block() {
         let mut changes: HashMap<PathBuf, FileChange> = HashMap::new();
@@ -22,6 +22,8 @@ pub enum SlashCommand {
     Tumix,
     TumixStop,
     Compact,
+    Pin,
+    Unpin,
     Undo,
     Diff,
     OpenImage,
@@ -50,6 +52,8 @@ impl SlashCommand {
             SlashCommand::Tumix => "run TUMIX multi-agent parallel execution (Round 1)",
             SlashCommand::TumixStop => "stop running TUMIX agents (optionally specify a session)",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
+            SlashCommand::Pin => "pin a message so compaction keeps it verbatim",
+            SlashCommand::Unpin => "stop keeping a pinned message across compaction",
             SlashCommand::Review => "review my current changes and find issues",
             SlashCommand::Resume => "resume a saved chat",
             SlashCommand::Undo => "ask Codex to undo a turn",
@@ -84,6 +88,8 @@ impl SlashCommand {
                 | SlashCommand::TumixStop
                 | SlashCommand::RefImage
                 | SlashCommand::ImageQuality
+                | SlashCommand::Pin
+                | SlashCommand::Unpin
         )
     }
 
@@ -111,6 +117,8 @@ impl SlashCommand {
             | SlashCommand::RefImage
             | SlashCommand::ImageQuality
             | SlashCommand::ClearRef
+            | SlashCommand::Pin
+            | SlashCommand::Unpin
             | SlashCommand::Mention
             | SlashCommand::Agent
             | SlashCommand::Skills
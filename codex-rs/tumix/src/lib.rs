@@ -9,6 +9,8 @@ pub mod meta;
 pub mod worktree;
 
 use anyhow::Result;
+use codex_core::config::Config;
+use codex_core::config::types::AuxiliaryTask;
 use serde::Deserialize;
 use serde::Serialize;
 use std::fmt;
@@ -42,6 +44,29 @@ pub struct Round1Result {
     pub agents: Vec<AgentResult>,
 }
 
+/// Model the meta-agent runs on, from the `meta_agent` entry of
+/// `[models.tasks]`.
+#[derive(Debug, Clone, Default)]
+pub struct MetaAgentModel {
+    pub model: String,
+    pub model_provider: Option<String>,
+    pub reasoning_effort: Option<String>,
+}
+
+impl MetaAgentModel {
+    /// The `meta_agent` route from `[models.tasks]`, if configured.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let route = config.task_models.get(&AuxiliaryTask::MetaAgent)?;
+        Some(Self {
+            model: route.model.clone(),
+            model_provider: Some(route.model_provider_id.clone()),
+            reasoning_effort: route
+                .model_reasoning_effort
+                .map(|effort| effort.to_string()),
+        })
+    }
+}
+
 /// Progress callback for reporting status updates
 pub type ProgressCallback = Box<dyn Fn(String) + Send + Sync>;
 
@@ -228,6 +253,16 @@ pub async fn run_tumix(
     parent_session: String,
     user_prompt: Option<String>,
     progress_cb: Option<ProgressCallback>,
+) -> Result<Round1Result> {
+    run_tumix_with_meta_model(parent_session, user_prompt, progress_cb, None).await
+}
+
+/// Same as [`run_tumix`], with the meta-agent on `meta_model` when set.
+pub async fn run_tumix_with_meta_model(
+    parent_session: String,
+    user_prompt: Option<String>,
+    progress_cb: Option<ProgressCallback>,
+    meta_model: Option<MetaAgentModel>,
 ) -> Result<Round1Result> {
     let progress_arc = progress_cb.map(Arc::new);
 
@@ -249,16 +284,17 @@ pub async fn run_tumix(
     let cancel_token = run_guard.token();
 
     report("🧠 Meta-agent分析任务，设计专家团队...".to_string());
-    let agents = match meta::generate_agents(&parent_session, user_prompt).await {
-        Ok(agents) => {
-            report(format!("✅ Meta-agent成功生成 {} 个专家角色", agents.len()));
-            agents
-        }
-        Err(e) => {
-            report(format!("❌ Meta-agent执行失败: {}", e));
-            return Err(e);
-        }
-    };
+    let agents =
+        match meta::generate_agents(&parent_session, user_prompt, meta_model.as_ref()).await {
+            Ok(agents) => {
+                report(format!("✅ Meta-agent成功生成 {} 个专家角色", agents.len()));
+                agents
+            }
+            Err(e) => {
+                report(format!("❌ Meta-agent执行失败: {}", e));
+                return Err(e);
+            }
+        };
 
     if agents.is_empty() {
         anyhow::bail!("Meta-agent返回了空列表，无法继续执行");
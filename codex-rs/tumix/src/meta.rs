@@ -1,6 +1,7 @@
 //! Meta-agent that generates specialized agent configurations based on task complexity
 
 use crate::AgentConfig;
+use crate::MetaAgentModel;
 use anyhow::Context;
 use anyhow::Result;
 use std::path::PathBuf;
@@ -37,10 +38,25 @@ fn strip_front_matter(template: &str) -> &str {
     text
 }
 
+/// `-c` overrides for the provider and reasoning effort of `meta_model`.
+fn model_override_args(meta_model: &MetaAgentModel) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(provider) = &meta_model.model_provider {
+        args.push("-c".to_string());
+        args.push(format!("model_provider=\"{provider}\""));
+    }
+    if let Some(effort) = &meta_model.reasoning_effort {
+        args.push("-c".to_string());
+        args.push(format!("model_reasoning_effort=\"{effort}\""));
+    }
+    args
+}
+
 /// Generate agent configurations via meta-agent (flexible count based on task)
 pub async fn generate_agents(
     parent_session: &str,
     user_prompt: Option<String>,
+    meta_model: Option<&MetaAgentModel>,
 ) -> Result<Vec<AgentConfig>> {
     let task_desc = if let Some(ref prompt) = user_prompt {
         format!("用户任务：{}\n\n", prompt)
@@ -65,17 +81,19 @@ pub async fn generate_agents(
     }
 
     // Build the command arguments
-    let args = vec![
+    let model_overrides = meta_model.map(model_override_args).unwrap_or_default();
+    let model = meta_model.map_or("gpt-5-codex-high", |meta_model| meta_model.model.as_str());
+    let mut args = vec![
         "exec",
         "--print-rollout-path",
         "--skip-git-repo-check",
         "--sandbox",
         "danger-full-access",
         "--model",
-        "gpt-5-codex-high",
-        "resume-clone",
-        parent_session,
+        model,
     ];
+    args.extend(model_overrides.iter().map(String::as_str));
+    args.extend(["resume-clone", parent_session]);
 
     // Build the full command line for debugging
     let full_command = format!(
@@ -253,6 +271,25 @@ fn extract_json(text: &str) -> Result<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_model_override_args() {
+        let meta_model = MetaAgentModel {
+            model: "gemini-2.5-pro".to_string(),
+            model_provider: Some("gemini".to_string()),
+            reasoning_effort: Some("high".to_string()),
+        };
+        assert_eq!(
+            model_override_args(&meta_model),
+            vec![
+                "-c",
+                "model_provider=\"gemini\"",
+                "-c",
+                "model_reasoning_effort=\"high\"",
+            ]
+        );
+        assert!(model_override_args(&MetaAgentModel::default()).is_empty());
+    }
+
     #[test]
     fn test_extract_json_with_markers() {
         let text = r#"
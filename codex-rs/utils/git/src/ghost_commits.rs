@@ -17,6 +17,7 @@ use crate::operations::normalize_relative_path;
 use crate::operations::repo_subdir;
 use crate::operations::resolve_head;
 use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_bytes;
 use crate::operations::run_git_for_status;
 use crate::operations::run_git_for_stdout;
 use crate::operations::run_git_for_stdout_all;
@@ -32,7 +33,7 @@ const DEFAULT_IGNORE_LARGE_UNTRACKED_FILES: i64 = 10 * 1024 * 1024;
 ///
 /// These are typically large dependency or build trees that are not useful
 /// for undo and can cause snapshots to grow without bound.
-const DEFAULT_IGNORED_DIR_NAMES: &[&str] = &[
+pub const DEFAULT_IGNORED_DIR_NAMES: &[&str] = &[
     "node_modules",
     ".venv",
     "venv",
@@ -453,6 +454,180 @@ pub fn restore_ghost_commit_with_options(
     )
 }
 
+/// Lists paths, relative to `repo_path`, whose contents differ between two
+/// ghost commits. Pass a fresh snapshot as `to` to compare against the current
+/// working tree, untracked files included.
+pub fn changed_paths_between_ghost_commits(
+    repo_path: &Path,
+    from: &GhostCommit,
+    to: &GhostCommit,
+) -> Result<Vec<PathBuf>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+
+    let repo_root = resolve_repository_root(repo_path)?;
+    let repo_prefix = repo_subdir(repo_root.as_path(), repo_path);
+
+    // Example:
+    //   git diff --name-only --no-renames -z <from> <to> -- <prefix>
+    let mut diff_args = vec![
+        OsString::from("diff"),
+        OsString::from("--name-only"),
+        OsString::from("--no-renames"),
+        OsString::from("-z"),
+        OsString::from(from.id()),
+        OsString::from(to.id()),
+        OsString::from("--"),
+    ];
+    if let Some(prefix) = repo_prefix.as_deref() {
+        diff_args.push(prefix.as_os_str().to_os_string());
+    } else {
+        diff_args.push(OsString::from("."));
+    }
+
+    let output = run_git_for_stdout_all(repo_root.as_path(), diff_args, None)?;
+    Ok(output
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(|path| to_session_relative_path(Path::new(path), repo_prefix.as_deref()))
+        .collect())
+}
+
+/// Lists paths, relative to `options.repo_path`, whose working tree contents
+/// differ from `commit`, counting the untracked files a new snapshot would
+/// pick up. Unlike diffing against a fresh snapshot, nothing is written to the
+/// object database.
+pub fn changed_paths_since_ghost_commit(
+    options: &CreateGhostCommitOptions<'_>,
+    commit: &GhostCommit,
+) -> Result<Vec<PathBuf>, GitToolingError> {
+    ensure_git_repository(options.repo_path)?;
+
+    let repo_root = resolve_repository_root(options.repo_path)?;
+    let repo_prefix = repo_subdir(repo_root.as_path(), options.repo_path);
+    let force_include = prepare_force_include(repo_prefix.as_deref(), &options.force_include)?;
+    let untracked = capture_existing_untracked(
+        repo_root.as_path(),
+        repo_prefix.as_deref(),
+        options.ghost_snapshot.ignore_large_untracked_files,
+        options.ghost_snapshot.ignore_large_untracked_dirs,
+        &force_include,
+    )?;
+    let snapshot_untracked: HashSet<PathBuf> =
+        untracked.untracked_files_for_index.into_iter().collect();
+
+    let index_tempdir = Builder::new().prefix("codex-git-index-").tempdir()?;
+    let index_path = index_tempdir.path().join("index");
+    let env = vec![(
+        OsString::from("GIT_INDEX_FILE"),
+        OsString::from(index_path.as_os_str()),
+    )];
+    let pathspec = repo_prefix.as_deref().map_or_else(
+        || OsString::from("."),
+        |prefix| prefix.as_os_str().to_os_string(),
+    );
+
+    // Compare the working tree against a temporary index holding the commit:
+    //   GIT_INDEX_FILE=/tmp/index git read-tree <commit>
+    //   GIT_INDEX_FILE=/tmp/index git update-index -q --refresh
+    //   GIT_INDEX_FILE=/tmp/index git diff-files --name-only -z -- <prefix>
+    //   GIT_INDEX_FILE=/tmp/index git ls-files --others --exclude-standard -z -- <prefix>
+    run_git_for_status(
+        repo_root.as_path(),
+        [OsString::from("read-tree"), OsString::from(commit.id())],
+        Some(env.as_slice()),
+    )?;
+    // `read-tree` leaves no stat data, so every entry would look modified
+    // until the index is refreshed against the working tree.
+    run_git_for_status(
+        repo_root.as_path(),
+        ["update-index", "-q", "--refresh"],
+        Some(env.as_slice()),
+    )?;
+    let modified = run_git_for_stdout_all(
+        repo_root.as_path(),
+        [
+            OsString::from("diff-files"),
+            OsString::from("--name-only"),
+            OsString::from("-z"),
+            OsString::from("--"),
+            pathspec.clone(),
+        ],
+        Some(env.as_slice()),
+    )?;
+    let added = run_git_for_stdout_all(
+        repo_root.as_path(),
+        [
+            OsString::from("ls-files"),
+            OsString::from("--others"),
+            OsString::from("--exclude-standard"),
+            OsString::from("-z"),
+            OsString::from("--"),
+            pathspec,
+        ],
+        Some(env.as_slice()),
+    )?;
+
+    let mut changed: Vec<PathBuf> = modified
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .collect();
+    // New files only count when a snapshot would have captured them.
+    changed.extend(
+        added
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .filter(|path| snapshot_untracked.contains(path)),
+    );
+    Ok(dedupe_paths(changed)
+        .into_iter()
+        .map(|path| to_session_relative_path(&path, repo_prefix.as_deref()))
+        .collect())
+}
+
+/// Reads `path`, relative to `repo_path`, as recorded in the ghost commit.
+/// Returns `None` when the file is not part of the snapshot.
+pub fn read_ghost_commit_file(
+    repo_path: &Path,
+    commit: &GhostCommit,
+    path: &Path,
+) -> Result<Option<Vec<u8>>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+
+    let repo_root = resolve_repository_root(repo_path)?;
+    let repo_prefix = repo_subdir(repo_root.as_path(), repo_path);
+    let normalized = normalize_relative_path(path)?;
+    let repo_relative = match repo_prefix {
+        Some(prefix) => prefix.join(normalized),
+        None => normalized,
+    };
+    let repo_relative = repo_relative.to_string_lossy().replace('\\', "/");
+
+    let listing = run_git_for_stdout(
+        repo_root.as_path(),
+        [
+            "ls-tree",
+            "--name-only",
+            commit.id(),
+            "--",
+            repo_relative.as_str(),
+        ],
+        None,
+    )?;
+    if listing.is_empty() {
+        return Ok(None);
+    }
+
+    let object = format!("{}:{repo_relative}", commit.id());
+    run_git_for_bytes(
+        repo_root.as_path(),
+        ["cat-file", "blob", object.as_str()],
+        None,
+    )
+    .map(Some)
+}
+
 /// Restore the working tree to match the given commit ID.
 pub fn restore_to_commit(repo_path: &Path, commit_id: &str) -> Result<(), GitToolingError> {
     ensure_git_repository(repo_path)?;
@@ -943,6 +1118,96 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    /// Lists files changed between ghost commits and reads their snapshot contents.
+    fn changed_paths_and_file_contents_between_ghost_commits() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join("tracked.txt"), "initial\n")?;
+        std::fs::write(repo.join("unchanged.txt"), "same\n")?;
+        run_git_in(repo, &["add", "."]);
+        run_git_in(
+            repo,
+            &[
+                "-c",
+                "user.name=Tester",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "init",
+            ],
+        );
+
+        let options = CreateGhostCommitOptions::new(repo);
+        let ghost = create_ghost_commit(&options)?;
+
+        std::fs::write(repo.join("tracked.txt"), "edited\n")?;
+        std::fs::write(repo.join("created.txt"), "new\n")?;
+
+        let current = create_ghost_commit(&options)?;
+        let mut changed = changed_paths_between_ghost_commits(repo, &ghost, &current)?;
+        changed.sort();
+        assert_eq!(
+            changed,
+            vec![PathBuf::from("created.txt"), PathBuf::from("tracked.txt")]
+        );
+        assert_eq!(
+            read_ghost_commit_file(repo, &ghost, Path::new("tracked.txt"))?,
+            Some(b"initial\n".to_vec())
+        );
+        assert_eq!(
+            read_ghost_commit_file(repo, &ghost, Path::new("created.txt"))?,
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    /// Lists working tree changes since a ghost commit without a new snapshot.
+    fn changed_paths_since_ghost_commit_lists_edits_and_new_files() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join("tracked.txt"), "initial\n")?;
+        std::fs::write(repo.join("unchanged.txt"), "same\n")?;
+        std::fs::write(repo.join(".gitignore"), "ignored.log\n")?;
+        run_git_in(repo, &["add", "."]);
+        run_git_in(
+            repo,
+            &[
+                "-c",
+                "user.name=Tester",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "init",
+            ],
+        );
+
+        let options = CreateGhostCommitOptions::new(repo);
+        let ghost = create_ghost_commit(&options)?;
+
+        std::fs::write(repo.join("tracked.txt"), "edited\n")?;
+        std::fs::write(repo.join("created.txt"), "new\n")?;
+        std::fs::write(repo.join("ignored.log"), "noise\n")?;
+        std::fs::remove_file(repo.join("unchanged.txt"))?;
+
+        let mut changed = changed_paths_since_ghost_commit(&options, &ghost)?;
+        changed.sort();
+        assert_eq!(
+            changed,
+            vec![
+                PathBuf::from("created.txt"),
+                PathBuf::from("tracked.txt"),
+                PathBuf::from("unchanged.txt"),
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     /// Verifies a ghost commit can be created and restored end to end.
     fn create_and_restore_roundtrip() -> Result<(), GitToolingError> {
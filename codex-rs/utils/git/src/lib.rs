@@ -17,14 +17,18 @@ pub use apply::stage_paths;
 pub use branch::merge_base_with_head;
 pub use errors::GitToolingError;
 pub use ghost_commits::CreateGhostCommitOptions;
+pub use ghost_commits::DEFAULT_IGNORED_DIR_NAMES;
 pub use ghost_commits::GhostSnapshotConfig;
 pub use ghost_commits::GhostSnapshotReport;
 pub use ghost_commits::IgnoredUntrackedFile;
 pub use ghost_commits::LargeUntrackedDir;
 pub use ghost_commits::RestoreGhostCommitOptions;
 pub use ghost_commits::capture_ghost_snapshot_report;
+pub use ghost_commits::changed_paths_between_ghost_commits;
+pub use ghost_commits::changed_paths_since_ghost_commit;
 pub use ghost_commits::create_ghost_commit;
 pub use ghost_commits::create_ghost_commit_with_report;
+pub use ghost_commits::read_ghost_commit_file;
 pub use ghost_commits::restore_ghost_commit;
 pub use ghost_commits::restore_ghost_commit_with_options;
 pub use ghost_commits::restore_to_commit;
@@ -182,6 +182,21 @@ where
     })
 }
 
+/// Executes `git` and returns the raw stdout bytes, for output such as blob
+/// contents that need not be UTF-8.
+pub(crate) fn run_git_for_bytes<I, S>(
+    dir: &Path,
+    args: I,
+    env: Option<&[(OsString, OsString)]>,
+) -> Result<Vec<u8>, GitToolingError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let run = run_git(dir, args, env)?;
+    Ok(run.output.stdout)
+}
+
 fn run_git<I, S>(
     dir: &Path,
     args: I,
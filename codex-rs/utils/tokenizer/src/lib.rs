@@ -0,0 +1,205 @@
+//! Token counting for text sent to models.
+//!
+//! OpenAI models are counted with their BPE encodings. Models without a local
+//! tokenizer, such as Gemini, use an estimate calibrated per character class,
+//! which stays much closer to the real count than a flat bytes-per-token ratio
+//! for CJK text and code.
+
+use std::num::NonZeroUsize;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+
+use codex_utils_cache::sha1_digest;
+use lru::LruCache;
+use serde::Deserialize;
+use serde::Serialize;
+use tiktoken_rs::CoreBPE;
+
+/// Bytes per token assumed by [`Tokenizer::Approximate`].
+pub const APPROX_BYTES_PER_TOKEN: usize = 4;
+
+/// Texts shorter than this are encoded directly; hashing them for the cache
+/// costs about as much as encoding them.
+const MIN_CACHED_TEXT_BYTES: usize = 1024;
+
+/// Texts longer than this are counted from a prefix of this size and scaled,
+/// so a huge tool output cannot stall a turn.
+const MAX_ENCODED_TEXT_BYTES: usize = 1024 * 1024;
+
+const CACHE_CAPACITY: usize = 4096;
+
+type CacheKey = (Tokenizer, [u8; 20]);
+
+static COUNT_CACHE: LazyLock<Mutex<LruCache<CacheKey, usize>>> = LazyLock::new(|| {
+    Mutex::new(LruCache::new(
+        NonZeroUsize::new(CACHE_CAPACITY).unwrap_or(NonZeroUsize::MIN),
+    ))
+});
+
+/// How a model family turns text into tokens.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tokenizer {
+    /// `o200k_base`, used by GPT-4o, the o-series, GPT-5 and gpt-oss models.
+    O200kBase,
+    /// `cl100k_base`, used by GPT-4 and GPT-3.5 models.
+    Cl100kBase,
+    /// Estimate calibrated per character class, for models whose tokenizer
+    /// is not available locally.
+    Calibrated,
+    /// A flat [`APPROX_BYTES_PER_TOKEN`] bytes per token.
+    #[default]
+    Approximate,
+}
+
+impl Tokenizer {
+    /// Number of tokens in `text`.
+    pub fn count(self, text: &str) -> usize {
+        match self.bpe() {
+            Some(bpe) => count_bpe(self, bpe, text),
+            None if self == Tokenizer::Calibrated => calibrated_count(text),
+            None => text.len().div_ceil(APPROX_BYTES_PER_TOKEN),
+        }
+    }
+
+    /// Bytes per token when the tokenizer uses a fixed ratio, in which case
+    /// token and byte budgets convert exactly.
+    pub fn fixed_bytes_per_token(self) -> Option<usize> {
+        match self {
+            Tokenizer::Approximate => Some(APPROX_BYTES_PER_TOKEN),
+            Tokenizer::O200kBase | Tokenizer::Cl100kBase | Tokenizer::Calibrated => None,
+        }
+    }
+
+    fn bpe(self) -> Option<&'static CoreBPE> {
+        match self {
+            Tokenizer::O200kBase => Some(tiktoken_rs::o200k_base_singleton()),
+            Tokenizer::Cl100kBase => Some(tiktoken_rs::cl100k_base_singleton()),
+            Tokenizer::Calibrated | Tokenizer::Approximate => None,
+        }
+    }
+}
+
+fn count_bpe(tokenizer: Tokenizer, bpe: &CoreBPE, text: &str) -> usize {
+    if text.len() < MIN_CACHED_TEXT_BYTES {
+        return bpe.encode_ordinary(text).len();
+    }
+
+    let key = (tokenizer, sha1_digest(text.as_bytes()));
+    if let Some(count) = COUNT_CACHE
+        .lock()
+        .ok()
+        .and_then(|mut cache| cache.get(&key).copied())
+    {
+        return count;
+    }
+
+    let count = if text.len() > MAX_ENCODED_TEXT_BYTES {
+        let sample = prefix_at_char_boundary(text, MAX_ENCODED_TEXT_BYTES);
+        let sample_tokens = bpe.encode_ordinary(sample).len();
+        scale(sample_tokens, sample.len(), text.len())
+    } else {
+        bpe.encode_ordinary(text).len()
+    };
+
+    if let Ok(mut cache) = COUNT_CACHE.lock() {
+        cache.put(key, count);
+    }
+    count
+}
+
+/// Sums per-character weights, in quarter tokens, by character class.
+fn calibrated_count(text: &str) -> usize {
+    if text.is_ascii() && !text.bytes().any(|b| b.is_ascii_punctuation()) {
+        return text.len().div_ceil(APPROX_BYTES_PER_TOKEN);
+    }
+
+    let quarters: usize = text
+        .chars()
+        .map(|ch| match ch {
+            // Words and whitespace: about four characters per token.
+            ch if ch.is_ascii_alphanumeric() || ch.is_ascii_whitespace() => 1,
+            // Operators and brackets in code often stand alone.
+            ch if ch.is_ascii() => 2,
+            // CJK ideographs, kana and hangul: about one token each.
+            ch if is_cjk(ch) => 4,
+            // Emoji and other symbols outside the BMP: several byte tokens.
+            ch if u32::from(ch) >= 0x1_0000 => 6,
+            // Accented Latin, Cyrillic, Greek and similar scripts.
+            _ => 2,
+        })
+        .sum();
+    quarters.div_ceil(4)
+}
+
+fn is_cjk(ch: char) -> bool {
+    matches!(
+        u32::from(ch),
+        0x2E80..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF | 0xFF00..=0xFFEF
+    )
+}
+
+fn prefix_at_char_boundary(text: &str, max_bytes: usize) -> &str {
+    let mut end = max_bytes.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+fn scale(sample_tokens: usize, sample_bytes: usize, total_bytes: usize) -> usize {
+    if sample_bytes == 0 {
+        return 0;
+    }
+    let scaled = (sample_tokens as u128 * total_bytes as u128).div_ceil(sample_bytes as u128);
+    usize::try_from(scaled).unwrap_or(usize::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn approximate_uses_four_bytes_per_token() {
+        assert_eq!(Tokenizer::Approximate.count(""), 0);
+        assert_eq!(Tokenizer::Approximate.count("abcde"), 2);
+        assert_eq!(Tokenizer::Approximate.fixed_bytes_per_token(), Some(4));
+    }
+
+    #[test]
+    fn bpe_counts_match_encodings() {
+        assert_eq!(Tokenizer::O200kBase.count("hello world"), 2);
+        assert_eq!(Tokenizer::Cl100kBase.count("hello world"), 2);
+        assert_eq!(Tokenizer::O200kBase.fixed_bytes_per_token(), None);
+    }
+
+    #[test]
+    fn cached_counts_are_stable() {
+        let text = "fn main() { println!(\"hi\"); }\n".repeat(100);
+        let first = Tokenizer::O200kBase.count(&text);
+        assert_eq!(Tokenizer::O200kBase.count(&text), first);
+        assert_ne!(Tokenizer::Cl100kBase.count(&text), 0);
+    }
+
+    #[test]
+    fn calibrated_counts_cjk_per_character() {
+        assert_eq!(Tokenizer::Calibrated.count("plain words here"), 4);
+        assert_eq!(Tokenizer::Calibrated.count("你好世界"), 4);
+        assert!(
+            Tokenizer::Calibrated.count("a[i] = b(c);")
+                > Tokenizer::Approximate.count("a[i] = b(c);")
+        );
+    }
+
+    #[test]
+    fn long_texts_are_scaled_from_a_prefix() {
+        let text = "word ".repeat(MAX_ENCODED_TEXT_BYTES / 5 * 2);
+        let count = Tokenizer::O200kBase.count(&text);
+        let exact = Tokenizer::O200kBase
+            .bpe()
+            .map(|bpe| bpe.encode_ordinary(&text).len());
+        let exact = exact.unwrap_or_default();
+        assert!(count.abs_diff(exact) <= exact / 100, "{count} vs {exact}");
+    }
+}